@@ -25,7 +25,7 @@ cargo install --path . --root "$installPath"
 # 3. copy resource folder
 read -r -p "Do you need to keep the resource files (please confirm that all resource files are working properly) (y or ..): " keepResFiles
 if [ "$keepResFiles" != "y" ]; then
-  cp -r   ./block_list  ./probe_modules_payload  ./others   "$installPath"
+  cp -r   ./block_list  ./probe_modules_payload  ./others  ./locales   "$installPath"
 fi
 
 # 4. clear
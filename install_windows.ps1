@@ -3,7 +3,7 @@
 $npcap = "npcap-1.81"
 $npcap_sdk = "npcap-sdk-1.15"
 $defaultInstallPath = "${env:USERPROFILE}\smap"
-$resourceFolder = @(".\\block_list",".\\probe_modules_payload",".\\others")
+$resourceFolder = @(".\\block_list",".\\probe_modules_payload",".\\others",".\\locales")
 $libPath = "C:\Users\$env:UserName\.rustup\toolchains\stable-x86_64-pc-windows-gnu\lib\rustlib\x86_64-pc-windows-gnu\lib"
 if (-not (Test-Path $libPath)) {
     Write-Error "Target lib path '$libPath' does not exist"
@@ -0,0 +1,240 @@
+use clap::Parser;
+use crate::api::Scan;
+use crate::core::conf::args::Args;
+use crate::core::conf::set_conf::ScanConf;
+use crate::core::errors::ConfError;
+
+
+/// 扫描任务构造器
+/// 模式参数(目标, 探测模块, 自定义参数等) 以外的设置 直接写入 扫描配置, 各模式据此生成 BaseConf, SenderBaseConf 和 ReceiverBaseConf
+pub struct ScanBuilder {
+    args:Args,
+    conf:ScanConf,
+}
+
+
+impl ScanBuilder {
+
+    /// 以 模式名称(如 c4, c6, p4) 创建构造器, 其余参数均为命令行默认值
+    pub fn new(mode:&str) -> Self {
+        let mut args = Args::parse_from(["smap"]);
+        args.mode = Some(mode.to_string());
+        let conf = ScanConf::from_args(&args);
+        Self { args, conf }
+    }
+
+    /// 以 已有参数 创建构造器
+    pub fn from_args(args:Args) -> Self {
+        let conf = ScanConf::from_args(&args);
+        Self { args, conf }
+    }
+
+    /// 自定义参数, 如: custom_arg("pmap_budget", "10")
+    pub fn custom_arg(mut self, name:&str, val:&str) -> Self {
+        self.args.custom_args.push(format!("{}={}", name, val));
+        self
+    }
+
+    pub fn probe_v4(mut self, name:&str) -> Self {
+        self.args.probe_v4 = Some(name.to_string());
+        self
+    }
+
+    pub fn probe_v6(mut self, name:&str) -> Self {
+        self.args.probe_v6 = Some(name.to_string());
+        self
+    }
+
+    // BaseConf
+
+    /// 网络接口, 可多次调用
+    pub fn interface(mut self, name:&str) -> Self {
+        self.conf.base.interface.push(name.to_string());
+        self
+    }
+
+    /// 系统无法获取网关硬件地址时 使用的替代值
+    pub fn gateway_mac(mut self, interface:&str, mac:&str) -> Self {
+        self.conf.base.set_gateway_mac.push(format!("{}@{}", interface, mac));
+        self
+    }
+
+    pub fn seed(mut self, seed:u64) -> Self {
+        self.conf.base.seed = Some(seed);
+        self
+    }
+
+    pub fn summary_file(mut self, path:&str) -> Self {
+        self.conf.base.summary_file = Some(path.to_string());
+        self
+    }
+
+    /// 覆盖单项系统配置, 如: sys_conf("conf.kp", "0.5")
+    /// 注意: 系统配置在进程中只初始化一次, 同一进程中的后续扫描 必须使用相同的系统配置, 否则 build 报错
+    pub fn sys_conf(mut self, key:&str, val:&str) -> Self {
+        self.args.set.push(format!("{}={}", key, val));
+        self
+    }
+
+    // SenderBaseConf
+
+    /// 目标地址范围, 格式与 -t 相同
+    pub fn targets(mut self, targets:&str) -> Self {
+        self.args.tar_ips = Some(targets.to_string());
+        self
+    }
+
+    pub fn target_file(mut self, path:&str) -> Self {
+        self.args.target_file = Some(path.to_string());
+        self
+    }
+
+    /// 目标端口范围, 格式与 -p 相同
+    pub fn ports(mut self, ports:&str) -> Self {
+        self.args.tar_ports = Some(ports.to_string());
+        self
+    }
+
+    pub fn source_addrs(mut self, addrs:&str) -> Self {
+        self.conf.sender.source_addrs = Some(addrs.to_string());
+        self
+    }
+
+    pub fn source_ports(mut self, ports:&str) -> Self {
+        self.conf.sender.source_ports = Some(ports.to_string());
+        self
+    }
+
+    pub fn ttl(mut self, ttl:u8) -> Self {
+        self.args.ttl = Some(ttl);
+        self
+    }
+
+    /// 发送速率 一秒多少个数据包
+    pub fn send_rate(mut self, rate:u64) -> Self {
+        self.conf.sender.send_rate = Some(rate);
+        self
+    }
+
+    /// 发送带宽(K, M, G)
+    pub fn band_width(mut self, band_width:&str) -> Self {
+        self.conf.sender.band_width = Some(band_width.to_string());
+        self
+    }
+
+    pub fn send_thread_num(mut self, num:usize) -> Self {
+        self.conf.sender.send_thread_num = Some(num);
+        self
+    }
+
+    pub fn send_attempt_num(mut self, num:i32) -> Self {
+        self.conf.sender.send_attempt_num = Some(num);
+        self
+    }
+
+    pub fn batch_size(mut self, size:u64) -> Self {
+        self.conf.sender.batch_size = Some(size);
+        self
+    }
+
+    pub fn cool_seconds(mut self, seconds:i64) -> Self {
+        self.conf.sender.cool_seconds = Some(seconds);
+        self
+    }
+
+    pub fn black_list_v4(mut self, path:&str) -> Self {
+        self.args.black_list_v4 = Some(path.to_string());
+        self
+    }
+
+    pub fn black_list_v6(mut self, path:&str) -> Self {
+        self.args.black_list_v6 = Some(path.to_string());
+        self
+    }
+
+    pub fn white_list_v4(mut self, path:&str) -> Self {
+        self.args.white_list_v4 = Some(path.to_string());
+        self
+    }
+
+    pub fn white_list_v6(mut self, path:&str) -> Self {
+        self.args.white_list_v6 = Some(path.to_string());
+        self
+    }
+
+    /// 发送后端: wire, count, pcapng, virtual
+    pub fn send_backend(mut self, backend:&str, output_file:Option<&str>) -> Self {
+        self.conf.sender.send_backend = Some(backend.to_string());
+        self.conf.sender.send_backend_file = output_file.map(|f| f.to_string());
+        self
+    }
+
+    /// 使用 进程内的虚拟网络 代替网络接口收发数据包
+    pub fn virtual_net(mut self, topology_file:&str) -> Self {
+        self.conf.sender.virtual_net = Some(topology_file.to_string());
+        self
+    }
+
+    // ReceiverBaseConf
+
+    /// 输出模块, 可多次调用; 输出文件为空时 使用模块的默认输出路径
+    pub fn output(mut self, name:&str, file_v4:Option<&str>, file_v6:Option<&str>) -> Self {
+        // 每个输出模块 各占一项, 以免 输出文件 与 输出模块 错位
+        self.conf.receiver.output_mod.push(name.to_string());
+        self.conf.receiver.output_file_v4.push(file_v4.map(|f| f.to_string()));
+        self.conf.receiver.output_file_v6.push(file_v6.map(|f| f.to_string()));
+        self
+    }
+
+    /// 输出字段, 可多次调用
+    pub fn field(mut self, name:&str) -> Self {
+        self.args.fields.push(name.to_string());
+        self
+    }
+
+    pub fn filter(mut self, filter:&str) -> Self {
+        self.conf.receiver.filter = Some(filter.to_string());
+        self
+    }
+
+    /// 接收线程数量, 大于1时 多个捕获线程 通过 PACKET_FANOUT 分担接收
+    pub fn recv_thread_num(mut self, num:usize) -> Self {
+        self.conf.receiver.recv_thread_num = Some(num);
+        self
+    }
+
+    pub fn allow_no_succ(mut self, allow:bool) -> Self {
+        self.conf.receiver.allow_no_succ = allow;
+        self
+    }
+
+    /// 将原始数据包保存到 pcapng文件, all 为 true 时保存全部捕获的数据包
+    pub fn save_packets(mut self, path:&str, all:bool) -> Self {
+        self.conf.receiver.save_packets = Some(path.to_string());
+        self.conf.receiver.save_all_packets = all;
+        self
+    }
+
+    pub fn replay_file(mut self, path:&str) -> Self {
+        self.conf.receiver.replay_file = Some(path.to_string());
+        self
+    }
+
+    /// 获取 当前模式参数
+    pub fn args(&self) -> &Args {
+        &self.args
+    }
+
+    /// 获取 当前扫描配置
+    pub fn conf(&self) -> &ScanConf {
+        &self.conf
+    }
+
+    /// 创建扫描任务, 运行时系统配置无效 或 与进程中已生效的系统配置不同时 报错
+    pub fn build(self) -> Result<Scan, ConfError> {
+        let mut conf = self.conf;
+        conf.mode = self.args.mode.clone().unwrap_or_default();
+        conf.args_record = format!("{:?} {:?}", self.args, conf);
+        Scan::with_conf(self.args, conf)
+    }
+}
@@ -0,0 +1,155 @@
+//! 库接口
+//! 通过 ScanBuilder 构造扫描任务, 执行后返回 统计信息, 探测结果可通过 回调函数 或 结果流 获取
+//!
+//! 例:
+//! let stats = ScanBuilder::new("c4").targets("192.168.1.0/24").ports("80").build()?
+//!     .execute_with(|record| println!("{:?}", record.get("saddr")));
+
+mod builder;
+pub mod stats;
+
+use std::sync::{Arc, Mutex};
+use std::sync::mpsc::{channel, Receiver};
+use std::thread;
+use std::thread::JoinHandle;
+use crate::core::conf::args::Args;
+use crate::core::conf::sys_config::SysConf;
+use crate::core::conf::tools::args_parse::others::parse_summary_file;
+use crate::core::sys::logger::set_logger;
+use crate::core::sender::reset_abort;
+use crate::core::monitor;
+use crate::modes::Mode;
+use crate::modules::output_modules::CallbackOutput;
+use crate::tools::file::write_to_file::write_record;
+
+pub use builder::ScanBuilder;
+pub use crate::core::conf::set_conf::ScanConf;
+pub use crate::core::conf::set_conf::base_conf::{BaseConf, BaseConfOpts};
+pub use crate::core::conf::set_conf::sender_conf::{SenderBaseConf, SenderConfOpts};
+pub use crate::core::conf::set_conf::receiver_conf::{ReceiverBaseConf, ReceiverConfOpts};
+pub use stats::{ScanStats, StatsRecord};
+pub use crate::core::errors::{SmapError, ConfError, InterfaceError, CaptureError, ProbeError, TargetError, OutputError, RunError, ErrInfo};
+pub use crate::modules::output_modules::{RecordCallback, ScanRecord};
+
+
+/// 同一进程中的扫描任务 依次执行, 统计信息 和 结果回调函数 均为进程级
+static RUN_LOCK:Mutex<()> = Mutex::new(());
+
+
+/// 扫描任务
+pub struct Scan {
+    // 模式参数: 目标, 探测模块, 自定义参数 等
+    args:Args,
+    conf:ScanConf,
+}
+
+
+impl Scan {
+
+    /// 创建扫描任务, 同时设置 运行时系统配置层, 必须在首次使用 SYS 之前
+    /// 同一进程中的扫描任务 共用系统配置, 配置层与已生效的不同时 报错
+    pub fn new(args:Args) -> Result<Self, ConfError> {
+        let conf = ScanConf::from_args(&args);
+        Self::with_conf(args, conf)
+    }
+
+    /// 以 模式参数 和 扫描配置 创建扫描任务
+    fn with_conf(args:Args, conf:ScanConf) -> Result<Self, ConfError> {
+        SysConf::set_runtime_layers(&args.config, &args.set, &args.lang)?;
+        Ok(Self { args, conf })
+    }
+
+    /// 从 命令行参数 创建扫描任务
+    pub fn from_cli() -> Result<Self, ConfError> {
+        Self::new(Args::get_args()?)
+    }
+
+    /// 按 参数中的日志设置 配置系统日志, 同一进程中只能调用一次
+    pub fn init_logger(&self) {
+        set_logger(&self.args);
+    }
+
+    pub fn args(&self) -> &Args {
+        &self.args
+    }
+
+    /// 是否 设置了有效的模式, 没有时 执行扫描 将返回错误
+    pub fn has_mode(&self) -> bool {
+        Mode::exists(&self.args)
+    }
+
+    /// 打印 帮助信息: 参数中指定的 模块帮助, 或 所有模块名称
+    pub fn print_help(&self) {
+        Mode::helper(&self.args)
+    }
+
+    pub fn conf(&self) -> &ScanConf {
+        &self.conf
+    }
+
+    /// 执行扫描, 返回 统计信息. 结果按参数中的输出模块输出
+    /// 失败时 错误同时写入记录文件
+    pub fn execute(self) -> Result<ScanStats, SmapError> {
+
+        let _guard = RUN_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        stats::clear();
+        reset_abort();
+        monitor::reset();
+
+        let mode_name = self.conf.mode.clone();
+
+        // 选择并创建模式, 模式结束时 释放发送后端等资源
+        let res = Mode::new(&self.args, &self.conf).and_then(|mode| mode.execute());
+
+        if let Err(e) = &res {
+            self.record_failure(&mode_name, e);
+        }
+        res?;
+
+        Ok(ScanStats {
+            mode: mode_name,
+            records: stats::take(),
+        })
+    }
+
+    /// 将 失败信息 写入记录文件, 写入失败时 只保留原始错误
+    fn record_failure(&self, mode_name:&str, e:&SmapError) {
+
+        if self.conf.base.no_allow_summary { return }
+        if let Some(summary_path) = parse_summary_file(&self.conf.base.summary_file) {
+            let header = vec!["time", "subsystem", "key", "error"];
+            let val = vec![chrono::Local::now().to_string(), e.subsystem().to_string(),
+                           e.info().key.to_string(), e.to_string().replace(",", " ")];
+            let _ = write_record(mode_name, "error", &summary_path, header, val);
+        }
+    }
+
+    /// 执行扫描, 每一行结果 都交给回调函数. 未设置其他输出模块时 只输出到回调函数
+    pub fn execute_with<F>(mut self, callback:F) -> Result<ScanStats, SmapError>
+        where F: Fn(ScanRecord) + Send + Sync + 'static {
+
+        if !self.conf.receiver.output_mod.iter().any(|o| o == "callback") {
+            self.conf.receiver.output_mod.push("callback".to_string());
+        }
+
+        CallbackOutput::set_callback(Some(Arc::new(callback)));
+        let stats = self.execute();
+        CallbackOutput::set_callback(None);
+        stats
+    }
+
+    /// 在独立线程中执行扫描, 返回 结果流 和 扫描线程
+    /// 扫描线程结束后 结果流关闭, 统计信息 由扫描线程返回
+    pub fn stream(self) -> (Receiver<ScanRecord>, JoinHandle<Result<ScanStats, SmapError>>) {
+
+        let (record_sender, record_receiver) = channel();
+        let handle = thread::spawn(move || {
+            self.execute_with(move |record| {
+                // 调用方提前丢弃结果流时, 剩余结果被忽略
+                let _ = record_sender.send(record);
+            })
+        });
+
+        (record_receiver, handle)
+    }
+}
@@ -0,0 +1,69 @@
+use std::sync::Mutex;
+
+
+/// 本次扫描中 各模式写出的统计信息, 与写入记录文件的内容一致
+static STATS:Mutex<Vec<StatsRecord>> = Mutex::new(Vec::new());
+
+
+/// 一条统计信息, 如 发送总数, 接收总数, 运行时间 等
+#[derive(Clone, Debug)]
+pub struct StatsRecord {
+
+    // 写出统计信息的模式, 如 CycleV4
+    pub mode:String,
+
+    // 统计信息类别, 如 result
+    pub target:String,
+
+    // (字段名, 字段值)
+    pub fields:Vec<(String, String)>,
+}
+
+
+/// 一次扫描的全部统计信息
+#[derive(Clone, Debug, Default)]
+pub struct ScanStats {
+
+    // 模式名称, 如 c4
+    pub mode:String,
+
+    pub records:Vec<StatsRecord>,
+}
+
+impl ScanStats {
+
+    /// 按 字段名称 获取统计值, 多条统计信息包含同一字段时 返回最后写出的值
+    pub fn get(&self, field:&str) -> Option<&str> {
+        self.records.iter().rev()
+            .find_map(|r| r.fields.iter().find(|(k, _)| k == field))
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// 按 字段名称 获取统计值 并转换为目标类型
+    pub fn get_as<T:std::str::FromStr>(&self, field:&str) -> Option<T> {
+        self.get(field).and_then(|v| v.parse::<T>().ok())
+    }
+}
+
+
+/// 记录一条统计信息, 由 write_to_summary! 调用, 不依赖是否设置了记录文件
+pub fn record(mode:&str, target:&str, header:&[&str], val:&[String]) {
+    let fields = header.iter().zip(val.iter())
+        .map(|(k, v)| (k.to_string(), v.clone())).collect();
+
+    STATS.lock().unwrap().push(StatsRecord {
+        mode: mode.to_string(),
+        target: target.to_string(),
+        fields,
+    });
+}
+
+/// 清空 已记录的统计信息
+pub(crate) fn clear() {
+    STATS.lock().unwrap().clear();
+}
+
+/// 取出 已记录的统计信息
+pub(crate) fn take() -> Vec<StatsRecord> {
+    std::mem::take(&mut *STATS.lock().unwrap())
+}
@@ -0,0 +1,182 @@
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, Error, ErrorKind, Read, Write};
+use toml_edit::{ArrayOfTables, Document, Item, Table, value};
+
+
+/// 断点文件 魔数 和 格式版本
+const MAGIC:&[u8; 8] = b"SMAPCKPT";
+const VERSION:u32 = 1;
+
+
+/// 单个发送线程的断点
+#[derive(Clone)]
+pub struct SenderState {
+
+    // 分配给该线程的 指数范围
+    pub start:u128,
+    pub end:u128,
+
+    // 已完成的轮次数量(如 端口扫描中 已完成的端口数量), 以及 当前轮次中 下一个待处理目标 对应的乘法群输出值
+    pub round:u64,
+    pub current:u128,
+    // 是否已处理完 全部目标
+    pub finished:bool,
+
+    // 累计的 发送成功, 发送失败, 被拦截 数量
+    pub sent:u64,
+    pub send_failed:u64,
+    pub blocked:u64,
+}
+
+
+/// 断点文件
+/// 格式: 魔数(8字节) | 版本(4字节) | 描述信息长度(8字节) | 描述信息(toml) | 查重器状态长度(8字节) | 查重器状态(zstd 压缩)
+/// 其中 整数均为小端, 描述信息 包括 模式, 随机数种子, 乘法群, 任务配置 和 各发送线程的断点
+pub struct CheckpointFile {
+
+    pub mode:String,
+    pub seed:u64,
+
+    // 乘法群的 模数 和 原根
+    pub group:(u128, u128),
+
+    // 解析后的 任务配置(toml), 续扫时 作为命令行参数的默认值
+    pub profile:Option<String>,
+
+    pub senders:Vec<SenderState>,
+}
+
+impl CheckpointFile {
+
+    /// 写入断点文件 和 接收线程查重器的状态
+    /// 先写入临时文件 再重命名, 写入中途终止时 不会损坏已有的断点文件
+    pub fn write(&self, path:&str, recorder:&[u8]) -> io::Result<()> {
+
+        let tmp_path = format!("{}.tmp", path);
+        let meta = self.meta().to_string();
+        let recorder = zstd::encode_all(recorder, 0)?;
+
+        let mut writer = BufWriter::new(File::create(&tmp_path)?);
+        writer.write_all(MAGIC)?;
+        writer.write_all(&VERSION.to_le_bytes())?;
+        writer.write_all(&(meta.len() as u64).to_le_bytes())?;
+        writer.write_all(meta.as_bytes())?;
+        writer.write_all(&(recorder.len() as u64).to_le_bytes())?;
+        writer.write_all(&recorder)?;
+        writer.into_inner().map_err(|e| e.into_error())?.sync_all()?;
+
+        fs::rename(&tmp_path, path)
+    }
+
+    /// 读取断点文件, 同时返回 查重器的状态
+    pub fn read(path:&str) -> io::Result<(Self, Vec<u8>)> {
+
+        let mut reader = BufReader::new(File::open(path)?);
+        let meta = Self::read_meta(&mut reader)?;
+
+        let recorder_len = read_u64(&mut reader)?;
+        let mut recorder = vec![];
+        reader.take(recorder_len).read_to_end(&mut recorder)?;
+        if recorder.len() as u64 != recorder_len { return Err(invalid("truncated recorder state")) }
+
+        Ok((Self::parse_meta(&meta)?, zstd::decode_all(&recorder[..])?))
+    }
+
+    /// 只读取 断点文件中的任务配置, 用于 解析命令行参数
+    pub fn read_profile(path:&str) -> io::Result<Option<String>> {
+        let mut reader = BufReader::new(File::open(path)?);
+        let meta = Self::read_meta(&mut reader)?;
+        Ok(Self::parse_meta(&meta)?.profile)
+    }
+
+    fn read_meta(reader:&mut impl Read) -> io::Result<Document> {
+
+        let mut magic = [0u8; 8];
+        reader.read_exact(&mut magic)?;
+        if &magic != MAGIC { return Err(invalid("not a checkpoint file")) }
+
+        let mut version = [0u8; 4];
+        reader.read_exact(&mut version)?;
+        if u32::from_le_bytes(version) != VERSION { return Err(invalid("unsupported checkpoint version")) }
+
+        let meta_len = read_u64(reader)?;
+        let mut meta = String::new();
+        reader.take(meta_len).read_to_string(&mut meta)?;
+
+        meta.parse::<Document>().map_err(|e| invalid(&e.to_string()))
+    }
+
+    /// 描述信息, 超过 i64 范围的整数 以字符串保存
+    fn meta(&self) -> Document {
+
+        let mut doc = Document::new();
+        doc["mode"] = value(self.mode.as_str());
+        doc["seed"] = value(self.seed.to_string());
+        doc["p"] = value(self.group.0.to_string());
+        doc["prim_root"] = value(self.group.1.to_string());
+        doc["time"] = value(chrono::Local::now().to_rfc3339());
+        if let Some(profile) = &self.profile {
+            doc["profile"] = value(profile.as_str());
+        }
+
+        let mut senders = ArrayOfTables::new();
+        for s in self.senders.iter() {
+            let mut t = Table::new();
+            t["start"] = value(s.start.to_string());
+            t["end"] = value(s.end.to_string());
+            t["round"] = value(s.round.to_string());
+            t["current"] = value(s.current.to_string());
+            t["finished"] = value(s.finished);
+            t["sent"] = value(s.sent.to_string());
+            t["send_failed"] = value(s.send_failed.to_string());
+            t["blocked"] = value(s.blocked.to_string());
+            senders.push(t);
+        }
+        doc["senders"] = Item::ArrayOfTables(senders);
+
+        doc
+    }
+
+    fn parse_meta(doc:&Document) -> io::Result<Self> {
+
+        let mut senders = vec![];
+        if let Some(tables) = doc.get("senders").and_then(|s| s.as_array_of_tables()) {
+            for t in tables.iter() {
+                senders.push(SenderState {
+                    start: get_num(t, "start")?,
+                    end: get_num(t, "end")?,
+                    round: get_num(t, "round")?,
+                    current: get_num(t, "current")?,
+                    finished: t.get("finished").and_then(|v| v.as_bool()).ok_or_else(|| invalid("finished"))?,
+                    sent: get_num(t, "sent")?,
+                    send_failed: get_num(t, "send_failed")?,
+                    blocked: get_num(t, "blocked")?,
+                });
+            }
+        }
+
+        Ok(Self {
+            mode: doc.get("mode").and_then(|v| v.as_str()).ok_or_else(|| invalid("mode"))?.to_string(),
+            seed: get_num(doc.as_table(), "seed")?,
+            group: (get_num(doc.as_table(), "p")?, get_num(doc.as_table(), "prim_root")?),
+            profile: doc.get("profile").and_then(|v| v.as_str()).map(|p| p.to_string()),
+            senders,
+        })
+    }
+}
+
+
+fn read_u64(reader:&mut impl Read) -> io::Result<u64> {
+    let mut buf = [0u8; 8];
+    reader.read_exact(&mut buf)?;
+    Ok(u64::from_le_bytes(buf))
+}
+
+/// 读取 以字符串保存的整数
+fn get_num<T:std::str::FromStr>(table:&Table, key:&str) -> io::Result<T> {
+    table.get(key).and_then(|v| v.as_str()).and_then(|v| v.parse::<T>().ok()).ok_or_else(|| invalid(key))
+}
+
+fn invalid(info:&str) -> Error {
+    Error::new(ErrorKind::InvalidData, info.to_string())
+}
@@ -0,0 +1,290 @@
+//! 断点续扫
+//! 循环群模式 的探测顺序 完全由 随机数种子, 乘法群 和 各发送线程的指数范围 决定,
+//! 发送线程 在每个发送轮次结束时 记录 下一个待处理目标 在乘法群中的位置 和 累计统计信息,
+//! 断点线程 按固定间隔 请求接收线程导出查重器的状态, 并与 各发送线程的断点 一同写入断点文件.
+//! 续扫(--resume)时 以断点文件中的任务配置 作为命令行参数的默认值, 各发送线程 从断点位置继续, 已完成的发送线程 不再启动.
+//! 发送线程的断点 先于查重器状态读取, 因此 断点之后 发出的探测 一定会在续扫时 重新发送, 可能重复输出的 只有这部分结果
+
+mod file;
+
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{channel, RecvTimeoutError, Sender};
+use std::thread;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+use log::warn;
+use crate::core::conf::profile;
+use crate::core::conf::set_conf::base_conf::BaseConf;
+use crate::core::errors::{ConfError, OutputError, SmapError};
+use crate::modules::target_iterators::CyclePosition;
+use crate::tools::check_duplicates::RecorderState;
+use crate::SYS;
+
+pub use file::{CheckpointFile, SenderState};
+
+
+/// 等待 接收线程 导出查重器状态的 最长时间, 超时时 沿用上次导出的状态
+const RECORDER_WAIT:Duration = Duration::from_secs(5);
+
+
+/// 发送线程 的续扫位置
+#[derive(Clone, Copy)]
+pub enum SenderResume {
+    // 从头开始
+    Start,
+    // 从 指定轮次 的 指定乘法群输出值 开始
+    At { round:u64, position:u128 },
+    // 已处理完 全部目标, 不再启动
+    Finished,
+}
+
+
+/// 发送线程 的断点记录器
+pub struct SenderCheckpoint {
+    slot:Option<Arc<Mutex<SenderState>>>,
+    resume:SenderResume,
+
+    // 续扫前的 累计 发送成功, 发送失败, 被拦截 数量
+    base:(u64, u64, u64),
+    round:u64,
+    finished:bool,
+}
+
+impl SenderCheckpoint {
+
+    /// 不记录断点
+    pub fn none() -> Self {
+        Self { slot: None, resume: SenderResume::Start, base: (0, 0, 0), round: 0, finished: false }
+    }
+
+    /// 续扫位置
+    pub fn resume_point(&self) -> SenderResume {
+        self.resume
+    }
+
+    /// 续扫时 该发送线程 是否已处理完全部目标
+    pub fn is_finished(&self) -> bool {
+        matches!(self.resume, SenderResume::Finished)
+    }
+
+    /// 续扫时 将 局部目标迭代器 移动到断点位置
+    pub fn seek<T:CyclePosition>(&self, target_iter:&mut T) {
+        if let SenderResume::At { position, .. } = self.resume {
+            target_iter.seek(position);
+        }
+    }
+
+    /// 进入 第 round 轮(从 0 开始), 只在 需要多轮遍历目标范围的发送函数中 调用
+    #[inline]
+    pub fn set_round(&mut self, round:u64) {
+        self.round = round;
+    }
+
+    /// 记录 下一个待处理目标的位置 和 本次运行的统计信息, 在每个发送轮次结束时调用
+    #[inline]
+    pub fn save(&self, position:u128, sent:u64, failed:u64, blocked:u64) {
+        if let Some(slot) = &self.slot {
+            let mut state = slot.lock().unwrap();
+            state.round = self.round;
+            state.current = position;
+            state.finished = self.finished;
+            state.sent = self.base.0 + sent;
+            state.send_failed = self.base.1 + failed;
+            state.blocked = self.base.2 + blocked;
+        }
+    }
+
+    /// 标记 已处理完全部目标, 在下一次记录时生效
+    #[inline]
+    pub fn finish(&mut self) {
+        self.finished = true;
+    }
+}
+
+
+/// 接收线程 与 断点线程 共享的查重器状态
+struct RecorderSlot {
+    // 断点线程 请求导出查重器状态, 导出完成后 由接收线程清除
+    requested:AtomicBool,
+    data:Mutex<Vec<u8>>,
+}
+
+
+/// 接收线程 的断点记录器
+pub struct RecvCheckpoint {
+    slot:Option<Arc<RecorderSlot>>,
+}
+
+impl RecvCheckpoint {
+
+    /// 不记录断点
+    pub fn none() -> Self {
+        Self { slot: None }
+    }
+
+    /// 由断点文件 恢复查重器的状态, 断点文件中 尚无查重器状态时 不做处理
+    pub fn restore<R:RecorderState>(&self, recorder:&mut R) -> Result<(), ConfError> {
+        if let Some(slot) = &self.slot {
+            let data = slot.data.lock().unwrap();
+            if !data.is_empty() && !recorder.restore(&data) {
+                return Err(ConfError::invalid("checkpoint_mismatch"))
+            }
+        }
+        Ok(())
+    }
+
+    /// 断点线程 请求时 导出查重器的状态, 在接收线程的 定期检查处 调用
+    #[inline]
+    pub fn check<R:RecorderState>(&self, recorder:&R) {
+        if let Some(slot) = &self.slot {
+            if slot.requested.load(Ordering::Acquire) {
+                self.save(recorder);
+            }
+        }
+    }
+
+    /// 导出查重器的状态, 接收结束时 调用
+    pub fn save<R:RecorderState>(&self, recorder:&R) {
+        if let Some(slot) = &self.slot {
+            *slot.data.lock().unwrap() = recorder.dump();
+            slot.requested.store(false, Ordering::Release);
+        }
+    }
+}
+
+
+/// 断点线程, 释放时 停止断点线程 并写入最终断点
+pub struct Checkpointer {
+
+    // 续扫时 读取的 各发送线程断点
+    resumed:Vec<SenderState>,
+
+    senders:Vec<Arc<Mutex<SenderState>>>,
+    recorder:Option<Arc<RecorderSlot>>,
+
+    // 释放该管道 即通知断点线程停止
+    stop:Option<Sender<()>>,
+    handle:Option<JoinHandle<()>>,
+}
+
+impl Checkpointer {
+
+    /// 启动断点线程, 未设置断点文件时 不启动
+    /// 续扫时 读取断点文件, 模式, 随机数种子, 乘法群 或 各发送线程的指数范围 与 本次扫描不一致时 返回错误
+    pub fn start(base_conf:&BaseConf, mode:&str, group:(u128, u128), ranges:Vec<(u128, u128)>) -> Result<Self, SmapError> {
+
+        let path = match &base_conf.checkpoint_file {
+            Some(p) => p.clone(),
+            None => return Ok(Self { resumed: vec![], senders: vec![], recorder: None, stop: None, handle: None }),
+        };
+
+        let mut file = CheckpointFile {
+            mode: mode.to_string(),
+            seed: base_conf.aes_rand.seed,
+            group,
+            profile: profile::get_resolved(base_conf.aes_rand.seed),
+            senders: ranges.iter().map(|&(start, end)| SenderState {
+                start, end, round: 0, current: 0, finished: false, sent: 0, send_failed: 0, blocked: 0,
+            }).collect(),
+        };
+
+        let (resumed, recorder) = match &base_conf.resume_file {
+            Some(resume_file) => {
+                let (saved, recorder) = CheckpointFile::read(resume_file).map_err(|e| {
+                    if e.kind() == std::io::ErrorKind::InvalidData {
+                        ConfError::invalid("checkpoint_invalid").with(format!("{} {}", resume_file, e))
+                    } else {
+                        ConfError::io("read_checkpoint_failed").with(resume_file)
+                    }
+                })?;
+
+                let matched = saved.mode == file.mode && saved.seed == file.seed && saved.group == file.group
+                    && saved.senders.len() == file.senders.len()
+                    && saved.senders.iter().zip(file.senders.iter()).all(|(a, b)| a.start == b.start && a.end == b.end);
+                if !matched {
+                    return Err(ConfError::invalid("checkpoint_mismatch").with(resume_file).into())
+                }
+
+                file.senders = saved.senders.clone();
+                (saved.senders, recorder)
+            }
+            None => (vec![], vec![]),
+        };
+
+        // 启动时 写入一次断点文件, 检查 断点文件 是否可写
+        file.write(&path, &recorder).map_err(|_| OutputError::io("write_checkpoint_failed").with(&path))?;
+
+        let senders:Vec<Arc<Mutex<SenderState>>> = file.senders.iter().map(|s| Arc::new(Mutex::new(s.clone()))).collect();
+        let slot = Arc::new(RecorderSlot { requested: AtomicBool::new(false), data: Mutex::new(recorder) });
+
+        let interval = Duration::from_secs(base_conf.checkpoint_interval);
+        let (stop, stop_receiver) = channel::<()>();
+        let thread_senders = senders.clone();
+        let thread_slot = slot.clone();
+        let handle = thread::spawn(move || {
+            loop {
+                let stopped = !matches!(stop_receiver.recv_timeout(interval), Err(RecvTimeoutError::Timeout));
+
+                // 先读取 发送线程的断点, 再请求 查重器的状态
+                file.senders = thread_senders.iter().map(|s| s.lock().unwrap().clone()).collect();
+                if !stopped {
+                    thread_slot.requested.store(true, Ordering::Release);
+                    let wait_start = Instant::now();
+                    while thread_slot.requested.load(Ordering::Acquire) && wait_start.elapsed() < RECORDER_WAIT {
+                        thread::sleep(Duration::from_millis(10));
+                    }
+                    thread_slot.requested.store(false, Ordering::Release);
+                }
+
+                // 断点文件 写入失败时 只发出警告, 不影响扫描
+                let data = thread_slot.data.lock().unwrap();
+                if file.write(&path, &data).is_err() {
+                    warn!("{} {}", SYS.get_info("warn", "write_checkpoint_failed"), path);
+                }
+                drop(data);
+
+                if stopped { break }
+            }
+        });
+
+        Ok(Self { resumed, senders, recorder: Some(slot), stop: Some(stop), handle: Some(handle) })
+    }
+
+    /// 第 index 个发送线程的 断点记录器, 其中包含 续扫位置
+    pub fn sender(&self, index:usize) -> SenderCheckpoint {
+        let slot = match self.senders.get(index) {
+            Some(slot) => slot.clone(),
+            None => return SenderCheckpoint::none(),
+        };
+        match self.resumed.get(index) {
+            None => SenderCheckpoint { slot: Some(slot), resume: SenderResume::Start, base: (0, 0, 0), round: 0, finished: false },
+            Some(s) => SenderCheckpoint {
+                slot: Some(slot),
+                resume: if s.finished { SenderResume::Finished } else { SenderResume::At { round: s.round, position: s.current } },
+                base: (s.sent, s.send_failed, s.blocked),
+                round: s.round,
+                finished: s.finished,
+            },
+        }
+    }
+
+    /// 接收线程的 断点记录器
+    pub fn receiver(&self) -> RecvCheckpoint {
+        RecvCheckpoint { slot: self.recorder.clone() }
+    }
+
+    /// 续扫前 已处理的目标数量, 用于计算 覆盖比例 和 预计剩余时间
+    pub fn resumed_processed(&self) -> u64 {
+        self.resumed.iter().map(|s| s.sent + s.send_failed + s.blocked).sum()
+    }
+}
+
+impl Drop for Checkpointer {
+    fn drop(&mut self) {
+        drop(self.stop.take());
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
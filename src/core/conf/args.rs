@@ -1,6 +1,11 @@
 
 //! 定义命令行参数并解析
-use clap::Parser;
+use std::env;
+use clap::{CommandFactory, FromArgMatches, Parser};
+use crate::core::checkpoint::CheckpointFile;
+use crate::core::conf::profile::{self, Profile};
+use crate::core::conf::sys_config::SysConf;
+use crate::core::errors::ConfError;
 
 
 #[derive(Parser, Debug)]
@@ -19,7 +24,7 @@ pub struct Args{
     #[arg(short = 'i', long = "interface", help = "设置本机的网络接口")]
     pub interface:Vec<String>,
 
-    #[arg(short = 'g', long = "set_gateway_mac", help = "当系统无法获取网关的硬件地址时, 使用用户传入的信息进行替代(系统能够获取时对应信息无效). 格式: -g 接口1名称|网关1硬件地址 -g 接口2名称|网关2硬件地址...")]
+    #[arg(short = 'g', long = "set_gateway_mac", help = "当系统无法获取网关的硬件地址时, 使用用户传入的信息进行替代(系统能够获取时对应信息无效). 格式: -g 接口1名称@网关1硬件地址 -g 接口2名称@网关2硬件地址...")]
     pub set_gateway_mac:Vec<String>,
 
     #[arg(long, help = "随机数种子, 用来 设置加密密钥 和 生成随机数")]
@@ -28,6 +33,18 @@ pub struct Args{
     #[arg(long = "summary_file", help = "记录文件路径. 保存一次扫描的配置信息, 扫描结果等")]
     pub summary_file:Option<String>,
 
+    #[arg(long = "config", help = "运行时系统配置文件路径(ini格式), 覆盖内置的系统配置. 未指定时读取环境变量 SMAP_CONFIG")]
+    pub config:Option<String>,
+
+    #[arg(long = "set", help = "覆盖单项系统配置, 可多次使用, 如: --set conf.kp=0.5")]
+    pub set:Vec<String>,
+
+    #[arg(long = "lang", help = "提示信息和帮助文本的语言, 如: en, zh. 未指定时读取环境变量 LANG")]
+    pub lang:Option<String>,
+
+    #[arg(long = "profile", help = "扫描任务配置文件路径(toml 或 yaml 格式), 键为命令行参数名称, 自定义参数写在 custom_args 表中. 命令行中指定的参数覆盖配置文件")]
+    pub profile:Option<String>,
+
     #[arg(long = "probe_v4", help = "设置ipv4探测模块")]
     pub probe_v4:Option<String>,
 
@@ -102,20 +119,53 @@ pub struct Args{
     #[arg(long = "cool_seconds", help = "所有发送线程结束后到接收线程结束前的冷却时间")]
     pub cool_seconds:Option<i64>,
 
+    #[arg(long = "send_backend", help = "发送后端: wire(网络接口), count(只计数, 不发送), pcapng(写入文件, 不发送), virtual(虚拟网络), xdp(网络接口的AF_XDP套接字, 仅linux). 默认为wire, 设置 --virtual_net 时默认为virtual, 设置 --replay_file 时默认为count")]
+    pub send_backend:Option<String>,
+
+    #[arg(long = "send_backend_file", help = "pcapng发送后端的输出文件路径")]
+    pub send_backend_file:Option<String>,
+
 
 
     // receiver
-    #[arg(short = 'o', long = "output", help = "设置输出模块")]
-    pub output_mod:Option<String>,
+    #[arg(short = 'o', long = "output", help = "设置输出模块, 可多次使用以同时输出到多个模块, 如: -o csv -o jsonl")]
+    pub output_mod:Vec<String>,
 
     #[arg(long = "allow_no_succ", default_value_t = false, help = "允许探测失败但验证成功的输出, 如ICMP包裹原始数据包, RST标志数据包等")]
     pub allow_no_succ:bool,
 
-    #[arg(long = "output_file_v4", help = "设置ipv4探测模块输出文件路径")]
-    pub output_file_v4:Option<String>,
+    #[arg(long = "output_file_v4", help = "设置ipv4探测模块输出文件路径, 使用多个输出模块时按顺序与输出模块一一对应")]
+    pub output_file_v4:Vec<String>,
+
+    #[arg(long = "output_file_v6", help = "设置ipv6探测模块输出文件路径, 使用多个输出模块时按顺序与输出模块一一对应")]
+    pub output_file_v6:Vec<String>,
+
+    #[arg(long = "output_compress", help = "输出文件压缩方式: gzip 或 zstd, 默认不压缩")]
+    pub output_compress:Option<String>,
+
+    #[arg(long = "output_rotate_size", help = "按大小轮转输出文件, 每个分段的最大字节数(可使用 K, M, G 后缀), 启用轮转时将同时生成分段清单")]
+    pub output_rotate_size:Option<String>,
+
+    #[arg(long = "output_rotate_seconds", help = "按时间轮转输出文件, 每个分段的最长持续秒数, 启用轮转时将同时生成分段清单")]
+    pub output_rotate_seconds:Option<u64>,
+
+    #[arg(long = "output_overwrite", help = "允许 sqlite 输出模块 覆盖已有的数据库, 默认在数据库已存在时报错")]
+    pub output_overwrite:bool,
 
-    #[arg(long = "output_file_v6", help = "设置ipv6探测模块输出文件路径")]
-    pub output_file_v6:Option<String>,
+    #[arg(long = "save_packets", help = "与正常输出并行, 将验证通过的原始数据包保存到指定的pcapng文件")]
+    pub save_packets:Option<String>,
+
+    #[arg(long = "save_all_packets", default_value_t = false, help = "保存捕获到的全部数据包(包括未通过验证的数据包), 需与 --save_packets 同时使用")]
+    pub save_all_packets:bool,
+
+    #[arg(long = "replay_file", help = "从pcap/pcapng文件回放数据包, 代替从网络接口捕获. 需使用 --seed 指定与原始扫描相同的随机数种子")]
+    pub replay_file:Option<String>,
+
+    #[arg(long = "virtual_net", help = "使用进程内的虚拟网络代替网络接口收发数据包, 参数为声明式拓扑文件路径, 用于无网络环境下的端到端测试")]
+    pub virtual_net:Option<String>,
+
+    #[arg(long = "recv_thread_num", help = "接收线程数量, 大于1时 通过 PACKET_FANOUT 将数据包按流哈希 分配给多个捕获线程(仅linux网络接口捕获, 及 c4 c6 模式的ip探测), 默认为1")]
+    pub recv_thread_num:Option<usize>,
 
     #[arg(long, help = "接收线程的数据包过滤方法")]
     pub filter:Option<String>,
@@ -152,10 +202,72 @@ pub struct Args{
     #[arg(long = "no_allow_summary", default_value_t = false, help = "是否允许记录探测摘要")]
     pub no_allow_summary:bool,
 
+    // status
+    #[arg(long = "status_interval", help = "每隔多少秒输出一次扫描进度(发送数量, 发送速率, 响应数量, 命中率, 重复率, 丢包数量, 覆盖比例, 预计剩余时间), 不设置时不输出")]
+    pub status_interval:Option<u64>,
+
+    #[arg(long = "status_file", help = "将扫描进度以 json lines 格式写入指定的状态文件, 未设置 --status_interval 时使用默认间隔")]
+    pub status_file:Option<String>,
+
+    #[arg(long = "metrics_file", help = "将扫描指标以 prometheus 文本格式写入指定文件(供 node_exporter textfile collector 读取), 未设置 --status_interval 时使用默认间隔")]
+    pub metrics_file:Option<String>,
+
+    // checkpoint
+    #[arg(long = "checkpoint", help = "断点文件路径, 循环群模式(c4, c6, p4)按固定间隔将扫描进度写入该文件, 可用 --resume 从断点继续扫描")]
+    pub checkpoint:Option<String>,
+
+    #[arg(long = "checkpoint_interval", help = "每隔多少秒写入一次断点文件")]
+    pub checkpoint_interval:Option<u64>,
+
+    #[arg(long = "resume", help = "从指定的断点文件继续扫描, 断点文件中保存的任务配置作为默认参数. 未设置 --checkpoint 时 新的断点写入该文件")]
+    pub resume:Option<String>,
+
 }
 
 impl Args {
-    pub fn get_args() -> Args {
-        Args::parse()
+    /// 解析 命令行参数, 叠加 扫描任务配置文件 和 断点文件中保存的任务配置
+    /// 配置文件 或 断点文件 无法读取 或 非法时 返回错误; 命令行参数非法 或 查看帮助时 由 clap 输出提示并退出
+    pub fn get_args() -> Result<Args, ConfError> {
+
+        // 解析参数前 预先读取语言设置, 以本地化帮助文本
+        let raw:Vec<String> = env::args().collect();
+        let lang = raw.iter().enumerate().find_map(|(i, a)| {
+            if a == "--lang" { raw.get(i + 1).cloned() } else { a.strip_prefix("--lang=").map(|l| l.to_string()) }
+        });
+
+        let mut cmd = Args::command();
+        if let Some(catalog) = SysConf::load_catalog(&lang) {
+            if let Some(about) = catalog.get("args", "about") {
+                cmd = cmd.long_about(about);
+            }
+            let ids:Vec<String> = cmd.get_arguments().map(|a| a.get_id().to_string()).collect();
+            for id in ids {
+                if let Some(help) = catalog.get("args", &id) {
+                    cmd = cmd.mut_arg(id, |a| a.help(help));
+                }
+            }
+        }
+
+        let mut argv = raw.clone();
+        let mut matches = cmd.clone().get_matches_from(&argv);
+
+        // 叠加 扫描任务配置文件, 命令行中指定的参数优先
+        if let Some(path) = matches.get_one::<String>("profile") {
+            argv = Profile::load(path)?.merge_argv(&cmd, &matches, &argv)?;
+            matches = cmd.clone().get_matches_from(&argv);
+        }
+
+        // 续扫时 叠加 断点文件中保存的任务配置, 命令行 和 任务配置文件 中指定的参数优先
+        if let Some(path) = matches.get_one::<String>("resume") {
+            let saved = CheckpointFile::read_profile(path)
+                .map_err(|e| ConfError::io("read_checkpoint_failed").with(format!("{}: {}", path, e)))?;
+            if let Some(text) = saved {
+                argv = Profile::parse(path, &text, false)?.merge_argv(&cmd, &matches, &argv)?;
+                matches = cmd.clone().get_matches_from(&argv);
+            }
+        }
+        profile::set_resolved(&cmd, &matches);
+
+        Ok(Args::from_arg_matches(&matches).unwrap_or_else(|e| e.exit()))
     }
 }
\ No newline at end of file
@@ -1,5 +1,6 @@
 pub mod args;
 pub mod set_conf;
 pub mod modules_config;
+pub mod profile;
 pub mod sys_config;
 pub mod tools;
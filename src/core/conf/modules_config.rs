@@ -1,15 +1,16 @@
 //! 各个模块的通用配置器
 
-use std::process::exit;
 use std::str::FromStr;
 use ahash::AHashMap;
-use log::error;
+use crate::core::errors::ConfError;
 use crate::SYS;
 
 #[derive(Clone)]
 pub struct ModuleConf{
 
     conf:AHashMap<String, String>,
+    // 用户输入的 自定义参数 名称, 不包括 模式 附加的参数
+    custom:Vec<String>,
 }
 
 
@@ -21,6 +22,7 @@ impl ModuleConf {
 
         ModuleConf {
             conf: AHashMap::with_capacity(parameter_num),
+            custom: vec![],
         }
 
     }
@@ -32,92 +34,94 @@ impl ModuleConf {
     }
 
     /// 解析自定义参数, 注意: 如果附加参数中包含和传入参数中相同的键, 其值将被替换为附加参数中对应的值
-    pub fn new_from_vec_args(args:&Vec<String>, additional_parameters:Vec<String>) -> Self {
+    /// 参数值中可以包含 '=', 只按第一个 '=' 进行分割
+    pub fn new_from_vec_args(args:&Vec<String>, additional_parameters:Vec<String>) -> Result<Self, ConfError> {
 
         let mut conf:AHashMap<String, String> = AHashMap::with_capacity(args.len());
+        let mut custom = Vec::with_capacity(args.len());
 
         for arg in args {
+            let (para, val) = Self::split_arg(arg)?;
+            custom.push(para.to_string());
+            conf.insert(para.to_string(), val.to_string());
+        }
 
-            let para_val:Vec<&str> = arg.split("=").collect();
+        for arg in additional_parameters.iter() {
+            let (para, val) = Self::split_arg(arg)?;
+            conf.insert(para.to_string(), val.to_string());
+        }
 
-            if para_val.len() != 2 {
-                // 如果 参数 不合法, 即不符合  para=val
-                error!("{}", SYS.get_info("err", "mod_arg_invalid"));
-                exit(1)
-            }
+        Ok(Self { conf, custom })
+    }
 
-            conf.insert(para_val[0].to_string(), para_val[1].to_string());
+    /// 将 para=val 分割为 参数名称 和 参数值
+    fn split_arg(arg:&str) -> Result<(&str, &str), ConfError> {
+        match arg.split_once('=') {
+            Some((para, val)) if !para.trim().is_empty() => Ok((para.trim(), val)),
+            // 如果 参数 不合法, 即不符合  para=val
+            _ => Err(ConfError::invalid("mod_arg_invalid").with(arg)),
         }
+    }
 
-        for arg in additional_parameters {
-
-            let para_val:Vec<&str> = arg.split("=").collect();
+    /// 按 模式 和 探测模块 声明的参数说明 检查自定义参数
+    /// 未声明的参数, 类型错误 或 超出取值范围 的参数 均视为错误
+    /// 返回: 所有已声明参数的 (名称, 生效值), 未设置且没有默认值的参数 生效值为空
+    pub fn check(&self, specs:&[&[ArgSpec]]) -> Result<Vec<(String, String)>, ConfError> {
 
-            if para_val.len() != 2 {
-                // 如果 参数 不合法, 即不符合  para=val
-                error!("{}", SYS.get_info("err", "mod_arg_invalid"));
-                exit(1)
+        for para in self.custom.iter() {
+            match specs.iter().flat_map(|s| s.iter()).find(|s| s.name == para) {
+                Some(spec) => spec.check(&self.conf[para])?,
+                None => return Err(ConfError::invalid("mod_arg_unknown").with(para)),
             }
-
-            conf.insert(para_val[0].to_string(), para_val[1].to_string());
         }
 
-        Self { conf }
+        let mut resolved:Vec<(String, String)> = Vec::new();
+        for spec in specs.iter().flat_map(|s| s.iter()) {
+            // 模式 和 探测模块 可能声明同名参数, 只记录一次
+            if resolved.iter().any(|(name, _)| name == spec.name) { continue }
+
+            let val = match self.conf.get(spec.name) {
+                Some(v) => v.clone(),
+                None => match spec.default_val() {
+                    Some(v) => v,
+                    None if spec.is_required() => return Err(ConfError::missing("mod_arg_required").with(spec.name)),
+                    None => String::new(),
+                }
+            };
+            resolved.push((spec.name.to_string(), val));
+        }
+        Ok(resolved)
     }
 
 
     /// 解析给定类型的参数
     /// # examples
-    /// let c:u32 = conf.get_conf(&String::from("para"));
+    /// let c:u32 = conf.get_conf(&String::from("para"))?;
     #[allow(dead_code)]
-    pub fn get_conf<T : FromStr>(&self, parameter:&String) -> T {
+    pub fn get_conf<T : FromStr>(&self, parameter:&String) -> Result<T, ConfError> {
 
         let res_str = match self.conf.get(parameter) {
-            None => {
-                error!("{} {}", SYS.get_info("err","get_parameter_failed"), parameter);
-                exit(1)
-            }
+            None => return Err(ConfError::missing("get_parameter_failed").with(parameter)),
             Some(v) => v.to_string()
         };
 
-        let res = res_str.parse::<T>();
-
-        match res {
-            Ok(r) => r,
-            Err(_) => {
-                error!("{} {}", SYS.get_info("err","convert_parameter_failed"), parameter);
-                exit(1)
-            }
-        }
-
+        res_str.parse::<T>().map_err(|_| ConfError::invalid("convert_parameter_failed").with(parameter))
     }
 
     /// 先尝试从 自定义参数 中查找对应配置, 如果失败会 尝试从 系统配置 查找
-    pub fn get_conf_or_from_sys<T : FromStr>(&self, parameter:&String) -> T {
+    pub fn get_conf_or_from_sys<T : FromStr>(&self, parameter:&String) -> Result<T, ConfError> {
 
         let res_str = match self.conf.get(parameter) {
             None => {
                 match SYS.get_info_without_panic("conf", parameter) {
-                    None => {
-                        error!("{} {}", SYS.get_info("err","get_parameter_failed"), parameter);
-                        exit(1)
-                    }
+                    None => return Err(ConfError::missing("get_parameter_failed").with(parameter)),
                     Some(v) => v
                 }
             }
             Some(v) => v.to_string()
         };
 
-        let res = res_str.parse::<T>();
-
-        match res {
-            Ok(r) => r,
-            Err(_) => {
-                error!("{} {}", SYS.get_info("err","convert_parameter_failed"), parameter);
-                exit(1)
-            }
-        }
-
+        res_str.parse::<T>().map_err(|_| ConfError::invalid("convert_parameter_failed").with(parameter))
     }
 
 
@@ -134,3 +138,144 @@ impl ModuleConf {
 
 
 }
+
+
+
+/// 自定义参数 的类型
+#[derive(Clone, Copy)]
+pub enum ArgType {
+    Bool,
+    Int,
+    Float,
+    Str,
+    /// 只能取 给定值 之一
+    Choice(&'static [&'static str]),
+}
+
+/// 自定义参数 的默认值
+#[derive(Clone, Copy)]
+pub enum ArgDefault {
+    /// 使用 系统配置(conf) 中的同名配置
+    Sys,
+    Value(&'static str),
+    /// 必须由用户指定
+    Required,
+    /// 不设置时 由模块自行处理
+    Unset,
+}
+
+/// 自定义参数(-a key=value) 说明: 名称, 类型, 取值范围 和 默认值
+/// 参数描述 从 系统配置 的 mod_args 中按名称读取
+#[derive(Clone, Copy)]
+pub struct ArgSpec {
+    pub name:&'static str,
+    pub ty:ArgType,
+    /// 数值参数的取值范围(闭区间), 为空时 不限制
+    pub range:Option<(f64, f64)>,
+    pub default:ArgDefault,
+}
+
+impl ArgSpec {
+
+    const fn of(name:&'static str, ty:ArgType) -> Self {
+        Self { name, ty, range: None, default: ArgDefault::Sys }
+    }
+
+    pub const fn bool(name:&'static str) -> Self { Self::of(name, ArgType::Bool) }
+    pub const fn int(name:&'static str) -> Self { Self::of(name, ArgType::Int) }
+    pub const fn float(name:&'static str) -> Self { Self::of(name, ArgType::Float) }
+    pub const fn str(name:&'static str) -> Self { Self::of(name, ArgType::Str) }
+    pub const fn choice(name:&'static str, values:&'static [&'static str]) -> Self { Self::of(name, ArgType::Choice(values)) }
+
+    pub const fn range(mut self, min:f64, max:f64) -> Self {
+        self.range = Some((min, max));
+        self
+    }
+
+    /// 只限制下界
+    pub const fn min(self, min:f64) -> Self {
+        self.range(min, f64::MAX)
+    }
+
+    pub const fn with_default(mut self, val:&'static str) -> Self {
+        self.default = ArgDefault::Value(val);
+        self
+    }
+
+    pub const fn required(mut self) -> Self {
+        self.default = ArgDefault::Required;
+        self
+    }
+
+    pub const fn unset(mut self) -> Self {
+        self.default = ArgDefault::Unset;
+        self
+    }
+
+    /// 检查 参数值 的类型和取值范围
+    pub fn check(&self, val:&str) -> Result<(), ConfError> {
+
+        let detail = || format!("{}={} ({})", self.name, val, self.type_name());
+
+        let num = match self.ty {
+            ArgType::Bool => {
+                val.parse::<bool>().map_err(|_| ConfError::invalid("mod_arg_type_invalid").with(detail()))?;
+                None
+            }
+            ArgType::Int => Some(val.parse::<i128>().map_err(|_| ConfError::invalid("mod_arg_type_invalid").with(detail()))? as f64),
+            ArgType::Float => Some(val.parse::<f64>().map_err(|_| ConfError::invalid("mod_arg_type_invalid").with(detail()))?),
+            ArgType::Str => None,
+            ArgType::Choice(values) => {
+                if !values.contains(&val) {
+                    return Err(ConfError::invalid("mod_arg_out_of_range").with(format!("{}={} ({})", self.name, val, self.range_text())))
+                }
+                None
+            }
+        };
+
+        if let (Some(n), Some((min, max))) = (num, self.range) {
+            if !(n >= min && n <= max) {
+                return Err(ConfError::invalid("mod_arg_out_of_range").with(format!("{}={} ({})", self.name, val, self.range_text())))
+            }
+        }
+        Ok(())
+    }
+
+    /// 默认值, 默认值来自系统配置时 返回当前生效的系统配置
+    pub fn default_val(&self) -> Option<String> {
+        match self.default {
+            ArgDefault::Sys => SYS.get_info_without_panic("conf", self.name),
+            ArgDefault::Value(v) => Some(v.to_string()),
+            ArgDefault::Required | ArgDefault::Unset => None,
+        }
+    }
+
+    /// 是否必须由用户指定, 默认值来自系统配置 但系统配置中不存在时 也视为必需
+    pub fn is_required(&self) -> bool {
+        match self.default {
+            ArgDefault::Sys => self.default_val().is_none(),
+            ArgDefault::Required => true,
+            _ => false,
+        }
+    }
+
+    pub fn type_name(&self) -> &'static str {
+        match self.ty {
+            ArgType::Bool => "bool",
+            ArgType::Int => "int",
+            ArgType::Float => "float",
+            ArgType::Str | ArgType::Choice(_) => "string",
+        }
+    }
+
+    /// 取值范围 的文字说明, 不限制时 为 -
+    pub fn range_text(&self) -> String {
+        match (self.ty, self.range) {
+            (ArgType::Bool, _) => "true | false".to_string(),
+            (ArgType::Choice(values), _) => values.join(" | "),
+            (_, Some((min, max))) if max == f64::MAX => format!(">= {}", min),
+            (_, Some((min, max))) => format!("[{}, {}]", min, max),
+            (_, None) => "-".to_string(),
+        }
+    }
+}
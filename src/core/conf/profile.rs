@@ -0,0 +1,277 @@
+//! 扫描任务配置文件(--profile): 以 toml 或 yaml 文件描述一次完整的扫描任务。
+//! 键为 命令行参数 的名称(如: mode, interface, tar_ips, probe_v4, output_mod, log_level), 也可以使用 长参数名(如: saddr, output)。
+//! 自定义参数 写在 custom_args 表中, 其中的子表 按 模式 或 探测模块 名称分组, 只在对应的 模式 或 探测模块 被选中时生效。
+//! 命令行中指定的参数 覆盖配置文件中的同名参数, 自定义参数 按参数名称覆盖。
+
+use std::fs;
+use clap::parser::ValueSource;
+use clap::{ArgAction, ArgMatches, Command};
+use once_cell::sync::OnceCell;
+use toml_edit::{Array, Document, Item, Table, Value};
+use crate::core::errors::ConfError;
+use crate::core::registry::find;
+use crate::modes::MODES;
+
+#[cfg(test)]
+mod tests;
+
+/// 解析后的 任务配置(命令行 叠加 配置文件), 以 toml 格式保存, 用于写入记录文件
+static RESOLVED:OnceCell<String> = OnceCell::new();
+
+/// 不能写入配置文件的参数: 配置文件自身, 断点续扫文件 和 帮助信息
+const EXCLUDED_ARGS:[&str; 8] = ["profile", "resume", "help", "version", "mode_help", "probe_v4_help", "probe_v6_help", "output_help"];
+
+/// 自定义参数 在配置文件中的表名
+const CUSTOM_ARGS:&str = "custom_args";
+
+enum ProfileValue {
+    Scalar(String),
+    List(Vec<String>),
+    Table(Vec<(String, ProfileValue)>),
+}
+
+pub struct Profile {
+    path:String,
+    entries:Vec<(String, ProfileValue)>,
+}
+
+impl Profile {
+
+    /// 读取 任务配置文件, 按扩展名区分格式: .yaml 和 .yml 为 yaml, 其他为 toml
+    pub fn load(path:&str) -> Result<Profile, ConfError> {
+
+        let text = fs::read_to_string(path)
+            .map_err(|e| ConfError::io("read_profile_failed").with(format!("{}: {}", path, e)))?;
+
+        Self::parse(path, &text, path.ends_with(".yaml") || path.ends_with(".yml"))
+    }
+
+    /// 解析 任务配置文本, path 只用于错误提示
+    pub fn parse(path:&str, text:&str, yaml:bool) -> Result<Profile, ConfError> {
+
+        let entries = if yaml {
+            Self::parse_yaml(text)
+        } else {
+            Self::parse_toml(text)
+        };
+
+        match entries {
+            Ok(entries) => Ok(Profile { path: path.to_string(), entries }),
+            Err(e) => Err(ConfError::invalid("profile_invalid").with(format!("{}: {}", path, e))),
+        }
+    }
+
+    fn parse_toml(text:&str) -> Result<Vec<(String, ProfileValue)>, String> {
+        let doc = text.parse::<Document>().map_err(|e| e.to_string())?;
+        Self::toml_table(doc.as_table().iter().map(|(k, v)| (k.to_string(), v.clone())).collect())
+    }
+
+    fn toml_table(items:Vec<(String, Item)>) -> Result<Vec<(String, ProfileValue)>, String> {
+        let mut entries = vec![];
+        for (key, item) in items {
+            let val = match item {
+                Item::Value(v) => Self::toml_value(v)?,
+                Item::Table(t) => ProfileValue::Table(Self::toml_table(t.iter().map(|(k, v)| (k.to_string(), v.clone())).collect())?),
+                _ => return Err(format!("unsupported value of {}", key)),
+            };
+            entries.push((key, val));
+        }
+        Ok(entries)
+    }
+
+    fn toml_value(val:Value) -> Result<ProfileValue, String> {
+        Ok(match val {
+            Value::String(s) => ProfileValue::Scalar(s.into_value()),
+            Value::Array(a) => {
+                let mut list = vec![];
+                for v in a.into_iter() {
+                    match Self::toml_value(v)? {
+                        ProfileValue::Scalar(s) => list.push(s),
+                        _ => return Err("nested arrays and tables are not supported in arrays".to_string()),
+                    }
+                }
+                ProfileValue::List(list)
+            }
+            Value::InlineTable(t) => ProfileValue::Table(Self::toml_table(t.iter().map(|(k, v)| (k.to_string(), Item::Value(v.clone()))).collect())?),
+            // 整数, 浮点数, 布尔值 和 日期 按原样转为字符串
+            other => ProfileValue::Scalar(other.decorated("", "").to_string()),
+        })
+    }
+
+    fn parse_yaml(text:&str) -> Result<Vec<(String, ProfileValue)>, String> {
+        let doc:serde_yaml::Value = serde_yaml::from_str(text).map_err(|e| e.to_string())?;
+        match Self::yaml_value(doc)? {
+            ProfileValue::Table(entries) => Ok(entries),
+            // 空文件
+            ProfileValue::Scalar(s) if s.is_empty() => Ok(vec![]),
+            _ => Err("the top level of a profile must be a mapping".to_string()),
+        }
+    }
+
+    fn yaml_value(val:serde_yaml::Value) -> Result<ProfileValue, String> {
+        use serde_yaml::Value as Y;
+        Ok(match val {
+            Y::Null => ProfileValue::Scalar(String::new()),
+            Y::Bool(b) => ProfileValue::Scalar(b.to_string()),
+            Y::Number(n) => ProfileValue::Scalar(n.to_string()),
+            Y::String(s) => ProfileValue::Scalar(s),
+            Y::Sequence(seq) => {
+                let mut list = vec![];
+                for v in seq {
+                    match Self::yaml_value(v)? {
+                        ProfileValue::Scalar(s) => list.push(s),
+                        _ => return Err("nested sequences and mappings are not supported in sequences".to_string()),
+                    }
+                }
+                ProfileValue::List(list)
+            }
+            Y::Mapping(map) => {
+                let mut entries = vec![];
+                for (k, v) in map {
+                    let key = match k {
+                        Y::String(s) => s,
+                        other => return Err(format!("invalid key: {:?}", other)),
+                    };
+                    entries.push((key, Self::yaml_value(v)?));
+                }
+                ProfileValue::Table(entries)
+            }
+            Y::Tagged(t) => Self::yaml_value(t.value)?,
+        })
+    }
+
+    /// 将 配置文件 转为 命令行参数, 并与 实际的命令行参数 合并
+    /// 命令行中已指定的参数 不再从配置文件中读取, 自定义参数 放在命令行自定义参数之前, 以便按名称被覆盖
+    pub fn merge_argv(&self, cmd:&Command, cli:&ArgMatches, raw:&[String]) -> Result<Vec<String>, ConfError> {
+
+        // 最终生效的 模式 和 探测模块 名称, 用于选择 自定义参数 子表
+        let selected = |id:&str| -> Option<String> {
+            match cli.get_one::<String>(id) {
+                Some(v) => Some(v.clone()),
+                None => self.entries.iter().find(|(k, _)| k == id).and_then(|(_, v)| match v {
+                    ProfileValue::Scalar(s) => Some(s.clone()),
+                    _ => None,
+                }),
+            }
+        };
+        let mode = selected("mode");
+        let probes = [selected("probe_v4"), selected("probe_v6")];
+        let is_selected = |name:&str| {
+            mode.as_deref().is_some_and(|m| m == name || find(MODES, m).is_some_and(|r| r.matches(name)))
+                || probes.iter().any(|p| p.as_deref() == Some(name))
+        };
+
+        let mut argv = vec![raw.first().cloned().unwrap_or_else(|| "smap".to_string())];
+
+        for (key, val) in self.entries.iter() {
+
+            let arg = cmd.get_arguments().find(|a| a.get_id() == key.as_str() || a.get_long() == Some(key.as_str()));
+            let arg = match arg {
+                Some(a) if !EXCLUDED_ARGS.contains(&a.get_id().as_str()) => a,
+                _ => return Err(self.fail("profile_unknown_key", key)),
+            };
+            let id = arg.get_id().as_str();
+            let flag = match arg.get_long() {
+                Some(long) => format!("--{}", long),
+                None => format!("-{}", arg.get_short().unwrap_or_default()),
+            };
+
+            if id == CUSTOM_ARGS {
+                for custom in self.custom_args(val, &is_selected)? {
+                    argv.push(flag.clone());
+                    argv.push(custom);
+                }
+                continue
+            }
+
+            // 命令行中已指定的参数 优先
+            if cli.value_source(id) == Some(ValueSource::CommandLine) { continue }
+
+            match (arg.get_action(), val) {
+                (ArgAction::SetTrue, ProfileValue::Scalar(s)) => match s.as_str() {
+                    "true" => argv.push(flag),
+                    "false" => {}
+                    _ => return Err(self.fail("profile_value_invalid", key)),
+                },
+                (ArgAction::Append, ProfileValue::List(list)) => for v in list {
+                    argv.push(format!("{}={}", flag, v));
+                },
+                (_, ProfileValue::Scalar(s)) => argv.push(format!("{}={}", flag, s)),
+                _ => return Err(self.fail("profile_value_invalid", key)),
+            }
+        }
+
+        argv.extend(raw.iter().skip(1).cloned());
+        Ok(argv)
+    }
+
+    /// 展开 自定义参数: 数组形式的 name=value, 或 表形式的 name = value; 子表 只在 对应的模式或探测模块 被选中时展开
+    fn custom_args(&self, val:&ProfileValue, is_selected:&dyn Fn(&str) -> bool) -> Result<Vec<String>, ConfError> {
+        Ok(match val {
+            ProfileValue::Scalar(s) => vec![s.clone()],
+            ProfileValue::List(list) => list.clone(),
+            ProfileValue::Table(entries) => {
+                let mut custom = vec![];
+                for (key, v) in entries {
+                    match v {
+                        ProfileValue::Scalar(s) => custom.push(format!("{}={}", key, s)),
+                        ProfileValue::Table(_) if is_selected(key) => custom.extend(self.custom_args(v, &|_| false)?),
+                        ProfileValue::Table(_) => {}
+                        ProfileValue::List(_) => return Err(self.fail("profile_value_invalid", &format!("{}.{}", CUSTOM_ARGS, key))),
+                    }
+                }
+                custom
+            }
+        })
+    }
+
+    /// 配置文件中的 参数名称 或 取值 非法
+    fn fail(&self, key:&'static str, name:&str) -> ConfError {
+        ConfError::invalid(key).with(format!("{}: {}", self.path, name))
+    }
+}
+
+
+/// 将 最终生效的命令行参数 转为 toml 格式的任务配置, 使用该配置(--profile)可以复现本次扫描
+pub fn set_resolved(cmd:&Command, matches:&ArgMatches) {
+
+    let mut doc = Document::new();
+    let mut custom = Table::new();
+
+    for arg in cmd.get_arguments() {
+        let id = arg.get_id().as_str();
+        if EXCLUDED_ARGS.contains(&id) || matches.value_source(id) != Some(ValueSource::CommandLine) { continue }
+
+        let vals:Vec<String> = match matches.get_raw(id) {
+            Some(raw) => raw.map(|v| v.to_string_lossy().to_string()).collect(),
+            None => continue,
+        };
+
+        if id == CUSTOM_ARGS {
+            for v in vals {
+                let (k, v) = v.split_once('=').unwrap_or((&v, ""));
+                custom[k.trim()] = toml_edit::value(v);
+            }
+        } else if let ArgAction::SetTrue = arg.get_action() {
+            doc[id] = toml_edit::value(true);
+        } else if let ArgAction::Append = arg.get_action() {
+            doc[id] = toml_edit::value(Array::from_iter(vals));
+        } else if let Some(v) = vals.into_iter().next() {
+            doc[id] = toml_edit::value(v);
+        }
+    }
+    if !custom.is_empty() {
+        doc[CUSTOM_ARGS] = Item::Table(custom);
+    }
+
+    let _ = RESOLVED.set(doc.to_string());
+}
+
+/// 解析后的 任务配置, 未指定随机数种子时 补充实际使用的种子, 以便完全复现; 未从命令行创建时 为空
+pub fn get_resolved(seed:u64) -> Option<String> {
+    let mut doc = RESOLVED.get()?.parse::<Document>().ok()?;
+    if !doc.contains_key("seed") {
+        doc["seed"] = toml_edit::value(seed.to_string());
+    }
+    Some(doc.to_string())
+}
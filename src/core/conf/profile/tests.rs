@@ -0,0 +1,126 @@
+use clap::{CommandFactory, FromArgMatches};
+use crate::core::conf::args::Args;
+use super::{Profile, ProfileValue};
+
+
+// 同一任务配置的 toml 和 yaml 两种写法
+const PROFILE_TOML:&str = r#"
+mode = "c4"
+seed = 7
+allow_no_succ = true
+output = ["csv", "jsonl"]
+tar_ips = "10.0.0.0/30"
+
+[custom_args]
+pmap_budget = 10
+
+[custom_args.c4]
+c4_only = "x"
+
+[custom_args.p4]
+p4_only = "y"
+"#;
+
+const PROFILE_YAML:&str = r#"
+mode: c4
+seed: 7
+allow_no_succ: true
+output: [csv, jsonl]
+tar_ips: 10.0.0.0/30
+custom_args:
+  pmap_budget: 10
+  c4:
+    c4_only: x
+  p4:
+    p4_only: y
+"#;
+
+
+/// 将 配置项 展开为 键=值 形式, 便于比较
+fn flatten(prefix:&str, entries:&[(String, ProfileValue)]) -> Vec<String> {
+    let mut res = vec![];
+    for (key, val) in entries {
+        let key = format!("{}{}", prefix, key);
+        match val {
+            ProfileValue::Scalar(s) => res.push(format!("{}={}", key, s)),
+            ProfileValue::List(list) => res.push(format!("{}=[{}]", key, list.join(","))),
+            ProfileValue::Table(t) => res.extend(flatten(&format!("{}.", key), t)),
+        }
+    }
+    res
+}
+
+fn merge(profile:&Profile, raw:&[&str]) -> Args {
+    let cmd = Args::command();
+    let raw:Vec<String> = raw.iter().map(|a| a.to_string()).collect();
+    let matches = cmd.clone().get_matches_from(&raw);
+    let argv = profile.merge_argv(&cmd, &matches, &raw).unwrap();
+    Args::from_arg_matches(&cmd.get_matches_from(&argv)).unwrap()
+}
+
+
+#[test]
+fn toml_values_map_to_strings() {
+    let profile = Profile::parse("p.toml", PROFILE_TOML, false).unwrap();
+
+    assert_eq!(flatten("", &profile.entries), vec![
+        "mode=c4", "seed=7", "allow_no_succ=true", "output=[csv,jsonl]", "tar_ips=10.0.0.0/30",
+        "custom_args.pmap_budget=10", "custom_args.c4.c4_only=x", "custom_args.p4.p4_only=y",
+    ]);
+}
+
+#[test]
+fn yaml_maps_like_toml() {
+    let toml = Profile::parse("p.toml", PROFILE_TOML, false).unwrap();
+    let yaml = Profile::parse("p.yaml", PROFILE_YAML, true).unwrap();
+
+    assert_eq!(flatten("", &yaml.entries), flatten("", &toml.entries));
+    // 空文件 没有配置项
+    assert!(Profile::parse("p.yaml", "", true).unwrap().entries.is_empty());
+}
+
+#[test]
+fn nested_lists_are_invalid() {
+    for (text, yaml) in [("a = [[1]]", false), ("- a", true), ("a: [[1]]", true)] {
+        let e = Profile::parse("p", text, yaml).err().unwrap();
+        assert_eq!(e.info().key, "profile_invalid");
+    }
+}
+
+#[test]
+fn command_line_overrides_profile() {
+    let profile = Profile::parse("p.toml", PROFILE_TOML, false).unwrap();
+    let args = merge(&profile, &["smap", "--seed", "9", "-a", "pmap_budget=20"]);
+
+    assert_eq!(args.mode.as_deref(), Some("c4"));
+    assert_eq!(args.seed, Some(9));
+    assert!(args.allow_no_succ);
+    assert_eq!(args.output_mod, vec!["csv", "jsonl"]);
+    assert_eq!(args.tar_ips.as_deref(), Some("10.0.0.0/30"));
+    // 只展开 被选中模式 的子表, 命令行中的自定义参数 在后, 按名称覆盖
+    assert_eq!(args.custom_args, vec!["pmap_budget=10", "c4_only=x", "pmap_budget=20"]);
+}
+
+#[test]
+fn custom_args_follow_selected_mode() {
+    let profile = Profile::parse("p.toml", PROFILE_TOML, false).unwrap();
+    let args = merge(&profile, &["smap", "-m", "p4"]);
+
+    assert_eq!(args.mode.as_deref(), Some("p4"));
+    assert_eq!(args.custom_args, vec!["pmap_budget=10", "p4_only=y"]);
+}
+
+#[test]
+fn invalid_keys_and_values_are_rejected() {
+    let cmd = Args::command();
+    let raw = vec!["smap".to_string()];
+    let matches = cmd.clone().get_matches_from(&raw);
+
+    for (text, key) in [("no_such_arg = 1", "profile_unknown_key"), ("resume = \"a\"", "profile_unknown_key"),
+                        ("allow_no_succ = \"maybe\"", "profile_value_invalid"),
+                        ("[custom_args]\na = [\"1\"]", "profile_value_invalid")] {
+        let profile = Profile::parse("p.toml", text, false).unwrap();
+        let e = profile.merge_argv(&cmd, &matches, &raw).err().unwrap();
+        assert_eq!(e.info().key, key, "{}", text);
+    }
+}
@@ -1,7 +1,8 @@
 use crate::core::conf::args::Args;
-use crate::core::conf::tools::args_parse::others::{parse_summary_file};
+use crate::core::conf::tools::args_parse::others::{parse_checkpoint_interval, parse_status_interval, parse_summary_file};
 use crate::core::conf::tools::net::interface::InterfaceConf;
 use crate::tools::encryption_algorithm::aes::AesRand;
+use crate::core::errors::SmapError;
 
 
 pub struct BaseConf {
@@ -15,41 +16,104 @@ pub struct BaseConf {
     // 配置与结果保存文件
     pub summary_file:Option<String>,
 
+    // 扫描进度 输出间隔(秒), 是否输出状态行, 状态文件 和 指标文件, 间隔为空时 不启动监视线程
+    pub status_interval:Option<u64>,
+    pub print_status:bool,
+    pub status_file:Option<String>,
+    pub metrics_file:Option<String>,
+
+    // 断点文件, 写入间隔(秒), 以及 续扫时读取的断点文件
+    pub checkpoint_file:Option<String>,
+    pub checkpoint_interval:u64,
+    pub resume_file:Option<String>,
+
+}
+
+/// 基础配置 选项, 字段含义 与 同名命令行参数 相同
+#[derive(Debug, Clone, Default)]
+pub struct BaseConfOpts {
+
+    pub interface:Vec<String>,
+    // 接口 -> 网关硬件地址, 格式: 接口名称@网关硬件地址
+    pub set_gateway_mac:Vec<String>,
+
+    pub seed:Option<u64>,
+
+    pub summary_file:Option<String>,
+    pub no_allow_summary:bool,
+
+    pub status_interval:Option<u64>,
+    pub status_file:Option<String>,
+    pub metrics_file:Option<String>,
+
+    pub checkpoint:Option<String>,
+    pub checkpoint_interval:Option<u64>,
+    pub resume:Option<String>,
+}
+
+impl BaseConfOpts {
+
+    pub fn from_args(args:&Args) -> Self {
+        Self {
+            interface: args.interface.clone(),
+            set_gateway_mac: args.set_gateway_mac.clone(),
+            seed: args.seed,
+            summary_file: args.summary_file.clone(),
+            no_allow_summary: args.no_allow_summary,
+            status_interval: args.status_interval,
+            status_file: args.status_file.clone(),
+            metrics_file: args.metrics_file.clone(),
+            checkpoint: args.checkpoint.clone(),
+            checkpoint_interval: args.checkpoint_interval,
+            resume: args.resume.clone(),
+        }
+    }
 }
 
 impl BaseConf {
 
     /// 构造基础配置
     /// 包括: 网络接口, 配置与结果保存文件, 加密机 和 随机数发生器
-    pub fn new(args:&Args) -> Self {
+    pub fn new(args:&BaseConfOpts) -> Result<Self, SmapError> {
 
         // 用户指定的接口列表
         let interface_arg = &args.interface;
         
         // 用户指定的 接口 -> 网关硬件地址   
         // 注意: 只有在系统无法自动获取网关地址时该信息才会生效
-        let interface_to_gateway = Self::get_interface_to_gateway(&args.set_gateway_mac);
+        let interface_to_gateway = Self::get_interface_to_gateway(&args.set_gateway_mac)?;
         
 
         // 生成接口配置信息
         let mut interface:Vec<InterfaceConf> = vec![];
         if interface_arg.len() == 0 {
             // 未指定接口, 由系统指定一个默认接口
-            interface.push(InterfaceConf::new(None, &interface_to_gateway));
+            interface.push(InterfaceConf::new(None, &interface_to_gateway)?);
         } else {
             // 如果指定一个接口就设置一个接口, 如果指定多个接口就设置多个接口
             for interface_name in interface_arg.clone() {
-                interface.push(InterfaceConf::new(Some(interface_name), &interface_to_gateway));
+                interface.push(InterfaceConf::new(Some(interface_name), &interface_to_gateway)?);
             }
 
         }
         
 
-        Self {
+        Ok(Self {
             interface,
             summary_file: if args.no_allow_summary { None } else { parse_summary_file(&args.summary_file) },
             aes_rand: AesRand::new(args.seed),
-        }
+
+            status_interval: parse_status_interval(args.status_interval, &args.status_file, &args.metrics_file)?,
+            // 只设置了指标文件时 不输出状态行
+            print_status: args.status_interval.is_some() || args.status_file.is_some(),
+            status_file: args.status_file.clone(),
+            metrics_file: args.metrics_file.clone(),
+
+            // 续扫 且 未指定断点文件时, 新的断点 写入续扫的断点文件
+            checkpoint_file: args.checkpoint.clone().or_else(|| args.resume.clone()),
+            checkpoint_interval: parse_checkpoint_interval(args.checkpoint_interval)?,
+            resume_file: args.resume.clone(),
+        })
 
     }
 
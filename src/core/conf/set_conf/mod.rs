@@ -1,3 +1,39 @@
 pub mod base_conf;
 pub mod sender_conf;
-pub mod receiver_conf;
\ No newline at end of file
+pub mod receiver_conf;
+
+use crate::core::conf::args::Args;
+use crate::core::conf::set_conf::base_conf::BaseConfOpts;
+use crate::core::conf::set_conf::receiver_conf::ReceiverConfOpts;
+use crate::core::conf::set_conf::sender_conf::SenderConfOpts;
+
+
+/// 扫描配置
+/// 由 命令行参数 或 库接口构造器 生成, 各模式据此构造 BaseConf, SenderBaseConf 和 ReceiverBaseConf
+#[derive(Debug, Clone, Default)]
+pub struct ScanConf {
+
+    // 模式名称, 记录在 输出文件 和 原始数据包保存文件 中
+    pub mode:String,
+
+    // 输入参数 的记录, 由 sqlite 等输出模块保存
+    pub args_record:String,
+
+    pub base:BaseConfOpts,
+    pub sender:SenderConfOpts,
+    pub receiver:ReceiverConfOpts,
+}
+
+impl ScanConf {
+
+    pub fn from_args(args:&Args) -> Self {
+        Self {
+            mode: args.mode.clone().unwrap_or_default(),
+            args_record: format!("{:?}", args),
+
+            base: BaseConfOpts::from_args(args),
+            sender: SenderConfOpts::from_args(args),
+            receiver: ReceiverConfOpts::from_args(args),
+        }
+    }
+}
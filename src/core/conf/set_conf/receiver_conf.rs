@@ -1,10 +1,16 @@
 
+use std::sync::{Arc, Mutex};
 use crate::core::conf::args::Args;
-use crate::modules::output_modules::OutputMod;
+use crate::core::conf::set_conf::ScanConf;
+use crate::core::conf::set_conf::base_conf::BaseConf;
+use crate::core::receiver::pcap::source::CaptureSource;
+use crate::modules::output_modules::{OutputMod, OutputRunInfo};
+use crate::tools::file::pcapng::PcapngWriter;
+use crate::core::errors::{CaptureError, SmapError};
 
 pub struct ReceiverBaseConf {
 
-    // 初始化输出模块
+    // 初始化输出模块(指定多个输出模块时为组合输出模块)
     pub output_v4:OutputMod,
     pub output_v6:OutputMod,
 
@@ -14,13 +20,72 @@ pub struct ReceiverBaseConf {
     // 允许探测失败的结果输出, 如 icmp差错报文包裹原始数据包, rst标志数据包等
     pub allow_no_succ:bool,
 
+    // 原始数据包保存文件, 所有接收线程共用
+    pub packet_saver:Option<Arc<Mutex<PcapngWriter>>>,
+    // 保存捕获到的全部数据包, 而不只是验证通过的数据包
+    pub save_all_packets:bool,
+
+    // 数据包来源: 网络接口, 回放文件 或 虚拟网络
+    pub capture_source:CaptureSource,
+
+    // 接收线程数量, 大于1时 多个捕获线程 通过 PACKET_FANOUT 分担接收
+    pub recv_thread_num:usize,
+
+}
+
+
+/// 接收者基础配置 选项, 字段含义 与 同名命令行参数 相同
+#[derive(Debug, Clone, Default)]
+pub struct ReceiverConfOpts {
+
+    // 输出模块 和 对应的输出文件, 下标一一对应, 输出文件为空时 使用模块的默认输出路径
+    pub output_mod:Vec<String>,
+    pub output_file_v4:Vec<Option<String>>,
+    pub output_file_v6:Vec<Option<String>>,
+
+    pub output_compress:Option<String>,
+    pub output_rotate_size:Option<String>,
+    pub output_rotate_seconds:Option<u64>,
+    pub output_overwrite:bool,
+
+    pub allow_no_succ:bool,
+    pub filter:Option<String>,
+
+    pub save_packets:Option<String>,
+    pub save_all_packets:bool,
+
+    pub replay_file:Option<String>,
+    pub recv_thread_num:Option<usize>,
+}
+
+impl ReceiverConfOpts {
+
+    pub fn from_args(args:&Args) -> Self {
+        Self {
+            output_mod: args.output_mod.clone(),
+            output_file_v4: args.output_file_v4.iter().map(|f| Some(f.clone())).collect(),
+            output_file_v6: args.output_file_v6.iter().map(|f| Some(f.clone())).collect(),
+            output_compress: args.output_compress.clone(),
+            output_rotate_size: args.output_rotate_size.clone(),
+            output_rotate_seconds: args.output_rotate_seconds,
+            output_overwrite: args.output_overwrite,
+            allow_no_succ: args.allow_no_succ,
+            filter: args.filter.clone(),
+            save_packets: args.save_packets.clone(),
+            save_all_packets: args.save_all_packets,
+            replay_file: args.replay_file.clone(),
+            recv_thread_num: args.recv_thread_num,
+        }
+    }
 }
 
 
 impl ReceiverBaseConf {
 
 
-    pub fn new(args:&Args, probe_filter:Vec<String>) -> Self {
+    pub fn new(conf:&ScanConf, base_conf:&BaseConf, probe_filter:Vec<String>) -> Result<Self, SmapError> {
+
+        let args = &conf.receiver;
 
 
         let probe_filter = if probe_filter.len() == 0 {
@@ -60,15 +125,41 @@ impl ReceiverBaseConf {
         };
 
 
-        Self {
-            output_v4: OutputMod::new(&Self::parse_output_mod(&args.output_mod), None, &args.output_file_v4, false),
-            output_v6: OutputMod::new(&Self::parse_output_mod(&args.output_mod), None, &args.output_file_v6, true),
+        // 输出文件的 压缩 与 分段轮转 配置
+        let writer_conf = Self::parse_output_writer_conf(conf)?;
+
+        // 运行信息, 由 sqlite 等输出模块记录
+        let run_info = OutputRunInfo {
+            mode: conf.mode.clone(),
+            args: conf.args_record.clone(),
+            seed: base_conf.aes_rand.seed,
+        };
+
+        Ok(Self {
+            output_v4: OutputMod::new_from_names(&Self::parse_output_mod(&args.output_mod), &args.output_file_v4, false, &writer_conf, &run_info)?,
+            output_v6: OutputMod::new_from_names(&Self::parse_output_mod(&args.output_mod), &args.output_file_v6, true, &writer_conf, &run_info)?,
             filter,
             allow_no_succ: args.allow_no_succ,
-        }
+            packet_saver: Self::open_packet_saver(args, &run_info)?,
+            save_all_packets: args.save_all_packets,
+            capture_source: Self::parse_capture_source(conf)?,
+            recv_thread_num: Self::parse_recv_thread_num(args.recv_thread_num)?,
+        })
 
     }
 
 
+    /// 创建 原始数据包保存文件, 文件注释中记录 模式 和 随机数种子, 以便离线重新分析
+    fn open_packet_saver(args:&ReceiverConfOpts, run_info:&OutputRunInfo) -> Result<Option<Arc<Mutex<PcapngWriter>>>, CaptureError> {
+
+        match &args.save_packets {
+            None => Ok(None),
+            Some(path) => {
+                let comment = format!("mode: {}, seed: {}", run_info.mode, run_info.seed);
+                let writer = PcapngWriter::new(path, &comment).map_err(|_| CaptureError::io("open_save_packets_file_failed").with(path))?;
+                Ok(Some(Arc::new(Mutex::new(writer))))
+            }
+        }
+    }
 
 }
\ No newline at end of file
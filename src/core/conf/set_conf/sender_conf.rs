@@ -1,8 +1,11 @@
 use std::net::{Ipv4Addr, Ipv6Addr};
 use crate::core::conf::args::Args;
+use crate::core::conf::set_conf::ScanConf;
 use crate::core::conf::tools::net::interface::InterfaceConf;
+use crate::core::sys::packet_sender::SendBackend;
 use crate::tools::blocker::ipv4_blocker::BlackWhiteListV4;
 use crate::tools::blocker::ipv6_blocker::BlackWhiteListV6;
+use crate::core::errors::SmapError;
 
 
 pub struct RateGlobalConf {
@@ -50,6 +53,64 @@ pub struct SenderBaseConf {
     // 在发送线程结束后等待这段冷却时间之后才终止接收线程
     pub cool_seconds:i64,
 
+    // 发送后端, 网络接口 或 模拟发送(计数 / 写入文件)
+    pub send_backend:SendBackend,
+
+    // 探测目标总数(已知时), 用于 估计扫描进度
+    pub tar_num:Option<u64>,
+
+}
+
+
+/// 发送者基础配置 选项, 字段含义 与 同名命令行参数 相同
+#[derive(Debug, Clone, Default)]
+pub struct SenderConfOpts {
+
+    pub source_addrs:Option<String>,
+    pub source_ports:Option<String>,
+
+    pub send_attempt_num:Option<i32>,
+    pub send_thread_num:Option<usize>,
+
+    pub source_black_list_v4:Option<String>,
+    pub source_black_list_v6:Option<String>,
+    pub source_white_list_v4:Option<String>,
+    pub source_white_list_v6:Option<String>,
+
+    pub send_rate:Option<u64>,
+    pub band_width:Option<String>,
+    pub batch_size:Option<u64>,
+    pub must_sleep:Option<u64>,
+
+    pub cool_seconds:Option<i64>,
+
+    pub send_backend:Option<String>,
+    pub send_backend_file:Option<String>,
+    pub virtual_net:Option<String>,
+}
+
+impl SenderConfOpts {
+
+    pub fn from_args(args:&Args) -> Self {
+        Self {
+            source_addrs: args.source_addrs.clone(),
+            source_ports: args.source_ports.clone(),
+            send_attempt_num: args.send_attempt_num,
+            send_thread_num: args.send_thread_num,
+            source_black_list_v4: args.source_black_list_v4.clone(),
+            source_black_list_v6: args.source_black_list_v6.clone(),
+            source_white_list_v4: args.source_white_list_v4.clone(),
+            source_white_list_v6: args.source_white_list_v6.clone(),
+            send_rate: args.send_rate,
+            band_width: args.band_width.clone(),
+            batch_size: args.batch_size,
+            must_sleep: args.must_sleep,
+            cool_seconds: args.cool_seconds,
+            send_backend: args.send_backend.clone(),
+            send_backend_file: args.send_backend_file.clone(),
+            virtual_net: args.virtual_net.clone(),
+        }
+    }
 }
 
 
@@ -58,23 +119,25 @@ impl SenderBaseConf {
     /// 构造<u>发送者</u>基础配置
     /// 包括: 源地址(ipv4, ipv6), 源端口, 发送重试次数, 发送线程数量
     /// 注意: 如需精确的时间估计, 请较为准确地计算冷却次数。冷却次数默认为1, 这意味着只有基础模式在不设冷却次数时的预测时间是较为准确的。
-    pub fn new(args:&Args, interface:&Vec<InterfaceConf>, target_num:Option<u64>, cool_num:Option<i64>,
-               max_packet_length:usize, have_v4:bool, have_v6:bool) -> Self {
+    pub fn new(conf:&ScanConf, interface:&Vec<InterfaceConf>, target_num:Option<u64>, cool_num:Option<i64>,
+               max_packet_length:usize, have_v4:bool, have_v6:bool) -> Result<Self, SmapError> {
+
+        let args = &conf.sender;
 
 
         // 源地址拦截器, 主要用途是清除 用户设定 或 自动从系统中获取到的 无效源地址, 比如私有地址, 特殊用途的地址等
-        let source_blocker_v4 = BlackWhiteListV4::new(&args.source_black_list_v4, &args.source_white_list_v4, true);
-        let source_blocker_v6 = BlackWhiteListV6::new(&args.source_black_list_v6, &args.source_white_list_v6, true);
+        let source_blocker_v4 = BlackWhiteListV4::new(&args.source_black_list_v4, &args.source_white_list_v4, true)?;
+        let source_blocker_v6 = BlackWhiteListV6::new(&args.source_black_list_v6, &args.source_white_list_v6, true)?;
 
         // 源地址
-        let source_addrs= Self::parse_source_addrs(&args.source_addrs,interface, have_v4, have_v6, &source_blocker_v4, &source_blocker_v6);
+        let source_addrs= Self::parse_source_addrs(&args.source_addrs,interface, have_v4, have_v6, &source_blocker_v4, &source_blocker_v6)?;
 
-        let send_thread_num = Self::parse_send_thread_num(args.send_thread_num);
+        let send_thread_num = Self::parse_send_thread_num(args.send_thread_num)?;
 
         let cool_seconds = Self::parse_cool_time(&args.cool_seconds);
 
 
-        Self {
+        Ok(Self {
 
             // 设置源地址
             source_interface_v4: source_addrs.0,
@@ -83,7 +146,7 @@ impl SenderBaseConf {
             source_addrs_v6: source_addrs.3,
 
             // 设置源端口
-            source_ports: Self::parse_source_ports(&args.source_ports),
+            source_ports: Self::parse_source_ports(&args.source_ports)?,
 
             // 设置 发送重试次数 和 发送线程
             send_attempt_num:Self::parse_send_attempt_num(args.send_attempt_num),
@@ -92,11 +155,21 @@ impl SenderBaseConf {
             // 设置全局发送速率
             global_rate_conf: Self::parse_send_rate(args.send_rate, &args.band_width,
                                                     max_packet_length,args.batch_size,
-                                                    args.must_sleep, target_num, send_thread_num, cool_seconds * cool_num.unwrap_or_else(|| 1)),
+                                                    args.must_sleep, target_num, send_thread_num, cool_seconds * cool_num.unwrap_or_else(|| 1))?,
             cool_seconds,
-        }
 
+            send_backend: Self::parse_send_backend(conf)?,
+
+            tar_num: target_num,
+        })
+
+
+    }
 
+    /// 全局目标速率(每秒数据包数量), 即 各发送线程目标速率之和. 未限制速率时 为空
+    pub fn total_rate(&self) -> Option<f64> {
+        let tar_rate = self.global_rate_conf.tar_rate;
+        if tar_rate == f64::MAX { None } else { Some(tar_rate * (self.send_thread_num as f64)) }
     }
 
 }
\ No newline at end of file
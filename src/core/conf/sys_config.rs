@@ -1,20 +1,84 @@
 //! 编译时读取 系统配置信息 以初始化 系统级关键参数 和 提示信息。
 //! 在编译前修改对应文件即可实现修改 程序语言, 关键底层配置 等。
+//! 运行时可依次叠加: 配置文件(--config 或 环境变量 SMAP_CONFIG) 和 单项覆盖(--set section.key=value), 在创建扫描任务时检查。
+//! 提示信息 可按语言(--lang 或 环境变量 LANG)从 消息目录 中读取, 目录中缺失的键 回退到 内置的默认语言。
 
+use std::env;
+use std::path::PathBuf;
 use std::process::exit;
 use std::str::FromStr;
 use configparser::ini::Ini;
+use once_cell::sync::{Lazy, OnceCell};
+use crate::core::errors::ConfError;
+
+/// 运行时配置层, 必须在 首次使用 SYS 之前设置
+static RUNTIME_LAYERS:OnceCell<RuntimeLayers> = OnceCell::new();
+
+/// 写入记录文件的 配置段
+const EFFECTIVE_CONF_SECTIONS:[&str; 2] = ["conf", "log"];
+
+/// 提示信息 所在的配置段, 只有这些段从 消息目录 中读取
+const MESSAGE_SECTIONS:[&str; 9] = ["err", "warn", "info", "debug", "trace", "print", "help", "args", "mod_args"];
+
+/// 内置配置(sys_conf.ini) 的语言
+const DEFAULT_LANG:&str = "zh";
+
+/// 编译时嵌入的 消息目录, 其他语言从 安装目录下的 locales/<语言>.ini 读取
+const EMBEDDED_LOCALES:[(&str, &str); 1] = [
+    ("en", include_str!("../../../locales/en.ini")),
+];
+
+struct RuntimeLayers {
+    config_file:Option<String>,
+    overrides:Vec<String>,
+    lang:Option<String>,
+
+    // 已检查的配置项: (配置段, 键, 值)
+    items:Vec<(String, String, String)>,
+}
 
 pub struct SysConf {
-    info:Ini
+    info:Ini,
+
+    // 当前语言的 消息目录, 使用默认语言时为空
+    catalog:Option<Ini>,
+
+    // 运行时配置文件 和 单项覆盖, 用于写入记录文件
+    config_file:Option<String>,
+    overrides:Vec<String>,
 }
 
 
 impl SysConf {
 
-    /// 编译时 读取并解析 系统配置文件
+    /// 编译时 读取并解析 系统配置文件, 并叠加 已检查的运行时配置层
     pub fn new() -> SysConf {
 
+        let mut info = Self::builtin();
+
+        let (config_file, overrides, lang, items) = match RUNTIME_LAYERS.get() {
+            Some(l) => (l.config_file.clone(), l.overrides.clone(), l.lang.clone(), l.items.clone()),
+            None => (None, vec![], None, vec![]),
+        };
+
+        let mut catalog = Self::load_catalog(&lang);
+
+        // 依次叠加 运行时配置文件 和 单项覆盖
+        for (section, key, val) in items {
+            if let Some(c) = &mut catalog {
+                if MESSAGE_SECTIONS.contains(&section.as_str()) {
+                    c.set(&section, &key, Some(val.clone()));
+                }
+            }
+            info.set(&section, &key, Some(val));
+        }
+
+        SysConf{ info, catalog, config_file, overrides }
+    }
+
+    /// 内置配置
+    fn builtin() -> Ini {
+
         // 这里的路径是从当前 rs 文件开始计算的
         let sys_text = include_str!("../../../sys_conf.ini");
 
@@ -27,17 +91,183 @@ impl SysConf {
                 eprintln!("can not open sys_conf.ini");
                 exit(1)
             }).unwrap();
+        info
+    }
+
+    /// 设置 运行时配置层, 必须在 首次使用 SYS 之前调用. 未指定配置文件时 使用环境变量 SMAP_CONFIG, 未指定语言时 使用环境变量 LANG
+    /// 配置层 在设置时检查, 同一进程中 只能设置一次, 再次设置 不同的配置层 时报错
+    pub fn set_runtime_layers(config_file:&Option<String>, overrides:&[String], lang:&Option<String>) -> Result<(), ConfError> {
 
+        let config_file = config_file.clone().or_else(|| env::var("SMAP_CONFIG").ok());
+        let same = |l:&RuntimeLayers| l.config_file == config_file && l.overrides == overrides && l.lang == *lang;
+
+        if let Some(l) = RUNTIME_LAYERS.get() {
+            return if same(l) { Ok(()) } else { Err(ConfError::conflict("runtime_layers_changed")) }
+        }
 
-        SysConf{ info }
+        // 系统配置 已在 未设置配置层 时初始化
+        if Lazy::get(&crate::SYS).is_some() {
+            let empty = config_file.is_none() && overrides.is_empty() && lang.is_none();
+            return if empty { Ok(()) } else { Err(ConfError::conflict("runtime_layers_changed")) }
+        }
+
+        let items = Self::check_layers(&config_file, overrides)?;
+        let layers = RuntimeLayers { config_file: config_file.clone(), overrides: overrides.to_vec(), lang: lang.clone(), items };
+
+        // 并发设置时 以先设置的为准
+        match RUNTIME_LAYERS.try_insert(layers) {
+            Ok(_) => Ok(()),
+            Err((l, _)) => if same(l) { Ok(()) } else { Err(ConfError::conflict("runtime_layers_changed")) },
+        }
     }
 
-    /// 获取 系统提示 信息, 如果无法获取到目标值, 就报错
+    /// 检查 运行时配置文件 和 单项覆盖, 返回 按叠加顺序排列的 (配置段, 键, 值)
+    /// 只能覆盖 内置配置中已存在的键, 不允许新增键, 以免拼写错误被静默忽略
+    /// 覆盖值 须与 内置值 类型相同(布尔, 整数, 小数), 以免 扫描中途读取配置时 才发现无法转换
+    fn check_layers(config_file:&Option<String>, overrides:&[String]) -> Result<Vec<(String, String, String)>, ConfError> {
+
+        let builtin = Self::builtin();
+        let mut items = vec![];
+
+        if let Some(path) = config_file {
+            let mut runtime = Ini::new_cs();
+            let map = runtime.load(path).map_err(|_| ConfError::io("open_runtime_config_failed").with(path))?;
+
+            for (section, kv) in map {
+                for (key, val) in kv {
+                    items.push((section.clone(), key, val.unwrap_or_default()));
+                }
+            }
+        }
+
+        for item in overrides.iter() {
+            let (section, key, val) = item.split_once('=')
+                .and_then(|(k, v)| k.trim().split_once('.').map(|(section, key)| (section.trim(), key.trim(), v.trim())))
+                .ok_or_else(|| ConfError::invalid("sys_conf_override_invalid").with(item))?;
+            items.push((section.to_string(), key.to_string(), val.to_string()));
+        }
+
+        for (section, key, val) in items.iter() {
+            let builtin_val = match builtin.get(section, key) {
+                Some(v) => v,
+                None => return Err(ConfError::invalid("unknown_sys_conf_item").with(format!("{}.{}", section, key))),
+            };
+
+            if !MESSAGE_SECTIONS.contains(&section.as_str()) && !Self::same_kind(&builtin_val, val) {
+                return Err(ConfError::invalid("sys_conf_item_type_invalid").with(format!("{}.{}={}", section, key, val)))
+            }
+        }
+
+        Ok(items)
+    }
+
+    /// 覆盖值 能否按 内置值的类型 解析, 内置值 不是 布尔 或 数值 时 视为字符串, 不作检查
+    fn same_kind(builtin_val:&str, val:&str) -> bool {
+        if builtin_val.parse::<bool>().is_ok() {
+            val.parse::<bool>().is_ok()
+        } else if builtin_val.parse::<i128>().is_ok() {
+            val.parse::<i128>().is_ok()
+        } else if builtin_val.parse::<f64>().is_ok() {
+            val.parse::<f64>().is_ok()
+        } else {
+            true
+        }
+    }
+
+    /// 读取 消息目录, 依次查找 编译时嵌入的目录 和 安装目录下的 locales/<语言>.ini
+    /// 使用默认语言 或 找不到对应目录时 返回空, 此时所有提示信息使用内置配置
+    /// 注意: 该函数在 解析命令行参数 之前被调用, 不能使用 SYS
+    pub fn load_catalog(lang:&Option<String>) -> Option<Ini> {
+
+        // 显式指定的语言 找不到目录时给出提示, 环境变量中的语言 静默回退
+        let explicit = lang.is_some();
+        let lang = Self::normalize_lang(&lang.clone().or_else(|| env::var("LANG").ok())?)?;
+
+        let text = match EMBEDDED_LOCALES.iter().find(|(l, _)| *l == lang) {
+            Some((_, text)) => text.to_string(),
+            None => {
+                let loaded = env::current_exe().ok().and_then(|mut path| {
+                    // 删除 二进制文件路径 和 上级目录, 与 get_current_path 一致
+                    path.pop();
+                    path.pop();
+                    let path:PathBuf = path.join("locales").join(format!("{}.ini", lang));
+                    std::fs::read_to_string(path).ok()
+                });
+
+                match loaded {
+                    Some(text) => text,
+                    None => {
+                        if explicit {
+                            eprintln!("Message catalog not found, using the default language: {}", lang);
+                        }
+                        return None
+                    }
+                }
+            }
+        };
+
+        let mut catalog = Ini::new_cs();
+        match catalog.read(text) {
+            Ok(_) => Some(catalog),
+            Err(_) => {
+                eprintln!("Invalid message catalog, using the default language: {}", lang);
+                None
+            }
+        }
+    }
+
+    /// 将 语言设置 规范为 目录名称, 如: en_US.UTF-8 => en
+    /// 默认语言, C 和 POSIX 返回空
+    fn normalize_lang(lang:&str) -> Option<String> {
+        let lang = lang.split(['_', '-', '.', '@']).next().unwrap_or("").trim().to_lowercase();
+        if lang.is_empty() || lang == DEFAULT_LANG || lang == "c" || lang == "posix" {
+            None
+        } else {
+            Some(lang)
+        }
+    }
+
+    /// 依次从 消息目录 和 内置配置 中查找
+    fn lookup(&self, section:&str, key:&str) -> Option<String> {
+        if let Some(catalog) = &self.catalog {
+            if MESSAGE_SECTIONS.contains(&section) {
+                if let Some(info) = catalog.get(section, key) {
+                    return Some(info)
+                }
+            }
+        }
+        self.info.get(section, key)
+    }
+
+    /// 获取 生效的配置项, 返回: (字段名, 字段值), 字段名形如 section.key, 按字段名排序
+    pub fn get_effective_conf(&self) -> Vec<(String, String)> {
+
+        let map = self.info.get_map_ref();
+        let mut conf = vec![];
+        for section in EFFECTIVE_CONF_SECTIONS {
+            if let Some(kv) = map.get(section) {
+                for (key, val) in kv {
+                    conf.push((format!("{}.{}", section, key), val.clone().unwrap_or_default()));
+                }
+            }
+        }
+        conf.sort();
+        conf
+    }
+
+    /// 运行时配置来源, 返回: (配置文件, 单项覆盖)
+    pub fn get_runtime_layers(&self) -> (String, String) {
+        (self.config_file.clone().unwrap_or_default(), self.overrides.join(" "))
+    }
+
+    /// 获取 系统提示 信息, 如果无法获取到目标值, 提示信息 返回 section.key, 其他配置 报错
     pub fn get_info(&self, section:&str, key:&str) -> String {
 
-        if let Some(info) = self.info.get(section,key) {
+        if let Some(info) = self.lookup(section, key) {
             info
-        }else {
+        } else if MESSAGE_SECTIONS.contains(&section) {
+            format!("{}.{}", section, key)
+        } else {
             eprintln!("Failed to get system configuration information!\nsection:{}    key:{}", section, key);
             exit(1)
         }
@@ -46,7 +276,7 @@ impl SysConf {
 
     /// 获取 系统信息, 如果无法获取到目标值, 就返回空
     pub fn get_info_without_panic(&self, section:&str, key:&str) -> Option<String> {
-        self.info.get(section,key)
+        self.lookup(section, key)
     }
 
 
@@ -1,15 +1,13 @@
-use std::process::exit;
 use std::str::FromStr;
 use ahash::AHashMap;
-use log::error;
 use crate::core::conf::set_conf::base_conf::BaseConf;
-use crate::SYS;
 use crate::tools::net_handle::net_interface::mac_addr::MacAddress;
+use crate::core::errors::ConfError;
 
 impl BaseConf {
     
     /// 根据输入的参数建立  接口名称 -> 网关硬件地址的映射
-    pub fn get_interface_to_gateway(interface_to_gateway:&Vec<String>) -> AHashMap<String, MacAddress> {
+    pub fn get_interface_to_gateway(interface_to_gateway:&Vec<String>) -> Result<AHashMap<String, MacAddress>, ConfError> {
         let mut i2g_map:AHashMap<String, MacAddress> = AHashMap::with_capacity(interface_to_gateway.len());
         
         for i2g in interface_to_gateway {
@@ -26,27 +24,24 @@ impl BaseConf {
                                 }
                                 Err(_) => {
                                     // 解析失败
-                                    error!("{} {}", SYS.get_info("err", "parse_gateway_mac_err"), i2g);
-                                    exit(1)
+                                    return Err(ConfError::invalid("parse_gateway_mac_err").with(i2g))
                                 }
                             }
                         }
                         None => {
                             // 解析失败
-                            error!("{} {}", SYS.get_info("err", "parse_gateway_mac_err"), i2g);
-                            exit(1)
+                            return Err(ConfError::invalid("parse_gateway_mac_err").with(i2g))
                         }
                     }
                 }
                 None => {
                     // 解析失败
-                    error!("{} {}", SYS.get_info("err", "parse_gateway_mac_err"), i2g);
-                    exit(1)
+                    return Err(ConfError::invalid("parse_gateway_mac_err").with(i2g))
                 },
             }
         }
         
-        i2g_map
+        Ok(i2g_map)
     }
     
     
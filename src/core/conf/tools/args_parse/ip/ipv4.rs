@@ -1,39 +1,35 @@
 use std::net::Ipv4Addr;
-use std::process::exit;
 use ahash::AHashSet;
-use log::error;
 use crate::core::conf::tools::args_parse::target_iterator::TarIterBaseConf;
 use crate::tools::others::parse::parse_str;
-use crate::SYS;
+use crate::core::errors::ConfError;
 use crate::tools::net_handle::net_type::net_v4::Netv4;
 
 /// 解析 ipv4地址 范围
 /// a-b  => (a,b)
-pub fn parse_ipv4_range(addrs:&str) -> (Ipv4Addr, Ipv4Addr){
+pub fn parse_ipv4_range(addrs:&str) -> Result<(Ipv4Addr, Ipv4Addr), ConfError> {
 
 
     let s:Vec<&str> = addrs.trim().split('-').collect();
 
     if s.len() == 2 {  // 两个地址
 
-        let first:Ipv4Addr = parse_str(s[0].trim());
-        let end:Ipv4Addr = parse_str(s[1].trim());
+        let first:Ipv4Addr = parse_str(s[0].trim())?;
+        let end:Ipv4Addr = parse_str(s[1].trim())?;
 
         if first <= end {
-            (first, end)
+            Ok((first, end))
         }else {
-            error!("{} {}", SYS.get_info("err","parse_ipv4_range_err"), addrs);
-            exit(1)
+            return Err(ConfError::invalid("parse_ipv4_range_err").with(addrs))
         }
 
     }else if s.len() == 1 { // 只有一个地址
 
-        let single:Ipv4Addr = parse_str(s[0].trim());
-        return (single, single);
+        let single:Ipv4Addr = parse_str(s[0].trim())?;
+        Ok((single, single))
 
     }else {
-        error!("{} {}", SYS.get_info("err","parse_ipv4_range_err"), addrs);
-        exit(1)
+        return Err(ConfError::invalid("parse_ipv4_range_err").with(addrs))
     }
 
 }
@@ -41,18 +37,18 @@ pub fn parse_ipv4_range(addrs:&str) -> (Ipv4Addr, Ipv4Addr){
 /// 解析<u>ipv4地址范围</u>(按顺序)
 /// 返回值: 0: 起始地址 1: 最终地址 2: 目标范围ip地址总数
 /// a-b 或 f/10  => u32: (a, b)  或 u32: ( f子网第一个有效地址, f子网最后一个有效地址 )
-pub fn parse_ipv4_cycle_group(addrs_str:&str) -> (u32, u32, u64){
+pub fn parse_ipv4_cycle_group(addrs_str:&str) -> Result<(u32, u32, u64), ConfError> {
 
     let addrs_str = addrs_str.trim();
 
     let first;
     let end;
     if addrs_str.contains('/') {
-        let net_v4 = Netv4::from_str(addrs_str);
+        let net_v4 = Netv4::from_str(addrs_str)?;
         first = net_v4.first();
         end = net_v4.last();
     } else {
-        let ips = parse_ipv4_range(addrs_str);
+        let ips = parse_ipv4_range(addrs_str)?;
         first = ips.0;
         end = ips.1;
     }
@@ -60,13 +56,13 @@ pub fn parse_ipv4_cycle_group(addrs_str:&str) -> (u32, u32, u64){
     let first_tar = u32::from(first);
     let end_tar = u32::from(end);
 
-    (first_tar, end_tar, TarIterBaseConf::get_tar_ip_num_u32(first_tar, end_tar))
+    Ok((first_tar, end_tar, TarIterBaseConf::get_tar_ip_num_u32(first_tar, end_tar)))
 }
 
 /// 解析 ipv4( ipv4 -> u32)地址 范围 (不按顺序)
 /// ipv4: a-b, c-d, e, f/10  => u32: a..b c..d  e 前缀为10的子网f
 #[allow(dead_code)]
-pub fn parse_ipv4_range_u32(input_addrs_str:&str) -> AHashSet<u32> {
+pub fn parse_ipv4_range_u32(input_addrs_str:&str) -> Result<AHashSet<u32>, ConfError> {
 
     let mut addrs_set = AHashSet::new();
 
@@ -77,11 +73,11 @@ pub fn parse_ipv4_range_u32(input_addrs_str:&str) -> AHashSet<u32> {
         let first;
         let end;
         if addrs_str.contains('/') {
-            let net_v4 = Netv4::from_str(addrs_str);
+            let net_v4 = Netv4::from_str(addrs_str)?;
             first = net_v4.first();
             end = net_v4.last();
         } else {
-            let ips = parse_ipv4_range(addrs_str);
+            let ips = parse_ipv4_range(addrs_str)?;
             first = ips.0;
             end = ips.1;
         }
@@ -96,13 +92,13 @@ pub fn parse_ipv4_range_u32(input_addrs_str:&str) -> AHashSet<u32> {
 
     }
 
-    addrs_set
+    Ok(addrs_set)
 }
 
 /// 解析 ipv4( ipv4 -> ipv4 )地址 范围 (不按顺序)
 /// ipv4: a-b, c-d, e, f/10 => ipv4: a..b c..d  e 前缀为10的子网f
 #[allow(dead_code)]
-pub fn parse_ipv4_range_ipaddr(input_addrs_str:&str) -> AHashSet<Ipv4Addr> {
+pub fn parse_ipv4_range_ipaddr(input_addrs_str:&str) -> Result<AHashSet<Ipv4Addr>, ConfError> {
 
     let mut addrs_set = AHashSet::new();
 
@@ -113,11 +109,11 @@ pub fn parse_ipv4_range_ipaddr(input_addrs_str:&str) -> AHashSet<Ipv4Addr> {
         let first;
         let end;
         if addrs_str.contains('/') {
-            let net_v4 = Netv4::from_str(addrs_str);
+            let net_v4 = Netv4::from_str(addrs_str)?;
             first = net_v4.first();
             end = net_v4.last();
         } else {
-            let ips = parse_ipv4_range(addrs_str);
+            let ips = parse_ipv4_range(addrs_str)?;
             first = ips.0;
             end = ips.1;
         }
@@ -132,7 +128,7 @@ pub fn parse_ipv4_range_ipaddr(input_addrs_str:&str) -> AHashSet<Ipv4Addr> {
 
     }
 
-    addrs_set
+    Ok(addrs_set)
 }
 
 
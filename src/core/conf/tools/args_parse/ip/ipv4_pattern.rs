@@ -1,27 +1,23 @@
 
 use std::net::Ipv4Addr;
-use std::process::exit;
-use log::error;
 use crate::tools::others::parse::parse_str;
-use crate::SYS;
+use crate::core::errors::ConfError;
 
 /// 解析<u>ipv4模式字符串</u>
 /// 输入: ipv4@ a-b, c, d-e 输出示例: <u>ip所占的比特位总数(0)</u>, 将<u>模式字符位置换成0后的值(最小ip)(1)</u>, 掩码(2)(变动位为0,其它为1)
 /// 和<u>片段信息(3)</u>, 最大ip  片段信息:  0: 片段长度 1: 片段相对最低位的偏移量
-pub fn parse_ipv4_pattern(pattern:&String) -> (u32, u32, u32, Vec<(u32, u32)>, u32) {
+pub fn parse_ipv4_pattern(pattern:&String) -> Result<(u32, u32, u32, Vec<(u32, u32)>, u32), ConfError> {
     let mut ip_parts = pattern.split("@");
 
     let ip: Ipv4Addr = match ip_parts.next() {
         None => {
-            error!("{} {}", SYS.get_info("err", "parse_ipv4_pattern_err"), pattern);
-            exit(1)
+            return Err(ConfError::invalid("parse_ipv4_pattern_err").with(pattern))
         }
         Some(s) => {
             match s.trim().parse() {
                 Ok(i) => i,
                 Err(_) => {
-                    error!("{} {}", SYS.get_info("err", "parse_ipv4_pattern_err"), pattern);
-                    exit(1)
+                    return Err(ConfError::invalid("parse_ipv4_pattern_err").with(pattern))
                 }
             }
         }
@@ -29,8 +25,7 @@ pub fn parse_ipv4_pattern(pattern:&String) -> (u32, u32, u32, Vec<(u32, u32)>, u
 
     let parts_str = match ip_parts.next() {
         None => {
-            error!("{} {}", SYS.get_info("err", "parse_ipv4_pattern_err"), pattern);
-            exit(1)
+            return Err(ConfError::invalid("parse_ipv4_pattern_err").with(pattern))
         }
         Some(s) => {
             s.trim()
@@ -41,7 +36,7 @@ pub fn parse_ipv4_pattern(pattern:&String) -> (u32, u32, u32, Vec<(u32, u32)>, u
 }
 
 
-fn parse_pattern_whole_range_v4(raw_ip:u32, parts_str:&str) -> (u32, u32, u32, Vec<(u32, u32)>, u32) {
+fn parse_pattern_whole_range_v4(raw_ip:u32, parts_str:&str) -> Result<(u32, u32, u32, Vec<(u32, u32)>, u32), ConfError> {
 
     let mut bits_for_ip = 0;
     let mut parts:Vec<(u32, u32)> = vec![];
@@ -53,12 +48,11 @@ fn parse_pattern_whole_range_v4(raw_ip:u32, parts_str:&str) -> (u32, u32, u32, V
     let mut pre_last:u32 = 0;
     for ps in s {   // 每个片段
 
-        let (first, last) = parse_pattern_local_range_v4(ps);
+        let (first, last) = parse_pattern_local_range_v4(ps)?;
 
         if pre_last >= first {
             // 当前片段的首索引 必须 大于上一个片段的 尾索引
-            error!("{} {}", SYS.get_info("err", "ipv4_pattern_local_part_err"), ps);
-            exit(1)
+            return Err(ConfError::invalid("ipv4_pattern_local_part_err").with(ps))
         } else {
             pre_last = last;
         }
@@ -99,8 +93,7 @@ fn parse_pattern_whole_range_v4(raw_ip:u32, parts_str:&str) -> (u32, u32, u32, V
     }
 
     if bits_for_ip == 0 || bits_for_ip > 32 {
-        error!("{}", SYS.get_info("err", "ipv4_pattern_bits_for_ip_invalid"));
-        exit(1)
+        return Err(ConfError::invalid("ipv4_pattern_bits_for_ip_invalid"))
     }
 
     // base_ip_val 也是起始ip
@@ -108,40 +101,37 @@ fn parse_pattern_whole_range_v4(raw_ip:u32, parts_str:&str) -> (u32, u32, u32, V
 
     let end_ip_val = base_ip_val | (!mask);
 
-    (bits_for_ip, base_ip_val, mask, parts, end_ip_val)
+    Ok((bits_for_ip, base_ip_val, mask, parts, end_ip_val))
 }
 
 
-pub fn parse_pattern_local_range_v4(part_str:&str) -> (u32, u32) {
+pub fn parse_pattern_local_range_v4(part_str:&str) -> Result<(u32, u32), ConfError> {
 
 
     let s:Vec<&str> = part_str.trim().split('-').collect();
 
     if s.len() == 2 {
 
-        let first:u32 = parse_str(s[0].trim());
-        let end:u32 = parse_str(s[1].trim());
+        let first:u32 = parse_str(s[0].trim())?;
+        let end:u32 = parse_str(s[1].trim())?;
 
         if first <= end && 1 <= first && first <= 32 && 1 <= end && end <= 32{
-            (first, end)
+            Ok((first, end))
         } else {
-            error!("{} {}", SYS.get_info("err", "ipv4_pattern_local_part_err"), part_str);
-            exit(1)
+            return Err(ConfError::invalid("ipv4_pattern_local_part_err").with(part_str))
         }
 
     }else if s.len() == 1 {
 
-        let single:u32 = parse_str(s[0].trim());
+        let single:u32 = parse_str(s[0].trim())?;
         if 1 <= single && single <= 32 {
-            return (single, single);
+            Ok((single, single))
         } else {
-            error!("{} {}", SYS.get_info("err", "ipv4_pattern_local_part_err"), part_str);
-            exit(1)
+            return Err(ConfError::invalid("ipv4_pattern_local_part_err").with(part_str))
         }
 
     }else {
-        error!("{} {}", SYS.get_info("err", "ipv4_pattern_local_part_err"), part_str);
-        exit(1)
+        return Err(ConfError::invalid("ipv4_pattern_local_part_err").with(part_str))
     }
 
 }
\ No newline at end of file
@@ -1,7 +1,6 @@
 use std::fs::OpenOptions;
 use std::io::{BufRead, BufReader};
 use std::net::Ipv6Addr;
-use std::process::exit;
 use std::str::FromStr;
 use ahash::AHashSet;
 use log::{error, warn};
@@ -10,33 +9,32 @@ use rand::seq::SliceRandom;
 use crate::core::conf::tools::args_parse::target_iterator::TarIterBaseConf;
 use crate::tools::others::parse::parse_str;
 use crate::SYS;
+use crate::core::errors::ConfError;
 use crate::tools::net_handle::net_type::net_v6::Netv6;
 
 /// 解析 ipv6地址 范围
 /// a-b  => (a,b)
-pub fn parse_ipv6_range(addrs:&str) -> (Ipv6Addr, Ipv6Addr){
+pub fn parse_ipv6_range(addrs:&str) -> Result<(Ipv6Addr, Ipv6Addr), ConfError> {
 
     let s:Vec<&str> = addrs.trim().split('-').collect();
 
     if s.len() == 2 {  // 两个地址
 
-        let first:Ipv6Addr = parse_str(s[0].trim());
-        let end:Ipv6Addr = parse_str(s[1].trim());
+        let first:Ipv6Addr = parse_str(s[0].trim())?;
+        let end:Ipv6Addr = parse_str(s[1].trim())?;
 
         if first <= end {
-            (first, end)
+            Ok((first, end))
         }else {
-            error!("{} {}", SYS.get_info("err","parse_ipv6_range_err"), addrs);
-            exit(1)
+            return Err(ConfError::invalid("parse_ipv6_range_err").with(addrs))
         }
 
     }else if s.len() == 1 { // 只有一个地址
 
-        let single:Ipv6Addr = parse_str(s[0].trim());
-        return (single, single);
+        let single:Ipv6Addr = parse_str(s[0].trim())?;
+        Ok((single, single))
     }else {
-        error!("{} {}", SYS.get_info("err","parse_ipv6_range_err"), addrs);
-        exit(1)
+        return Err(ConfError::invalid("parse_ipv6_range_err").with(addrs))
     }
 
 }
@@ -45,18 +43,18 @@ pub fn parse_ipv6_range(addrs:&str) -> (Ipv6Addr, Ipv6Addr){
 
 /// 解析 ipv6地址范围 (按顺序, 适用于 循环群算法(zmap) )
 /// a-b 或 f/10  => u128: (a, b)  或 u128: ( f子网第一个有效地址, f子网最后一个有效地址 )
-pub fn parse_ipv6_cycle_group(addrs_str:&str) -> (u128, u128, u64){
+pub fn parse_ipv6_cycle_group(addrs_str:&str) -> Result<(u128, u128, u64), ConfError> {
 
     let addrs_str = addrs_str.trim();
 
     let first;
     let end;
     if addrs_str.contains('/') {
-        let net_v6 = Netv6::from_str(addrs_str);
+        let net_v6 = Netv6::from_str(addrs_str)?;
         first = net_v6.first();
         end = net_v6.last();
     } else {
-        let ips = parse_ipv6_range(addrs_str);
+        let ips = parse_ipv6_range(addrs_str)?;
         first = ips.0;
         end = ips.1;
     }
@@ -64,7 +62,7 @@ pub fn parse_ipv6_cycle_group(addrs_str:&str) -> (u128, u128, u64){
     let first_tar = u128::from(first);
     let end_tar = u128::from(end);
 
-    (first_tar, end_tar, TarIterBaseConf::get_tar_ip_num_u128(first_tar, end_tar))
+    Ok((first_tar, end_tar, TarIterBaseConf::get_tar_ip_num_u128(first_tar, end_tar)?))
 }
 
 
@@ -75,7 +73,7 @@ pub fn parse_ipv6_cycle_group(addrs_str:&str) -> (u128, u128, u64){
 /// 解析 ipv6( ipv6 -> u128 )地址 范围 (不按顺序)
 /// ipv6: a-b, c-d, e, f/10 => u128: a..b c..d  e 前缀为10的子网f
 #[allow(dead_code)]
-pub fn parse_ipv6_range_u128(input_addrs_str:&str) -> AHashSet<u128> {
+pub fn parse_ipv6_range_u128(input_addrs_str:&str) -> Result<AHashSet<u128>, ConfError> {
 
     let mut addrs_set = AHashSet::new();
 
@@ -86,11 +84,11 @@ pub fn parse_ipv6_range_u128(input_addrs_str:&str) -> AHashSet<u128> {
         let first;
         let end;
         if addrs_str.contains('/') {
-            let net_v6 = Netv6::from_str(addrs_str);
+            let net_v6 = Netv6::from_str(addrs_str)?;
             first = net_v6.first();
             end = net_v6.last();
         } else {
-            let ips = parse_ipv6_range(addrs_str);
+            let ips = parse_ipv6_range(addrs_str)?;
             first = ips.0;
             end = ips.1;
         }
@@ -105,14 +103,14 @@ pub fn parse_ipv6_range_u128(input_addrs_str:&str) -> AHashSet<u128> {
 
     }
 
-    addrs_set
+    Ok(addrs_set)
 }
 
 
 /// 解析 ipv6( ipv6 -> ipv6 )地址 范围 (不按顺序)
 /// ipv6: a-b, c-d, e, f/10 => ipv6: a..b c..d  e 前缀为10的子网f
 #[allow(dead_code)]
-pub fn parse_ipv6_range_ipaddr(input_addrs_str:&str) -> AHashSet<Ipv6Addr>{
+pub fn parse_ipv6_range_ipaddr(input_addrs_str:&str) -> Result<AHashSet<Ipv6Addr>, ConfError> {
 
     let mut addrs_set = AHashSet::new();
 
@@ -123,11 +121,11 @@ pub fn parse_ipv6_range_ipaddr(input_addrs_str:&str) -> AHashSet<Ipv6Addr>{
         let first;
         let end;
         if addrs_str.contains('/') {
-            let net_v6 = Netv6::from_str(addrs_str);
+            let net_v6 = Netv6::from_str(addrs_str)?;
             first = net_v6.first();
             end = net_v6.last();
         } else {
-            let ips = parse_ipv6_range(addrs_str);
+            let ips = parse_ipv6_range(addrs_str)?;
             first = ips.0;
             end = ips.1;
         }
@@ -142,7 +140,7 @@ pub fn parse_ipv6_range_ipaddr(input_addrs_str:&str) -> AHashSet<Ipv6Addr>{
 
     }
 
-    addrs_set
+    Ok(addrs_set)
 }
 
 
@@ -1,10 +1,8 @@
-use std::process::exit;
-use log::error;
-use crate::SYS;
+use crate::core::errors::ConfError;
 
 /// 将ipv6模式<u>二进制字符串</u>解析为, <u>ip所占的比特位总数(0)</u>, 将<u>模式字符位置换成0后的值(1)</u>, 掩码(2)(变动位为0,其它为1)
 /// 和<u>片段信息(3)</u>, 最大ip  片段信息:  0: 片段长度 1: 片段相对最低位的偏移量
-pub fn parse_ipv6_binary_pattern(pattern:&String) -> (u32, u128, u128, Vec<(u32, u32)>, u128) {
+pub fn parse_ipv6_binary_pattern(pattern:&String) -> Result<(u32, u128, u128, Vec<(u32, u32)>, u128), ConfError> {
 
     // 将所有 模式位 变为 0 后的值
     let mut base_ip_val:u128 = 0;
@@ -59,21 +57,19 @@ pub fn parse_ipv6_binary_pattern(pattern:&String) -> (u32, u128, u128, Vec<(u32,
 
     if count != 128 {
         // 如果有效字符不等于128, 说明是无效字符串
-        error!("{} {}", SYS.get_info("err", "parse_ipv6_binary_pattern_err"), pattern);
-        exit(1)
+        return Err(ConfError::invalid("parse_ipv6_binary_pattern_err").with(pattern))
     }
 
 
     let bits_for_ip = pattern_location.len() as u32;
 
     if bits_for_ip == 0 || bits_for_ip > 64 {
-        error!("{}", SYS.get_info("err", "ipv6_pattern_bits_for_ip_invalid"));
-        exit(1)
+        return Err(ConfError::invalid("ipv6_pattern_bits_for_ip_invalid"))
     }
 
     let end_ip_val = base_ip_val | (!mask);
 
-    (bits_for_ip, base_ip_val, mask, get_parts_from_binary_locations(pattern_location), end_ip_val)
+    Ok((bits_for_ip, base_ip_val, mask, get_parts_from_binary_locations(pattern_location), end_ip_val))
 
 }
 
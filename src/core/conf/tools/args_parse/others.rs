@@ -1,3 +1,4 @@
+use crate::core::errors::ConfError;
 use crate::SYS;
 
 
@@ -14,5 +15,29 @@ pub fn parse_summary_file(summary_file:&Option<String>) -> Option<String> {
 }
 
 
+/// 解析扫描进度输出间隔(秒), 只设置了 状态文件 或 指标文件 时 使用默认间隔, 都未设置时 为空
+pub fn parse_status_interval(status_interval:Option<u64>, status_file:&Option<String>, metrics_file:&Option<String>) -> Result<Option<u64>, ConfError> {
+
+    match status_interval {
+        Some(0) => Err(ConfError::invalid("status_interval_invalid")),
+        Some(i) => Ok(Some(i)),
+        None => {
+            if status_file.is_some() || metrics_file.is_some() {
+                Ok(Some(SYS.get_conf("conf", "default_status_interval")))
+            } else {
+                Ok(None)
+            }
+        }
+    }
+}
 
 
+/// 解析断点文件写入间隔(秒), 未设置时 使用默认间隔
+pub fn parse_checkpoint_interval(checkpoint_interval:Option<u64>) -> Result<u64, ConfError> {
+
+    match checkpoint_interval {
+        Some(0) => Err(ConfError::invalid("checkpoint_interval_invalid")),
+        Some(i) => Ok(i),
+        None => Ok(SYS.get_conf("conf", "default_checkpoint_interval")),
+    }
+}
@@ -1,36 +1,32 @@
-use crate::SYS;
-use std::process::exit;
 use std::str::FromStr;
 use ahash::AHashSet;
-use log::error;
 use crate::tools::others::parse::parse_str;
+use crate::core::errors::ConfError;
 
 /// 解析范围
 /// a-b => (a,b)
-pub fn parse_range<T:PartialOrd + Copy + FromStr>(ports_str:&str, info:&str) -> (T, T) {
+pub fn parse_range<T:PartialOrd + Copy + FromStr>(ports_str:&str, info:&'static str) -> Result<(T, T), ConfError> {
 
 
     let s:Vec<&str> = ports_str.trim().split('-').collect();
 
     if s.len() == 2 {  // 两个端口
 
-        let first:T = parse_str(s[0].trim());
-        let end:T = parse_str(s[1].trim());
+        let first:T = parse_str(s[0].trim())?;
+        let end:T = parse_str(s[1].trim())?;
 
         if first <= end {
-            (first, end)
+            Ok((first, end))
         }else {
-            error!("{} {}", SYS.get_info("err", info), ports_str);
-            exit(1)
+            Err(ConfError::invalid(info).with(ports_str))
         }
 
     }else if s.len() == 1 { // 只有一个端口
 
-        let single:T = parse_str(s[0].trim());
-        return (single, single);
+        let single:T = parse_str(s[0].trim())?;
+        Ok((single, single))
     }else {
-        error!("{} {}", SYS.get_info("err", info), ports_str);
-        exit(1)
+        Err(ConfError::invalid(info).with(ports_str))
     }
 
 }
@@ -38,7 +34,7 @@ pub fn parse_range<T:PartialOrd + Copy + FromStr>(ports_str:&str, info:&str) ->
 /// 解析整体的端口范围 set格式 (随机顺序)
 /// a-b, c-d, e  => a..b  c..d e   or  * => 0..65535(不按顺序)
 #[allow(dead_code)]
-pub fn parse_ports_set(ports_str:&str) -> AHashSet<u16> {
+pub fn parse_ports_set(ports_str:&str) -> Result<AHashSet<u16>, ConfError> {
 
     let ports_str = ports_str.trim();
 
@@ -57,7 +53,7 @@ pub fn parse_ports_set(ports_str:&str) -> AHashSet<u16> {
 
         for ps in s {   // 每个端口范围分段
 
-            let (first, last) = parse_range(ps, "parse_ports_range_err");
+            let (first, last) = parse_range(ps, "parse_ports_range_err")?;
 
             for pi in first..=last {
 
@@ -66,13 +62,13 @@ pub fn parse_ports_set(ports_str:&str) -> AHashSet<u16> {
         }
     }
 
-    ports_set
+    Ok(ports_set)
 }
 
 
 /// 解析整体的端口范围 vec格式 (随机顺序)
 /// a-b, c-d, e  => a..b  c..d e   or  * => 0..65535(不按顺序)
-pub fn parse_ports_vec(ports_str:&str) -> Vec<u16> {
+pub fn parse_ports_vec(ports_str:&str) -> Result<Vec<u16>, ConfError> {
 
     let ports_str = ports_str.trim();
 
@@ -91,7 +87,7 @@ pub fn parse_ports_vec(ports_str:&str) -> Vec<u16> {
 
         for ps in s {   // 每个端口范围分段
 
-            let (first, last) = parse_range(ps, "parse_ports_range_err");
+            let (first, last) = parse_range(ps, "parse_ports_range_err")?;
 
             for pi in first..=last {
 
@@ -105,7 +101,7 @@ pub fn parse_ports_vec(ports_str:&str) -> Vec<u16> {
         ports_vec.push(port);
     }
 
-    ports_vec
+    Ok(ports_vec)
 }
 
 
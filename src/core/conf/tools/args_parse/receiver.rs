@@ -1,16 +1,122 @@
+use crate::core::conf::set_conf::ScanConf;
 use crate::core::conf::set_conf::receiver_conf::{ReceiverBaseConf};
+#[cfg(target_os = "linux")]
+use crate::core::conf::set_conf::sender_conf::SenderBaseConf;
+use crate::core::receiver::pcap::source::CaptureSource;
+use crate::core::sys::virtual_net::VirtualNet;
+use crate::modules::output_modules::output_writer::{OutputCompress, OutputWriterConf};
 use crate::SYS;
+use crate::core::errors::{ConfError, OutputError};
 impl ReceiverBaseConf {
 
 
-    pub fn parse_output_mod(output_mod:&Option<String>) -> String {
+    pub fn parse_output_mod(output_mod:&Vec<String>) -> Vec<String> {
 
-        if let Some(o) = output_mod {
-            o.to_string()
+        if output_mod.is_empty() {
+            vec![SYS.get_info("conf", "default_output_mod")]
         } else {
-            SYS.get_info("conf", "default_output_mod")
+            output_mod.clone()
         }
 
     }
 
-}
\ No newline at end of file
+
+    /// 解析输出文件的 压缩 与 分段轮转 配置
+    pub fn parse_output_writer_conf(conf:&ScanConf) -> Result<OutputWriterConf, OutputError> {
+
+        let args = &conf.receiver;
+
+        let compress = match &args.output_compress {
+            None => OutputCompress::None,
+            Some(c) => {
+                match c.as_str() {
+                    "none" => OutputCompress::None,
+                    "gzip" | "gz" => OutputCompress::Gzip,
+                    "zstd" | "zst" => OutputCompress::Zstd,
+                    _ => {
+                        return Err(OutputError::invalid("output_compress_invalid").with(c))
+                    }
+                }
+            }
+        };
+
+        let rotate_size = match &args.output_rotate_size {
+            None => 0,
+            Some(s) => Self::parse_output_rotate_size(s)?,
+        };
+
+        Ok(OutputWriterConf {
+            compress,
+            rotate_size,
+            rotate_seconds: args.output_rotate_seconds.unwrap_or(0),
+            // 续扫时 追加到已有的输出文件
+            append: conf.base.resume.is_some(),
+            overwrite: args.output_overwrite,
+        })
+    }
+
+
+    /// 解析分段大小, 如: 1048576, 512K, 100M, 2G (以1024为进制)
+    pub fn parse_output_rotate_size(size_str:&str) -> Result<u64, OutputError> {
+
+        let size_str = size_str.trim();
+
+        let (val_str, unit) = match size_str.chars().last() {
+            Some('K') | Some('k') => (&size_str[..size_str.len()-1], 1u64 << 10),
+            Some('M') | Some('m') => (&size_str[..size_str.len()-1], 1u64 << 20),
+            Some('G') | Some('g') => (&size_str[..size_str.len()-1], 1u64 << 30),
+            _ => (size_str, 1),
+        };
+
+        match val_str.parse::<u64>() {
+            Ok(val) if val != 0 => val.checked_mul(unit)
+                .ok_or_else(|| OutputError::invalid("parse_output_rotate_size_err").with(size_str)),
+            _ => {
+                Err(OutputError::invalid("parse_output_rotate_size_err").with(size_str))
+            }
+        }
+    }
+
+
+    /// 解析数据包来源, 回放时必须指定随机数种子
+    pub fn parse_capture_source(conf:&ScanConf) -> Result<CaptureSource, ConfError> {
+
+        let args = &conf.receiver;
+        if let Some(topology_file) = &conf.sender.virtual_net {
+            if args.replay_file.is_some() {
+                return Err(ConfError::conflict("replay_with_virtual_net"))
+            }
+            return Ok(CaptureSource::Virtual(VirtualNet::global(topology_file)?))
+        }
+
+        match &args.replay_file {
+            // 使用 AF_XDP 发送后端时, 接收线程 同样通过 AF_XDP 接收
+            #[cfg(target_os = "linux")]
+            None if SenderBaseConf::send_backend_name(conf) == "xdp" => {
+                let source_ports = SenderBaseConf::parse_source_ports(&conf.sender.source_ports)?;
+                let min_port = source_ports.iter().min().copied().unwrap_or(0);
+                let max_port = source_ports.iter().max().copied().unwrap_or(u16::MAX);
+                Ok(CaptureSource::Xdp(min_port, max_port))
+            }
+            // 模拟发送时 没有响应, 不打开网络接口
+            None if matches!(SenderBaseConf::send_backend_name(conf).as_str(), "count" | "pcapng") => Ok(CaptureSource::Idle),
+            None => Ok(CaptureSource::Device),
+            Some(path) => {
+                if conf.base.seed.is_none() {
+                    return Err(ConfError::missing("replay_without_seed"))
+                }
+                Ok(CaptureSource::Replay(path.clone()))
+            }
+        }
+    }
+
+
+    /// 解析 接收线程数量, 未设置时为 1, 手动设置为 0 时报错
+    pub fn parse_recv_thread_num(thread_num:Option<usize>) -> Result<usize, ConfError> {
+        match thread_num {
+            None => Ok(1),
+            Some(0) => Err(ConfError::invalid("recv_thread_num_not_zero")),
+            Some(n) => Ok(n),
+        }
+    }
+}
@@ -1,19 +1,75 @@
 use std::net::{Ipv4Addr, Ipv6Addr};
-use std::process::exit;
+use std::sync::Arc;
 use std::thread::available_parallelism;
 use chrono::{DateTime, Local, TimeZone, Utc};
-use log::{error, warn};
+use log::warn;
+use crate::core::conf::set_conf::ScanConf;
 use crate::core::conf::set_conf::sender_conf::{RateGlobalConf, SenderBaseConf};
 use crate::core::conf::tools::args_parse::ip::mix::parse_mix_ip_range_ipaddr;
 use crate::core::conf::tools::args_parse::port::parse_ports_vec;
 use crate::core::conf::tools::net::interface::InterfaceConf;
+use crate::core::sys::packet_sender::{DryRunBackend, SendBackend};
+use crate::core::sys::virtual_net::VirtualNet;
 use crate::SYS;
 use crate::tools::blocker::ipv4_blocker::BlackWhiteListV4;
 use crate::tools::blocker::ipv6_blocker::BlackWhiteListV6;
+use crate::tools::file::get_path::get_current_path;
+use crate::tools::others::time::get_fmt_time;
+use crate::core::errors::{ConfError, InterfaceError, SmapError};
 
 
 impl SenderBaseConf {
 
+    /// 发送后端名称
+    /// 未指定时, 如果设置了虚拟网络则发送到虚拟网络, 设置了回放文件则只计数不发送, 否则通过网络接口发送
+    pub fn send_backend_name(conf:&ScanConf) -> String {
+
+        let args = &conf.sender;
+        match &args.send_backend {
+            Some(b) => b.trim().to_lowercase(),
+            None => if args.virtual_net.is_some() {
+                "virtual".to_string()
+            } else if args.send_backend_file.is_some() {
+                "pcapng".to_string()
+            } else if conf.receiver.replay_file.is_some() {
+                "count".to_string()
+            } else {
+                "wire".to_string()
+            }
+        }
+    }
+
+    /// 解析 发送后端
+    pub fn parse_send_backend(conf:&ScanConf) -> Result<SendBackend, SmapError> {
+
+        let args = &conf.sender;
+        let backend = Self::send_backend_name(conf);
+
+        match backend.as_str() {
+            "wire" => Ok(SendBackend::Wire),
+            #[cfg(target_os = "linux")]
+            "xdp" => Ok(SendBackend::Xdp),
+            "count" => Ok(SendBackend::DryRun(Arc::new(DryRunBackend::new(None, "")?))),
+            "pcapng" => {
+                let path = match &args.send_backend_file {
+                    Some(p) => p.clone(),
+                    None => get_current_path(&get_fmt_time(&SYS.get_info("conf", "dry_run_file_pattern")))?,
+                };
+                let comment = format!("dry run, mode: {}", conf.mode);
+                Ok(SendBackend::DryRun(Arc::new(DryRunBackend::new(Some(path), &comment)?)))
+            }
+            "virtual" => match &args.virtual_net {
+                Some(topology_file) => Ok(SendBackend::Virtual(VirtualNet::global(topology_file)?)),
+                None => {
+                    Err(ConfError::missing("virtual_net_not_set").into())
+                }
+            }
+            _ => {
+                Err(ConfError::invalid("send_backend_invalid").with(backend).into())
+            }
+        }
+    }
+
     /// 解析 重试次数
     pub fn parse_send_attempt_num(att: Option<i32>) -> i32 {
         if let Some(a) = att {
@@ -28,7 +84,7 @@ impl SenderBaseConf {
     pub fn parse_source_addrs(source_addr: &Option<String>, interfaces: &Vec<InterfaceConf>,
                               have_v4:bool, have_v6:bool,
                               blocker_v4:&BlackWhiteListV4, blocker_v6:&BlackWhiteListV6 )
-                              -> (Vec<String>, Vec<Vec<Ipv4Addr>>, Vec<String>, Vec<Vec<Ipv6Addr>>) {
+                              -> Result<(Vec<String>, Vec<Vec<Ipv4Addr>>, Vec<String>, Vec<Vec<Ipv6Addr>>), SmapError> {
 
         // v4源接口 及其 对应源地址
         let mut send_source_interface_v4 = vec![];
@@ -42,7 +98,7 @@ impl SenderBaseConf {
         if let Some(saddr) = source_addr {
 
             // 解析输入的源地址
-            let (vec_v4, vec_v6) = parse_mix_ip_range_ipaddr(saddr);
+            let (vec_v4, vec_v6) = parse_mix_ip_range_ipaddr(saddr)?;
 
             // 遍历所有网络接口
             for interface in interfaces {
@@ -127,21 +183,19 @@ impl SenderBaseConf {
 
         if have_v4 && send_source_addrs_v4.len() == 0 {
             // 如果  目标范围包括 ipv4,  且不存在有效ipv4源地址
-            error!("{}", SYS.get_info("err", "source_ips_not_exist_v4"));
-            exit(1)
+            return Err(InterfaceError::not_found("source_ips_not_exist_v4").into())
         }
 
         if have_v6 && send_source_addrs_v6.len() == 0 {
             // 如果  目标范围包括 ipv6,  且不存在有效ipv6源地址
-            error!("{}", SYS.get_info("err", "source_ips_not_exist_v6"));
-            exit(1)
+            return Err(InterfaceError::not_found("source_ips_not_exist_v6").into())
         }
 
-        (send_source_interface_v4, send_source_addrs_v4, send_source_interface_v6, send_source_addrs_v6)
+        Ok((send_source_interface_v4, send_source_addrs_v4, send_source_interface_v6, send_source_addrs_v6))
     }
 
     /// 解析 源端口
-    pub fn parse_source_ports(source_ports: &Option<String>) -> Vec<u16> {
+    pub fn parse_source_ports(source_ports: &Option<String>) -> Result<Vec<u16>, ConfError> {
         if let Some(sports) = source_ports {
             parse_ports_vec(sports)
         } else {
@@ -157,28 +211,26 @@ impl SenderBaseConf {
     /// 如果不设置, 会根据系统线程数量设置发送线程数量
     /// 如果系统线程为 1，则直接设为 1
     /// 如果系统线程为 其他值, 则为系统线程数量 减1
-    pub fn parse_send_thread_num(thread_num: Option<usize>) -> usize {
+    pub fn parse_send_thread_num(thread_num: Option<usize>) -> Result<usize, ConfError> {
         if let Some(n) = thread_num {
             if n == 0 {
-                error!("{}", SYS.get_info("err","send_thread_num_not_zero"));
-                exit(1)
+                return Err(ConfError::invalid("send_thread_num_not_zero"))
             }
-            n
+            Ok(n)
         } else {
             match available_parallelism() {
                 Ok(n) => {
                     let sys_thread_num: usize = n.into();
 
                     if sys_thread_num == 1 {
-                        1
+                        Ok(1)
                     } else {
-                        sys_thread_num - 1
+                        Ok(sys_thread_num - 1)
                     }
                 },
 
                 Err(_) => {
-                    error!("{}", SYS.get_info("err","get_thread_num_failed"));
-                    exit(1)
+                    Err(ConfError::invalid("get_thread_num_failed"))
                 }
             }
         }
@@ -209,7 +261,7 @@ impl SenderBaseConf {
                            batch_size_arg:Option<u64>,
                            must_sleep_arg:Option<u64>,
                            target_num:Option<u64>, thread_num:usize, cool_time:i64)
-    -> RateGlobalConf {
+    -> Result<RateGlobalConf, ConfError> {
 
     let batch_size = batch_size_arg.unwrap_or_else(|| SYS.get_conf("conf", "default_batch_size"));
 
@@ -221,14 +273,12 @@ impl SenderBaseConf {
             match band_width_arg {
                 Some(_) => {
                     // rate 和 bandwidth 只能二选一
-                    error!("{}", SYS.get_info("err", "rate_bandwidth_both_exist"));
-                    exit(1)
+                    Err(ConfError::conflict("rate_bandwidth_both_exist"))
                 }
                 None => {
                     // 按照 rate 进行发送
                     if r == 0 {
-                        error!("{}", SYS.get_info("err", "rate_invalid"));
-                        exit(1)
+                        return Err(ConfError::invalid("rate_invalid"))
                     }
 
                     if let Some(tar_num) = target_num {
@@ -243,11 +293,11 @@ impl SenderBaseConf {
                         let end_time = DateTime::from_timestamp(end_time, 0).unwrap().naive_local();
                         let end_time: DateTime<Local> =  Local.from_utc_datetime(&end_time);
 
-                        println!("{} {}", SYS.get_info("print", "forecast_completion_time"),
+                        eprintln!("{} {}", SYS.get_info("print", "forecast_completion_time"),
                                  end_time.format(&SYS.get_info("print", "forecast_completion_time_pattern")));
                     }
 
-                    RateGlobalConf {
+                    Ok(RateGlobalConf {
                         tar_rate: {
                             (r as f64) / (thread_num as f64)
                         },
@@ -257,7 +307,7 @@ impl SenderBaseConf {
                         running_time: -1.0,
                         must_sleep,
                         batch_size,
-                    }
+                    })
                 }
             }
         }
@@ -266,7 +316,7 @@ impl SenderBaseConf {
             match band_width_arg {
                 Some(b) => {
                     // 指定了带宽( 每秒多少比特 位 )
-                    let band_width = Self::parse_band_width(&b);
+                    let band_width = Self::parse_band_width(&b)?;
 
                     let mut pkt_len = (max_packet_length * 8) + (8 * 24);
 
@@ -291,17 +341,17 @@ impl SenderBaseConf {
                         let end_time = DateTime::from_timestamp(end_time, 0).unwrap().naive_local();
                         let end_time: DateTime<Local> =  Local.from_utc_datetime(&end_time);
 
-                        println!("{} {}", SYS.get_info("print", "forecast_completion_time"),
+                        eprintln!("{} {}", SYS.get_info("print", "forecast_completion_time"),
                                  end_time.format(&SYS.get_info("print", "forecast_completion_time_pattern")));
                     }
 
-                    RateGlobalConf {
+                    Ok(RateGlobalConf {
                         tar_rate: new_rate / (thread_num as f64),
                         // 由于强制了发送速率, 预期运行时间无效
                         running_time: -1.0,
                         must_sleep,
                         batch_size,
-                    }
+                    })
                 }
 
                 // 既没指定 rate, 也没指定 带宽
@@ -309,23 +359,15 @@ impl SenderBaseConf {
                     match target_num {
                         Some(tar_num) => {
                             // 请求 用户 输入 运行时间 或 截止时间
-                            println!("{}", SYS.get_info("print", "input_end_time"));
+                            eprintln!("{}", SYS.get_info("print", "input_end_time"));
 
                             // 2023-10-26 21:15:00+08:00   注意时区
                             let mut input_end_time = String::new();
-                            std::io::stdin().read_line(&mut input_end_time).map_err(
-                                |_| {
-                                    error!("{}", SYS.get_info("err","input_end_time_err"));
-                                    exit(1)
-                                }
-                            ).unwrap();
+                            std::io::stdin().read_line(&mut input_end_time).map_err(|_| ConfError::io("input_end_time_err"))?;
 
                             // 目标时间 和 当前时间, 秒时间戳
                             let tar_time = input_end_time.parse::<DateTime<Utc>>()
-                                .map_err(|_| {
-                                    error!("{}", SYS.get_info("err", "parse_end_time_err"));
-                                    exit(1)
-                                }).unwrap().timestamp();
+                                .map_err(|_| ConfError::invalid("parse_end_time_err"))?.timestamp();
                             let now_time = Utc::now().timestamp();
 
                             // 计算 需要多少秒完成发送
@@ -333,11 +375,10 @@ impl SenderBaseConf {
 
                             if tar_running_time <= 0 {
                                 // 如果运行时间非法
-                                error!("{}", SYS.get_info("err", "send_time_invalid"));
-                                exit(1)
+                                return Err(ConfError::invalid("send_time_invalid"))
                             }
                             // 计算 每秒 需要发送多少帧
-                            RateGlobalConf {
+                            Ok(RateGlobalConf {
                                 // 注意: 这里为全局参考速率, 具体的速率应由具体的线程根据目标数量独立计算
                                 tar_rate: ((tar_num as f64) / (tar_running_time as f64)) / (thread_num as f64),
 
@@ -345,7 +386,7 @@ impl SenderBaseConf {
                                 running_time: tar_running_time as f64,
                                 must_sleep,
                                 batch_size,
-                            }
+                            })
                         }
                         None => {
                             // 既没指定速率, 也没指定带宽, 也无法获得探测目标总量
@@ -353,13 +394,13 @@ impl SenderBaseConf {
                             // 提示警告, 目标速率将被设为无限大 (仍处于管制状态, 速率控制器将消耗极少性能资源(可能几个微秒))
                             warn!("{}", SYS.get_info("warn","max_rate"));
 
-                            RateGlobalConf {
+                            Ok(RateGlobalConf {
                                 tar_rate: f64::MAX,
 
                                 running_time: -1.0,
                                 must_sleep,
                                 batch_size,
-                            }
+                            })
                         }
                     }
                 }
@@ -370,37 +411,29 @@ impl SenderBaseConf {
 
 
     /// 解析带宽字符串
-    pub fn parse_band_width(band_str:&str) -> u64 {
+    pub fn parse_band_width(band_str:&str) -> Result<u64, ConfError> {
 
         let band_str = band_str.trim();  // 清除首尾空格
         let band_l = band_str.len();    // 获取字符串长度
 
         let val:u64 = (&band_str[..band_l-1]).parse().map_err(
             // 获取前半部分
-            |_|{  // 定义错误捕获封包
-                error!("{} {}", SYS.get_info("err", "parse_band_width_err"), band_str);
-                exit(1)
-            }).unwrap();
+            |_| ConfError::invalid("parse_band_width_err").with(band_str))?;
 
         let last:char = (&band_str[band_l-1..]).parse().map_err(  // 截取最后字符
-             |_|{  // 定义错误捕获封包
-                 error!("{} {}", SYS.get_info("err", "parse_band_width_err"), band_str);
-                 exit(1)
-             }
-        ).unwrap();
+             |_| ConfError::invalid("parse_band_width_err").with(band_str))?;
 
         match last {
 
-            'G' | 'g' => val * 1_000_000_000,
+            'G' | 'g' => Ok(val * 1_000_000_000),
 
-            'M' | 'm' => val * 1_000_000,
+            'M' | 'm' => Ok(val * 1_000_000),
 
-            'K' | 'k' => val * 1_000,
+            'K' | 'k' => Ok(val * 1_000),
 
 
             _ => {
-                error!("{} {}", SYS.get_info("err", "parse_band_width_err"), band_str);
-                exit(1)
+                Err(ConfError::invalid("parse_band_width_err").with(band_str))
             }
 
         }
@@ -418,9 +451,9 @@ impl SenderBaseConf {
         }
     }
 
-    pub fn get_tar_num_with_option(tar_ip_num:Option<u64>, tar_ports_num:usize) -> Option<u64> {
+    pub fn get_tar_num_with_option(tar_ip_num:Option<u64>, tar_ports_num:usize) -> Result<Option<u64>, ConfError> {
         match tar_ip_num {
-            None => None,
+            None => Ok(None),
             Some(t) => {
                 // 如果设定了多个端口, 就将目标数量设为 端口对数量 乘以 设定端口数量
                 // 警告: 如果指定端口, 文件中的端口会被替换为设定端口
@@ -430,39 +463,36 @@ impl SenderBaseConf {
 
                 if tar_num > (u64::MAX as u128) {
                     // 超出范围限制
-                    error!("{}", SYS.get_info("err", "tar_num_over_range"));
-                    exit(1)
+                    return Err(ConfError::invalid("tar_num_over_range"))
                 }
 
-                Some(tar_num as u64)
+                Ok(Some(tar_num as u64))
             }
         }
     }
 
-    pub fn get_tar_num(tar_ip_num:u64, tar_repeat_num:usize) -> Option<u64> {
+    pub fn get_tar_num(tar_ip_num:u64, tar_repeat_num:usize) -> Result<Option<u64>, ConfError> {
 
         let tar_num:u128 = (tar_ip_num as u128) * (tar_repeat_num as u128);
 
         if tar_num > (u64::MAX as u128) {
             // 超出范围限制
-            error!("{}", SYS.get_info("err", "tar_num_over_range"));
-            exit(1)
+            return Err(ConfError::invalid("tar_num_over_range"))
         }
 
-        Some(tar_num as u64)
+        Ok(Some(tar_num as u64))
     }
 
-    pub fn get_tar_num_without_option(tar_ip_num:u64, tar_ports_num:usize) -> u64 {
+    pub fn get_tar_num_without_option(tar_ip_num:u64, tar_ports_num:usize) -> Result<u64, ConfError> {
 
         let tar_num:u128 = (tar_ip_num as u128) * (tar_ports_num as u128);
 
         if tar_num > (u64::MAX as u128) {
             // 超出范围限制
-            error!("{}", SYS.get_info("err", "tar_num_over_range"));
-            exit(1)
+            return Err(ConfError::invalid("tar_num_over_range"))
         }
 
-        tar_num as u64
+        Ok(tar_num as u64)
     }
 
 }
\ No newline at end of file
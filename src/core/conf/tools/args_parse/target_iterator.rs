@@ -1,7 +1,7 @@
-use std::process::exit;
-use log::{error, warn};
+use log::warn;
 use crate::core::conf::tools::args_parse::port::parse_ports_vec;
 use crate::SYS;
+use crate::core::errors::ConfError;
 
 /// 定义目标迭代器基本配置
 pub struct TarIterBaseConf {}
@@ -11,33 +11,31 @@ impl TarIterBaseConf {
 
 
     /// 解析 目标文件
-    pub fn parse_targets_file(tar_file_str:&Option<String>) -> String {
+    pub fn parse_targets_file(tar_file_str:&Option<String>) -> Result<String, ConfError> {
 
         if let Some(t) = tar_file_str {
-            t.to_string()
+            Ok(t.to_string())
         } else {
             // 未设置 探测目标文件, 报错并提示用户输出
-            error!("{}", SYS.get_info("err","targets_file_not_exist"));
-            exit(1)
+            Err(ConfError::missing("targets_file_not_exist"))
         }
     }
 
 
 
     /// 解析目标地址
-    pub fn parse_tar_ip(tar_ip_str:&Option<String>) -> String {
+    pub fn parse_tar_ip(tar_ip_str:&Option<String>) -> Result<String, ConfError> {
 
         if let Some(t) = tar_ip_str {
-            t.to_string()
+            Ok(t.to_string())
         } else {
             // 未设置 目标地址, 报错并提示用户输出
-            error!("{}", SYS.get_info("err","target_ips_not_exist"));
-            exit(1)
+            Err(ConfError::missing("target_ips_not_exist"))
         }
     }
 
     /// 解析目标端口
-    pub fn parse_tar_port(tar_port_str:&Option<String>, default_tar_ports:&str) -> Vec<u16> {
+    pub fn parse_tar_port(tar_port_str:&Option<String>, default_tar_ports:&str) -> Result<Vec<u16>, ConfError> {
 
         if let Some(t) = tar_port_str {
             parse_ports_vec(t)
@@ -56,36 +54,34 @@ impl TarIterBaseConf {
 
 
     /// 计算 ipv6 目标范围大小, 注意:ipv6的最大扫描范围不应超过 u64 所能表示的范围 (0..2^(64)-1)
-    pub fn get_tar_ip_num_u128(first:u128, end:u128) -> u64 {
+    pub fn get_tar_ip_num_u128(first:u128, end:u128) -> Result<u64, ConfError> {
         let tar_num = end - first + 1;
 
         // 注意, ipv6的最大扫描范围不应超过 u64 所能表示的范围 (0..2^(64)-1)
         if tar_num > (u64::MAX as u128) {
-            error!("{}", SYS.get_info("err","number_of_target_ipv6_addresses_out_of_range"));
-            exit(1)
+            return Err(ConfError::invalid("number_of_target_ipv6_addresses_out_of_range"))
         }
 
-        tar_num as u64
+        Ok(tar_num as u64)
     }
 
 
     /// 通过<u>目标位置二进制位数</u>计算目标数量
-    pub fn get_tar_ip_num_binary(bits_num:u32) -> u64 {
+    pub fn get_tar_ip_num_binary(bits_num:u32) -> Result<u64, ConfError> {
 
         let tar_num = 2u128.pow(bits_num);
 
         if tar_num > (u64::MAX as u128 + 1) {
             // 超过 2^64 的数量为非法
-            error!("{}", SYS.get_info("err","number_of_target_ipv6_addresses_out_of_range"));
-            exit(1)
+            return Err(ConfError::invalid("number_of_target_ipv6_addresses_out_of_range"))
         } else if tar_num == (u64::MAX as u128 + 1) {
 
             // 二进制循环群不对ip长度范围进行检查,且对速率控制器的影响几乎为0
             // 所以这里取近似值
-            return u64::MAX
+            return Ok(u64::MAX)
         }
 
-        tar_num as u64
+        Ok(tar_num as u64)
     }
 
     /// 使用乘法群的p-1和线程数量来进行目标分配, 注意返回向量的长度不一定和预设线程数量相等,这可能是因为有线程一个目标都分配不到.
@@ -272,11 +268,10 @@ impl TarIterBaseConf {
 
 
     /// 返回值: 0:为ipv4分配的发送线程总数   1:为ipv6分配的发送线程总数
-    pub fn assign_threads_for_v4_v6(total_p_sub_one_v4:u128, total_p_sub_one_v6:u128, send_thread_num:usize) -> (usize, usize) {
+    pub fn assign_threads_for_v4_v6(total_p_sub_one_v4:u128, total_p_sub_one_v6:u128, send_thread_num:usize) -> Result<(usize, usize), ConfError> {
 
         if send_thread_num < 2 {
-            error!("{}", SYS.get_info("err", "threads_num_less_than_two"));
-            exit(1)
+            return Err(ConfError::invalid("threads_num_less_than_two"))
         }
 
         let send_thread_num_u128 = send_thread_num as u128;
@@ -303,26 +298,24 @@ impl TarIterBaseConf {
                 // 因为 total_p_sub_one_v4 / (total_p_sub_one_v4 + total_p_sub_one_v6)
                 //  +  total_p_sub_one_v6 / (total_p_sub_one_v4 + total_p_sub_one_v6)
                 // 之和为 1, 如果一方出现小数, 则计算出的线程数 与 总线程数相差 1, 如果超出1表示出现异常
-                error!("{}", SYS.get_info("err", "assign_threads_failed"));
-                exit(1)
+                return Err(ConfError::invalid("assign_threads_failed"))
             }
         } else {
             (base_thread_num_v4 as usize, base_thread_num_v6 as usize)
         };
 
         if (thread_num_v4 + thread_num_v6) != send_thread_num {
-            error!("{}", SYS.get_info("err", "assign_threads_failed"));
-            exit(1)
+            return Err(ConfError::invalid("assign_threads_failed"))
         }
 
         if thread_num_v4 == 0 && total_p_sub_one_v4 != 0 {
             // 当存在 ipv4目标 时, 为ipv4分配的发送线程应至少为1
-            (1, thread_num_v6 - 1)
+            Ok((1, thread_num_v6 - 1))
         } else if thread_num_v6 == 0 && total_p_sub_one_v6 != 0 {
             // 当存在 ipv6目标 时, 为ipv6分配的发送线程应至少为1
-            (thread_num_v4 - 1, 1)
+            Ok((thread_num_v4 - 1, 1))
         } else {
-            (thread_num_v4, thread_num_v6)
+            Ok((thread_num_v4, thread_num_v6))
         }
     }
 
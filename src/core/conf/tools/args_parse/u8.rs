@@ -1,42 +1,38 @@
-use std::process::exit;
 use ahash::AHashSet;
-use log::error;
-use crate::SYS;
 use crate::tools::others::parse::parse_str;
+use crate::core::errors::ConfError;
 
 /// 解析数据范围
 /// a-b => (a,b)
-pub fn parse_u8_range(u8_str:&str) -> (u8, u8) {
+pub fn parse_u8_range(u8_str:&str) -> Result<(u8, u8), ConfError> {
 
 
     let s:Vec<&str> = u8_str.trim().split('-').collect();
 
     if s.len() == 2 {  // 两个
 
-        let first:u8 = parse_str(s[0].trim());
-        let end:u8 = parse_str(s[1].trim());
+        let first:u8 = parse_str(s[0].trim())?;
+        let end:u8 = parse_str(s[1].trim())?;
 
         if first <= end {
-            (first, end)
+            Ok((first, end))
         }else {
-            error!("{} {}", SYS.get_info("err", "parse_u8_range_err"), u8_str);
-            exit(1)
+            return Err(ConfError::invalid("parse_u8_range_err").with(u8_str))
         }
 
     }else if s.len() == 1 { // 只有一个
 
-        let single:u8 = parse_str(s[0].trim());
-        return (single, single);
+        let single:u8 = parse_str(s[0].trim())?;
+        Ok((single, single))
     }else {
-        error!("{} {}", SYS.get_info("err", "parse_u8_range_err"), u8_str);
-        exit(1)
+        return Err(ConfError::invalid("parse_u8_range_err").with(u8_str))
     }
 
 }
 
 /// 解析整体范围 set格式 (随机顺序)
 /// a-b, c-d, e  => a..b  c..d e   or  * => 0..255(不按顺序)
-pub fn parse_u8_set(u8_str:&str) -> AHashSet<u8> {
+pub fn parse_u8_set(u8_str:&str) -> Result<AHashSet<u8>, ConfError> {
 
     let u8_str = u8_str.trim();
 
@@ -54,7 +50,7 @@ pub fn parse_u8_set(u8_str:&str) -> AHashSet<u8> {
 
         for ps in s {   // 每个端口范围分段
 
-            let (first, last) = parse_u8_range(ps);
+            let (first, last) = parse_u8_range(ps)?;
 
             for pi in first..=last {
 
@@ -63,5 +59,5 @@ pub fn parse_u8_set(u8_str:&str) -> AHashSet<u8> {
         }
     }
 
-    u8_set
+    Ok(u8_set)
 }
\ No newline at end of file
@@ -4,10 +4,8 @@ use crate::tools::net_handle::net_interface::interface::NetInterface;
 use crate::tools::net_handle::net_interface::mac_addr::MacAddress;
 use crate::tools::net_handle::net_type::net_v4::Netv4;
 use crate::tools::net_handle::net_type::net_v6::Netv6;
-use std::process::exit;
 use ahash::AHashMap;
-use log::error;
-use crate::SYS;
+use crate::core::errors::InterfaceError;
 
 /// 网络接口配置信息
 pub struct InterfaceConf {
@@ -46,9 +44,9 @@ impl InterfaceConf {
     /// 网络接口配置
     /// 如果传入 Some(接口名称)，查询对应接口的信息并配置
     /// 如果传入 None, 将使用默认网络接口
-    pub fn new(interface_name:Option<String>, interface_to_gateway:&AHashMap<String, MacAddress>) -> Self {
+    pub fn new(interface_name:Option<String>, interface_to_gateway:&AHashMap<String, MacAddress>) -> Result<Self, InterfaceError> {
 
-        let selected_interface = NetInterface::new(interface_name);
+        let selected_interface = NetInterface::new(interface_name)?;
 
         // 获取选定的 接口名称
         let selected_interface_name = selected_interface.interface.name;
@@ -69,8 +67,7 @@ impl InterfaceConf {
                     // 如果用户指定了网关硬件地址
                     gateway_mac = (*g).clone();
                 } else {
-                    error!("{} {}", SYS.get_info("err", "gateway_info_not_found"), selected_interface_name);
-                    exit(1)
+                    return Err(InterfaceError::not_found("gateway_info_not_found").with(selected_interface_name))
                 }
             }
         }
@@ -81,8 +78,7 @@ impl InterfaceConf {
                 MacAddress::from_mac_addr(m)
             }
             None => {
-                error!("{} {}", SYS.get_info("err", "local_mac_not_found"), selected_interface_name);
-                exit(1)
+                return Err(InterfaceError::not_found("local_mac_not_found").with(selected_interface_name))
             }
         };
 
@@ -95,18 +91,23 @@ impl InterfaceConf {
             let local_ipv4_info = selected_interface.interface.ipv4;
             let local_ipv6_info = selected_interface.interface.ipv6;
 
+            // 前缀长度无效的地址 被忽略, 以保持 ip 和 net 的下标一致
             for net_v4 in local_ipv4_info.iter() {
-                local_ipv4.push(net_v4.addr());
-                local_ipv4_net.push(Netv4::new(net_v4.addr(), net_v4.prefix_len()));
+                if let Ok(net) = Netv4::new(net_v4.addr(), net_v4.prefix_len()) {
+                    local_ipv4.push(net_v4.addr());
+                    local_ipv4_net.push(net);
+                }
             }
 
             for net_v6 in local_ipv6_info.iter() {
-                local_ipv6.push(net_v6.addr());
-                local_ipv6_net.push(Netv6::new(net_v6.addr(), net_v6.prefix_len()));
+                if let Ok(net) = Netv6::new(net_v6.addr(), net_v6.prefix_len()) {
+                    local_ipv6.push(net_v6.addr());
+                    local_ipv6_net.push(net);
+                }
             }
         }
 
-        Self {
+        Ok(Self {
             name_index: (selected_interface_name, selected_interface.interface.index as c_int),
 
             gateway_mac,
@@ -122,7 +123,7 @@ impl InterfaceConf {
 
             receive_speed: selected_interface.interface.receive_speed,
             transmit_speed: selected_interface.interface.transmit_speed,
-        }
+        })
 
 
     }
@@ -0,0 +1,247 @@
+//! 各子系统的错误类型
+//! 错误只记录 提示信息的键(sys_conf.ini 的 err 段) 和 附加信息, 显示时按当前语言读取提示信息
+//! 构造器 和 执行函数 将错误逐层返回给调用者, 由调用者决定 记录, 清理 或 退出
+
+use std::fmt::{Display, Formatter};
+use crate::SYS;
+
+
+/// 错误信息: 提示信息的键 和 附加信息(如 输入参数, 文件路径)
+#[derive(Debug, Clone)]
+pub struct ErrInfo {
+    pub key:&'static str,
+    pub detail:String,
+}
+
+impl ErrInfo {
+
+    pub fn new(key:&'static str) -> Self {
+        Self { key, detail: String::new() }
+    }
+
+    pub fn with<T:Display>(key:&'static str, detail:T) -> Self {
+        Self { key, detail: detail.to_string() }
+    }
+}
+
+impl Display for ErrInfo {
+    fn fmt(&self, f:&mut Formatter<'_>) -> std::fmt::Result {
+        if self.detail.is_empty() {
+            write!(f, "{}", SYS.get_info("err", self.key))
+        } else {
+            write!(f, "{} {}", SYS.get_info("err", self.key), self.detail)
+        }
+    }
+}
+
+
+/// 为各子系统的错误枚举 生成 构造函数, 错误信息获取 和 显示
+macro_rules! subsystem_error {
+    ($(#[$meta:meta])* $name:ident { $($(#[$v_meta:meta])* $variant:ident => $ctor:ident),* $(,)? }) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone)]
+        pub enum $name {
+            $($(#[$v_meta])* $variant(ErrInfo),)*
+        }
+
+        impl $name {
+            $(
+                pub fn $ctor(key:&'static str) -> Self {
+                    $name::$variant(ErrInfo::new(key))
+                }
+            )*
+
+            pub fn info(&self) -> &ErrInfo {
+                match self {
+                    $($name::$variant(i) => i,)*
+                }
+            }
+
+            /// 追加 附加信息
+            pub fn with<T:Display>(mut self, detail:T) -> Self {
+                match &mut self {
+                    $($name::$variant(i) => i.detail = detail.to_string(),)*
+                }
+                self
+            }
+        }
+
+        impl Display for $name {
+            fn fmt(&self, f:&mut Formatter<'_>) -> std::fmt::Result {
+                self.info().fmt(f)
+            }
+        }
+
+        impl std::error::Error for $name {}
+    };
+}
+
+
+subsystem_error! {
+    /// 配置错误: 命令行参数, 自定义参数, 系统配置
+    ConfError {
+        // 参数格式 或 取值无效
+        Invalid => invalid,
+        // 互斥的参数被同时指定
+        Conflict => conflict,
+        // 缺少必需的参数
+        Missing => missing,
+        // 模式不存在
+        NotExist => not_exist,
+        // 读写配置相关文件失败
+        Io => io,
+    }
+}
+
+subsystem_error! {
+    /// 网络接口错误
+    InterfaceError {
+        // 找不到网络接口 或 接口信息(网关, 硬件地址)
+        NotFound => not_found,
+        // 打开 或 设置 发送套接字失败
+        Socket => socket,
+    }
+}
+
+subsystem_error! {
+    /// 数据包捕获错误
+    CaptureError {
+        // 打开 网络接口, 回放文件 或 虚拟网络 失败
+        Open => open,
+        // 设置过滤规则 或 捕获参数 失败
+        Setup => setup,
+        // 不支持的链路层类型
+        Unsupported => unsupported,
+        // 保存原始数据包失败
+        Io => io,
+    }
+}
+
+subsystem_error! {
+    /// 探测模块错误
+    ProbeError {
+        // 探测模块不存在 或 未激活
+        NotExist => not_exist,
+        // 探测模块参数无效
+        Invalid => invalid,
+        // 负载无效 或 无法读取
+        Payload => payload,
+    }
+}
+
+subsystem_error! {
+    /// 目标来源错误: 目标地址, 目标文件, 黑白名单
+    TargetError {
+        // 目标 或 名单条目 无效
+        Invalid => invalid,
+        // 没有可用的目标
+        Empty => empty,
+        // 读取 目标文件 或 名单文件 失败
+        Io => io,
+    }
+}
+
+subsystem_error! {
+    /// 输出错误: 输出模块 和 记录文件
+    OutputError {
+        // 输出模块不存在 或 未激活
+        NotExist => not_exist,
+        // 输出参数无效
+        Invalid => invalid,
+        // 写入 或 打开 输出目标失败
+        Io => io,
+    }
+}
+
+subsystem_error! {
+    /// 运行错误: 线程间通信 和 线程异常
+    RunError {
+        // 线程通信失败
+        Channel => channel,
+        // 线程异常退出
+        Thread => thread,
+    }
+}
+
+
+/// 扫描过程中的错误
+#[derive(Debug, Clone)]
+pub enum SmapError {
+    Conf(ConfError),
+    Interface(InterfaceError),
+    Capture(CaptureError),
+    Probe(ProbeError),
+    Target(TargetError),
+    Output(OutputError),
+    Run(RunError),
+}
+
+impl SmapError {
+
+    pub fn info(&self) -> &ErrInfo {
+        match self {
+            SmapError::Conf(e) => e.info(),
+            SmapError::Interface(e) => e.info(),
+            SmapError::Capture(e) => e.info(),
+            SmapError::Probe(e) => e.info(),
+            SmapError::Target(e) => e.info(),
+            SmapError::Output(e) => e.info(),
+            SmapError::Run(e) => e.info(),
+        }
+    }
+
+    /// 子系统名称, 用于写入记录文件
+    pub fn subsystem(&self) -> &'static str {
+        match self {
+            SmapError::Conf(_) => "conf",
+            SmapError::Interface(_) => "interface",
+            SmapError::Capture(_) => "capture",
+            SmapError::Probe(_) => "probe",
+            SmapError::Target(_) => "target",
+            SmapError::Output(_) => "output",
+            SmapError::Run(_) => "run",
+        }
+    }
+}
+
+impl Display for SmapError {
+    fn fmt(&self, f:&mut Formatter<'_>) -> std::fmt::Result {
+        self.info().fmt(f)
+    }
+}
+
+impl std::error::Error for SmapError {}
+
+macro_rules! impl_from_subsystem {
+    ($($t:ident => $v:ident),*) => {
+        $(
+            impl From<$t> for SmapError {
+                fn from(e:$t) -> Self { SmapError::$v(e) }
+            }
+        )*
+    };
+}
+
+impl_from_subsystem!(ConfError => Conf, InterfaceError => Interface, CaptureError => Capture,
+    ProbeError => Probe, TargetError => Target, OutputError => Output, RunError => Run);
+
+/// 目标来源中的 格式错误(如 名单条目, 目标地址) 视为 无效目标
+impl From<ConfError> for TargetError {
+    fn from(e:ConfError) -> Self {
+        TargetError::Invalid(e.info().clone())
+    }
+}
+
+/// 探测模块的 自定义参数 缺失 或 无法转换 视为 探测模块参数无效
+impl From<ConfError> for ProbeError {
+    fn from(e:ConfError) -> Self {
+        ProbeError::Invalid(e.info().clone())
+    }
+}
+
+/// 输出路径 无法获取 或 创建 视为 打开输出目标失败
+impl From<ConfError> for OutputError {
+    fn from(e:ConfError) -> Self {
+        OutputError::Io(e.info().clone())
+    }
+}
@@ -1,5 +1,9 @@
 pub mod conf;
+pub mod errors;
+pub mod registry;
 
 pub mod sys;
 pub mod receiver;
-pub mod sender;
\ No newline at end of file
+pub mod sender;
+pub mod monitor;
+pub mod checkpoint;
\ No newline at end of file
@@ -0,0 +1,300 @@
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+
+/// 单个发送线程的统计信息, 同一网络接口上 先后运行的发送线程(如 多轮次的推荐扫描) 复用已结束线程的统计信息
+pub struct SenderStats {
+
+    // 发送线程编号, 按注册顺序分配
+    pub thread:usize,
+    pub interface:String,
+
+    pub sent:AtomicU64,
+    pub send_failed:AtomicU64,
+    pub blocked:AtomicU64,
+
+    // 速率控制器 最近一次的 pid 输出(每秒数据包数量), 以 f64 的位模式保存
+    pid_output:AtomicU64,
+
+    // 是否有发送线程 正在使用
+    active:AtomicBool,
+}
+
+impl SenderStats {
+
+    pub fn pid_output(&self) -> f64 {
+        f64::from_bits(self.pid_output.load(Ordering::Relaxed))
+    }
+}
+
+
+/// 单个网络接口上 接收线程的统计信息, 同一网络接口上 先后运行的接收线程 累加到同一统计信息
+pub struct ReceiverStats {
+
+    pub interface:String,
+
+    pub validation_passed:AtomicU64,
+    pub validation_failed:AtomicU64,
+    pub success:AtomicU64,
+    pub repeat:AtomicU64,
+
+    // libpcap 统计: 通过过滤规则的数据包数量, 因缓冲区已满 和 被网络接口 丢弃的数据包数量
+    pub pcap_received:AtomicU64,
+    pub pcap_dropped:AtomicU64,
+    pub pcap_if_dropped:AtomicU64,
+}
+
+impl ReceiverStats {
+
+    fn new(interface:&str) -> Self {
+        Self {
+            interface: interface.to_string(),
+            validation_passed: AtomicU64::new(0),
+            validation_failed: AtomicU64::new(0),
+            success: AtomicU64::new(0),
+            repeat: AtomicU64::new(0),
+            pcap_received: AtomicU64::new(0),
+            pcap_dropped: AtomicU64::new(0),
+            pcap_if_dropped: AtomicU64::new(0),
+        }
+    }
+}
+
+
+/// 单个网络接口上 接收流水线中 同一阶段的队列统计信息, 同一阶段的多个队列(如 多线程接收) 累加到同一统计信息
+pub struct QueueStats {
+
+    pub interface:String,
+    // 从队列中取出数据的阶段: format 或 write
+    pub stage:&'static str,
+
+    pub capacity:AtomicU64,
+    // 当前 队列中的数据数量
+    pub depth:AtomicU64,
+    // 超过队列容量, 数据 进入溢出区的次数
+    pub full:AtomicU64,
+    // 溢出区 也已满, 等待下一阶段 取出数据的次数
+    pub blocked:AtomicU64,
+}
+
+
+static SENDERS:Mutex<Vec<Arc<SenderStats>>> = Mutex::new(Vec::new());
+static RECEIVERS:Mutex<Vec<Arc<ReceiverStats>>> = Mutex::new(Vec::new());
+static QUEUES:Mutex<Vec<Arc<QueueStats>>> = Mutex::new(Vec::new());
+
+
+/// 清空 统计信息, 在每次扫描开始前调用
+pub fn reset() {
+    SENDERS.lock().unwrap().clear();
+    RECEIVERS.lock().unwrap().clear();
+    QUEUES.lock().unwrap().clear();
+}
+
+/// 当前全部 发送线程 的统计信息
+pub fn senders() -> Vec<Arc<SenderStats>> {
+    SENDERS.lock().unwrap().clone()
+}
+
+/// 当前全部 网络接口 的接收统计信息
+pub fn receivers() -> Vec<Arc<ReceiverStats>> {
+    RECEIVERS.lock().unwrap().clone()
+}
+
+/// 当前全部 接收流水线队列 的统计信息
+pub fn queues() -> Vec<Arc<QueueStats>> {
+    QUEUES.lock().unwrap().clone()
+}
+
+/// 在 网络接口的 接收流水线阶段 上注册一个队列, 返回该阶段的统计信息
+pub fn queue_stats(interface:&str, stage:&'static str, capacity:usize) -> Arc<QueueStats> {
+    let mut queues = QUEUES.lock().unwrap();
+    let stats = match queues.iter().find(|q| q.interface == interface && q.stage == stage) {
+        Some(q) => q.clone(),
+        None => {
+            let q = Arc::new(QueueStats {
+                interface: interface.to_string(),
+                stage,
+                capacity: AtomicU64::new(0),
+                depth: AtomicU64::new(0),
+                full: AtomicU64::new(0),
+                blocked: AtomicU64::new(0),
+            });
+            queues.push(q.clone());
+            q
+        }
+    };
+    stats.capacity.fetch_add(capacity as u64, Ordering::Relaxed);
+    stats
+}
+
+/// 按 网络接口名称 查找接收统计信息, 不存在时创建
+fn receiver_stats(interface:&str) -> Arc<ReceiverStats> {
+    let mut receivers = RECEIVERS.lock().unwrap();
+    match receivers.iter().find(|r| r.interface == interface) {
+        Some(r) => r.clone(),
+        None => {
+            let r = Arc::new(ReceiverStats::new(interface));
+            receivers.push(r.clone());
+            r
+        }
+    }
+}
+
+#[inline]
+fn add(counter:&AtomicU64, cur:u64, last:&mut u64) {
+    counter.fetch_add(cur - *last, Ordering::Relaxed);
+    *last = cur;
+}
+
+
+/// 全部统计信息 在某一时刻的合计值
+#[derive(Clone, Copy, Default)]
+pub struct Snapshot {
+
+    // 发送成功, 发送失败, 被黑名单拦截 的数量
+    pub sent:u64,
+    pub send_failed:u64,
+    pub blocked:u64,
+
+    // 验证通过的响应数量, 以及其中 探测成功 和 重复 的数量
+    pub responses:u64,
+    pub hits:u64,
+    pub duplicates:u64,
+
+    // 捕获时 因缓冲区已满 或 被网络接口 丢弃的数据包数量
+    pub pcap_dropped:u64,
+}
+
+impl Snapshot {
+
+    pub fn take() -> Self {
+        let mut s = Self::default();
+        for sender in SENDERS.lock().unwrap().iter() {
+            s.sent += sender.sent.load(Ordering::Relaxed);
+            s.send_failed += sender.send_failed.load(Ordering::Relaxed);
+            s.blocked += sender.blocked.load(Ordering::Relaxed);
+        }
+        for receiver in RECEIVERS.lock().unwrap().iter() {
+            s.responses += receiver.validation_passed.load(Ordering::Relaxed);
+            s.hits += receiver.success.load(Ordering::Relaxed);
+            s.duplicates += receiver.repeat.load(Ordering::Relaxed);
+            s.pcap_dropped += receiver.pcap_dropped.load(Ordering::Relaxed) + receiver.pcap_if_dropped.load(Ordering::Relaxed);
+        }
+        s
+    }
+
+    /// 已处理的目标数量, 包括 发送成功, 发送失败 和 被拦截 的目标
+    pub fn processed(&self) -> u64 {
+        self.sent + self.send_failed + self.blocked
+    }
+}
+
+
+/// 发送线程计数器: 记录已发布的值, 每个发送轮次结束时 只将新增部分累加到本线程的统计信息
+pub struct SendCounter {
+    stats:Arc<SenderStats>,
+    last:[u64; 3],
+}
+
+impl SendCounter {
+
+    /// 在 网络接口 上注册发送线程, 优先复用 同一接口上已结束线程的统计信息
+    pub fn new(interface:&str) -> Self {
+        let mut senders = SENDERS.lock().unwrap();
+
+        let reusable = senders.iter().find(|s| s.interface == interface && !s.active.load(Ordering::Relaxed)).cloned();
+        let stats = match reusable {
+            Some(s) => s,
+            None => {
+                let s = Arc::new(SenderStats {
+                    thread: senders.len(),
+                    interface: interface.to_string(),
+                    sent: AtomicU64::new(0),
+                    send_failed: AtomicU64::new(0),
+                    blocked: AtomicU64::new(0),
+                    pid_output: AtomicU64::new(0f64.to_bits()),
+                    active: AtomicBool::new(false),
+                });
+                senders.push(s.clone());
+                s
+            }
+        };
+        stats.active.store(true, Ordering::Relaxed);
+
+        Self { stats, last: [0; 3] }
+    }
+
+    /// 发布 本线程的累计统计信息, 以及 速率控制器的 pid 输出
+    #[inline]
+    pub fn publish(&mut self, sent:u64, failed:u64, blocked:u64, pid_output:f64) {
+        add(&self.stats.sent, sent, &mut self.last[0]);
+        add(&self.stats.send_failed, failed, &mut self.last[1]);
+        add(&self.stats.blocked, blocked, &mut self.last[2]);
+        self.stats.pid_output.store(pid_output.to_bits(), Ordering::Relaxed);
+    }
+}
+
+impl Drop for SendCounter {
+    fn drop(&mut self) {
+        self.stats.active.store(false, Ordering::Relaxed);
+    }
+}
+
+
+/// 接收线程计数器: 记录已发布的值, 定期只将新增部分累加到 所在网络接口的统计信息
+pub struct RecvCounter {
+    stats:Arc<ReceiverStats>,
+    last:[u64; 4],
+}
+
+impl RecvCounter {
+
+    pub fn new(interface:&str) -> Self {
+        Self { stats: receiver_stats(interface), last: [0; 4] }
+    }
+
+    /// 发布 本线程的累计统计信息: 验证通过数量, 验证失败数量, 探测成功数量, 重复数量
+    #[inline]
+    pub fn publish(&mut self, passed:u64, failed:u64, success:u64, repeat:u64) {
+        add(&self.stats.validation_passed, passed, &mut self.last[0]);
+        add(&self.stats.validation_failed, failed, &mut self.last[1]);
+        add(&self.stats.success, success, &mut self.last[2]);
+        add(&self.stats.repeat, repeat, &mut self.last[3]);
+    }
+}
+
+
+/// 捕获统计计数器: libpcap 按捕获句柄返回 32位 的累计值, 按回绕处理后 将新增部分累加到 所在网络接口的统计信息
+pub struct CaptureCounter {
+    stats:Arc<ReceiverStats>,
+    last:[u32; 3],
+
+    // 本捕获句柄的 64位 累计值
+    total:[u64; 3],
+}
+
+impl CaptureCounter {
+
+    pub fn new(interface:&str) -> Self {
+        Self { stats: receiver_stats(interface), last: [0; 3], total: [0; 3] }
+    }
+
+    pub fn publish(&mut self, received:u32, dropped:u32, if_dropped:u32) {
+        for (i, (counter, cur)) in [(&self.stats.pcap_received, received), (&self.stats.pcap_dropped, dropped),
+                                    (&self.stats.pcap_if_dropped, if_dropped)].into_iter().enumerate() {
+            let delta = cur.wrapping_sub(self.last[i]) as u64;
+            counter.fetch_add(delta, Ordering::Relaxed);
+            self.total[i] += delta;
+            self.last[i] = cur;
+        }
+    }
+
+    /// 本捕获句柄的累计值: 接收数量, 因缓冲区已满 丢弃的数量, 被网络接口 丢弃的数量
+    pub fn total(&self) -> (u64, u64, u64) {
+        (self.total[0], self.total[1], self.total[2])
+    }
+
+    pub fn interface(&self) -> &str {
+        &self.stats.interface
+    }
+}
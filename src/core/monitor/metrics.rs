@@ -0,0 +1,134 @@
+use std::fmt::Write;
+use std::fs;
+use std::sync::atomic::{AtomicU64, Ordering};
+use crate::core::errors::OutputError;
+use crate::core::monitor::{queues, receivers, senders, Tick};
+
+
+/// prometheus 文本格式 指标输出器, 供 node_exporter 的 textfile collector 读取
+/// 每次输出时 先写入临时文件 再重命名, 读取方不会读到写了一半的文件
+pub struct MetricsWriter {
+    path:String,
+    tmp_path:String,
+
+    // 所有指标共有的标签: 模式名称 和 探测模块名称
+    labels:String,
+}
+
+impl MetricsWriter {
+
+    pub fn new(path:&str, mode:&str, probe:&str) -> Result<Self, OutputError> {
+
+        let tmp_path = format!("{}.tmp", path);
+
+        // 启动时 检查 指标文件所在目录 是否可写
+        fs::write(&tmp_path, "").and_then(|_| fs::remove_file(&tmp_path))
+            .map_err(|_| OutputError::io("write_metrics_file_failed").with(path))?;
+
+        Ok(Self {
+            path: path.to_string(),
+            tmp_path,
+            labels: format!("mode=\"{}\",probe=\"{}\"", escape(mode), escape(probe)),
+        })
+    }
+
+    /// 重写 指标文件, 指标文件 只用于展示, 写入失败时 不影响扫描
+    pub fn write(&self, tick:&Tick) {
+        let text = self.render(tick);
+        let _ = fs::write(&self.tmp_path, text).and_then(|_| fs::rename(&self.tmp_path, &self.path));
+    }
+
+    fn render(&self, tick:&Tick) -> String {
+
+        let mut out = String::new();
+
+        // 按 发送线程 统计
+        let senders = senders();
+        let by_thread = |get:&dyn Fn(&crate::core::monitor::SenderStats) -> String| -> Vec<(String, String)> {
+            senders.iter().map(|s| (format!("thread=\"{}\",interface=\"{}\"", s.thread, escape(&s.interface)), get(s))).collect()
+        };
+        self.family(&mut out, "smap_packets_sent_total", "counter",
+                    "Packets sent successfully.", by_thread(&|s| load(&s.sent)));
+        self.family(&mut out, "smap_packets_send_failed_total", "counter",
+                    "Packets that could not be sent after all attempts.", by_thread(&|s| load(&s.send_failed)));
+        self.family(&mut out, "smap_targets_blocked_total", "counter",
+                    "Targets skipped by the black or white list.", by_thread(&|s| load(&s.blocked)));
+        self.family(&mut out, "smap_rate_controller_pid_output", "gauge",
+                    "Latest PID output of the send rate controller, in packets per second.", by_thread(&|s| s.pid_output().to_string()));
+
+        // 按 网络接口 统计
+        let receivers = receivers();
+        let by_interface = |get:&dyn Fn(&crate::core::monitor::ReceiverStats) -> String| -> Vec<(String, String)> {
+            receivers.iter().map(|r| (format!("interface=\"{}\"", escape(&r.interface)), get(r))).collect()
+        };
+        self.family(&mut out, "smap_packets_received_total", "counter",
+                    "Captured packets handed to the probe module.",
+                    by_interface(&|r| (r.validation_passed.load(Ordering::Relaxed) + r.validation_failed.load(Ordering::Relaxed)).to_string()));
+        self.family(&mut out, "smap_packets_validation_passed_total", "counter",
+                    "Received packets that passed validation.", by_interface(&|r| load(&r.validation_passed)));
+        self.family(&mut out, "smap_packets_validation_failed_total", "counter",
+                    "Received packets that failed validation.", by_interface(&|r| load(&r.validation_failed)));
+        self.family(&mut out, "smap_packets_repeat_total", "counter",
+                    "Validated packets from targets that had already responded or were out of range.", by_interface(&|r| load(&r.repeat)));
+        self.family(&mut out, "smap_packets_success_total", "counter",
+                    "Validated packets from successfully probed targets.", by_interface(&|r| load(&r.success)));
+        self.family(&mut out, "smap_pcap_received_total", "counter",
+                    "Packets received by the capture, as reported by libpcap.", by_interface(&|r| load(&r.pcap_received)));
+        self.family(&mut out, "smap_pcap_dropped_total", "counter",
+                    "Packets dropped because the capture buffer was full, as reported by libpcap.", by_interface(&|r| load(&r.pcap_dropped)));
+        self.family(&mut out, "smap_pcap_if_dropped_total", "counter",
+                    "Packets dropped by the network interface or its driver, as reported by libpcap.", by_interface(&|r| load(&r.pcap_if_dropped)));
+
+        // 按 网络接口 和 接收流水线阶段 统计
+        let queues = queues();
+        let by_stage = |get:&dyn Fn(&crate::core::monitor::QueueStats) -> String| -> Vec<(String, String)> {
+            queues.iter().map(|q| (format!("interface=\"{}\",stage=\"{}\"", escape(&q.interface), q.stage), get(q))).collect()
+        };
+        self.family(&mut out, "smap_recv_queue_depth", "gauge",
+                    "Items waiting in the receiver pipeline queues feeding the stage.", by_stage(&|q| load(&q.depth)));
+        self.family(&mut out, "smap_recv_queue_capacity", "gauge",
+                    "Capacity of the receiver pipeline queues feeding the stage.", by_stage(&|q| load(&q.capacity)));
+        self.family(&mut out, "smap_recv_queue_full_total", "counter",
+                    "Items that exceeded the capacity of a queue feeding the stage and were kept in its spill area.", by_stage(&|q| load(&q.full)));
+        self.family(&mut out, "smap_recv_queue_blocked_total", "counter",
+                    "Times the previous stage waited for the stage because the spill area of a queue feeding it was full.", by_stage(&|q| load(&q.blocked)));
+
+        // 整体进度
+        let global = |v:f64| vec![(String::new(), v.to_string())];
+        self.family(&mut out, "smap_send_rate", "gauge",
+                    "Send rate since the previous update, in packets per second.", global(tick.rate));
+        if let Some(r) = tick.tar_rate {
+            self.family(&mut out, "smap_target_send_rate", "gauge", "Target send rate, in packets per second.", global(r));
+        }
+        self.family(&mut out, "smap_scan_elapsed_seconds", "gauge", "Seconds since the scan started.", global(tick.elapsed));
+        if let (Some(t), Some(c)) = (tick.tar_num, tick.covered) {
+            self.family(&mut out, "smap_scan_targets", "gauge", "Number of targets of the scan.", global(t as f64));
+            self.family(&mut out, "smap_scan_covered_ratio", "gauge", "Share of the targets that have been processed.", global(c));
+        }
+
+        out
+    }
+
+    /// 输出一个指标: 说明, 类型 和 各组标签的值
+    fn family(&self, out:&mut String, name:&str, kind:&str, help:&str, samples:Vec<(String, String)>) {
+        let _ = writeln!(out, "# HELP {} {}", name, help);
+        let _ = writeln!(out, "# TYPE {} {}", name, kind);
+        for (labels, val) in samples {
+            if labels.is_empty() {
+                let _ = writeln!(out, "{}{{{}}} {}", name, self.labels, val);
+            } else {
+                let _ = writeln!(out, "{}{{{},{}}} {}", name, self.labels, labels, val);
+            }
+        }
+    }
+}
+
+
+fn load(counter:&AtomicU64) -> String {
+    counter.load(Ordering::Relaxed).to_string()
+}
+
+/// 标签值 转义: 反斜杠, 双引号 和 换行
+fn escape(s:&str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
@@ -0,0 +1,141 @@
+//! 扫描进度监视
+//! 发送线程 和 接收线程 将本线程的统计信息 以增量形式累加到 按线程 和 按网络接口 注册的统计信息,
+//! 监视线程 按固定间隔读取统计信息, 输出 发送数量, 发送速率, 响应数量, 命中率, 重复率, 丢包数量, 覆盖比例 和 预计剩余时间,
+//! 并可同时写入 状态文件(json lines) 和 指标文件(prometheus 文本格式)
+
+mod counters;
+mod status;
+mod metrics;
+
+use std::sync::mpsc::{channel, RecvTimeoutError, Sender};
+use std::thread;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+use crate::core::conf::set_conf::base_conf::BaseConf;
+use crate::core::conf::set_conf::sender_conf::SenderBaseConf;
+use crate::core::errors::SmapError;
+use crate::core::monitor::metrics::MetricsWriter;
+use crate::core::monitor::status::StatusWriter;
+
+pub use counters::{reset, senders, receivers, queues, queue_stats, Snapshot, SenderStats, ReceiverStats, QueueStats, SendCounter, RecvCounter, CaptureCounter};
+
+
+/// 一次输出时的 扫描进度
+pub struct Tick {
+    pub snapshot:Snapshot,
+
+    // 开始以来的秒数
+    pub elapsed:f64,
+    // 上次输出以来的 发送速率, 以及 全局目标速率(未限制时为空), 均为 每秒数据包数量
+    pub rate:f64,
+    pub tar_rate:Option<f64>,
+
+    // 探测目标总数, 已覆盖的比例, 预计剩余秒数, 目标总数未知时 均为空
+    pub tar_num:Option<u64>,
+    pub covered:Option<f64>,
+    pub eta:Option<f64>,
+}
+
+
+/// 由 相邻两次读取的统计信息 计算扫描进度
+struct Progress {
+    start:Instant,
+
+    // 上次读取的 时间 和 发送成功数量
+    last:(Instant, u64),
+
+    tar_num:Option<u64>,
+    tar_rate:Option<f64>,
+
+    // 续扫前 已处理的目标数量
+    resumed:u64,
+}
+
+impl Progress {
+
+    fn new(tar_num:Option<u64>, tar_rate:Option<f64>, resumed:u64) -> Self {
+        let start = Instant::now();
+        Self { start, last: (start, 0), tar_num, tar_rate, resumed }
+    }
+
+    fn tick(&mut self) -> Tick {
+
+        let now = Instant::now();
+        let snapshot = Snapshot::take();
+        let elapsed = now.duration_since(self.start).as_secs_f64();
+
+        // 当前发送速率: 上次读取以来的 发送成功数量 / 间隔时间
+        let interval = now.duration_since(self.last.0).as_secs_f64();
+        let rate = if interval > 0.0 { (snapshot.sent.saturating_sub(self.last.1) as f64) / interval } else { 0.0 };
+        self.last = (now, snapshot.sent);
+
+        // 覆盖比例 和 预计剩余时间 按 已处理的目标数量(包括续扫前) 计算, 其中 预计剩余时间 使用 本次开始以来的平均处理速率
+        let processed = snapshot.processed();
+        let covered = self.tar_num.map(|t| ratio(processed + self.resumed, t).min(1.0));
+        let eta = match self.tar_num {
+            Some(t) if processed > 0 => Some((t.saturating_sub(processed + self.resumed) as f64) * elapsed / (processed as f64)),
+            _ => None,
+        };
+
+        Tick { snapshot, elapsed, rate, tar_rate: self.tar_rate, tar_num: self.tar_num, covered, eta }
+    }
+}
+
+
+/// 监视线程, 释放时 停止监视线程 并输出最终进度
+pub struct Monitor {
+
+    // 释放该管道 即通知监视线程停止
+    stop:Option<Sender<()>>,
+    handle:Option<JoinHandle<()>>,
+}
+
+impl Monitor {
+
+    /// 启动监视线程, 未设置 输出间隔 时不启动. 模式名称 和 探测模块名称 用作指标标签, 续扫时 传入续扫前已处理的目标数量
+    pub fn start(base_conf:&BaseConf, sender_conf:&SenderBaseConf, mode:&str, probe:&str, resumed:u64) -> Result<Self, SmapError> {
+
+        let interval = match base_conf.status_interval {
+            Some(i) => Duration::from_secs(i),
+            None => return Ok(Self { stop: None, handle: None }),
+        };
+
+        let mut status = if base_conf.print_status {
+            Some(StatusWriter::new(&base_conf.status_file)?)
+        } else { None };
+        let metrics = match &base_conf.metrics_file {
+            Some(path) => Some(MetricsWriter::new(path, mode, probe)?),
+            None => None,
+        };
+        let mut progress = Progress::new(sender_conf.tar_num, sender_conf.total_rate(), resumed);
+
+        let (stop, stop_receiver) = channel::<()>();
+        let handle = thread::spawn(move || {
+            loop {
+                let stopped = !matches!(stop_receiver.recv_timeout(interval), Err(RecvTimeoutError::Timeout));
+
+                let tick = progress.tick();
+                if let Some(s) = &mut status { s.write(&tick) }
+                if let Some(m) = &metrics { m.write(&tick) }
+
+                if stopped { break }
+            }
+        });
+
+        Ok(Self { stop: Some(stop), handle: Some(handle) })
+    }
+}
+
+impl Drop for Monitor {
+    fn drop(&mut self) {
+        drop(self.stop.take());
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+
+fn ratio(a:u64, b:u64) -> f64 {
+    if b == 0 { 0.0 } else { (a as f64) / (b as f64) }
+}
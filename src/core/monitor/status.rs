@@ -0,0 +1,78 @@
+use std::fs::File;
+use std::io::Write;
+use serde_json::json;
+use crate::core::errors::OutputError;
+use crate::core::monitor::{ratio, Tick};
+use crate::SYS;
+
+
+/// 扫描进度 输出器: 向终端输出状态行, 设置了状态文件时 同时写入一行 json
+pub struct StatusWriter {
+    pattern:String,
+    file:Option<File>,
+}
+
+impl StatusWriter {
+
+    pub fn new(status_file:&Option<String>) -> Result<Self, OutputError> {
+
+        let file = match status_file {
+            Some(path) => Some(File::create(path).map_err(|_| OutputError::io("open_status_file_failed").with(path))?),
+            None => None,
+        };
+
+        Ok(Self {
+            pattern: SYS.get_info("print", "status_line"),
+            file,
+        })
+    }
+
+    pub fn write(&mut self, tick:&Tick) {
+
+        let s = &tick.snapshot;
+        let hit_rate = ratio(s.hits, s.sent);
+        let dup_rate = ratio(s.duplicates, s.responses);
+
+        eprintln!("{}", self.pattern
+            .replace("{elapsed}", &fmt_seconds(tick.elapsed))
+            .replace("{sent}", &s.sent.to_string())
+            .replace("{rate}", &format!("{:.0}", tick.rate))
+            .replace("{tar_rate}", &tick.tar_rate.map_or("-".to_string(), |r| format!("{:.0}", r)))
+            .replace("{responses}", &s.responses.to_string())
+            .replace("{hit_rate}", &format!("{:.2}", hit_rate * 100.0))
+            .replace("{dup_rate}", &format!("{:.2}", dup_rate * 100.0))
+            .replace("{drops}", &s.pcap_dropped.to_string())
+            .replace("{covered}", &tick.covered.map_or("-".to_string(), |c| format!("{:.2}", c * 100.0)))
+            .replace("{eta}", &tick.eta.map_or("-".to_string(), fmt_seconds)));
+
+        if let Some(file) = &mut self.file {
+            let line = json!({
+                "time": chrono::Local::now().to_rfc3339(),
+                "elapsed": tick.elapsed,
+                "sent": s.sent,
+                "send_failed": s.send_failed,
+                "blocked": s.blocked,
+                "send_rate": tick.rate,
+                "target_rate": tick.tar_rate,
+                "responses": s.responses,
+                "hits": s.hits,
+                "hit_rate": hit_rate,
+                "duplicates": s.duplicates,
+                "duplicate_rate": dup_rate,
+                "pcap_dropped": s.pcap_dropped,
+                "targets": tick.tar_num,
+                "covered": tick.covered,
+                "eta": tick.eta,
+            });
+            // 状态文件 只用于展示, 写入失败时 不影响扫描
+            let _ = writeln!(file, "{}", line).and_then(|_| file.flush());
+        }
+    }
+}
+
+
+/// 将秒数 格式化为 时:分:秒
+fn fmt_seconds(seconds:f64) -> String {
+    let seconds = seconds as u64;
+    format!("{:02}:{:02}:{:02}", seconds / 3600, (seconds / 60) % 60, seconds % 60)
+}
@@ -1,6 +1,40 @@
+use std::thread::JoinHandle;
+use crate::core::errors::{RunError, SmapError};
+use crate::core::sender::request_abort;
+use crate::core::monitor::RecvCounter;
+use crate::core::receiver::pcap::source::CaptureStats;
+
 pub mod pcap;
 
 
+/// 接收线程在 发送线程启动后 出错时使用: 通知所有发送线程尽快停止发送, 并返回该错误
+pub fn abort_senders<E:Into<SmapError>>(e:E) -> SmapError {
+    request_abort();
+    e.into()
+}
+
+/// 等待接收线程结束, 并取出其返回值
+pub fn join_receiver<T>(handle:JoinHandle<Result<T, SmapError>>) -> Result<T, SmapError> {
+    handle.join().map_err(|_| SmapError::from(RunError::thread("recv_thread_err")))?
+}
+
+/// 接收线程句柄, 用于在接收线程提前退出时取出其错误
+pub trait ReceiverHandle {
+    fn join_err(self) -> Option<SmapError>;
+}
+
+impl<T> ReceiverHandle for JoinHandle<Result<T, SmapError>> {
+    fn join_err(self) -> Option<SmapError> {
+        join_receiver(self).err()
+    }
+}
+
+/// 接收线程未能发出准备完毕消息时使用: 优先返回接收线程自身的错误
+pub fn recv_ready_failed<H:ReceiverHandle>(handle:H) -> SmapError {
+    handle.join_err().unwrap_or_else(|| RunError::channel("recv_ready_receive_failed").into())
+}
+
+
 pub struct ReceiverInfoV4{
 
     pub recv_validation_passed:u64,
@@ -9,6 +43,11 @@ pub struct ReceiverInfoV4{
     pub recv_success:u64,
     pub recv_failed:u64,
     pub recv_repeat:u64,
+
+    // libpcap 捕获统计
+    pub pcap_received:u64,
+    pub pcap_dropped:u64,
+    pub pcap_if_dropped:u64,
 }
 
 impl ReceiverInfoV4 {
@@ -20,9 +59,42 @@ impl ReceiverInfoV4 {
             recv_success: 0,
             recv_failed: 0,
             recv_repeat: 0,
+            pcap_received: 0,
+            pcap_dropped: 0,
+            pcap_if_dropped: 0,
         }
     }
 
+    /// 记录 接收结束时的 libpcap 捕获统计
+    pub fn set_capture_stats(&mut self, stats:CaptureStats) {
+        self.pcap_received = stats.received;
+        self.pcap_dropped = stats.dropped;
+        self.pcap_if_dropped = stats.if_dropped;
+    }
+
+    /// 将 统计信息 发布到 扫描进度计数器
+    pub fn publish(&self, counter:&mut RecvCounter) {
+        counter.publish(self.recv_validation_passed, self.recv_validation_failed, self.recv_success, self.recv_repeat);
+    }
+
+    /// 记录 格式化线程 统计的 探测成功 和 探测失败 的数量
+    pub fn add_processed(&mut self, (success, failed):(u64, u64)) {
+        self.recv_success += success;
+        self.recv_failed += failed;
+    }
+
+    /// 合并 另一接收线程的 统计信息(多线程接收)
+    pub fn merge(&mut self, other:&Self) {
+        self.recv_validation_passed += other.recv_validation_passed;
+        self.recv_validation_failed += other.recv_validation_failed;
+        self.recv_success += other.recv_success;
+        self.recv_failed += other.recv_failed;
+        self.recv_repeat += other.recv_repeat;
+        self.pcap_received += other.pcap_received;
+        self.pcap_dropped += other.pcap_dropped;
+        self.pcap_if_dropped += other.pcap_if_dropped;
+    }
+
 }
 
 
@@ -35,6 +107,11 @@ pub struct ReceiverInfoV6 {
     pub recv_success:u128,
     pub recv_failed:u64,
     pub recv_repeat:u128,
+
+    // libpcap 捕获统计
+    pub pcap_received:u64,
+    pub pcap_dropped:u64,
+    pub pcap_if_dropped:u64,
 }
 
 impl ReceiverInfoV6 {
@@ -45,8 +122,42 @@ impl ReceiverInfoV6 {
             recv_success: 0,
             recv_failed: 0,
             recv_repeat: 0,
+            pcap_received: 0,
+            pcap_dropped: 0,
+            pcap_if_dropped: 0,
         }
     }
 
+    /// 记录 接收结束时的 libpcap 捕获统计
+    pub fn set_capture_stats(&mut self, stats:CaptureStats) {
+        self.pcap_received = stats.received;
+        self.pcap_dropped = stats.dropped;
+        self.pcap_if_dropped = stats.if_dropped;
+    }
+
+    /// 将 统计信息 发布到 扫描进度计数器
+    pub fn publish(&self, counter:&mut RecvCounter) {
+        counter.publish(self.recv_validation_passed as u64, self.recv_validation_failed as u64,
+                        self.recv_success as u64, self.recv_repeat as u64);
+    }
+
+    /// 记录 格式化线程 统计的 探测成功 和 探测失败 的数量
+    pub fn add_processed(&mut self, (success, failed):(u64, u64)) {
+        self.recv_success += success as u128;
+        self.recv_failed += failed;
+    }
+
+    /// 合并 另一接收线程的 统计信息(多线程接收)
+    pub fn merge(&mut self, other:&Self) {
+        self.recv_validation_passed += other.recv_validation_passed;
+        self.recv_validation_failed += other.recv_validation_failed;
+        self.recv_success += other.recv_success;
+        self.recv_failed += other.recv_failed;
+        self.recv_repeat += other.recv_repeat;
+        self.pcap_received += other.pcap_received;
+        self.pcap_dropped += other.pcap_dropped;
+        self.pcap_if_dropped += other.pcap_if_dropped;
+    }
+
 }
 
@@ -5,10 +5,11 @@ use crate::tools::encryption_algorithm::aes::AesRand;
 
 impl PcapReceiver {
 
+    /// 处理数据包, 返回 数据包是否通过验证
     pub fn handle_packet_v6_alia(
         net_layer_header_and_data:&[u8], aes_rand:&AesRand, probe:&Box<dyn CodeProbeMethodV6>,
         hash_set:&mut AHashSet<u128>, recorder:&mut Vec<u8>, scan_flag:u8,
-    ){
+    ) -> bool {
 
         // ipv6 首部长度固定为 40字节  注意: 包含扩展首部
         let net_layer_data = &net_layer_header_and_data[40..];
@@ -16,7 +17,7 @@ impl PcapReceiver {
         if let Some((ipv6_addr, _, code_vec)) = probe.receive_packet_v6(net_layer_header_and_data, net_layer_data, aes_rand) {
 
             // 优先判断标识字段
-            if code_vec[0] != scan_flag { return }
+            if code_vec[0] != scan_flag { return false }
 
             // 去除重复的
             if !hash_set.contains(&ipv6_addr) {
@@ -27,6 +28,10 @@ impl PcapReceiver {
 
                 hash_set.insert(ipv6_addr);
             }
+
+            true
+        } else {
+            false
         }
     }
 
@@ -1,17 +1,20 @@
-use std::process::exit;
 use std::sync::Arc;
 use std::sync::mpsc::{Receiver, Sender};
 use chrono::Utc;
-use log::error;
 use crate::core::conf::set_conf::base_conf::BaseConf;
 use crate::core::conf::set_conf::receiver_conf::ReceiverBaseConf;
 use crate::core::receiver::pcap::{Codec, PcapReceiver};
+use crate::core::receiver::pcap::data_link::ip_version;
+use crate::core::receiver::pcap::packet_saver::PacketSaver;
+use crate::core::receiver::pcap::pipeline::{FormatStage, WriteStage};
+use crate::core::receiver::abort_senders;
 use crate::core::receiver::{ReceiverInfoV6, ReceiverInfoV4};
 use crate::modules::output_modules::OutputMod;
 use crate::modules::probe_modules::probe_mod_v4::ProbeModV4;
 use crate::modules::probe_modules::probe_mod_v6::ProbeModV6;
 use crate::SYS;
 use crate::tools::check_duplicates::hash_set::{HashSetV4Port, HashSetV6Port};
+use crate::core::errors::{RunError, SmapError};
 
 impl PcapReceiver {
 
@@ -23,7 +26,7 @@ impl PcapReceiver {
                   hash_set_v4_cap:usize, hash_set_v6_cap:usize,
                   // 接收准备完成管道: 用于在接收线程准备好进行接收时,向主线程发送允许执行发送线程的信号
                   // 接收关闭时间管道: 用于接收从主线程传递过来的接收线程关闭时间
-                  recv_ready_sender:Sender<bool>, recv_close_time_receiver:Receiver<i64>) -> (ReceiverInfoV4, ReceiverInfoV6) {
+                  recv_ready_sender:Sender<bool>, recv_close_time_receiver:Receiver<i64>) -> Result<(ReceiverInfoV4, ReceiverInfoV6), SmapError> {
 
         // 将 两种模块 捕获数据包的最大长度中的最大值 作为 捕获数据包的最大长度
         let snap_len = if probe_mod_v4.snap_len_v4 > probe_mod_v6.snap_len_v6 {
@@ -33,21 +36,31 @@ impl PcapReceiver {
         };
 
         // 初始化 探测模块
-        let probe_v4 = ProbeModV4::init(probe_mod_v4, sports.clone());
-        let probe_v6 = ProbeModV6::init(probe_mod_v6, sports);
+        let probe_v4 = ProbeModV4::init(probe_mod_v4.clone(), sports.clone())?;
+        let probe_v6 = ProbeModV6::init(probe_mod_v6.clone(), sports.clone())?;
 
         // 初始化 输出模块
-        let mut output_v4 = OutputMod::init(&receiver_conf.output_v4);
-        let mut output_v6 = OutputMod::init(&receiver_conf.output_v6);
+        let mut output_v4 = OutputMod::init(&receiver_conf.output_v4)?;
+        let mut output_v6 = OutputMod::init(&receiver_conf.output_v6)?;
 
         // 向输出文件输入首行
-        output_v4.writer_line(&probe_v4.print_header());
-        output_v6.writer_line(&probe_v6.print_header());
+        output_v4.writer_header(&probe_v4.print_header());
+        output_v6.writer_header(&probe_v6.print_header());
+
+        // 启动 写入线程 和 格式化线程, 本线程 只进行 捕获, 验证 和 查重
+        let interface = &base_conf.interface[interface_index].name_index.0;
+        let allow_no_succ:bool = receiver_conf.allow_no_succ;
+        let write_stage_v4 = WriteStage::spawn(output_v4, interface);
+        let write_stage_v6 = WriteStage::spawn(output_v6, interface);
+        let format_stage_v4 = FormatStage::spawn_v4(probe_mod_v4, sports.clone(), allow_no_succ,
+            write_stage_v4.lines(), interface, None);
+        let format_stage_v6 = FormatStage::spawn_v6(probe_mod_v6, sports, allow_no_succ,
+            write_stage_v6.lines(), interface, None);
 
         // 初始化 数据包捕获器
         let receiver = PcapReceiver::init(
-            &base_conf.interface[interface_index].name_index.0,
-            snap_len, &receiver_conf.filter);
+            &base_conf.interface[interface_index].name_index.0, &receiver_conf.capture_source,
+            snap_len, &receiver_conf.filter)?;
 
         // 接收线程信息统计
         let mut receiver_info_v4 = ReceiverInfoV4::new();
@@ -65,19 +78,19 @@ impl PcapReceiver {
         let mut send_running = true;               // 发送进程 正在运行标识
         let act_check_count:u32 = SYS.get_conf("conf", "active_check_count");
 
-        // 是否允许输出 验证成功但探测失败的结果
-        let allow_no_succ:bool = receiver_conf.allow_no_succ;
-
         // 向主线程发送 接收线程 准备完毕的 管道消息
         if let Err(_) = recv_ready_sender.send(true) {
-            error!("{}", SYS.get_info("err","recv_ready_send_failed"));
-            exit(1)
+            return Err(RunError::channel("recv_ready_send_failed").into())
         }
 
+        // 初始化 原始数据包保存器
+        let packet_saver = PacketSaver::new(&receiver_conf, &receiver).map_err(abort_senders)?;
+
         drop(base_conf);
         drop(receiver_conf);
 
-        for packet in receiver.active_capture.iter(Codec) {
+        let mut packets = receiver.active_capture.iter(Codec);
+        for packet in packets.by_ref() {
 
             match packet {
 
@@ -85,25 +98,25 @@ impl PcapReceiver {
 
                     // 头部信息, 包含时间戳, 数据包长度等信息
                     let header = packet.header;
-                    // 数据链路层报头
-                    let data_link_header = &packet.data[..receiver.data_link_len];
-                    // 网络层数据包
-                    let net_layer_data = &packet.data[receiver.data_link_len..];
+                    // 数据链路层报头 和 网络层数据包, 网络层数据的偏移 按数据包确定(如 vlan 标签)
+                    let (data_link_header, net_layer_data) = receiver.data_link.split(&packet.data);
 
-                    let ip_ver = net_layer_data[0] >> 4;
-                    if ip_ver == 4 {
+                    let ip_ver = ip_version(net_layer_data);
+                    let validation_passed = if ip_ver == 4 {
                         Self::handle_packet_v4_port(&header,data_link_header,
                                                net_layer_data, &aes_rand, &mut hash_set_v4,
-                                               &mut receiver_info_v4, allow_no_succ, &probe_v4, &mut output_v4);
-                    }
-                    if ip_ver == 6 {
+                                               &mut receiver_info_v4, &probe_v4, format_stage_v4.responses())
+                    } else if ip_ver == 6 {
                         // 如果是ipv6的数据包
                         Self::handle_packet_v6_port(&header,data_link_header,
                                                net_layer_data, &aes_rand, &mut hash_set_v6,
-                                               &mut receiver_info_v6, allow_no_succ, &probe_v6, &mut output_v6);
-                    }
+                                               &mut receiver_info_v6, &probe_v6, format_stage_v6.responses())
+                    } else { false };
                     // 如果是 其它特殊类型, 不进行处理
 
+                    // 保存原始数据包
+                    packet_saver.save(&packet.header, &packet.data, validation_passed).map_err(abort_senders)?;
+
                     {   // 计算终止条件
                         act_count += 1;
                         if act_count % act_check_count == 0 {
@@ -114,8 +127,6 @@ impl PcapReceiver {
                                     Ok(close_time) => {
 
                                         if  (header.ts.tv_sec as i64) >=  close_time {     // 检查有没有 达到 目标时间
-                                            output_v4.close_output();
-                                            output_v6.close_output();
                                             break                          // 如果达到就退出
                                         }
 
@@ -128,8 +139,6 @@ impl PcapReceiver {
                                 }
                             } else {
                                 if  (header.ts.tv_sec as i64) >=  tar_time {     // 检查有没有 达到 目标时间
-                                    output_v4.close_output();
-                                    output_v6.close_output();
                                     break                        // 如果达到就退出
                                 }
                             }
@@ -147,8 +156,6 @@ impl PcapReceiver {
 
                                 let now_time = Utc::now().timestamp();
                                 if  now_time >=  close_time {     // 检查有没有 达到 目标时间
-                                    output_v4.close_output();
-                                    output_v6.close_output();
                                     break                          // 如果达到就退出
                                 }
 
@@ -165,8 +172,6 @@ impl PcapReceiver {
                         // 计算当前时间戳
                         let now_time = Utc::now().timestamp();
                         if  now_time >=  tar_time {     // 检查有没有 达到 目标时间
-                            output_v4.close_output();
-                            output_v6.close_output();
                             break                          // 如果达到就退出
                         }
 
@@ -175,6 +180,17 @@ impl PcapReceiver {
             }
         }
 
-        (receiver_info_v4, receiver_info_v6)
+        // 等待 格式化线程 和 写入线程 处理完 队列中的响应
+        receiver_info_v4.add_processed(format_stage_v4.finish()?);
+        receiver_info_v6.add_processed(format_stage_v6.finish()?);
+        write_stage_v4.finish()?;
+        write_stage_v6.finish()?;
+
+        // 读取 捕获统计, 两种协议共用同一捕获
+        let capture_stats = packets.finish();
+        receiver_info_v4.set_capture_stats(capture_stats);
+        receiver_info_v6.set_capture_stats(capture_stats);
+
+        Ok((receiver_info_v4, receiver_info_v6))
     }
 }
\ No newline at end of file
@@ -0,0 +1,168 @@
+use std::sync::Arc;
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::thread;
+use chrono::Utc;
+use crate::core::conf::set_conf::base_conf::BaseConf;
+use crate::core::conf::set_conf::receiver_conf::ReceiverBaseConf;
+use crate::core::receiver::pcap::{Codec, PcapReceiver};
+use crate::core::receiver::pcap::data_link::ip_version;
+use crate::core::receiver::pcap::fanout::{stop, supervise};
+use crate::core::receiver::pcap::pipeline::{FormatStage, WriteStage};
+use crate::core::receiver::pcap::packet_saver::PacketSaver;
+use crate::core::receiver::abort_senders;
+use crate::core::receiver::ReceiverInfoV4;
+use crate::core::monitor::RecvCounter;
+use crate::core::checkpoint::RecvCheckpoint;
+use crate::modules::output_modules::OutputMod;
+use crate::modules::probe_modules::probe_mod_v4::ProbeModV4;
+use crate::SYS;
+use crate::tools::check_duplicates::{DuplicateCheckerV4, RecorderState};
+use crate::core::errors::{RunError, SmapError};
+use crate::tools::encryption_algorithm::aes::AesRand;
+
+impl PcapReceiver {
+
+    /// 多线程接收: 每个捕获线程 读取 PACKET_FANOUT 组中的一个捕获, 共用 查重器 和 写入线程
+    pub fn run_v4_fanout<B:DuplicateCheckerV4 + RecorderState + Clone + Send + 'static>(
+               interface_index:usize,
+               base_conf:Arc<BaseConf>, receiver_conf:Arc<ReceiverBaseConf>,
+               probe_mod:Arc<ProbeModV4>, bit_map:B,
+               recv_ready_sender:Sender<bool>, recv_close_time_receiver:Receiver<i64>,
+               checkpoint:RecvCheckpoint) -> Result<ReceiverInfoV4, SmapError> {
+
+        let interface = base_conf.interface[interface_index].name_index.0.clone();
+
+        // 初始化 数据包捕获器, 每个捕获线程 一个
+        let receivers = PcapReceiver::init_fanout(&interface, &receiver_conf.capture_source,
+            probe_mod.snap_len_v4, &receiver_conf.filter, receiver_conf.recv_thread_num)?;
+
+        // 初始化 输出模块, 向输出文件输入首行, 并启动 写入线程
+        let mut output = OutputMod::init(&receiver_conf.output_v4)?;
+        output.writer_header(&ProbeModV4::init(probe_mod.clone(), vec![])?.print_header());
+        let write_stage = WriteStage::spawn(output, &interface);
+
+        // 启动 捕获线程
+        let mut close_time_senders = Vec::with_capacity(receivers.len());
+        let mut handles = Vec::with_capacity(receivers.len());
+        for receiver in receivers {
+            let (close_time_sender, close_time_receiver) = channel();
+            close_time_senders.push(close_time_sender);
+
+            let receiver_conf = receiver_conf.clone();
+            let probe_mod = probe_mod.clone();
+            let bit_map = bit_map.clone();
+            let aes_rand = base_conf.aes_rand.clone();
+            // 每个捕获线程 使用一个格式化线程
+            let format_stage = FormatStage::spawn_v4(probe_mod.clone(), vec![], receiver_conf.allow_no_succ,
+                write_stage.lines(), &interface, Some(RecvCounter::new(&interface)));
+            let interface = interface.clone();
+            handles.push(thread::spawn(move || {
+                Self::fanout_worker_v4(receiver, receiver_conf, probe_mod, bit_map, aes_rand,
+                                       format_stage, &interface, close_time_receiver)
+            }));
+        }
+        drop(base_conf);
+        drop(receiver_conf);
+
+        // 向主线程发送 接收线程 准备完毕的 管道消息
+        if recv_ready_sender.send(true).is_err() {
+            // 停止 已启动的 捕获线程 和 写入线程
+            stop(handles, close_time_senders);
+            let _ = write_stage.finish();
+            return Err(RunError::channel("recv_ready_send_failed").into())
+        }
+
+        let infos = supervise(handles, recv_close_time_receiver, close_time_senders, &checkpoint, &bit_map)?;
+
+        // 等待 写入线程 写完 队列中的输出行
+        write_stage.finish()?;
+
+        let mut receiver_info = ReceiverInfoV4::new();
+        for info in infos {
+            receiver_info.merge(&info);
+        }
+
+        checkpoint.save(&bit_map);
+
+        Ok(receiver_info)
+    }
+
+
+    /// 捕获线程: 与 run_v4 的接收循环相同
+    fn fanout_worker_v4<B:DuplicateCheckerV4>(receiver:PcapReceiver, receiver_conf:Arc<ReceiverBaseConf>,
+                                             probe_mod:Arc<ProbeModV4>, mut bit_map:B, aes_rand:AesRand,
+                                             format_stage:FormatStage<u32>, interface:&str,
+                                             recv_close_time_receiver:Receiver<i64>) -> Result<ReceiverInfoV4, SmapError> {
+
+        let probe = ProbeModV4::init(probe_mod, vec![])?;
+
+        let mut receiver_info = ReceiverInfoV4::new();
+        // 各捕获线程 分别向 同一网络接口的计数器 发布增量
+        let mut recv_counter = RecvCounter::new(interface);
+
+        let mut tar_time = i64::MAX;
+        let mut act_count:u32 = 0;
+        let mut send_running = true;
+        let act_check_count:u32 = SYS.get_conf("conf", "active_check_count");
+
+        let packet_saver = PacketSaver::new(&receiver_conf, &receiver).map_err(abort_senders)?;
+        drop(receiver_conf);
+
+        let mut packets = receiver.active_capture.iter(Codec);
+        for packet in packets.by_ref() {
+
+            match packet {
+
+                Ok(packet) => {
+
+                    let header = packet.header;
+                    let (data_link_header, net_layer_data) = receiver.data_link.split(&packet.data);
+
+                    let ip_ver = ip_version(net_layer_data);
+                    let validation_passed = if ip_ver == 4 {
+                        Self::handle_packet_v4(&header, data_link_header, net_layer_data, &aes_rand,
+                                               &mut bit_map, &mut receiver_info, &probe, format_stage.responses())
+                    } else { false };
+
+                    packet_saver.save(&packet.header, &packet.data, validation_passed).map_err(abort_senders)?;
+
+                    act_count += 1;
+                    if act_count.is_multiple_of(act_check_count) {
+                        if send_running {
+                            if let Ok(close_time) = recv_close_time_receiver.try_recv() {
+                                if header.ts.tv_sec >= close_time { break }
+                                tar_time = close_time;
+                                send_running = false;
+                            }
+                        } else if header.ts.tv_sec >= tar_time { break }
+
+                        receiver_info.publish(&mut recv_counter);
+                        act_count = 0;
+                    }
+                },
+
+                Err(_) => {         // 超时
+
+                    receiver_info.publish(&mut recv_counter);
+
+                    if send_running {
+                        if let Ok(close_time) = recv_close_time_receiver.try_recv() {
+                            if Utc::now().timestamp() >= close_time { break }
+                            tar_time = close_time;
+                            send_running = false;
+                        }
+                    } else if Utc::now().timestamp() >= tar_time { break }
+                }
+            }
+        }
+
+        let processed = format_stage.finish()?;
+        receiver_info.publish(&mut recv_counter);
+
+        // 读取 捕获统计
+        receiver_info.set_capture_stats(packets.finish());
+        receiver_info.add_processed(processed);
+
+        Ok(receiver_info)
+    }
+}
@@ -3,8 +3,11 @@
 
 
 pub mod run;
+pub mod fanout;
 pub mod handle_packet;
 
+#[cfg(test)]
+mod tests;
 
 
 
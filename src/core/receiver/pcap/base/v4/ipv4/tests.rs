@@ -0,0 +1,96 @@
+use std::sync::Arc;
+use std::sync::mpsc::channel;
+use clap::Parser;
+use crate::core::checkpoint::RecvCheckpoint;
+use crate::core::conf::args::Args;
+use crate::core::conf::modules_config::ModuleConf;
+use crate::core::conf::set_conf::base_conf::BaseConf;
+use crate::core::conf::set_conf::receiver_conf::ReceiverBaseConf;
+use crate::core::conf::set_conf::ScanConf;
+use crate::core::conf::tools::args_parse::ip::ipv4::parse_ipv4_cycle_group;
+use crate::core::conf::tools::args_parse::target_iterator::TarIterBaseConf;
+use crate::core::conf::tools::net::interface::InterfaceConf;
+use crate::core::receiver::pcap::PcapReceiver;
+use crate::modules::probe_modules::probe_mod_v4::ProbeModV4;
+use crate::tools::check_duplicates::bit_map::BitMapV4;
+use crate::tools::encryption_algorithm::aes::AesRand;
+use crate::tools::net_handle::net_interface::mac_addr::MacAddress;
+
+
+// 由 虚拟网络 生成的回放文件(种子为 1, 目标为 10.0.0.0/30):
+// 10.0.0.2 的回显应答, 10.0.0.1 的回显应答, 10.0.0.1 的重复应答, 标识符被篡改的 10.0.0.2 的回显应答
+const REPLAY_FILE:&str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/replay_icmp_v4.pcap");
+const SEED:u64 = 1;
+
+
+/// 回放 不需要网络接口, 接口名称 只用于统计
+fn replay_base_conf() -> BaseConf {
+    BaseConf {
+        interface: vec![InterfaceConf {
+            name_index: ("replay".to_string(), 0),
+            gateway_mac: MacAddress::new([0; 6]),
+            local_mac: MacAddress::new([0; 6]),
+            local_ipv4: vec![],
+            local_ipv4_net: vec![],
+            local_ipv6: vec![],
+            local_ipv6_net: vec![],
+            receive_speed: None,
+            transmit_speed: None,
+        }],
+        aes_rand: AesRand::new(Some(SEED)),
+        summary_file: None,
+        status_interval: None,
+        print_status: false,
+        status_file: None,
+        metrics_file: None,
+        checkpoint_file: None,
+        checkpoint_interval: 60,
+        resume_file: None,
+    }
+}
+
+#[test]
+fn replay_icmp_v4() {
+
+    let output_file = std::env::temp_dir().join(format!("smap_replay_icmp_v4_{}.csv", std::process::id()));
+    let args = Args::parse_from(["smap", "-m", "c4", "-t", "10.0.0.0/30", "--probe_v4", "icmp_v4", "--seed", &SEED.to_string(),
+        "--replay_file", REPLAY_FILE, "--output_file_v4", output_file.to_str().unwrap()]);
+
+    // 与 c4 模式 相同的方式 构造 探测模块 和 位图
+    let (start_ip, end_ip, tar_ip_num) = parse_ipv4_cycle_group(&TarIterBaseConf::parse_tar_ip(&args.tar_ips).unwrap()).unwrap();
+    let tar_ports = TarIterBaseConf::parse_tar_port(&args.tar_ports, "default_ports").unwrap();
+
+    let base_conf = replay_base_conf();
+    let module_conf = ModuleConf::new_from_vec_args(&args.custom_args, vec![]).unwrap();
+    let probe = ProbeModV4::new("icmp_v4", module_conf, &tar_ports, SEED, &args.fields).unwrap();
+    let receiver_conf = ReceiverBaseConf::new(&ScanConf::from_args(&args), &base_conf, vec![probe.filter_v4.clone()]).unwrap();
+
+    let bit_map = BitMapV4::new(start_ip, end_ip, tar_ip_num).unwrap();
+
+    let (recv_ready_sender, recv_ready_receiver) = channel();
+    let (recv_close_time_sender, recv_close_time_receiver) = channel();
+    // 回放文件 读取完毕后 立即关闭
+    recv_close_time_sender.send(0).unwrap();
+
+    let info = PcapReceiver::run_v4(0, Arc::new(base_conf), Arc::new(receiver_conf), Arc::new(probe),
+                                    bit_map, recv_ready_sender, recv_close_time_receiver, RecvCheckpoint::none()).unwrap();
+    assert!(recv_ready_receiver.recv().unwrap());
+
+    let output = std::fs::read_to_string(&output_file).unwrap();
+    let _ = std::fs::remove_file(&output_file);
+
+    let rows:Vec<&str> = output.lines().collect();
+    assert_eq!(rows, vec![
+        "source_addr,outer_source_addr,icmp_type,icmp_code,identifier,sequence_num,classification",
+        "10.0.0.2,,0,0,43120,65306,echo_reply",
+        "10.0.0.1,,0,0,56747,400,echo_reply",
+    ]);
+
+    assert_eq!(info.recv_validation_passed, 3);
+    assert_eq!(info.recv_validation_failed, 1);
+    assert_eq!(info.recv_repeat, 1);
+    assert_eq!(info.recv_success, 2);
+    assert_eq!(info.recv_failed, 0);
+    // 回放文件 没有捕获统计
+    assert_eq!(info.pcap_received, 0);
+}
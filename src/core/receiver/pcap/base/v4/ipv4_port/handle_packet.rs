@@ -2,8 +2,8 @@
 
 use pcap::PacketHeader;
 use crate::core::receiver::pcap::PcapReceiver;
+use crate::core::receiver::pcap::pipeline::{ResponseV4, StageSender};
 use crate::core::receiver::ReceiverInfoV4;
-use crate::modules::output_modules::OutputMethod;
 use crate::modules::probe_modules::probe_mod_v4::ProbeMethodV4;
 use crate::tools::check_duplicates::DuplicateCheckerV4Port;
 use crate::tools::encryption_algorithm::aes::AesRand;
@@ -12,10 +12,11 @@ use crate::tools::net_handle::packet::v4::packet_v4_u32::Ipv4PacketU32;
 
 impl PcapReceiver  {
 
+    /// 处理数据包, 返回 数据包是否通过验证
     #[inline]
     pub fn handle_packet_v4_port<B:DuplicateCheckerV4Port>(header:&PacketHeader, data_link_header:&[u8], net_layer_header_and_data:&[u8],
-                            aes_rand:&AesRand, bit_map:&mut B, receiver_info:&mut ReceiverInfoV4, allow_no_succ:bool,
-                            probe:&Box<dyn ProbeMethodV4>, output:&mut Box<dyn OutputMethod>){
+                            aes_rand:&AesRand, bit_map:&mut B, receiver_info:&mut ReceiverInfoV4,
+                            probe:&Box<dyn ProbeMethodV4>, responses:&StageSender<ResponseV4>) -> bool {
 
         // ipv4 数据包头部
         let v4_header = Ipv4PacketU32::parse_ipv4_packet(net_layer_header_and_data);
@@ -33,7 +34,7 @@ impl PcapReceiver  {
         } else {
             // 如果验证失败, 直接退出
             receiver_info.recv_validation_failed += 1;
-            return
+            return false
         }
 
         // 如果有内层源地址, 源地址为内层源地址; 如果没有内层源地址, 源地址为数据包的源地址
@@ -44,25 +45,8 @@ impl PcapReceiver  {
             // 同一端口对 只有一次输出
             // 注意:所有icmp错误报文均被标记为 0端口, 所有运输层以下协议也均标记为 0端口
 
-            let (is_successful, output_line_data) = probe.process_packet_v4(header, data_link_header,
-                                                                            &v4_header, &net_layer_data, inner_src_ip);
-
-            if is_successful {
-                // 如果 探测成功
-
-                if output_line_data.len() != 0 {
-                    output.writer_line(&output_line_data);
-                }
-
-                receiver_info.recv_success += 1;
-            } else {
-
-                if allow_no_succ && output_line_data.len() != 0 {
-                    output.writer_line(&output_line_data);
-                }
-
-                receiver_info.recv_failed += 1;
-            }
+            // 首个响应 交给格式化线程 生成输出行
+            responses.send(ResponseV4::new(header, data_link_header, net_layer_header_and_data, inner_src_ip));
 
             // 为 该源地址 做重复标记
             bit_map.set(src_ip, sport);
@@ -70,5 +54,6 @@ impl PcapReceiver  {
             receiver_info.recv_repeat += 1;
         }
 
+        true
     }
 }
\ No newline at end of file
@@ -1,7 +1,7 @@
 use pcap::PacketHeader;
 use crate::core::receiver::pcap::PcapReceiver;
+use crate::core::receiver::pcap::pipeline::{ResponseV6, StageSender};
 use crate::core::receiver::{ReceiverInfoV6};
-use crate::modules::output_modules::OutputMethod;
 use crate::modules::probe_modules::probe_mod_v6::ProbeMethodV6;
 use crate::tools::check_duplicates::DuplicateCheckerV6;
 use crate::tools::encryption_algorithm::aes::AesRand;
@@ -10,10 +10,11 @@ use crate::tools::net_handle::packet::v6::packet_v6_u128::Ipv6PacketU128;
 
 impl PcapReceiver  {
 
+    /// 处理数据包, 返回 数据包是否通过验证
     #[inline]
     pub fn handle_packet_v6<B:DuplicateCheckerV6>(header:&PacketHeader, data_link_header:&[u8], net_layer_header_and_data:&[u8],
-                            aes_rand:&AesRand, bit_map:&mut B, receiver_info:&mut ReceiverInfoV6, allow_no_succ:bool,
-                            probe:&Box<dyn ProbeMethodV6>, output:&mut Box<dyn OutputMethod>){
+                            aes_rand:&AesRand, bit_map:&mut B, receiver_info:&mut ReceiverInfoV6,
+                            probe:&Box<dyn ProbeMethodV6>, responses:&StageSender<ResponseV6>) -> bool {
 
         // ipv6 数据包头部
         let v6_header = Ipv6PacketU128::parse_ipv6_packet(net_layer_header_and_data);
@@ -30,39 +31,21 @@ impl PcapReceiver  {
         } else {
             // 如果验证失败, 直接退出
             receiver_info.recv_validation_failed += 1;
-            return;
+            return false
         }
 
         // 如果有内层源地址, 源地址为内层源地址; 如果没有内层源地址, 源地址为数据包的源地址
         let src_ip = inner_src_ip.unwrap_or_else(|| v6_header.source_addr);
 
-        if bit_map.not_marked_and_valid(src_ip) {
-            // 对 源地址(如果存在内层源地址, 则为内层源ip) 进行重复检查
+        if bit_map.mark(src_ip) {
+            // 对 源地址(如果存在内层源地址, 则为内层源ip) 进行重复检查, 并做重复标记
 
-            let (is_successful, output_line_data) = probe.process_packet_v6(header, data_link_header,
-                                                                            &v6_header, &net_layer_data, inner_src_ip);
-
-            if is_successful {
-                // 如果 探测成功
-
-                if output_line_data.len() != 0 {
-                    output.writer_line(&output_line_data);
-                }
-
-                receiver_info.recv_success += 1;
-            } else {
-
-                if allow_no_succ && output_line_data.len() != 0 {
-                    output.writer_line(&output_line_data);
-                }
-
-                receiver_info.recv_failed += 1;
-            }
-
-            // 为 该源地址 做重复标记
-            bit_map.set(src_ip);
+            // 首个响应 交给格式化线程 生成输出行
+            responses.send(ResponseV6::new(header, data_link_header, net_layer_header_and_data, inner_src_ip));
         } else {
             receiver_info.recv_repeat += 1;
         }
+
+        true
     }
 }
\ No newline at end of file
@@ -1,2 +1,3 @@
 pub mod run;
+pub mod fanout;
 pub mod handle_packet;
\ No newline at end of file
@@ -0,0 +1,113 @@
+//! 数据链路层解码
+//! 支持 以太网(可带 一层 或 两层 802.1Q / 802.1ad 标签), linux cooked capture(SLL 和 SLL2) 和 原始ip,
+//! 网络层数据的偏移 按数据包确定, 数据链路层报头(包括 vlan 标签) 仍交给探测模块
+
+use pcap::Linktype;
+
+#[cfg(test)]
+mod tests;
+
+
+// 802.1Q, 802.1ad, 以及 早期的 QinQ 实现使用的 协议类型
+const ETHERTYPE_VLAN:[u16; 3] = [0x8100, 0x88a8, 0x9100];
+// 每个 vlan 标签: 标签控制信息(2字节) + 内层协议类型(2字节)
+const VLAN_TAG_LEN:usize = 4;
+const MAX_VLAN_TAGS:usize = 2;
+
+// libpcap 中 原始ip 的 DLT 编号 因平台而异, 写入文件时 应使用 LINKTYPE_RAW
+#[cfg(not(target_os = "openbsd"))]
+const DLT_RAW:i32 = 12;
+#[cfg(target_os = "openbsd")]
+const DLT_RAW:i32 = 14;
+
+/// 相对以太网报头(14字节) 可能增加的最大长度: SLL2 报头(20字节) 和 两层 vlan 标签
+pub const MAX_EXTRA_HEADER_LEN:usize = (20 - 14) + VLAN_TAG_LEN * MAX_VLAN_TAGS;
+
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DataLink {
+    Ethernet,
+    // linux cooked capture, 如 在 any 接口 或 部分隧道接口上 捕获
+    LinuxSll,
+    LinuxSll2,
+    Raw,
+}
+
+impl DataLink {
+
+    /// linktype 为 libpcap 返回的 DLT 编号
+    pub fn from_linktype(linktype:Linktype) -> Option<Self> {
+        match dlt_to_linktype(linktype) {
+            Linktype::ETHERNET => Some(Self::Ethernet),
+            Linktype::LINUX_SLL => Some(Self::LinuxSll),
+            Linktype::LINUX_SLL2 => Some(Self::LinuxSll2),
+            Linktype::RAW => Some(Self::Raw),
+            _ => None,
+        }
+    }
+
+    /// 固定报头长度, 以及 协议类型字段 在报头中的位置
+    fn fixed_header(&self) -> (usize, Option<usize>) {
+        match self {
+            // 目的地址(6) 源地址(6) 协议类型(2)
+            Self::Ethernet => (14, Some(12)),
+            // 包类型(2) 硬件类型(2) 地址长度(2) 地址(8) 协议类型(2)
+            Self::LinuxSll => (16, Some(14)),
+            // 协议类型(2) 保留(2) 接口索引(4) 硬件类型(2) 包类型(1) 地址长度(1) 地址(8)
+            Self::LinuxSll2 => (20, Some(0)),
+            Self::Raw => (0, None),
+        }
+    }
+
+    /// 网络层数据 在帧中的偏移, 跳过 最多两层 vlan 标签. 帧过短时 返回空
+    pub fn net_offset(&self, frame:&[u8]) -> Option<usize> {
+
+        let (mut offset, proto_pos) = self.fixed_header();
+        let Some(proto_pos) = proto_pos else { return Some(0) };
+        if frame.len() < offset { return None }
+
+        let mut proto = u16::from_be_bytes([frame[proto_pos], frame[proto_pos + 1]]);
+        for _ in 0..MAX_VLAN_TAGS {
+            if !ETHERTYPE_VLAN.contains(&proto) { break }
+            if frame.len() < offset + VLAN_TAG_LEN { return None }
+
+            proto = u16::from_be_bytes([frame[offset + 2], frame[offset + 3]]);
+            offset += VLAN_TAG_LEN;
+        }
+        Some(offset)
+    }
+
+    /// 用户设置的过滤规则 只匹配 不带 vlan 标签的以太网帧, 以太网中 扩展为 同时匹配 带 一层 或 两层 vlan 标签的帧
+    pub fn wrap_filter(&self, filter:&str) -> String {
+        match self {
+            Self::Ethernet => format!("({f}) or (vlan and ({f})) or (vlan and vlan and ({f}))", f = filter),
+            _ => filter.to_string(),
+        }
+    }
+
+    /// 将帧 分割为 数据链路层报头 和 网络层数据包, 帧过短时 网络层数据包 为空
+    #[inline]
+    pub fn split<'a>(&self, frame:&'a [u8]) -> (&'a [u8], &'a [u8]) {
+        match self.net_offset(frame) {
+            Some(offset) => frame.split_at(offset),
+            None => (frame, &[]),
+        }
+    }
+}
+
+
+/// 将 libpcap 返回的 DLT 编号 转换为 pcap / pcapng 文件中使用的 LINKTYPE 编号
+/// 二者 只在 少数平台相关的类型(如 原始ip) 上不同
+pub fn dlt_to_linktype(dlt:Linktype) -> Linktype {
+    match dlt.0 {
+        DLT_RAW => Linktype::RAW,
+        _ => dlt,
+    }
+}
+
+
+/// 网络层数据包的 ip 版本, 网络层数据包 为空时 返回 0
+#[inline]
+pub fn ip_version(net_layer_data:&[u8]) -> u8 {
+    net_layer_data.first().map_or(0, |b| b >> 4)
+}
@@ -0,0 +1,129 @@
+use pcap::Linktype;
+use super::{dlt_to_linktype, ip_version, DataLink, DLT_RAW};
+
+
+// 最小的 ipv4 报头起始部分: 版本 4, 报头长度 5
+const IPV4:[u8; 4] = [0x45, 0x00, 0x00, 0x14];
+
+
+/// 以太网帧: 目的地址 源地址, 各 vlan 标签, 协议类型, 网络层数据
+fn ethernet(tags:&[u16], payload:&[u8]) -> Vec<u8> {
+    let mut frame = vec![0xaa; 12];
+    for tpid in tags {
+        frame.extend_from_slice(&tpid.to_be_bytes());
+        // 标签控制信息: vlan 100
+        frame.extend_from_slice(&100u16.to_be_bytes());
+    }
+    frame.extend_from_slice(&0x0800u16.to_be_bytes());
+    frame.extend_from_slice(payload);
+    frame
+}
+
+#[test]
+fn split_ethernet() {
+    let frame = ethernet(&[], &IPV4);
+    let (header, net) = DataLink::Ethernet.split(&frame);
+
+    assert_eq!(header.len(), 14);
+    assert_eq!(net, &IPV4);
+    assert_eq!(ip_version(net), 4);
+}
+
+#[test]
+fn split_single_tagged() {
+    let frame = ethernet(&[0x8100], &IPV4);
+    let (header, net) = DataLink::Ethernet.split(&frame);
+
+    assert_eq!(header.len(), 18);
+    assert_eq!(net, &IPV4);
+}
+
+#[test]
+fn split_double_tagged() {
+    for outer in [0x88a8, 0x9100] {
+        let frame = ethernet(&[outer, 0x8100], &IPV4);
+        let (header, net) = DataLink::Ethernet.split(&frame);
+
+        assert_eq!(header.len(), 22);
+        assert_eq!(net, &IPV4);
+    }
+}
+
+#[test]
+fn split_linux_sll() {
+    // 包类型 硬件类型 地址长度 地址(8) 协议类型
+    let mut frame = vec![0; 14];
+    frame.extend_from_slice(&0x0800u16.to_be_bytes());
+    frame.extend_from_slice(&IPV4);
+    let (header, net) = DataLink::LinuxSll.split(&frame);
+
+    assert_eq!(header.len(), 16);
+    assert_eq!(net, &IPV4);
+
+    // cooked capture 中的 vlan 标签 紧跟在 报头之后
+    let mut frame = vec![0; 14];
+    frame.extend_from_slice(&0x8100u16.to_be_bytes());
+    frame.extend_from_slice(&[0x00, 0x64, 0x08, 0x00]);
+    frame.extend_from_slice(&IPV4);
+    let (header, net) = DataLink::LinuxSll.split(&frame);
+
+    assert_eq!(header.len(), 20);
+    assert_eq!(net, &IPV4);
+}
+
+#[test]
+fn split_linux_sll2() {
+    // 协议类型 在 报头起始位置
+    let mut frame = 0x86ddu16.to_be_bytes().to_vec();
+    frame.extend_from_slice(&[0; 18]);
+    frame.extend_from_slice(&[0x60, 0x00, 0x00, 0x00]);
+    let (header, net) = DataLink::LinuxSll2.split(&frame);
+
+    assert_eq!(header.len(), 20);
+    assert_eq!(ip_version(net), 6);
+}
+
+#[test]
+fn split_raw() {
+    let (header, net) = DataLink::Raw.split(&IPV4);
+
+    assert!(header.is_empty());
+    assert_eq!(net, &IPV4);
+}
+
+#[test]
+fn split_truncated() {
+    // 短于 以太网报头
+    let frame = [0xaa; 10];
+    assert_eq!(DataLink::Ethernet.split(&frame), (&frame[..], &[][..]));
+
+    // vlan 标签 不完整
+    let frame = ethernet(&[0x8100], &[]);
+    let frame = &frame[..16];
+    assert_eq!(DataLink::Ethernet.split(frame), (frame, &[][..]));
+
+    // 短于 SLL2 报头
+    let frame = [0x08, 0x00, 0, 0, 0, 0];
+    assert_eq!(DataLink::LinuxSll2.split(&frame), (&frame[..], &[][..]));
+
+    // 只有报头, 没有网络层数据
+    let frame = ethernet(&[], &[]);
+    let (header, net) = DataLink::Ethernet.split(&frame);
+    assert_eq!(header.len(), 14);
+    assert_eq!(ip_version(net), 0);
+}
+
+#[test]
+fn wrap_filter() {
+    assert_eq!(DataLink::Ethernet.wrap_filter("icmp"), "(icmp) or (vlan and (icmp)) or (vlan and vlan and (icmp))");
+    assert_eq!(DataLink::LinuxSll.wrap_filter("icmp"), "icmp");
+    assert_eq!(DataLink::Raw.wrap_filter("icmp"), "icmp");
+}
+
+#[test]
+fn dlt_raw_to_linktype() {
+    assert_eq!(dlt_to_linktype(Linktype(DLT_RAW)), Linktype::RAW);
+    assert_eq!(dlt_to_linktype(Linktype::RAW), Linktype::RAW);
+    assert_eq!(dlt_to_linktype(Linktype::ETHERNET), Linktype::ETHERNET);
+    assert_eq!(DataLink::from_linktype(Linktype(DLT_RAW)), Some(DataLink::Raw));
+}
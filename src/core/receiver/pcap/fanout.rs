@@ -0,0 +1,50 @@
+//! 多线程接收
+//! 多个捕获线程 各自读取一个加入同一 PACKET_FANOUT 组的捕获, 独立完成 验证 和 查重, 并各自使用一个格式化线程,
+//! 查重器 由各捕获线程 共用(原子位图), 输出行 由 共用的写入线程 写入输出模块.
+//! 原接收线程 负责 转发接收关闭时间 和 导出查重器状态(断点续扫)
+
+use std::sync::mpsc::{Receiver, Sender};
+use std::thread;
+use std::thread::JoinHandle;
+use std::time::Duration;
+use crate::core::checkpoint::RecvCheckpoint;
+use crate::core::errors::SmapError;
+use crate::core::receiver::join_receiver;
+use crate::SYS;
+use crate::tools::check_duplicates::RecorderState;
+
+
+/// 等待 所有捕获线程 结束, 任一捕获线程出错时 返回该错误
+/// 收到主线程传递的 接收关闭时间 后, 转发给 所有捕获线程, 并在断点线程请求时 导出共用查重器的状态
+pub fn supervise<T, R:RecorderState>(handles:Vec<JoinHandle<Result<T, SmapError>>>,
+                                     recv_close_time_receiver:Receiver<i64>, close_time_senders:Vec<Sender<i64>>,
+                                     checkpoint:&RecvCheckpoint, recorder:&R) -> Result<Vec<T>, SmapError> {
+
+    let timeout = Duration::from_millis(SYS.get_conf::<u64>("conf", "capture_timeout"));
+    let mut close_time_senders = Some(close_time_senders);
+
+    while !handles.iter().all(|h| h.is_finished()) {
+        thread::sleep(timeout);
+
+        if close_time_senders.is_some() {
+            if let Ok(close_time) = recv_close_time_receiver.try_recv() {
+                for sender in close_time_senders.take().unwrap() {
+                    let _ = sender.send(close_time);
+                }
+            }
+        }
+        checkpoint.check(recorder);
+    }
+
+    handles.into_iter().map(join_receiver).collect()
+}
+
+/// 接收线程 无法继续时 停止 所有捕获线程: 发送 已经过去的接收关闭时间, 并等待 各捕获线程结束
+pub fn stop<T>(handles:Vec<JoinHandle<Result<T, SmapError>>>, close_time_senders:Vec<Sender<i64>>) {
+    for sender in close_time_senders {
+        let _ = sender.send(i64::MIN);
+    }
+    for handle in handles {
+        let _ = handle.join();
+    }
+}
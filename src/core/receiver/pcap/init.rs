@@ -1,18 +1,129 @@
-use std::process::exit;
-use log::error;
 use pcap::{Capture, Linktype};
 use crate::core::receiver::pcap::PcapReceiver;
+use crate::core::receiver::pcap::data_link::{DataLink, MAX_EXTRA_HEADER_LEN};
+use crate::core::receiver::pcap::source::{CaptureSource, PacketSource};
+use crate::core::sys::virtual_net::VirtualNet;
+#[cfg(target_os = "linux")]
+use crate::core::sys::xdp::XdpReceiver;
+#[cfg(target_os = "linux")]
+use log::info;
+use log::warn;
+use std::sync::mpsc::channel;
+#[cfg(target_os = "linux")]
+use std::sync::atomic::{AtomicU16, Ordering};
 use crate::SYS;
+use crate::core::errors::CaptureError;
 
 impl PcapReceiver {
 
-    pub fn init(interface_name:&str, probe_mod_snap_len:usize, filter:&Option<String>) -> Self {
+    pub fn init(interface_name:&str, capture_source:&CaptureSource, probe_mod_snap_len:usize, filter:&Option<String>) -> Result<Self, CaptureError> {
+
+        let active_capture = match capture_source {
+            CaptureSource::Device => Self::open_device(interface_name, probe_mod_snap_len)?,
+            // 从 离线捕获文件 回放, 而不是从网络接口捕获
+            CaptureSource::Replay(path) => Self::open_offline(path)?,
+            // 从 虚拟网络 接收响应帧
+            CaptureSource::Virtual(net) => Self::open_virtual(net, probe_mod_snap_len)?,
+            // 从 网络接口的 AF_XDP 套接字 接收, 打开失败时 退回 libpcap 捕获
+            #[cfg(target_os = "linux")]
+            CaptureSource::Xdp(min_port, max_port) => match Self::open_xdp(interface_name, (*min_port, *max_port), probe_mod_snap_len, 1)?.pop() {
+                Some(source) => source,
+                None => Self::open_device(interface_name, probe_mod_snap_len)?,
+            },
+            // 模拟发送 不接收数据包
+            CaptureSource::Idle => Self::open_idle(probe_mod_snap_len)?,
+        };
+
+        Self::from_source(interface_name, active_capture, probe_mod_snap_len, filter)
+    }
+
+    /// 设置 已打开的数据包来源: 确定 数据链路层类型, 设置 过滤规则
+    fn from_source(interface_name:&str, mut active_capture:PacketSource, probe_mod_snap_len:usize, filter:&Option<String>) -> Result<Self, CaptureError> {
+
+        active_capture.set_interface(interface_name);
+
+
+        // 获取数据链路层类型, 网络层数据的偏移 在接收时 按数据包确定
+        let data_link = match DataLink::from_linktype(active_capture.get_datalink()) {
+            Some(d) => d,
+            None => {
+                return Err(CaptureError::unsupported("data_link_type_not_supported")
+                    .with(format!("{:?}", active_capture.get_datalink())))
+            }
+        };
+
+        if let Some(program) = filter {
+            // 如果设置了过滤器
+            if let Err(_) = active_capture.filter(&data_link.wrap_filter(program), true) {
+
+                // 如果过滤器设置失败
+                return Err(CaptureError::setup("set_filter_failed").with(program))
+            }
+
+        }
+
+        Ok(Self {
+            active_capture,
+            data_link,
+            snap_len: probe_mod_snap_len,
+        })
+
+
+    }
+
+
+    /// 打开 worker_num 个捕获, 每个捕获 由一个接收线程读取
+    /// 网络接口捕获(linux) 时 各捕获加入同一 PACKET_FANOUT 组, 同一数据流的数据包 总是交给同一捕获,
+    /// AF_XDP 时 各捕获 分别读取 一部分接收队列, 捕获数量 不超过 接收队列数量,
+    /// 其它数据包来源 不能分担接收, 只打开一个捕获
+    pub fn init_fanout(interface_name:&str, capture_source:&CaptureSource, probe_mod_snap_len:usize, filter:&Option<String>,
+                       worker_num:usize) -> Result<Vec<Self>, CaptureError> {
+
+        // AF_XDP: 接收队列 按序号 分给各接收线程, 打开失败时 退回 libpcap 捕获 的 PACKET_FANOUT 组
+        #[cfg(target_os = "linux")]
+        let capture_source = match capture_source {
+            CaptureSource::Xdp(min_port, max_port) if worker_num > 1 => {
+                let sources = Self::open_xdp(interface_name, (*min_port, *max_port), probe_mod_snap_len, worker_num)?;
+                if !sources.is_empty() {
+                    return sources.into_iter()
+                        .map(|source| Self::from_source(interface_name, source, probe_mod_snap_len, filter)).collect()
+                }
+                &CaptureSource::Device
+            }
+            other => other,
+        };
+
+        #[cfg(target_os = "linux")]
+        if worker_num > 1 && matches!(capture_source, CaptureSource::Device) {
+
+            // 组号 在系统范围内唯一, 使用 进程号 和 本进程内的序号
+            static FANOUT_SEQ:AtomicU16 = AtomicU16::new(0);
+            let group = (std::process::id() as u16).wrapping_add(FANOUT_SEQ.fetch_add(1, Ordering::Relaxed));
+
+            let mut receivers = Vec::with_capacity(worker_num);
+            for _ in 0..worker_num {
+                let receiver = Self::init(interface_name, capture_source, probe_mod_snap_len, filter)?;
+                receiver.active_capture.join_fanout(group)
+                    .map_err(|e| CaptureError::setup("join_fanout_failed").with(e))?;
+                receivers.push(receiver);
+            }
+            return Ok(receivers)
+        }
+
+        if worker_num > 1 {
+            warn!("{} {}", SYS.get_info("warn", "recv_fanout_unsupported"), interface_name);
+        }
+        Ok(vec![Self::init(interface_name, capture_source, probe_mod_snap_len, filter)?])
+    }
 
 
+    /// 打开 网络接口 捕获
+    fn open_device(interface_name:&str, probe_mod_snap_len:usize) -> Result<PacketSource, CaptureError> {
+
         let device = pcap::Device::from(interface_name);                    // 获得指定设备
         let capture = Capture::from_device(device);     // 由设备获得捕获
 
-        let mut active_capture;
+        let active_capture;
         if let Ok(inactive_capture) = capture {
 
             // 如果从指定网络接口获得 捕获
@@ -20,8 +131,8 @@ impl PcapReceiver {
 
                 .buffer_size(SYS.get_conf("conf", "pcap_recv_buffer_size"))
 
-                // 设置每个捕获的数据包的长度
-                .snaplen(probe_mod_snap_len as i32)
+                // 设置每个捕获的数据包的长度, 探测模块 按以太网报头 计算长度, 为 SLL2 报头 和 vlan 标签 留出空间
+                .snaplen((probe_mod_snap_len + MAX_EXTRA_HEADER_LEN) as i32)
 
                 // 使网络接口能够捕获所有通过它的数据包，而不仅仅是那些目标为它的数据包
                 .promisc(true)
@@ -40,61 +151,68 @@ impl PcapReceiver {
 
                 // 设置非阻塞模式
                 active_capture = c.setnonblock()
-                    .map_err(|_|{
-                        error!("{}", SYS.get_info("err", "receiver_set_nonblock"));
-                        exit(1)
-                    }).unwrap();
+                    .map_err(|_| CaptureError::setup("receiver_set_nonblock"))?;
             } else {
-                error!("{}", SYS.get_info("err", "open_capture_failed"));
-                exit(1)
+                return Err(CaptureError::open("open_capture_failed"))
             }
         } else {
-            error!("{}", SYS.get_info("err", "open_capture_failed"));
-            exit(1)
+            return Err(CaptureError::open("open_capture_failed"))
         }
 
+        Ok(PacketSource::new(active_capture.into(), false, SYS.get_conf("conf", "capture_timeout")))
+    }
 
-        let data_link_len:usize;
-        {
-            // 获取数据链路层类型，并进行相关处理
-            match active_capture.get_datalink() {
-                Linktype::ETHERNET => {
-                    // 如果是 以太网
-                    // 数据链路层的字节数， 用于分割掉数据链路层的包
-                    data_link_len = 14;
-                }
 
-                Linktype::RAW => {
-                    // 如果是 原始类型
-                    // 数据链路层字节数设为0
-                    data_link_len = 0;
-                }
+    /// 打开 离线捕获文件(pcap 或 pcapng)
+    /// 注意: 回放时应使用与原始扫描相同的随机数种子, 否则验证将无法通过
+    /// 多阶段的模式(如拓扑探测)中, 每个阶段的接收线程都将从文件开头回放
+    fn open_offline(path:&str) -> Result<PacketSource, CaptureError> {
 
-                _ => {
-                    error!("{} {:?}", SYS.get_info("err", "data_link_type_not_supported"),
-                        active_capture.get_datalink());
+        let capture = Capture::from_file(path).map_err(|_| CaptureError::open("open_replay_file_failed").with(path))?;
 
-                    exit(1)
-                }
-            }
-        }
+        Ok(PacketSource::new(capture.into(), true, SYS.get_conf("conf", "capture_timeout")))
+    }
 
-        if let Some(program) = filter {
-            // 如果设置了过滤器
-            if let Err(_) = active_capture.filter(&program, true) {
 
-                // 如果过滤器设置失败
-                error!("{} {}",SYS.get_info("err", "set_filter_failed"), program);
-                exit(1)
-            }
+    /// 接入 虚拟网络, 虚拟网络生成的响应帧均为 以太网帧
+    fn open_virtual(net:&VirtualNet, probe_mod_snap_len:usize) -> Result<PacketSource, CaptureError> {
 
-        }
+        let capture = Capture::dead(Linktype::ETHERNET).map_err(|_| CaptureError::open("open_capture_failed"))?;
 
-        Self {
-            active_capture,
-            data_link_len,
-        }
+        Ok(PacketSource::new_virtual(capture.into(), net.subscribe(), probe_mod_snap_len, SYS.get_conf("conf", "capture_timeout")))
+    }
+
+
+    /// 模拟发送时 不接收任何数据包, 不需要 打开网络接口 和 管理员权限
+    fn open_idle(probe_mod_snap_len:usize) -> Result<PacketSource, CaptureError> {
 
+        let capture = Capture::dead(Linktype::ETHERNET).map_err(|_| CaptureError::open("open_capture_failed"))?;
+        let (_, port) = channel();
 
+        Ok(PacketSource::new_virtual(capture.into(), port, probe_mod_snap_len, SYS.get_conf("conf", "capture_timeout")))
+    }
+
+
+    /// 打开 网络接口的 AF_XDP 套接字, 并挂载 XDP 程序, 接收队列 按序号 分给 至多 worker_num 个数据包来源
+    /// 网络接口 或 内核 不支持 AF_XDP, 或 网络接口上 已挂载其他 XDP 程序时, 返回空, 由调用者 退回 libpcap 捕获
+    #[cfg(target_os = "linux")]
+    fn open_xdp(interface_name:&str, source_ports:(u16, u16), probe_mod_snap_len:usize, worker_num:usize) -> Result<Vec<PacketSource>, CaptureError> {
+
+        let receivers = match XdpReceiver::open(interface_name, source_ports, worker_num) {
+            Ok(r) => r,
+            Err(e) => {
+                warn!("{} {} {}", SYS.get_info("warn", "xdp_receiver_fallback"), interface_name, e);
+                return Ok(vec![])
+            }
+        };
+        info!("{} {} {}", SYS.get_info("info", "xdp_receiver_opened"), interface_name,
+            if receivers.iter().all(|r| r.zero_copy()) { "zero-copy" } else { "copy" });
+
+        let mut sources = Vec::with_capacity(receivers.len());
+        for receiver in receivers {
+            let capture = Capture::dead(Linktype::ETHERNET).map_err(|_| CaptureError::open("open_capture_failed"))?;
+            sources.push(PacketSource::new_xdp(capture.into(), receiver, probe_mod_snap_len, SYS.get_conf("conf", "capture_timeout")));
+        }
+        Ok(sources)
     }
-}
\ No newline at end of file
+}
@@ -1,4 +1,9 @@
 pub mod init;
+pub mod packet_saver;
+pub mod source;
+pub mod data_link;
+pub mod fanout;
+pub mod pipeline;
 pub mod base;
 pub mod pmap;
 mod topo;
@@ -9,11 +14,13 @@ mod tree_trace;
 mod scour6;
 mod asset;
 
-use pcap::{Active, Capture};
+use crate::core::receiver::pcap::data_link::DataLink;
+use crate::core::receiver::pcap::source::PacketSource;
 
 pub struct PcapReceiver {
-    active_capture:Capture<Active>,     // 打开状态下的 捕获
-    data_link_len:usize,
+    active_capture:PacketSource,        // 打开状态下的 捕获(网络接口 或 回放文件)
+    data_link:DataLink,                 // 数据链路层类型, 用于 按数据包 确定网络层数据的偏移
+    snap_len:usize,                     // 每个捕获的数据包的最大长度
 }
 
 use pcap::{Packet, PacketCodec, PacketHeader};
@@ -0,0 +1,64 @@
+use std::sync::{Arc, Mutex};
+use pcap::PacketHeader;
+use crate::core::conf::set_conf::receiver_conf::ReceiverBaseConf;
+use crate::core::receiver::pcap::PcapReceiver;
+use crate::core::receiver::pcap::data_link::dlt_to_linktype;
+use crate::tools::file::pcapng::PcapngWriter;
+use crate::core::errors::CaptureError;
+
+
+/// 原始数据包保存器
+/// 与正常输出并行, 将 验证通过 的数据包(或 捕获到的全部数据包) 写入 pcapng 文件
+/// 同一次运行中的所有接收线程共用一个 pcapng 文件, 各阶段的捕获器分别作为文件中的接口
+pub struct PacketSaver {
+    writer:Option<Arc<Mutex<PcapngWriter>>>,
+    interface_id:u32,
+    save_all:bool,
+}
+
+
+impl PacketSaver {
+
+    pub fn new(receiver_conf:&ReceiverBaseConf, receiver:&PcapReceiver) -> Result<Self, CaptureError> {
+
+        let writer = match &receiver_conf.packet_saver {
+            Some(w) => w.clone(),
+            None => return Ok(Self { writer: None, interface_id: 0, save_all: false }),
+        };
+
+        let link_type = dlt_to_linktype(receiver.active_capture.get_datalink()).0 as u16;
+        let interface_id = writer.lock().unwrap().interface_id(link_type, receiver.snap_len as u32)
+            .map_err(|_| CaptureError::io("save_packet_failed"))?;
+
+        Ok(Self {
+            writer: Some(writer),
+            interface_id,
+            save_all: receiver_conf.save_all_packets,
+        })
+    }
+
+    /// 保存数据包, validation_passed 为 数据包是否通过验证
+    #[inline]
+    pub fn save(&self, header:&PacketHeader, data:&[u8], validation_passed:bool) -> Result<(), CaptureError> {
+
+        if let Some(writer) = &self.writer {
+            if validation_passed || self.save_all {
+                writer.lock().unwrap().write_packet(self.interface_id,
+                                                    header.ts.tv_sec, header.ts.tv_usec, data, header.len)
+                    .map_err(|e| CaptureError::io("save_packet_failed").with(e))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Drop for PacketSaver {
+    fn drop(&mut self) {
+        // 接收线程结束时, 将缓冲区中的数据包写入文件
+        if let Some(writer) = &self.writer {
+            if let Ok(mut w) = writer.lock() {
+                let _ = w.flush();
+            }
+        }
+    }
+}
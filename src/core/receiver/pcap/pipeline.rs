@@ -0,0 +1,325 @@
+//! 接收流水线: 捕获 → 验证, 查重 → 格式化 → 写入
+//! 捕获线程 只进行 验证 和 查重, 每个目标的首个响应 交给格式化线程 生成输出行, 输出行 交给写入线程 写入输出模块.
+//! 各阶段 通过有界队列连接, 超过 队列容量 的数据 暂存在 溢出区, 下一阶段 取空队列后 从溢出区 继续取出.
+//! 溢出区 也已满时 上一阶段 等待下一阶段 取出数据(反压), 不丢弃数据, 等待次数 通过警告 和 prometheus 指标 给出.
+//! pmap, topo, 树搜索, asset6 和 scour6 等模式的接收线程 在捕获时 更新扫描状态, 输出 依赖扫描状态 或 输出模块 在多个扫描阶段间 传递, 不使用该流水线
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::mpsc::{sync_channel, Receiver, SyncSender, TryRecvError, TrySendError};
+use std::thread;
+use std::thread::JoinHandle;
+use log::warn;
+use pcap::PacketHeader;
+use crate::core::errors::{OutputError, ProbeError, RunError, SmapError};
+use crate::core::monitor::{queue_stats, QueueStats, RecvCounter};
+use crate::modules::output_modules::OutputMethod;
+use crate::modules::probe_modules::probe_mod_v4::ProbeModV4;
+use crate::modules::probe_modules::probe_mod_v6::ProbeModV6;
+use crate::SYS;
+use crate::tools::net_handle::packet::v4::packet_v4_u32::Ipv4PacketU32;
+use crate::tools::net_handle::packet::v6::packet_v6_u128::Ipv6PacketU128;
+
+
+/// 通过 验证 和 查重 的响应
+pub struct Response<I> {
+    pub header:PacketHeader,
+    // 数据链路层报头 和 网络层数据包
+    frame:Box<[u8]>,
+    net_offset:usize,
+    pub inner_ip:Option<I>,
+}
+
+impl<I> Response<I> {
+
+    pub fn new(header:&PacketHeader, data_link_header:&[u8], net_layer_data:&[u8], inner_ip:Option<I>) -> Self {
+        let mut frame = Vec::with_capacity(data_link_header.len() + net_layer_data.len());
+        frame.extend_from_slice(data_link_header);
+        frame.extend_from_slice(net_layer_data);
+        Self { header: *header, frame: frame.into_boxed_slice(), net_offset: data_link_header.len(), inner_ip }
+    }
+
+    /// 数据链路层报头 和 网络层数据包
+    #[inline]
+    pub fn split(&self) -> (&[u8], &[u8]) {
+        self.frame.split_at(self.net_offset)
+    }
+}
+
+pub type ResponseV4 = Response<u32>;
+pub type ResponseV6 = Response<u128>;
+
+
+/// 单个队列的 溢出区 和 等待次数
+struct QueueState<T> {
+    spill:Mutex<VecDeque<T>>,
+    // 溢出区中的数据数量, 为0时 发送端 不必锁定溢出区
+    spilled:AtomicUsize,
+    blocked:AtomicU64,
+}
+
+
+/// 队列的发送端, 记录 队列长度, 进入溢出区的次数 和 等待下一阶段的次数
+pub struct StageSender<T> {
+    sender:SyncSender<T>,
+    state:Arc<QueueState<T>>,
+    spill_capacity:usize,
+    stats:Arc<QueueStats>,
+}
+
+impl<T> Clone for StageSender<T> {
+    fn clone(&self) -> Self {
+        Self { sender: self.sender.clone(), state: self.state.clone(), spill_capacity: self.spill_capacity, stats: self.stats.clone() }
+    }
+}
+
+impl<T> StageSender<T> {
+
+    /// 队列未满时 直接放入队列, 超过队列容量时 放入溢出区, 溢出区已满时 等待下一阶段 取出数据. 下一阶段已退出时 丢弃
+    pub fn send(&self, item:T) {
+        self.stats.depth.fetch_add(1, Ordering::Relaxed);
+        let item = if self.state.spilled.load(Ordering::Relaxed) == 0 {
+            match self.sender.try_send(item) {
+                Ok(()) => return,
+                Err(TrySendError::Full(item)) => item,
+                Err(TrySendError::Disconnected(_)) => {
+                    self.stats.depth.fetch_sub(1, Ordering::Relaxed);
+                    return
+                }
+            }
+        } else { item };
+
+        if self.stats.full.fetch_add(1, Ordering::Relaxed) == 0 {
+            warn!("{} {} {}", SYS.get_info("warn", "recv_queue_full"), self.stats.interface, self.stats.stage);
+        }
+
+        let mut spill = self.state.spill.lock().unwrap();
+        spill.push_back(item);
+
+        // 按顺序 将溢出区中的数据 放回队列
+        while let Some(item) = spill.pop_front() {
+            match self.sender.try_send(item) {
+                Ok(()) => {}
+                Err(TrySendError::Full(item)) => { spill.push_front(item); break }
+                Err(TrySendError::Disconnected(_)) => { self.discard(&mut spill); break }
+            }
+        }
+
+        if spill.len() > self.spill_capacity {
+            // 溢出区已满, 等待下一阶段 取出数据
+            self.state.blocked.fetch_add(1, Ordering::Relaxed);
+            if self.stats.blocked.fetch_add(1, Ordering::Relaxed) == 0 {
+                // 每个阶段 只警告一次
+                warn!("{} {} {}", SYS.get_info("warn", "recv_queue_overflow"), self.stats.interface, self.stats.stage);
+            }
+            while spill.len() > self.spill_capacity {
+                let item = spill.pop_front().unwrap();
+                if self.sender.send(item).is_err() { self.discard(&mut spill); break }
+            }
+        }
+        self.state.spilled.store(spill.len(), Ordering::Relaxed);
+    }
+
+    /// 下一阶段已退出, 清空溢出区
+    fn discard(&self, spill:&mut VecDeque<T>) {
+        // 包括 未能放回队列的 当前数据
+        self.stats.depth.fetch_sub(spill.len() as u64 + 1, Ordering::Relaxed);
+        spill.clear();
+    }
+}
+
+
+/// 队列的接收端
+pub struct StageReceiver<T> {
+    receiver:Receiver<T>,
+    state:Arc<QueueState<T>>,
+    stats:Arc<QueueStats>,
+}
+
+impl<T> StageReceiver<T> {
+
+    /// 优先从队列中取出, 队列为空时 从溢出区中取出. 所有发送端 都已关闭 且 队列和溢出区 都为空时 返回空
+    pub fn recv(&self) -> Option<T> {
+        let item = match self.receiver.try_recv() {
+            Ok(item) => Some(item),
+            Err(e) => {
+                // 溢出区 被发送端锁定时 发送端 正在向队列放入数据 或 等待队列有空位, 直接等待队列
+                let item = match self.state.spill.try_lock() {
+                    Ok(mut spill) => {
+                        let item = spill.pop_front();
+                        self.state.spilled.store(spill.len(), Ordering::Relaxed);
+                        item
+                    }
+                    Err(_) => None,
+                };
+
+                match (item, e) {
+                    (Some(item), _) => Some(item),
+                    (None, TryRecvError::Empty) => self.receiver.recv().ok(),
+                    (None, TryRecvError::Disconnected) => None,
+                }
+            }
+        }?;
+        self.stats.depth.fetch_sub(1, Ordering::Relaxed);
+        Some(item)
+    }
+}
+
+impl<T> Drop for StageReceiver<T> {
+
+    /// 接收结束时 给出 该队列 因溢出区已满 等待下一阶段的次数
+    fn drop(&mut self) {
+        let blocked = self.state.blocked.load(Ordering::Relaxed);
+        if blocked > 0 {
+            warn!("{} {} {} {}", SYS.get_info("warn", "recv_queue_blocked"), self.stats.interface, self.stats.stage, blocked);
+        }
+    }
+}
+
+
+/// 创建 网络接口上 某一阶段的有界队列, 容量 由 配置项 capacity_key 设置, 溢出区容量 由 recv_queue_spill_capacity 设置
+pub fn stage_queue<T>(interface:&str, stage:&'static str, capacity_key:&str) -> (StageSender<T>, StageReceiver<T>) {
+    let capacity:usize = SYS.get_conf("conf", capacity_key);
+    let spill_capacity:usize = SYS.get_conf("conf", "recv_queue_spill_capacity");
+    let stats = queue_stats(interface, stage, capacity);
+    let state = Arc::new(QueueState { spill: Mutex::new(VecDeque::new()), spilled: AtomicUsize::new(0), blocked: AtomicU64::new(0) });
+    let (sender, receiver) = sync_channel(capacity);
+    (StageSender { sender, state: state.clone(), spill_capacity, stats: stats.clone() },
+     StageReceiver { receiver, state, stats })
+}
+
+
+/// 写入阶段: 由 写入线程 独占输出模块, 所有输出行的发送端 关闭后 关闭输出模块
+pub struct WriteStage {
+    lines:StageSender<Vec<String>>,
+    handle:JoinHandle<Result<(), OutputError>>,
+}
+
+impl WriteStage {
+
+    /// 输出模块 应已写入首行
+    pub fn spawn(mut output:Box<dyn OutputMethod>, interface:&str) -> Self {
+
+        let (lines, line_receiver) = stage_queue::<Vec<String>>(interface, "write", "recv_write_queue_capacity");
+
+        let handle = thread::spawn(move || {
+            while let Some(line) = line_receiver.recv() {
+                output.writer_line(&line);
+            }
+            output.close_output()
+        });
+
+        Self { lines, handle }
+    }
+
+    /// 输出行的发送端, 多个格式化线程 可共用同一写入线程
+    pub fn lines(&self) -> StageSender<Vec<String>> {
+        self.lines.clone()
+    }
+
+    /// 等待 写入线程 写完队列中的输出行 并关闭输出模块, 其它发送端 应已关闭
+    pub fn finish(self) -> Result<(), SmapError> {
+        drop(self.lines);
+        self.handle.join().map_err(|_| RunError::thread("recv_thread_err"))??;
+        Ok(())
+    }
+}
+
+
+/// 格式化阶段: 由 格式化线程 调用探测模块 生成输出行, 统计 探测成功 和 探测失败 的数量
+pub struct FormatStage<I> {
+    responses:StageSender<Response<I>>,
+    handle:JoinHandle<Result<(u64, u64), SmapError>>,
+}
+
+impl<I:Send + 'static> FormatStage<I> {
+
+    /// init 在格式化线程中 创建格式化方法, 格式化方法 返回 是否探测成功 和 输出行. 创建失败时 格式化线程 返回错误
+    /// 设置 计数器 时, 每处理 active_check_count 个响应 将 探测成功数量 发布到 扫描进度计数器, 结束时 再发布一次
+    fn spawn<F, G>(init:F, allow_no_succ:bool, lines:StageSender<Vec<String>>, interface:&str,
+                   mut recv_counter:Option<RecvCounter>) -> Self
+        where F:FnOnce() -> Result<G, ProbeError> + Send + 'static, G:FnMut(&Response<I>) -> (bool, Vec<String>) {
+
+        let (responses, response_receiver) = stage_queue::<Response<I>>(interface, "format", "recv_format_queue_capacity");
+
+        let handle = thread::spawn(move || {
+            let mut format = init()?;
+            let (mut success, mut failed) = (0u64, 0u64);
+            let act_check_count:u64 = SYS.get_conf("conf", "active_check_count");
+
+            while let Some(response) = response_receiver.recv() {
+                let (is_successful, output_line_data) = format(&response);
+
+                if is_successful {
+                    success += 1;
+                } else {
+                    failed += 1;
+                }
+
+                // 是否允许输出 验证成功但探测失败的结果
+                if (is_successful || allow_no_succ) && !output_line_data.is_empty() {
+                    lines.send(output_line_data);
+                }
+
+                if (success + failed).is_multiple_of(act_check_count) {
+                    if let Some(c) = recv_counter.as_mut() {
+                        c.publish(0, 0, success, 0);
+                    }
+                }
+            }
+            if let Some(c) = recv_counter.as_mut() {
+                c.publish(0, 0, success, 0);
+            }
+            Ok((success, failed))
+        });
+
+        Self { responses, handle }
+    }
+
+    /// 响应的发送端, 由 捕获线程 使用
+    pub fn responses(&self) -> &StageSender<Response<I>> {
+        &self.responses
+    }
+
+    /// 等待 格式化线程 处理完队列中的响应, 返回 探测成功 和 探测失败 的数量
+    pub fn finish(self) -> Result<(u64, u64), SmapError> {
+        drop(self.responses);
+        self.handle.join().map_err(|_| SmapError::from(RunError::thread("recv_thread_err")))?
+    }
+}
+
+impl FormatStage<u32> {
+
+    pub fn spawn_v4(probe_mod:Arc<ProbeModV4>, sports:Vec<u16>, allow_no_succ:bool, lines:StageSender<Vec<String>>,
+                    interface:&str, recv_counter:Option<RecvCounter>) -> Self {
+
+        Self::spawn(move || {
+            let probe = ProbeModV4::init(probe_mod, sports)?;
+            Ok(move |r:&ResponseV4| {
+                let (data_link_header, net_layer_header_and_data) = r.split();
+                let v4_header = Ipv4PacketU32::parse_ipv4_packet(net_layer_header_and_data);
+                let net_layer_data = &net_layer_header_and_data[(v4_header.ihl as usize) * 4 ..];
+                probe.process_packet_v4(&r.header, data_link_header, &v4_header, net_layer_data, r.inner_ip)
+            })
+        }, allow_no_succ, lines, interface, recv_counter)
+    }
+}
+
+impl FormatStage<u128> {
+
+    pub fn spawn_v6(probe_mod:Arc<ProbeModV6>, sports:Vec<u16>, allow_no_succ:bool, lines:StageSender<Vec<String>>,
+                    interface:&str, recv_counter:Option<RecvCounter>) -> Self {
+
+        Self::spawn(move || {
+            let probe = ProbeModV6::init(probe_mod, sports)?;
+            Ok(move |r:&ResponseV6| {
+                let (data_link_header, net_layer_header_and_data) = r.split();
+                let v6_header = Ipv6PacketU128::parse_ipv6_packet(net_layer_header_and_data);
+                // ipv6 首部长度固定为 40字节  注意: 包含扩展首部
+                let net_layer_data = &net_layer_header_and_data[40..];
+                probe.process_packet_v6(&r.header, data_link_header, &v6_header, net_layer_data, r.inner_ip)
+            })
+        }, allow_no_succ, lines, interface, recv_counter)
+    }
+}
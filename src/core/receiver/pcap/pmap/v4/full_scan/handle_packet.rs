@@ -9,9 +9,10 @@ use crate::tools::net_handle::packet::v4::packet_v4_u32::Ipv4PacketU32;
 
 impl PcapReceiver  {
 
+    /// 处理数据包, 返回 目标端口是否探测成功
     #[inline]
     pub fn pmap_full_scan_handle_packet_v4<B:DuplicateCheckerV4Port>(data_link_header:&[u8], net_layer_header_and_data:&[u8],
-                            aes_rand:&AesRand, recorder:&mut B, probe:&Box<dyn ProbeMethodV4>){
+                            aes_rand:&AesRand, recorder:&mut B, probe:&Box<dyn ProbeMethodV4>) -> bool {
 
         // ipv4 数据包头部
         let v4_header = Ipv4PacketU32::parse_ipv4_packet(net_layer_header_and_data);
@@ -25,7 +26,11 @@ impl PcapReceiver  {
             let src_port = u16::from_be_bytes([net_layer_data[0], net_layer_data[1]]);
 
             // 直接将  源地址 和 源端口 加入, 多次对 同一端口对 进行标记, 等同于 一次标记
-            recorder.set(v4_header.source_addr, src_port)
+            recorder.set(v4_header.source_addr, src_port);
+
+            true
+        } else {
+            false
         }
     }
 }
\ No newline at end of file
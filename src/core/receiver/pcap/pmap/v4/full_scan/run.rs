@@ -2,36 +2,43 @@
 
 
 
-use std::process::exit;
 use std::sync::Arc;
 use std::sync::mpsc::{Receiver, Sender};
 use chrono::Utc;
-use log::error;
 use crate::core::conf::set_conf::base_conf::BaseConf;
 use crate::core::conf::set_conf::receiver_conf::ReceiverBaseConf;
 use crate::core::receiver::pcap::{Codec, PcapReceiver};
+use crate::core::receiver::pcap::data_link::ip_version;
+use crate::core::receiver::pcap::packet_saver::PacketSaver;
+use crate::core::receiver::abort_senders;
+use crate::core::monitor::RecvCounter;
+use crate::core::checkpoint::RecvCheckpoint;
 use crate::modules::probe_modules::probe_mod_v4::ProbeModV4;
 use crate::SYS;
-use crate::tools::check_duplicates::{DuplicateCheckerV4Port};
+use crate::tools::check_duplicates::{DuplicateCheckerV4Port, RecorderState};
+use crate::core::errors::{RunError, SmapError};
+use crate::core::receiver::pcap::source::CaptureStats;
 
 impl PcapReceiver {
 
-    pub fn pmap_full_scan_v4<B:DuplicateCheckerV4Port>(// 网络接口下标, 用来指定接收器绑定的网络接口
+    pub fn pmap_full_scan_v4<B:DuplicateCheckerV4Port + RecorderState>(// 网络接口下标, 用来指定接收器绑定的网络接口
                        interface_index:usize,
                        base_conf:Arc<BaseConf>, receiver_conf:Arc<ReceiverBaseConf>,
                        // 探测模块用来验证信息, 处理数据包
                        probe_mod:Arc<ProbeModV4>, sports:Vec<u16>, mut recorder:B,
                        // 接收准备完成管道: 用于在接收线程准备好进行接收时,向主线程发送允许执行发送线程的信号
                        // 接收关闭时间管道: 用于接收从主线程传递过来的接收线程关闭时间
-                       recv_ready_sender:Sender<bool>, recv_close_time_receiver:Receiver<i64>) -> B {
+                       recv_ready_sender:Sender<bool>, recv_close_time_receiver:Receiver<i64>,
+                       // 断点记录器: 断点线程请求时 导出查重器的状态
+                       checkpoint:RecvCheckpoint) -> Result<(B, CaptureStats), SmapError> {
 
         // 初始化 数据包捕获器
         let receiver = PcapReceiver::init(
-            &base_conf.interface[interface_index].name_index.0,
-            probe_mod.snap_len_v4, &receiver_conf.filter);
+            &base_conf.interface[interface_index].name_index.0, &receiver_conf.capture_source,
+            probe_mod.snap_len_v4, &receiver_conf.filter)?;
 
         // 初始化 探测模块
-        let probe = ProbeModV4::init(probe_mod, sports);
+        let probe = ProbeModV4::init(probe_mod, sports)?;
 
         // 复制加密机
         let aes_rand = base_conf.aes_rand.clone();
@@ -42,16 +49,24 @@ impl PcapReceiver {
         let mut send_running = true;               // 发送进程 正在运行标识
         let act_check_count:u32 = SYS.get_conf("conf", "active_check_count");
 
+        // 探测成功 和 未成功 的数量, 定期发布到 扫描进度计数器
+        let mut recv_success:u64 = 0;
+        let mut recv_failed:u64 = 0;
+        let mut recv_counter = RecvCounter::new(&base_conf.interface[interface_index].name_index.0);
+
         // 向主线程发送 接收线程 准备完毕的 管道消息
         if let Err(_) = recv_ready_sender.send(true) {
-            error!("{}", SYS.get_info("err","recv_ready_send_failed"));
-            exit(1)
+            return Err(RunError::channel("recv_ready_send_failed").into())
         }
 
+        // 初始化 原始数据包保存器
+        let packet_saver = PacketSaver::new(&receiver_conf, &receiver).map_err(abort_senders)?;
+
         drop(base_conf);
         drop(receiver_conf);
 
-        for packet in receiver.active_capture.iter(Codec) {
+        let mut packets = receiver.active_capture.iter(Codec);
+        for packet in packets.by_ref() {
 
             match packet {
 
@@ -59,19 +74,22 @@ impl PcapReceiver {
 
                     // 头部信息, 包含时间戳, 数据包长度等信息
                     let header = packet.header;
-                    // 数据链路层报头
-                    let data_link_header = &packet.data[..receiver.data_link_len];
-                    // 网络层数据包
-                    let net_layer_data = &packet.data[receiver.data_link_len..];
+                    // 数据链路层报头 和 网络层数据包, 网络层数据的偏移 按数据包确定(如 vlan 标签)
+                    let (data_link_header, net_layer_data) = receiver.data_link.split(&packet.data);
 
-                    let ip_ver = net_layer_data[0] >> 4;
-                    if ip_ver == 4 {
+                    let ip_ver = ip_version(net_layer_data);
+                    let validation_passed = if ip_ver == 4 {
                         // 如果是ipv4的数据包
                         Self::pmap_full_scan_handle_packet_v4(data_link_header, net_layer_data, &aes_rand,
-                                                              &mut recorder, &probe);
-                    }
+                                                              &mut recorder, &probe)
+                    } else { false };
                     // 如果是 ipv6 的包, 或者其它特殊类型, 不进行处理
 
+                    if validation_passed { recv_success += 1; } else { recv_failed += 1; }
+
+                    // 保存原始数据包
+                    packet_saver.save(&packet.header, &packet.data, validation_passed).map_err(abort_senders)?;
+
                     {   // 计算终止条件
                         act_count += 1;
                         if act_count % act_check_count == 0 {
@@ -97,6 +115,8 @@ impl PcapReceiver {
                                     break                        // 如果达到就退出
                                 }
                             }
+                            recv_counter.publish(recv_success, recv_failed, recv_success, 0);
+                            checkpoint.check(&recorder);
                             act_count = 0;      // 重新 设为 0
                         }
                     }
@@ -104,6 +124,9 @@ impl PcapReceiver {
 
                 Err(_) => {         // 主要是超时错误， 没接收到数据包就 报错
 
+                    recv_counter.publish(recv_success, recv_failed, recv_success, 0);
+                    checkpoint.check(&recorder);
+
                     if send_running {   // 还未收到 发送进程传递的 结束消息
 
                         match recv_close_time_receiver.try_recv() { // 检查有没有消息
@@ -135,6 +158,12 @@ impl PcapReceiver {
             }
         }
 
-       recorder
+       recv_counter.publish(recv_success, recv_failed, recv_success, 0);
+       checkpoint.save(&recorder);
+
+       // 读取 捕获统计, 丢包过多时 给出警告
+       let capture_stats = packets.finish();
+
+       Ok((recorder, capture_stats))
     }
 }
\ No newline at end of file
@@ -11,9 +11,10 @@ use crate::tools::net_handle::packet::v6::packet_v6_u128::Ipv6PacketU128;
 
 impl PcapReceiver  {
 
+    /// 处理数据包, 返回 目标端口是否探测成功
     #[inline]
     pub fn pmap_full_scan_handle_packet_v6<B:DuplicateCheckerV6Port>(data_link_header:&[u8], net_layer_header_and_data:&[u8],
-                                                                     aes_rand:&AesRand, recorder:&mut B, probe:&Box<dyn ProbeMethodV6>){
+                                                                     aes_rand:&AesRand, recorder:&mut B, probe:&Box<dyn ProbeMethodV6>) -> bool {
 
         // ipv6 数据包头部
         let v6_header = Ipv6PacketU128::parse_ipv6_packet(net_layer_header_and_data);
@@ -27,7 +28,11 @@ impl PcapReceiver  {
             let src_port = u16::from_be_bytes([net_layer_data[0], net_layer_data[1]]);
 
             // 直接将  源地址 和 源端口 加入, 多次对 同一端口对 进行标记, 等同于 一次标记
-            recorder.set(v6_header.source_addr, src_port)
+            recorder.set(v6_header.source_addr, src_port);
+
+            true
+        } else {
+            false
         }
     }
 }
\ No newline at end of file
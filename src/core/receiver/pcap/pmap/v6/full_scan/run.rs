@@ -4,17 +4,20 @@
 
 
 
-use std::process::exit;
 use std::sync::Arc;
 use std::sync::mpsc::{Receiver, Sender};
 use chrono::Utc;
-use log::error;
 use crate::core::conf::set_conf::base_conf::BaseConf;
 use crate::core::conf::set_conf::receiver_conf::ReceiverBaseConf;
 use crate::core::receiver::pcap::{Codec, PcapReceiver};
+use crate::core::receiver::pcap::data_link::ip_version;
+use crate::core::receiver::pcap::packet_saver::PacketSaver;
+use crate::core::receiver::abort_senders;
 use crate::modules::probe_modules::probe_mod_v6::ProbeModV6;
 use crate::SYS;
 use crate::tools::check_duplicates::DuplicateCheckerV6Port;
+use crate::core::errors::{RunError, SmapError};
+use crate::core::receiver::pcap::source::CaptureStats;
 
 impl PcapReceiver {
 
@@ -25,15 +28,15 @@ impl PcapReceiver {
                                                        probe_mod:Arc<ProbeModV6>, sports:Vec<u16>, mut recorder:B,
                                                        // 接收准备完成管道: 用于在接收线程准备好进行接收时,向主线程发送允许执行发送线程的信号
                                                        // 接收关闭时间管道: 用于接收从主线程传递过来的接收线程关闭时间
-                                                       recv_ready_sender:Sender<bool>, recv_close_time_receiver:Receiver<i64>) -> B {
+                                                       recv_ready_sender:Sender<bool>, recv_close_time_receiver:Receiver<i64>) -> Result<(B, CaptureStats), SmapError> {
 
         // 初始化 数据包捕获器
         let receiver = PcapReceiver::init(
-            &base_conf.interface[interface_index].name_index.0,
-            probe_mod.snap_len_v6, &receiver_conf.filter);
+            &base_conf.interface[interface_index].name_index.0, &receiver_conf.capture_source,
+            probe_mod.snap_len_v6, &receiver_conf.filter)?;
 
         // 初始化 探测模块
-        let probe = ProbeModV6::init(probe_mod, sports);
+        let probe = ProbeModV6::init(probe_mod, sports)?;
 
         // 复制加密机
         let aes_rand = base_conf.aes_rand.clone();
@@ -46,14 +49,17 @@ impl PcapReceiver {
 
         // 向主线程发送 接收线程 准备完毕的 管道消息
         if let Err(_) = recv_ready_sender.send(true) {
-            error!("{}", SYS.get_info("err","recv_ready_send_failed"));
-            exit(1)
+            return Err(RunError::channel("recv_ready_send_failed").into())
         }
 
+        // 初始化 原始数据包保存器
+        let packet_saver = PacketSaver::new(&receiver_conf, &receiver).map_err(abort_senders)?;
+
         drop(base_conf);
         drop(receiver_conf);
 
-        for packet in receiver.active_capture.iter(Codec) {
+        let mut packets = receiver.active_capture.iter(Codec);
+        for packet in packets.by_ref() {
 
             match packet {
 
@@ -61,19 +67,20 @@ impl PcapReceiver {
 
                     // 头部信息, 包含时间戳, 数据包长度等信息
                     let header = packet.header;
-                    // 数据链路层报头
-                    let data_link_header = &packet.data[..receiver.data_link_len];
-                    // 网络层数据包
-                    let net_layer_data = &packet.data[receiver.data_link_len..];
+                    // 数据链路层报头 和 网络层数据包, 网络层数据的偏移 按数据包确定(如 vlan 标签)
+                    let (data_link_header, net_layer_data) = receiver.data_link.split(&packet.data);
 
-                    let ip_ver = net_layer_data[0] >> 4;
-                    if ip_ver == 6 {
+                    let ip_ver = ip_version(net_layer_data);
+                    let validation_passed = if ip_ver == 6 {
                         // 如果是ipv6的数据包
                         Self::pmap_full_scan_handle_packet_v6(data_link_header, net_layer_data, &aes_rand,
-                                                              &mut recorder, &probe);
-                    }
+                                                              &mut recorder, &probe)
+                    } else { false };
                     // 如果是 ipv6 的包, 或者其它特殊类型, 不进行处理
 
+                    // 保存原始数据包
+                    packet_saver.save(&packet.header, &packet.data, validation_passed).map_err(abort_senders)?;
+
                     {   // 计算终止条件
                         act_count += 1;
                         if act_count % act_check_count == 0 {
@@ -137,6 +144,9 @@ impl PcapReceiver {
             }
         }
 
-        recorder
+        // 读取 捕获统计, 丢包过多时 给出警告
+        let capture_stats = packets.finish();
+
+        Ok((recorder, capture_stats))
     }
 }
\ No newline at end of file
@@ -8,12 +8,13 @@ use crate::tools::encryption_algorithm::aes::AesRand;
 impl PcapReceiver {
     
     
+    /// 处理数据包, 返回 数据包是否通过验证
     pub fn prefix_scan_handle_packet_v6(ts:&libc::timeval, net_layer_header_and_data:&[u8], double_tree_struct:&mut Ipv6VecDoubleTree,
                                         aes_rand:&AesRand, probe:&Box<dyn TopoMethodV6>, all_nodes:&mut AHashSet<u128>,
-                                        output:&mut Box<dyn OutputMethod>) {
+                                        output:&mut Box<dyn OutputMethod>) -> bool {
         
         // ipv6数据包(包括数据)的长度必须大于 40
-        if 40 > net_layer_header_and_data.len() { return }
+        if 40 > net_layer_header_and_data.len() { return false }
         // 网络层数据
         let net_layer_data = &net_layer_header_and_data[40..];
 
@@ -23,7 +24,7 @@ impl PcapReceiver {
 
                 // 如果 响应地址 和 目的地址 相等, 将被视为异常情况, 
                 // 不统计该接口地址, 并且不参与reward计算
-                if res.dest_ip == res.responder { return }
+                if res.dest_ip == res.responder { return true }
                 
                 // 交由 double_tree 算法处理
                 if double_tree_struct.receive(&res, all_nodes) {
@@ -32,8 +33,10 @@ impl PcapReceiver {
                     // 输出该条目信息
                     output.writer_line(&probe.print_record(&res, net_layer_header_and_data));
                 }
+
+                true
             }
-            None => return,
+            None => false,
         }
     }
 }
\ No newline at end of file
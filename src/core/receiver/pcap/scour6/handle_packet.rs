@@ -7,13 +7,14 @@ use crate::tools::encryption_algorithm::aes::AesRand;
 impl PcapReceiver {
 
 
+    /// 处理数据包, 返回 数据包是否通过验证
     pub fn code_topo_prefix_scan_handle_packet_v6_2(ts:&libc::timeval, net_layer_header_and_data:&[u8], 
                                                      recorder:&mut Vec<u64>, recorder2:&mut Vec<AHashMap<u64, u128>>,
                                                     prefix_len:usize, aes_rand:&AesRand, probe:&Box<dyn CodeTopoProbeMethodV6>, 
-                                                    all_nodes:&mut AHashMap<u128, u8>, output:&mut Box<dyn OutputMethod>) {
+                                                    all_nodes:&mut AHashMap<u128, u8>, output:&mut Box<dyn OutputMethod>) -> bool {
 
         // ipv6数据包(包括数据)的长度必须大于 40
-        if 40 > net_layer_header_and_data.len() { return }
+        if 40 > net_layer_header_and_data.len() { return false }
         // 网络层数据
         let net_layer_data = &net_layer_header_and_data[40..];
 
@@ -23,7 +24,7 @@ impl PcapReceiver {
                 
                 // 如果 响应地址 和 目的地址 相等, 将被视为异常情况, 
                 // 不统计该接口地址, 并且不参与reward计算
-                if res.dest_ip == res.responder { return }
+                if res.dest_ip == res.responder { return true }
                 
                 // 输出该条目信息
                 output.writer_line(&probe.print_record(&res));
@@ -32,7 +33,7 @@ impl PcapReceiver {
                 let code_vec = &res.code;
                 let tar_prefix_index = u32::from_be_bytes([0, code_vec[0], code_vec[1], code_vec[2]]) as usize;
                 // 编码不能超过前缀总数量
-                if tar_prefix_index >= prefix_len { return }
+                if tar_prefix_index >= prefix_len { return true }
                 
                 
                 
@@ -64,8 +65,10 @@ impl PcapReceiver {
                         cur_dest_recorder.insert(dest_prefix, res.responder);
                     }
                 }
+
+                true
             }
-            None => return,
+            None => false,
         }
     }
 }
\ No newline at end of file
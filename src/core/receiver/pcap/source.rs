@@ -0,0 +1,365 @@
+use std::sync::Arc;
+use std::sync::mpsc::{Receiver, RecvTimeoutError};
+use std::thread::sleep;
+use std::time::Duration;
+use log::warn;
+use pcap::{Activated, BpfProgram, Capture, Error, Linktype, Packet, PacketCodec, PacketIter};
+#[cfg(target_os = "linux")]
+use pcap::PacketHeader;
+use crate::core::monitor::CaptureCounter;
+use crate::core::sys::virtual_net::{VirtualFrame, VirtualNet};
+#[cfg(target_os = "linux")]
+use crate::core::sys::xdp::XdpReceiver;
+use crate::SYS;
+
+
+/// 接收线程的数据包来源
+pub enum CaptureSource {
+
+    // 网络接口
+    Device,
+
+    // 离线捕获文件(回放)
+    Replay(String),
+
+    // 进程内的虚拟网络
+    Virtual(Arc<VirtualNet>),
+
+    // 网络接口的 AF_XDP 套接字, 参数为 探测使用的 源端口范围(闭区间)
+    #[cfg(target_os = "linux")]
+    Xdp(u16, u16),
+
+    // 不接收数据包, 用于 只计数 或 写入pcapng文件 的模拟发送
+    Idle,
+}
+
+
+/// 数据包来源: 网络接口, 离线捕获文件(回放), 虚拟网络 或 AF_XDP 套接字
+pub struct PacketSource {
+    capture:Capture<dyn Activated>,
+
+    // 是否为 离线捕获文件
+    offline:bool,
+
+    // 读取超时时间(毫秒)
+    timeout:i32,
+
+    // 虚拟网络的 响应帧管道, 以及 由过滤规则编译得到的 bpf程序
+    virtual_port:Option<Receiver<VirtualFrame>>,
+    program:Option<BpfProgram>,
+    snap_len:usize,
+
+    // AF_XDP 接收器, 同样由接收线程 自行执行 bpf程序
+    #[cfg(target_os = "linux")]
+    xdp:Option<XdpReceiver>,
+
+    // 所在网络接口名称, 用于按接口统计
+    interface:String,
+}
+
+impl PacketSource {
+
+    pub fn new(capture:Capture<dyn Activated>, offline:bool, timeout:i32) -> Self {
+        Self {
+            capture, offline, timeout, virtual_port: None, program: None, snap_len: usize::MAX,
+            #[cfg(target_os = "linux")]
+            xdp: None,
+            interface: String::new(),
+        }
+    }
+
+    /// 虚拟网络 捕获源, capture 仅用于提供 链路层类型 和 编译过滤规则
+    pub fn new_virtual(capture:Capture<dyn Activated>, port:Receiver<VirtualFrame>, snap_len:usize, timeout:i32) -> Self {
+        Self {
+            capture, offline: false, timeout, virtual_port: Some(port), program: None, snap_len,
+            #[cfg(target_os = "linux")]
+            xdp: None,
+            interface: String::new(),
+        }
+    }
+
+    /// AF_XDP 捕获源, capture 仅用于提供 链路层类型 和 编译过滤规则
+    #[cfg(target_os = "linux")]
+    pub fn new_xdp(capture:Capture<dyn Activated>, receiver:XdpReceiver, snap_len:usize, timeout:i32) -> Self {
+        Self { capture, offline: false, timeout, virtual_port: None, program: None, snap_len, xdp: Some(receiver), interface: String::new() }
+    }
+
+    /// 是否 由接收线程自行执行 bpf程序
+    fn user_filter(&self) -> bool {
+        #[cfg(target_os = "linux")]
+        if self.xdp.is_some() { return true }
+        self.virtual_port.is_some()
+    }
+
+    pub fn set_interface(&mut self, interface:&str) {
+        self.interface = interface.to_string();
+    }
+
+    pub fn get_datalink(&self) -> Linktype {
+        self.capture.get_datalink()
+    }
+
+    /// 加入 PACKET_FANOUT 组, 同一组内的捕获 由内核按流哈希 分配数据包(只用于 linux 网络接口捕获)
+    #[cfg(target_os = "linux")]
+    pub fn join_fanout(&self, group:u16) -> std::io::Result<()> {
+
+        extern "C" {
+            fn pcap_fileno(p:*mut libc::c_void) -> libc::c_int;
+        }
+
+        // 分片数据包 在重组后 再按流哈希分配
+        let fanout = (group as libc::c_uint) | ((libc::PACKET_FANOUT_HASH | libc::PACKET_FANOUT_FLAG_DEFRAG) << 16);
+        let res = unsafe {
+            let fd = pcap_fileno(self.capture.as_ptr() as *mut libc::c_void);
+            libc::setsockopt(fd, libc::SOL_PACKET, libc::PACKET_FANOUT, &fanout as *const libc::c_uint as *const libc::c_void,
+                             std::mem::size_of::<libc::c_uint>() as libc::socklen_t)
+        };
+        if res < 0 { Err(std::io::Error::last_os_error()) } else { Ok(()) }
+    }
+
+    pub fn filter(&mut self, program:&str, optimize:bool) -> Result<(), Error> {
+        if self.user_filter() {
+            // 虚拟网络 和 AF_XDP 不经过内核的过滤, 由接收线程自行执行 bpf程序
+            self.program = Some(self.capture.compile(program, optimize)?);
+            return Ok(())
+        }
+        self.capture.filter(program, optimize)
+    }
+
+    pub fn iter<C:PacketCodec>(self, codec:C) -> PacketSourceIter<C> {
+
+        let timeout = Duration::from_millis(self.timeout.max(0) as u64);
+
+        #[cfg(target_os = "linux")]
+        if let Some(receiver) = self.xdp {
+            return PacketSourceIter::Xdp {
+                receiver, program: self.program, codec, snap_len: self.snap_len, timeout,
+                stats: CaptureCounter::new(&self.interface),
+                stats_check: SYS.get_conf("conf", "active_check_count"),
+                count: 0,
+                received: 0,
+            }
+        }
+
+        match self.virtual_port {
+            Some(port) => PacketSourceIter::Virtual {
+                port, program: self.program, codec, snap_len: self.snap_len, timeout,
+            },
+            None => PacketSourceIter::Capture {
+                iter: self.capture.iter(codec),
+                offline: self.offline,
+                timeout,
+                stats: CaptureCounter::new(&self.interface),
+                stats_check: SYS.get_conf("conf", "active_check_count"),
+                count: 0,
+            },
+        }
+    }
+}
+
+
+/// 单个接收线程的 libpcap 捕获统计
+#[derive(Clone, Copy, Default)]
+pub struct CaptureStats {
+
+    // 通过过滤规则的数据包数量
+    pub received:u64,
+
+    // 因缓冲区已满 被丢弃的数据包数量
+    pub dropped:u64,
+
+    // 被网络接口 或 驱动 丢弃的数据包数量
+    pub if_dropped:u64,
+}
+
+impl CaptureStats {
+
+    /// 丢包率: 丢弃数量 / (接收数量 + 丢弃数量)
+    pub fn drop_ratio(&self) -> f64 {
+        let lost = self.dropped + self.if_dropped;
+        let all = self.received + lost;
+        if all == 0 { 0.0 } else { (lost as f64) / (all as f64) }
+    }
+
+    /// 累加 另一接收线程 或 另一扫描阶段 的捕获统计
+    pub fn merge(&mut self, other:&Self) {
+        self.received += other.received;
+        self.dropped += other.dropped;
+        self.if_dropped += other.if_dropped;
+    }
+}
+
+
+pub enum PacketSourceIter<C:PacketCodec> {
+    Capture {
+        iter:PacketIter<dyn Activated, C>,
+        offline:bool,
+        timeout:Duration,
+
+        // 捕获统计: 每接收 stats_check 个数据包 或 读取超时 时, 读取一次 libpcap 的统计信息
+        stats:CaptureCounter,
+        stats_check:u32,
+        count:u32,
+    },
+    Virtual {
+        port:Receiver<VirtualFrame>,
+        program:Option<BpfProgram>,
+        codec:C,
+        snap_len:usize,
+        timeout:Duration,
+    },
+    #[cfg(target_os = "linux")]
+    Xdp {
+        receiver:XdpReceiver,
+        program:Option<BpfProgram>,
+        codec:C,
+        snap_len:usize,
+        timeout:Duration,
+
+        // 每接收 stats_check 个数据包 或 读取超时 时, 发布一次 接收数量 和 内核统计的丢包数量
+        stats:CaptureCounter,
+        stats_check:u32,
+        count:u32,
+        received:u32,
+    },
+}
+
+impl<C:PacketCodec> PacketSourceIter<C> {
+
+    /// 接收结束时调用: 最后读取一次 libpcap(或 AF_XDP) 的统计信息, 丢包率超过阈值时 给出 缓冲区大小 或 发送速率 的建议
+    /// 回放文件 和 虚拟网络 没有捕获统计, 均返回 0
+    pub fn finish(&mut self) -> CaptureStats {
+
+        // AF_XDP 的缓冲区丢包 为 接收队列已满 或 填充队列为空
+        let (stats, xdp) = match self {
+            PacketSourceIter::Capture { iter, offline: false, stats, .. } => {
+                if let Ok(stat) = iter.capture_mut().stats() {
+                    stats.publish(stat.received, stat.dropped, stat.if_dropped);
+                }
+                (stats, false)
+            }
+            #[cfg(target_os = "linux")]
+            PacketSourceIter::Xdp { receiver, stats, received, .. } => {
+                stats.publish(*received, receiver.dropped() as u32, 0);
+                (stats, true)
+            }
+            _ => return CaptureStats::default(),
+        };
+        let (received, dropped, if_dropped) = stats.total();
+        let capture_stats = CaptureStats { received, dropped, if_dropped };
+
+        let ratio = capture_stats.drop_ratio();
+        let warn_ratio:f64 = SYS.get_conf("conf", "pcap_drop_warn_ratio");
+        if ratio > warn_ratio {
+            // 缓冲区丢包 按丢包率 等比例增大缓冲区(取 2 的幂), 网络接口丢包 只能降低发送速率
+            let info = if dropped > 0 && xdp {
+                let ring_size:u64 = SYS.get_conf("conf", "xdp_ring_size");
+                let suggested_ring = ((ring_size as f64) / (1.0 - ratio).max(0.01)) as u64;
+                SYS.get_info("warn", "xdp_ring_drop").replace("{ring}", &suggested_ring.next_power_of_two().to_string())
+            } else if dropped > 0 {
+                let buffer_size:u64 = SYS.get_conf("conf", "pcap_recv_buffer_size");
+                let suggested_buffer = ((buffer_size as f64) / (1.0 - ratio).max(0.01)) as u64;
+                SYS.get_info("warn", "pcap_buffer_drop").replace("{buffer}", &suggested_buffer.next_power_of_two().to_string())
+            } else {
+                SYS.get_info("warn", "pcap_if_drop")
+            };
+
+            warn!("{}", info
+                .replace("{interface}", stats.interface())
+                .replace("{received}", &received.to_string())
+                .replace("{dropped}", &dropped.to_string())
+                .replace("{if_dropped}", &if_dropped.to_string())
+                .replace("{ratio}", &format!("{:.2}", ratio * 100.0))
+                .replace("{rate}", &format!("{:.0}", (1.0 - ratio) * 100.0)));
+        }
+
+        capture_stats
+    }
+}
+
+impl<C:PacketCodec> Iterator for PacketSourceIter<C> {
+    type Item = Result<C::Item, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+
+        match self {
+            PacketSourceIter::Capture { iter, offline, timeout, stats, stats_check, count } => {
+                let next = match iter.next() {
+                    None if *offline => {
+                        // 回放文件读取完毕后, 按 读取超时 处理, 由接收线程按原有逻辑等待关闭时间并关闭输出
+                        sleep(*timeout);
+                        return Some(Err(Error::TimeoutExpired))
+                    }
+                    other => other,
+                };
+
+                // 回放文件 没有捕获统计
+                if !*offline {
+                    *count += 1;
+                    if *count >= *stats_check || matches!(next, Some(Err(_))) {
+                        *count = 0;
+                        if let Ok(stat) = iter.capture_mut().stats() {
+                            stats.publish(stat.received, stat.dropped, stat.if_dropped);
+                        }
+                    }
+                }
+                next
+            }
+
+            PacketSourceIter::Virtual { port, program, codec, snap_len, timeout } => {
+                loop {
+                    let (mut header, mut data) = match port.recv_timeout(*timeout) {
+                        Ok(frame) => frame,
+                        // 虚拟网络 不会关闭, 没有响应帧时 按读取超时处理
+                        Err(RecvTimeoutError::Timeout) => return Some(Err(Error::TimeoutExpired)),
+                        // 没有发送端(模拟发送) 时 等待一个读取超时
+                        Err(RecvTimeoutError::Disconnected) => {
+                            sleep(*timeout);
+                            return Some(Err(Error::TimeoutExpired))
+                        }
+                    };
+
+                    if let Some(p) = program {
+                        if !p.filter(&data) { continue }
+                    }
+
+                    data.truncate(*snap_len);
+                    header.caplen = data.len() as u32;
+                    return Some(Ok(codec.decode(Packet::new(&header, &data))))
+                }
+            }
+
+            #[cfg(target_os = "linux")]
+            PacketSourceIter::Xdp { receiver, program, codec, snap_len, timeout, stats, stats_check, count, received } => {
+                loop {
+                    // 在 UMEM 中 直接过滤和解析, 解析完成后 接收帧 才归还内核
+                    let decoded = receiver.recv(*timeout, |header, data| {
+                        if let Some(p) = program.as_ref() {
+                            if !p.filter(data) { return None }
+                        }
+                        let data = &data[..data.len().min(*snap_len)];
+                        let header = PacketHeader { caplen: data.len() as u32, ..*header };
+                        Some(codec.decode(Packet::new(&header, data)))
+                    });
+
+                    let item = match decoded {
+                        Some(Some(item)) => item,
+                        Some(None) => continue,
+                        None => {
+                            stats.publish(*received, receiver.dropped() as u32, 0);
+                            return Some(Err(Error::TimeoutExpired))
+                        }
+                    };
+
+                    *received = received.wrapping_add(1);
+                    *count += 1;
+                    if *count >= *stats_check {
+                        *count = 0;
+                        stats.publish(*received, receiver.dropped() as u32, 0);
+                    }
+                    return Some(Ok(item))
+                }
+            }
+        }
+    }
+}
@@ -7,10 +7,11 @@ use crate::tools::encryption_algorithm::aes::AesRand;
 
 impl PcapReceiver {
 
+    /// 处理数据包, 返回 数据包是否通过验证
     pub fn handle_packet_v6_vec(
         net_layer_header_and_data:&[u8], aes_rand:&AesRand, probe:&Box<dyn CodeProbeMethodV6>,
         output:&mut Box<dyn OutputMethod>, hash_set:&mut AHashSet<u128>, region_recorder:&mut Vec<u64>, scan_flag:u8,
-    ){
+    ) -> bool {
 
         // ipv6 首部长度固定为 40字节  注意: 包含扩展首部
         let net_layer_data = &net_layer_header_and_data[40..];
@@ -18,7 +19,7 @@ impl PcapReceiver {
         if let Some((ipv6_addr, _, region_code_vec)) = probe.receive_packet_v6(net_layer_header_and_data, net_layer_data, aes_rand) {
 
             // 优先判断标识字段
-            if region_code_vec[0] != scan_flag { return }
+            if region_code_vec[0] != scan_flag { return false }
 
             // 去除重复的
             if !hash_set.contains(&ipv6_addr) {
@@ -32,6 +33,10 @@ impl PcapReceiver {
                 
                 hash_set.insert(ipv6_addr);
             }
+
+            true
+        } else {
+            false
         }
     }
     
@@ -7,14 +7,15 @@ use crate::tools::encryption_algorithm::aes::AesRand;
 impl PcapReceiver {
 
 
+    /// 处理数据包, 返回 数据包是否通过验证
     pub fn topo_pre_scan_handle_packet_v4(ts:&libc::timeval, net_layer_header_and_data:&[u8], active_count:&mut u32,
                                                                 state_chain:&mut TopoStateChainV4, aes_rand:&AesRand,
-                                                                probe:&Box<dyn TopoMethodV4>, output:&mut Box<dyn OutputMethod>){
+                                                                probe:&Box<dyn TopoMethodV4>, output:&mut Box<dyn OutputMethod>) -> bool {
         
         let ipv4_header_len = ((net_layer_header_and_data[0] & 0b_0000_1111u8) as usize) * 4;
 
         // ipv4数据包(包括数据)的长度必须大于 首部长度
-        if ipv4_header_len > net_layer_header_and_data.len() { return }
+        if ipv4_header_len > net_layer_header_and_data.len() { return false }
 
         // 网络层数据
         let net_layer_data = &net_layer_header_and_data[ipv4_header_len..];
@@ -58,8 +59,10 @@ impl PcapReceiver {
                         state_chain.bit_map.set(responder_usize, true);
                     }
                 }
+
+                true
             }
-            None => return,
+            None => false,
         }
     }
 
@@ -1,16 +1,19 @@
 
-use std::process::exit;
 use std::sync::Arc;
 use std::sync::mpsc::{Receiver, Sender};
 use chrono::Utc;
-use log::error;
 use crate::core::conf::set_conf::base_conf::BaseConf;
 use crate::core::conf::set_conf::receiver_conf::ReceiverBaseConf;
 use crate::core::receiver::pcap::{Codec, PcapReceiver};
+use crate::core::receiver::pcap::data_link::ip_version;
+use crate::core::receiver::pcap::packet_saver::PacketSaver;
+use crate::core::receiver::abort_senders;
 use crate::modules::output_modules::OutputMethod;
 use crate::modules::probe_modules::topology_probe::topo_mod_v4::TopoModV4;
 use crate::modules::target_iterators::TopoStateChainV4;
 use crate::SYS;
+use crate::core::errors::{RunError, SmapError};
+use crate::core::receiver::pcap::source::CaptureStats;
 
 impl PcapReceiver {
 
@@ -19,21 +22,21 @@ impl PcapReceiver {
         base_conf:Arc<BaseConf>, receiver_conf:Arc<ReceiverBaseConf>,
         sub_probe_mod:Arc<TopoModV4>, sports:Vec<u16>, mut state_chain:TopoStateChainV4,
         recv_ready_sender:Sender<bool>, recv_close_time_receiver:Receiver<i64>
-    ) -> (TopoStateChainV4,Box<dyn OutputMethod>) {
+    ) -> Result<(TopoStateChainV4,Box<dyn OutputMethod>, CaptureStats), SmapError> {
 
         // 初始化 数据包捕获器
         let receiver = PcapReceiver::init(
-            &base_conf.interface[interface_index].name_index.0,
-            sub_probe_mod.snap_len_v4, &Some(sub_probe_mod.filter_v4.clone()));
+            &base_conf.interface[interface_index].name_index.0, &receiver_conf.capture_source,
+            sub_probe_mod.snap_len_v4, &Some(sub_probe_mod.filter_v4.clone()))?;
 
         // 向输出文件输入  辅助预扫描提示
-        output.writer_line(&vec!["sub_scan".to_string(), "---".to_string(), sub_probe_mod.name.to_string()]);
+        output.begin_section("sub_scan", sub_probe_mod.name);
 
         // 初始化 拓扑探测模块
-        let sub_probe = TopoModV4::init(sub_probe_mod, sports);
+        let sub_probe = TopoModV4::init(sub_probe_mod, sports)?;
         
         // 向输出文件输入首行
-        output.writer_line(&sub_probe.print_header());
+        output.writer_header(&sub_probe.print_header());
 
         // 复制加密机
         let aes_rand = base_conf.aes_rand.clone();
@@ -46,17 +49,20 @@ impl PcapReceiver {
 
         // 向主线程发送 接收线程 准备完毕的 管道消息
         if let Err(_) = recv_ready_sender.send(true) {
-            error!("{}", SYS.get_info("err","recv_ready_send_failed"));
-            exit(1)
+            return Err(RunError::channel("recv_ready_send_failed").into())
         }
 
         // 总活跃计数
         let mut active_count:u32 = 0;
 
+        // 初始化 原始数据包保存器
+        let packet_saver = PacketSaver::new(&receiver_conf, &receiver).map_err(abort_senders)?;
+
         drop(base_conf);
         drop(receiver_conf);
 
-        for packet in receiver.active_capture.iter(Codec) {
+        let mut packets = receiver.active_capture.iter(Codec);
+        for packet in packets.by_ref() {
 
             match packet {
 
@@ -64,17 +70,20 @@ impl PcapReceiver {
 
                     // 头部信息, 包含时间戳, 数据包长度等信息
                     let header = packet.header;
-                    // 网络层数据包
-                    let net_layer_data = &packet.data[receiver.data_link_len..];
+                    // 网络层数据包, 网络层数据的偏移 按数据包确定(如 vlan 标签)
+                    let (_, net_layer_data) = receiver.data_link.split(&packet.data);
 
-                    let ip_ver = net_layer_data[0] >> 4;
-                    if ip_ver == 4 {
+                    let ip_ver = ip_version(net_layer_data);
+                    let validation_passed = if ip_ver == 4 {
                         // 如果是ipv4的数据包
                         PcapReceiver::topo_sub_pre_scan_handle_packet_v4(&header.ts, &net_layer_data,&mut active_count,
-                                                                     &mut state_chain, &aes_rand, &sub_probe, &mut output);
-                    }
+                                                                     &mut state_chain, &aes_rand, &sub_probe, &mut output)
+                    } else { false };
                     // 如果是 ipv6 的包, 或者其它特殊类型, 不进行处理
 
+                    // 保存原始数据包
+                    packet_saver.save(&packet.header, &packet.data, validation_passed).map_err(abort_senders)?;
+
                     {   // 计算终止条件
                         act_count += 1;
                         if act_count % act_check_count == 0 {
@@ -136,14 +145,17 @@ impl PcapReceiver {
             }
         }
         // 向输出文件输入  辅助预扫描结束提示
-        output.writer_line(&vec!["sub_scan".to_string(), "---".to_string(), "over".to_string()]);
+        output.end_section("sub_scan");
 
         // 关闭输出
-        output.close_output();
+        output.close_output()?;
 
         // 添加 新探活的数量
         state_chain.target_count += active_count;
 
-        (state_chain, output)
+        // 读取 捕获统计, 丢包过多时 给出警告
+        let capture_stats = packets.finish();
+
+        Ok((state_chain, output, capture_stats))
     }
 }
\ No newline at end of file
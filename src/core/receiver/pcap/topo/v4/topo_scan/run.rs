@@ -1,15 +1,18 @@
-use std::process::exit;
 use std::sync::Arc;
 use std::sync::mpsc::{Receiver, Sender};
 use chrono::Utc;
-use log::error;
 use crate::core::conf::set_conf::base_conf::BaseConf;
 use crate::core::conf::set_conf::receiver_conf::ReceiverBaseConf;
 use crate::core::receiver::pcap::{Codec, PcapReceiver};
+use crate::core::receiver::pcap::data_link::ip_version;
+use crate::core::receiver::pcap::packet_saver::PacketSaver;
+use crate::core::receiver::abort_senders;
 use crate::modules::output_modules::OutputMethod;
 use crate::modules::probe_modules::topology_probe::topo_mod_v4::TopoModV4;
 use crate::modules::target_iterators::TopoStateChainV4;
 use crate::SYS;
+use crate::core::errors::{RunError, SmapError};
+use crate::core::receiver::pcap::source::CaptureStats;
 
 impl PcapReceiver {
 
@@ -18,15 +21,15 @@ impl PcapReceiver {
         base_conf:Arc<BaseConf>, receiver_conf:Arc<ReceiverBaseConf>,
         probe_mod:Arc<TopoModV4>, sports:Vec<u16>, mut state_chain:TopoStateChainV4,
         recv_ready_sender:Sender<bool>, recv_close_time_receiver:Receiver<i64>
-    ) -> (TopoStateChainV4,Box<dyn OutputMethod>)  {
+    ) -> Result<(TopoStateChainV4,Box<dyn OutputMethod>, CaptureStats), SmapError> {
 
         // 初始化 数据包捕获器
         let receiver = PcapReceiver::init(
-            &base_conf.interface[interface_index].name_index.0,
-            probe_mod.snap_len_v4, &receiver_conf.filter);
+            &base_conf.interface[interface_index].name_index.0, &receiver_conf.capture_source,
+            probe_mod.snap_len_v4, &receiver_conf.filter)?;
 
         // 初始化 拓扑探测模块
-        let probe = TopoModV4::init(probe_mod, sports);
+        let probe = TopoModV4::init(probe_mod, sports)?;
 
         // 复制加密机
         let aes_rand = base_conf.aes_rand.clone();
@@ -39,14 +42,17 @@ impl PcapReceiver {
 
         // 向主线程发送 接收线程 准备完毕的 管道消息
         if let Err(_) = recv_ready_sender.send(true) {
-            error!("{}", SYS.get_info("err","recv_ready_send_failed"));
-            exit(1)
+            return Err(RunError::channel("recv_ready_send_failed").into())
         }
 
+        // 初始化 原始数据包保存器
+        let packet_saver = PacketSaver::new(&receiver_conf, &receiver).map_err(abort_senders)?;
+
         drop(base_conf);
         drop(receiver_conf);
 
-        for packet in receiver.active_capture.iter(Codec) {
+        let mut packets = receiver.active_capture.iter(Codec);
+        for packet in packets.by_ref() {
 
             match packet {
 
@@ -54,17 +60,20 @@ impl PcapReceiver {
 
                     // 头部信息, 包含时间戳, 数据包长度等信息
                     let header = packet.header;
-                    // 网络层数据包
-                    let net_layer_data = &packet.data[receiver.data_link_len..];
+                    // 网络层数据包, 网络层数据的偏移 按数据包确定(如 vlan 标签)
+                    let (_, net_layer_data) = receiver.data_link.split(&packet.data);
 
-                    let ip_ver = net_layer_data[0] >> 4;
-                    if ip_ver == 4 {
+                    let ip_ver = ip_version(net_layer_data);
+                    let validation_passed = if ip_ver == 4 {
                         // 如果是ipv4的数据包
                         PcapReceiver::topo_scan_handle_packet_v4(&header.ts, &net_layer_data, &mut state_chain,
-                                                                 &aes_rand, &probe, &mut output);
-                    }
+                                                                 &aes_rand, &probe, &mut output)
+                    } else { false };
                     // 如果是 ipv6 的包, 或者其它特殊类型, 不进行处理
 
+                    // 保存原始数据包
+                    packet_saver.save(&packet.header, &packet.data, validation_passed).map_err(abort_senders)?;
+
                     {   // 计算终止条件
                         act_count += 1;
                         if act_count % act_check_count == 0 {
@@ -130,8 +139,11 @@ impl PcapReceiver {
         state_chain.print_silent_target(&probe, &mut output);
 
         // 关闭输出
-        output.close_output();
+        output.close_output()?;
+
+        // 读取 捕获统计, 丢包过多时 给出警告
+        let capture_stats = packets.finish();
 
-        (state_chain, output)
+        Ok((state_chain, output, capture_stats))
     }
 }
\ No newline at end of file
@@ -8,11 +8,12 @@ use crate::tools::encryption_algorithm::aes::AesRand;
 impl PcapReceiver {
 
 
+    /// 处理数据包, 返回 数据包是否通过验证
     pub fn topo_scan_handle_packet_v6(ts:&libc::timeval, net_layer_header_and_data:&[u8], state_chain:&mut TopoStateChainV6,
-                                      aes_rand:&AesRand, probe:&Box<dyn TopoMethodV6>, output:&mut Box<dyn OutputMethod>){
+                                      aes_rand:&AesRand, probe:&Box<dyn TopoMethodV6>, output:&mut Box<dyn OutputMethod>) -> bool {
 
         // ipv6数据包(包括数据)的长度必须大于 40
-        if 40 > net_layer_header_and_data.len() { return }
+        if 40 > net_layer_header_and_data.len() { return false }
 
         // 网络层数据
         let net_layer_data = &net_layer_header_and_data[40..];
@@ -53,8 +54,10 @@ impl PcapReceiver {
 
                     }
                 }
+
+                true
             }
-            None => return,
+            None => false,
         }
     }
 
@@ -7,12 +7,13 @@ use crate::tools::encryption_algorithm::aes::AesRand;
 impl PcapReceiver {
 
 
+    /// 处理数据包, 返回 数据包是否通过验证
     pub fn code_topo_prefix_scan_handle_packet_v6(ts:&libc::timeval, net_layer_header_and_data:&[u8], recorder:&mut Vec<u64>, prefix_len:usize,
                                         aes_rand:&AesRand, probe:&Box<dyn CodeTopoProbeMethodV6>, all_nodes:&mut AHashSet<u128>,
-                                        output:&mut Box<dyn OutputMethod>, only_from_dest:bool) {
+                                        output:&mut Box<dyn OutputMethod>, only_from_dest:bool) -> bool {
 
         // ipv6数据包(包括数据)的长度必须大于 40
-        if 40 > net_layer_header_and_data.len() { return }
+        if 40 > net_layer_header_and_data.len() { return false }
         // 网络层数据
         let net_layer_data = &net_layer_header_and_data[40..];
 
@@ -22,13 +23,13 @@ impl PcapReceiver {
 
                 // 如果 响应地址 和 目的地址 相等, 将被视为异常情况, 
                 // 不统计该接口地址, 并且不参与reward计算
-                if res.dest_ip == res.responder { return }
+                if res.dest_ip == res.responder { return true }
                 
                 if only_from_dest {
                     // 如果仅允许来自边缘设备的
                     if !res.from_destination {
                         // 如果不是边缘设备
-                        return
+                        return true
                     }
                 }
 
@@ -40,13 +41,15 @@ impl PcapReceiver {
 
                 // 编码不能超过前缀总数量
                 // 响应者必须为全新的
-                if code >= prefix_len || all_nodes.contains(&res.responder) { return }
+                if code >= prefix_len || all_nodes.contains(&res.responder) { return true }
                 
                 // 如果没有该响应地址, 直接加入
                 all_nodes.insert(res.responder);
                 recorder[code] += 1;
+
+                true
             }
-            None => return,
+            None => false,
         }
     }
 }
\ No newline at end of file
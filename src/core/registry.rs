@@ -0,0 +1,120 @@
+//! 模式 和 各类模块 的注册表
+//! 每个 模式 或 模块 在自身的定义处声明 注册信息(名称, 别名, 帮助信息, 自定义参数 和 构造函数),
+//! 各类别的注册表 只列出这些注册信息. 创建, 初始化 和 帮助信息 均从注册表中按名称查找
+
+use crate::core::conf::modules_config::ArgSpec;
+use crate::SYS;
+
+
+/// 注册信息
+/// N: 构造函数类型,  I: 线程初始化函数类型(不需要时为空)
+pub struct Registration<N:'static, I:'static = ()> {
+
+    /// 名称
+    pub name:&'static str,
+    /// 别名, 可代替名称使用
+    pub aliases:&'static [&'static str],
+
+    /// 帮助信息, 为空时 打印默认提示
+    pub help:Option<fn() -> String>,
+    /// 可接受的 自定义参数(-a key=value) 说明
+    pub args:&'static [ArgSpec],
+
+    /// 是否在 模块列表 中显示, 废弃 或 测试用的模块 不显示
+    pub listed:bool,
+
+    pub new:N,
+    pub init:I,
+}
+
+impl<N, I> Registration<N, I> {
+
+    /// 名称 或 别名 是否与输入一致
+    pub fn matches(&self, name:&str) -> bool {
+        self.name == name || self.aliases.contains(&name)
+    }
+}
+
+
+/// 按 名称 或 别名 查找注册信息
+pub fn find<'a, N, I>(registry:&'a [Registration<N, I>], name:&str) -> Option<&'a Registration<N, I>> {
+    registry.iter().find(|r| r.matches(name))
+}
+
+
+/// 不区分 构造函数类型 的注册表, 用于 打印模块列表 和 查找帮助信息
+pub trait Registry {
+
+    /// 需要在 模块列表 中显示的 名称
+    fn listed_names(&self) -> Vec<&'static str>;
+
+    /// 按 名称 或 别名 查找, 返回 帮助信息 及 别名 和 可接受的自定义参数; 未注册时 返回空
+    fn help_text(&self, name:&str, no_help:&str) -> Option<String>;
+}
+
+impl<N, I> Registry for &[Registration<N, I>] {
+
+    fn listed_names(&self) -> Vec<&'static str> {
+        self.iter().filter(|r| r.listed).map(|r| r.name).collect()
+    }
+
+    fn help_text(&self, name:&str, no_help:&str) -> Option<String> {
+
+        let reg = find(self, name)?;
+
+        let mut text = match reg.help {
+            Some(help) => help(),
+            None => no_help.to_string(),
+        };
+        if !reg.aliases.is_empty() {
+            text.push_str(&format!("\n{} {}", SYS.get_info("print", "module_aliases"), reg.aliases.join(", ")));
+        }
+        if !reg.args.is_empty() {
+            text.push_str(&format!("\n{}\n{}", SYS.get_info("print", "accepted_custom_args"), args_table(reg.args)));
+        }
+        Some(text)
+    }
+}
+
+
+/// 自定义参数说明表: 名称, 类型, 取值范围, 默认值, 描述
+pub fn args_table(specs:&[ArgSpec]) -> String {
+
+    let mut rows:Vec<[String; 5]> = vec![[
+        SYS.get_info("print", "mod_arg_name"), SYS.get_info("print", "mod_arg_type"),
+        SYS.get_info("print", "mod_arg_range"), SYS.get_info("print", "mod_arg_default"),
+        SYS.get_info("print", "mod_arg_desc"),
+    ]];
+    for spec in specs {
+        let default = match spec.default_val() {
+            Some(v) if v.is_empty() => "\"\"".to_string(),
+            Some(v) => v,
+            None if spec.is_required() => SYS.get_info("print", "mod_arg_required"),
+            None => "-".to_string(),
+        };
+        rows.push([spec.name.to_string(), spec.type_name().to_string(), spec.range_text(), default,
+            SYS.get_info("mod_args", spec.name)]);
+    }
+
+    // 按 显示宽度 对齐, 最后一列不补齐
+    let mut widths = [0usize; 4];
+    for row in rows.iter() {
+        for (w, cell) in widths.iter_mut().zip(row.iter()) {
+            *w = (*w).max(display_width(cell));
+        }
+    }
+    rows.iter().map(|row| {
+        let mut line = String::from("  ");
+        for (w, cell) in widths.iter().zip(row.iter()) {
+            line.push_str(cell);
+            line.push_str(&" ".repeat(w - display_width(cell) + 2));
+        }
+        line.push_str(&row[4]);
+        line
+    }).collect::<Vec<String>>().join("\n")
+}
+
+/// 终端显示宽度, 全角字符 按两列计算
+fn display_width(s:&str) -> usize {
+    s.chars().map(|c| if c >= '\u{2E80}' { 2 } else { 1 }).sum()
+}
@@ -39,4 +39,7 @@ pub use v6::send_v6_code_vec;
 pub use v6::send_v6_code_port_vec;
 pub use v6::send_prefixes_v6;
 
-pub use v6::send_prefixes_v6_2;
\ No newline at end of file
+pub use v6::send_prefixes_v6_2;
+
+// 发送中止标志
+pub use tools::abort::{request_abort, reset_abort};
@@ -0,0 +1,21 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+// 发送中止标志: 接收线程出错时置位, 各发送线程在每个批次结束时检查并尽快退出
+static SEND_ABORT: AtomicBool = AtomicBool::new(false);
+
+
+/// 请求所有发送线程停止发送
+pub fn request_abort() {
+    SEND_ABORT.store(true, Ordering::Relaxed);
+}
+
+/// 是否已请求停止发送
+#[inline]
+pub fn abort_requested() -> bool {
+    SEND_ABORT.load(Ordering::Relaxed)
+}
+
+/// 清除中止标志, 在每次扫描开始前调用
+pub fn reset_abort() {
+    SEND_ABORT.store(false, Ordering::Relaxed);
+}
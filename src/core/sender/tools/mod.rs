@@ -1,4 +1,5 @@
 
 
+pub mod abort;
 pub mod rate_controller;
 pub mod source_ip_iter;
@@ -16,6 +16,8 @@ pub struct RateController{
 
     integral_term:f64,
     prev_m:Option<f64>,
+    // 最近一次的 pid 输出, 以 每微秒 计
+    pid_out:f64,
 
 
     start_time:i64,
@@ -61,6 +63,7 @@ impl RateController {
 
             integral_term:0.0,
             prev_m:None,
+            pid_out:0.0,
 
             must_sleep,                 // 必须休眠的时间
 
@@ -113,6 +116,7 @@ impl RateController {
 
         // pid 控制
         let pid_out = self.next_control_output(now_total_rate);
+        self.pid_out = pid_out;
         // ( pid_out + now_total_rate )  *  ( used_time_micros  +  ?  ) = self.total_count
 
         let pid_rate = pid_out + now_total_rate;        // 计算指导速率
@@ -149,6 +153,11 @@ impl RateController {
 
     }
 
+    /// 最近一次的 pid 输出, 转换为 每秒多少数据包
+    pub fn pid_output(&self) -> f64 {
+        self.pid_out * 1_000_000.0
+    }
+
     #[inline]
     fn next_control_output(&mut self, measurement:f64) -> f64{
 
@@ -1,7 +1,5 @@
 use std::net::Ipv4Addr;
-use log::error;
-use crate::SYS;
-use std::process::exit;
+use crate::core::errors::ConfError;
 
 pub struct SourceIpIterV4 {
 
@@ -15,11 +13,10 @@ pub struct SourceIpIterV4 {
 impl SourceIpIterV4 {
 
 
-    pub fn new(addrs:&Vec<Ipv4Addr>) -> Self {
+    pub fn new(addrs:&[Ipv4Addr]) -> Result<Self, ConfError> {
 
-        if addrs.len() == 0 {
-            error!("{}", SYS.get_info("err", "source_ips_is_null_v4"));
-            exit(1)
+        if addrs.is_empty() {
+            return Err(ConfError::missing("source_ips_is_null_v4"))
         }
 
         let mut addrs_u32 = vec![];
@@ -27,11 +24,11 @@ impl SourceIpIterV4 {
             addrs_u32.push(u32::from(*i));
         }
 
-        Self {
+        Ok(Self {
             addrs:addrs_u32,
             len: addrs.len(),
             index: 0,
-        }
+        })
 
     }
 
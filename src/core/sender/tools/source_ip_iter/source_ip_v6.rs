@@ -1,7 +1,5 @@
 use std::net::Ipv6Addr;
-use std::process::exit;
-use log::error;
-use crate::SYS;
+use crate::core::errors::ConfError;
 
 pub struct SourceIpIterV6 {
 
@@ -15,11 +13,10 @@ pub struct SourceIpIterV6 {
 impl SourceIpIterV6 {
 
 
-    pub fn new(addrs:&Vec<Ipv6Addr>) -> Self {
+    pub fn new(addrs:&[Ipv6Addr]) -> Result<Self, ConfError> {
 
-        if addrs.len() == 0 {
-            error!("{}", SYS.get_info("err", "source_ips_is_null_v6"));
-            exit(1)
+        if addrs.is_empty() {
+            return Err(ConfError::missing("source_ips_is_null_v6"))
         }
 
         let mut addrs_u128 = vec![];
@@ -27,11 +24,11 @@ impl SourceIpIterV6 {
             addrs_u128.push(u128::from(*i));
         }
 
-        Self {
+        Ok(Self {
             addrs:addrs_u128,
             len: addrs.len(),
             index: 0,
-        }
+        })
 
     }
 
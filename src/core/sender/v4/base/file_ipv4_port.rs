@@ -1,10 +1,9 @@
 use std::net;
 use std::sync::Arc;
-use log::debug;
-use crate::SYS;
 use crate::core::conf::set_conf::base_conf::BaseConf;
 use crate::core::conf::set_conf::sender_conf::SenderBaseConf;
 use crate::core::sender::tools::rate_controller::RateController;
+use crate::core::sender::tools::abort::abort_requested;
 use crate::core::sender::tools::source_ip_iter::source_ip_v4::SourceIpIterV4;
 use crate::modules::probe_modules::probe_mod_v4::ProbeModV4;
 use crate::modules::target_iterators::{Ipv4IterFP};
@@ -21,6 +20,7 @@ target_os = "macos",
 target_os = "ios",
 target_os = "linux"))]
 use crate::core::sys::packet_sender::PacketSender;
+use crate::core::errors::SmapError;
 
 
 #[cfg(any(
@@ -34,12 +34,12 @@ target_os = "ios",
 target_os = "linux"))]
 pub fn send_file_v4_port<T:Ipv4IterFP>(interface_index:usize, mut target_iter:T, local_tar_num:u64,
                            blocker:BlackWhiteListV4, probe_mod_v4: Arc<ProbeModV4>, ttl:Option<u8>,
-                           base_conf:Arc<BaseConf>, sender_conf:Arc<SenderBaseConf>) -> (u64, u64, u64) {
+                           base_conf:Arc<BaseConf>, sender_conf:Arc<SenderBaseConf>) -> Result<(u64, u64, u64), SmapError> {
 
 
     // 初始化 数据包发送器
-    let sender = PacketSender::new(
-        &base_conf.interface[interface_index].name_index, &base_conf.interface[interface_index].gateway_mac);
+    let sender = PacketSender::new(&sender_conf.send_backend,
+        &base_conf.interface[interface_index].name_index, &base_conf.interface[interface_index].gateway_mac)?;
 
     // 取出常用变量
     let batch_size = sender_conf.global_rate_conf.batch_size;
@@ -52,10 +52,13 @@ pub fn send_file_v4_port<T:Ipv4IterFP>(interface_index:usize, mut target_iter:T,
     let mut total_blocked:u64 = 0;
 
     // 初始化 源地址迭代器
-    let mut source_ip_iter = SourceIpIterV4::new(&sender_conf.source_addrs_v4[interface_index]);
+    let mut source_ip_iter = SourceIpIterV4::new(&sender_conf.source_addrs_v4[interface_index])?;
+
+    // 初始化 批量发送缓冲区, 探测模块 直接将数据包写入其中的槽位
+    let mut batch = sender.new_batch(batch_size, probe_mod_v4.max_packet_length_v4);
 
     // 初始化 探测模块
-    let mut probe = ProbeModV4::init(probe_mod_v4, sender_conf.source_ports.clone());
+    let mut probe = ProbeModV4::init(probe_mod_v4, sender_conf.source_ports.clone())?;
 
     // 探测模块线程初始化
     // 建议: 由 探测模块 生成 原始数据包缓冲区, 包含所有数据包中不变的内容, 后续改动直接在此基础上修改
@@ -86,26 +89,15 @@ pub fn send_file_v4_port<T:Ipv4IterFP>(interface_index:usize, mut target_iter:T,
 
                         let cur_source_ip = source_ip_iter.get_src_ip_with_change();
 
-                        // 由探测模块生成数据包
-                        let packet = probe.make_packet_v4(
-                            cur_source_ip, cur_target.2, cur_target.3, ttl, &aes_rand);
-
-                        let mut sent_successfully = false;
-                        for _ in 0..send_attempts {
-                            let res = sender.send_packet(&packet);
+                        // 由探测模块 将数据包写入发送槽位
+                        let packet = batch.next_slot(Ipv4Addr::from(cur_target.2).into(), cur_target.3);
+                        probe.write_packet_v4(cur_source_ip, cur_target.2, cur_target.3, ttl, &aes_rand, packet);
 
-                            if res >= 0 {
-                                sent_successfully = true;
-                                break;
-                            }
-                        }
-                        if sent_successfully {
-                            // 统计发送成功的数据包
-                            send_success += 1;
-                        } else {
-                            // 统计发送失败的数据包
-                            debug!("{} {} {}", SYS.get_info("debug", "send_failed"), Ipv4Addr::from(cur_target.2), cur_target.3);
-                            send_failed += 1;
+                        // 槽位已满时 先发送已写入的数据包
+                        if batch.is_full() {
+                            let (success, failed) = sender.send_batch(&mut batch, send_attempts);
+                            send_success += success;
+                            send_failed += failed;
                         }
                     } else {
                         total_blocked += 1;
@@ -127,26 +119,15 @@ pub fn send_file_v4_port<T:Ipv4IterFP>(interface_index:usize, mut target_iter:T,
 
                         let cur_source_ip = source_ip_iter.get_src_ip_with_change();
 
-                        // 由探测模块生成数据包
-                        let packet = probe.make_packet_v4(
-                            cur_source_ip, cur_target.2, cur_target.3, ttl, &aes_rand);
+                        // 由探测模块 将数据包写入发送槽位
+                        let packet = batch.next_slot(Ipv4Addr::from(cur_target.2).into(), cur_target.3);
+                        probe.write_packet_v4(cur_source_ip, cur_target.2, cur_target.3, ttl, &aes_rand, packet);
 
-                        let mut sent_successfully = false;
-                        for _ in 0..send_attempts {
-                            let res = sender.send_packet(&packet);
-
-                            if res >= 0 {
-                                sent_successfully = true;
-                                break;
-                            }
-                        }
-                        if sent_successfully {
-                            // 统计发送成功的数据包
-                            send_success += 1;
-                        } else {
-                            // 统计发送失败的数据包
-                            debug!("{} {} {}", SYS.get_info("debug", "send_failed"), Ipv4Addr::from(cur_target.2), cur_target.3);
-                            send_failed += 1;
+                        // 槽位已满时 先发送已写入的数据包
+                        if batch.is_full() {
+                            let (success, failed) = sender.send_batch(&mut batch, send_attempts);
+                            send_success += success;
+                            send_failed += failed;
                         }
                     } else {
                         total_blocked += 1;
@@ -160,9 +141,18 @@ pub fn send_file_v4_port<T:Ipv4IterFP>(interface_index:usize, mut target_iter:T,
                 break 'big_batch;
             }
         }
+        // 发送 本轮次剩余的数据包
+        let (success, failed) = sender.send_batch(&mut batch, send_attempts);
+        send_success += success;
+        send_failed += failed;
         rate_controller.sleep();
+        if abort_requested() { break 'big_batch; }
     }
 
-    (send_success, send_failed, total_blocked)
+    // 发送 剩余的数据包
+    let (success, failed) = sender.send_batch(&mut batch, send_attempts);
+    send_success += success;
+    send_failed += failed;
+    Ok((send_success, send_failed, total_blocked))
 }
 
@@ -4,22 +4,27 @@ use crate::SYS;
 use crate::core::conf::set_conf::base_conf::BaseConf;
 use crate::core::conf::set_conf::sender_conf::SenderBaseConf;
 use crate::core::sender::tools::rate_controller::RateController;
+use crate::core::sender::tools::abort::abort_requested;
+use crate::core::monitor::SendCounter;
+use crate::core::checkpoint::SenderCheckpoint;
 use crate::core::sender::tools::source_ip_iter::source_ip_v4::SourceIpIterV4;
 use crate::core::sys::packet_sender::PcapSender;
 use crate::modules::probe_modules::probe_mod_v4::ProbeModV4;
-use crate::modules::target_iterators::Ipv4IterP;
+use crate::modules::target_iterators::{CyclePosition, Ipv4IterP};
 use crate::tools::blocker::ipv4_blocker::BlackWhiteListV4;
+use crate::core::errors::SmapError;
 
 
-pub fn send_v4_port<T:Ipv4IterP>(interface_index:usize,
+pub fn send_v4_port<T:Ipv4IterP + CyclePosition>(interface_index:usize,
                            mut target_iter:T, local_tar_num:u64,
                            blocker:BlackWhiteListV4, probe_mod_v4: Arc<ProbeModV4>, ttl:Option<u8>,
-                           base_conf:Arc<BaseConf>, sender_conf:Arc<SenderBaseConf>) -> (u64, u64, u64) {
+                           base_conf:Arc<BaseConf>, sender_conf:Arc<SenderBaseConf>,
+                           mut checkpoint:SenderCheckpoint) -> Result<(u64, u64, u64), SmapError> {
 
     // 初始化 pcap 发包器
     let (mut pcap_sender, mut send_queue, batch_size) = PcapSender::init(
         &base_conf.interface[interface_index].name_index.0, sender_conf.global_rate_conf.batch_size,
-        probe_mod_v4.max_packet_length_v4);
+        probe_mod_v4.max_packet_length_v4)?;
 
 
     // 取出常用变量
@@ -32,11 +37,14 @@ pub fn send_v4_port<T:Ipv4IterP>(interface_index:usize,
     let mut total_send_failed:u64 = 0;
     let mut total_blocked:u64 = 0;
 
+    // 每个发送轮次结束时 将统计信息 发布到扫描进度计数器
+    let mut send_counter = SendCounter::new(&base_conf.interface[interface_index].name_index.0);
+
     // 初始化 源地址迭代器
-    let mut source_ip_iter = SourceIpIterV4::new(&sender_conf.source_addrs_v4[interface_index]);
+    let mut source_ip_iter = SourceIpIterV4::new(&sender_conf.source_addrs_v4[interface_index])?;
 
     // 初始化 探测模块
-    let mut probe = ProbeModV4::init(probe_mod_v4, sender_conf.source_ports.clone());
+    let mut probe = ProbeModV4::init(probe_mod_v4, sender_conf.source_ports.clone())?;
 
     // 探测模块线程初始化
     // 建议: 由 探测模块 生成 原始数据包缓冲区, 包含所有数据包中不变的内容, 后续改动直接在此基础上修改
@@ -156,6 +164,7 @@ pub fn send_v4_port<T:Ipv4IterP>(interface_index:usize,
                 }
 
                 // 处理完最终值, 直接退出大循环
+                checkpoint.finish();
                 break 'big_batch;
             }
         }
@@ -180,9 +189,14 @@ pub fn send_v4_port<T:Ipv4IterP>(interface_index:usize,
             }
         }
 
+        send_counter.publish(total_send_success, total_send_failed, total_blocked, rate_controller.pid_output());
+        checkpoint.save(target_iter.position(), total_send_success, total_send_failed, total_blocked);
         rate_controller.sleep();
+        if abort_requested() { break 'big_batch; }
     }
 
-    (total_send_success, total_send_failed, total_blocked)
+    send_counter.publish(total_send_success, total_send_failed, total_blocked, rate_controller.pid_output());
+    checkpoint.save(target_iter.position(), total_send_success, total_send_failed, total_blocked);
+    Ok((total_send_success, total_send_failed, total_blocked))
 }
 
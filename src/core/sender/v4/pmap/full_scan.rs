@@ -5,14 +5,15 @@
 
 use std::net;
 use std::sync::Arc;
-use log::debug;
-use crate::SYS;
 use crate::core::conf::set_conf::base_conf::BaseConf;
 use crate::core::conf::set_conf::sender_conf::SenderBaseConf;
 use crate::core::sender::tools::rate_controller::RateController;
+use crate::core::sender::tools::abort::abort_requested;
+use crate::core::monitor::SendCounter;
+use crate::core::checkpoint::{SenderCheckpoint, SenderResume};
 use crate::core::sender::tools::source_ip_iter::source_ip_v4::SourceIpIterV4;
 use crate::modules::probe_modules::probe_mod_v4::ProbeModV4;
-use crate::modules::target_iterators::{Ipv4Iter};
+use crate::modules::target_iterators::{CyclePosition, Ipv4Iter};
 use crate::tools::blocker::ipv4_blocker::BlackWhiteListV4;
 use net::Ipv4Addr;
 
@@ -26,6 +27,7 @@ target_os = "macos",
 target_os = "ios",
 target_os = "linux"))]
 use crate::core::sys::packet_sender::PacketSender;
+use crate::core::errors::SmapError;
 
 
 #[cfg(any(
@@ -39,12 +41,13 @@ target_os = "ios",
 target_os = "linux"))]
 pub fn pmap_full_scan_send_v4<T>(interface_index:usize, original_target_iter:T,
                                  blocker:BlackWhiteListV4, probe_mod_v4: Arc<ProbeModV4>, tar_ports:Vec<u16>,
-                                 base_conf:Arc<BaseConf>, sender_conf:Arc<SenderBaseConf>) -> (u64, u64, u64)
-    where T: Ipv4Iter + Clone {
+                                 base_conf:Arc<BaseConf>, sender_conf:Arc<SenderBaseConf>,
+                                 mut checkpoint:SenderCheckpoint) -> Result<(u64, u64, u64), SmapError>
+    where T: Ipv4Iter + CyclePosition + Clone {
 
     // 初始化 数据包发送器
-    let sender = PacketSender::new(
-        &base_conf.interface[interface_index].name_index, &base_conf.interface[interface_index].gateway_mac);
+    let sender = PacketSender::new(&sender_conf.send_backend,
+        &base_conf.interface[interface_index].name_index, &base_conf.interface[interface_index].gateway_mac)?;
 
     // 取出常用变量
     let batch_size = sender_conf.global_rate_conf.batch_size;
@@ -56,11 +59,17 @@ pub fn pmap_full_scan_send_v4<T>(interface_index:usize, original_target_iter:T,
     let mut send_failed:u64 = 0;
     let mut total_blocked:u64 = 0;
 
+    // 每个发送轮次结束时 将统计信息 发布到扫描进度计数器
+    let mut send_counter = SendCounter::new(&base_conf.interface[interface_index].name_index.0);
+
     // 初始化 源地址迭代器
-    let mut source_ip_iter = SourceIpIterV4::new(&sender_conf.source_addrs_v4[interface_index]);
+    let mut source_ip_iter = SourceIpIterV4::new(&sender_conf.source_addrs_v4[interface_index])?;
+
+    // 初始化 批量发送缓冲区, 探测模块 直接将数据包写入其中的槽位
+    let mut batch = sender.new_batch(batch_size, probe_mod_v4.max_packet_length_v4);
 
     // 初始化 探测模块
-    let mut probe = ProbeModV4::init(probe_mod_v4, sender_conf.source_ports.clone());
+    let mut probe = ProbeModV4::init(probe_mod_v4, sender_conf.source_ports.clone())?;
 
     // 探测模块线程初始化
     probe.thread_initialize_v4(&base_conf.interface[interface_index].local_mac,
@@ -74,11 +83,21 @@ pub fn pmap_full_scan_send_v4<T>(interface_index:usize, original_target_iter:T,
     drop(base_conf);
     drop(sender_conf);
 
+    // 续扫时 跳过已完成的端口, 断点所在的端口 从断点位置开始
+    let (resume_round, resume_position) = match checkpoint.resume_point() {
+        SenderResume::At { round, position } => (round as usize, Some(position)),
+        _ => (0, None),
+    };
+
     // 每次取出一个端口, 对所有地址的该端口进行探测
-    for port in tar_ports.into_iter() {
+    for (round, port) in tar_ports.into_iter().enumerate().skip(resume_round) {
 
         // 复制原始迭代器
         let mut target_iter = original_target_iter.clone();
+        if let Some(position) = resume_position.filter(|_| round == resume_round) {
+            target_iter.seek(position);
+        }
+        checkpoint.set_round(round as u64);
 
         // 获得首个目标     0:是否为非最终值, 1:最终值是否有效, 2:ip地址
         let mut cur_target = target_iter.get_first_ip();
@@ -93,26 +112,15 @@ pub fn pmap_full_scan_send_v4<T>(interface_index:usize, original_target_iter:T,
 
                         let cur_source_ip = source_ip_iter.get_src_ip_with_change();
 
-                        // 由探测模块生成数据包
-                        let packet = probe.make_packet_v4(
-                            cur_source_ip, cur_target.2, port, None, &aes_rand);
-
-                        let mut sent_successfully = false;
-                        for _ in 0..send_attempts {
-                            let res = sender.send_packet(&packet);
+                        // 由探测模块 将数据包写入发送槽位
+                        let packet = batch.next_slot(Ipv4Addr::from(cur_target.2).into(), port);
+                        probe.write_packet_v4(cur_source_ip, cur_target.2, port, None, &aes_rand, packet);
 
-                            if res >= 0 {
-                                sent_successfully = true;
-                                break;
-                            }
-                        }
-                        if sent_successfully {
-                            // 统计发送成功的数据包
-                            send_success += 1;
-                        } else {
-                            // 统计发送失败的数据包
-                            debug!("{} {} {}", SYS.get_info("debug", "send_failed"), Ipv4Addr::from(cur_target.2), 0);
-                            send_failed += 1;
+                        // 槽位已满时 先发送已写入的数据包
+                        if batch.is_full() {
+                            let (success, failed) = sender.send_batch(&mut batch, send_attempts);
+                            send_success += success;
+                            send_failed += failed;
                         }
                     } else {
                         total_blocked += 1;
@@ -131,26 +139,15 @@ pub fn pmap_full_scan_send_v4<T>(interface_index:usize, original_target_iter:T,
 
                             let cur_source_ip = source_ip_iter.get_src_ip_with_change();
 
-                            // 由探测模块生成数据包
-                            let packet = probe.make_packet_v4(
-                                cur_source_ip, cur_target.2, port, None, &aes_rand);
-
-                            let mut sent_successfully = false;
-                            for _ in 0..send_attempts {
-                                let res = sender.send_packet(&packet);
+                            // 由探测模块 将数据包写入发送槽位
+                            let packet = batch.next_slot(Ipv4Addr::from(cur_target.2).into(), port);
+                            probe.write_packet_v4(cur_source_ip, cur_target.2, port, None, &aes_rand, packet);
 
-                                if res >= 0 {
-                                    sent_successfully = true;
-                                    break;
-                                }
-                            }
-                            if sent_successfully {
-                                // 统计发送成功的数据包
-                                send_success += 1;
-                            } else {
-                                // 统计发送失败的数据包
-                                debug!("{} {} {}", SYS.get_info("debug", "send_failed"), Ipv4Addr::from(cur_target.2), 0);
-                                send_failed += 1;
+                            // 槽位已满时 先发送已写入的数据包
+                            if batch.is_full() {
+                                let (success, failed) = sender.send_batch(&mut batch, send_attempts);
+                                send_success += success;
+                                send_failed += failed;
                             }
                         } else {
                             total_blocked += 1;
@@ -160,11 +157,29 @@ pub fn pmap_full_scan_send_v4<T>(interface_index:usize, original_target_iter:T,
                     break 'big_batch;
                 }
             }
+            // 发送 本轮次剩余的数据包
+            let (success, failed) = sender.send_batch(&mut batch, send_attempts);
+            send_success += success;
+            send_failed += failed;
+            send_counter.publish(send_success, send_failed, total_blocked, rate_controller.pid_output());
+            checkpoint.save(target_iter.position(), send_success, send_failed, total_blocked);
             rate_controller.sleep();
+            if abort_requested() { break 'big_batch; }
         }
+        // 中止时 不再探测其余端口, 保留最近一次记录的断点
+        if abort_requested() { break }
     }
 
-    (send_success, send_failed, total_blocked)
+    // 发送 剩余的数据包
+    let (success, failed) = sender.send_batch(&mut batch, send_attempts);
+    send_success += success;
+    send_failed += failed;
+    send_counter.publish(send_success, send_failed, total_blocked, rate_controller.pid_output());
+    if !abort_requested() {
+        checkpoint.finish();
+        checkpoint.save(0, send_success, send_failed, total_blocked);
+    }
+    Ok((send_success, send_failed, total_blocked))
 }
 
 
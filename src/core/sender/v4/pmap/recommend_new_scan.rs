@@ -6,6 +6,8 @@ use crate::SYS;
 use crate::core::conf::set_conf::base_conf::BaseConf;
 use crate::core::conf::set_conf::sender_conf::SenderBaseConf;
 use crate::core::sender::tools::rate_controller::RateController;
+use crate::core::sender::tools::abort::abort_requested;
+use crate::core::monitor::SendCounter;
 use crate::core::sender::tools::source_ip_iter::source_ip_v4::SourceIpIterV4;
 use crate::modules::probe_modules::probe_mod_v4::ProbeModV4;
 use crate::modules::target_iterators::{Ipv4Iter, PmapGraph, PmapIpStruct, PmapIterV4};
@@ -22,6 +24,7 @@ target_os = "macos",
 target_os = "ios",
 target_os = "linux"))]
 use crate::core::sys::packet_sender::PacketSender;
+use crate::core::errors::SmapError;
 
 
 #[cfg(any(
@@ -35,11 +38,11 @@ target_os = "ios",
 target_os = "linux"))]
 pub fn pmap_recommend_new_scan_send_v4_port(interface_index:usize, mut target_iter:PmapIterV4,
                                             blocker:BlackWhiteListV4, probe_mod_v4: Arc<ProbeModV4>, graph:Arc<PmapGraph>,
-                                            base_conf:Arc<BaseConf>, sender_conf:Arc<SenderBaseConf>) -> (u64, u64, u64, PmapIterV4) {
+                                            base_conf:Arc<BaseConf>, sender_conf:Arc<SenderBaseConf>) -> Result<(u64, u64, u64, PmapIterV4), SmapError> {
 
     // 初始化 数据包发送器
-    let sender = PacketSender::new(
-        &base_conf.interface[interface_index].name_index, &base_conf.interface[interface_index].gateway_mac);
+    let sender = PacketSender::new(&sender_conf.send_backend,
+        &base_conf.interface[interface_index].name_index, &base_conf.interface[interface_index].gateway_mac)?;
 
     // 取出常用变量
     let batch_size = sender_conf.global_rate_conf.batch_size;
@@ -51,11 +54,14 @@ pub fn pmap_recommend_new_scan_send_v4_port(interface_index:usize, mut target_it
     let mut send_failed:u64 = 0;
     let mut total_blocked:u64 = 0;
 
+    // 每个发送轮次结束时 将统计信息 发布到扫描进度计数器
+    let mut send_counter = SendCounter::new(&base_conf.interface[interface_index].name_index.0);
+
     // 初始化 源地址迭代器
-    let mut source_ip_iter = SourceIpIterV4::new(&sender_conf.source_addrs_v4[interface_index]);
+    let mut source_ip_iter = SourceIpIterV4::new(&sender_conf.source_addrs_v4[interface_index])?;
 
     // 初始化 探测模块
-    let mut probe = ProbeModV4::init(probe_mod_v4, sender_conf.source_ports.clone());
+    let mut probe = ProbeModV4::init(probe_mod_v4, sender_conf.source_ports.clone())?;
 
     // 探测模块线程初始化
     probe.thread_initialize_v4(&base_conf.interface[interface_index].local_mac,
@@ -68,7 +74,7 @@ pub fn pmap_recommend_new_scan_send_v4_port(interface_index:usize, mut target_it
 
     // 注意: 同一网络内所有地址的初始状态和初始推荐端口都一致
     let mut first_ip_struct = PmapIpStruct::new();
-    let first_port = first_ip_struct.send_port(&graph);
+    let first_port = first_ip_struct.send_port(&graph)?;
     let first_ip_struct = first_ip_struct;
 
     // 初始化 PID速率控制器         tar_num设为0, 强制使用 全局指导速率
@@ -162,7 +168,9 @@ pub fn pmap_recommend_new_scan_send_v4_port(interface_index:usize, mut target_it
                 break 'big_batch;
             }
         }
+        send_counter.publish(send_success, send_failed, total_blocked, rate_controller.pid_output());
         rate_controller.sleep();
+        if abort_requested() { break 'big_batch; }
     }
 
     // 将 保存所有地址状态信息的向量 中的 冗余容量 进行清除
@@ -171,7 +179,8 @@ pub fn pmap_recommend_new_scan_send_v4_port(interface_index:usize, mut target_it
     // 重置引导迭代器
     target_iter.reset_guide_iter();
 
-    (send_success, send_failed, total_blocked, target_iter)
+    send_counter.publish(send_success, send_failed, total_blocked, rate_controller.pid_output());
+    Ok((send_success, send_failed, total_blocked, target_iter))
 }
 
 
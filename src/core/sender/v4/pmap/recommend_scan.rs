@@ -9,6 +9,8 @@ use crate::SYS;
 use crate::core::conf::set_conf::base_conf::BaseConf;
 use crate::core::conf::set_conf::sender_conf::SenderBaseConf;
 use crate::core::sender::tools::rate_controller::RateController;
+use crate::core::sender::tools::abort::abort_requested;
+use crate::core::monitor::SendCounter;
 use crate::core::sender::tools::source_ip_iter::source_ip_v4::SourceIpIterV4;
 use crate::modules::probe_modules::probe_mod_v4::ProbeModV4;
 use crate::modules::target_iterators::{Ipv4Iter, PmapGraph, PmapIterV4};
@@ -25,6 +27,7 @@ target_os = "macos",
 target_os = "ios",
 target_os = "linux"))]
 use crate::core::sys::packet_sender::PacketSender;
+use crate::core::errors::SmapError;
 
 
 #[cfg(any(
@@ -38,11 +41,11 @@ target_os = "ios",
 target_os = "linux"))]
 pub fn pmap_recommend_scan_send_v4_port(interface_index:usize, mut target_iter:PmapIterV4,
                                         blocker:BlackWhiteListV4, probe_mod_v4: Arc<ProbeModV4>, graph:Arc<PmapGraph>,
-                                        base_conf:Arc<BaseConf>, sender_conf:Arc<SenderBaseConf>) -> (u64, u64, u64, PmapIterV4) {
+                                        base_conf:Arc<BaseConf>, sender_conf:Arc<SenderBaseConf>) -> Result<(u64, u64, u64, PmapIterV4), SmapError> {
 
     // 初始化 数据包发送器
-    let sender = PacketSender::new(
-        &base_conf.interface[interface_index].name_index, &base_conf.interface[interface_index].gateway_mac);
+    let sender = PacketSender::new(&sender_conf.send_backend,
+        &base_conf.interface[interface_index].name_index, &base_conf.interface[interface_index].gateway_mac)?;
 
     // 取出常用变量
     let batch_size = sender_conf.global_rate_conf.batch_size;
@@ -54,11 +57,14 @@ pub fn pmap_recommend_scan_send_v4_port(interface_index:usize, mut target_iter:P
     let mut send_failed:u64 = 0;
     let mut total_blocked:u64 = 0;
 
+    // 每个发送轮次结束时 将统计信息 发布到扫描进度计数器
+    let mut send_counter = SendCounter::new(&base_conf.interface[interface_index].name_index.0);
+
     // 初始化 源地址迭代器
-    let mut source_ip_iter = SourceIpIterV4::new(&sender_conf.source_addrs_v4[interface_index]);
+    let mut source_ip_iter = SourceIpIterV4::new(&sender_conf.source_addrs_v4[interface_index])?;
 
     // 初始化 探测模块
-    let mut probe = ProbeModV4::init(probe_mod_v4, sender_conf.source_ports.clone());
+    let mut probe = ProbeModV4::init(probe_mod_v4, sender_conf.source_ports.clone())?;
 
     // 探测模块线程初始化
     probe.thread_initialize_v4(&base_conf.interface[interface_index].local_mac,
@@ -87,7 +93,7 @@ pub fn pmap_recommend_scan_send_v4_port(interface_index:usize, mut target_iter:P
                 if blocker.ip_is_avail(cur_target.2) {
                     // 如果没被黑名单阻止
 
-                    let cur_port = target_iter.ips_struct[cur_tar_index].send_port(graph_ptr);
+                    let cur_port = target_iter.ips_struct[cur_tar_index].send_port(graph_ptr)?;
                     // 注意: cur_tar_index 是有效ip的索引, 每执行一次端口推荐, 索引值加一
                     cur_tar_index += 1;
                     let cur_source_ip = source_ip_iter.get_src_ip_with_change();
@@ -128,7 +134,7 @@ pub fn pmap_recommend_scan_send_v4_port(interface_index:usize, mut target_iter:P
                     if blocker.ip_is_avail(cur_target.2) {
                         // 如果当前 ip 被放行
 
-                        let cur_port = target_iter.ips_struct[cur_tar_index].send_port(graph_ptr);
+                        let cur_port = target_iter.ips_struct[cur_tar_index].send_port(graph_ptr)?;
                         // 如果是最后一个有效ip, 就不需要让索引号自增
                         let cur_source_ip = source_ip_iter.get_src_ip_with_change();
 
@@ -162,13 +168,16 @@ pub fn pmap_recommend_scan_send_v4_port(interface_index:usize, mut target_iter:P
                 break 'big_batch;
             }
         }
+        send_counter.publish(send_success, send_failed, total_blocked, rate_controller.pid_output());
         rate_controller.sleep();
+        if abort_requested() { break 'big_batch; }
     }
 
     // 重置引导迭代器
     target_iter.reset_guide_iter();
 
-    (send_success, send_failed, total_blocked, target_iter)
+    send_counter.publish(send_success, send_failed, total_blocked, rate_controller.pid_output());
+    Ok((send_success, send_failed, total_blocked, target_iter))
 }
 
 
@@ -7,23 +7,28 @@ use crate::SYS;
 use crate::core::conf::set_conf::base_conf::BaseConf;
 use crate::core::conf::set_conf::sender_conf::SenderBaseConf;
 use crate::core::sender::tools::rate_controller::RateController;
+use crate::core::sender::tools::abort::abort_requested;
+use crate::core::monitor::SendCounter;
+use crate::core::checkpoint::{SenderCheckpoint, SenderResume};
 use crate::core::sender::tools::source_ip_iter::source_ip_v4::SourceIpIterV4;
 use crate::core::sys::packet_sender::PcapSender;
 use crate::modules::probe_modules::probe_mod_v4::ProbeModV4;
-use crate::modules::target_iterators::{Ipv4Iter};
+use crate::modules::target_iterators::{CyclePosition, Ipv4Iter};
 use crate::tools::blocker::ipv4_blocker::BlackWhiteListV4;
+use crate::core::errors::SmapError;
 
 
 /// 对 所有地址 的 全部目标端口 进行探测, 每次从目标端口向量取出一个端口, 对所有地址进行探测
 pub fn pmap_full_scan_send_v4<T>(interface_index:usize, original_target_iter:T,
                                  blocker:BlackWhiteListV4, probe_mod_v4: Arc<ProbeModV4>, tar_ports:Vec<u16>,
-                                 base_conf:Arc<BaseConf>, sender_conf:Arc<SenderBaseConf>) -> (u64, u64, u64)
-    where T: Ipv4Iter + Clone {
+                                 base_conf:Arc<BaseConf>, sender_conf:Arc<SenderBaseConf>,
+                                 mut checkpoint:SenderCheckpoint) -> Result<(u64, u64, u64), SmapError>
+    where T: Ipv4Iter + CyclePosition + Clone {
 
     // 初始化 pcap 发包器
     let (mut pcap_sender, mut send_queue, batch_size) = PcapSender::init(
         &base_conf.interface[interface_index].name_index.0, sender_conf.global_rate_conf.batch_size,
-        probe_mod_v4.max_packet_length_v4);
+        probe_mod_v4.max_packet_length_v4)?;
 
     // 取出常用变量
     let send_attempts = sender_conf.send_attempt_num;
@@ -35,11 +40,14 @@ pub fn pmap_full_scan_send_v4<T>(interface_index:usize, original_target_iter:T,
     let mut total_send_failed:u64 = 0;
     let mut total_blocked:u64 = 0;
 
+    // 每个发送轮次结束时 将统计信息 发布到扫描进度计数器
+    let mut send_counter = SendCounter::new(&base_conf.interface[interface_index].name_index.0);
+
     // 初始化 源地址迭代器
-    let mut source_ip_iter = SourceIpIterV4::new(&sender_conf.source_addrs_v4[interface_index]);
+    let mut source_ip_iter = SourceIpIterV4::new(&sender_conf.source_addrs_v4[interface_index])?;
 
     // 初始化 探测模块
-    let mut probe = ProbeModV4::init(probe_mod_v4, sender_conf.source_ports.clone());
+    let mut probe = ProbeModV4::init(probe_mod_v4, sender_conf.source_ports.clone())?;
 
     // 探测模块线程初始化
     // 建议: 由 探测模块 生成 原始数据包缓冲区, 包含所有数据包中不变的内容, 后续改动直接在此基础上修改
@@ -54,11 +62,21 @@ pub fn pmap_full_scan_send_v4<T>(interface_index:usize, original_target_iter:T,
     drop(base_conf);
     drop(sender_conf);
 
+    // 续扫时 跳过已完成的端口, 断点所在的端口 从断点位置开始
+    let (resume_round, resume_position) = match checkpoint.resume_point() {
+        SenderResume::At { round, position } => (round as usize, Some(position)),
+        _ => (0, None),
+    };
+
     // 每次取出一个端口, 对所有地址的该端口进行探测
-    for port in tar_ports.into_iter() {
+    for (round, port) in tar_ports.into_iter().enumerate().skip(resume_round) {
 
         // 复制原始迭代器
         let mut target_iter = original_target_iter.clone();
+        if let Some(position) = resume_position.filter(|_| round == resume_round) {
+            target_iter.seek(position);
+        }
+        checkpoint.set_round(round as u64);
 
         // 获得首个目标     0:是否为非最终值, 1:最终值是否有效, 2:ip地址
         let mut cur_target = target_iter.get_first_ip();
@@ -183,10 +201,20 @@ pub fn pmap_full_scan_send_v4<T>(interface_index:usize, original_target_iter:T,
                 }
             }
 
+            send_counter.publish(total_send_success, total_send_failed, total_blocked, rate_controller.pid_output());
+            checkpoint.save(target_iter.position(), total_send_success, total_send_failed, total_blocked);
             rate_controller.sleep();
+            if abort_requested() { break 'big_batch; }
         }
+        // 中止时 不再探测其余端口, 保留最近一次记录的断点
+        if abort_requested() { break }
     }
 
-    (total_send_success, total_send_failed, total_blocked)
+    send_counter.publish(total_send_success, total_send_failed, total_blocked, rate_controller.pid_output());
+    if !abort_requested() {
+        checkpoint.finish();
+        checkpoint.save(0, total_send_success, total_send_failed, total_blocked);
+    }
+    Ok((total_send_success, total_send_failed, total_blocked))
 }
 
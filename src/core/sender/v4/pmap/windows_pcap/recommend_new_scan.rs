@@ -7,21 +7,24 @@ use crate::SYS;
 use crate::core::conf::set_conf::base_conf::BaseConf;
 use crate::core::conf::set_conf::sender_conf::SenderBaseConf;
 use crate::core::sender::tools::rate_controller::RateController;
+use crate::core::sender::tools::abort::abort_requested;
+use crate::core::monitor::SendCounter;
 use crate::core::sender::tools::source_ip_iter::source_ip_v4::SourceIpIterV4;
 use crate::core::sys::packet_sender::PcapSender;
 use crate::modules::probe_modules::probe_mod_v4::ProbeModV4;
 use crate::modules::target_iterators::{Ipv4Iter, PmapGraph, PmapIpStruct, PmapIterV4};
 use crate::tools::blocker::ipv4_blocker::BlackWhiteListV4;
+use crate::core::errors::SmapError;
 
 
 pub fn pmap_recommend_new_scan_send_v4_port(interface_index:usize, mut target_iter:PmapIterV4,
                                         blocker:BlackWhiteListV4, probe_mod_v4: Arc<ProbeModV4>, graph:Arc<PmapGraph>,
-                                        base_conf:Arc<BaseConf>, sender_conf:Arc<SenderBaseConf>) -> (u64, u64, u64, PmapIterV4) {
+                                        base_conf:Arc<BaseConf>, sender_conf:Arc<SenderBaseConf>) -> Result<(u64, u64, u64, PmapIterV4), SmapError> {
 
     // 初始化 pcap 发包器
     let (mut pcap_sender, mut send_queue, batch_size) = PcapSender::init(
         &base_conf.interface[interface_index].name_index.0, sender_conf.global_rate_conf.batch_size,
-        probe_mod_v4.max_packet_length_v4);
+        probe_mod_v4.max_packet_length_v4)?;
 
     // 取出常用变量
     let send_attempts = sender_conf.send_attempt_num;
@@ -33,11 +36,14 @@ pub fn pmap_recommend_new_scan_send_v4_port(interface_index:usize, mut target_it
     let mut total_send_failed:u64 = 0;
     let mut total_blocked:u64 = 0;
 
+    // 每个发送轮次结束时 将统计信息 发布到扫描进度计数器
+    let mut send_counter = SendCounter::new(&base_conf.interface[interface_index].name_index.0);
+
     // 初始化 源地址迭代器
-    let mut source_ip_iter = SourceIpIterV4::new(&sender_conf.source_addrs_v4[interface_index]);
+    let mut source_ip_iter = SourceIpIterV4::new(&sender_conf.source_addrs_v4[interface_index])?;
 
     // 初始化 探测模块
-    let mut probe = ProbeModV4::init(probe_mod_v4, sender_conf.source_ports.clone());
+    let mut probe = ProbeModV4::init(probe_mod_v4, sender_conf.source_ports.clone())?;
 
     // 探测模块线程初始化
     // 建议: 由 探测模块 生成 原始数据包缓冲区, 包含所有数据包中不变的内容, 后续改动直接在此基础上修改
@@ -51,7 +57,7 @@ pub fn pmap_recommend_new_scan_send_v4_port(interface_index:usize, mut target_it
 
     // 注意: 同一网络内所有地址的初始状态和初始推荐端口都一致
     let mut first_ip_struct = PmapIpStruct::new();
-    let first_port = first_ip_struct.send_port(&graph);
+    let first_port = first_ip_struct.send_port(&graph)?;
     let first_ip_struct = first_ip_struct;
 
     // 初始化 PID速率控制器                                                                          强制全局指导速率
@@ -180,7 +186,9 @@ pub fn pmap_recommend_new_scan_send_v4_port(interface_index:usize, mut target_it
             }
         }
 
+        send_counter.publish(total_send_success, total_send_failed, total_blocked, rate_controller.pid_output());
         rate_controller.sleep();
+        if abort_requested() { break 'big_batch; }
     }
 
     // 将 保存所有地址状态信息的向量 中的 冗余容量 进行清除
@@ -189,6 +197,7 @@ pub fn pmap_recommend_new_scan_send_v4_port(interface_index:usize, mut target_it
     // 重置引导迭代器
     target_iter.reset_guide_iter();
 
-    (total_send_success, total_send_failed, total_blocked, target_iter)
+    send_counter.publish(total_send_success, total_send_failed, total_blocked, rate_controller.pid_output());
+    Ok((total_send_success, total_send_failed, total_blocked, target_iter))
 }
 
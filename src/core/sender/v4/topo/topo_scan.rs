@@ -6,6 +6,7 @@ use crate::SYS;
 use crate::core::conf::set_conf::base_conf::BaseConf;
 use crate::core::conf::set_conf::sender_conf::SenderBaseConf;
 use crate::core::sender::tools::rate_controller::RateController;
+use crate::core::sender::tools::abort::abort_requested;
 use crate::core::sender::tools::source_ip_iter::source_ip_v4::SourceIpIterV4;
 use crate::modules::target_iterators::Topo4Iter;
 use net::Ipv4Addr;
@@ -21,6 +22,7 @@ target_os = "ios",
 target_os = "linux"))]
 use crate::core::sys::packet_sender::PacketSender;
 use crate::modules::probe_modules::topology_probe::topo_mod_v4::TopoModV4;
+use crate::core::errors::SmapError;
 
 
 #[cfg(any(
@@ -33,11 +35,11 @@ target_os = "macos",
 target_os = "ios",
 target_os = "linux"))]
 pub fn topo_scan_send_v4<T:Topo4Iter>(interface_index:usize, mut target_iter:T, probe_mod_v4: Arc<TopoModV4>, 
-                                      base_conf:Arc<BaseConf>, sender_conf:Arc<SenderBaseConf>) -> (u64, u64) {
+                                      base_conf:Arc<BaseConf>, sender_conf:Arc<SenderBaseConf>) -> Result<(u64, u64), SmapError> {
     
     // 初始化 数据包发送器
-    let sender = PacketSender::new(
-        &base_conf.interface[interface_index].name_index, &base_conf.interface[interface_index].gateway_mac);
+    let sender = PacketSender::new(&sender_conf.send_backend,
+        &base_conf.interface[interface_index].name_index, &base_conf.interface[interface_index].gateway_mac)?;
 
     // 取出常用变量
     let batch_size = sender_conf.global_rate_conf.batch_size;
@@ -49,11 +51,11 @@ pub fn topo_scan_send_v4<T:Topo4Iter>(interface_index:usize, mut target_iter:T,
     let mut send_failed:u64 = 0;
 
     // 初始化 源地址迭代器
-    let mut source_ip_iter = SourceIpIterV4::new(&sender_conf.source_addrs_v4[interface_index]);
+    let mut source_ip_iter = SourceIpIterV4::new(&sender_conf.source_addrs_v4[interface_index])?;
     let cur_source_ip = source_ip_iter.get_src_ip_with_change();
 
     // 初始化 拓扑探测模块
-    let mut probe = TopoModV4::init(probe_mod_v4, sender_conf.source_ports.clone());
+    let mut probe = TopoModV4::init(probe_mod_v4, sender_conf.source_ports.clone())?;
 
     // 探测模块线程初始化
     probe.thread_initialize_v4(&base_conf.interface[interface_index].local_mac,
@@ -134,8 +136,9 @@ pub fn topo_scan_send_v4<T:Topo4Iter>(interface_index:usize, mut target_iter:T,
             }
         }
         rate_controller.sleep();
+        if abort_requested() { break 'big_batch; }
     }
 
-    (send_success, send_failed)
+    Ok((send_success, send_failed))
 }
 
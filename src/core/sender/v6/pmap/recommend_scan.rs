@@ -7,6 +7,7 @@ use crate::SYS;
 use crate::core::conf::set_conf::base_conf::BaseConf;
 use crate::core::conf::set_conf::sender_conf::SenderBaseConf;
 use crate::core::sender::tools::rate_controller::RateController;
+use crate::core::sender::tools::abort::abort_requested;
 use crate::core::sender::tools::source_ip_iter::source_ip_v6::SourceIpIterV6;
 use crate::modules::probe_modules::probe_mod_v6::ProbeModV6;
 use crate::modules::target_iterators::{Ipv6Iter, PmapGraph, PmapIterV6};
@@ -23,6 +24,7 @@ target_os = "macos",
 target_os = "ios",
 target_os = "linux"))]
 use crate::core::sys::packet_sender::PacketSender;
+use crate::core::errors::SmapError;
 
 
 #[cfg(any(
@@ -36,11 +38,11 @@ target_os = "ios",
 target_os = "linux"))]
 pub fn pmap_recommend_scan_send_v6_port(interface_index:usize, mut target_iter:PmapIterV6,
                                         blocker:BlackWhiteListV6, probe_mod_v6: Arc<ProbeModV6>, graph:Arc<PmapGraph>,
-                                        base_conf:Arc<BaseConf>, sender_conf:Arc<SenderBaseConf>) -> (u64, u64, u64, PmapIterV6) {
+                                        base_conf:Arc<BaseConf>, sender_conf:Arc<SenderBaseConf>) -> Result<(u64, u64, u64, PmapIterV6), SmapError> {
 
     // 初始化 数据包发送器
-    let sender = PacketSender::new(
-        &base_conf.interface[interface_index].name_index, &base_conf.interface[interface_index].gateway_mac);
+    let sender = PacketSender::new(&sender_conf.send_backend,
+        &base_conf.interface[interface_index].name_index, &base_conf.interface[interface_index].gateway_mac)?;
 
     // 取出常用变量
     let batch_size = sender_conf.global_rate_conf.batch_size;
@@ -53,10 +55,10 @@ pub fn pmap_recommend_scan_send_v6_port(interface_index:usize, mut target_iter:P
     let mut total_blocked:u64 = 0;
 
     // 初始化 源地址迭代器
-    let mut source_ip_iter = SourceIpIterV6::new(&sender_conf.source_addrs_v6[interface_index]);
+    let mut source_ip_iter = SourceIpIterV6::new(&sender_conf.source_addrs_v6[interface_index])?;
 
     // 初始化 探测模块
-    let mut probe = ProbeModV6::init(probe_mod_v6, sender_conf.source_ports.clone());
+    let mut probe = ProbeModV6::init(probe_mod_v6, sender_conf.source_ports.clone())?;
 
     // 探测模块线程初始化
     probe.thread_initialize_v6(&base_conf.interface[interface_index].local_mac,
@@ -85,7 +87,7 @@ pub fn pmap_recommend_scan_send_v6_port(interface_index:usize, mut target_iter:P
                 if blocker.ip_is_avail(cur_target.2) {
                     // 如果没被黑名单阻止
 
-                    let cur_port = target_iter.ips_struct[cur_tar_index].send_port(graph_ptr);
+                    let cur_port = target_iter.ips_struct[cur_tar_index].send_port(graph_ptr)?;
                     // 注意: cur_tar_index 是有效ip的索引, 每执行一次端口推荐, 索引值加一
                     cur_tar_index += 1;
                     let cur_source_ip = source_ip_iter.get_src_ip_with_change();
@@ -126,7 +128,7 @@ pub fn pmap_recommend_scan_send_v6_port(interface_index:usize, mut target_iter:P
                     if blocker.ip_is_avail(cur_target.2) {
                         // 如果当前 ip 被放行
 
-                        let cur_port = target_iter.ips_struct[cur_tar_index].send_port(graph_ptr);
+                        let cur_port = target_iter.ips_struct[cur_tar_index].send_port(graph_ptr)?;
                         // 如果是最后一个有效ip, 就不需要让索引号自增
                         let cur_source_ip = source_ip_iter.get_src_ip_with_change();
 
@@ -161,12 +163,13 @@ pub fn pmap_recommend_scan_send_v6_port(interface_index:usize, mut target_iter:P
             }
         }
         rate_controller.sleep();
+        if abort_requested() { break 'big_batch; }
     }
 
     // 重置引导迭代器
     target_iter.reset_guide_iter();
 
-    (send_success, send_failed, total_blocked, target_iter)
+    Ok((send_success, send_failed, total_blocked, target_iter))
 }
 
 
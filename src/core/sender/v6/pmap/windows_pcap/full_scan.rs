@@ -6,23 +6,25 @@ use crate::SYS;
 use crate::core::conf::set_conf::base_conf::BaseConf;
 use crate::core::conf::set_conf::sender_conf::SenderBaseConf;
 use crate::core::sender::tools::rate_controller::RateController;
+use crate::core::sender::tools::abort::abort_requested;
 use crate::core::sender::tools::source_ip_iter::source_ip_v6::SourceIpIterV6;
 use crate::core::sys::packet_sender::PcapSender;
 use crate::modules::probe_modules::probe_mod_v6::ProbeModV6;
 use crate::modules::target_iterators::Ipv6Iter;
 use crate::tools::blocker::ipv6_blocker::BlackWhiteListV6;
+use crate::core::errors::SmapError;
 
 
 /// 对 所有地址 的 全部目标端口 进行探测, 每次从目标端口向量取出一个端口, 对所有地址进行探测
 pub fn pmap_full_scan_send_v6<T>(interface_index:usize, original_target_iter:T,
                                  blocker:BlackWhiteListV6, probe_mod_v6: Arc<ProbeModV6>, tar_ports:Vec<u16>,
-                                 base_conf:Arc<BaseConf>, sender_conf:Arc<SenderBaseConf>) -> (u64, u64, u64)
+                                 base_conf:Arc<BaseConf>, sender_conf:Arc<SenderBaseConf>) -> Result<(u64, u64, u64), SmapError>
     where T: Ipv6Iter + Clone {
 
     // 初始化 pcap 发包器
     let (mut pcap_sender, mut send_queue, batch_size) = PcapSender::init(
         &base_conf.interface[interface_index].name_index.0, sender_conf.global_rate_conf.batch_size,
-        probe_mod_v6.max_packet_length_v6);
+        probe_mod_v6.max_packet_length_v6)?;
 
     // 取出常用变量
     let send_attempts = sender_conf.send_attempt_num;
@@ -35,10 +37,10 @@ pub fn pmap_full_scan_send_v6<T>(interface_index:usize, original_target_iter:T,
     let mut total_blocked:u64 = 0;
 
     // 初始化 源地址迭代器
-    let mut source_ip_iter = SourceIpIterV6::new(&sender_conf.source_addrs_v6[interface_index]);
+    let mut source_ip_iter = SourceIpIterV6::new(&sender_conf.source_addrs_v6[interface_index])?;
 
     // 初始化 探测模块
-    let mut probe = ProbeModV6::init(probe_mod_v6, sender_conf.source_ports.clone());
+    let mut probe = ProbeModV6::init(probe_mod_v6, sender_conf.source_ports.clone())?;
 
     // 探测模块线程初始化
     // 建议: 由 探测模块 生成 原始数据包缓冲区, 包含所有数据包中不变的内容, 后续改动直接在此基础上修改
@@ -183,9 +185,10 @@ pub fn pmap_full_scan_send_v6<T>(interface_index:usize, original_target_iter:T,
             }
 
             rate_controller.sleep();
+            if abort_requested() { break 'big_batch; }
         }
     }
 
-    (total_send_success, total_send_failed, total_blocked)
+    Ok((total_send_success, total_send_failed, total_blocked))
 }
 
@@ -6,21 +6,23 @@ use crate::SYS;
 use crate::core::conf::set_conf::base_conf::BaseConf;
 use crate::core::conf::set_conf::sender_conf::SenderBaseConf;
 use crate::core::sender::tools::rate_controller::RateController;
+use crate::core::sender::tools::abort::abort_requested;
 use crate::core::sender::tools::source_ip_iter::source_ip_v6::SourceIpIterV6;
 use crate::core::sys::packet_sender::PcapSender;
 use crate::modules::probe_modules::probe_mod_v6::ProbeModV6;
 use crate::modules::target_iterators::{Ipv6Iter, PmapGraph, PmapIpStruct, PmapIterV6};
 use crate::tools::blocker::ipv6_blocker::BlackWhiteListV6;
+use crate::core::errors::SmapError;
 
 
 pub fn pmap_recommend_new_scan_send_v6_port(interface_index:usize, mut target_iter:PmapIterV6,
                                             blocker:BlackWhiteListV6, probe_mod_v6: Arc<ProbeModV6>, graph:Arc<PmapGraph>,
-                                            base_conf:Arc<BaseConf>, sender_conf:Arc<SenderBaseConf>) -> (u64, u64, u64, PmapIterV6) {
+                                            base_conf:Arc<BaseConf>, sender_conf:Arc<SenderBaseConf>) -> Result<(u64, u64, u64, PmapIterV6), SmapError> {
 
     // 初始化 pcap 发包器
     let (mut pcap_sender, mut send_queue, batch_size) = PcapSender::init(
         &base_conf.interface[interface_index].name_index.0, sender_conf.global_rate_conf.batch_size,
-        probe_mod_v6.max_packet_length_v6);
+        probe_mod_v6.max_packet_length_v6)?;
 
     // 取出常用变量
     let send_attempts = sender_conf.send_attempt_num;
@@ -33,10 +35,10 @@ pub fn pmap_recommend_new_scan_send_v6_port(interface_index:usize, mut target_it
     let mut total_blocked:u64 = 0;
 
     // 初始化 源地址迭代器
-    let mut source_ip_iter = SourceIpIterV6::new(&sender_conf.source_addrs_v6[interface_index]);
+    let mut source_ip_iter = SourceIpIterV6::new(&sender_conf.source_addrs_v6[interface_index])?;
 
     // 初始化 探测模块
-    let mut probe = ProbeModV6::init(probe_mod_v6, sender_conf.source_ports.clone());
+    let mut probe = ProbeModV6::init(probe_mod_v6, sender_conf.source_ports.clone())?;
 
     // 探测模块线程初始化
     // 建议: 由 探测模块 生成 原始数据包缓冲区, 包含所有数据包中不变的内容, 后续改动直接在此基础上修改
@@ -49,7 +51,7 @@ pub fn pmap_recommend_new_scan_send_v6_port(interface_index:usize, mut target_it
 
     // 注意: 同一网络内所有地址的初始状态和初始推荐端口都一致
     let mut first_ip_struct = PmapIpStruct::new();
-    let first_port = first_ip_struct.send_port(&graph);
+    let first_port = first_ip_struct.send_port(&graph)?;
     let first_ip_struct = first_ip_struct;
 
     // 初始化 PID速率控制器                                                                          强制全局指导速率
@@ -178,6 +180,7 @@ pub fn pmap_recommend_new_scan_send_v6_port(interface_index:usize, mut target_it
         }
 
         rate_controller.sleep();
+        if abort_requested() { break 'big_batch; }
     }
 
     // 将 保存所有地址状态信息的向量 中的 冗余容量 进行清除
@@ -186,6 +189,6 @@ pub fn pmap_recommend_new_scan_send_v6_port(interface_index:usize, mut target_it
     // 重置引导迭代器
     target_iter.reset_guide_iter();
 
-    (total_send_success, total_send_failed, total_blocked, target_iter)
+    Ok((total_send_success, total_send_failed, total_blocked, target_iter))
 }
 
@@ -4,6 +4,7 @@ use log::debug;
 use crate::core::conf::set_conf::base_conf::BaseConf;
 use crate::core::conf::set_conf::sender_conf::SenderBaseConf;
 use crate::core::sender::tools::rate_controller::RateController;
+use crate::core::sender::tools::abort::abort_requested;
 use crate::core::sender::tools::source_ip_iter::source_ip_v6::SourceIpIterV6;
 use crate::modules::probe_modules::probe_mod_v6::ProbeModV6;
 
@@ -18,6 +19,7 @@ use crate::modules::probe_modules::probe_mod_v6::ProbeModV6;
     target_os = "linux"))]
 use crate::core::sys::packet_sender::PacketSender;
 use crate::SYS;
+use crate::core::errors::SmapError;
 
 #[cfg(any(
     target_os = "freebsd",
@@ -30,11 +32,11 @@ use crate::SYS;
     target_os = "linux"))]
 pub fn pmap_file_full_scan_send_v6(interface_index:usize, probe_mod_v6: Arc<ProbeModV6>,
                                    tar_ips:Vec<u128>, tar_ports:Vec<u16>,
-                                   base_conf:Arc<BaseConf>, sender_conf:Arc<SenderBaseConf>) -> (u64, u64) {
+                                   base_conf:Arc<BaseConf>, sender_conf:Arc<SenderBaseConf>) -> Result<(u64, u64), SmapError> {
 
     // 初始化 数据包发送器
-    let sender = PacketSender::new(
-        &base_conf.interface[interface_index].name_index, &base_conf.interface[interface_index].gateway_mac);
+    let sender = PacketSender::new(&sender_conf.send_backend,
+        &base_conf.interface[interface_index].name_index, &base_conf.interface[interface_index].gateway_mac)?;
 
     // 取出常用变量
     let batch_size = sender_conf.global_rate_conf.batch_size;
@@ -46,10 +48,10 @@ pub fn pmap_file_full_scan_send_v6(interface_index:usize, probe_mod_v6: Arc<Prob
     let mut send_failed:u64 = 0;
 
     // 初始化 源地址迭代器
-    let mut source_ip_iter = SourceIpIterV6::new(&sender_conf.source_addrs_v6[interface_index]);
+    let mut source_ip_iter = SourceIpIterV6::new(&sender_conf.source_addrs_v6[interface_index])?;
 
     // 初始化 探测模块
-    let mut probe = ProbeModV6::init(probe_mod_v6, sender_conf.source_ports.clone());
+    let mut probe = ProbeModV6::init(probe_mod_v6, sender_conf.source_ports.clone())?;
 
     // 探测模块线程初始化
     probe.thread_initialize_v6(&base_conf.interface[interface_index].local_mac,
@@ -64,7 +66,7 @@ pub fn pmap_file_full_scan_send_v6(interface_index:usize, probe_mod_v6: Arc<Prob
     drop(sender_conf);
 
     // 每次取出一个端口, 对所有地址的该端口进行探测
-    for port in tar_ports.into_iter() {
+    'ports: for port in tar_ports.into_iter() {
 
         let mut batch_count:u64 = 0;
 
@@ -101,10 +103,11 @@ pub fn pmap_file_full_scan_send_v6(interface_index:usize, probe_mod_v6: Arc<Prob
 
                 // 批次速率控制
                 rate_controller.sleep();
+                if abort_requested() { break 'ports; }
             }
 
         }
     }
 
-    (send_success, send_failed)
+    Ok((send_success, send_failed))
 }
\ No newline at end of file
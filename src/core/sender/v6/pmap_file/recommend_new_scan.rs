@@ -5,6 +5,7 @@ use crate::SYS;
 use crate::core::conf::set_conf::base_conf::BaseConf;
 use crate::core::conf::set_conf::sender_conf::SenderBaseConf;
 use crate::core::sender::tools::rate_controller::RateController;
+use crate::core::sender::tools::abort::abort_requested;
 use crate::core::sender::tools::source_ip_iter::source_ip_v6::SourceIpIterV6;
 use crate::modules::probe_modules::probe_mod_v6::ProbeModV6;
 use crate::modules::target_iterators::{PmapFileIterV6, PmapGraph, PmapIpStruct};
@@ -20,6 +21,7 @@ use net::Ipv6Addr;
     target_os = "ios",
     target_os = "linux"))]
 use crate::core::sys::packet_sender::PacketSender;
+use crate::core::errors::SmapError;
 
 #[cfg(any(
     target_os = "freebsd",
@@ -33,11 +35,11 @@ use crate::core::sys::packet_sender::PacketSender;
 pub fn pmap_file_recommend_new_scan_send_v6_port(
     interface_index:usize, mut target_iter:PmapFileIterV6, probe_mod_v6: Arc<ProbeModV6>,
     graph:Arc<PmapGraph>, base_conf:Arc<BaseConf>, sender_conf:Arc<SenderBaseConf>
-) -> (u64, u64, PmapFileIterV6) {
+) -> Result<(u64, u64, PmapFileIterV6), SmapError> {
 
     // 初始化 数据包发送器
-    let sender = PacketSender::new(
-        &base_conf.interface[interface_index].name_index, &base_conf.interface[interface_index].gateway_mac);
+    let sender = PacketSender::new(&sender_conf.send_backend,
+        &base_conf.interface[interface_index].name_index, &base_conf.interface[interface_index].gateway_mac)?;
 
     // 取出常用变量
     let batch_size = sender_conf.global_rate_conf.batch_size;
@@ -49,10 +51,10 @@ pub fn pmap_file_recommend_new_scan_send_v6_port(
     let mut send_failed:u64 = 0;
 
     // 初始化 源地址迭代器
-    let mut source_ip_iter = SourceIpIterV6::new(&sender_conf.source_addrs_v6[interface_index]);
+    let mut source_ip_iter = SourceIpIterV6::new(&sender_conf.source_addrs_v6[interface_index])?;
 
     // 初始化 探测模块
-    let mut probe = ProbeModV6::init(probe_mod_v6, sender_conf.source_ports.clone());
+    let mut probe = ProbeModV6::init(probe_mod_v6, sender_conf.source_ports.clone())?;
 
     // 探测模块线程初始化
     probe.thread_initialize_v6(&base_conf.interface[interface_index].local_mac,
@@ -62,7 +64,7 @@ pub fn pmap_file_recommend_new_scan_send_v6_port(
 
     // 注意: 同一网络内所有地址的初始状态和初始推荐端口都一致
     let mut first_ip_struct = PmapIpStruct::new();
-    let first_port = first_ip_struct.send_port(&graph);
+    let first_port = first_ip_struct.send_port(&graph)?;
     let first_ip_struct = first_ip_struct;
 
     // 初始化 PID速率控制器                                                                          强制全局指导速率
@@ -115,11 +117,12 @@ pub fn pmap_file_recommend_new_scan_send_v6_port(
 
             // 批次速率控制
             rate_controller.sleep();
+            if abort_requested() { break; }
         }
     }
 
     // 将 保存所有地址状态信息的向量 中的 冗余容量 进行清除
     target_iter.ips_struct.shrink_to_fit();
 
-    (send_success, send_failed, target_iter)
+    Ok((send_success, send_failed, target_iter))
 }
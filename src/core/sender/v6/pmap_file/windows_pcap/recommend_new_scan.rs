@@ -3,21 +3,23 @@ use log::debug;
 use crate::core::conf::set_conf::base_conf::BaseConf;
 use crate::core::conf::set_conf::sender_conf::SenderBaseConf;
 use crate::core::sender::tools::rate_controller::RateController;
+use crate::core::sender::tools::abort::abort_requested;
 use crate::core::sender::tools::source_ip_iter::source_ip_v6::SourceIpIterV6;
 use crate::core::sys::packet_sender::PcapSender;
 use crate::modules::probe_modules::probe_mod_v6::ProbeModV6;
 use crate::modules::target_iterators::{PmapFileIterV6, PmapGraph, PmapIpStruct};
 use crate::SYS;
+use crate::core::errors::SmapError;
 
 pub fn pmap_file_recommend_new_scan_send_v6_port(
     interface_index:usize, mut target_iter:PmapFileIterV6, probe_mod_v6: Arc<ProbeModV6>,
     graph:Arc<PmapGraph>, base_conf:Arc<BaseConf>, sender_conf:Arc<SenderBaseConf>
-) -> (u64, u64, PmapFileIterV6) {
+) -> Result<(u64, u64, PmapFileIterV6), SmapError> {
 
     // 初始化 pcap 发包器
     let (mut pcap_sender, mut send_queue, batch_size) = PcapSender::init(
         &base_conf.interface[interface_index].name_index.0, sender_conf.global_rate_conf.batch_size,
-        probe_mod_v6.max_packet_length_v6);
+        probe_mod_v6.max_packet_length_v6)?;
 
     // 取出常用变量
     let send_attempts = sender_conf.send_attempt_num;
@@ -29,10 +31,10 @@ pub fn pmap_file_recommend_new_scan_send_v6_port(
     let mut total_send_failed:u64 = 0;
 
     // 初始化 源地址迭代器
-    let mut source_ip_iter = SourceIpIterV6::new(&sender_conf.source_addrs_v6[interface_index]);
+    let mut source_ip_iter = SourceIpIterV6::new(&sender_conf.source_addrs_v6[interface_index])?;
 
     // 初始化 探测模块
-    let mut probe = ProbeModV6::init(probe_mod_v6, sender_conf.source_ports.clone());
+    let mut probe = ProbeModV6::init(probe_mod_v6, sender_conf.source_ports.clone())?;
 
     // 探测模块线程初始化
     // 建议: 由 探测模块 生成 原始数据包缓冲区, 包含所有数据包中不变的内容, 后续改动直接在此基础上修改
@@ -42,7 +44,7 @@ pub fn pmap_file_recommend_new_scan_send_v6_port(
 
     // 注意: 同一网络内所有地址的初始状态和初始推荐端口都一致
     let mut first_ip_struct = PmapIpStruct::new();
-    let first_port = first_ip_struct.send_port(&graph);
+    let first_port = first_ip_struct.send_port(&graph)?;
     let first_ip_struct = first_ip_struct;
 
     // 初始化 PID速率控制器                                                                          强制全局指导速率
@@ -120,6 +122,7 @@ pub fn pmap_file_recommend_new_scan_send_v6_port(
 
             // 批次速率控制
             rate_controller.sleep();
+            if abort_requested() { break; }
         }
     }
 
@@ -148,5 +151,5 @@ pub fn pmap_file_recommend_new_scan_send_v6_port(
     // 将 保存所有地址状态信息的向量 中的 冗余容量 进行清除
     target_iter.ips_struct.shrink_to_fit();
 
-    (total_send_success, total_send_failed, target_iter)
+    Ok((total_send_success, total_send_failed, target_iter))
 }
\ No newline at end of file
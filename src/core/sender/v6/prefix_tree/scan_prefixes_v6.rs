@@ -4,6 +4,7 @@ use log::debug;
 use crate::core::conf::set_conf::base_conf::BaseConf;
 use crate::core::conf::set_conf::sender_conf::SenderBaseConf;
 use crate::core::sender::tools::rate_controller::RateController;
+use crate::core::sender::tools::abort::abort_requested;
 use crate::core::sender::tools::source_ip_iter::source_ip_v6::SourceIpIterV6;
 use crate::modules::probe_modules::topology_probe::topo_mod_v6::TopoModV6;
 use crate::SYS;
@@ -18,6 +19,7 @@ use crate::SYS;
     target_os = "ios",
     target_os = "linux"))]
 use crate::core::sys::packet_sender::PacketSender;
+use crate::core::errors::SmapError;
 
 #[cfg(any(
     target_os = "freebsd",
@@ -31,11 +33,11 @@ use crate::core::sys::packet_sender::PacketSender;
 pub fn send_prefixes_v6(
     interface_index:usize, targets:Vec<(u128, u8, u8)>,
     probe_mod_v6: Arc<TopoModV6>, base_conf:Arc<BaseConf>, sender_conf:Arc<SenderBaseConf>
-) -> (u64, u64) {
+) -> Result<(u64, u64), SmapError> {
 
     // 初始化 数据包发送器
-    let sender = PacketSender::new(
-        &base_conf.interface[interface_index].name_index, &base_conf.interface[interface_index].gateway_mac);
+    let sender = PacketSender::new(&sender_conf.send_backend,
+        &base_conf.interface[interface_index].name_index, &base_conf.interface[interface_index].gateway_mac)?;
 
     // 取出常用变量
     let batch_size = sender_conf.global_rate_conf.batch_size;
@@ -45,12 +47,12 @@ pub fn send_prefixes_v6(
     let mut total_send_failed: u64 = 0;
 
     // 初始化 源地址迭代器
-    let source_ip_iter = SourceIpIterV6::new(&sender_conf.source_addrs_v6[interface_index]);
+    let source_ip_iter = SourceIpIterV6::new(&sender_conf.source_addrs_v6[interface_index])?;
     let cur_source_ip = source_ip_iter.get_src_ip();
     drop(source_ip_iter);
 
     // 初始化 拓扑探测模块
-    let mut probe = TopoModV6::init(probe_mod_v6, sender_conf.source_ports.clone());
+    let mut probe = TopoModV6::init(probe_mod_v6, sender_conf.source_ports.clone())?;
 
     // 探测模块线程初始化
     probe.thread_initialize_v6(&base_conf.interface[interface_index].local_mac,
@@ -91,8 +93,9 @@ pub fn send_prefixes_v6(
         batch_count += 1;
         if batch_count >= batch_size {
             rate_controller.sleep();
+            if abort_requested() { break; }
             batch_count = 0;
         }
     }
-    (total_send_success, total_send_failed)
+    Ok((total_send_success, total_send_failed))
 }
\ No newline at end of file
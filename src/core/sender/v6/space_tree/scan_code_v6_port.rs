@@ -11,6 +11,7 @@ use crate::SYS;
 use crate::core::conf::set_conf::base_conf::BaseConf;
 use crate::core::conf::set_conf::sender_conf::SenderBaseConf;
 use crate::core::sender::tools::rate_controller::RateController;
+use crate::core::sender::tools::abort::abort_requested;
 use crate::core::sender::tools::source_ip_iter::source_ip_v6::SourceIpIterV6;
 use net::Ipv6Addr;
 use crate::modules::probe_modules::active_probe_ipv6_code::CodeProbeModV6;
@@ -25,6 +26,7 @@ use crate::modules::probe_modules::active_probe_ipv6_code::CodeProbeModV6;
     target_os = "ios",
     target_os = "linux"))]
 use crate::core::sys::packet_sender::PacketSender;
+use crate::core::errors::SmapError;
 
 
 #[cfg(any(
@@ -39,11 +41,11 @@ use crate::core::sys::packet_sender::PacketSender;
 pub fn send_v6_code_port_vec(
     interface_index:usize, tar_addrs:Vec<(Vec<u8>, u16, u128)>,
     probe_mod_v6: Arc<CodeProbeModV6>,
-    base_conf:Arc<BaseConf>, sender_conf:Arc<SenderBaseConf>) -> (u64, u64) {
+    base_conf:Arc<BaseConf>, sender_conf:Arc<SenderBaseConf>) -> Result<(u64, u64), SmapError> {
 
     // 初始化 数据包发送器
-    let sender = PacketSender::new(
-        &base_conf.interface[interface_index].name_index, &base_conf.interface[interface_index].gateway_mac);
+    let sender = PacketSender::new(&sender_conf.send_backend,
+        &base_conf.interface[interface_index].name_index, &base_conf.interface[interface_index].gateway_mac)?;
 
     // 取出常用变量
     let batch_size = sender_conf.global_rate_conf.batch_size;
@@ -53,10 +55,10 @@ pub fn send_v6_code_port_vec(
     let mut total_send_failed: u64 = 0;
 
     // 初始化 源地址迭代器
-    let mut source_ip_iter = SourceIpIterV6::new(&sender_conf.source_addrs_v6[interface_index]);
+    let mut source_ip_iter = SourceIpIterV6::new(&sender_conf.source_addrs_v6[interface_index])?;
 
     // 初始化 探测模块
-    let mut probe = CodeProbeModV6::init(probe_mod_v6, sender_conf.source_ports.clone());
+    let mut probe = CodeProbeModV6::init(probe_mod_v6, sender_conf.source_ports.clone())?;
 
     // 探测模块线程初始化
     // 建议: 由 探测模块 生成 原始数据包缓冲区, 包含所有数据包中不变的内容, 后续改动直接在此基础上修改
@@ -76,7 +78,7 @@ pub fn send_v6_code_port_vec(
         let cur_source_ip = source_ip_iter.get_src_ip_with_change();
 
         // 由探测模块生成数据包
-        let packet = probe.make_packet_v6(cur_source_ip, dest_addr, dest_port, code, &aes_rand);
+        let packet = probe.make_packet_v6(cur_source_ip, dest_addr, dest_port, code, &aes_rand)?;
 
         let mut sent_successfully = false;
         for _ in 0..send_attempts {
@@ -99,10 +101,11 @@ pub fn send_v6_code_port_vec(
         batch_count += 1;
         if batch_count >= batch_size {
             rate_controller.sleep();
+            if abort_requested() { break; }
             batch_count = 0;
         }
     }
 
-    (total_send_success, total_send_failed)
+    Ok((total_send_success, total_send_failed))
 }
 
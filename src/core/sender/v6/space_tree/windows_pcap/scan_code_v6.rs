@@ -3,20 +3,22 @@ use log::debug;
 use crate::core::conf::set_conf::base_conf::BaseConf;
 use crate::core::conf::set_conf::sender_conf::SenderBaseConf;
 use crate::core::sender::tools::rate_controller::RateController;
+use crate::core::sender::tools::abort::abort_requested;
 use crate::core::sender::tools::source_ip_iter::source_ip_v6::SourceIpIterV6;
 use crate::core::sys::packet_sender::PcapSender;
 use crate::modules::probe_modules::active_probe_ipv6_code::CodeProbeModV6;
 use crate::SYS;
+use crate::core::errors::SmapError;
 
 pub fn send_v6_code_vec(
     interface_index:usize, tar_addrs:Vec<(Vec<u8>, u128)>,
     probe_mod_v6: Arc<CodeProbeModV6>,
-    base_conf:Arc<BaseConf>, sender_conf:Arc<SenderBaseConf>) -> (u64, u64) {
+    base_conf:Arc<BaseConf>, sender_conf:Arc<SenderBaseConf>) -> Result<(u64, u64), SmapError> {
 
     // 初始化 pcap 发包器
     let (mut pcap_sender, mut send_queue, batch_size) = PcapSender::init(
         &base_conf.interface[interface_index].name_index.0, sender_conf.global_rate_conf.batch_size,
-        probe_mod_v6.max_packet_length_v6);
+        probe_mod_v6.max_packet_length_v6)?;
 
     // 取出常用变量
     let send_attempts = sender_conf.send_attempt_num;
@@ -26,10 +28,10 @@ pub fn send_v6_code_vec(
     let mut total_send_failed: u64 = 0;
 
     // 初始化 源地址迭代器
-    let mut source_ip_iter = SourceIpIterV6::new(&sender_conf.source_addrs_v6[interface_index]);
+    let mut source_ip_iter = SourceIpIterV6::new(&sender_conf.source_addrs_v6[interface_index])?;
 
     // 初始化 探测模块
-    let mut probe = CodeProbeModV6::init(probe_mod_v6, Vec::new());
+    let mut probe = CodeProbeModV6::init(probe_mod_v6, Vec::new())?;
 
     // 探测模块线程初始化
     probe.thread_initialize_v6(&base_conf.interface[interface_index].local_mac,
@@ -51,7 +53,7 @@ pub fn send_v6_code_vec(
         let cur_source_ip = source_ip_iter.get_src_ip_with_change();
 
         // 由探测模块生成数据包
-        let packet = probe.make_packet_v6(cur_source_ip, dest_addr, 0, region_code, &aes_rand);
+        let packet = probe.make_packet_v6(cur_source_ip, dest_addr, 0, region_code, &aes_rand)?;
 
         let mut add_successfully = false;
         for _ in 0..send_attempts {
@@ -94,11 +96,12 @@ pub fn send_v6_code_vec(
             }
 
             rate_controller.sleep();
+            if abort_requested() { break; }
 
             batch_count = 0;
             batch_send_success = 0;
             batch_send_failed = 0;
         }
     }
-    (total_send_success, total_send_failed)
+    Ok((total_send_success, total_send_failed))
 }
\ No newline at end of file
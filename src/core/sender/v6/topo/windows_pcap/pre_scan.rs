@@ -6,19 +6,21 @@ use crate::core::sys::packet_sender::PcapSender;
 use crate::core::conf::set_conf::base_conf::BaseConf;
 use crate::core::conf::set_conf::sender_conf::SenderBaseConf;
 use crate::core::sender::tools::rate_controller::RateController;
+use crate::core::sender::tools::abort::abort_requested;
 use crate::core::sender::tools::source_ip_iter::source_ip_v6::SourceIpIterV6;
 use crate::modules::probe_modules::topology_probe::topo_mod_v6::TopoModV6;
 use crate::modules::target_iterators::Ipv6Iter;
 use crate::tools::blocker::ipv6_blocker::BlackWhiteListV6;
+use crate::core::errors::SmapError;
 
 
 pub fn topo_pre_scan_send_v6<T:Ipv6Iter>(interface_index:usize, mut target_iter:T, blocker:BlackWhiteListV6, ttl:u8,
-                                         probe_mod_v6: Arc<TopoModV6>, base_conf:Arc<BaseConf>, sender_conf:Arc<SenderBaseConf>) -> (u64, u64, u64) {
+                                         probe_mod_v6: Arc<TopoModV6>, base_conf:Arc<BaseConf>, sender_conf:Arc<SenderBaseConf>) -> Result<(u64, u64, u64), SmapError> {
 
     // 初始化 pcap 发包器
     let (mut pcap_sender, mut send_queue, batch_size) = PcapSender::init(
         &base_conf.interface[interface_index].name_index.0, sender_conf.global_rate_conf.batch_size,
-        probe_mod_v6.max_packet_length_v6);
+        probe_mod_v6.max_packet_length_v6)?;
 
     // 取出常用变量
     let send_attempts = sender_conf.send_attempt_num;
@@ -31,12 +33,12 @@ pub fn topo_pre_scan_send_v6<T:Ipv6Iter>(interface_index:usize, mut target_iter:
     let mut total_blocked:u64 = 0;
 
     // 初始化 源地址迭代器
-    let source_ip_iter = SourceIpIterV6::new(&sender_conf.source_addrs_v6[interface_index]);
+    let source_ip_iter = SourceIpIterV6::new(&sender_conf.source_addrs_v6[interface_index])?;
     let cur_source_ip = source_ip_iter.get_src_ip();
     drop(source_ip_iter);
 
     // 初始化 拓扑探测模块
-    let mut probe = TopoModV6::init(probe_mod_v6, sender_conf.source_ports.clone());
+    let mut probe = TopoModV6::init(probe_mod_v6, sender_conf.source_ports.clone())?;
 
     // 探测模块线程初始化
     probe.thread_initialize_v6(&base_conf.interface[interface_index].local_mac,
@@ -176,7 +178,8 @@ pub fn topo_pre_scan_send_v6<T:Ipv6Iter>(interface_index:usize, mut target_iter:
         }
 
         rate_controller.sleep();
+        if abort_requested() { break 'big_batch; }
     }
-    (total_send_success, total_send_failed, total_blocked)
+    Ok((total_send_success, total_send_failed, total_blocked))
 }
 
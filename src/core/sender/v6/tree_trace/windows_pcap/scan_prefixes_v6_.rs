@@ -3,20 +3,22 @@ use log::debug;
 use crate::core::conf::set_conf::base_conf::BaseConf;
 use crate::core::conf::set_conf::sender_conf::SenderBaseConf;
 use crate::core::sender::tools::rate_controller::RateController;
+use crate::core::sender::tools::abort::abort_requested;
 use crate::core::sender::tools::source_ip_iter::source_ip_v6::SourceIpIterV6;
 use crate::core::sys::packet_sender::PcapSender;
 use crate::modules::probe_modules::topo_mod_v6::CodeTopoProbeModV6;
 use crate::SYS;
+use crate::core::errors::SmapError;
 
 pub fn send_prefixes_v6_2(
     interface_index:usize, targets:Vec<(u64, u8, Vec<u8>)>,
     probe_mod_v6: Arc<CodeTopoProbeModV6>, base_conf:Arc<BaseConf>, sender_conf:Arc<SenderBaseConf>
-) -> (u64, u64) {
+) -> Result<(u64, u64), SmapError> {
 
     // 初始化 pcap 发包器
     let (mut pcap_sender, mut send_queue, batch_size) = PcapSender::init(
         &base_conf.interface[interface_index].name_index.0, sender_conf.global_rate_conf.batch_size,
-        probe_mod_v6.max_packet_length_v6);
+        probe_mod_v6.max_packet_length_v6)?;
 
     // 取出常用变量
     let send_attempts = sender_conf.send_attempt_num;
@@ -26,12 +28,12 @@ pub fn send_prefixes_v6_2(
     let mut total_send_failed:u64 = 0;
 
     // 初始化 源地址迭代器
-    let source_ip_iter = SourceIpIterV6::new(&sender_conf.source_addrs_v6[interface_index]);
+    let source_ip_iter = SourceIpIterV6::new(&sender_conf.source_addrs_v6[interface_index])?;
     let cur_source_ip = source_ip_iter.get_src_ip();
     drop(source_ip_iter);
 
     // 初始化 拓扑探测模块
-    let mut probe = CodeTopoProbeModV6::init(probe_mod_v6);
+    let mut probe = CodeTopoProbeModV6::init(probe_mod_v6)?;
 
     // 探测模块线程初始化
     probe.thread_initialize_v6(&base_conf.interface[interface_index].local_mac,
@@ -95,11 +97,12 @@ pub fn send_prefixes_v6_2(
             }
 
             rate_controller.sleep();
+            if abort_requested() { break; }
 
             batch_count = 0;
             batch_send_success = 0;
             batch_send_failed = 0;
         }
     }
-    (total_send_success, total_send_failed)
+    Ok((total_send_success, total_send_failed))
 }
\ No newline at end of file
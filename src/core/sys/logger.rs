@@ -63,7 +63,7 @@ pub fn set_logger(args:&Args){
         if args.disable_sys_log {
             // 如果系统日志被禁用
             set_logger_file(pattern, level,&log_file);
-            println!("{}", SYS.get_info("print", "syslog_off"));
+            eprintln!("{}", SYS.get_info("print", "syslog_off"));
         }else {
             // 如果开启系统日志
             set_logger2(pattern,level,&log_file);
@@ -81,7 +81,7 @@ pub fn set_logger(args:&Args){
         if args.disable_sys_log {
             // 如果系统日志被禁用
             set_logger_file(pattern, level,&log_file);
-            println!("{}", SYS.get_info("print", "syslog_off"));
+            eprintln!("{}", SYS.get_info("print", "syslog_off"));
         }else {
             // 如果开启系统日志
             set_logger2(pattern,level,&log_file);
@@ -97,7 +97,7 @@ pub fn set_logger(args:&Args){
             set_logger_std(pattern,level);
             debug!("{}", SYS.get_info("debug", "syslog_on"));
         } else {
-            println!("{}", SYS.get_info("print", "syslog_off"));
+            eprintln!("{}", SYS.get_info("print", "syslog_off"));
         }
 
     }
@@ -1,6 +1,10 @@
 
 pub mod logger;
 pub mod packet_sender;
+pub mod virtual_net;
+
+#[cfg(target_os = "linux")]
+pub mod xdp;
 
 
 
@@ -0,0 +1,174 @@
+use std::net::IpAddr;
+use log::debug;
+use crate::core::sys::packet_sender::PacketBuf;
+#[cfg(target_os = "linux")]
+use crate::core::sys::packet_sender::linux::packet_sender::MmsgHeaders;
+#[cfg(target_os = "linux")]
+use crate::core::sys::xdp::XdpFrames;
+use crate::SYS;
+
+#[cfg(test)]
+mod tests;
+
+
+/// 批量发送缓冲区
+/// 每个槽位 保存一个数据包 和 对应的目标, 探测模块 直接将数据包写入槽位, 槽位的内存在各发送轮次间 重复使用
+/// AF_XDP 后端 每个槽位 每次占用一个 UMEM 发送帧, 提交后 由内核发送完成时 归还
+pub struct PacketBatch {
+    packets:Vec<PacketBuf>,
+    targets:Vec<(IpAddr, u16)>,
+
+    // 已写入的槽位数量
+    len:usize,
+
+    // AF_XDP 发送帧 的来源
+    #[cfg(target_os = "linux")]
+    frames:Option<XdpFrames>,
+
+    // sendmmsg 的消息头, 与槽位数量一致, 各次提交间 重复使用
+    #[cfg(target_os = "linux")]
+    headers:MmsgHeaders,
+}
+
+impl PacketBatch {
+
+    /// 槽位数量 为 发送轮次大小 和 单次提交上限 中的较小值
+    pub fn new(batch_size:u64, max_packet_len:usize) -> Self {
+        let max_send_batch:u64 = SYS.get_conf("conf", "max_send_batch");
+        let slots = batch_size.min(max_send_batch).max(1) as usize;
+
+        Self {
+            packets: (0..slots).map(|_| {
+                let mut packet = PacketBuf::new();
+                packet.reserve(max_packet_len);
+                packet
+            }).collect(),
+            targets: vec![(IpAddr::from([0u8; 4]), 0); slots],
+            len: 0,
+            #[cfg(target_os = "linux")]
+            frames: None,
+            #[cfg(target_os = "linux")]
+            headers: MmsgHeaders::with_capacity(slots),
+        }
+    }
+
+    /// 槽位 改为直接写入 AF_XDP 发送帧, 槽位数量 不超过 可同时占用的发送帧数量
+    #[cfg(target_os = "linux")]
+    pub(super) fn with_frames(mut self, frames:XdpFrames) -> Self {
+        let slots = self.packets.len().min(frames.capacity()).max(1);
+        self.packets.truncate(slots);
+        self.targets.truncate(slots);
+        self.frames = Some(frames);
+        self
+    }
+
+    /// 占用下一个槽位 并记录目标地址和端口, 返回 已清空的数据包缓冲区
+    /// 暂时没有空闲的发送帧时 该槽位 使用堆缓冲区, 提交时 再复制到发送帧
+    #[inline]
+    pub fn next_slot(&mut self, ip:IpAddr, port:u16) -> &mut PacketBuf {
+        self.targets[self.len] = (ip, port);
+        let packet = &mut self.packets[self.len];
+        #[cfg(target_os = "linux")]
+        if let Some(frames) = &self.frames {
+            if let Some(frame) = frames.alloc() {
+                *packet = frame;
+            }
+        }
+        packet.clear();
+        self.len += 1;
+        packet
+    }
+
+    /// 槽位是否已全部占用, 已满时 应先发送再继续写入
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.len == self.packets.len()
+    }
+
+    /// 已写入的数据包
+    #[inline]
+    pub fn packets(&self) -> &[PacketBuf] {
+        &self.packets[..self.len]
+    }
+
+    /// 第 index 个槽位的 目标地址和端口
+    #[inline]
+    pub fn target(&self, index:usize) -> (IpAddr, u16) {
+        self.targets[index]
+    }
+
+    /// 释放全部槽位, 保留已分配的内存. 未提交的发送帧 归还 AF_XDP 套接字
+    #[inline]
+    pub fn clear(&mut self) {
+        #[cfg(target_os = "linux")]
+        if let Some(frames) = &self.frames {
+            for packet in self.packets[..self.len].iter_mut() {
+                frames.free(packet);
+            }
+        }
+        self.len = 0;
+    }
+}
+
+impl Drop for PacketBatch {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+
+/// 逐个发送 缓冲区中的数据包, 用于 不支持批量提交的发送后端
+/// 每个数据包 最多尝试 attempts 次, 返回 发送成功数量 和 发送失败数量
+pub(super) fn send_each(batch:&PacketBatch, attempts:i32, send:impl Fn(&[u8]) -> isize) -> (u64, u64) {
+
+    let mut success = 0;
+    let mut failed = 0;
+    for (index, packet) in batch.packets().iter().enumerate() {
+        if (0..attempts).any(|_| send(packet) >= 0) {
+            success += 1;
+        } else {
+            report_failed(batch, index);
+            failed += 1;
+        }
+    }
+    (success, failed)
+}
+
+/// 批量提交 缓冲区中的数据包, submit 提交给定的全部数据包, 返回 从首个数据包起 连续发送成功的数量, 首个数据包发送失败时 返回值小于等于0
+/// 每次提交后 从首个未成功的数据包 继续提交, 该数据包 累计尝试 attempts 次仍失败时 记为发送失败, 与逐个发送的重试语义一致
+/// AF_XDP 后端 提交时 取走槽位中的发送帧, 因此 submit 需要 可变引用. 网络接口后端 将消息头 写入 缓冲区自带的数组
+#[cfg(target_os = "linux")]
+pub(super) fn send_in_bulk(batch:&mut PacketBatch, attempts:i32, submit:impl Fn(&mut [PacketBuf], &mut MmsgHeaders) -> isize) -> (u64, u64) {
+
+    let len = batch.len;
+    let mut success = 0;
+    let mut failed = 0;
+
+    // 首个未成功的数据包 及其 已尝试次数
+    let mut start = 0;
+    let mut tried = 0;
+    while start < len {
+        if tried >= attempts {
+            report_failed(batch, start);
+            failed += 1;
+            start += 1;
+            tried = 0;
+            continue
+        }
+
+        let sent = submit(&mut batch.packets[start..len], &mut batch.headers);
+        if sent > 0 {
+            success += sent as u64;
+            start += sent as usize;
+            tried = 0;
+        } else {
+            tried += 1;
+        }
+    }
+    (success, failed)
+}
+
+fn report_failed(batch:&PacketBatch, index:usize) {
+    let (ip, port) = batch.target(index);
+    debug!("{} {} {}", SYS.get_info("debug", "send_failed"), ip, port);
+}
@@ -0,0 +1,52 @@
+use std::cell::RefCell;
+use std::net::IpAddr;
+use super::{send_in_bulk, PacketBatch};
+
+
+/// 写入 slots 个数据包, 第 i 个数据包 只含一个字节 i
+fn filled_batch(slots:u8) -> PacketBatch {
+    let mut batch = PacketBatch::new(slots as u64, 64);
+    for i in 0..slots {
+        batch.next_slot(IpAddr::from([10, 0, 0, i]), 80).push(i);
+    }
+    batch
+}
+
+/// 按顺序返回 预设的提交结果, 并记录 每次提交的 首个数据包 和 数据包数量
+fn run(batch:&mut PacketBatch, attempts:i32, results:&[isize]) -> ((u64, u64), Vec<(u8, usize)>) {
+    let calls = RefCell::new(vec![]);
+    let res = send_in_bulk(batch, attempts, |packets, _| {
+        let mut calls = calls.borrow_mut();
+        calls.push((packets[0][0], packets.len()));
+        results[calls.len() - 1]
+    });
+    (res, calls.into_inner())
+}
+
+#[test]
+fn partial_submit_resumes_from_first_unsent() {
+    let mut batch = filled_batch(5);
+
+    let (res, calls) = run(&mut batch, 3, &[2, 1, 2]);
+    assert_eq!(res, (5, 0));
+    assert_eq!(calls, vec![(0, 5), (2, 3), (3, 2)]);
+}
+
+#[test]
+fn each_packet_is_tried_attempts_times() {
+    let mut batch = filled_batch(5);
+
+    // 0 和 -1 都算作 首个数据包 发送失败, 成功提交后 重新计数
+    let (res, calls) = run(&mut batch, 2, &[2, 0, -1, 1, 0, -1]);
+    assert_eq!(res, (3, 2));
+    assert_eq!(calls, vec![(0, 5), (2, 3), (2, 3), (3, 2), (4, 1), (4, 1)]);
+}
+
+#[test]
+fn no_attempts_marks_all_failed() {
+    let mut batch = filled_batch(3);
+
+    let (res, calls) = run(&mut batch, 0, &[]);
+    assert_eq!(res, (0, 3));
+    assert!(calls.is_empty());
+}
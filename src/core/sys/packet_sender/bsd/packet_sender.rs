@@ -1,8 +1,7 @@
 use std::ffi::CString;
 use std::mem;
-use std::process::exit;
 use libc::{c_int, ssize_t};
-use log::error;
+use crate::core::errors::InterfaceError;
 use crate::SYS;
 use crate::tools::net_handle::net_interface::mac_addr::MacAddress;
 
@@ -68,15 +67,14 @@ impl PacketSender {
     }
 
 
-    pub fn new(interface_name_index:&(String, c_int), _mac:&MacAddress) -> Self {
+    pub fn new(interface_name_index:&(String, c_int), _mac:&MacAddress) -> Result<Self, InterfaceError> {
 
         let attempts:usize = SYS.get_conf("conf","get_socket_attempts");
         let fd = Self::get_fd(attempts);
 
-        if fd == -1 {
+        if fd < 0 {
             // 获取 fd 失败
-            error!("{}", SYS.get_info("err", "get_socket_failed"));
-            exit(1)
+            return Err(InterfaceError::socket("get_socket_failed").with(&interface_name_index.0))
         }
 
         // 写入 网络接口 名称
@@ -91,8 +89,7 @@ impl PacketSender {
                 libc::close(fd);
             }
             // 失败处理
-            error!("{}", SYS.get_info("err", "bind_failed"));
-            exit(1)
+            return Err(InterfaceError::socket("bind_failed").with(&interface_name_index.0))
         }
 
         // 启用IO完成通知
@@ -101,8 +98,7 @@ impl PacketSender {
                 libc::close(fd);
             }
             // 失败处理
-            error!("{}", SYS.get_info("err", "io_message_disabled"));
-            exit(1)
+            return Err(InterfaceError::socket("io_message_disabled").with(&interface_name_index.0))
         }
 
         /*
@@ -117,14 +113,14 @@ impl PacketSender {
         }
         */
 
-        Self {
+        Ok(Self {
             sock: fd,
-        }
+        })
     }
 
 
     #[inline]
-    pub fn send_packet(&self, buf:&Vec<u8>) -> ssize_t {
+    pub fn send_packet(&self, buf:&[u8]) -> ssize_t {
         unsafe {
             libc::write(self.sock, buf.as_ptr() as *const libc::c_void, buf.len())
         }
@@ -0,0 +1,123 @@
+use std::ops::{Deref, DerefMut};
+use std::ptr;
+
+
+/// 数据包缓冲区, 由探测模块 写入数据包
+/// 通常为 堆上的可增长缓冲区; AF_XDP 后端批量发送时 为 UMEM 中的一个发送帧, 容量固定为 帧大小, 写入后 直接提交 不再复制
+pub struct PacketBuf {
+    inner:Inner,
+}
+
+enum Inner {
+    Heap(Vec<u8>),
+
+    // UMEM 发送帧: 帧地址(相对 UMEM 起始位置), 帧起始指针, 已写入长度, 帧大小
+    Frame { addr:u64, ptr:*mut u8, len:usize, cap:usize },
+}
+
+impl PacketBuf {
+
+    pub fn new() -> Self {
+        Self { inner: Inner::Heap(Vec::new()) }
+    }
+
+    /// 使用 UMEM 中的发送帧 作为缓冲区
+    /// 调用者 需保证 ptr 指向 cap 字节的可写内存, 且在 该帧被提交 或 归还 之前 始终有效
+    pub(crate) unsafe fn frame(addr:u64, ptr:*mut u8, cap:usize) -> Self {
+        Self { inner: Inner::Frame { addr, ptr, len: 0, cap } }
+    }
+
+    /// 取出 发送帧地址 和 已写入长度, 缓冲区 变为空的堆缓冲区. 不是发送帧时 返回 None
+    pub(crate) fn take_frame(&mut self) -> Option<(u64, usize)> {
+        match self.inner {
+            Inner::Frame { addr, len, .. } => {
+                self.inner = Inner::Heap(Vec::new());
+                Some((addr, len))
+            }
+            Inner::Heap(_) => None,
+        }
+    }
+
+    #[inline]
+    pub fn clear(&mut self) {
+        match &mut self.inner {
+            Inner::Heap(v) => v.clear(),
+            Inner::Frame { len, .. } => *len = 0,
+        }
+    }
+
+    /// 发送帧 的容量固定, 探测模块的 最大数据包长度 不超过 帧大小 时 才会使用发送帧
+    #[inline]
+    pub fn reserve(&mut self, additional:usize) {
+        if let Inner::Heap(v) = &mut self.inner {
+            v.reserve(additional);
+        }
+    }
+
+    #[inline]
+    pub fn extend_from_slice(&mut self, other:&[u8]) {
+        match &mut self.inner {
+            Inner::Heap(v) => v.extend_from_slice(other),
+            Inner::Frame { ptr, len, cap, .. } => {
+                assert!(*len + other.len() <= *cap, "数据包长度 超过 发送帧大小");
+                unsafe { ptr::copy_nonoverlapping(other.as_ptr(), ptr.add(*len), other.len()); }
+                *len += other.len();
+            }
+        }
+    }
+
+    #[inline]
+    pub fn push(&mut self, byte:u8) {
+        self.extend_from_slice(&[byte]);
+    }
+
+    pub fn into_vec(self) -> Vec<u8> {
+        match self.inner {
+            Inner::Heap(v) => v,
+            Inner::Frame { .. } => self.to_vec(),
+        }
+    }
+}
+
+impl Default for PacketBuf {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Deref for PacketBuf {
+    type Target = [u8];
+
+    #[inline]
+    fn deref(&self) -> &[u8] {
+        match &self.inner {
+            Inner::Heap(v) => v,
+            Inner::Frame { ptr, len, .. } => unsafe { std::slice::from_raw_parts(*ptr, *len) },
+        }
+    }
+}
+
+impl DerefMut for PacketBuf {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut [u8] {
+        match &mut self.inner {
+            Inner::Heap(v) => v,
+            Inner::Frame { ptr, len, .. } => unsafe { std::slice::from_raw_parts_mut(*ptr, *len) },
+        }
+    }
+}
+
+impl Extend<u8> for PacketBuf {
+    fn extend<I:IntoIterator<Item=u8>>(&mut self, iter:I) {
+        match &mut self.inner {
+            Inner::Heap(v) => v.extend(iter),
+            Inner::Frame { .. } => for byte in iter { self.push(byte) },
+        }
+    }
+}
+
+impl<'a> Extend<&'a u8> for PacketBuf {
+    fn extend<I:IntoIterator<Item=&'a u8>>(&mut self, iter:I) {
+        self.extend(iter.into_iter().copied())
+    }
+}
@@ -0,0 +1,111 @@
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+use log::{error, info};
+use crate::core::sys::virtual_net::VirtualNet;
+use crate::SYS;
+use crate::tools::file::pcapng::PcapngWriter;
+use crate::core::errors::OutputError;
+
+#[cfg(test)]
+mod tests;
+
+
+/// 以太网 链路层类型
+const LINKTYPE_ETHERNET:u16 = 1;
+
+
+/// 发送后端
+#[derive(Clone)]
+pub enum SendBackend {
+
+    // 通过网络接口发送
+    Wire,
+
+    // 不发送到网络, 只在内存中计数 或 写入pcapng文件
+    DryRun(Arc<DryRunBackend>),
+
+    // 发送到 进程内的虚拟网络, 由虚拟响应器生成响应
+    Virtual(Arc<VirtualNet>),
+
+    // 通过网络接口的 AF_XDP 套接字发送, 驱动不支持时 退回 Wire
+    #[cfg(target_os = "linux")]
+    Xdp,
+}
+
+
+/// 模拟发送后端, 所有发送线程共用
+/// 速率控制器, 黑白名单 和 目标迭代器 均按正常流程运行, 只有最终的发送动作被替换
+pub struct DryRunBackend {
+    writer:Option<Mutex<PcapngWriter>>,
+    output_file:Option<String>,
+
+    frames:AtomicU64,
+    bytes:AtomicU64,
+}
+
+
+impl DryRunBackend {
+
+    /// output_file 为空时 只计数
+    pub fn new(output_file:Option<String>, comment:&str) -> Result<Self, OutputError> {
+
+        let writer = match &output_file {
+            None => None,
+            Some(path) => {
+                let w = PcapngWriter::new(path, comment)
+                    .and_then(|mut w| w.interface_id(LINKTYPE_ETHERNET, u16::MAX as u32).map(|_| w))
+                    .and_then(|mut w| w.flush().map(|_| w))
+                    .map_err(|_| OutputError::io("open_dry_run_file_failed").with(path))?;
+                Some(Mutex::new(w))
+            }
+        };
+
+        Ok(Self {
+            writer,
+            output_file,
+            frames: AtomicU64::new(0),
+            bytes: AtomicU64::new(0),
+        })
+    }
+
+    /// 已发送的 数据包数量 和 字节数
+    pub fn counts(&self) -> (u64, u64) {
+        (self.frames.load(Ordering::Relaxed), self.bytes.load(Ordering::Relaxed))
+    }
+
+    /// 模拟发送, 与真实发送器一致, 返回值小于0表示发送失败
+    #[inline]
+    pub fn send_packet(&self, buf:&[u8]) -> isize {
+
+        if let Some(writer) = &self.writer {
+            let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+            let res = writer.lock().unwrap().write_packet(0, now.as_secs() as i64, now.subsec_micros() as i64,
+                                                          buf, buf.len() as u32);
+            if res.is_err() {
+                return -1
+            }
+        }
+
+        self.frames.fetch_add(1, Ordering::Relaxed);
+        self.bytes.fetch_add(buf.len() as u64, Ordering::Relaxed);
+        buf.len() as isize
+    }
+}
+
+impl Drop for DryRunBackend {
+    fn drop(&mut self) {
+
+        if let Some(writer) = &self.writer {
+            if let Ok(mut w) = writer.lock() {
+                if w.flush().is_err() {
+                    error!("{} {}", SYS.get_info("err", "flush_dry_run_file_failed"), self.output_file.clone().unwrap_or_default());
+                }
+            }
+        }
+
+        let (frames, bytes) = self.counts();
+        info!("{} {} {} {}", SYS.get_info("info", "dry_run_finished"), frames, bytes,
+            self.output_file.clone().unwrap_or_default());
+    }
+}
@@ -0,0 +1,39 @@
+use std::fs;
+use super::DryRunBackend;
+
+
+#[test]
+fn count_frames_and_bytes() {
+    let backend = DryRunBackend::new(None, "").unwrap();
+
+    assert_eq!(backend.send_packet(&[0u8; 60]), 60);
+    assert_eq!(backend.send_packet(&[0u8; 74]), 74);
+    assert_eq!(backend.counts(), (2, 134));
+}
+
+#[test]
+fn write_pcapng() {
+    let path = std::env::temp_dir().join(format!("smap_dry_run_{}.pcapng", std::process::id()));
+    let path_str = path.to_string_lossy().to_string();
+
+    let backend = DryRunBackend::new(Some(path_str), "dry run test").unwrap();
+    let frame:Vec<u8> = (0..64).collect();
+    assert_eq!(backend.send_packet(&frame), 64);
+    assert_eq!(backend.send_packet(&frame[..42]), 42);
+    assert_eq!(backend.counts(), (2, 106));
+
+    // 释放时 写入缓冲区中的数据包
+    drop(backend);
+    let data = fs::read(&path).unwrap();
+    let _ = fs::remove_file(&path);
+
+    // 节头块 的块类型
+    assert_eq!(&data[..4], &[0x0a, 0x0d, 0x0d, 0x0a]);
+    assert_eq!(data.windows(frame.len()).filter(|w| *w == &frame[..]).count(), 1);
+}
+
+#[test]
+fn open_file_failed() {
+    let path = std::env::temp_dir().join("smap_dry_run_missing_dir").join("send.pcapng");
+    assert!(DryRunBackend::new(Some(path.to_string_lossy().to_string()), "").is_err());
+}
@@ -1,9 +1,8 @@
 use std::mem;
 use libc;
 use libc::{c_int, c_uchar};
-use std::process::exit;
-use log::error;
-use crate::SYS;
+use crate::core::errors::InterfaceError;
+use crate::core::sys::packet_sender::PacketBuf;
 use crate::tools::net_handle::net_interface::mac_addr::MacAddress;
 
 
@@ -16,11 +15,33 @@ pub struct PacketSender {
 }
 
 
+/// sendmmsg 使用的 消息头数组, 保存在 批量发送缓冲区 中, 各次提交间 重复使用
+/// 消息头中的指针 只在 单次提交 期间有效, 每次提交前 重新填写
+#[cfg(target_os = "linux")]
+#[derive(Default)]
+pub struct MmsgHeaders {
+    iovecs:Vec<libc::iovec>,
+    msgs:Vec<libc::mmsghdr>,
+}
+
+#[cfg(target_os = "linux")]
+impl MmsgHeaders {
+
+    /// 按 批量发送缓冲区 的槽位数量 预先分配
+    pub fn with_capacity(slots:usize) -> Self {
+        Self {
+            iovecs: Vec::with_capacity(slots),
+            msgs: Vec::with_capacity(slots),
+        }
+    }
+}
+
+
 #[cfg(any(target_os = "linux"))]
 impl PacketSender {
 
     /// 初始化数据包发送器, 注意index为网卡的系统index, 不是输入的网卡向量下标
-    pub fn new(interface_name_index:&(String, c_int), mac:&MacAddress) -> Self {
+    pub fn new(interface_name_index:&(String, c_int), mac:&MacAddress) -> Result<Self, InterfaceError> {
 
         // Every packet
         let eth_p_all:i32 = 0x0003;
@@ -29,10 +50,9 @@ impl PacketSender {
             libc::socket(libc::AF_PACKET, libc::SOCK_RAW, eth_p_all.to_be())
         };
 
-        if sock <= 0 {
+        if sock < 0 {
             // 获取 fd 失败
-            error!("{}", SYS.get_info("err", "get_socket_failed"));
-            exit(1)
+            return Err(InterfaceError::socket("get_socket_failed").with(&interface_name_index.0))
         }
 
         let mut sockaddr:libc::sockaddr_ll = unsafe { mem::zeroed() };
@@ -46,23 +66,47 @@ impl PacketSender {
         // 设置不阻塞模式
         // unsafe { libc::fcntl(sock, libc::F_SETFL, libc::O_NONBLOCK); }
 
-        Self {
+        Ok(Self {
             sock,
             sockaddr,
             // sockaddr_ptr: unsafe { mem::zeroed() },
             addr_len:mem::size_of_val(&sockaddr) as u32
-        }
+        })
     }
 
 
     /// 发送数据包
     #[inline]
-    pub fn send_packet(&self, buf:&Vec<u8>) -> libc::ssize_t {
+    pub fn send_packet(&self, buf:&[u8]) -> libc::ssize_t {
         unsafe { libc::sendto(self.sock, buf.as_ptr() as *const libc::c_void, buf.len(), 0, 
                               mem::transmute(&self.sockaddr as *const libc::sockaddr_ll), self.addr_len) }
     }
 
 
+    /// 使用 sendmmsg 一次提交多个数据包, 返回 从首个数据包起 连续发送成功的数量, 首个数据包发送失败时 返回 -1
+    /// 单次提交的数量 超过内核上限(UIO_MAXIOV)时 只发送前面的部分. 消息头 写入 批量发送缓冲区 自带的数组, 不重新分配内存
+    pub fn send_packets(&self, packets:&[PacketBuf], headers:&mut MmsgHeaders) -> libc::ssize_t {
+
+        headers.iovecs.clear();
+        headers.iovecs.extend(packets.iter().map(|p| libc::iovec {
+            iov_base: p.as_ptr() as *mut libc::c_void,
+            iov_len: p.len(),
+        }));
+
+        headers.msgs.clear();
+        for iov in headers.iovecs.iter_mut() {
+            let mut msg:libc::mmsghdr = unsafe { mem::zeroed() };
+            msg.msg_hdr.msg_name = &self.sockaddr as *const libc::sockaddr_ll as *mut libc::c_void;
+            msg.msg_hdr.msg_namelen = self.addr_len;
+            msg.msg_hdr.msg_iov = iov as *mut libc::iovec;
+            msg.msg_hdr.msg_iovlen = 1;
+            headers.msgs.push(msg);
+        }
+
+        unsafe { libc::sendmmsg(self.sock, headers.msgs.as_mut_ptr(), headers.msgs.len() as libc::c_uint, 0) as libc::ssize_t }
+    }
+
+
 }
 
 #[cfg(any(target_os = "linux"))]
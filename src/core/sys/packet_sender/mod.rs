@@ -24,10 +24,143 @@ target_os = "solaris",
 target_os = "openbsd",
 target_os = "macos",
 target_os = "ios"))]
-pub use bsd::packet_sender::PacketSender;
+use bsd::packet_sender::PacketSender as RawPacketSender;
 
 #[cfg(target_os = "linux")]
-pub use linux::packet_sender::PacketSender;
+use linux::packet_sender::PacketSender as RawPacketSender;
 
 #[cfg(windows)]
-pub use windows::pcap::PcapSender;
\ No newline at end of file
+pub use windows::pcap::PcapSender;
+
+mod dry_run;
+pub use dry_run::{SendBackend, DryRunBackend};
+
+mod batch;
+pub use batch::PacketBatch;
+
+mod buf;
+pub use buf::PacketBuf;
+
+#[cfg(any(
+target_os = "freebsd",
+target_os = "netbsd",
+target_os = "illumos",
+target_os = "solaris",
+target_os = "openbsd",
+target_os = "macos",
+target_os = "ios",
+target_os = "linux"))]
+use crate::tools::net_handle::net_interface::mac_addr::MacAddress;
+#[cfg(any(
+target_os = "freebsd",
+target_os = "netbsd",
+target_os = "illumos",
+target_os = "solaris",
+target_os = "openbsd",
+target_os = "macos",
+target_os = "ios",
+target_os = "linux"))]
+use crate::core::errors::InterfaceError;
+#[cfg(any(
+target_os = "freebsd",
+target_os = "netbsd",
+target_os = "illumos",
+target_os = "solaris",
+target_os = "openbsd",
+target_os = "macos",
+target_os = "ios",
+target_os = "linux"))]
+use crate::core::sys::virtual_net::VirtualNet;
+#[cfg(target_os = "linux")]
+use crate::core::sys::xdp::XdpSender;
+#[cfg(target_os = "linux")]
+use log::warn;
+#[cfg(target_os = "linux")]
+use crate::SYS;
+
+
+/// 数据包发送器, 按 发送后端 将数据包发送到网络接口 或 模拟发送
+#[cfg(any(
+target_os = "freebsd",
+target_os = "netbsd",
+target_os = "illumos",
+target_os = "solaris",
+target_os = "openbsd",
+target_os = "macos",
+target_os = "ios",
+target_os = "linux"))]
+pub enum PacketSender {
+    Wire(RawPacketSender),
+    DryRun(std::sync::Arc<DryRunBackend>),
+    Virtual(std::sync::Arc<VirtualNet>),
+    #[cfg(target_os = "linux")]
+    Xdp(XdpSender),
+}
+
+#[cfg(any(
+target_os = "freebsd",
+target_os = "netbsd",
+target_os = "illumos",
+target_os = "solaris",
+target_os = "openbsd",
+target_os = "macos",
+target_os = "ios",
+target_os = "linux"))]
+impl PacketSender {
+
+    /// 初始化数据包发送器, 只有 发送后端 为网络接口时才会打开套接字
+    pub fn new(backend:&SendBackend, interface_name_index:&(String, libc::c_int), mac:&MacAddress) -> Result<Self, InterfaceError> {
+        Ok(match backend {
+            SendBackend::Wire => PacketSender::Wire(RawPacketSender::new(interface_name_index, mac)?),
+            SendBackend::DryRun(d) => PacketSender::DryRun(d.clone()),
+            SendBackend::Virtual(v) => PacketSender::Virtual(v.clone()),
+            // 打开失败 或 发送队列均已被占用时, 该发送线程 退回 AF_PACKET 套接字
+            #[cfg(target_os = "linux")]
+            SendBackend::Xdp => match XdpSender::open(interface_name_index) {
+                Ok(s) => PacketSender::Xdp(s),
+                Err(e) => {
+                    warn!("{} {} {}", SYS.get_info("warn", "xdp_sender_fallback"), interface_name_index.0, e);
+                    PacketSender::Wire(RawPacketSender::new(interface_name_index, mac)?)
+                }
+            },
+        })
+    }
+
+    /// 创建 批量发送缓冲区, AF_XDP 后端 的槽位 直接写入 UMEM 中的发送帧
+    pub fn new_batch(&self, batch_size:u64, max_packet_len:usize) -> PacketBatch {
+        let batch = PacketBatch::new(batch_size, max_packet_len);
+        #[cfg(target_os = "linux")]
+        if let PacketSender::Xdp(s) = self {
+            if let Some(frames) = s.frames(max_packet_len) {
+                return batch.with_frames(frames)
+            }
+        }
+        batch
+    }
+
+    /// 发送数据包
+    #[inline]
+    pub fn send_packet(&self, buf:&[u8]) -> libc::ssize_t {
+        match self {
+            PacketSender::Wire(s) => s.send_packet(buf),
+            PacketSender::DryRun(d) => d.send_packet(buf) as libc::ssize_t,
+            PacketSender::Virtual(v) => v.send_packet(buf) as libc::ssize_t,
+            #[cfg(target_os = "linux")]
+            PacketSender::Xdp(s) => s.send_packet(buf),
+        }
+    }
+
+    /// 发送 批量发送缓冲区中的全部数据包, 每个数据包 最多尝试 attempts 次, 发送后 清空缓冲区
+    /// 返回 发送成功数量 和 发送失败数量. 网络接口后端(linux) 使用 sendmmsg 一次提交多个数据包, AF_XDP 后端 一次提交多个发送帧
+    pub fn send_batch(&self, batch:&mut PacketBatch, attempts:i32) -> (u64, u64) {
+        let res = match self {
+            #[cfg(target_os = "linux")]
+            PacketSender::Wire(s) => batch::send_in_bulk(batch, attempts, |packets, headers| s.send_packets(packets, headers)),
+            #[cfg(target_os = "linux")]
+            PacketSender::Xdp(s) => batch::send_in_bulk(batch, attempts, |packets, _| s.send_packets(packets)),
+            _ => batch::send_each(batch, attempts, |packet| self.send_packet(packet)),
+        };
+        batch.clear();
+        res
+    }
+}
@@ -1,7 +1,7 @@
-use std::process::exit;
-use log::{error, warn};
+use log::warn;
 use pcap::{Active, Capture};
 use pcap::sendqueue::SendQueue;
+use crate::core::errors::InterfaceError;
 use crate::SYS;
 
 
@@ -11,7 +11,7 @@ pub struct PcapSender {}
 #[cfg(windows)]
 impl PcapSender {
 
-    pub fn init(interface_name:&str, batch_size:u64, max_packet_length:usize) -> (Capture<Active>, SendQueue, u32){
+    pub fn init(interface_name:&str, batch_size:u64, max_packet_length:usize) -> Result<(Capture<Active>, SendQueue, u32), InterfaceError> {
 
         // 7 byte MAC preamble, 1 byte Start frame, 4 byte CRC, 12 byte inter-frame gap
         let mut pkt_len = (max_packet_length as u64) + 24;
@@ -42,25 +42,14 @@ impl PcapSender {
 
 
         let pcap_sender = pcap::Capture::from_device(interface_name)
-            .map_err(|_|{
-                error!("{}", SYS.get_info("err","open_pcap_sender_failed"));
-                exit(1)
-            }).unwrap()
-            .open()
-            .map_err(|_|{
-                error!("{}", SYS.get_info("err","open_pcap_sender_failed"));
-                exit(1)
-            }).unwrap();
-
-        let send_queue = SendQueue::new(queue_bytes_num).map_err(
-            |_|{
-                error!("{}", SYS.get_info("err","create_send_queue_failed"));
-                exit(1)
-            }
-        ).unwrap();
-
-
-        (pcap_sender, send_queue, queue_size)
+            .and_then(|c| c.open())
+            .map_err(|_| InterfaceError::socket("open_pcap_sender_failed").with(interface_name))?;
+
+        let send_queue = SendQueue::new(queue_bytes_num)
+            .map_err(|_| InterfaceError::socket("create_send_queue_failed").with(interface_name))?;
+
+
+        Ok((pcap_sender, send_queue, queue_size))
 
     }
 }
\ No newline at end of file
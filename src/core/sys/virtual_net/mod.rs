@@ -0,0 +1,97 @@
+//! 进程内的虚拟网络响应器
+//! 作为 发送后端 接收发送线程生成的探测帧, 按 声明式拓扑文件 生成响应帧, 并投递给所有 虚拟捕获源(接收线程)
+//! 用于在无网络连接的环境(如 持续集成)中完整运行各个模式
+
+mod topology;
+mod responder;
+
+use std::net::IpAddr;
+use std::sync::{Arc, Mutex};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::time::{SystemTime, UNIX_EPOCH};
+use ahash::AHashMap;
+use once_cell::sync::OnceCell;
+use pcap::PacketHeader;
+use crate::core::sys::virtual_net::topology::Topology;
+use crate::core::errors::ConfError;
+
+
+/// 同一次运行中 发送后端 和 接收线程 共用的虚拟网络
+static VIRTUAL_NET:OnceCell<Arc<VirtualNet>> = OnceCell::new();
+
+/// 投递给 虚拟捕获源 的响应帧
+pub type VirtualFrame = (PacketHeader, Vec<u8>);
+
+
+pub struct VirtualNet {
+    topology:Topology,
+
+    // 虚拟捕获源, 接收线程结束后 对应的管道将被移除
+    ports:Mutex<Vec<Sender<VirtualFrame>>>,
+
+    // 各响应地址 当前秒 及 当前秒内已发出的响应数量
+    rate_counter:Mutex<AHashMap<IpAddr, (u64, u64)>>,
+}
+
+impl VirtualNet {
+
+    /// 获取 虚拟网络, 首次调用时读取拓扑文件
+    pub fn global(topology_file:&str) -> Result<Arc<VirtualNet>, ConfError> {
+        VIRTUAL_NET.get_or_try_init(|| Ok(Arc::new(VirtualNet {
+            topology: Topology::from_file(topology_file)?,
+            ports: Mutex::new(vec![]),
+            rate_counter: Mutex::new(AHashMap::new()),
+        }))).cloned()
+    }
+
+    /// 接入一个 虚拟捕获源, 此后生成的所有响应帧都将投递给它
+    pub fn subscribe(&self) -> Receiver<VirtualFrame> {
+        let (sender, receiver) = channel();
+        self.ports.lock().unwrap().push(sender);
+        receiver
+    }
+
+    /// 发送探测帧, 与真实发送器一致, 返回值小于0表示发送失败
+    pub fn send_packet(&self, buf:&[u8]) -> isize {
+
+        if let Some((responder, frame)) = responder::respond(&self.topology, buf) {
+            if self.allow(responder) {
+                self.deliver(frame);
+            }
+        }
+        buf.len() as isize
+    }
+
+    /// 速率限制, 超过 响应地址 每秒最大响应数量 的响应将被丢弃
+    fn allow(&self, responder:IpAddr) -> bool {
+
+        let limit = match self.topology.get_limit(responder) {
+            Some(l) => l,
+            None => return true,
+        };
+
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        let mut counter = self.rate_counter.lock().unwrap();
+        let (sec, count) = counter.entry(responder).or_insert((now, 0));
+
+        if *sec != now {
+            *sec = now;
+            *count = 0;
+        }
+        *count += 1;
+        *count <= limit
+    }
+
+    fn deliver(&self, frame:Vec<u8>) {
+
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+        let header = PacketHeader {
+            ts: libc::timeval { tv_sec: now.as_secs() as _, tv_usec: now.subsec_micros() as _ },
+            caplen: frame.len() as u32,
+            len: frame.len() as u32,
+        };
+
+        // 移除 已经关闭的捕获源
+        self.ports.lock().unwrap().retain(|port| port.send((header, frame.clone())).is_ok());
+    }
+}
@@ -0,0 +1,309 @@
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use crate::core::sys::virtual_net::topology::Topology;
+use crate::tools::net_handle::packet::tcp::TcpPacket;
+use crate::tools::net_handle::packet::udp::UdpPacket;
+use crate::tools::net_handle::packet::v4::icmp_v4::IcmpV4Packet;
+use crate::tools::net_handle::packet::v4::packet_v4_u32::Ipv4PacketU32;
+use crate::tools::net_handle::packet::v6::icmp_v6::IcmpV6Packet;
+
+
+/// 响应数据包 的初始 ttl
+const DEFAULT_TTL:u8 = 64;
+
+/// icmp差错报文 中最多包裹的原始数据包长度, 使差错报文不超过 最小mtu
+const MAX_QUOTE_V4:usize = 576 - 28;
+const MAX_QUOTE_V6:usize = 1280 - 48;
+
+const TCP_SYN:u8 = 0b0000_0010;
+const TCP_RST:u8 = 0b0000_0100;
+const TCP_ACK:u8 = 0b0001_0000;
+
+
+/// 根据 虚拟拓扑 生成对 探测帧 的响应帧
+/// 返回: (响应地址, 以太网响应帧)
+pub fn respond(topology:&Topology, frame:&[u8]) -> Option<(IpAddr, Vec<u8>)> {
+
+    // 以太网报头
+    if frame.len() < 14 { return None }
+
+    match u16::from_be_bytes([frame[12], frame[13]]) {
+        0x0800 => respond_v4(topology, frame),
+        0x86DD => respond_v6(topology, frame),
+        _ => None
+    }
+}
+
+
+fn respond_v4(topology:&Topology, frame:&[u8]) -> Option<(IpAddr, Vec<u8>)> {
+
+    let ip = &frame[14..];
+    if ip.len() < 20 { return None }
+
+    let header_len = ((ip[0] & 0x0f) as usize) * 4;
+    let total_len = (u16::from_be_bytes([ip[2], ip[3]]) as usize).min(ip.len());
+    if header_len < 20 || total_len < header_len { return None }
+    let ip = &ip[..total_len];
+
+    let ttl = ip[8].max(1) as usize;
+    let protocol = ip[9];
+    let src = Ipv4Addr::new(ip[12], ip[13], ip[14], ip[15]);
+    let dst = Ipv4Addr::new(ip[16], ip[17], ip[18], ip[19]);
+    let payload = &ip[header_len..];
+
+    let route = topology.get_route(IpAddr::V4(dst));
+    if ttl <= route.len() {
+        // 在第 ttl 跳的路由器处超时, 路由器收到时 ttl 为 1
+        let router = match route[ttl - 1] { IpAddr::V4(r) => r, IpAddr::V6(_) => return None };
+        let icmp = icmp_error_v4(11, 0, &quote_v4(ip, 1));
+        return Some((IpAddr::V4(router), build_v4(frame, router, src, DEFAULT_TTL.saturating_sub((ttl - 1) as u8), 1, icmp)))
+    }
+
+    let host = topology.get_host(IpAddr::V4(dst))?;
+    let hops = route.len();
+    let reply_ttl = DEFAULT_TTL.saturating_sub(hops as u8);
+
+    let reply = match protocol {
+        // icmp 回显请求
+        1 => {
+            if !host.icmp || payload.len() < 8 || payload[0] != 8 { return None }
+            let mut icmp = payload.to_vec();
+            icmp[0] = 0;
+            set_check_sum(&mut icmp, 2, IcmpV4Packet::get_check_sum);
+            (1, icmp)
+        }
+        6 => {
+            let mut tcp = tcp_reply(payload, |dport| host.tcp.contains(&dport))?;
+            let len = tcp.len() as u32;
+            set_check_sum(&mut tcp, 16, |d| TcpPacket::get_check_sum_v4(&dst.octets(), &src.octets(), len, d));
+            (6, tcp)
+        }
+        17 => {
+            if payload.len() < 8 { return None }
+            let dport = u16::from_be_bytes([payload[2], payload[3]]);
+            if host.udp.contains(&dport) {
+                let mut udp = udp_reply(payload);
+                let len = udp.len() as u32;
+                set_check_sum(&mut udp, 6, |d| UdpPacket::get_check_sum_v4(&dst.octets(), &src.octets(), len, d));
+                (17, udp)
+            } else {
+                // 端口不可达, 包裹的数据包中 ttl 为到达目标时的剩余值
+                (1, icmp_error_v4(3, 3, &quote_v4(ip, (ttl - hops) as u8)))
+            }
+        }
+        _ => return None
+    };
+
+    Some((IpAddr::V4(dst), build_v4(frame, dst, src, reply_ttl, reply.0, reply.1)))
+}
+
+
+fn respond_v6(topology:&Topology, frame:&[u8]) -> Option<(IpAddr, Vec<u8>)> {
+
+    let ip = &frame[14..];
+    if ip.len() < 40 { return None }
+
+    let total_len = (40 + u16::from_be_bytes([ip[4], ip[5]]) as usize).min(ip.len());
+    let ip = &ip[..total_len];
+
+    let next_header = ip[6];
+    let hop_limit = ip[7].max(1) as usize;
+    let src = Ipv6Addr::from(<[u8; 16]>::try_from(&ip[8..24]).ok()?);
+    let dst = Ipv6Addr::from(<[u8; 16]>::try_from(&ip[24..40]).ok()?);
+    let payload = &ip[40..];
+
+    let route = topology.get_route(IpAddr::V6(dst));
+    if hop_limit <= route.len() {
+        let router = match route[hop_limit - 1] { IpAddr::V6(r) => r, IpAddr::V4(_) => return None };
+        let icmp = icmp_error_v6(router, src, 3, 0, &quote_v6(ip, 1));
+        return Some((IpAddr::V6(router), build_v6(frame, router, src, DEFAULT_TTL.saturating_sub((hop_limit - 1) as u8), 58, icmp)))
+    }
+
+    let host = topology.get_host(IpAddr::V6(dst))?;
+    let hops = route.len();
+    let reply_hop_limit = DEFAULT_TTL.saturating_sub(hops as u8);
+
+    let reply = match next_header {
+        // icmp_v6 回显请求
+        58 => {
+            if !host.icmp || payload.len() < 8 || payload[0] != 128 { return None }
+            let mut icmp = payload.to_vec();
+            icmp[0] = 129;
+            let len = icmp.len() as u32;
+            set_check_sum(&mut icmp, 2, |d| IcmpV6Packet::get_check_sum(&dst.octets(), &src.octets(), len, d));
+            (58, icmp)
+        }
+        6 => {
+            let mut tcp = tcp_reply(payload, |dport| host.tcp.contains(&dport))?;
+            let len = tcp.len() as u32;
+            set_check_sum(&mut tcp, 16, |d| TcpPacket::get_check_sum_v6(&dst.octets(), &src.octets(), len, d));
+            (6, tcp)
+        }
+        17 => {
+            if payload.len() < 8 { return None }
+            let dport = u16::from_be_bytes([payload[2], payload[3]]);
+            if host.udp.contains(&dport) {
+                let mut udp = udp_reply(payload);
+                let len = udp.len() as u32;
+                set_check_sum(&mut udp, 6, |d| UdpPacket::get_check_sum_v6(&dst.octets(), &src.octets(), len, d));
+                (17, udp)
+            } else {
+                (58, icmp_error_v6(dst, src, 1, 4, &quote_v6(ip, (hop_limit - hops) as u8)))
+            }
+        }
+        _ => return None
+    };
+
+    Some((IpAddr::V6(dst), build_v6(frame, dst, src, reply_hop_limit, reply.0, reply.1)))
+}
+
+
+/// 生成 tcp响应: 开放端口 回复 syn-ack, 关闭端口 回复 rst
+fn tcp_reply(probe:&[u8], is_open:impl Fn(u16) -> bool) -> Option<Vec<u8>> {
+
+    if probe.len() < 20 { return None }
+
+    let sport = u16::from_be_bytes([probe[0], probe[1]]);
+    let dport = u16::from_be_bytes([probe[2], probe[3]]);
+    let seq = u32::from_be_bytes([probe[4], probe[5], probe[6], probe[7]]);
+    let ack = u32::from_be_bytes([probe[8], probe[9], probe[10], probe[11]]);
+    let flags = probe[13];
+
+    // 不响应 rst
+    if flags & TCP_RST != 0 { return None }
+
+    let (reply_seq, reply_ack, reply_flags, with_mss) = if flags & TCP_SYN != 0 && flags & TCP_ACK == 0 {
+        if is_open(dport) {
+            // 由 探测序列号 派生 响应序列号, 保证重复探测时结果一致
+            (seq.rotate_left(16) ^ 0x5a5a_5a5a, seq.wrapping_add(1), TCP_SYN | TCP_ACK, true)
+        } else {
+            (0, seq.wrapping_add(1), TCP_RST | TCP_ACK, false)
+        }
+    } else {
+        // 非 syn 探测(如 ack扫描), 使用 探测确认号 作为 rst 的序列号
+        (ack, 0, TCP_RST, false)
+    };
+
+    let header_len:u8 = if with_mss { 6 } else { 5 };
+
+    let mut tcp = Vec::with_capacity(24);
+    tcp.extend_from_slice(&dport.to_be_bytes());
+    tcp.extend_from_slice(&sport.to_be_bytes());
+    tcp.extend_from_slice(&reply_seq.to_be_bytes());
+    tcp.extend_from_slice(&reply_ack.to_be_bytes());
+    tcp.extend_from_slice(&[header_len << 4, reply_flags, 0xff, 0xff, 0, 0, 0, 0]);
+    if with_mss {
+        tcp.extend_from_slice(&TcpPacket::get_mss_option());
+    }
+    Some(tcp)
+}
+
+
+/// 生成 udp响应: 交换端口, 原样返回负载
+fn udp_reply(probe:&[u8]) -> Vec<u8> {
+
+    let data = &probe[8..];
+    let len = (8 + data.len()) as u16;
+
+    let mut udp = Vec::with_capacity(8 + data.len());
+    udp.extend_from_slice(&probe[2..4]);
+    udp.extend_from_slice(&probe[0..2]);
+    udp.extend_from_slice(&len.to_be_bytes());
+    udp.extend_from_slice(&[0, 0]);
+    udp.extend_from_slice(data);
+    udp
+}
+
+
+/// 包裹的原始ipv4数据包, ttl 为 路由器 或 目标 收到时的值
+fn quote_v4(ip:&[u8], arrived_ttl:u8) -> Vec<u8> {
+
+    let mut quoted = ip[..ip.len().min(MAX_QUOTE_V4)].to_vec();
+    quoted[8] = arrived_ttl;
+
+    // 只有 无选项的首部 才重新计算校验和
+    if (quoted[0] & 0x0f) == 5 {
+        quoted[10] = 0;
+        quoted[11] = 0;
+        let check_sum = Ipv4PacketU32::get_check_sum_from_buf(&quoted[..20]);
+        quoted[10] = check_sum[0];
+        quoted[11] = check_sum[1];
+    }
+    quoted
+}
+
+fn quote_v6(ip:&[u8], arrived_hop_limit:u8) -> Vec<u8> {
+    let mut quoted = ip[..ip.len().min(MAX_QUOTE_V6)].to_vec();
+    quoted[7] = arrived_hop_limit;
+    quoted
+}
+
+fn icmp_error_v4(icmp_type:u8, code:u8, quoted:&[u8]) -> Vec<u8> {
+
+    let mut icmp = vec![icmp_type, code, 0, 0, 0, 0, 0, 0];
+    icmp.extend_from_slice(quoted);
+    set_check_sum(&mut icmp, 2, IcmpV4Packet::get_check_sum);
+    icmp
+}
+
+fn icmp_error_v6(src:Ipv6Addr, dst:Ipv6Addr, icmp_type:u8, code:u8, quoted:&[u8]) -> Vec<u8> {
+
+    let mut icmp = vec![icmp_type, code, 0, 0, 0, 0, 0, 0];
+    icmp.extend_from_slice(quoted);
+    let len = icmp.len() as u32;
+    set_check_sum(&mut icmp, 2, |d| IcmpV6Packet::get_check_sum(&src.octets(), &dst.octets(), len, d));
+    icmp
+}
+
+/// 将 校验和字段 置零后计算, 并写入指定位置
+fn set_check_sum(data:&mut [u8], index:usize, check_sum:impl Fn(&[u8]) -> [u8; 2]) {
+    data[index] = 0;
+    data[index + 1] = 0;
+    let sum = check_sum(data);
+    data[index] = sum[0];
+    data[index + 1] = sum[1];
+}
+
+
+/// 以太网报头: 交换探测帧的 源地址 和 目的地址
+fn ethernet_reply(frame:&[u8], ether_type:u16, capacity:usize) -> Vec<u8> {
+
+    let mut reply = Vec::with_capacity(14 + capacity);
+    reply.extend_from_slice(&frame[6..12]);
+    reply.extend_from_slice(&frame[0..6]);
+    reply.extend_from_slice(&ether_type.to_be_bytes());
+    reply
+}
+
+fn build_v4(frame:&[u8], src:Ipv4Addr, dst:Ipv4Addr, ttl:u8, protocol:u8, payload:Vec<u8>) -> Vec<u8> {
+
+    let total_len = (20 + payload.len()) as u16;
+
+    let mut header = [0u8; 20];
+    header[0] = 0x45;
+    header[2..4].copy_from_slice(&total_len.to_be_bytes());
+    // 不分片
+    header[6] = 0x40;
+    header[8] = ttl;
+    header[9] = protocol;
+    header[12..16].copy_from_slice(&src.octets());
+    header[16..20].copy_from_slice(&dst.octets());
+    let check_sum = Ipv4PacketU32::get_check_sum_from_buf(&header);
+    header[10..12].copy_from_slice(&check_sum);
+
+    let mut reply = ethernet_reply(frame, 0x0800, total_len as usize);
+    reply.extend_from_slice(&header);
+    reply.extend_from_slice(&payload);
+    reply
+}
+
+fn build_v6(frame:&[u8], src:Ipv6Addr, dst:Ipv6Addr, hop_limit:u8, next_header:u8, payload:Vec<u8>) -> Vec<u8> {
+
+    let mut reply = ethernet_reply(frame, 0x86DD, 40 + payload.len());
+    reply.extend_from_slice(&[0x60, 0, 0, 0]);
+    reply.extend_from_slice(&(payload.len() as u16).to_be_bytes());
+    reply.extend_from_slice(&[next_header, hop_limit]);
+    reply.extend_from_slice(&src.octets());
+    reply.extend_from_slice(&dst.octets());
+    reply.extend_from_slice(&payload);
+    reply
+}
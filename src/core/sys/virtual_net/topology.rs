@@ -0,0 +1,180 @@
+use std::cmp::Reverse;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::net::IpAddr;
+use ahash::{AHashMap, AHashSet};
+use crate::core::conf::tools::args_parse::port::parse_ports_vec;
+use crate::tools::file::parse_context::parse_line_with_annotation;
+use crate::core::errors::ConfError;
+
+
+/// 地址前缀, 以 u128 统一表示 ipv4 和 ipv6
+#[derive(Clone)]
+pub struct Prefix {
+    is_v6:bool,
+    net:u128,
+    len:u8,
+}
+
+impl Prefix {
+
+    /// 解析 地址 或 地址前缀, 如: 10.0.0.0/24, 2001:db8::/32, 10.0.0.1
+    fn parse(s:&str) -> Option<Self> {
+
+        let mut parts = s.trim().split('/');
+        let ip:IpAddr = parts.next()?.parse().ok()?;
+        let max_len = if ip.is_ipv6() { 128 } else { 32 };
+
+        let len = match parts.next() {
+            Some(l) => l.parse::<u8>().ok()?,
+            None => max_len,
+        };
+        if len > max_len || parts.next().is_some() { return None }
+
+        let (is_v6, val) = Self::ip_to_u128(ip);
+        Some(Self { is_v6, net: Self::mask(val, len, max_len), len })
+    }
+
+    fn ip_to_u128(ip:IpAddr) -> (bool, u128) {
+        match ip {
+            IpAddr::V4(v4) => (false, u32::from(v4) as u128),
+            IpAddr::V6(v6) => (true, u128::from(v6)),
+        }
+    }
+
+    fn mask(val:u128, len:u8, max_len:u8) -> u128 {
+        let move_bits = (max_len - len) as u32;
+        val.checked_shr(move_bits).unwrap_or(0).checked_shl(move_bits).unwrap_or(0)
+    }
+
+    pub fn contains(&self, ip:IpAddr) -> bool {
+        let (is_v6, val) = Self::ip_to_u128(ip);
+        let max_len = if is_v6 { 128 } else { 32 };
+        is_v6 == self.is_v6 && Self::mask(val, self.len, max_len) == self.net
+    }
+}
+
+
+/// 主机提供的服务
+#[derive(Clone, Default)]
+pub struct Services {
+    // 是否响应 icmp 回显请求
+    pub icmp:bool,
+    // 开放的 tcp端口, 其余端口 回复 rst
+    pub tcp:AHashSet<u16>,
+    // 开放的 udp端口, 其余端口 回复 端口不可达
+    pub udp:AHashSet<u16>,
+}
+
+
+/// 声明式的虚拟网络拓扑
+/// 每行一条声明, # 或 ; 之后为注释:
+///   host  <地址>   [icmp] [tcp=<端口>] [udp=<端口>]     存活主机
+///   alias <前缀>   [icmp] [tcp=<端口>] [udp=<端口>]     别名前缀, 前缀内所有地址都作为同一主机响应
+///   route <前缀>   <路由器1>,<路由器2>,...              前往该前缀需依次经过的路由器, 用于 ttl 超时响应
+///   limit <前缀>   <数量>                              前缀内每个地址 每秒最多发出的响应数量
+pub struct Topology {
+    hosts:AHashMap<IpAddr, Services>,
+    aliases:Vec<(Prefix, Services)>,
+    routes:Vec<(Prefix, Vec<IpAddr>)>,
+    limits:Vec<(Prefix, u64)>,
+}
+
+impl Topology {
+
+    pub fn from_file(path:&str) -> Result<Self, ConfError> {
+
+        let file = File::open(path).map_err(|_| ConfError::io("open_virtual_net_file_failed").with(path))?;
+
+        let mut topology = Self {
+            hosts: AHashMap::new(),
+            aliases: vec![],
+            routes: vec![],
+            limits: vec![],
+        };
+
+        for line in BufReader::new(file).lines() {
+            let line = match line {
+                Ok(l) => l,
+                Err(_) => continue,
+            };
+
+            if let Some(l) = parse_line_with_annotation(line) {
+                if topology.parse_line(&l).is_none() {
+                    return Err(ConfError::invalid("virtual_net_line_invalid").with(l))
+                }
+            }
+        }
+
+        // 按 前缀长度 从长到短排列, 匹配时取 最长前缀
+        topology.aliases.sort_by_key(|r| Reverse(r.0.len));
+        topology.routes.sort_by_key(|r| Reverse(r.0.len));
+        topology.limits.sort_by_key(|r| Reverse(r.0.len));
+
+        Ok(topology)
+    }
+
+    fn parse_line(&mut self, line:&str) -> Option<()> {
+
+        let mut items = line.split_whitespace();
+        let kind = items.next()?;
+        let target = items.next()?;
+
+        match kind {
+            "host" => {
+                let ip:IpAddr = target.parse().ok()?;
+                self.hosts.insert(ip, Self::parse_services(items)?);
+            }
+            "alias" => {
+                self.aliases.push((Prefix::parse(target)?, Self::parse_services(items)?));
+            }
+            "route" => {
+                let mut routers = vec![];
+                for r in items.next()?.split(',') {
+                    routers.push(r.trim().parse::<IpAddr>().ok()?);
+                }
+                if items.next().is_some() { return None }
+                self.routes.push((Prefix::parse(target)?, routers));
+            }
+            "limit" => {
+                let num = items.next()?.parse::<u64>().ok()?;
+                if items.next().is_some() { return None }
+                self.limits.push((Prefix::parse(target)?, num));
+            }
+            _ => return None
+        }
+        Some(())
+    }
+
+    fn parse_services<'a>(items:impl Iterator<Item = &'a str>) -> Option<Services> {
+
+        let mut services = Services::default();
+        for item in items {
+            match item.split_once('=') {
+                None if item == "icmp" => services.icmp = true,
+                Some(("tcp", ports)) => services.tcp.extend(parse_ports_vec(ports).ok()?),
+                Some(("udp", ports)) => services.udp.extend(parse_ports_vec(ports).ok()?),
+                _ => return None
+            }
+        }
+        Some(services)
+    }
+
+    /// 查找 目标地址 对应的主机, 精确声明的主机 优先于 别名前缀
+    pub fn get_host(&self, ip:IpAddr) -> Option<&Services> {
+        self.hosts.get(&ip).or_else(|| self.aliases.iter().find(|(p, _)| p.contains(ip)).map(|(_, s)| s))
+    }
+
+    /// 前往 目标地址 的路由路径
+    pub fn get_route(&self, ip:IpAddr) -> &[IpAddr] {
+        match self.routes.iter().find(|(p, _)| p.contains(ip)) {
+            Some((_, r)) => r,
+            None => &[],
+        }
+    }
+
+    /// 响应地址 每秒最多发出的响应数量
+    pub fn get_limit(&self, ip:IpAddr) -> Option<u64> {
+        self.limits.iter().find(|(p, _)| p.contains(ip)).map(|(_, n)| *n)
+    }
+}
@@ -0,0 +1,209 @@
+//! AF_XDP 发送 和 接收后端(linux)
+//! 每个 发送线程 独占网络接口的一个发送队列, 接收线程 在每个接收队列上 各打开一个套接字,
+//! 并在网络接口上挂载 XDP 程序, 将 探测响应 重定向到对应队列的套接字, 其余数据包 仍交由内核协议栈处理.
+//! 多个接收线程 共用一个 XDP 程序, 接收队列 按序号 轮流分给各接收线程.
+//! 同一队列上的 发送线程 和 接收线程 共用一个套接字(一个队列 只能绑定一个 UMEM).
+//! 探测模块 直接将数据包写入 UMEM 中的发送帧, 接收线程 直接解析 UMEM 中的接收帧, 均不再复制.
+//! 驱动支持时 使用 零拷贝模式, 否则 使用 复制模式 和 内核通用 XDP 路径(如 veth)
+
+mod ring;
+mod socket;
+mod program;
+
+use std::ffi::CString;
+use std::fs;
+use std::io;
+use std::sync::{Arc, Mutex, Weak};
+use std::time::Duration;
+use libc::c_int;
+use pcap::PacketHeader;
+use crate::core::sys::packet_sender::PacketBuf;
+use crate::SYS;
+
+use program::XdpProgram;
+use socket::XskPort;
+
+
+/// 已打开的套接字, 按 网络接口 和 队列 共用
+static PORTS:Mutex<Vec<Weak<XskPort>>> = Mutex::new(Vec::new());
+
+
+/// 获取 网络接口指定队列上的套接字, 尚未打开时 创建并登记
+fn port(ifindex:u32, queue:u32) -> io::Result<Arc<XskPort>> {
+
+    let mut ports = PORTS.lock().unwrap();
+    ports.retain(|p| p.strong_count() > 0);
+
+    if let Some(p) = ports.iter().filter_map(|p| p.upgrade()).find(|p| p.ifindex == ifindex && p.queue == queue) {
+        return Ok(p)
+    }
+
+    let p = Arc::new(XskPort::open(ifindex, queue, SYS.get_conf("conf", "xdp_ring_size"), SYS.get_conf("conf", "xdp_frame_size"))?);
+    ports.push(Arc::downgrade(&p));
+    Ok(p)
+}
+
+/// 网络接口的 接收队列(rx) 或 发送队列(tx) 数量, 无法读取时 视为 1
+fn queue_num(interface:&str, kind:&str) -> u32 {
+    let prefix = format!("{}-", kind);
+    match fs::read_dir(format!("/sys/class/net/{}/queues", interface)) {
+        Ok(dir) => (dir.filter_map(|e| e.ok())
+            .filter(|e| e.file_name().to_string_lossy().starts_with(&prefix))
+            .count() as u32).max(1),
+        Err(_) => 1,
+    }
+}
+
+
+/// AF_XDP 数据包发送器, 占用 网络接口上 第一个空闲的发送队列, 释放时 归还该队列
+pub struct XdpSender {
+    port:Arc<XskPort>,
+}
+
+impl XdpSender {
+
+    /// 注意index为网卡的系统index, 所有发送队列 均已被占用时 返回错误
+    pub fn open(interface_name_index:&(String, c_int)) -> io::Result<Self> {
+
+        let ifindex = interface_name_index.1 as u32;
+        for queue in 0..queue_num(&interface_name_index.0, "tx") {
+            let port = port(ifindex, queue)?;
+            if port.take_tx() {
+                return Ok(Self { port })
+            }
+        }
+        Err(io::Error::from(io::ErrorKind::AddrInUse))
+    }
+
+    /// 批量发送缓冲区 使用的发送帧来源, 探测模块的 最大数据包长度 超过 帧大小 时 返回 None
+    pub fn frames(&self, max_packet_len:usize) -> Option<XdpFrames> {
+        if max_packet_len > self.port.frame_size() { return None }
+        Some(XdpFrames { port: self.port.clone() })
+    }
+
+    /// 提交多个数据包, 返回 从首个数据包起 连续提交成功的数量, 首个数据包提交失败时 返回 -1
+    #[inline]
+    pub fn send_packets(&self, packets:&mut [PacketBuf]) -> libc::ssize_t {
+        self.port.submit(packets)
+    }
+
+    /// 复制 并提交 单个数据包
+    #[inline]
+    pub fn send_packet(&self, packet:&[u8]) -> libc::ssize_t {
+        self.port.send(packet)
+    }
+}
+
+impl Drop for XdpSender {
+    fn drop(&mut self) {
+        self.port.release_tx();
+    }
+}
+
+
+/// 批量发送缓冲区 的发送帧来源, 与 发送器 共用套接字
+pub struct XdpFrames {
+    port:Arc<XskPort>,
+}
+
+impl XdpFrames {
+
+    /// 可同时占用的发送帧数量, 保留一半 给 已提交但尚未发送完成的数据包
+    pub fn capacity(&self) -> usize {
+        (self.port.tx_frame_num() / 2).max(1)
+    }
+
+    /// 取出一个空闲的发送帧, 没有时 返回 None
+    #[inline]
+    pub fn alloc(&self) -> Option<PacketBuf> {
+        self.port.alloc_frame()
+    }
+
+    /// 归还 未提交的发送帧, 不是发送帧时 不做处理
+    #[inline]
+    pub fn free(&self, buf:&mut PacketBuf) {
+        self.port.free_frame(buf)
+    }
+}
+
+
+/// AF_XDP 数据包接收器, 占用 网络接口上的部分 或 全部接收队列, 最后一个接收器 释放时 卸载 XDP 程序
+pub struct XdpReceiver {
+    ports:Vec<Arc<XskPort>>,
+    // 下一次 首先读取的队列
+    next:usize,
+
+    // 在 套接字 之后释放
+    _program:Arc<XdpProgram>,
+}
+
+impl XdpReceiver {
+
+    /// 挂载 XDP 程序, 并将 网络接口的接收队列 按序号 轮流分给 worker_num 个接收器, 接收队列 少于 worker_num 时 只创建 队列数量个接收器
+    /// source_ports 为 探测使用的 源端口范围(闭区间), 目的端口在该范围内的 tcp 和 udp 数据包 视为探测响应
+    pub fn open(interface:&str, source_ports:(u16, u16), worker_num:usize) -> io::Result<Vec<Self>> {
+
+        let name = CString::new(interface).map_err(|_| io::Error::from(io::ErrorKind::InvalidInput))?;
+        let ifindex = unsafe { libc::if_nametoindex(name.as_ptr()) };
+        if ifindex == 0 {
+            return Err(io::Error::last_os_error())
+        }
+
+        let queues = queue_num(interface, "rx");
+        let program = Arc::new(XdpProgram::attach(ifindex, queues, source_ports)?);
+
+        // 先构造 接收器, 中途失败时 由 释放过程 归还已占用的接收方向
+        let worker_num = worker_num.clamp(1, queues as usize);
+        let mut receivers:Vec<Self> = (0..worker_num)
+            .map(|_| Self { ports: Vec::new(), next: 0, _program: program.clone() }).collect();
+        for queue in 0..queues {
+            let port = port(ifindex, queue)?;
+            if !port.take_rx() {
+                return Err(io::Error::from(io::ErrorKind::AddrInUse))
+            }
+            receivers[queue as usize % worker_num].ports.push(port.clone());
+            program.register(queue, port.fd())?;
+        }
+        Ok(receivers)
+    }
+
+    /// 是否 全部队列 均以零拷贝模式绑定
+    pub fn zero_copy(&self) -> bool {
+        self.ports.iter().all(|p| p.zero_copy)
+    }
+
+    /// 轮流读取 各队列, 将 UMEM 中的数据包 直接交给 f 处理, 全部为空时 等待数据包到达, 超过 timeout 仍没有数据包 时 返回空
+    pub fn recv<R>(&mut self, timeout:Duration, mut f:impl FnMut(&PacketHeader, &[u8]) -> R) -> Option<R> {
+
+        for waited in [false, true] {
+            for i in 0..self.ports.len() {
+                let index = (self.next + i) % self.ports.len();
+                if let Some(res) = self.ports[index].recv(&mut f) {
+                    self.next = (index + 1) % self.ports.len();
+                    return Some(res)
+                }
+            }
+            if waited { break }
+
+            // 等待 任一队列 可读, 同时唤醒 需要唤醒的填充队列
+            let mut fds:Vec<libc::pollfd> = self.ports.iter()
+                .map(|p| libc::pollfd { fd: p.fd(), events: libc::POLLIN, revents: 0 }).collect();
+            let res = unsafe { libc::poll(fds.as_mut_ptr(), fds.len() as libc::nfds_t, timeout.as_millis() as c_int) };
+            if res <= 0 { return None }
+        }
+        None
+    }
+
+    /// 全部队列的 丢包数量
+    pub fn dropped(&self) -> u64 {
+        self.ports.iter().map(|p| p.dropped()).sum()
+    }
+}
+
+impl Drop for XdpReceiver {
+    fn drop(&mut self) {
+        for port in self.ports.iter() {
+            port.release_rx();
+        }
+    }
+}
@@ -0,0 +1,319 @@
+use std::io;
+use std::mem::size_of;
+use libc::{c_int, c_long};
+
+
+// bpf 系统调用命令
+const BPF_MAP_CREATE:c_long = 0;
+const BPF_MAP_UPDATE_ELEM:c_long = 2;
+const BPF_PROG_LOAD:c_long = 5;
+const BPF_LINK_CREATE:c_long = 28;
+
+const BPF_MAP_TYPE_XSKMAP:u32 = 17;
+const BPF_PROG_TYPE_XDP:u32 = 6;
+const BPF_XDP:u32 = 37;
+const BPF_PSEUDO_MAP_FD:u8 = 1;
+const BPF_FUNC_REDIRECT_MAP:i32 = 51;
+
+// 挂载模式: 驱动原生模式, 内核通用模式(任意网络接口)
+const XDP_FLAGS_SKB_MODE:u32 = 1 << 1;
+const XDP_FLAGS_DRV_MODE:u32 = 1 << 2;
+
+const XDP_PASS:i32 = 2;
+
+// xdp_md 中 各字段的偏移量
+const XDP_MD_DATA:i16 = 0;
+const XDP_MD_DATA_END:i16 = 4;
+const XDP_MD_RX_QUEUE_INDEX:i16 = 16;
+
+
+/// 挂载在网络接口上的 XDP 程序, 释放时 自动卸载
+/// 程序 只将 探测响应 重定向到 对应接收队列的 AF_XDP 套接字, 其余数据包 交由内核协议栈处理:
+/// 目的端口 在 源端口范围内 的 tcp 和 udp 数据包, icmp 和 icmpv6 的 回显应答,
+/// 以及 引用了 源端口在源端口范围内的 tcp/udp 数据包 或 回显请求 的 差错报文(目的不可达, 超时, 参数问题)
+/// 其他 icmp 报文(如 回显请求, 数据包过大, 邻居发现) 和 带有 ipv4选项 或 ipv6扩展首部 的数据包 均交由内核处理
+pub struct XdpProgram {
+    map_fd:c_int,
+    prog_fd:c_int,
+    link_fd:c_int,
+}
+
+impl XdpProgram {
+
+    /// 加载程序 并挂载到网络接口, 依次尝试 驱动原生模式 和 内核通用模式
+    /// queue_num 为 接收队列数量, source_ports 为 探测使用的 源端口范围(闭区间)
+    pub fn attach(ifindex:u32, queue_num:u32, source_ports:(u16, u16)) -> io::Result<Self> {
+
+        let map_fd = create_xsk_map(queue_num)?;
+        let mut program = XdpProgram { map_fd, prog_fd: -1, link_fd: -1 };
+
+        program.prog_fd = load_program(map_fd, source_ports)?;
+
+        for flags in [XDP_FLAGS_DRV_MODE, XDP_FLAGS_SKB_MODE] {
+            let mut attr = BpfAttr::default();
+            attr.set_u32(0, program.prog_fd as u32);
+            attr.set_u32(4, ifindex);
+            attr.set_u32(8, BPF_XDP);
+            attr.set_u32(12, flags);
+            if let Ok(fd) = bpf(BPF_LINK_CREATE, &attr) {
+                program.link_fd = fd;
+                return Ok(program)
+            }
+        }
+        Err(io::Error::last_os_error())
+    }
+
+    /// 将 AF_XDP 套接字 注册到 对应接收队列
+    pub fn register(&self, queue:u32, socket_fd:c_int) -> io::Result<()> {
+        let key = queue;
+        let value = socket_fd as u32;
+
+        let mut attr = BpfAttr::default();
+        attr.set_u32(0, self.map_fd as u32);
+        attr.set_u64(8, &key as *const u32 as u64);
+        attr.set_u64(16, &value as *const u32 as u64);
+        bpf(BPF_MAP_UPDATE_ELEM, &attr).map(|_| ())
+    }
+}
+
+impl Drop for XdpProgram {
+    fn drop(&mut self) {
+        for fd in [self.link_fd, self.prog_fd, self.map_fd] {
+            if fd >= 0 {
+                unsafe { libc::close(fd); }
+            }
+        }
+    }
+}
+
+
+/// bpf 系统调用参数(union bpf_attr), 按字节偏移 写入各命令的字段
+#[repr(C, align(8))]
+struct BpfAttr([u8; 128]);
+
+impl Default for BpfAttr {
+    fn default() -> Self {
+        Self([0; 128])
+    }
+}
+
+impl BpfAttr {
+
+    fn set_u32(&mut self, offset:usize, val:u32) {
+        self.0[offset..offset + 4].copy_from_slice(&val.to_ne_bytes());
+    }
+
+    fn set_u64(&mut self, offset:usize, val:u64) {
+        self.0[offset..offset + 8].copy_from_slice(&val.to_ne_bytes());
+    }
+}
+
+fn bpf(cmd:c_long, attr:&BpfAttr) -> io::Result<c_int> {
+    let res = unsafe { libc::syscall(libc::SYS_bpf, cmd, attr as *const BpfAttr, size_of::<BpfAttr>()) };
+    if res < 0 { Err(io::Error::last_os_error()) } else { Ok(res as c_int) }
+}
+
+
+fn create_xsk_map(queue_num:u32) -> io::Result<c_int> {
+    let mut attr = BpfAttr::default();
+    attr.set_u32(0, BPF_MAP_TYPE_XSKMAP);
+    attr.set_u32(4, 4);
+    attr.set_u32(8, 4);
+    attr.set_u32(12, queue_num.max(1));
+    bpf(BPF_MAP_CREATE, &attr)
+}
+
+fn load_program(map_fd:c_int, source_ports:(u16, u16)) -> io::Result<c_int> {
+
+    let insns = assemble(map_fd, source_ports);
+    let license = b"Dual MIT/GPL\0";
+
+    let mut attr = BpfAttr::default();
+    attr.set_u32(0, BPF_PROG_TYPE_XDP);
+    attr.set_u32(4, insns.len() as u32);
+    attr.set_u64(8, insns.as_ptr() as u64);
+    attr.set_u64(16, license.as_ptr() as u64);
+    bpf(BPF_PROG_LOAD, &attr)
+}
+
+
+/// 汇编 XDP 程序, 以 标签 表示跳转目标
+fn assemble(map_fd:c_int, source_ports:(u16, u16)) -> Vec<u64> {
+
+    // 寄存器: r1 上下文, r2 数据起始, r3 数据结束, r4 边界检查, r5 读取的字段
+    let mut a = Asm::default();
+
+    a.ldx(LDX_W, 2, 1, XDP_MD_DATA);
+    a.ldx(LDX_W, 3, 1, XDP_MD_DATA_END);
+    a.bound(14);
+    a.ldx(LDX_H, 5, 2, 12);
+    a.jmp_imm(JEQ, 5, u16::from_ne_bytes([0x08, 0x00]) as i32, "v4");
+    a.jmp_imm(JEQ, 5, u16::from_ne_bytes([0x86, 0xdd]) as i32, "v6");
+    a.ja("pass");
+
+    // ipv4: 只处理 首部长度为 20字节 的数据包
+    a.label("v4");
+    a.bound(14 + 20 + 4);
+    a.ldx(LDX_B, 5, 2, 14);
+    a.jmp_imm(JNE, 5, 0x45, "pass");
+    a.ldx(LDX_B, 5, 2, 23);
+    a.jmp_imm(JEQ, 5, 1, "icmp_v4");
+    a.jmp_imm(JEQ, 5, 6, "port_v4");
+    a.jmp_imm(JEQ, 5, 17, "port_v4");
+    a.ja("pass");
+    a.label("port_v4");
+    a.ldx(LDX_H, 5, 2, 14 + 20 + 2);
+    a.ja("port");
+
+    // icmp: 回显应答, 以及 目的不可达, 超时, 参数问题
+    a.label("icmp_v4");
+    a.ldx(LDX_B, 5, 2, 14 + 20);
+    a.jmp_imm(JEQ, 5, 0, "redirect");
+    a.jmp_imm(JEQ, 5, 3, "quote_v4");
+    a.jmp_imm(JEQ, 5, 11, "quote_v4");
+    a.jmp_imm(JEQ, 5, 12, "quote_v4");
+    a.ja("pass");
+
+    // 差错报文 引用的 原始数据包: 首部长度为 20字节, 检查 源端口 或 回显请求
+    a.label("quote_v4");
+    a.bound(14 + 20 + 8 + 20 + 2);
+    a.ldx(LDX_B, 5, 2, 14 + 20 + 8);
+    a.jmp_imm(JNE, 5, 0x45, "pass");
+    a.ldx(LDX_B, 5, 2, 14 + 20 + 8 + 9);
+    a.jmp_imm(JEQ, 5, 6, "quote_port_v4");
+    a.jmp_imm(JEQ, 5, 17, "quote_port_v4");
+    a.jmp_imm(JNE, 5, 1, "pass");
+    a.ldx(LDX_B, 5, 2, 14 + 20 + 8 + 20);
+    a.jmp_imm(JEQ, 5, 8, "redirect");
+    a.ja("pass");
+    a.label("quote_port_v4");
+    a.ldx(LDX_H, 5, 2, 14 + 20 + 8 + 20);
+    a.ja("port");
+
+    // ipv6: 只处理 下一首部 为 上层协议 的数据包
+    a.label("v6");
+    a.bound(14 + 40 + 4);
+    a.ldx(LDX_B, 5, 2, 20);
+    a.jmp_imm(JEQ, 5, 58, "icmp_v6");
+    a.jmp_imm(JEQ, 5, 6, "port_v6");
+    a.jmp_imm(JEQ, 5, 17, "port_v6");
+    a.ja("pass");
+    a.label("port_v6");
+    a.ldx(LDX_H, 5, 2, 14 + 40 + 2);
+    a.ja("port");
+
+    // icmpv6: 回显应答, 以及 目的不可达, 超时, 参数问题
+    a.label("icmp_v6");
+    a.ldx(LDX_B, 5, 2, 14 + 40);
+    a.jmp_imm(JEQ, 5, 129, "redirect");
+    a.jmp_imm(JEQ, 5, 1, "quote_v6");
+    a.jmp_imm(JEQ, 5, 3, "quote_v6");
+    a.jmp_imm(JEQ, 5, 4, "quote_v6");
+    a.ja("pass");
+
+    // 差错报文 引用的 原始数据包: 下一首部 为 上层协议, 检查 源端口 或 回显请求
+    a.label("quote_v6");
+    a.bound(14 + 40 + 8 + 40 + 2);
+    a.ldx(LDX_B, 5, 2, 14 + 40 + 8 + 6);
+    a.jmp_imm(JEQ, 5, 6, "quote_port_v6");
+    a.jmp_imm(JEQ, 5, 17, "quote_port_v6");
+    a.jmp_imm(JNE, 5, 58, "pass");
+    a.ldx(LDX_B, 5, 2, 14 + 40 + 8 + 40);
+    a.jmp_imm(JEQ, 5, 128, "redirect");
+    a.ja("pass");
+    a.label("quote_port_v6");
+    a.ldx(LDX_H, 5, 2, 14 + 40 + 8 + 40);
+
+    // 目的端口 转为主机字节序 后 与 源端口范围 比较
+    a.label("port");
+    a.op(TO_BE, 5, 0, 0, 16);
+    a.jmp_imm(JLT, 5, source_ports.0 as i32, "pass");
+    a.jmp_imm(JGT, 5, source_ports.1 as i32, "pass");
+
+    // bpf_redirect_map(map, rx_queue_index, XDP_PASS): 该队列没有套接字时 交由内核处理
+    a.label("redirect");
+    a.ldx(LDX_W, 2, 1, XDP_MD_RX_QUEUE_INDEX);
+    a.op(LD_IMM64, 1, BPF_PSEUDO_MAP_FD, 0, map_fd);
+    a.op(0, 0, 0, 0, 0);
+    a.op(MOV_IMM, 3, 0, 0, XDP_PASS);
+    a.op(CALL, 0, 0, 0, BPF_FUNC_REDIRECT_MAP);
+    a.op(EXIT, 0, 0, 0, 0);
+
+    a.label("pass");
+    a.op(MOV_IMM, 0, 0, 0, XDP_PASS);
+    a.op(EXIT, 0, 0, 0, 0);
+
+    a.finish()
+}
+
+
+// 指令操作码
+const LDX_W:u8 = 0x61;
+const LDX_H:u8 = 0x69;
+const LDX_B:u8 = 0x71;
+const MOV_IMM:u8 = 0xb7;
+const MOV_REG:u8 = 0xbf;
+const ADD_IMM:u8 = 0x07;
+const LD_IMM64:u8 = 0x18;
+const TO_BE:u8 = 0xdc;
+const JA:u8 = 0x05;
+const JEQ:u8 = 0x15;
+const JNE:u8 = 0x55;
+const JGT:u8 = 0x25;
+const JLT:u8 = 0xa5;
+const JGT_REG:u8 = 0x2d;
+const CALL:u8 = 0x85;
+const EXIT:u8 = 0x95;
+
+
+/// 简单的 eBPF 汇编器
+#[derive(Default)]
+struct Asm {
+    insns:Vec<u64>,
+    labels:Vec<(&'static str, usize)>,
+    // 待回填的跳转指令: 指令下标, 目标标签
+    jumps:Vec<(usize, &'static str)>,
+}
+
+impl Asm {
+
+    fn op(&mut self, code:u8, dst:u8, src:u8, off:i16, imm:i32) {
+        self.insns.push((code as u64) | (((dst | (src << 4)) as u64) << 8) | ((off as u16 as u64) << 16) | ((imm as u32 as u64) << 32));
+    }
+
+    fn ldx(&mut self, code:u8, dst:u8, src:u8, off:i16) {
+        self.op(code, dst, src, off, 0);
+    }
+
+    /// 数据包长度 小于 len 时 跳转到 pass
+    fn bound(&mut self, len:i32) {
+        self.op(MOV_REG, 4, 2, 0, 0);
+        self.op(ADD_IMM, 4, 0, 0, len);
+        self.jumps.push((self.insns.len(), "pass"));
+        self.op(JGT_REG, 4, 3, 0, 0);
+    }
+
+    fn jmp_imm(&mut self, code:u8, dst:u8, imm:i32, label:&'static str) {
+        self.jumps.push((self.insns.len(), label));
+        self.op(code, dst, 0, 0, imm);
+    }
+
+    fn ja(&mut self, label:&'static str) {
+        self.jumps.push((self.insns.len(), label));
+        self.op(JA, 0, 0, 0, 0);
+    }
+
+    fn label(&mut self, name:&'static str) {
+        self.labels.push((name, self.insns.len()));
+    }
+
+    /// 回填 跳转偏移量: 目标指令下标 - (跳转指令下标 + 1)
+    fn finish(mut self) -> Vec<u64> {
+        for (index, label) in self.jumps.iter() {
+            let target = self.labels.iter().find(|(name, _)| name == label).map(|(_, i)| *i).unwrap();
+            let off = (target as i64 - *index as i64 - 1) as i16;
+            self.insns[*index] = (self.insns[*index] & !(0xffff << 16)) | ((off as u16 as u64) << 16);
+        }
+        self.insns
+    }
+}
@@ -0,0 +1,138 @@
+use std::io;
+use std::mem::size_of;
+use std::ptr;
+use std::sync::atomic::{AtomicU32, Ordering};
+use libc::{c_int, c_void};
+
+
+/// 内存映射区域, 释放时 解除映射
+pub struct Mmap {
+    pub addr:*mut u8,
+    len:usize,
+}
+
+impl Mmap {
+
+    /// 匿名映射, 用作 UMEM
+    pub fn anonymous(len:usize) -> io::Result<Self> {
+        Self::map(len, libc::MAP_PRIVATE | libc::MAP_ANONYMOUS | libc::MAP_POPULATE, -1, 0)
+    }
+
+    /// 映射 套接字的环形队列
+    fn socket(fd:c_int, len:usize, offset:libc::off_t) -> io::Result<Self> {
+        Self::map(len, libc::MAP_SHARED | libc::MAP_POPULATE, fd, offset)
+    }
+
+    fn map(len:usize, flags:c_int, fd:c_int, offset:libc::off_t) -> io::Result<Self> {
+        let addr = unsafe {
+            libc::mmap(ptr::null_mut(), len, libc::PROT_READ | libc::PROT_WRITE, flags, fd, offset)
+        };
+        if addr == libc::MAP_FAILED {
+            return Err(io::Error::last_os_error())
+        }
+        Ok(Self { addr: addr as *mut u8, len })
+    }
+}
+
+impl Drop for Mmap {
+    fn drop(&mut self) {
+        unsafe { libc::munmap(self.addr as *mut c_void, self.len); }
+    }
+}
+
+
+/// 单生产者 单消费者 环形队列, 由 内核 和 本线程 分别作为 生产者 或 消费者
+/// T 为 队列元素: 填充队列 和 完成队列 为 UMEM 帧地址(u64), 接收队列 和 发送队列 为 描述符(xdp_desc)
+pub struct Ring<T> {
+    _map:Mmap,
+    producer:*const AtomicU32,
+    consumer:*const AtomicU32,
+    flags:*const AtomicU32,
+    desc:*mut T,
+
+    size:u32,
+    mask:u32,
+
+    // 本地缓存的 生产者 和 消费者 位置, 只在需要时 读取共享位置
+    cached_prod:u32,
+    cached_cons:u32,
+}
+
+// 环形队列 只由持有它的线程访问, 共享位置 通过原子操作 与内核同步
+unsafe impl<T> Send for Ring<T> {}
+
+impl<T:Copy> Ring<T> {
+
+    /// 映射 环形队列, offset 为 套接字选项 XDP_MMAP_OFFSETS 返回的该队列偏移量, pgoff 为 映射的页偏移
+    pub fn map(fd:c_int, size:u32, offset:&libc::xdp_ring_offset, pgoff:libc::off_t) -> io::Result<Self> {
+
+        let len = offset.desc as usize + size as usize * size_of::<T>();
+        let map = Mmap::socket(fd, len, pgoff)?;
+
+        let base = map.addr;
+        let ring = unsafe {
+            Self {
+                producer: base.add(offset.producer as usize) as *const AtomicU32,
+                consumer: base.add(offset.consumer as usize) as *const AtomicU32,
+                flags: base.add(offset.flags as usize) as *const AtomicU32,
+                desc: base.add(offset.desc as usize) as *mut T,
+                size,
+                mask: size - 1,
+                cached_prod: 0,
+                cached_cons: 0,
+                _map: map,
+            }
+        };
+        Ok(ring)
+    }
+
+    /// 是否需要 通过系统调用 唤醒内核处理该队列
+    #[inline]
+    pub fn needs_wakeup(&self) -> bool {
+        unsafe { (*self.flags).load(Ordering::Relaxed) & libc::XDP_RING_NEED_WAKEUP != 0 }
+    }
+
+    /// 作为生产者: 可写入的元素数量
+    #[inline]
+    pub fn free(&mut self) -> u32 {
+        let free = self.size - self.cached_prod.wrapping_sub(self.cached_cons);
+        if free > 0 { return free }
+        self.cached_cons = unsafe { (*self.consumer).load(Ordering::Acquire) };
+        self.size - self.cached_prod.wrapping_sub(self.cached_cons)
+    }
+
+    /// 作为生产者: 写入第 index 个待提交元素
+    #[inline]
+    pub fn write(&mut self, index:u32, item:T) {
+        unsafe { *self.desc.add((self.cached_prod.wrapping_add(index) & self.mask) as usize) = item; }
+    }
+
+    /// 作为生产者: 提交 n 个已写入的元素
+    #[inline]
+    pub fn submit(&mut self, n:u32) {
+        self.cached_prod = self.cached_prod.wrapping_add(n);
+        unsafe { (*self.producer).store(self.cached_prod, Ordering::Release); }
+    }
+
+    /// 作为消费者: 可读取的元素数量
+    #[inline]
+    pub fn available(&mut self) -> u32 {
+        let available = self.cached_prod.wrapping_sub(self.cached_cons);
+        if available > 0 { return available }
+        self.cached_prod = unsafe { (*self.producer).load(Ordering::Acquire) };
+        self.cached_prod.wrapping_sub(self.cached_cons)
+    }
+
+    /// 作为消费者: 读取第 index 个待释放元素
+    #[inline]
+    pub fn read(&self, index:u32) -> T {
+        unsafe { *self.desc.add((self.cached_cons.wrapping_add(index) & self.mask) as usize) }
+    }
+
+    /// 作为消费者: 释放 n 个已读取的元素
+    #[inline]
+    pub fn release(&mut self, n:u32) {
+        self.cached_cons = self.cached_cons.wrapping_add(n);
+        unsafe { (*self.consumer).store(self.cached_cons, Ordering::Release); }
+    }
+}
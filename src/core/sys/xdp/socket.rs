@@ -0,0 +1,346 @@
+use std::io;
+use std::mem::{size_of, zeroed};
+use std::ptr;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+use libc::{c_int, c_void, socklen_t, xdp_desc};
+use pcap::PacketHeader;
+use crate::core::sys::packet_sender::PacketBuf;
+use crate::core::sys::xdp::ring::{Mmap, Ring};
+
+
+/// 发送方向的状态: 发送队列, 完成队列, 以及 空闲的发送帧
+struct TxState {
+    tx:Ring<xdp_desc>,
+    comp:Ring<u64>,
+    free_frames:Vec<u64>,
+}
+
+/// 接收方向的状态: 接收队列 和 填充队列
+struct RxState {
+    rx:Ring<xdp_desc>,
+    fill:Ring<u64>,
+}
+
+
+/// 绑定到 网络接口某一队列 的 AF_XDP 套接字
+/// UMEM 的前一半帧 用于接收, 后一半帧 用于发送. 发送 和 接收 使用互不相交的环形队列,
+/// 因此 同一队列上的 发送线程 和 接收线程 可以共用一个套接字, 二者各自独占一个方向
+pub struct XskPort {
+    fd:c_int,
+    pub ifindex:u32,
+    pub queue:u32,
+
+    umem:Mmap,
+    frame_size:u32,
+    // 发送帧数量
+    tx_frame_num:usize,
+
+    // 是否以 need_wakeup 模式绑定, 否则 每次提交后 都需要通过系统调用 通知内核
+    need_wakeup:bool,
+    // 是否以 零拷贝 模式绑定
+    pub zero_copy:bool,
+
+    tx:Mutex<TxState>,
+    rx:Mutex<RxState>,
+
+    // 发送方向 和 接收方向 是否已被占用
+    tx_taken:AtomicBool,
+    rx_taken:AtomicBool,
+}
+
+// 套接字 和 UMEM 的访问 均通过 各方向的互斥锁 进行
+unsafe impl Send for XskPort {}
+unsafe impl Sync for XskPort {}
+
+impl XskPort {
+
+    /// 创建 AF_XDP 套接字 并绑定到 网络接口的指定队列
+    /// 依次尝试 零拷贝模式 和 复制模式, 驱动不支持 XDP 时 复制模式 由内核的通用路径实现
+    pub fn open(ifindex:u32, queue:u32, ring_size:u32, frame_size:u32) -> io::Result<Self> {
+
+        let fd = unsafe { libc::socket(libc::AF_XDP, libc::SOCK_RAW | libc::SOCK_CLOEXEC, 0) };
+        if fd < 0 {
+            return Err(io::Error::last_os_error())
+        }
+
+        match Self::setup(fd, ifindex, queue, ring_size, frame_size) {
+            Ok(port) => Ok(port),
+            Err(e) => {
+                unsafe { libc::close(fd); }
+                Err(e)
+            }
+        }
+    }
+
+    fn setup(fd:c_int, ifindex:u32, queue:u32, ring_size:u32, frame_size:u32) -> io::Result<Self> {
+
+        // 注册 UMEM: 接收帧 和 发送帧 各 ring_size 个
+        let frame_num = ring_size as usize * 2;
+        let umem = Mmap::anonymous(frame_num * frame_size as usize)?;
+        let mut reg:libc::xdp_umem_reg = unsafe { zeroed() };
+        reg.addr = umem.addr as u64;
+        reg.len = (frame_num * frame_size as usize) as u64;
+        reg.chunk_size = frame_size;
+        set_opt(fd, libc::XDP_UMEM_REG, &reg)?;
+
+        for opt in [libc::XDP_UMEM_FILL_RING, libc::XDP_UMEM_COMPLETION_RING, libc::XDP_RX_RING, libc::XDP_TX_RING] {
+            set_opt(fd, opt, &ring_size)?;
+        }
+
+        // 映射 四个环形队列
+        let mut off:libc::xdp_mmap_offsets = unsafe { zeroed() };
+        let mut len = size_of::<libc::xdp_mmap_offsets>() as socklen_t;
+        if unsafe { libc::getsockopt(fd, libc::SOL_XDP, libc::XDP_MMAP_OFFSETS, &mut off as *mut _ as *mut c_void, &mut len) } < 0 {
+            return Err(io::Error::last_os_error())
+        }
+        let mut fill = Ring::<u64>::map(fd, ring_size, &off.fr, libc::XDP_UMEM_PGOFF_FILL_RING as libc::off_t)?;
+        let comp = Ring::<u64>::map(fd, ring_size, &off.cr, libc::XDP_UMEM_PGOFF_COMPLETION_RING as libc::off_t)?;
+        let rx = Ring::<xdp_desc>::map(fd, ring_size, &off.rx, libc::XDP_PGOFF_RX_RING)?;
+        let tx = Ring::<xdp_desc>::map(fd, ring_size, &off.tx, libc::XDP_PGOFF_TX_RING)?;
+
+        // 填充队列 放入全部接收帧, 发送帧 全部空闲
+        for i in 0..ring_size {
+            fill.write(i, i as u64 * frame_size as u64);
+        }
+        fill.submit(ring_size);
+        let free_frames = (ring_size..ring_size * 2).map(|i| i as u64 * frame_size as u64).collect();
+
+        // 绑定: 零拷贝 -> 复制模式 -> 不使用 need_wakeup(较早的内核)
+        let mut bound = None;
+        for (flags, need_wakeup, zero_copy) in [
+            (libc::XDP_ZEROCOPY | libc::XDP_USE_NEED_WAKEUP, true, true),
+            (libc::XDP_COPY | libc::XDP_USE_NEED_WAKEUP, true, false),
+            (libc::XDP_COPY, false, false),
+        ] {
+            let mut addr:libc::sockaddr_xdp = unsafe { zeroed() };
+            addr.sxdp_family = libc::AF_XDP as u16;
+            addr.sxdp_flags = flags;
+            addr.sxdp_ifindex = ifindex;
+            addr.sxdp_queue_id = queue;
+
+            let res = unsafe {
+                libc::bind(fd, &addr as *const _ as *const libc::sockaddr, size_of::<libc::sockaddr_xdp>() as socklen_t)
+            };
+            if res == 0 {
+                bound = Some((need_wakeup, zero_copy));
+                break
+            }
+        }
+        let (need_wakeup, zero_copy) = bound.ok_or_else(io::Error::last_os_error)?;
+
+        Ok(Self {
+            fd, ifindex, queue, umem, frame_size, tx_frame_num: ring_size as usize, need_wakeup, zero_copy,
+            tx: Mutex::new(TxState { tx, comp, free_frames }),
+            rx: Mutex::new(RxState { rx, fill }),
+            tx_taken: AtomicBool::new(false),
+            rx_taken: AtomicBool::new(false),
+        })
+    }
+
+    pub fn fd(&self) -> c_int {
+        self.fd
+    }
+
+    /// 占用 发送方向, 已被其他线程占用时 返回 false
+    pub fn take_tx(&self) -> bool {
+        self.tx_taken.compare_exchange(false, true, Ordering::AcqRel, Ordering::Acquire).is_ok()
+    }
+
+    pub fn release_tx(&self) {
+        self.tx_taken.store(false, Ordering::Release);
+    }
+
+    /// 占用 接收方向, 已被其他线程占用时 返回 false
+    pub fn take_rx(&self) -> bool {
+        self.rx_taken.compare_exchange(false, true, Ordering::AcqRel, Ordering::Acquire).is_ok()
+    }
+
+    pub fn release_rx(&self) {
+        self.rx_taken.store(false, Ordering::Release);
+    }
+
+    pub fn frame_size(&self) -> usize {
+        self.frame_size as usize
+    }
+
+    pub fn tx_frame_num(&self) -> usize {
+        self.tx_frame_num
+    }
+
+    /// 取出一个空闲的发送帧 作为数据包缓冲区, 没有空闲的发送帧时 通知内核发送 并等待已完成的发送帧, 仍没有时 返回 None
+    pub fn alloc_frame(&self) -> Option<PacketBuf> {
+
+        let mut state = self.tx.lock().unwrap();
+        let state = &mut *state;
+        if state.free_frames.is_empty() {
+            self.reclaim(state);
+        }
+        if state.free_frames.is_empty() {
+            self.kick();
+            self.wait(libc::POLLOUT);
+            self.reclaim(state);
+        }
+
+        let addr = state.free_frames.pop()?;
+        // UMEM 与 套接字 同时释放, 发送帧 在 提交 或 归还 之前 只由 持有该缓冲区的发送线程 写入
+        Some(unsafe { PacketBuf::frame(addr, self.umem.addr.add(addr as usize), self.frame_size as usize) })
+    }
+
+    /// 归还 未提交的发送帧
+    pub fn free_frame(&self, buf:&mut PacketBuf) {
+        if let Some((addr, _)) = buf.take_frame() {
+            self.tx.lock().unwrap().free_frames.push(addr);
+        }
+    }
+
+    /// 提交多个数据包到发送队列, 返回 从首个数据包起 连续提交成功的数量
+    /// 已写入发送帧的数据包 直接提交该帧, 其余数据包 复制到空闲的发送帧. 首个数据包 无法提交时 返回 -1
+    pub fn submit(&self, packets:&mut [PacketBuf]) -> isize {
+
+        let mut state = self.tx.lock().unwrap();
+        let state = &mut *state;
+        self.reclaim(state);
+
+        // 发送队列 已满时, 通知内核发送 并等待 已完成的发送帧
+        if state.tx.free() == 0 {
+            self.kick();
+            self.wait(libc::POLLOUT);
+            self.reclaim(state);
+        }
+
+        let n = packets.len().min(state.tx.free() as usize);
+        let mut submitted = 0;
+        for packet in packets[..n].iter_mut() {
+            let desc = match packet.take_frame() {
+                Some((addr, len)) => xdp_desc { addr, len: len as u32, options: 0 },
+                None => match self.copy_to_frame(state, packet) {
+                    Some(desc) => desc,
+                    None => break,
+                },
+            };
+            state.tx.write(submitted, desc);
+            submitted += 1;
+        }
+        if submitted == 0 { return -1 }
+
+        state.tx.submit(submitted);
+        if !self.need_wakeup || state.tx.needs_wakeup() {
+            self.kick();
+        }
+        submitted as isize
+    }
+
+    /// 将单个数据包 复制到空闲的发送帧 并提交, 成功时 返回 1, 否则 返回 -1
+    pub fn send(&self, packet:&[u8]) -> isize {
+
+        let mut state = self.tx.lock().unwrap();
+        let state = &mut *state;
+        self.reclaim(state);
+
+        if state.free_frames.is_empty() || state.tx.free() == 0 {
+            self.kick();
+            self.wait(libc::POLLOUT);
+            self.reclaim(state);
+        }
+        if state.tx.free() == 0 { return -1 }
+
+        let Some(desc) = self.copy_to_frame(state, packet) else { return -1 };
+        state.tx.write(0, desc);
+        state.tx.submit(1);
+        if !self.need_wakeup || state.tx.needs_wakeup() {
+            self.kick();
+        }
+        1
+    }
+
+    /// 将数据包 复制到空闲的发送帧, 没有空闲的发送帧 或 数据包超过帧大小 时 返回 None
+    fn copy_to_frame(&self, state:&mut TxState, packet:&[u8]) -> Option<xdp_desc> {
+        if packet.len() > self.frame_size as usize { return None }
+
+        let addr = state.free_frames.pop()?;
+        unsafe {
+            ptr::copy_nonoverlapping(packet.as_ptr(), self.umem.addr.add(addr as usize), packet.len());
+        }
+        Some(xdp_desc { addr, len: packet.len() as u32, options: 0 })
+    }
+
+    /// 回收 已发送完成的发送帧
+    fn reclaim(&self, state:&mut TxState) {
+        let n = state.comp.available();
+        for i in 0..n {
+            state.free_frames.push(state.comp.read(i));
+        }
+        state.comp.release(n);
+    }
+
+    /// 通知内核处理发送队列, 队列忙 或 缓冲区不足 时 由下一次提交重试
+    fn kick(&self) {
+        unsafe { libc::sendto(self.fd, ptr::null(), 0, libc::MSG_DONTWAIT, ptr::null(), 0); }
+    }
+
+    /// 等待 套接字 可读 或 可写, 最多 1 毫秒
+    fn wait(&self, events:libc::c_short) {
+        let mut pfd = libc::pollfd { fd: self.fd, events, revents: 0 };
+        unsafe { libc::poll(&mut pfd, 1, 1); }
+    }
+
+    /// 读取 接收队列中的一个数据包, 将 UMEM 中的数据 直接交给 f 处理, 处理完成后 将接收帧 归还填充队列
+    pub fn recv<R>(&self, f:impl FnOnce(&PacketHeader, &[u8]) -> R) -> Option<R> {
+
+        let mut state = self.rx.lock().unwrap();
+        if state.rx.available() == 0 {
+            return None
+        }
+
+        let desc = state.rx.read(0);
+        let len = desc.len as usize;
+
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+        let header = PacketHeader {
+            ts: libc::timeval { tv_sec: now.as_secs() as libc::time_t, tv_usec: now.subsec_micros() as libc::suseconds_t },
+            caplen: len as u32,
+            len: len as u32,
+        };
+
+        // 接收帧 在归还填充队列之前 不会被内核改写
+        let data = unsafe { std::slice::from_raw_parts(self.umem.addr.add(desc.addr as usize), len) };
+        let res = f(&header, data);
+        state.rx.release(1);
+
+        // 对齐模式下 描述符地址 可能包含帧内偏移, 归还时 使用帧起始地址
+        if state.fill.free() > 0 {
+            state.fill.write(0, desc.addr - desc.addr % self.frame_size as u64);
+            state.fill.submit(1);
+        }
+        Some(res)
+    }
+
+    /// 内核统计: 因 接收队列已满 或 填充队列为空 等原因 丢弃的数据包数量
+    pub fn dropped(&self) -> u64 {
+        let mut stats:libc::xdp_statistics = unsafe { zeroed() };
+        let mut len = size_of::<libc::xdp_statistics>() as socklen_t;
+        let res = unsafe {
+            libc::getsockopt(self.fd, libc::SOL_XDP, libc::XDP_STATISTICS, &mut stats as *mut _ as *mut c_void, &mut len)
+        };
+        if res < 0 { return 0 }
+        // 较早的内核 只返回前三项
+        let full = len as usize >= size_of::<libc::xdp_statistics>();
+        stats.rx_dropped + if full { stats.rx_ring_full + stats.rx_fill_ring_empty_descs } else { 0 }
+    }
+}
+
+impl Drop for XskPort {
+    fn drop(&mut self) {
+        unsafe { libc::close(self.fd); }
+    }
+}
+
+
+fn set_opt<T>(fd:c_int, opt:c_int, val:&T) -> io::Result<()> {
+    let res = unsafe {
+        libc::setsockopt(fd, libc::SOL_XDP, opt, val as *const T as *const c_void, size_of::<T>() as socklen_t)
+    };
+    if res < 0 { Err(io::Error::last_os_error()) } else { Ok(()) }
+}
@@ -0,0 +1,22 @@
+//! SMap 库
+//! 命令行程序 只是对本库的一层包装, 外部系统可通过 api 模块 构造扫描任务, 获取统计信息 和 探测结果。
+
+pub mod core;
+pub mod modes;
+pub mod modules;
+pub mod tools;
+pub mod api;
+
+use once_cell::sync::Lazy;
+use crate::core::conf::sys_config::SysConf;
+use crate::tools::net_handle::dns::dns_resolver::DNSResolver;
+
+// 在编译时获取 系统配置 和 提示信息, 并叠加运行时配置
+pub static SYS: Lazy<SysConf> = Lazy::new(|| {
+    SysConf::new()
+});
+
+// 全局 DNS 解析器
+pub static DNS: Lazy<DNSResolver> = Lazy::new(|| {
+   DNSResolver::new()
+});
@@ -1,42 +1,25 @@
-
-
-mod core;
-mod modes;
-mod modules;
-mod tools;
-
-use once_cell::sync::Lazy;
-use crate::core::conf::args::Args;
-use crate::core::conf::sys_config::SysConf;
-use crate::core::sys::logger::set_logger;
-use crate::modes::{Mode};
-use crate::tools::net_handle::dns::dns_resolver::DNSResolver;
-
-// 在编译时获取 系统配置 和 提示信息
-pub static SYS: Lazy<SysConf> = Lazy::new(|| {
-    SysConf::new()
-});
-
-// 全局 DNS 解析器
-pub static DNS: Lazy<DNSResolver> = Lazy::new(|| {
-   DNSResolver::new()
-});
+use std::process::exit;
+use log::error;
+use smap::api::Scan;
 
 fn main() {
-    
-    // 定义模式
-    let mode;
-    {
-        // 获取命令行参数
-        let args = Args::get_args();
-
-        // 配置 系统日志
-        set_logger(&args);
 
-        // 选择并创建模式
-        mode = Mode::new(&args);
+    // 从命令行参数 创建扫描任务, 配置系统日志 并执行
+    // 此时日志尚未配置, 错误直接输出到 标准错误
+    let scan = Scan::from_cli().unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        exit(1)
+    });
+    scan.init_logger();
+
+    // 没有设置模式 或 模式不存在时, 打印帮助信息
+    if !scan.has_mode() {
+        scan.print_help();
+        exit(0)
     }
 
-    // 执行模式
-    mode.execute();
+    if let Err(e) = scan.execute() {
+        error!("{}", e);
+        exit(1)
+    }
 }
@@ -1,130 +1,49 @@
-use crate::modes;
-use crate::modes::Helper;
-use crate::modules::{output_modules, probe_modules};
-
-///  mode helper
-pub fn mode_help(mode_name:&str) -> String {
-
-    match mode_name {
-
-
-        "cycle_v4" | "c4"  => modes::v4::CycleV4::print_help(),
-
-        "cycle_v6" | "c6" => modes::v6::CycleV6::print_help(),
-
-        "cycle_v6_pattern" | "c6p" => modes::v6::CycleV6Pattern::print_help(),
-
-        "file_v4" | "f4" => modes::v4::V4FileReader::print_help(),
-
-        "file_v6" | "f6" => modes::v6::V6FileReader::print_help(),
-
-        "cycle_v4_v6" | "c46" => modes::mix::CycleV4V6::print_help(),
-
-        "pmap_v4" | "p4" => modes::v4::PmapV4::print_help(),
-
-        "pmap_v6" | "p6" => modes::v6::PmapV6::print_help(),
-
-        "pmap_file_v6" | "pf6" => modes::v6::PmapFileV6::print_help(),
+use crate::core::registry::Registry;
+use crate::modes::MODES;
+use crate::modules::output_modules::OUTPUT_MODS;
+use crate::modules::probe_modules::active_probe_ipv6_code::CODE_PROBE_MODS_V6;
+use crate::modules::probe_modules::probe_mod_v4::PROBE_MODS_V4;
+use crate::modules::probe_modules::probe_mod_v6::PROBE_MODS_V6;
+use crate::modules::probe_modules::topo_mod_v6::CODE_TOPO_PROBE_MODS_V6;
+use crate::modules::probe_modules::topology_probe::topo_mod_v4::TOPO_MODS_V4;
+use crate::modules::probe_modules::topology_probe::topo_mod_v6::TOPO_MODS_V6;
+
+/// ipv4探测模块 的所有注册表
+pub fn probe_v4_registries() -> [&'static dyn Registry; 2] {
+    [&PROBE_MODS_V4, &TOPO_MODS_V4]
+}
 
-        "topo_v4" | "t4" => modes::v4::Topo4::print_help(),
-        
-        "topo_v6" | "t6" => modes::v6::Topo6::print_help(),
+/// ipv6探测模块 的所有注册表
+pub fn probe_v6_registries() -> [&'static dyn Registry; 4] {
+    [&PROBE_MODS_V6, &TOPO_MODS_V6, &CODE_PROBE_MODS_V6, &CODE_TOPO_PROBE_MODS_V6]
+}
 
-        "ipv6_addrs_gen" => modes::v6::SpaceTree6::print_help(),
-        
-        "ipv6_prefix_tree" => modes::v6::PrefixTree6::print_help(),
-        
-        "ipv6_prefix_fixed_tree" => modes::v6::PrefixFixedTree6::print_help(),
-        
-        "ipv6_aliased_prefixes_check" | "ac6" => modes::v6::IPv6AliasedCheck::print_help(),
-        
-        "asset6" | "a6" => modes::v6::Asset6::print_help(),
-        
-        "scour6" | "s6" => modes::v6::Scour6::print_help(),
-        
-        "e6" => modes::v6::Edge6::print_help(),
+/// 依次在各注册表中查找帮助信息
+fn find_help(registries:&[&dyn Registry], name:&str, no_help:&str) -> String {
 
-        _ => {
-            "no mode help".to_string()
-        }
-    }
+    registries.iter()
+        .find_map(|r| r.help_text(name, no_help))
+        .unwrap_or_else(|| no_help.to_string())
+}
 
+///  mode helper
+pub fn mode_help(mode_name:&str) -> String {
+    find_help(&[&MODES], mode_name, "no mode help")
 }
 
 
 /// probeV4 helper
 pub fn probe_v4_help(name:&str) -> String {
-
-    match name {
-
-
-        "icmp_v4"  => probe_modules::probe_mod_v4::IcmpEchoV4::print_help(),
-
-        "tcp_syn_scan_v4" => probe_modules::probe_mod_v4::TcpSynScanV4::print_help(),
-        "tcp_syn_ack_scan_v4" => probe_modules::probe_mod_v4::TcpSynAckScanV4::print_help(),
-        "tcp_syn_opt_v4" => probe_modules::probe_mod_v4::TcpSynOptV4::print_help(),
-
-        "udp_scan_v4" => probe_modules::probe_mod_v4::UdpScanV4::print_help(),
-
-
-        "topo_udp_v4" => probe_modules::topology_probe::topo_mod_v4::TopoUdpV4::print_help(),
-        "topo_icmp_v4" => probe_modules::topology_probe::topo_mod_v4::TopoIcmpV4::print_help(),
-        "topo_tcp_v4" => probe_modules::topology_probe::topo_mod_v4::TopoTcpV4::print_help(),
-
-        _ => {
-            "no help".to_string()
-        }
-    }
-
+    find_help(&probe_v4_registries(), name, "no help")
 }
 
 /// probeV6 helper
 pub fn probe_v6_help(name:&str) -> String {
-
-    match name {
-
-        "icmp_v6"  => probe_modules::probe_mod_v6::IcmpEchoV6::print_help(),
-
-        "tcp_syn_scan_v6" => probe_modules::probe_mod_v6::TcpSynScanV6::print_help(),
-        "tcp_syn_ack_scan_v6" => probe_modules::probe_mod_v6::TcpSynAckScanV6::print_help(),
-        "tcp_syn_opt_v6" => probe_modules::probe_mod_v6::TcpSynOptV6::print_help(),
-
-        "udp_scan_v6" => probe_modules::probe_mod_v6::UdpScanV6::print_help(),
-
-        "topo_udp_v6" => probe_modules::topology_probe::topo_mod_v6::TopoUdpV6::print_help(),
-        "topo_icmp_v6" => probe_modules::topology_probe::topo_mod_v6::TopoIcmpV6::print_help(),
-        "topo_tcp_v6" => probe_modules::topology_probe::topo_mod_v6::TopoTcpV6::print_help(),
-        
-        "code_icmp_v6" => probe_modules::active_probe_ipv6_code::CodeIcmpEchoV6::print_help(),
-        "code_tcp_syn_scan_v6" => probe_modules::active_probe_ipv6_code::CodeTcpSynScanV6::print_help(),
-        
-        "tcp_syn_payload_scan_v6" => probe_modules::probe_mod_v6::TcpSynPayloadScan::print_help(),
-
-        _ => {
-            "no help".to_string()
-        }
-    }
-
+    find_help(&probe_v6_registries(), name, "no help")
 }
 
 
 /// output helper
 pub fn output_help(name:&str) -> String {
-
-    match name {
-
-
-        "csv"  => output_modules::Csv::print_help(),
-
-        _ => {
-            "no help".to_string()
-        }
-    }
-
+    find_help(&[&OUTPUT_MODS], name, "no help")
 }
-
-
-
-
-
-
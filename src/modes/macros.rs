@@ -36,17 +36,17 @@ macro_rules! creat_channels {
 macro_rules! ending_the_receiving_thread {
     ($self:ident; $msg_sender:ident) => (
         let end_time = chrono::Utc::now().timestamp() + $self.sender_conf.cool_seconds;
-        // 向接收线程发送终止时间失败
-        if let Err(_) = $msg_sender.send(end_time){ error!("{}", crate::SYS.get_info("err","send_recv_close_time_failed")); std::process::exit(1) }
+        // 发送失败说明接收线程已提前退出, 其错误将在等待接收线程时返回
+        let _ = $msg_sender.send(end_time);
     )
 }
 
 #[macro_export]
 macro_rules! recv_ready {
-    ($receiver:ident) => (
+    ($receiver:ident; $receiver_res:ident) => (
         if let Err(_) = $receiver.recv() {
-            error!("{}", crate::SYS.get_info("err", "recv_ready_receive_failed"));
-            std::process::exit(1)
+            // 接收线程未能准备完毕, 发送线程不再执行
+            return Err($crate::core::receiver::recv_ready_failed($receiver_res))
         }
     )
 }
@@ -65,10 +65,15 @@ macro_rules! init_var {
 macro_rules! wait_sender_threads {
     ($sender_threads:ident; $($field:ident),*; $block:block) => (
         for sender_thread in $sender_threads {
-            let sender_res = sender_thread.join();
-
-            if let Ok(($($field,)*)) = sender_res  $block
-            else { error!("{}", crate::SYS.get_info("err", "send_thread_err")); std::process::exit(1) }
+            match sender_thread.join() {
+                Ok(Ok(($($field,)*))) => $block,
+                // 发送线程出错时, 其余发送线程 在当前批次结束后停止发送
+                Ok(Err(e)) => {
+                    $crate::core::sender::request_abort();
+                    return Err(e)
+                }
+                Err(_) => return Err($crate::core::errors::RunError::thread("send_thread_err").into()),
+            }
         }
     );
 }
@@ -77,12 +82,12 @@ macro_rules! wait_sender_threads {
 macro_rules! computing_time {
     ($start_time:ident; $end_time:ident, $running_time:ident) => (
         let $end_time = chrono::Local::now();
-        let $running_time = crate::tools::others::time::get_fmt_duration(($end_time - $start_time).num_seconds(), crate::SYS.get_info("print", "running_time_pattern"));
-        println!("{} {}", crate::SYS.get_info("print", "show_running_time"), $running_time);
+        let $running_time = $crate::tools::others::time::get_fmt_duration(($end_time - $start_time).num_seconds(), $crate::SYS.get_info("print", "running_time_pattern"));
+        eprintln!("{} {}", $crate::SYS.get_info("print", "show_running_time"), $running_time);
     );
     ($start_time:ident, $end_time:ident; $running_time:ident) => (
-        let $running_time = crate::tools::others::time::get_fmt_duration(($end_time - $start_time).num_seconds(), crate::SYS.get_info("print", "running_time_pattern"));
-        println!("{} {}", crate::SYS.get_info("print", "show_running_time"), $running_time);
+        let $running_time = $crate::tools::others::time::get_fmt_duration(($end_time - $start_time).num_seconds(), $crate::SYS.get_info("print", "running_time_pattern"));
+        eprintln!("{} {}", $crate::SYS.get_info("print", "show_running_time"), $running_time);
     );
 }
 
@@ -92,36 +97,52 @@ macro_rules! write_to_summary {
     // 输出结果
 
     ($self:ident; $mode:expr; $target:expr; [$($field:ident),*; $(($custom_field_str:expr, $custom_field:ident)),*]) => (
-        if let Some(summary_path) = &$self.base_conf.summary_file {
+        {
             let header = vec![$(stringify!($field),)*  $($custom_field_str,)* ];
             let val = vec![$($field.to_string(),)*  $($custom_field.to_string(),)* ];
-            crate::tools::file::write_to_file::write_record($mode, $target, summary_path, header, val);
+            // 统计信息 同时交给 库接口
+            $crate::api::stats::record($mode, $target, &header, &val);
+            if let Some(summary_path) = &$self.base_conf.summary_file {
+                $crate::tools::file::write_to_file::write_record($mode, $target, summary_path, header, val)?;
+            }
         }
     );
 
     ($self:ident; $mode:expr; $target:expr; #[$clas:ident; $($field:ident),*; $(($custom_field_str:expr, $custom_field:ident)),*]) => (
-        if let Some(summary_path) = &$self.base_conf.summary_file {
+        {
             let header = vec![$(stringify!($field),)*  $($custom_field_str,)* ];
             let val = vec![$($clas.$field.to_string(),)*  $($clas.$custom_field.to_string(),)* ];
-            crate::tools::file::write_to_file::write_record($mode, $target, summary_path, header, val);
+            // 统计信息 同时交给 库接口
+            $crate::api::stats::record($mode, $target, &header, &val);
+            if let Some(summary_path) = &$self.base_conf.summary_file {
+                $crate::tools::file::write_to_file::write_record($mode, $target, summary_path, header, val)?;
+            }
         }
     );
 
 
     ($self:ident; $mode:expr; $target:expr; [$($field:ident),*; $(($custom_field_str:expr, $custom_field:ident)),*]; #[$clas:ident; $($clas_field:ident),*; $(($clas_custom_field_str:expr, $clas_custom_field:ident)),*]) => (
-        if let Some(summary_path) = &$self.base_conf.summary_file {
+        {
             let header = vec![$(stringify!($field),)*  $($custom_field_str,)*  $(stringify!($clas_field),)*  $($clas_custom_field_str,)*  ];
             let val = vec![ $($field.to_string(),)*  $($custom_field.to_string(),)*  $($clas.$clas_field.to_string(),)*  $($clas.$clas_custom_field.to_string(),)* ];
-            crate::tools::file::write_to_file::write_record($mode, $target, summary_path, header, val);
+            // 统计信息 同时交给 库接口
+            $crate::api::stats::record($mode, $target, &header, &val);
+            if let Some(summary_path) = &$self.base_conf.summary_file {
+                $crate::tools::file::write_to_file::write_record($mode, $target, summary_path, header, val)?;
+            }
         }
     );
 
 
     ($self:ident; $mode:expr; $target:expr; [$($field:ident),*; $(($custom_field_str:expr, $custom_field:ident)),*]; #[$clas:ident; $($clas_field:ident),*; $(($clas_custom_field_str:expr, $clas_custom_field:ident)),*]; #[$clas2:ident; $($clas2_field:ident),*; $(($clas2_custom_field_str:expr, $clas2_custom_field:ident)),*]) => (
-        if let Some(summary_path) = &$self.base_conf.summary_file {
+        {
             let header = vec![$(stringify!($field),)*  $($custom_field_str,)*  $(stringify!($clas_field),)*  $($clas_custom_field_str,)*   $(stringify!($clas2_field),)*  $($clas2_custom_field_str,)* ];
             let val = vec![ $($field.to_string(),)*  $($custom_field.to_string(),)*  $($clas.$clas_field.to_string(),)*  $($clas.$clas_custom_field.to_string(),)*  $($clas2.$clas2_field.to_string(),)*  $($clas2.$clas2_custom_field.to_string(),)*  ];
-            crate::tools::file::write_to_file::write_record($mode, $target, summary_path, header, val);
+            // 统计信息 同时交给 库接口
+            $crate::api::stats::record($mode, $target, &header, &val);
+            if let Some(summary_path) = &$self.base_conf.summary_file {
+                $crate::tools::file::write_to_file::write_record($mode, $target, summary_path, header, val)?;
+            }
         }
     );
 
@@ -134,18 +155,24 @@ macro_rules! write_to_summary {
             let header = vec![ $($field_str,)* ];
             let val = vec![ $($field_val.to_string(),)* ];
 
-            crate::tools::file::write_to_file::write_record($mode, $target, summary_path, header, val);
+            $crate::tools::file::write_to_file::write_record($mode, $target, summary_path, header, val)?;
         }
     );
 
 
-    ($base:ident; $mode:expr; $target:expr; $args:ident; $(($field_str:expr, $field_val:ident)),* ) => (
+    ($base:ident; $mode:expr; $target:expr; $args:ident; $mod_args:ident; $(($field_str:expr, $field_val:ident)),* ) => (
         if let Some(summary_path) = &$base.summary_file {
             // 将 所有输入参数 写入记录文件
             let header = vec![ "time", "args",  $($field_str,)* ];
             let val = vec![ chrono::Local::now().to_string(), format!("{:?}", $args).replace(",", " "), $($field_val.to_string(),)* ];
 
-            crate::tools::file::write_to_file::write_record($mode, $target, summary_path, header, val);
+            $crate::tools::file::write_to_file::write_record($mode, $target, summary_path, header, val)?;
+            // 将 生效的系统配置 写入记录文件
+            $crate::tools::file::write_to_file::write_sys_conf_record($mode, summary_path)?;
+            // 将 生效的自定义参数 写入记录文件
+            $crate::tools::file::write_to_file::write_mod_args_record($mode, summary_path, &$mod_args)?;
+            // 将 解析后的扫描任务配置 写入记录文件
+            $crate::tools::file::write_to_file::write_profile_record($mode, summary_path, $base.aes_rand.seed)?;
         }
     );
 
@@ -155,7 +182,7 @@ macro_rules! write_to_summary {
 macro_rules! get_conf_from_mod_or_sys {
     ($mod_conf:ident; $($field:ident),*) => (
         $(
-            let $field = $mod_conf.get_conf_or_from_sys(&String::from(stringify!($field)));
+            let $field = $mod_conf.get_conf_or_from_sys(&String::from(stringify!($field)))?;
         )*
     );
 }
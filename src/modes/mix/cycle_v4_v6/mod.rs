@@ -5,7 +5,7 @@ use std::sync::Arc;
 use crate::core::conf::set_conf::base_conf::BaseConf;
 use crate::core::conf::set_conf::receiver_conf::ReceiverBaseConf;
 use crate::core::conf::set_conf::sender_conf::SenderBaseConf;
-use crate::modes::{Helper};
+use crate::modes::{Helper, ModeReg};
 use crate::modules::probe_modules::probe_mod_v4::ProbeModV4;
 use crate::modules::probe_modules::probe_mod_v6::ProbeModV6;
 use crate::modules::target_iterators::{CycleIpv4Port, CycleIpv6Port};
@@ -47,4 +47,19 @@ impl  Helper  for CycleV4V6 {
     fn print_help() -> String {
         SYS.get_info("help", "CycleV4V6")
     }
-}
\ No newline at end of file
+}
+
+
+impl CycleV4V6 {
+
+    /// 模式注册信息
+    pub const MODE:ModeReg = ModeReg {
+        name: "c46",
+        aliases: &["cycle_v4_v6"],
+        help: Some(CycleV4V6::print_help),
+        args: &[],
+        listed: true,
+        new: |args, conf| Ok(Box::new(CycleV4V6::new(args, conf)?)),
+        init: (),
+    };
+}
@@ -1,4 +1,5 @@
 use crate::core::conf::args::Args;
+use crate::core::conf::set_conf::ScanConf;
 use crate::core::conf::modules_config::ModuleConf;
 use crate::core::conf::set_conf::base_conf::BaseConf;
 use crate::core::conf::set_conf::receiver_conf::ReceiverBaseConf;
@@ -12,44 +13,50 @@ use crate::modules::target_iterators::{CycleIpv4Port, CycleIpv6Port};
 use crate::tools::blocker::ipv4_blocker::BlackWhiteListV4;
 use crate::tools::blocker::ipv6_blocker::BlackWhiteListV6;
 use crate::write_to_summary;
+use crate::core::errors::SmapError;
 
 impl CycleV4V6 {
 
-    pub fn new(args:&Args) -> Self {
+    pub fn new(args:&Args, conf:&ScanConf) -> Result<Self, SmapError> {
 
         //  获取探测目标
         //  v4,v6:(start_ip, end_ip, tar_ip_num)
         //  ips_v4, ips_v6: (min_ip, max_ip, 总数量),  total_ip_num: ipv4 和 ipv6的总数量之和
         let (v4_ranges, v6_ranges, ips_v4, ips_v6, total_ip_num) =
-        parse_mix_v4_v6_cycle_group(&TarIterBaseConf::parse_tar_ip(&args.tar_ips));
-        let tar_ports = TarIterBaseConf::parse_tar_port(&args.tar_ports, "default_ports");
+        parse_mix_v4_v6_cycle_group(&TarIterBaseConf::parse_tar_ip(&args.tar_ips)?)?;
+        let tar_ports = TarIterBaseConf::parse_tar_port(&args.tar_ports, "default_ports")?;
 
         // 基础配置
-        let mut base_conf = BaseConf::new(args);
+        let mut base_conf = BaseConf::new(&conf.base)?;
 
         // ipv4 ipv6 探测模块
         let probe_v4;
         let probe_v6;
         let max_packet_length;
+        let mod_args;
         {
-            let probe_args = ModuleConf::new_from_vec_args(&args.custom_args, vec![]);
+            // 解析 并检查 自定义参数, 两种探测模块 共用同一组自定义参数
+            let probe_name_v4 = SenderBaseConf::parse_probe_v4(&args.probe_v4, "default_probe_mod_v4");
+            let probe_name_v6 = SenderBaseConf::parse_probe_v6(&args.probe_v6, "default_probe_mod_v6");
+            let probe_args = ModuleConf::new_from_vec_args(&args.custom_args, vec![])?;
+            mod_args = probe_args.check(&[ProbeModV4::args(&probe_name_v4)?, ProbeModV6::args(&probe_name_v6)?])?;
 
             probe_v4 = ProbeModV4::new(
-                &SenderBaseConf::parse_probe_v4(&args.probe_v4, "default_probe_mod_v4"),
+                &probe_name_v4,
                 probe_args.clone(),
                 &{
                     // 如果不存在ipv4目标, 就将 ipv4探测模块的 目标地址强制设为 0, 绕过icmp模块合法性检查
                     // 警告: 如果默认探测模块为 非网络层探测模块时, 此处会可能会报错
                     // 此处的唯一作用是绕过探测模块合法性检查
                     if ips_v4.2 == 0 { vec![0] } else { tar_ports.clone() }
-                }, base_conf.aes_rand.seed,  &args.fields);
+                }, base_conf.aes_rand.seed,  &args.fields)?;
 
             probe_v6 = ProbeModV6::new(
-                &SenderBaseConf::parse_probe_v6(&args.probe_v6, "default_probe_mod_v6"),
+                &probe_name_v6,
                 probe_args,
                 &{
                     if ips_v6.2 == 0 { vec![0] } else { tar_ports.clone() }
-                }, base_conf.aes_rand.seed, &args.fields);
+                }, base_conf.aes_rand.seed, &args.fields)?;
 
             // 将 两种模块 捕获数据包的最大长度中的最大值 作为 捕获数据包的最大长度
             if ips_v4.2 != 0 && ips_v6.2 != 0 {
@@ -75,7 +82,7 @@ impl CycleV4V6 {
         {
             for (start_ip, _, tar_ip_num) in v4_ranges.iter() {
                 let ipv4_iter = CycleIpv4Port::new(*start_ip, *tar_ip_num, tar_ports.clone(),
-                                               &mut base_conf.aes_rand.rng);
+                                               &mut base_conf.aes_rand.rng)?;
                 total_p_sub_one_v4 += ipv4_iter.p_sub_one as u128;
                 p_sub_one_vec_v4.push(ipv4_iter.p_sub_one);
                 target_iters_v4.push(ipv4_iter);
@@ -83,7 +90,7 @@ impl CycleV4V6 {
 
             for (start_ip, _, tar_ip_num) in v6_ranges.iter() {
                 let ipv6_iter = CycleIpv6Port::new(*start_ip, *tar_ip_num, tar_ports.clone(),
-                                               &mut base_conf.aes_rand.rng);
+                                               &mut base_conf.aes_rand.rng)?;
                 total_p_sub_one_v6 += ipv6_iter.p_sub_one;
                 p_sub_one_vec_v6.push(ipv6_iter.p_sub_one);
                 target_iters_v6.push(ipv6_iter);
@@ -91,12 +98,12 @@ impl CycleV4V6 {
         }
 
         // 发送模块基础配置
-        let sender_conf= SenderBaseConf::new(args, &base_conf.interface,
-                                             SenderBaseConf::get_tar_num(total_ip_num, tar_ports.len()), None,
-                                             max_packet_length, total_p_sub_one_v4 != 0, total_p_sub_one_v6 != 0);
+        let sender_conf= SenderBaseConf::new(conf, &base_conf.interface,
+                                             SenderBaseConf::get_tar_num(total_ip_num, tar_ports.len())?, None,
+                                             max_packet_length, total_p_sub_one_v4 != 0, total_p_sub_one_v6 != 0)?;
 
         // 计算为 ipv4 和 ipv6分配的发送线程数量
-        let (thread_num_v4, thread_num_v6) = TarIterBaseConf::assign_threads_for_v4_v6(total_p_sub_one_v4, total_p_sub_one_v6, sender_conf.send_thread_num);
+        let (thread_num_v4, thread_num_v6) = TarIterBaseConf::assign_threads_for_v4_v6(total_p_sub_one_v4, total_p_sub_one_v6, sender_conf.send_thread_num)?;
 
         let (assigned_tasks_v4, assigned_tasks_v6) = TarIterBaseConf::cycle_group_assign_targets_mix(
              p_sub_one_vec_v4, p_sub_one_vec_v6, thread_num_v4, thread_num_v6
@@ -104,22 +111,22 @@ impl CycleV4V6 {
 
         // 定义全局 黑白名单拦截器
         let blocker_v4 = BlackWhiteListV4::new(
-            &args.black_list_v4, &args.white_list_v4, false);
+            &args.black_list_v4, &args.white_list_v4, false)?;
         let blocker_v6 = BlackWhiteListV6::new(
-            &args.black_list_v6, &args.white_list_v6, false);
+            &args.black_list_v6, &args.white_list_v6, false)?;
 
         // 接收模块基础配置
         let receiver_conf= if ips_v4.2 != 0 && ips_v6.2 != 0 {
-            ReceiverBaseConf::new(args, vec![probe_v4.filter_v4.clone(), probe_v6.filter_v6.clone()])
+            ReceiverBaseConf::new(conf, &base_conf, vec![probe_v4.filter_v4.clone(), probe_v6.filter_v6.clone()])?
         } else if ips_v4.2 != 0 {
-            ReceiverBaseConf::new(args, vec![probe_v4.filter_v4.clone()])
-        } else { ReceiverBaseConf::new(args, vec![probe_v6.filter_v6.clone()]) };
+            ReceiverBaseConf::new(conf, &base_conf, vec![probe_v4.filter_v4.clone()])?
+        } else { ReceiverBaseConf::new(conf, &base_conf, vec![probe_v6.filter_v6.clone()])? };
         
         let ttl = args.ttl;
 
-        write_to_summary!(base_conf; "CycleV4V6"; "args"; args;);
+        write_to_summary!(base_conf; "CycleV4V6"; "args"; args; mod_args;);
 
-        Self {
+        Ok(Self {
             base_conf: base_conf.into(),
             sender_conf: sender_conf.into(),
             receiver_conf: receiver_conf.into(),
@@ -130,8 +137,8 @@ impl CycleV4V6 {
 
 
             // 注意: 这里计算的范围大小是从 最小ip 到 最大ip 之间的范围大小, 用来设置 接收线程的位图
-            tar_num_v4: SenderBaseConf::get_tar_num_without_option(ips_v4.2, tar_ports.len()),
-            tar_num_v6: SenderBaseConf::get_tar_num_without_option(ips_v6.2, tar_ports.len()),
+            tar_num_v4: SenderBaseConf::get_tar_num_without_option(ips_v4.2, tar_ports.len())?,
+            tar_num_v6: SenderBaseConf::get_tar_num_without_option(ips_v6.2, tar_ports.len())?,
 
             // 外层向量长度为 ipv4线程数量,  内层向量长度为 ipv4范围数量
 
@@ -147,6 +154,6 @@ impl CycleV4V6 {
             v4_ranges,
             v6_ranges,
             ttl,
-        }
+        })
     }
 }
\ No newline at end of file
@@ -5,93 +5,89 @@ mod v6;
 mod mix;
 mod macros;
 
-use std::process::exit;
+use log::warn;
 use crate::core::conf::args::Args;
+use crate::core::conf::set_conf::ScanConf;
+use crate::core::errors::{ConfError, SmapError};
+use crate::core::registry::{find, Registration};
 use crate::modes::helper_mode::helper;
+use crate::SYS;
 
 pub struct Mode {}
 pub use helper_mode::Helper;
 
-/// 激活的所有模块
-const MODES: [&str; 18] = ["c4",
-                          "c6",
-                          "c6p",
-                          "f4",
-                          "f6",
-                          "c46",
-                          "p4",
-                          "p6",
-                          "pf6",
-                          "t4",
-                          "t6",
-                          "ipv6_addrs_gen",
-                          "ac6",
-                          "ipv6_double_tree_test", 
-                          "tt6", "s6", "a6","e6"];
+/// 模式注册信息: 构造函数 接收 模式参数(目标, 探测模块, 自定义参数等) 和 扫描配置, 返回 模式
+pub type ModeReg = Registration<fn(&Args, &ScanConf) -> Result<Box<dyn ModeMethod>, SmapError>>;
+
+/// 所有已注册的模式, 列表按此顺序打印
+pub const MODES:&[ModeReg] = &[
+    v4::CycleV4::MODE,
+    v6::CycleV6::MODE,
+    v6::CycleV6Pattern::MODE,
+    v4::V4FileReader::MODE,
+    v6::V6FileReader::MODE,
+    mix::CycleV4V6::MODE,
+    v4::PmapV4::MODE,
+    v6::PmapV6::MODE,
+    v6::PmapFileV6::MODE,
+    v4::Topo4::MODE,
+    v6::Topo6::MODE,
+    v6::SpaceTree6::MODE,
+    v6::IPv6AliasedCheck::MODE,
+
+    // 警告: 该模式只用作测试, 请及时删除
+    v6::DoubleTreeTest::MODE,
+
+    v6::TreeTrace6::MODE,
+    v6::Scour6::MODE,
+    v6::Asset6::MODE,
+    v6::Edge6::MODE,
+
+    // 暂时废弃， 但是其中包含可重用的算法代码
+    v6::PrefixTree6::MODE,
+    v6::PrefixFixedTree6::MODE,
+];
+
+/// 支持 断点续扫 的模式
+const CHECKPOINT_MODES:[&str; 3] = [v4::CycleV4::MODE.name, v6::CycleV6::MODE.name, v4::PmapV4::MODE.name];
+
+/// 支持 多线程接收 的模式, 其他模式 只使用单个接收线程
+const FANOUT_MODES:[&str; 2] = [v4::CycleV4::MODE.name, v6::CycleV6::MODE.name];
+
 impl Mode {
 
-    pub fn new(args:&Args) -> Box<dyn ModeMethod> {
+    pub fn new(args:&Args, conf:&ScanConf) -> Result<Box<dyn ModeMethod>, SmapError> {
 
-        let mode = match args.mode.clone() {
+        let mode = match &args.mode {
             Some(m) => m,
             // 没有设置模式
-            None => { helper(args); exit(0) }
+            None => return Err(ConfError::missing("mode_not_set").into()),
         };
 
-        match mode.as_str() {
-
-
-            "c4"  => Box::new(v4::CycleV4::new(args)),
-
-            "c6" => Box::new(v6::CycleV6::new(args)),
-
-            "c6p" => Box::new(v6::CycleV6Pattern::new(args)),
-
-            "f4"  => Box::new(v4::V4FileReader::new(args)),
-
-            "f6"  => Box::new(v6::V6FileReader::new(args)),
-
-            "c46" => Box::new(mix::CycleV4V6::new(args)),
-
-            "p4" => Box::new(v4::PmapV4::new(args)),
-
-            "p6" => Box::new(v6::PmapV6::new(args)),
-
-            "pf6" => Box::new(v6::PmapFileV6::new(args)),
-
-            "t4" => Box::new(v4::Topo4::new(args)),
-            
-            "t6" => Box::new(v6::Topo6::new(args)),
-
-            "ipv6_addrs_gen" => Box::new(v6::SpaceTree6::new(args)),
-
-            "ac6" => Box::new(v6::IPv6AliasedCheck::new(args)),
-
-            // 暂时废弃， 但是其中包含可重用的算法代码
-            "ipv6_prefix_tree" => Box::new(v6::PrefixTree6::new(args)),
-            "ipv6_prefix_fixed_tree" => Box::new(v6::PrefixFixedTree6::new(args)),
-
-            // 警告: 该模式只用作测试, 请及时删除
-            "ipv6_double_tree_test" => Box::new(v6::DoubleTreeTest::new(args)),
-
-            "tt6" => Box::new(v6::TreeTrace6::new(args)),
-
-            "s6" => Box::new(v6::Scour6::new(args)),
-            
-            "a6" => Box::new(v6::Asset6::new(args)),
-            
-            "e6" => Box::new(v6::Edge6::new(args)),
-
-            _ => {
-                // 未查询到有效模式
-                helper(args);
-                exit(0)
+        match find(MODES, mode) {
+            Some(reg) => {
+                if (conf.base.checkpoint.is_some() || conf.base.resume.is_some()) && !CHECKPOINT_MODES.contains(&reg.name) {
+                    return Err(ConfError::invalid("checkpoint_not_supported").with(reg.name).into())
+                }
+                if conf.receiver.recv_thread_num.is_some_and(|n| n > 1) && !FANOUT_MODES.contains(&reg.name) {
+                    warn!("{} {}", SYS.get_info("warn", "recv_fanout_mode_unsupported"), reg.name);
+                }
+                (reg.new)(args, conf)
             }
+            // 未查询到有效模式
+            None => Err(ConfError::not_exist("mode_not_exist").with(mode).into()),
         }
-
     }
 
+    /// 是否 设置了有效的模式
+    pub fn exists(args:&Args) -> bool {
+        args.mode.as_ref().is_some_and(|m| find(MODES, m).is_some())
+    }
 
+    /// 打印 模块帮助信息, 或 没有有效模式时 列出所有模块
+    pub fn helper(args:&Args) {
+        helper(args)
+    }
 }
 
 
@@ -99,7 +95,7 @@ impl Mode {
 pub trait ModeMethod {
 
     // 执行函数
-    fn execute(&self);
+    fn execute(&self) -> Result<(), SmapError>;
 
 }
 
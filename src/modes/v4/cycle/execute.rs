@@ -1,19 +1,34 @@
-use std::process::exit;
 use std::thread;
+use log::warn;
 use chrono::{Local};
-use log::error;
 use crate::core::receiver::pcap::PcapReceiver;
 use crate::core::sender::{send_v4, send_v4_port};
 use crate::modes::ModeMethod;
 use crate::modes::v4::cycle::CycleV4;
-use crate::modules::target_iterators::CycleIpv4Type;
+use crate::modules::target_iterators::{CycleIpv4Type, CyclePosition};
 use crate::{computing_time, creat_channels, ending_the_receiving_thread, init_var, prepare_data, recv_ready, SYS, wait_sender_threads, write_to_summary};
 use crate::tools::check_duplicates::bit_map::{BitMapV4, BitMapV4Port};
+use crate::core::errors::SmapError;
+use crate::core::receiver::join_receiver;
+use crate::core::monitor::Monitor;
+use crate::core::checkpoint::Checkpointer;
 
 impl ModeMethod for CycleV4 {
 
     /// zmap_v4 执行函数
-    fn execute(&self){
+    fn execute(&self) -> Result<(), SmapError> {
+
+        // 启动 断点线程, 续扫时 读取并校验断点文件
+        let group = match &self.target_iter {
+            CycleIpv4Type::CycleIpv4(t) => t.group(),
+            CycleIpv4Type::CycleIpv4Port(t) => t.group(),
+        };
+        let checkpointer = Checkpointer::start(&self.base_conf, Self::MODE.name, group,
+            self.assigned_target_range.iter().map(|a| (a.0 as u128, a.1 as u128)).collect())?;
+
+        // 启动 扫描进度监视线程
+        let monitor = Monitor::start(&self.base_conf, &self.sender_conf, Self::MODE.name, self.probe.name,
+                                     checkpointer.resumed_processed())?;
 
         // 创建信息传递管道
         creat_channels!((recv_ready_sender, recv_ready_receiver, bool),(recv_close_time_sender, recv_close_time_receiver, i64));
@@ -23,38 +38,55 @@ impl ModeMethod for CycleV4 {
         {
             prepare_data!(self; start_ip, end_ip, tar_ip_num);
             prepare_data!(self; clone; base_conf, receiver_conf, probe);
+            let checkpoint = checkpointer.receiver();
 
             match &self.target_iter {
                 CycleIpv4Type::CycleIpv4(_) => {
                     receiver_res = thread::spawn(move || {
-                        // 初始化 位图
-                        let bit_map = BitMapV4::new(start_ip, end_ip, tar_ip_num);
-                        PcapReceiver::run_v4(0, base_conf, receiver_conf, probe,
-                                             bit_map, recv_ready_sender, recv_close_time_receiver)
+                        // 初始化 位图, 续扫时 由断点文件恢复
+                        let mut bit_map = BitMapV4::new(start_ip, end_ip, tar_ip_num)?;
+                        checkpoint.restore(&mut bit_map)?;
+                        if receiver_conf.recv_thread_num > 1 {
+                            // 多线程接收, 各接收线程 共用位图
+                            PcapReceiver::run_v4_fanout(0, base_conf, receiver_conf, probe,
+                                                 bit_map.into_shared(), recv_ready_sender, recv_close_time_receiver, checkpoint)
+                        } else {
+                            PcapReceiver::run_v4(0, base_conf, receiver_conf, probe,
+                                                 bit_map, recv_ready_sender, recv_close_time_receiver, checkpoint)
+                        }
                     });
                 }
                 CycleIpv4Type::CycleIpv4Port(t) => {
+                    // 端口探测 只使用单个接收线程
+                    if receiver_conf.recv_thread_num > 1 {
+                        warn!("{} {}", SYS.get_info("warn", "recv_fanout_mode_unsupported"), Self::MODE.name);
+                    }
                     let sports = self.sender_conf.source_ports.clone();
                     let tar_ports = t.tar_ports.clone();
 
                     receiver_res = thread::spawn(move || {
-                        // 初始化 位图
-                        let bit_map = BitMapV4Port::new(start_ip, end_ip, tar_ip_num, tar_ports);
+                        // 初始化 位图, 续扫时 由断点文件恢复
+                        let mut bit_map = BitMapV4Port::new(start_ip, end_ip, tar_ip_num, tar_ports)?;
+                        checkpoint.restore(&mut bit_map)?;
                         PcapReceiver::run_v4_port(0, base_conf, receiver_conf, probe, sports, bit_map,
-                                             recv_ready_sender, recv_close_time_receiver)
+                                             recv_ready_sender, recv_close_time_receiver, checkpoint)
                     });
                 }
             };
         }
         // 只有接收线程准备完毕后，发送线程才能继续执行
-        recv_ready!(recv_ready_receiver);
+        recv_ready!(recv_ready_receiver; receiver_res);
 
         // 记录 开始发送的时间
         let start_time = Local::now();
 
         // 执行发送线程
         let mut sender_threads = vec![];
-        for assigned_targets in self.assigned_target_range.iter() {
+        for (index, assigned_targets) in self.assigned_target_range.iter().enumerate() {
+
+            // 续扫时 不再启动 已处理完全部目标的发送线程
+            let checkpoint = checkpointer.sender(index);
+            if checkpoint.is_finished() { continue }
 
             prepare_data!(self; ttl);
             prepare_data!(self; clone; blocker, base_conf, sender_conf, probe);
@@ -62,21 +94,23 @@ impl ModeMethod for CycleV4 {
             let sender_thread;
             match &self.target_iter {
                 CycleIpv4Type::CycleIpv4(t) => {
-                    // 初始化 局部目标迭代器
-                    let target_iter = t.init(assigned_targets.0, assigned_targets.1);
+                    // 初始化 局部目标迭代器, 续扫时 从断点位置开始
+                    let mut target_iter = t.init(assigned_targets.0, assigned_targets.1)?;
+                    checkpoint.seek(&mut target_iter);
 
                     sender_thread = thread::spawn(move || {
                         send_v4(0, target_iter, 0,
-                                     blocker,probe, ttl, base_conf, sender_conf)
+                                     blocker,probe, ttl, base_conf, sender_conf, checkpoint)
                     });
                 }
                 CycleIpv4Type::CycleIpv4Port(t) => {
-                    // 初始化 局部目标迭代器
-                    let target_iter = t.init(assigned_targets.0, assigned_targets.1);
+                    // 初始化 局部目标迭代器, 续扫时 从断点位置开始
+                    let mut target_iter = t.init(assigned_targets.0, assigned_targets.1)?;
+                    checkpoint.seek(&mut target_iter);
 
                     sender_thread = thread::spawn(move || {
                         send_v4_port(0, target_iter, 0,
-                                     blocker,probe, ttl, base_conf, sender_conf)
+                                     blocker,probe, ttl, base_conf, sender_conf, checkpoint)
                     });
                 }
             }
@@ -90,25 +124,29 @@ impl ModeMethod for CycleV4 {
                 total_send_failed += send_failed;
                 total_blocked += blocked;
         });
-        println!("{} {} {} {}", SYS.get_info("print", "send_finished"), total_send_success, total_send_failed, total_blocked);
+        eprintln!("{} {} {} {}", SYS.get_info("print", "send_finished"), total_send_success, total_send_failed, total_blocked);
 
         // 计算终止时间 并向接收线程传递
         ending_the_receiving_thread!(self; recv_close_time_sender);
 
         // 等待接收线程按照预定时间关闭
-        if let Ok(receiver_info) = receiver_res.join() {
-            // 处理接收线程返回的信息
-
-            // 计算 结束时间 和 格式化后的运行时间 并显示
-            computing_time!(start_time; end_time, running_time);
-            println!("{} {} {} {}", SYS.get_info("print", "recv_finished_with_out_of_range"),
-                     receiver_info.recv_success, receiver_info.recv_repeat, receiver_info.recv_failed);
-
-            write_to_summary!(self; "CycleV4"; "result";
-                [start_time, end_time, running_time, total_send_success, total_send_failed, total_blocked;];
-                #[receiver_info; recv_success, recv_failed, recv_validation_passed, recv_validation_failed; ("receive_repeat_and_out_of_range", recv_repeat)]
-            );
-
-        } else { error!("{}", SYS.get_info("err", "recv_thread_err"));exit(1) }
+        let receiver_info = join_receiver(receiver_res)?;
+        // 停止 断点线程, 写入最终断点
+        drop(checkpointer);
+        // 停止 监视线程, 输出最终进度
+        drop(monitor);
+        // 处理接收线程返回的信息
+
+        // 计算 结束时间 和 格式化后的运行时间 并显示
+        computing_time!(start_time; end_time, running_time);
+        eprintln!("{} {} {} {}", SYS.get_info("print", "recv_finished_with_out_of_range"),
+                 receiver_info.recv_success, receiver_info.recv_repeat, receiver_info.recv_failed);
+
+        write_to_summary!(self; "CycleV4"; "result";
+            [start_time, end_time, running_time, total_send_success, total_send_failed, total_blocked;];
+            #[receiver_info; recv_success, recv_failed, recv_validation_passed, recv_validation_failed, pcap_received, pcap_dropped, pcap_if_dropped; ("receive_repeat_and_out_of_range", recv_repeat)]
+        );
+
+        Ok(())
     }
 }
\ No newline at end of file
@@ -7,7 +7,7 @@ use std::sync::Arc;
 use crate::core::conf::set_conf::base_conf::BaseConf;
 use crate::core::conf::set_conf::receiver_conf::ReceiverBaseConf;
 use crate::core::conf::set_conf::sender_conf::SenderBaseConf;
-use crate::modes::{Helper};
+use crate::modes::{Helper, ModeReg};
 use crate::modules::probe_modules::probe_mod_v4::ProbeModV4;
 use crate::modules::target_iterators::CycleIpv4Type;
 use crate::SYS;
@@ -39,3 +39,18 @@ impl  Helper  for CycleV4 {
         SYS.get_info("help", "CycleV4")
     }
 }
+
+
+impl CycleV4 {
+
+    /// 模式注册信息
+    pub const MODE:ModeReg = ModeReg {
+        name: "c4",
+        aliases: &["cycle_v4"],
+        help: Some(CycleV4::print_help),
+        args: &[],
+        listed: true,
+        new: |args, conf| Ok(Box::new(CycleV4::new(args, conf)?)),
+        init: (),
+    };
+}
@@ -1,17 +1,18 @@
-use std::process::exit;
 use std::thread;
 use chrono::{Local};
-use log::error;
 use crate::core::receiver::pcap::PcapReceiver;
-use crate::core::sender::{send_file_v4_port};
+use crate::core::sender::{request_abort, send_file_v4_port};
 use crate::modes::ModeMethod;
 use crate::modes::v4::file_reader::V4FileReader;
 use crate::{computing_time, creat_channels, ending_the_receiving_thread, init_var, prepare_data, recv_ready, SYS, wait_sender_threads, write_to_summary};
 use crate::tools::check_duplicates::hash_set::HashSetV4Port;
+use crate::core::errors::SmapError;
+use crate::core::receiver::join_receiver;
+use crate::core::checkpoint::RecvCheckpoint;
 
 impl ModeMethod for V4FileReader {
 
-    fn execute(&self){
+    fn execute(&self) -> Result<(), SmapError> {
 
         // 创建信息传递管道
         creat_channels!((recv_ready_sender, recv_ready_receiver, bool),(recv_close_time_sender, recv_close_time_receiver, i64));
@@ -28,12 +29,12 @@ impl ModeMethod for V4FileReader {
                 //                      recv_ready_sender, recv_close_time_receiver)
                 // 初始化 哈希集合查重器
                 let hash_set = HashSetV4Port::new(tar_num);
-                PcapReceiver::run_v4_port(0, base_conf, receiver_conf, probe, sports, hash_set, recv_ready_sender, recv_close_time_receiver)
+                PcapReceiver::run_v4_port(0, base_conf, receiver_conf, probe, sports, hash_set, recv_ready_sender, recv_close_time_receiver, RecvCheckpoint::none())
             });
         }
 
         // 只有接收线程准备完毕后，发送线程才能继续执行
-        recv_ready!(recv_ready_receiver);
+        recv_ready!(recv_ready_receiver; receiver_res);
 
         // 记录 开始发送的时间
         let start_time = Local::now();
@@ -46,7 +47,8 @@ impl ModeMethod for V4FileReader {
             for assigned_targets in self.assigned_target_range.iter() {
 
                 // 初始化 局部目标迭代器
-                let target_iter_option = self.target_iter.get_ipv4_file_reader(assigned_targets, *tar_port);
+                let target_iter_option = self.target_iter.get_ipv4_file_reader(assigned_targets, *tar_port)
+                    .inspect_err(|_| request_abort())?;
 
                 if let Some(target_iter) = target_iter_option {
                     // 获得有效目标迭代器
@@ -68,23 +70,24 @@ impl ModeMethod for V4FileReader {
             });
         }
 
-        println!("{} {} {} {}", SYS.get_info("print", "send_finished"), total_send_success, total_send_failed, total_blocked);
+        eprintln!("{} {} {} {}", SYS.get_info("print", "send_finished"), total_send_success, total_send_failed, total_blocked);
 
         // 计算终止时间 并向接收线程传递
         ending_the_receiving_thread!(self; recv_close_time_sender);
 
         // 等待接收线程按照预定时间关闭
-        if let Ok(receiver_info) = receiver_res.join() {
-            // 处理接收线程返回的信息
-
-            // 计算 结束时间 和 格式化后的运行时间 并显示
-            computing_time!(start_time; end_time, running_time);
-            println!("{} {} {} {}", SYS.get_info("print", "recv_finished"), receiver_info.recv_success, receiver_info.recv_repeat, receiver_info.recv_failed);
-
-            write_to_summary!(self; "V4FileReader"; "result";
-                [start_time, end_time, running_time, total_send_success, total_send_failed, total_blocked;];
-                #[receiver_info; recv_success, recv_failed, recv_repeat, recv_validation_passed, recv_validation_failed; ]
-            );
-        } else {  error!("{}", SYS.get_info("err", "recv_thread_err")); exit(1) }
+        let receiver_info = join_receiver(receiver_res)?;
+        // 处理接收线程返回的信息
+
+        // 计算 结束时间 和 格式化后的运行时间 并显示
+        computing_time!(start_time; end_time, running_time);
+        eprintln!("{} {} {} {}", SYS.get_info("print", "recv_finished"), receiver_info.recv_success, receiver_info.recv_repeat, receiver_info.recv_failed);
+
+        write_to_summary!(self; "V4FileReader"; "result";
+            [start_time, end_time, running_time, total_send_success, total_send_failed, total_blocked;];
+            #[receiver_info; recv_success, recv_failed, recv_repeat, recv_validation_passed, recv_validation_failed, pcap_received, pcap_dropped, pcap_if_dropped; ]
+        );
+
+        Ok(())
     }
 }
\ No newline at end of file
@@ -1,40 +1,65 @@
-use std::process::exit;
 use std::sync::{Arc};
 use std::thread;
 use std::thread::JoinHandle;
 use ahash::AHashMap;
 use bitvec::macros::internal::funty::Fundamental;
 use chrono::Local;
-use log::{error};
 use crate::core::conf::tools::args_parse::target_iterator::TarIterBaseConf;
 use crate::core::receiver::pcap::PcapReceiver;
 use crate::core::sender::{pmap_full_scan_send_v4, pmap_recommend_new_scan_send_v4_port, pmap_recommend_scan_send_v4_port};
 use crate::modes::ModeMethod;
 use crate::modes::v4::pmap::PmapV4;
 use crate::modules::output_modules::{OutputMod};
-use crate::modules::target_iterators::{PmapGraph, PmapState};
+use crate::modules::target_iterators::{CyclePosition, PmapGraph, PmapState};
 use crate::{computing_time, creat_channels, ending_the_receiving_thread, init_var, prepare_data, recv_ready, SYS, wait_sender_threads, write_to_summary};
 use crate::tools::check_duplicates::bit_map::BitMapV4Port;
 use crate::tools::check_duplicates::hash_set::HashSetV4Port;
+use crate::core::errors::{RunError, SmapError};
+use crate::core::receiver::{join_receiver, ReceiverHandle};
+use crate::core::receiver::pcap::source::CaptureStats;
+use crate::core::monitor::Monitor;
+use crate::core::checkpoint::{Checkpointer, RecvCheckpoint};
 
 enum Recorder4P {
-    B4P(JoinHandle<BitMapV4Port>),
-    H4P(JoinHandle<HashSetV4Port>)
+    B4P(JoinHandle<Result<(BitMapV4Port, CaptureStats), SmapError>>),
+    H4P(JoinHandle<Result<(HashSetV4Port, CaptureStats), SmapError>>)
+}
+
+impl ReceiverHandle for Recorder4P {
+    fn join_err(self) -> Option<SmapError> {
+        match self {
+            Recorder4P::B4P(b) => b.join_err(),
+            Recorder4P::H4P(h) => h.join_err(),
+        }
+    }
 }
 
 impl ModeMethod for PmapV4 {
-    fn execute(&self) {
+    fn execute(&self) -> Result<(), SmapError> {
+
+        // 获取 完全扫描的多线程任务分配列表
+        let full_scan_tar_ranges = TarIterBaseConf::cycle_group_assign_targets_u64(self.full_scan_last_index, self.sender_conf.send_thread_num as u64);
+
+        // 启动 断点线程, 续扫时 读取并校验断点文件. 断点只覆盖 完全扫描阶段, 续扫时 推荐扫描阶段 重新执行
+        let checkpointer = Checkpointer::start(&self.base_conf, Self::MODE.name, self.tar_iter_without_port.group(),
+            full_scan_tar_ranges.iter().map(|r| (r.0 as u128, r.1 as u128)).collect())?;
+
+        // 启动 扫描进度监视线程
+        let monitor = Monitor::start(&self.base_conf, &self.sender_conf, Self::MODE.name, self.probe.name,
+                                     checkpointer.resumed_processed())?;
 
         // 定义 概率相关图
         let mut graph;
         // 初始化 输出模块
-        let mut out_mod = OutputMod::init(&self.receiver_conf.output_v4);
+        let mut out_mod = OutputMod::init(&self.receiver_conf.output_v4)?;
 
         // 定义 开始的时间
         let start_time;
         // 初始化全局 发送线程消息  接收线程消息
         init_var!(u64; 0; total_send_success, total_send_failed, total_blocked);
         init_var!(usize; 0; total_ip_count, total_pair_count);
+        // 各扫描阶段 捕获统计 之和
+        let mut capture_stats = CaptureStats::default();
 
         // 预扫描阶段
         {
@@ -44,45 +69,50 @@ impl ModeMethod for PmapV4 {
                 // 完全扫描 接收线程  数据准备
                 prepare_data!(self; clone; base_conf, receiver_conf, probe);
                 let sports = self.sender_conf.source_ports.clone();
-                // 执行接收线程
+                let checkpoint = checkpointer.receiver();
+                // 执行接收线程, 续扫时 由断点文件恢复 标记记录器
                 if self.pmap_use_hash_recorder {
                     prepare_data!(self; as_usize; tar_ip_num);
                     Recorder4P::H4P(thread::spawn(move || {
-                        let recorder = HashSetV4Port::new(tar_ip_num);
+                        let mut recorder = HashSetV4Port::new(tar_ip_num);
+                        checkpoint.restore(&mut recorder)?;
                         PcapReceiver::pmap_full_scan_v4(0, base_conf, receiver_conf, probe, sports, recorder,
-                                                            recv_ready_sender, recv_close_time_receiver)
+                                                            recv_ready_sender, recv_close_time_receiver, checkpoint)
                     }))
                 } else {
                     prepare_data!(self; start_ip, end_ip, tar_ip_num);
                     prepare_data!(self; clone; tar_ports);
                     Recorder4P::B4P(thread::spawn(move || {
                         // 注意: 这里应该用 全部目标范围, 而不是只有预探测目标范围
-                        let bit_map = BitMapV4Port::new(start_ip, end_ip, tar_ip_num, tar_ports);
+                        let mut bit_map = BitMapV4Port::new(start_ip, end_ip, tar_ip_num, tar_ports)?;
+                        checkpoint.restore(&mut bit_map)?;
                         PcapReceiver::pmap_full_scan_v4(0, base_conf, receiver_conf, probe, sports, bit_map,
-                                                        recv_ready_sender, recv_close_time_receiver)
+                                                        recv_ready_sender, recv_close_time_receiver, checkpoint)
                     }))
                 }
             };
 
             {   // 只有接收线程准备完毕后，发送线程才能继续执行
-                recv_ready!(recv_ready_receiver);
-
-                // 获取 完全扫描的多线程任务分配列表
-                let full_scan_tar_ranges = TarIterBaseConf::cycle_group_assign_targets_u64(self.full_scan_last_index, self.sender_conf.send_thread_num as u64);
+                recv_ready!(recv_ready_receiver; full_scan_result);
 
                 // 记录 开始发送的时间
                 start_time = Local::now();
 
                 // 执行 完全扫描 发送线程
                 let mut full_scan_sender_threads = vec![];
-                for target_range in full_scan_tar_ranges.into_iter() {
+                for (index, target_range) in full_scan_tar_ranges.into_iter().enumerate() {
+
+                    // 续扫时 不再启动 已处理完全部目标的发送线程, 未完成的发送线程 从断点所在的端口和位置 继续
+                    let checkpoint = checkpointer.sender(index);
+                    if checkpoint.is_finished() { continue }
+
                     // 发送线程 数据准备
                     prepare_data!(self; clone; blocker, base_conf, sender_conf, probe, tar_ports);
                     // 初始化 局部目标迭代器
-                    let target_iter = self.tar_iter_without_port.init(target_range.0, target_range.1);
+                    let target_iter = self.tar_iter_without_port.init(target_range.0, target_range.1)?;
 
                     let full_scan_sender = thread::spawn(move || {
-                        pmap_full_scan_send_v4(0, target_iter, blocker, probe, tar_ports, base_conf, sender_conf)
+                        pmap_full_scan_send_v4(0, target_iter, blocker, probe, tar_ports, base_conf, sender_conf, checkpoint)
                     });
 
                     full_scan_sender_threads.push(full_scan_sender);
@@ -98,56 +128,52 @@ impl ModeMethod for PmapV4 {
             }
 
             // 完全扫描全局迭代器
-            let full_scan_iter = self.tar_iter_without_port.init(1, self.full_scan_last_index);
+            let full_scan_iter = self.tar_iter_without_port.init(1, self.full_scan_last_index)?;
             match full_scan_result {
                 Recorder4P::B4P(b) => {
-                    match &b.join() {
-                        Ok(bit_map) => {
-                            if self.recommend_scan {     // 将完全扫描阶段的结果进行输出, 使用结果对概率相关图进行训练
-                                graph = Arc::new(PmapGraph::new(self.tar_ports.clone(), self.port_num_limit));
-                                match Arc::get_mut(&mut graph) {
-                                    Some(g_ptr) => {
-                                        let (ip_count, pair_count) = Self::full_scan_output_and_train(full_scan_iter, bit_map,  &self.blocker, &mut out_mod, g_ptr);
-                                        total_ip_count += ip_count;
-                                        total_pair_count += pair_count;
-                                    }
-                                    None => { error!("{}", SYS.get_info("err", "get_graph_arc_failed")); exit(1) }
-                                }
-                            } else {        // 将完全扫描阶段的结果进行输出
-                                let (ip_count, pair_count) = Self::full_scan_output(full_scan_iter, bit_map,  &self.blocker, &mut out_mod);
+                    let (bit_map, stats) = &join_receiver(b)?;
+                    capture_stats.merge(stats);
+                    if self.recommend_scan {     // 将完全扫描阶段的结果进行输出, 使用结果对概率相关图进行训练
+                        graph = Arc::new(PmapGraph::new(self.tar_ports.clone(), self.port_num_limit));
+                        match Arc::get_mut(&mut graph) {
+                            Some(g_ptr) => {
+                                let (ip_count, pair_count) = Self::full_scan_output_and_train(full_scan_iter, bit_map,  &self.blocker, &mut out_mod, g_ptr)?;
                                 total_ip_count += ip_count;
                                 total_pair_count += pair_count;
-                                graph = Arc::new(PmapGraph::new_void());
                             }
+                            None => return Err(RunError::thread("get_graph_arc_failed").into()),
                         }
-                        Err(_) => { error!("{}", SYS.get_info("err", "recv_thread_err")); exit(1) }
+                    } else {        // 将完全扫描阶段的结果进行输出
+                        let (ip_count, pair_count) = Self::full_scan_output(full_scan_iter, bit_map,  &self.blocker, &mut out_mod)?;
+                        total_ip_count += ip_count;
+                        total_pair_count += pair_count;
+                        graph = Arc::new(PmapGraph::new_void());
                     }
                 }
                 Recorder4P::H4P(h) => {
-                    match &h.join() {
-                        Ok(hash_set) => {
-                            if self.recommend_scan {     // 将完全扫描阶段的结果进行输出, 使用结果对概率相关图进行训练
-                                graph = Arc::new(PmapGraph::new(self.tar_ports.clone(), self.port_num_limit));
-                                match Arc::get_mut(&mut graph) {
-                                    Some(g_ptr) => {
-                                        let (ip_count, pair_count) = Self::full_scan_output_and_train(full_scan_iter, hash_set, &self.blocker, &mut out_mod, g_ptr);
-                                        total_ip_count += ip_count;
-                                        total_pair_count += pair_count;
-                                    }
-                                    None => { error!("{}", SYS.get_info("err", "get_graph_arc_failed")); exit(1) }
-                                }
-                            } else {        // 将完全扫描阶段的结果进行输出
-                                let (ip_count, pair_count) = Self::full_scan_output(full_scan_iter, hash_set,  &self.blocker, &mut out_mod);
+                    let (hash_set, stats) = &join_receiver(h)?;
+                    capture_stats.merge(stats);
+                    if self.recommend_scan {     // 将完全扫描阶段的结果进行输出, 使用结果对概率相关图进行训练
+                        graph = Arc::new(PmapGraph::new(self.tar_ports.clone(), self.port_num_limit));
+                        match Arc::get_mut(&mut graph) {
+                            Some(g_ptr) => {
+                                let (ip_count, pair_count) = Self::full_scan_output_and_train(full_scan_iter, hash_set, &self.blocker, &mut out_mod, g_ptr)?;
                                 total_ip_count += ip_count;
                                 total_pair_count += pair_count;
-                                graph = Arc::new(PmapGraph::new_void());
                             }
+                            None => return Err(RunError::thread("get_graph_arc_failed").into()),
                         }
-                        Err(_) => { error!("{}", SYS.get_info("err", "recv_thread_err")); exit(1) }
+                    } else {        // 将完全扫描阶段的结果进行输出
+                        let (ip_count, pair_count) = Self::full_scan_output(full_scan_iter, hash_set,  &self.blocker, &mut out_mod)?;
+                        total_ip_count += ip_count;
+                        total_pair_count += pair_count;
+                        graph = Arc::new(PmapGraph::new_void());
                     }
                 }
             }
         }
+        // 停止 断点线程, 写入 完全扫描阶段的最终断点
+        drop(checkpointer);
 
         // 活跃端口推荐探测阶段
         if self.recommend_scan {
@@ -165,7 +191,7 @@ impl ModeMethod for PmapV4 {
 
                 // 生成 pmap迭代器 队列
                 let mut pmap_iter_queue= Self::create_pmap4_iter_queue(
-                    batch_range.0-1, batch_range.1, self.sender_conf.send_thread_num as u64, &self.tar_iter_without_port);
+                    batch_range.0-1, batch_range.1, self.sender_conf.send_thread_num as u64, &self.tar_iter_without_port)?;
 
                 let mut sent_port_count_add_one:u32 = 0;
                 loop {
@@ -187,21 +213,21 @@ impl ModeMethod for PmapV4 {
                             Recorder4P::H4P(thread::spawn(move || {
                                 let hash_set = HashSetV4Port::new(tar_ip_num);
                                 PcapReceiver::pmap_full_scan_v4(0, base_conf, receiver_conf, probe, sports, hash_set,
-                                                                     recv_ready_sender, recv_close_time_receiver)
+                                                                     recv_ready_sender, recv_close_time_receiver, RecvCheckpoint::none())
                             }))
                         } else {
                             prepare_data!(self; start_ip, end_ip, tar_ip_num);
                             prepare_data!(self; clone; tar_ports);
                             Recorder4P::B4P(thread::spawn(move || {
-                                let bit_map = BitMapV4Port::new(start_ip, end_ip, tar_ip_num, tar_ports);
+                                let bit_map = BitMapV4Port::new(start_ip, end_ip, tar_ip_num, tar_ports)?;
                                 PcapReceiver::pmap_full_scan_v4(0, base_conf, receiver_conf, probe, sports, bit_map,
-                                                                     recv_ready_sender, recv_close_time_receiver)
+                                                                     recv_ready_sender, recv_close_time_receiver, RecvCheckpoint::none())
                             }))
                         }
                     };
 
                     // 只有接收线程准备完毕后，发送线程才能继续执行
-                    recv_ready!(recv_ready_receiver);
+                    recv_ready!(recv_ready_receiver; recommend_scan_result);
 
                     // 执行 推荐扫描 发送线程
                     let mut recommend_scan_sender_threads = vec![];
@@ -239,17 +265,19 @@ impl ModeMethod for PmapV4 {
                     match Arc::get_mut(&mut graph) {
                         Some(g_ptr) => {
                             match recommend_scan_result {
-                                Recorder4P::B4P(b) =>
-                                    if let Ok(bit_map) = &b.join() {
-                                        Self::pmap_receive(bit_map, g_ptr, &mut states_map, &mut pmap_iter_queue, &self.blocker);
-                                    },
-                                Recorder4P::H4P(h) =>
-                                    if let Ok(hash_set) = &h.join() {
-                                        Self::pmap_receive(hash_set, g_ptr, &mut states_map, &mut pmap_iter_queue, &self.blocker);
-                                    },
+                                Recorder4P::B4P(b) => {
+                                    let (recorder, stats) = &join_receiver(b)?;
+                                    capture_stats.merge(stats);
+                                    Self::pmap_receive(recorder, g_ptr, &mut states_map, &mut pmap_iter_queue, &self.blocker)
+                                }
+                                Recorder4P::H4P(h) => {
+                                    let (recorder, stats) = &join_receiver(h)?;
+                                    capture_stats.merge(stats);
+                                    Self::pmap_receive(recorder, g_ptr, &mut states_map, &mut pmap_iter_queue, &self.blocker)
+                                }
                             }
                         }       // 如果 获取概率相关图的可变指针失败
-                        None => { error!("{}", SYS.get_info("err", "get_graph_arc_failed")); exit(1) }
+                        None => return Err(RunError::thread("get_graph_arc_failed").into()),
                     }
                 }
 
@@ -260,14 +288,14 @@ impl ModeMethod for PmapV4 {
                 if self.pmap_allow_graph_iter {
                     match Arc::get_mut(&mut graph) {
                         Some(g_ptr) => {                 // 使用 开放端口列表 对 概率相关图 进行更新
-                            let (ip_count, pair_count) = Self::recommend_scan_output_train(g_ptr, pmap_iter_queue, &mut out_mod, &self.blocker);
+                            let (ip_count, pair_count) = Self::recommend_scan_output_train(g_ptr, pmap_iter_queue, &mut out_mod, &self.blocker)?;
                             total_ip_count += ip_count;
                             total_pair_count += pair_count;
                         }
-                        None => { error!("{}", SYS.get_info("err", "get_graph_arc_failed")); exit(1) }
+                        None => return Err(RunError::thread("get_graph_arc_failed").into()),
                     }
                 } else {
-                    let (ip_count, pair_count) = Self::recommend_scan_output(pmap_iter_queue, &mut out_mod, &self.blocker);
+                    let (ip_count, pair_count) = Self::recommend_scan_output(pmap_iter_queue, &mut out_mod, &self.blocker)?;
                     total_ip_count += ip_count;
                     total_pair_count += pair_count;
                 }
@@ -275,13 +303,19 @@ impl ModeMethod for PmapV4 {
             // 清理 概率相关图
             drop(graph);
         }
+        // 停止 监视线程, 输出最终进度
+        drop(monitor);
+
         // 探测 和 接收 执行完毕
-        println!("{} {} {} {}", SYS.get_info("print", "send_finished"), total_send_success, total_send_failed, total_blocked);
-        println!("{} {} {}", SYS.get_info("print", "pmap_scan_finished"), total_ip_count, total_pair_count);
+        eprintln!("{} {} {} {}", SYS.get_info("print", "send_finished"), total_send_success, total_send_failed, total_blocked);
+        eprintln!("{} {} {}", SYS.get_info("print", "pmap_scan_finished"), total_ip_count, total_pair_count);
         computing_time!(start_time; end_time, running_time);
 
         write_to_summary!(self; "PmapV4"; "result";
-            [start_time, end_time, running_time, total_send_success, total_send_failed, total_blocked, total_ip_count, total_pair_count;]
+            [start_time, end_time, running_time, total_send_success, total_send_failed, total_blocked, total_ip_count, total_pair_count;];
+            #[capture_stats;; ("pcap_received", received), ("pcap_dropped", dropped), ("pcap_if_dropped", if_dropped)]
         );
+
+        Ok(())
     }
 }
\ No newline at end of file
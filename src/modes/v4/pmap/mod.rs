@@ -2,11 +2,12 @@ use std::sync::Arc;
 use crate::core::conf::set_conf::base_conf::BaseConf;
 use crate::core::conf::set_conf::receiver_conf::ReceiverBaseConf;
 use crate::core::conf::set_conf::sender_conf::SenderBaseConf;
-use crate::modes::Helper;
+use crate::modes::{Helper, ModeReg};
 use crate::modules::probe_modules::probe_mod_v4::ProbeModV4;
 use crate::modules::target_iterators::CycleIpv4;
 use crate::SYS;
 use crate::tools::blocker::ipv4_blocker::BlackWhiteListV4;
+use crate::core::conf::modules_config::ArgSpec;
 
 mod execute;
 mod new;
@@ -58,4 +59,27 @@ impl Helper for PmapV4 {
     fn print_help() -> String {
         SYS.get_info("help", "PmapV4")
     }
-}
\ No newline at end of file
+}
+
+
+impl PmapV4 {
+
+    /// 模式注册信息
+    pub const MODE:ModeReg = ModeReg {
+        name: "p4",
+        aliases: &["pmap_v4"],
+        help: Some(PmapV4::print_help),
+        args: &[
+            ArgSpec::int("pmap_budget").range(1.0, u32::MAX as f64),
+            ArgSpec::int("pmap_batch_num").min(1.0),
+            ArgSpec::bool("pmap_allow_graph_iter"),
+            ArgSpec::bool("pmap_use_hash_recorder"),
+            ArgSpec::float("pmap_sampling_pro").range(0.0, 1.0),
+            ArgSpec::int("pmap_min_sample_num").min(0.0),
+            ArgSpec::int("pmap_port_num_limit").min(1.0),
+        ],
+        listed: true,
+        new: |args, conf| Ok(Box::new(PmapV4::new(args, conf)?)),
+        init: (),
+    };
+}
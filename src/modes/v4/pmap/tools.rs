@@ -1,28 +1,26 @@
 use std::net::Ipv4Addr;
-use std::process::exit;
 use std::sync::Arc;
 use ahash::AHashMap;
-use log::error;
 use crate::core::conf::tools::args_parse::target_iterator::TarIterBaseConf;
 use crate::modes::v4::PmapV4;
 use crate::modules::output_modules::OutputMethod;
 use crate::modules::target_iterators::{CycleIpv4, Ipv4Iter, PmapGraph, PmapIterV4, PmapState};
-use crate::{init_var, SYS};
+use crate::init_var;
+use crate::core::errors::{ConfError, OutputError, SmapError, TargetError};
 use crate::tools::blocker::ipv4_blocker::BlackWhiteListV4;
 use crate::tools::check_duplicates::{ExtractActPortsV4, NotMarkedV4Port};
 
 
 impl PmapV4 {
 
-    pub fn get_sample_last_index(tar_ip_num:u64, p_sub_one:u64, sampling_pro:f64, min_sample_num:u64) -> u64 {
+    pub fn get_sample_last_index(tar_ip_num:u64, p_sub_one:u64, sampling_pro:f64, min_sample_num:u64) -> Result<u64, ConfError> {
 
         // 采样比例 小于等于 0  或  大于 1 均为非法
         if sampling_pro <= 0.0 ||  1.0 < sampling_pro {
-            error!("{}", SYS.get_info("err", "sampling_pro_invalid"));
-            exit(1)
+            return Err(ConfError::invalid("sampling_pro_invalid"))
         }
 
-        if sampling_pro > 0.99 {
+        Ok(if sampling_pro > 0.99 {
             // 当 采样比例 大于 0.99 时, 直接 取消端口推荐, 对 所有端口对 进行探测
             // 注意这里的最终索引为:  p-1
             p_sub_one
@@ -46,11 +44,11 @@ impl PmapV4 {
 
                 if last_index < 1 { 1 } else { last_index }
             }
-        }
+        })
     }
 
     pub fn full_scan_output<T: Ipv4Iter, B:ExtractActPortsV4>(mut iter:T, res:&B, blocker:&BlackWhiteListV4,
-                                                    out_mod:&mut Box<dyn OutputMethod>) -> (usize, usize) {
+                                                    out_mod:&mut Box<dyn OutputMethod>) -> Result<(usize, usize), OutputError> {
 
         // 存在活跃端口的ip计数
         let mut ip_count:usize = 0;
@@ -94,16 +92,16 @@ impl PmapV4 {
                 }
 
                 // 关闭输出
-                out_mod.close_output();
+                out_mod.close_output()?;
 
-                return (ip_count, pair_count)
+                return Ok((ip_count, pair_count))
             }
         }
     }
 
 
     pub fn full_scan_output_and_train<T: Ipv4Iter, B:ExtractActPortsV4>(mut iter:T, res:&B, blocker:&BlackWhiteListV4,
-                                                              out_mod:&mut Box<dyn OutputMethod>, graph:&mut PmapGraph)  -> (usize, usize){
+                                                              out_mod:&mut Box<dyn OutputMethod>, graph:&mut PmapGraph)  -> Result<(usize, usize), OutputError> {
 
         // 存在活跃端口的ip计数
         let mut ip_count:usize = 0;
@@ -149,18 +147,18 @@ impl PmapV4 {
                 }
 
                 // 关闭输出
-                out_mod.close_output();
+                out_mod.close_output()?;
 
                 // 生成 绝对概率表
                 graph.update_end();
 
-                return (ip_count, pair_count)
+                return Ok((ip_count, pair_count))
             }
         }
     }
 
 
-    pub fn create_pmap4_iter_queue(pre_last: u64, end_index: u64, thread_num: u64, guide_iter:&CycleIpv4) -> Vec<PmapIterV4> {
+    pub fn create_pmap4_iter_queue(pre_last: u64, end_index: u64, thread_num: u64, guide_iter:&CycleIpv4) -> Result<Vec<PmapIterV4>, TargetError> {
 
         // 创建 pmap_v4 迭代器队列
         let mut pmap_iter_queue = Vec::new();
@@ -171,10 +169,10 @@ impl PmapV4 {
         for target_range in recommend_scan_tar_ranges {
 
             // 初始化 局部引导迭代器
-            let local_guide_iter = guide_iter.init(target_range.0, target_range.1);
+            let local_guide_iter = guide_iter.init(target_range.0, target_range.1)?;
             pmap_iter_queue.push(PmapIterV4::new(target_range.2 as usize, local_guide_iter));
         }
-        pmap_iter_queue
+        Ok(pmap_iter_queue)
     }
 
 
@@ -224,7 +222,7 @@ impl PmapV4 {
         }
     }
 
-    pub fn recommend_scan_output_train(g_ptr:&mut PmapGraph, pmap_iter_queue:Vec<PmapIterV4>, out_mod:&mut Box<dyn OutputMethod>, blocker:&BlackWhiteListV4) -> (usize, usize){
+    pub fn recommend_scan_output_train(g_ptr:&mut PmapGraph, pmap_iter_queue:Vec<PmapIterV4>, out_mod:&mut Box<dyn OutputMethod>, blocker:&BlackWhiteListV4) -> Result<(usize, usize), SmapError> {
 
         init_var!(usize; 0; ip_count, pair_count);
         for pmap_iter in pmap_iter_queue.into_iter() {
@@ -264,7 +262,7 @@ impl PmapV4 {
 
                             // 注意: ip_struct 和 有效ip的索引永远保持一致
                             // 如果出现无法获取到 ip_struct 的情况, 说明出现错误
-                            None => { error!("{}", SYS.get_info("err", "get_ip_struct_failed")); exit(1) }
+                            None => return Err(TargetError::invalid("get_ip_struct_failed").into()),
                         }
                     }
 
@@ -299,7 +297,7 @@ impl PmapV4 {
 
                             // 注意: ip_struct 和 有效ip的索引永远保持一致
                             // 如果出现无法获取到 ip_struct 的情况, 说明出现错误
-                            None => { error!("{}", SYS.get_info("err", "get_ip_struct_failed")); exit(1) }
+                            None => return Err(TargetError::invalid("get_ip_struct_failed").into()),
                         }
                     }
                     break 'cur_iter
@@ -308,15 +306,15 @@ impl PmapV4 {
         }
 
         // 关闭输出
-        out_mod.close_output();
+        out_mod.close_output()?;
         // 生成 绝对概率表
         g_ptr.update_end();
 
-        (ip_count, pair_count)
+        Ok((ip_count, pair_count))
     }
 
 
-    pub fn recommend_scan_output(pmap_iter_queue:Vec<PmapIterV4>, out_mod:&mut Box<dyn OutputMethod>, blocker:&BlackWhiteListV4) -> (usize, usize){
+    pub fn recommend_scan_output(pmap_iter_queue:Vec<PmapIterV4>, out_mod:&mut Box<dyn OutputMethod>, blocker:&BlackWhiteListV4) -> Result<(usize, usize), SmapError> {
 
         init_var!(usize; 0; ip_count, pair_count);
         for pmap_iter in pmap_iter_queue.into_iter() {
@@ -353,7 +351,7 @@ impl PmapV4 {
 
                             // 注意: ip_struct 和 有效ip的索引永远保持一致
                             // 如果出现无法获取到 ip_struct 的情况, 说明出现错误
-                            None => { error!("{}", SYS.get_info("err", "get_ip_struct_failed")); exit(1) }
+                            None => return Err(TargetError::invalid("get_ip_struct_failed").into()),
                         }
                     }
 
@@ -385,7 +383,7 @@ impl PmapV4 {
 
                             // 注意: ip_struct 和 有效ip的索引永远保持一致
                             // 如果出现无法获取到 ip_struct 的情况, 说明出现错误
-                            None => { error!("{}", SYS.get_info("err", "get_ip_struct_failed")); exit(1) }
+                            None => return Err(TargetError::invalid("get_ip_struct_failed").into()),
                         }
                     }
                     break 'cur_iter
@@ -393,8 +391,8 @@ impl PmapV4 {
             }
         }
         // 关闭输出
-        out_mod.close_output();
-        (ip_count, pair_count)
+        out_mod.close_output()?;
+        Ok((ip_count, pair_count))
     }
 
 }
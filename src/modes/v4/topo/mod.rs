@@ -2,11 +2,12 @@ use std::sync::Arc;
 use crate::core::conf::set_conf::base_conf::BaseConf;
 use crate::core::conf::set_conf::receiver_conf::ReceiverBaseConf;
 use crate::core::conf::set_conf::sender_conf::SenderBaseConf;
-use crate::modes::Helper;
+use crate::modes::{Helper, ModeReg};
 use crate::modules::probe_modules::topology_probe::topo_mod_v4::TopoModV4;
 use crate::modules::target_iterators::CycleIpv4Pattern;
 use crate::SYS;
 use crate::tools::blocker::ipv4_blocker::BlackWhiteListV4;
+use crate::core::conf::modules_config::ArgSpec;
 
 mod new;
 mod execute;
@@ -42,3 +43,21 @@ impl Helper for Topo4 {
     }
 }
 
+
+impl Topo4 {
+
+    /// 模式注册信息
+    pub const MODE:ModeReg = ModeReg {
+        name: "t4",
+        aliases: &["topo_v4"],
+        help: Some(Topo4::print_help),
+        args: &[
+            ArgSpec::int("topo4_rand_bits").range(0.0, 32.0),
+            ArgSpec::int("topo_max_ttl").range(1.0, 64.0),
+            ArgSpec::str("topo_sub_probe_v4").unset(),
+        ],
+        listed: true,
+        new: |args, conf| Ok(Box::new(Topo4::new(args, conf)?)),
+        init: (),
+    };
+}
@@ -1,20 +1,18 @@
 use std::net::Ipv4Addr;
-use std::process::exit;
 use std::sync::Arc;
-use log::error;
 use rand::prelude::StdRng;
 use rand::Rng;
 use crate::core::conf::modules_config::ModuleConf;
 use crate::core::conf::tools::args_parse::ip::ipv4_pattern::{parse_ipv4_pattern, parse_pattern_local_range_v4};
 use crate::modes::v4::Topo4;
 use crate::modules::probe_modules::topology_probe::topo_mod_v4::TopoModV4;
-use crate::SYS;
+use crate::core::errors::{ConfError, ProbeError};
 
 impl Topo4 {
-    pub fn topo4_get_target_ips(tar_ips_str: &String, rand_bits: String, rng: &mut StdRng) -> (u32, u32, u32, Vec<(u32, u32)>, u32) {
+    pub fn topo4_get_target_ips(tar_ips_str: &String, rand_bits: String, rng: &mut StdRng) -> Result<(u32, u32, u32, Vec<(u32, u32)>, u32), ConfError> {
         
         if tar_ips_str.contains("@") {
-            let (ip_bits_num, mut base_ip_val, mask, parts, mut max_ip) = parse_ipv4_pattern(tar_ips_str);
+            let (ip_bits_num, mut base_ip_val, mask, parts, mut max_ip) = parse_ipv4_pattern(tar_ips_str)?;
 
             if rand_bits != "0" {
                 // 如果 rand_bits 为 0, 表示不存在指定的随机位
@@ -24,7 +22,7 @@ impl Topo4 {
                 let rand_ip_val: u32 = rng.random();
 
                 // 生成真正的随机值
-                let rand_mask = Self::get_rand_mask(rand_bits, mask);
+                let rand_mask = Self::get_rand_mask(rand_bits, mask)?;
                 let rand_val = rand_ip_val & rand_mask;
 
                 // 获取随机处理后的 base_ip 和 max_ip
@@ -32,7 +30,7 @@ impl Topo4 {
                 max_ip = (max_ip & (!rand_mask)) | rand_val;
             }
 
-            (ip_bits_num, base_ip_val, mask, parts, max_ip)
+            Ok((ip_bits_num, base_ip_val, mask, parts, max_ip))
         } else {
             // 如果未给定目标ip
             // 生成随机ip值
@@ -44,19 +42,18 @@ impl Topo4 {
     }
 
     /// 获取随机位掩码， 注意：随机位为1， 非随机位为0
-    fn get_rand_mask(rand_bits: String, mask: u32) -> u32 {
+    fn get_rand_mask(rand_bits: String, mask: u32) -> Result<u32, ConfError> {
         let s: Vec<&str> = rand_bits.split(',').collect();
 
         let mut rand_mask: u32 = u32::MAX;
         let mut pre_last: u32 = 0;
         for ps in s {   // 每个片段
 
-            let (first, last) = parse_pattern_local_range_v4(ps);
+            let (first, last) = parse_pattern_local_range_v4(ps)?;
 
             if pre_last >= first {
                 // 当前片段的首索引 必须 大于上一个片段的 尾索引
-                error!("{} {}", SYS.get_info("err", "ipv4_pattern_local_part_err"), ps);
-                exit(1)
+                return Err(ConfError::invalid("ipv4_pattern_local_part_err").with(ps))
             } else {
                 pre_last = last;
             }
@@ -93,16 +90,16 @@ impl Topo4 {
             rand_mask = rand_mask & cur_mask;
         }
 
-        (!rand_mask) & mask
+        Ok((!rand_mask) & mask)
     }
     
     
-    pub fn get_sub_probe(para:&str, mod_conf:ModuleConf) -> Option<Arc<TopoModV4>> {
+    pub fn get_sub_probe(para:&str, mod_conf:ModuleConf) -> Result<Option<Arc<TopoModV4>>, ProbeError> {
         
         match mod_conf.get_info(&para.to_string()) {
-            None => None,
+            None => Ok(None),
             Some(name) => {
-                Some(Arc::new(TopoModV4::new(&name, mod_conf)))
+                Ok(Some(Arc::new(TopoModV4::new(&name, mod_conf)?)))
             }
         }
         
@@ -1,8 +1,7 @@
-use std::process::exit;
 use std::thread;
 use ahash::AHashSet;
 use chrono::Local;
-use log::{error, info};
+use log::info;
 use crate::{computing_time, creat_channels, ending_the_receiving_thread, init_var, prepare_data, recv_ready, wait_sender_threads, write_to_summary, SYS};
 use crate::core::receiver::pcap::PcapReceiver;
 use crate::core::sender::send_v6_code_port_vec;
@@ -10,9 +9,12 @@ use crate::modes::ModeMethod;
 use crate::modes::v6::aliased_prefixes_check::IPv6AliasedCheck;
 use crate::modules::output_modules::OutputMod;
 use crate::tools::others::split::split_chains;
+use crate::core::errors::SmapError;
+use crate::core::receiver::join_receiver;
+use crate::core::receiver::pcap::source::CaptureStats;
 
 impl ModeMethod for IPv6AliasedCheck {
-    fn execute(&self) {
+    fn execute(&self) -> Result<(), SmapError> {
 
         // 初始化别名前缀检查器
         let mut checker = self.ipv6_aliased_checker.clone();
@@ -23,12 +25,14 @@ impl ModeMethod for IPv6AliasedCheck {
         let mut alia_prefixes:Vec<u128> = Vec::with_capacity(((total_prefixes_len as f64) * 0.01) as usize);
 
         // 初始化 输出模块
-        let mut out_mod = OutputMod::init(&self.receiver_conf.output_v6);
+        let mut out_mod = OutputMod::init(&self.receiver_conf.output_v6)?;
         out_mod.writer_line(&vec![String::from("ipv6_aliased_prefixes")]);
 
         // 记录 开始发送的时间
         let start_time = Local::now();
         init_var!(u64; 0; total_send_success, total_send_failed, toal_act_addrs_len);
+        // 各扫描阶段 捕获统计 之和
+        let mut capture_stats = CaptureStats::default();
         'scan:loop {
             // 生成探测目标
             let cur_targets = checker.gen_targets();
@@ -54,7 +58,7 @@ impl ModeMethod for IPv6AliasedCheck {
             }
 
             // 只有接收线程准备完毕后，发送线程才能继续执行
-            recv_ready!(recv_ready_receiver);
+            recv_ready!(recv_ready_receiver; receiver_res);
 
             // 执行发送线程
             let tar_addrs_list = split_chains(cur_targets, self.sender_conf.send_thread_num);
@@ -77,15 +81,15 @@ impl ModeMethod for IPv6AliasedCheck {
             ending_the_receiving_thread!(self; recv_close_time_sender);
 
             // 等待接收线程按照预定时间关闭
-            if let Ok((act_addrs_len, recorder)) = receiver_res.join() {
-                // 处理接收线程返回的信息
-                
-                // 分析, 收集, 输出  别名前缀
-                checker.get_alia_prefixes(recorder, &mut alia_prefixes, &mut out_mod);
-                
-                // 记录 唯一活跃地址数量
-                toal_act_addrs_len += act_addrs_len as u64;
-            } else { error!("{}", SYS.get_info("err", "recv_thread_err"));exit(1) }
+            let (act_addrs_len, recorder, stats) = join_receiver(receiver_res)?;
+            capture_stats.merge(&stats);
+            // 处理接收线程返回的信息
+            
+            // 分析, 收集, 输出  别名前缀
+            checker.get_alia_prefixes(recorder, &mut alia_prefixes, &mut out_mod);
+            
+            // 记录 唯一活跃地址数量
+            toal_act_addrs_len += act_addrs_len as u64;
             
             // 改变标识字段
             checker.change_scan_flag();
@@ -113,12 +117,14 @@ impl ModeMethod for IPv6AliasedCheck {
         }
         
         // 输出模块关闭输出, 刷新缓冲区
-        out_mod.close_output();
+        out_mod.close_output()?;
 
         info!("{} {} {} {} {} {} {}", SYS.get_info("info", "alia_checker_recv_finished"),
                     aliased_prefixes_len, total_prefixes_len, aliased_addrs_len, total_send_success, total_send_failed, toal_act_addrs_len);
 
-        write_to_summary!(self; "IPv6AliasedChecker"; "result"; [start_time, end_time, running_time, total_send_success, total_send_failed, aliased_prefixes_len, total_prefixes_len, toal_act_addrs_len;]);
-        
+        write_to_summary!(self; "IPv6AliasedChecker"; "result"; [start_time, end_time, running_time, total_send_success, total_send_failed, aliased_prefixes_len, total_prefixes_len, toal_act_addrs_len;];
+            #[capture_stats;; ("pcap_received", received), ("pcap_dropped", dropped), ("pcap_if_dropped", if_dropped)]);
+
+        Ok(())
     }
 }
\ No newline at end of file
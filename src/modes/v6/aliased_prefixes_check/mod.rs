@@ -2,10 +2,11 @@ use std::sync::Arc;
 use crate::core::conf::set_conf::base_conf::BaseConf;
 use crate::core::conf::set_conf::receiver_conf::ReceiverBaseConf;
 use crate::core::conf::set_conf::sender_conf::SenderBaseConf;
-use crate::modes::Helper;
+use crate::modes::{Helper, ModeReg};
 use crate::modules::probe_modules::active_probe_ipv6_code::CodeProbeModV6;
 use crate::modules::target_iterators::IPv6AliaChecker;
 use crate::SYS;
+use crate::core::conf::modules_config::ArgSpec;
 
 mod execute;
 mod new;
@@ -36,4 +37,27 @@ impl Helper for IPv6AliasedCheck {
     fn print_help() -> String {
         SYS.get_info("help", "IPv6AliasedCheck")
     }
-}
\ No newline at end of file
+}
+
+
+impl IPv6AliasedCheck {
+
+    /// 模式注册信息
+    pub const MODE:ModeReg = ModeReg {
+        name: "ac6",
+        aliases: &["ipv6_aliased_prefixes_check"],
+        help: Some(IPv6AliasedCheck::print_help),
+        args: &[
+            ArgSpec::int("prefix_len").range(0.0, 127.0),
+            ArgSpec::int("prefix_count").min(1.0),
+            ArgSpec::int("rand_addr_len").min(1.0),
+            ArgSpec::float("aliased_threshold").range(0.0, 1.0),
+            ArgSpec::bool("output_alia_addrs"),
+            ArgSpec::int("prefixes_len_per_batch").min(1.0),
+            ArgSpec::str("not_aliased_records_path"),
+        ],
+        listed: true,
+        new: |args, conf| Ok(Box::new(IPv6AliasedCheck::new(args, conf)?)),
+        init: (),
+    };
+}
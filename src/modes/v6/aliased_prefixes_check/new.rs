@@ -1,4 +1,5 @@
 use crate::core::conf::args::Args;
+use crate::core::conf::set_conf::ScanConf;
 use crate::core::conf::modules_config::ModuleConf;
 use crate::core::conf::set_conf::base_conf::BaseConf;
 use crate::core::conf::set_conf::receiver_conf::ReceiverBaseConf;
@@ -8,40 +9,44 @@ use crate::{get_conf_from_mod_or_sys, write_to_summary};
 use crate::modes::v6::aliased_prefixes_check::IPv6AliasedCheck;
 use crate::modules::probe_modules::active_probe_ipv6_code::CodeProbeModV6;
 use crate::modules::target_iterators::IPv6AliaChecker;
+use crate::core::errors::SmapError;
 
 impl IPv6AliasedCheck {
 
 
-    pub fn new(args:&Args) -> Self {
+    pub fn new(args:&Args, conf:&ScanConf) -> Result<Self, SmapError> {
 
         // 基础配置
-        let base_conf = BaseConf::new(args);
+        let base_conf = BaseConf::new(&conf.base)?;
 
         // 解析自定义参数   注意: 这里将编码长度设为四个字节
-        let module_conf = ModuleConf::new_from_vec_args(&args.custom_args, vec!["payload_len=4".to_string()]);
+        let module_conf = ModuleConf::new_from_vec_args(&args.custom_args, vec!["payload_len=4".to_string()])?;
+        // 按 模式 和 探测模块 声明的参数说明 检查自定义参数
+        let probe_name = SenderBaseConf::parse_probe_v6(&args.probe_v6, "default_code_probe_mod_v6");
+        let mod_args = module_conf.check(&[Self::MODE.args, CodeProbeModV6::args(&probe_name)?])?;
         
         // 获取自定义参数: 前缀长度, 前缀数量, 每个前缀随机地址的数量, 占比多少会被计为别名前缀, 是否输出别名地址, 每轮次探测前缀的数量
         get_conf_from_mod_or_sys!(module_conf; prefix_len, prefix_count, rand_addr_len, aliased_threshold, output_alia_addrs, prefixes_len_per_batch, not_aliased_records_path);
         
         // 初始化 别名前缀检查器
-        let ipv6_aliased_checker = IPv6AliaChecker::new(TarIterBaseConf::parse_targets_file(&args.target_file), 
-                                                        prefix_len, prefix_count, rand_addr_len, aliased_threshold, prefixes_len_per_batch, not_aliased_records_path);
+        let ipv6_aliased_checker = IPv6AliaChecker::new(TarIterBaseConf::parse_targets_file(&args.target_file)?, 
+                                                        prefix_len, prefix_count, rand_addr_len, aliased_threshold, prefixes_len_per_batch, not_aliased_records_path)?;
         
         //  ipv6探测模块(区域编码)
         let probe = CodeProbeModV6::new(
-            &SenderBaseConf::parse_probe_v6(&args.probe_v6, "default_code_probe_mod_v6"), module_conf
-        );
+            &probe_name, module_conf
+        )?;
 
         // 发送模块基础配置
-        let sender_conf= SenderBaseConf::new(args, &base_conf.interface, None, None,
-                                             probe.max_packet_length_v6, false, true);
+        let sender_conf= SenderBaseConf::new(conf, &base_conf.interface, None, None,
+                                             probe.max_packet_length_v6, false, true)?;
 
         // 接收模块基础配置
-        let receiver_conf= ReceiverBaseConf::new(args, vec![probe.filter_v6.clone()]);
+        let receiver_conf= ReceiverBaseConf::new(conf, &base_conf, vec![probe.filter_v6.clone()])?;
 
-        write_to_summary!(base_conf; "IPv6AliasedChecker"; "args"; args;);
+        write_to_summary!(base_conf; "IPv6AliasedChecker"; "args"; args; mod_args;);
         
-        Self {
+        Ok(Self {
             base_conf: base_conf.into(),
             sender_conf: sender_conf.into(),
             receiver_conf: receiver_conf.into(),
@@ -49,7 +54,7 @@ impl IPv6AliasedCheck {
             
             ipv6_aliased_checker,
             output_alia_addrs,
-        }
+        })
         
     }
     
@@ -1,8 +1,7 @@
-use std::process::exit;
 use std::thread;
 use ahash::{AHashMap, AHashSet};
 use chrono::Local;
-use log::{error, info, trace};
+use log::{info, trace};
 use crate::modes::ModeMethod;
 use crate::modes::v6::asset6::Asset6;
 use crate::{computing_time, creat_channels, ending_the_receiving_thread, init_var, prepare_data, recv_ready, wait_sender_threads, write_to_summary, SYS};
@@ -11,22 +10,27 @@ use crate::core::sender::send_v6_code_port_vec;
 use crate::modules::output_modules::OutputMod;
 use crate::modules::target_iterators::{IPv6AliaChecker, IPv6PortSpaceTree};
 use crate::tools::others::split::split_chains;
+use crate::core::errors::SmapError;
+use crate::core::receiver::join_receiver;
+use crate::core::receiver::pcap::source::CaptureStats;
 
 impl ModeMethod for Asset6 {
-    fn execute(&self) {
+    fn execute(&self) -> Result<(), SmapError> {
 
         // 实例化空间树
         let mut space_tree = self.addr_port_space_tree.clone();
-        space_tree.init_density_tree();
+        space_tree.init_density_tree()?;
         info!("{}", SYS.get_info("info", "ipv6_space_tree_instantiation"));
 
         // 定义扫描开始时间
         let start_time = Local::now();
         // 定义 接收线程, 发送线程 消息; 已经使用的总预算
         init_var!(u64; 0; total_send_success, total_send_failed, total_active_num, total_used_budget, all_sent, all_failed);
+        // 各扫描阶段 捕获统计 之和
+        let mut capture_stats = CaptureStats::default();
 
         // 初始化 输出模块
-        let mut out_mod = OutputMod::init(&self.receiver_conf.output_v6);
+        let mut out_mod = OutputMod::init(&self.receiver_conf.output_v6)?;
         
         // 存放所有已知的别名前缀
         let mut aliased_prefixes:AHashSet<u64> = AHashSet::new();
@@ -80,7 +84,7 @@ impl ModeMethod for Asset6 {
             }
 
             // 只有接收线程准备完毕后，发送线程才能继续执行
-            recv_ready!(recv_ready_receiver);
+            recv_ready!(recv_ready_receiver; receiver_res);
 
             // 执行发送线程
             let tar_addrs_list = split_chains(targets, self.sender_conf.send_thread_num);
@@ -105,13 +109,10 @@ impl ModeMethod for Asset6 {
             ending_the_receiving_thread!(self; recv_close_time_sender);
 
             // 等待接收线程按照预定时间关闭
-            let cur_records = if let Ok((records, aliased_prefixes_, open_addrs_ports_)) = receiver_res.join() {
-                aliased_prefixes = aliased_prefixes_;
-                open_addrs_ports = open_addrs_ports_;
-                records
-            } else {
-                error!("{}", SYS.get_info("err", "recv_thread_err")); exit(1)
-            };
+            let (cur_records, aliased_prefixes_, open_addrs_ports_, stats) = join_receiver(receiver_res)?;
+            capture_stats.merge(&stats);
+            aliased_prefixes = aliased_prefixes_;
+            open_addrs_ports = open_addrs_ports_;
 
             // 如果记录不为空
             if !cur_records.is_empty() {
@@ -140,7 +141,7 @@ impl ModeMethod for Asset6 {
                     }
 
                     // 只有接收线程准备完毕后，发送线程才能继续执行
-                    recv_ready!(recv_ready_receiver);
+                    recv_ready!(recv_ready_receiver; aliased_check_receiver_res);
 
                     // 执行发送线程
                     let tar_addrs_list = split_chains(aliased_check_targets, self.sender_conf.send_thread_num);
@@ -163,23 +164,21 @@ impl ModeMethod for Asset6 {
                     ending_the_receiving_thread!(self; recv_close_time_sender);
 
                     // 等待接收线程按照预定时间关闭
-                    if let Ok(aliased_check_res) = aliased_check_receiver_res.join() {
-                        // 得到当前轮次的目标中对应的别名前缀
-                        let cur_aliased_prefixes = space_tree.parse_aliased_result(aliased_check_res, aliased_check_prefixes);
-                        // 根据别名前缀集合清理记录信息并更新空间树
-                        let (region_recorder, cur_act_num) = IPv6PortSpaceTree::clear_and_print_records(&cur_aliased_prefixes, &mut open_addrs_ports, self.max_port_num, cur_records, &mut out_mod, space_tree.cur_extra_region_num);
-                        // 将当前轮次的别名前缀向总别名前缀集合并
-                        aliased_prefixes.extend(cur_aliased_prefixes);
-
-                        info!("{} {}", SYS.get_info("info", "cur_active_num"),cur_act_num);
-                        total_active_num += cur_act_num;
-                        space_tree.update_tree(region_recorder);
-                    } else {
-                        error!("{}", SYS.get_info("err", "recv_thread_err")); exit(1)
-                    };
+                    let (aliased_check_res, stats) = join_receiver(aliased_check_receiver_res)?;
+                    capture_stats.merge(&stats);
+                    // 得到当前轮次的目标中对应的别名前缀
+                    let cur_aliased_prefixes = space_tree.parse_aliased_result(aliased_check_res, aliased_check_prefixes);
+                    // 根据别名前缀集合清理记录信息并更新空间树
+                    let (region_recorder, cur_act_num) = IPv6PortSpaceTree::clear_and_print_records(&cur_aliased_prefixes, &mut open_addrs_ports, self.max_port_num, cur_records, &mut out_mod, space_tree.cur_extra_region_num)?;
+                    // 将当前轮次的别名前缀向总别名前缀集合并
+                    aliased_prefixes.extend(cur_aliased_prefixes);
+
+                    info!("{} {}", SYS.get_info("info", "cur_active_num"),cur_act_num);
+                    total_active_num += cur_act_num;
+                    space_tree.update_tree(region_recorder);
                 } else {
                     // 如果不开启别名检查
-                    let (region_recorder, cur_act_num) = IPv6PortSpaceTree::print_records(cur_records, &mut open_addrs_ports, self.max_port_num, &mut out_mod, space_tree.cur_extra_region_num);
+                    let (region_recorder, cur_act_num) = IPv6PortSpaceTree::print_records(cur_records, &mut open_addrs_ports, self.max_port_num, &mut out_mod, space_tree.cur_extra_region_num)?;
                     info!("{} {}", SYS.get_info("info", "cur_active_num"),cur_act_num);
                     total_active_num += cur_act_num;
                     space_tree.update_tree(region_recorder);
@@ -206,15 +205,17 @@ impl ModeMethod for Asset6 {
         let hit_rate = (total_active_num as f64) / (total_send_success as f64);
 
         // 终端显示结果
-        println!("{} {}% {} {} {}", SYS.get_info("print", "ipv6_addrs_gen_finished"), hit_rate * 100.0, total_active_num, total_send_success, total_send_failed);
+        eprintln!("{} {}% {} {} {}", SYS.get_info("print", "ipv6_addrs_gen_finished"), hit_rate * 100.0, total_active_num, total_send_success, total_send_failed);
 
         // 计算命中率
         let a_hit_rate = (total_active_num as f64) / (all_sent as f64);
         trace!("{} {}% {} {} {}", SYS.get_info("print", "ipv6_addrs_gen_finished_con_aliased"), a_hit_rate * 100.0, total_active_num, all_sent, all_failed);
 
         write_to_summary!(self; "Asset6"; "result";
-            [start_time, end_time, running_time, hit_rate, total_active_num, total_send_success, total_send_failed;]
+            [start_time, end_time, running_time, hit_rate, total_active_num, total_send_success, total_send_failed;];
+            #[capture_stats;; ("pcap_received", received), ("pcap_dropped", dropped), ("pcap_if_dropped", if_dropped)]
         );
-        
+
+        Ok(())
     }
 }
\ No newline at end of file
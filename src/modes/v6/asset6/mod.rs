@@ -8,10 +8,11 @@ use std::sync::Arc;
 use crate::core::conf::set_conf::base_conf::BaseConf;
 use crate::core::conf::set_conf::receiver_conf::ReceiverBaseConf;
 use crate::core::conf::set_conf::sender_conf::SenderBaseConf;
-use crate::modes::Helper;
+use crate::modes::{Helper, ModeReg};
 use crate::modules::probe_modules::active_probe_ipv6_code::CodeProbeModV6;
 use crate::modules::target_iterators::IPv6PortSpaceTree;
 use crate::SYS;
+use crate::core::conf::modules_config::ArgSpec;
 
 
 
@@ -47,4 +48,34 @@ impl Helper for Asset6 {
     fn print_help() -> String {
         SYS.get_info("help", "Asset6")
     }
-}
\ No newline at end of file
+}
+
+
+impl Asset6 {
+
+    /// 模式注册信息
+    pub const MODE:ModeReg = ModeReg {
+        name: "a6",
+        aliases: &["asset6"],
+        help: Some(Asset6::print_help),
+        args: &[
+            ArgSpec::int("budget").min(1.0),
+            ArgSpec::int("batch_size").min(1.0),
+            ArgSpec::int("divide_dim").range(1.0, 32.0),
+            ArgSpec::int("max_leaf_size").min(1.0),
+            ArgSpec::bool("no_allow_gen_seeds"),
+            ArgSpec::float("learning_rate").range(0.0, 1.0),
+            ArgSpec::int("region_extraction_num").range(1.0, u32::MAX as f64),
+            ArgSpec::int("seeds_num").min(0.0),
+            ArgSpec::float("port_entropy_mul").min(0.0),
+            ArgSpec::float("aliased_threshold").range(0.0, 1.0),
+            ArgSpec::bool("no_allow_gen_seeds_from_file"),
+            ArgSpec::bool("aliased_prefixes_check"),
+            ArgSpec::str("aliased_prefixes_path"),
+            ArgSpec::int("max_port_num").range(1.0, 65535.0),
+        ],
+        listed: true,
+        new: |args, conf| Ok(Box::new(Asset6::new(args, conf)?)),
+        init: (),
+    };
+}
@@ -6,7 +6,7 @@ use std::sync::Arc;
 use crate::core::conf::set_conf::base_conf::BaseConf;
 use crate::core::conf::set_conf::receiver_conf::ReceiverBaseConf;
 use crate::core::conf::set_conf::sender_conf::SenderBaseConf;
-use crate::modes::{Helper};
+use crate::modes::{Helper, ModeReg};
 use crate::modules::probe_modules::probe_mod_v6::ProbeModV6;
 use crate::modules::target_iterators::{CycleIpv6Type};
 use crate::SYS;
@@ -38,3 +38,18 @@ impl  Helper  for CycleV6 {
         SYS.get_info("help", "CycleV6")
     }
 }
+
+
+impl CycleV6 {
+
+    /// 模式注册信息
+    pub const MODE:ModeReg = ModeReg {
+        name: "c6",
+        aliases: &["cycle_v6"],
+        help: Some(CycleV6::print_help),
+        args: &[],
+        listed: true,
+        new: |args, conf| Ok(Box::new(CycleV6::new(args, conf)?)),
+        init: (),
+    };
+}
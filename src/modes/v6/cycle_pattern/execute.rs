@@ -1,7 +1,5 @@
-use std::process::exit;
 use std::thread;
 use chrono::{Local};
-use log::error;
 use crate::core::receiver::pcap::PcapReceiver;
 use crate::core::sender::{send_v6, send_v6_port};
 use crate::modes::ModeMethod;
@@ -9,11 +7,14 @@ use crate::modes::v6::cycle_pattern::CycleV6Pattern;
 use crate::modules::target_iterators::CycleIpv6PatternType;
 use crate::{computing_time, creat_channels, ending_the_receiving_thread, init_var, prepare_data, recv_ready, SYS, wait_sender_threads, write_to_summary};
 use crate::tools::check_duplicates::bit_map::{BitMapV6Pattern, BitMapV6PatternPort};
+use crate::core::errors::SmapError;
+use crate::core::receiver::join_receiver;
+use crate::core::checkpoint::{RecvCheckpoint, SenderCheckpoint};
 
 impl ModeMethod for CycleV6Pattern {
 
     /// zmap_v6_pattern 执行函数
-    fn execute(&self){
+    fn execute(&self) -> Result<(), SmapError> {
 
         // 创建信息传递管道
         creat_channels!((recv_ready_sender, recv_ready_receiver, bool),(recv_close_time_sender, recv_close_time_receiver, i64));
@@ -27,9 +28,9 @@ impl ModeMethod for CycleV6Pattern {
             match &self.target_iter {
                 CycleIpv6PatternType::CycleIpv6Pattern(_) => {
                     receiver_res = thread::spawn(move || {
-                        let bit_map = BitMapV6Pattern::new(ip_bits_num, base_ip_val, mask, parts);
+                        let bit_map = BitMapV6Pattern::new(ip_bits_num, base_ip_val, mask, parts)?;
                         PcapReceiver::run_v6(0, base_conf, receiver_conf, probe, bit_map,
-                                                     recv_ready_sender, recv_close_time_receiver)
+                                                     recv_ready_sender, recv_close_time_receiver, RecvCheckpoint::none())
                     });
                 }
                 CycleIpv6PatternType::CycleIpv6PatternPort(t) => {
@@ -37,16 +38,16 @@ impl ModeMethod for CycleV6Pattern {
                     let tar_ports = t.tar_ports.clone();
 
                     receiver_res = thread::spawn(move || {
-                        let bit_map = BitMapV6PatternPort::new(ip_bits_num, base_ip_val, mask, parts, tar_ports);
+                        let bit_map = BitMapV6PatternPort::new(ip_bits_num, base_ip_val, mask, parts, tar_ports)?;
                         PcapReceiver::run_v6_port(0, base_conf, receiver_conf, probe, sports, bit_map,
-                                                          recv_ready_sender, recv_close_time_receiver)
+                                                          recv_ready_sender, recv_close_time_receiver, RecvCheckpoint::none())
                     });
                 }
             }
         }
 
         // 只有接收线程准备完毕后，发送线程才能继续执行
-        recv_ready!(recv_ready_receiver);
+        recv_ready!(recv_ready_receiver; receiver_res);
 
         // 记录 开始发送的时间
         let start_time = Local::now();
@@ -62,15 +63,15 @@ impl ModeMethod for CycleV6Pattern {
             match &self.target_iter {
                 CycleIpv6PatternType::CycleIpv6Pattern(t) => {
                     // 初始化 局部目标迭代器
-                    let target_iter = t.init(assigned_targets.0, assigned_targets.1);
+                    let target_iter = t.init(assigned_targets.0, assigned_targets.1)?;
                     sender_thread = thread::spawn(move || {
-                        send_v6(0, target_iter, 0, blocker, probe, ttl, base_conf, sender_conf)
+                        send_v6(0, target_iter, 0, blocker, probe, ttl, base_conf, sender_conf, SenderCheckpoint::none())
                     });
                 }
                 CycleIpv6PatternType::CycleIpv6PatternPort(t) => {
-                    let target_iter = t.init(assigned_targets.0, assigned_targets.1);
+                    let target_iter = t.init(assigned_targets.0, assigned_targets.1)?;
                     sender_thread = thread::spawn(move || {
-                        send_v6_port(0, target_iter, 0, blocker, probe, ttl, base_conf, sender_conf)
+                        send_v6_port(0, target_iter, 0, blocker, probe, ttl, base_conf, sender_conf, SenderCheckpoint::none())
                     });
                 }
             }
@@ -84,23 +85,24 @@ impl ModeMethod for CycleV6Pattern {
             total_send_failed += send_failed;
             total_blocked += blocked;
         });
-        println!("{} {} {} {}", SYS.get_info("print", "send_finished"), total_send_success, total_send_failed, total_blocked);
+        eprintln!("{} {} {} {}", SYS.get_info("print", "send_finished"), total_send_success, total_send_failed, total_blocked);
 
         // 计算终止时间 并向接收线程传递
         ending_the_receiving_thread!(self; recv_close_time_sender);
 
         // 等待接收线程按照预定时间关闭
-        if let Ok(receiver_info) = receiver_res.join() {
-            // 处理接收线程返回的信息
-
-            // 计算 结束时间 和 格式化后的运行时间 并显示
-            computing_time!(start_time; end_time, running_time);
-            println!("{} {} {} {}", SYS.get_info("print", "recv_finished_with_out_of_range"), receiver_info.recv_success, receiver_info.recv_repeat, receiver_info.recv_failed);
-
-            write_to_summary!(self; "CycleV6Pattern"; "result";
-                [start_time, end_time, running_time, total_send_success, total_send_failed, total_blocked;];
-                #[receiver_info; recv_success, recv_failed, recv_validation_passed, recv_validation_failed; ("receive_repeat_and_out_of_range", recv_repeat)]
-            );
-        } else { error!("{}", SYS.get_info("err", "recv_thread_err")); exit(1) }
+        let receiver_info = join_receiver(receiver_res)?;
+        // 处理接收线程返回的信息
+
+        // 计算 结束时间 和 格式化后的运行时间 并显示
+        computing_time!(start_time; end_time, running_time);
+        eprintln!("{} {} {} {}", SYS.get_info("print", "recv_finished_with_out_of_range"), receiver_info.recv_success, receiver_info.recv_repeat, receiver_info.recv_failed);
+
+        write_to_summary!(self; "CycleV6Pattern"; "result";
+            [start_time, end_time, running_time, total_send_success, total_send_failed, total_blocked;];
+            #[receiver_info; recv_success, recv_failed, recv_validation_passed, recv_validation_failed, pcap_received, pcap_dropped, pcap_if_dropped; ("receive_repeat_and_out_of_range", recv_repeat)]
+        );
+
+        Ok(())
     }
 }
\ No newline at end of file
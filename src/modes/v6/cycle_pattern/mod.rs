@@ -6,7 +6,7 @@ use std::sync::Arc;
 use crate::core::conf::set_conf::base_conf::BaseConf;
 use crate::core::conf::set_conf::receiver_conf::ReceiverBaseConf;
 use crate::core::conf::set_conf::sender_conf::SenderBaseConf;
-use crate::modes::{Helper};
+use crate::modes::{Helper, ModeReg};
 use crate::modules::probe_modules::probe_mod_v6::ProbeModV6;
 use crate::modules::target_iterators::{CycleIpv6PatternType};
 use crate::SYS;
@@ -39,4 +39,19 @@ impl  Helper  for CycleV6Pattern {
 
         SYS.get_info("help", "CycleV6Pattern")
     }
-}
\ No newline at end of file
+}
+
+
+impl CycleV6Pattern {
+
+    /// 模式注册信息
+    pub const MODE:ModeReg = ModeReg {
+        name: "c6p",
+        aliases: &["cycle_v6_pattern"],
+        help: Some(CycleV6Pattern::print_help),
+        args: &[],
+        listed: true,
+        new: |args, conf| Ok(Box::new(CycleV6Pattern::new(args, conf)?)),
+        init: (),
+    };
+}
@@ -2,9 +2,10 @@ use std::sync::Arc;
 use crate::core::conf::set_conf::base_conf::BaseConf;
 use crate::core::conf::set_conf::receiver_conf::ReceiverBaseConf;
 use crate::core::conf::set_conf::sender_conf::SenderBaseConf;
-use crate::modes::Helper;
+use crate::modes::{Helper, ModeReg};
 use crate::modules::probe_modules::topo_mod_v6::CodeTopoProbeModV6;
 use crate::SYS;
+use crate::core::conf::modules_config::ArgSpec;
 
 mod execute;
 mod new;
@@ -40,4 +41,23 @@ impl Helper for Edge6 {
     fn print_help() -> String {
         SYS.get_info("help", "Edge6")
     }
-}
\ No newline at end of file
+}
+
+
+impl Edge6 {
+
+    /// 模式注册信息
+    pub const MODE:ModeReg = ModeReg {
+        name: "e6",
+        aliases: &[],
+        help: Some(Edge6::print_help),
+        args: &[
+            ArgSpec::int("budget").min(1.0),
+            ArgSpec::int("batch_size").min(1.0),
+            ArgSpec::int("window_size").range(1.0, u32::MAX as f64),
+        ],
+        listed: true,
+        new: |args, conf| Ok(Box::new(Edge6::new(args, conf)?)),
+        init: (),
+    };
+}
@@ -6,7 +6,7 @@ use std::sync::Arc;
 use crate::core::conf::set_conf::base_conf::BaseConf;
 use crate::core::conf::set_conf::receiver_conf::ReceiverBaseConf;
 use crate::core::conf::set_conf::sender_conf::SenderBaseConf;
-use crate::modes::{Helper};
+use crate::modes::{Helper, ModeReg};
 use crate::modules::probe_modules::probe_mod_v6::ProbeModV6;
 use crate::modules::target_iterators::TargetFileReader;
 use crate::SYS;
@@ -39,4 +39,19 @@ impl  Helper  for V6FileReader {
 
         SYS.get_info("help", "V6FileReader")
     }
-}
\ No newline at end of file
+}
+
+
+impl V6FileReader {
+
+    /// 模式注册信息
+    pub const MODE:ModeReg = ModeReg {
+        name: "f6",
+        aliases: &["file_v6"],
+        help: Some(V6FileReader::print_help),
+        args: &[],
+        listed: true,
+        new: |args, conf| Ok(Box::new(V6FileReader::new(args, conf)?)),
+        init: (),
+    };
+}
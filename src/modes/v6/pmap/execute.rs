@@ -1,11 +1,9 @@
-use std::process::exit;
 use std::sync::Arc;
 use std::thread;
 use std::thread::JoinHandle;
 use ahash::AHashMap;
 use bitvec::macros::internal::funty::Fundamental;
 use chrono::Local;
-use log::error;
 use crate::{computing_time, creat_channels, ending_the_receiving_thread, init_var, prepare_data, recv_ready, SYS, wait_sender_threads, write_to_summary};
 use crate::core::conf::tools::args_parse::target_iterator::TarIterBaseConf;
 use crate::core::receiver::pcap::PcapReceiver;
@@ -16,25 +14,39 @@ use crate::modules::output_modules::OutputMod;
 use crate::modules::target_iterators::{PmapGraph, PmapState};
 use crate::tools::check_duplicates::bit_map::BitMapV6PatternPort;
 use crate::tools::check_duplicates::hash_set::HashSetV6Port;
+use crate::core::errors::{RunError, SmapError};
+use crate::core::receiver::{join_receiver, ReceiverHandle};
+use crate::core::receiver::pcap::source::CaptureStats;
 
 enum Recorder6P {
-    B6P(JoinHandle<BitMapV6PatternPort>),
-    H6P(JoinHandle<HashSetV6Port>)
+    B6P(JoinHandle<Result<(BitMapV6PatternPort, CaptureStats), SmapError>>),
+    H6P(JoinHandle<Result<(HashSetV6Port, CaptureStats), SmapError>>)
+}
+
+impl ReceiverHandle for Recorder6P {
+    fn join_err(self) -> Option<SmapError> {
+        match self {
+            Recorder6P::B6P(b) => b.join_err(),
+            Recorder6P::H6P(h) => h.join_err(),
+        }
+    }
 }
 
 impl ModeMethod for PmapV6 {
-    fn execute(&self) {
+    fn execute(&self) -> Result<(), SmapError> {
 
         // 定义 概率相关图
         let mut graph;
         // 初始化 输出模块
-        let mut out_mod = OutputMod::init(&self.receiver_conf.output_v6);
+        let mut out_mod = OutputMod::init(&self.receiver_conf.output_v6)?;
 
         // 定义 开始的时间
         let start_time;
         // 初始化全局 发送线程消息  接收线程消息
         init_var!(u64; 0; total_send_success, total_send_failed, total_blocked);
         init_var!(usize; 0; total_ip_count, total_pair_count);
+        // 各扫描阶段 捕获统计 之和
+        let mut capture_stats = CaptureStats::default();
 
         // 预扫描阶段
         {
@@ -57,7 +69,7 @@ impl ModeMethod for PmapV6 {
                     prepare_data!(self; clone; parts, tar_ports);
                     Recorder6P::B6P(thread::spawn(move || {
                         // 注意: 这里应该用 全部目标范围, 而不是只有预探测目标范围
-                        let bit_map = BitMapV6PatternPort::new(ip_bits_num, base_ip_val, mask, parts, tar_ports);
+                        let bit_map = BitMapV6PatternPort::new(ip_bits_num, base_ip_val, mask, parts, tar_ports)?;
                         PcapReceiver::pmap_full_scan_v6(0, base_conf, receiver_conf, probe, sports, bit_map,
                                                         recv_ready_sender, recv_close_time_receiver)
                     }))
@@ -65,7 +77,7 @@ impl ModeMethod for PmapV6 {
             };
 
             {   // 只有接收线程准备完毕后，发送线程才能继续执行
-                recv_ready!(recv_ready_receiver);
+                recv_ready!(recv_ready_receiver; full_scan_result);
 
                 // 获取 完全扫描的多线程任务分配列表
                 let full_scan_tar_ranges = TarIterBaseConf::cycle_group_assign_targets_u128(self.full_scan_last_index, self.sender_conf.send_thread_num as u128);
@@ -79,7 +91,7 @@ impl ModeMethod for PmapV6 {
                     // 发送线程 数据准备
                     prepare_data!(self; clone; blocker, base_conf, sender_conf, probe, tar_ports);
                     // 初始化 局部目标迭代器
-                    let target_iter = self.tar_iter_without_port.init(target_range.0, target_range.1);
+                    let target_iter = self.tar_iter_without_port.init(target_range.0, target_range.1)?;
 
                     let full_scan_sender = thread::spawn(move || {
                         pmap_full_scan_send_v6(0, target_iter, blocker, probe, tar_ports, base_conf, sender_conf)
@@ -98,52 +110,46 @@ impl ModeMethod for PmapV6 {
             }
 
             // 完全扫描全局迭代器
-            let full_scan_iter = self.tar_iter_without_port.init(1, self.full_scan_last_index);
+            let full_scan_iter = self.tar_iter_without_port.init(1, self.full_scan_last_index)?;
             match full_scan_result {
                 Recorder6P::B6P(b) => {
-                    match &b.join() {
-                        Ok(bit_map) => {
-                            if self.recommend_scan {     // 将完全扫描阶段的结果进行输出, 使用结果对概率相关图进行训练
-                                graph = Arc::new(PmapGraph::new(self.tar_ports.clone(), self.port_num_limit));
-                                match Arc::get_mut(&mut graph) {
-                                    Some(g_ptr) => {
-                                        let (ip_count, pair_count) = Self::full_scan_output_and_train(full_scan_iter, bit_map,  &self.blocker, &mut out_mod, g_ptr);
-                                        total_ip_count += ip_count;
-                                        total_pair_count += pair_count;
-                                    }
-                                    None => { error!("{}", SYS.get_info("err", "get_graph_arc_failed")); exit(1) }
-                                }
-                            } else {        // 将完全扫描阶段的结果进行输出
-                                let (ip_count, pair_count) = Self::full_scan_output(full_scan_iter, bit_map,  &self.blocker, &mut out_mod);
+                    let (bit_map, stats) = &join_receiver(b)?;
+                    capture_stats.merge(stats);
+                    if self.recommend_scan {     // 将完全扫描阶段的结果进行输出, 使用结果对概率相关图进行训练
+                        graph = Arc::new(PmapGraph::new(self.tar_ports.clone(), self.port_num_limit));
+                        match Arc::get_mut(&mut graph) {
+                            Some(g_ptr) => {
+                                let (ip_count, pair_count) = Self::full_scan_output_and_train(full_scan_iter, bit_map,  &self.blocker, &mut out_mod, g_ptr)?;
                                 total_ip_count += ip_count;
                                 total_pair_count += pair_count;
-                                graph = Arc::new(PmapGraph::new_void());
                             }
+                            None => return Err(RunError::thread("get_graph_arc_failed").into()),
                         }
-                        Err(_) => { error!("{}", SYS.get_info("err", "recv_thread_err")); exit(1) }
+                    } else {        // 将完全扫描阶段的结果进行输出
+                        let (ip_count, pair_count) = Self::full_scan_output(full_scan_iter, bit_map,  &self.blocker, &mut out_mod)?;
+                        total_ip_count += ip_count;
+                        total_pair_count += pair_count;
+                        graph = Arc::new(PmapGraph::new_void());
                     }
                 }
                 Recorder6P::H6P(h) => {
-                    match &h.join() {
-                        Ok(hash_set) => {
-                            if self.recommend_scan {     // 将完全扫描阶段的结果进行输出, 使用结果对概率相关图进行训练
-                                graph = Arc::new(PmapGraph::new(self.tar_ports.clone(), self.port_num_limit));
-                                match Arc::get_mut(&mut graph) {
-                                    Some(g_ptr) => {
-                                        let (ip_count, pair_count) = Self::full_scan_output_and_train(full_scan_iter, hash_set, &self.blocker, &mut out_mod, g_ptr);
-                                        total_ip_count += ip_count;
-                                        total_pair_count += pair_count;
-                                    }
-                                    None => { error!("{}", SYS.get_info("err", "get_graph_arc_failed")); exit(1) }
-                                }
-                            } else {        // 将完全扫描阶段的结果进行输出
-                                let (ip_count, pair_count) = Self::full_scan_output(full_scan_iter, hash_set,  &self.blocker, &mut out_mod);
+                    let (hash_set, stats) = &join_receiver(h)?;
+                    capture_stats.merge(stats);
+                    if self.recommend_scan {     // 将完全扫描阶段的结果进行输出, 使用结果对概率相关图进行训练
+                        graph = Arc::new(PmapGraph::new(self.tar_ports.clone(), self.port_num_limit));
+                        match Arc::get_mut(&mut graph) {
+                            Some(g_ptr) => {
+                                let (ip_count, pair_count) = Self::full_scan_output_and_train(full_scan_iter, hash_set, &self.blocker, &mut out_mod, g_ptr)?;
                                 total_ip_count += ip_count;
                                 total_pair_count += pair_count;
-                                graph = Arc::new(PmapGraph::new_void());
                             }
+                            None => return Err(RunError::thread("get_graph_arc_failed").into()),
                         }
-                        Err(_) => { error!("{}", SYS.get_info("err", "recv_thread_err")); exit(1) }
+                    } else {        // 将完全扫描阶段的结果进行输出
+                        let (ip_count, pair_count) = Self::full_scan_output(full_scan_iter, hash_set,  &self.blocker, &mut out_mod)?;
+                        total_ip_count += ip_count;
+                        total_pair_count += pair_count;
+                        graph = Arc::new(PmapGraph::new_void());
                     }
                 }
             }
@@ -165,7 +171,7 @@ impl ModeMethod for PmapV6 {
 
                 // 生成 pmap迭代器 队列
                 let mut pmap_iter_queue= Self::create_pmap6_iter_queue(
-                    batch_range.0-1, batch_range.1, self.sender_conf.send_thread_num as u128, &self.tar_iter_without_port);
+                    batch_range.0-1, batch_range.1, self.sender_conf.send_thread_num as u128, &self.tar_iter_without_port)?;
 
                 let mut sent_port_count_add_one:u32 = 0;
                 loop {
@@ -193,7 +199,7 @@ impl ModeMethod for PmapV6 {
                             prepare_data!(self; ip_bits_num, base_ip_val, mask);
                             prepare_data!(self; clone; parts, tar_ports);
                             Recorder6P::B6P(thread::spawn(move || {
-                                let bit_map = BitMapV6PatternPort::new(ip_bits_num, base_ip_val, mask, parts, tar_ports);
+                                let bit_map = BitMapV6PatternPort::new(ip_bits_num, base_ip_val, mask, parts, tar_ports)?;
                                 PcapReceiver::pmap_full_scan_v6(0, base_conf, receiver_conf, probe, sports, bit_map,
                                                                      recv_ready_sender, recv_close_time_receiver)
                             }))
@@ -201,7 +207,7 @@ impl ModeMethod for PmapV6 {
                     };
 
                     // 只有接收线程准备完毕后，发送线程才能继续执行
-                    recv_ready!(recv_ready_receiver);
+                    recv_ready!(recv_ready_receiver; recommend_scan_result);
 
                     // 执行 推荐扫描 发送线程
                     let mut recommend_scan_sender_threads = vec![];
@@ -239,17 +245,19 @@ impl ModeMethod for PmapV6 {
                     match Arc::get_mut(&mut graph) {
                         Some(g_ptr) => {
                             match recommend_scan_result {
-                                Recorder6P::B6P(b) =>
-                                    if let Ok(bit_map) = &b.join() {
-                                        Self::pmap_receive(bit_map, g_ptr, &mut states_map, &mut pmap_iter_queue, &self.blocker);
-                                    },
-                                Recorder6P::H6P(h) =>
-                                    if let Ok(hash_set) = &h.join() {
-                                        Self::pmap_receive(hash_set, g_ptr, &mut states_map, &mut pmap_iter_queue, &self.blocker);
-                                    },
+                                Recorder6P::B6P(b) => {
+                                    let (recorder, stats) = &join_receiver(b)?;
+                                    capture_stats.merge(stats);
+                                    Self::pmap_receive(recorder, g_ptr, &mut states_map, &mut pmap_iter_queue, &self.blocker)
+                                }
+                                Recorder6P::H6P(h) => {
+                                    let (recorder, stats) = &join_receiver(h)?;
+                                    capture_stats.merge(stats);
+                                    Self::pmap_receive(recorder, g_ptr, &mut states_map, &mut pmap_iter_queue, &self.blocker)
+                                }
                             }
                         }       // 如果 获取概率相关图的可变指针失败
-                        None => { error!("{}", SYS.get_info("err", "get_graph_arc_failed")); exit(1) }
+                        None => return Err(RunError::thread("get_graph_arc_failed").into()),
                     }
                 }
 
@@ -260,14 +268,14 @@ impl ModeMethod for PmapV6 {
                 if self.pmap_allow_graph_iter {
                     match Arc::get_mut(&mut graph) {
                         Some(g_ptr) => {                 // 使用 开放端口列表 对 概率相关图 进行更新
-                            let (ip_count, pair_count) = Self::recommend_scan_output_train(g_ptr, pmap_iter_queue, &mut out_mod, &self.blocker);
+                            let (ip_count, pair_count) = Self::recommend_scan_output_train(g_ptr, pmap_iter_queue, &mut out_mod, &self.blocker)?;
                             total_ip_count += ip_count;
                             total_pair_count += pair_count;
                         }
-                        None => { error!("{}", SYS.get_info("err", "get_graph_arc_failed"));  exit(1) }
+                        None => return Err(RunError::thread("get_graph_arc_failed").into()),
                     }
                 } else {
-                    let (ip_count, pair_count) = Self::recommend_scan_output(pmap_iter_queue, &mut out_mod, &self.blocker);
+                    let (ip_count, pair_count) = Self::recommend_scan_output(pmap_iter_queue, &mut out_mod, &self.blocker)?;
                     total_ip_count += ip_count;
                     total_pair_count += pair_count;
                 }
@@ -277,12 +285,15 @@ impl ModeMethod for PmapV6 {
         }
 
         // 探测 和 接收 执行完毕
-        println!("{} {} {} {}", SYS.get_info("print", "send_finished"), total_send_success, total_send_failed, total_blocked);
-        println!("{} {} {}", SYS.get_info("print", "pmap_scan_finished"), total_ip_count, total_pair_count);
+        eprintln!("{} {} {} {}", SYS.get_info("print", "send_finished"), total_send_success, total_send_failed, total_blocked);
+        eprintln!("{} {} {}", SYS.get_info("print", "pmap_scan_finished"), total_ip_count, total_pair_count);
         computing_time!(start_time; end_time, running_time);
 
         write_to_summary!(self; "PmapV6"; "result";
-            [start_time, end_time, running_time, total_send_success, total_send_failed, total_blocked, total_ip_count, total_pair_count;]
+            [start_time, end_time, running_time, total_send_success, total_send_failed, total_blocked, total_ip_count, total_pair_count;];
+            #[capture_stats;; ("pcap_received", received), ("pcap_dropped", dropped), ("pcap_if_dropped", if_dropped)]
         );
+
+        Ok(())
     }
 }
\ No newline at end of file
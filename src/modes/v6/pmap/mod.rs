@@ -2,11 +2,12 @@ use std::sync::Arc;
 use crate::core::conf::set_conf::base_conf::BaseConf;
 use crate::core::conf::set_conf::receiver_conf::ReceiverBaseConf;
 use crate::core::conf::set_conf::sender_conf::SenderBaseConf;
-use crate::modes::Helper;
+use crate::modes::{Helper, ModeReg};
 use crate::modules::probe_modules::probe_mod_v6::ProbeModV6;
 use crate::modules::target_iterators::{CycleIpv6Pattern};
 use crate::SYS;
 use crate::tools::blocker::ipv6_blocker::BlackWhiteListV6;
+use crate::core::conf::modules_config::ArgSpec;
 
 mod execute;
 mod new;
@@ -64,3 +65,25 @@ impl Helper for PmapV6 {
     }
 }
 
+
+impl PmapV6 {
+
+    /// 模式注册信息
+    pub const MODE:ModeReg = ModeReg {
+        name: "p6",
+        aliases: &["pmap_v6"],
+        help: Some(PmapV6::print_help),
+        args: &[
+            ArgSpec::int("pmap_budget").range(1.0, u32::MAX as f64),
+            ArgSpec::int("pmap_batch_num").min(1.0),
+            ArgSpec::bool("pmap_allow_graph_iter"),
+            ArgSpec::bool("pmap_use_hash_recorder"),
+            ArgSpec::float("pmap_sampling_pro").range(0.0, 1.0),
+            ArgSpec::int("pmap_min_sample_num").min(0.0),
+            ArgSpec::int("pmap_port_num_limit").min(1.0),
+        ],
+        listed: true,
+        new: |args, conf| Ok(Box::new(PmapV6::new(args, conf)?)),
+        init: (),
+    };
+}
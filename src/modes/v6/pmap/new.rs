@@ -1,41 +1,44 @@
-use std::process::exit;
-use log::error;
 use crate::core::conf::args::Args;
+use crate::core::conf::set_conf::ScanConf;
 use crate::core::conf::modules_config::ModuleConf;
 use crate::core::conf::set_conf::base_conf::BaseConf;
 use crate::core::conf::set_conf::sender_conf::SenderBaseConf;
 use crate::core::conf::tools::args_parse::ip::ipv6_binary_pattern::parse_ipv6_binary_pattern;
 use crate::core::conf::tools::args_parse::ip::ipv6_pattern::parse_ipv6_pattern;
 use crate::core::conf::tools::args_parse::target_iterator::TarIterBaseConf;
-use crate::{get_conf_from_mod_or_sys, SYS, write_to_summary};
+use crate::{get_conf_from_mod_or_sys, write_to_summary};
 use crate::core::conf::set_conf::receiver_conf::ReceiverBaseConf;
 use crate::modes::v6::pmap::PmapV6;
 use crate::modes::v6::PmapFileV6;
 use crate::modules::probe_modules::probe_mod_v6::ProbeModV6;
 use crate::modules::target_iterators::{CycleIpv6Pattern};
 use crate::tools::blocker::ipv6_blocker::BlackWhiteListV6;
+use crate::core::errors::{ProbeError, SmapError};
 
 impl PmapV6 {
 
 
-    pub fn new(args:&Args) -> Self {
+    pub fn new(args:&Args, conf:&ScanConf) -> Result<Self, SmapError> {
 
         // 获取 探测目标
-        let tar_ips_str = &TarIterBaseConf::parse_tar_ip(&args.tar_ips);
+        let tar_ips_str = &TarIterBaseConf::parse_tar_ip(&args.tar_ips)?;
         let (ip_bits_num, base_ip_val, mask, parts, max_ip) = if tar_ips_str.contains('@'){
-            parse_ipv6_pattern(tar_ips_str)  // 如果字符串中包含 @ 字符, 当作 一般模式字符串 处理
-        } else { parse_ipv6_binary_pattern(tar_ips_str) }; // 如果不包含 @ 字符, 当作二进制字符串处理
-        let tar_ports = TarIterBaseConf::parse_tar_port(&args.tar_ports, "pmap_default_ports");
-        let tar_ip_num = TarIterBaseConf::get_tar_ip_num_binary(ip_bits_num);
+            parse_ipv6_pattern(tar_ips_str)?  // 如果字符串中包含 @ 字符, 当作 一般模式字符串 处理
+        } else { parse_ipv6_binary_pattern(tar_ips_str)? }; // 如果不包含 @ 字符, 当作二进制字符串处理
+        let tar_ports = TarIterBaseConf::parse_tar_port(&args.tar_ports, "pmap_default_ports")?;
+        let tar_ip_num = TarIterBaseConf::get_tar_ip_num_binary(ip_bits_num)?;
 
         // 基础配置
-        let mut base_conf = BaseConf::new(args);
+        let mut base_conf = BaseConf::new(&conf.base)?;
 
         // 解析自定义参数    注意: 这里强制所有探测模块检查接收数据包的源端口
-        let module_conf = ModuleConf::new_from_vec_args(&args.custom_args, vec!["not_check_sport=false".to_string()]);
+        let module_conf = ModuleConf::new_from_vec_args(&args.custom_args, vec!["not_check_sport=false".to_string()])?;
+        // 按 模式 和 探测模块 声明的参数说明 检查自定义参数
+        let probe_name = SenderBaseConf::parse_probe_v6(&args.probe_v6, "pmap_default_probe_mod_v6");
+        let mod_args = module_conf.check(&[Self::MODE.args, ProbeModV6::args(&probe_name)?])?;
 
         // 创建 无端口目标迭代器
-        let tar_iter_without_port = CycleIpv6Pattern::new(ip_bits_num, base_ip_val, parts.clone(), &mut base_conf.aes_rand.rng);
+        let tar_iter_without_port = CycleIpv6Pattern::new(ip_bits_num, base_ip_val, parts.clone(), &mut base_conf.aes_rand.rng)?;
 
         // 从 自定义参数 或 系统配置 中读取 预算 和 推荐轮次, 是否允许概率相关图迭代
         get_conf_from_mod_or_sys!(module_conf; pmap_budget, pmap_batch_num, pmap_allow_graph_iter, 
@@ -43,24 +46,24 @@ impl PmapV6 {
 
         // 计算 完全扫描(预扫描)最终索引
         let full_scan_last_index = Self::get_sample_last_index(tar_ip_num, tar_iter_without_port.p_sub_one,
-                                                               pmap_sampling_pro, pmap_min_sample_num);
+                                                               pmap_sampling_pro, pmap_min_sample_num)?;
 
         // ipv6 探测模块
         let probe = ProbeModV6::new(
-            &SenderBaseConf::parse_probe_v6(&args.probe_v6, "pmap_default_probe_mod_v6"),
-            module_conf, &tar_ports, base_conf.aes_rand.seed, &args.fields);
+            &probe_name,
+            module_conf, &tar_ports, base_conf.aes_rand.seed, &args.fields)?;
 
         // 如果 目标探测模块不使用端口, 直接报错并退出
-        if !probe.use_tar_ports { error!("{}", SYS.get_info("err", "probe_not_use_ports")); exit(1) }
+        if !probe.use_tar_ports { return Err(ProbeError::invalid("probe_not_use_ports").into()) }
 
         // 如果 两者不等, 说明 不只有完全扫描; 如果 两者相等, 说明 只有完全扫描
         let recommend_scan = full_scan_last_index != tar_iter_without_port.p_sub_one;
 
         // 推测出的 抽样数量
-        let guess_sample_num = PmapFileV6::get_sample_num(tar_ip_num as usize, pmap_sampling_pro, pmap_min_sample_num as usize) as u64;
+        let guess_sample_num = PmapFileV6::get_sample_num(tar_ip_num as usize, pmap_sampling_pro, pmap_min_sample_num as usize)? as u64;
 
         // 发送模块基础配置
-        let sender_conf= SenderBaseConf::new(args, &base_conf.interface,
+        let sender_conf= SenderBaseConf::new(conf, &base_conf.interface,
                                              Some(if guess_sample_num < tar_ip_num {
                                                  guess_sample_num * (tar_ports.len() as u64) + (tar_ip_num - guess_sample_num) * (pmap_budget as u64)
                                              } else {
@@ -69,17 +72,17 @@ impl PmapV6 {
                                              if recommend_scan {
                                                     Some((pmap_batch_num as i64) * (pmap_budget as i64) + 1)
                                              } else { None },
-                                             probe.max_packet_length_v6, false, true);
+                                             probe.max_packet_length_v6, false, true)?;
 
         // 定义全局 黑白名单拦截器
-        let blocker = BlackWhiteListV6::new(&args.black_list_v6, &args.white_list_v6, false);
+        let blocker = BlackWhiteListV6::new(&args.black_list_v6, &args.white_list_v6, false)?;
 
         // 接收模块基础配置
-        let receiver_conf= ReceiverBaseConf::new(args, vec![probe.filter_v6.clone()]);
+        let receiver_conf= ReceiverBaseConf::new(conf, &base_conf, vec![probe.filter_v6.clone()])?;
 
-        write_to_summary!(base_conf; "PmapV6"; "args"; args;);
+        write_to_summary!(base_conf; "PmapV6"; "args"; args; mod_args;);
 
-        Self {
+        Ok(Self {
             base_conf: base_conf.into(),
 
             tar_iter_without_port,
@@ -103,6 +106,6 @@ impl PmapV6 {
             tar_ports,
             port_num_limit: pmap_port_num_limit,
             blocker: blocker.gen_local_constraints(base_ip_val, max_ip),
-        }
+        })
     }
 }
\ No newline at end of file
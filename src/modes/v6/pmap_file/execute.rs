@@ -1,9 +1,7 @@
-use std::process::exit;
 use std::sync::Arc;
 use std::thread;
 use ahash::AHashMap;
 use chrono::Local;
-use log::error;
 use crate::{computing_time, creat_channels, ending_the_receiving_thread, init_var, prepare_data, recv_ready, wait_sender_threads, write_to_summary, SYS};
 use crate::core::conf::tools::args_parse::ip::ipv6::get_ipv6_addrs_from_file;
 use crate::core::receiver::pcap::PcapReceiver;
@@ -14,14 +12,17 @@ use crate::modules::output_modules::OutputMod;
 use crate::modules::target_iterators::{PmapGraph, PmapState};
 use crate::tools::check_duplicates::hash_set::HashSetV6Port;
 use crate::tools::others::split::split_chains;
+use crate::core::errors::{RunError, SmapError, TargetError};
+use crate::core::receiver::join_receiver;
+use crate::core::receiver::pcap::source::CaptureStats;
 
 impl ModeMethod for PmapFileV6 {
-    fn execute(&self) {
+    fn execute(&self) -> Result<(), SmapError> {
 
         // 从 文件 中读取所有目标地址
         // 注意: 该地址列表经过完全随机化
         let mut tar_ips = get_ipv6_addrs_from_file(&self.path, None);
-        if tar_ips.is_empty() { error!("{}", SYS.get_info("err", "target_ips_not_exist")); exit(1) }
+        if tar_ips.is_empty() { return Err(TargetError::empty("target_ips_not_exist").with(&self.path).into()) }
 
         // 取出 总目标数量
         let tar_ip_num = tar_ips.len();
@@ -29,18 +30,20 @@ impl ModeMethod for PmapFileV6 {
         let tar_ports_num = self.tar_ports.len();
         
         // 计算 预扫描抽样数量
-        let sample_num = Self::get_sample_num(tar_ip_num, self.sampling_pro, self.min_sample_num);
+        let sample_num = Self::get_sample_num(tar_ip_num, self.sampling_pro, self.min_sample_num)?;
 
         // 定义 概率相关图
         let mut graph;
         // 初始化 输出模块
-        let mut out_mod = OutputMod::init(&self.receiver_conf.output_v6);
+        let mut out_mod = OutputMod::init(&self.receiver_conf.output_v6)?;
 
         // 定义 开始的时间
         let start_time;
         // 初始化全局 发送线程消息  接收线程消息
         init_var!(u64; 0; total_send_success, total_send_failed);
         init_var!(usize; 0; total_ip_count, total_pair_count);
+        // 各扫描阶段 捕获统计 之和
+        let mut capture_stats = CaptureStats::default();
 
         // 如果 抽样数量 小于 总数量, 说明 不只有完全扫描;
         let recommend_scan = sample_num < tar_ip_num;
@@ -66,7 +69,7 @@ impl ModeMethod for PmapFileV6 {
 
             {
                 // 只有接收线程准备完毕后，发送线程才能继续执行
-                recv_ready!(recv_ready_receiver);
+                recv_ready!(recv_ready_receiver; full_scan_result);
 
                 // 记录 开始发送的时间
                 start_time = Local::now();
@@ -93,27 +96,24 @@ impl ModeMethod for PmapFileV6 {
                 ending_the_receiving_thread!(self; recv_close_time_sender);
             }
             
-            match full_scan_result.join() {
-                Ok(hash_set) => {
-                    if recommend_scan {
-                        // 将完全扫描阶段的结果进行输出, 使用结果对概率相关图进行训练
-                        graph = Arc::new(PmapGraph::new(self.tar_ports.clone(), self.port_num_limit));
-                        match Arc::get_mut(&mut graph) {
-                            Some(g_ptr) => {
-                                let (ip_count, pair_count) = Self::full_scan_output_and_train(pre_scan_targets, hash_set, &mut out_mod, g_ptr);
-                                total_ip_count += ip_count;
-                                total_pair_count += pair_count;
-                            }
-                            None => { error!("{}", SYS.get_info("err", "get_graph_arc_failed")); exit(1) }
-                        }
-                    } else {
-                        let (ip_count, pair_count) = Self::full_scan_output(pre_scan_targets, hash_set, &mut out_mod);
+            let (hash_set, stats) = join_receiver(full_scan_result)?;
+            capture_stats.merge(&stats);
+            if recommend_scan {
+                // 将完全扫描阶段的结果进行输出, 使用结果对概率相关图进行训练
+                graph = Arc::new(PmapGraph::new(self.tar_ports.clone(), self.port_num_limit));
+                match Arc::get_mut(&mut graph) {
+                    Some(g_ptr) => {
+                        let (ip_count, pair_count) = Self::full_scan_output_and_train(pre_scan_targets, hash_set, &mut out_mod, g_ptr)?;
                         total_ip_count += ip_count;
                         total_pair_count += pair_count;
-                        graph = Arc::new(PmapGraph::new_void());
                     }
+                    None => return Err(RunError::thread("get_graph_arc_failed").into()),
                 }
-                Err(_) => { error!("{}", SYS.get_info("err", "recv_thread_err")); exit(1) }
+            } else {
+                let (ip_count, pair_count) = Self::full_scan_output(pre_scan_targets, hash_set, &mut out_mod)?;
+                total_ip_count += ip_count;
+                total_pair_count += pair_count;
+                graph = Arc::new(PmapGraph::new_void());
             }
         }
 
@@ -168,7 +168,7 @@ impl ModeMethod for PmapFileV6 {
                     };
 
                     // 只有接收线程准备完毕后，发送线程才能继续执行
-                    recv_ready!(recv_ready_receiver);
+                    recv_ready!(recv_ready_receiver; recommend_scan_result);
 
                     // 执行 推荐扫描 发送线程
                     let mut recommend_scan_sender_threads = vec![];
@@ -203,15 +203,12 @@ impl ModeMethod for PmapFileV6 {
                     // 使用这一轮次得到的数据对 ips_struct 和 状态库 进行更新
                     match Arc::get_mut(&mut graph) {
                         Some(g_ptr) => {
-                            if let Ok(hash_set) = recommend_scan_result.join() {
-                                Self::pmap_receive(hash_set, g_ptr, &mut states_map, &mut pmap_iter_queue);
-                            }
+                            let (hash_set, stats) = join_receiver(recommend_scan_result)?;
+                            capture_stats.merge(&stats);
+                            Self::pmap_receive(hash_set, g_ptr, &mut states_map, &mut pmap_iter_queue);
                         }
                         // 如果 获取概率相关图的可变指针失败
-                        None => {
-                            error!("{}", SYS.get_info("err", "get_graph_arc_failed"));
-                            exit(1)
-                        }
+                        None => return Err(RunError::thread("get_graph_arc_failed").into()),
                     }
                 }
 
@@ -222,17 +219,14 @@ impl ModeMethod for PmapFileV6 {
                 if self.pmap_allow_graph_iter {
                     match Arc::get_mut(&mut graph) {
                         Some(g_ptr) => {                 // 使用 开放端口列表 对 概率相关图 进行更新
-                            let (ip_count, pair_count) = Self::recommend_scan_output_train(g_ptr, pmap_iter_queue, &mut out_mod);
+                            let (ip_count, pair_count) = Self::recommend_scan_output_train(g_ptr, pmap_iter_queue, &mut out_mod)?;
                             total_ip_count += ip_count;
                             total_pair_count += pair_count;
                         }
-                        None => {
-                            error!("{}", SYS.get_info("err", "get_graph_arc_failed"));
-                            exit(1)
-                        }
+                        None => return Err(RunError::thread("get_graph_arc_failed").into()),
                     }
                 } else {
-                    let (ip_count, pair_count) = Self::recommend_scan_output(pmap_iter_queue, &mut out_mod);
+                    let (ip_count, pair_count) = Self::recommend_scan_output(pmap_iter_queue, &mut out_mod)?;
                     total_ip_count += ip_count;
                     total_pair_count += pair_count;
                 }
@@ -243,13 +237,15 @@ impl ModeMethod for PmapFileV6 {
         }
 
         // 探测 和 接收 执行完毕
-        println!("{} {} {} {}", SYS.get_info("print", "send_finished"), total_send_success, total_send_failed, 0);
-        println!("{} {} {}", SYS.get_info("print", "pmap_scan_finished"), total_ip_count, total_pair_count);
+        eprintln!("{} {} {} {}", SYS.get_info("print", "send_finished"), total_send_success, total_send_failed, 0);
+        eprintln!("{} {} {}", SYS.get_info("print", "pmap_scan_finished"), total_ip_count, total_pair_count);
         computing_time!(start_time; end_time, running_time);
 
         write_to_summary!(self; "PmapFileV6"; "result";
-            [start_time, end_time, running_time, total_send_success, total_send_failed, total_ip_count, total_pair_count;]
+            [start_time, end_time, running_time, total_send_success, total_send_failed, total_ip_count, total_pair_count;];
+            #[capture_stats;; ("pcap_received", received), ("pcap_dropped", dropped), ("pcap_if_dropped", if_dropped)]
         );
-        
+
+        Ok(())
     }
 }
\ No newline at end of file
@@ -6,9 +6,10 @@ use std::sync::Arc;
 use crate::core::conf::set_conf::base_conf::BaseConf;
 use crate::core::conf::set_conf::receiver_conf::ReceiverBaseConf;
 use crate::core::conf::set_conf::sender_conf::SenderBaseConf;
-use crate::modes::Helper;
+use crate::modes::{Helper, ModeReg};
 use crate::modules::probe_modules::probe_mod_v6::ProbeModV6;
 use crate::SYS;
+use crate::core::conf::modules_config::ArgSpec;
 
 pub struct PmapFileV6 {
 
@@ -47,4 +48,27 @@ impl Helper for PmapFileV6 {
     fn print_help() -> String {
         SYS.get_info("help", "PmapFileV6")
     }
-}
\ No newline at end of file
+}
+
+
+impl PmapFileV6 {
+
+    /// 模式注册信息
+    pub const MODE:ModeReg = ModeReg {
+        name: "pf6",
+        aliases: &["pmap_file_v6"],
+        help: Some(PmapFileV6::print_help),
+        args: &[
+            ArgSpec::int("pmap_budget").range(1.0, u32::MAX as f64),
+            ArgSpec::int("pmap_batch_num").min(1.0),
+            ArgSpec::bool("pmap_allow_graph_iter"),
+            ArgSpec::float("pmap_sampling_pro").range(0.0, 1.0),
+            ArgSpec::int("pmap_min_sample_num").min(0.0),
+            ArgSpec::int("pmap_port_num_limit").min(1.0),
+            ArgSpec::int("pmap_max_pairs_num").min(1.0),
+        ],
+        listed: true,
+        new: |args, conf| Ok(Box::new(PmapFileV6::new(args, conf)?)),
+        init: (),
+    };
+}
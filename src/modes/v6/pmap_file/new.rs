@@ -1,29 +1,32 @@
-use std::process::exit;
-use log::error;
 use crate::core::conf::args::Args;
+use crate::core::conf::set_conf::ScanConf;
 use crate::core::conf::modules_config::ModuleConf;
 use crate::core::conf::set_conf::base_conf::BaseConf;
 use crate::core::conf::tools::args_parse::target_iterator::TarIterBaseConf;
 use crate::modes::v6::pmap_file::PmapFileV6;
-use crate::{get_conf_from_mod_or_sys, write_to_summary, SYS};
+use crate::{get_conf_from_mod_or_sys, write_to_summary};
 use crate::core::conf::set_conf::receiver_conf::ReceiverBaseConf;
 use crate::core::conf::set_conf::sender_conf::SenderBaseConf;
 use crate::modules::probe_modules::probe_mod_v6::ProbeModV6;
 use crate::tools::file::parse_context::count_file_lines;
+use crate::core::errors::{ProbeError, SmapError};
 
 impl PmapFileV6 {
 
-    pub fn new(args:&Args) -> Self {
+    pub fn new(args:&Args, conf:&ScanConf) -> Result<Self, SmapError> {
         
-        let path = TarIterBaseConf::parse_targets_file(&args.target_file);
+        let path = TarIterBaseConf::parse_targets_file(&args.target_file)?;
         
         // 获取目标端口范围
-        let tar_ports = TarIterBaseConf::parse_tar_port(&args.tar_ports, "pmap_default_ports");
+        let tar_ports = TarIterBaseConf::parse_tar_port(&args.tar_ports, "pmap_default_ports")?;
 
         // 基础配置
-        let base_conf = BaseConf::new(args);
+        let base_conf = BaseConf::new(&conf.base)?;
         // 解析自定义参数    注意: 这里强制所有探测模块检查接收数据包的源端口
-        let module_conf = ModuleConf::new_from_vec_args(&args.custom_args, vec!["not_check_sport=false".to_string()]);
+        let module_conf = ModuleConf::new_from_vec_args(&args.custom_args, vec!["not_check_sport=false".to_string()])?;
+        // 按 模式 和 探测模块 声明的参数说明 检查自定义参数
+        let probe_name = SenderBaseConf::parse_probe_v6(&args.probe_v6, "pmap_default_probe_mod_v6");
+        let mod_args = module_conf.check(&[Self::MODE.args, ProbeModV6::args(&probe_name)?])?;
 
         // 从 自定义参数 或 系统配置 中读取 预算 和 推荐轮次, 是否允许概率相关图迭代
         get_conf_from_mod_or_sys!(module_conf; pmap_budget, pmap_batch_num, pmap_allow_graph_iter, 
@@ -31,10 +34,10 @@ impl PmapFileV6 {
 
         // ipv6 探测模块
         let probe = ProbeModV6::new(
-            &SenderBaseConf::parse_probe_v6(&args.probe_v6, "pmap_default_probe_mod_v6"),
-            module_conf, &tar_ports, base_conf.aes_rand.seed, &args.fields);
+            &probe_name,
+            module_conf, &tar_ports, base_conf.aes_rand.seed, &args.fields)?;
         // 如果 目标探测模块不使用端口, 直接报错并退出
-        if !probe.use_tar_ports { error!("{}", SYS.get_info("err", "probe_not_use_ports")); exit(1) }
+        if !probe.use_tar_ports { return Err(ProbeError::invalid("probe_not_use_ports").into()) }
         
         // 推测出的目标数量
         let guess_tar_num = if cfg!(target_os = "windows") { None } else { count_file_lines(&path) };
@@ -43,7 +46,7 @@ impl PmapFileV6 {
         let sender_conf= match guess_tar_num { 
             Some(g) => {
                 // 推测出的 抽样数量
-                let guess_sample_num = Self::get_sample_num(g as usize, pmap_sampling_pro, pmap_min_sample_num) as u64;
+                let guess_sample_num = Self::get_sample_num(g as usize, pmap_sampling_pro, pmap_min_sample_num)? as u64;
                 // 需要进行推荐扫描
                 let recommend_scan = guess_sample_num < g;
                 
@@ -54,19 +57,19 @@ impl PmapFileV6 {
                     g * (tar_ports.len() as u64)
                 };
                 
-                SenderBaseConf::new(args, &base_conf.interface, Some(guess_packet_num), 
+                SenderBaseConf::new(conf, &base_conf.interface, Some(guess_packet_num), 
                                     if recommend_scan {Some((pmap_batch_num as i64) * (pmap_budget as i64) + 1)} else { None }, 
-                                    probe.max_packet_length_v6, false, true)
+                                    probe.max_packet_length_v6, false, true)?
             }
-            None => SenderBaseConf::new(args, &base_conf.interface, None, None, probe.max_packet_length_v6, false, true)
+            None => SenderBaseConf::new(conf, &base_conf.interface, None, None, probe.max_packet_length_v6, false, true)?
         };
 
         // 接收模块基础配置
-        let receiver_conf= ReceiverBaseConf::new(args, vec![probe.filter_v6.clone()]);
+        let receiver_conf= ReceiverBaseConf::new(conf, &base_conf, vec![probe.filter_v6.clone()])?;
 
-        write_to_summary!(base_conf; "PmapFileV6"; "args"; args;);
+        write_to_summary!(base_conf; "PmapFileV6"; "args"; args; mod_args;);
         
-        Self {
+        Ok(Self {
             base_conf: base_conf.into(),
             sender_conf: sender_conf.into(),
             receiver_conf: receiver_conf.into(),
@@ -84,7 +87,7 @@ impl PmapFileV6 {
             tar_ports,
             port_num_limit: pmap_port_num_limit,
             max_pairs_num: pmap_max_pairs_num,
-        }
+        })
     }
     
     
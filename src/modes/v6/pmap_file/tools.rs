@@ -1,12 +1,11 @@
 use std::net::Ipv6Addr;
-use std::process::exit;
 use std::sync::Arc;
 use ahash::AHashMap;
-use log::error;
 use crate::modes::v6::pmap_file::PmapFileV6;
 use crate::modules::output_modules::OutputMethod;
 use crate::modules::target_iterators::{PmapFileIterV6, PmapGraph, PmapState};
-use crate::{init_var, SYS};
+use crate::init_var;
+use crate::core::errors::{ConfError, OutputError};
 use crate::tools::check_duplicates::{ExtractActPortsV6, NotMarkedV6Port};
 use crate::tools::others::split::split_chains;
 
@@ -14,15 +13,14 @@ impl PmapFileV6 {
 
 
     /// 根据 预扫描抽样比例 和 最小抽样数量 计算 预扫描需要扫描的目标地址数量
-    pub(crate) fn get_sample_num(tar_ip_num:usize, sampling_pro:f64, min_sample_num:usize) -> usize {
+    pub(crate) fn get_sample_num(tar_ip_num:usize, sampling_pro:f64, min_sample_num:usize) -> Result<usize, ConfError> {
 
         // 采样比例 小于等于 0  或  大于 1 均为非法
         if sampling_pro <= 0.0 ||  1.0 < sampling_pro {
-            error!("{}", SYS.get_info("err", "sampling_pro_invalid"));
-            exit(1)
+            return Err(ConfError::invalid("sampling_pro_invalid"))
         }
 
-        if sampling_pro > 0.99 {
+        Ok(if sampling_pro > 0.99 {
             // 当 采样比例 大于 0.99 时, 直接 取消端口推荐, 对 所有端口对 进行探测
             tar_ip_num
         } else {
@@ -45,14 +43,14 @@ impl PmapFileV6 {
 
                 if sampling_num < 1 { 1 } else { sampling_num }
             }
-        }
+        })
 
     }
 
 
     pub fn full_scan_output<B:ExtractActPortsV6>(pre_scan_ips:Vec<u128>, res:B,
                                                  out_mod:&mut Box<dyn OutputMethod>) 
-        -> (usize, usize) {
+        -> Result<(usize, usize), OutputError> {
 
         // 存在活跃端口的ip计数
         let mut ip_count:usize = 0;
@@ -73,16 +71,16 @@ impl PmapFileV6 {
         }
 
         // 关闭输出
-        out_mod.close_output();
+        out_mod.close_output()?;
 
-        (ip_count, pair_count)
+        Ok((ip_count, pair_count))
     }
 
 
     pub fn full_scan_output_and_train<B:ExtractActPortsV6>(pre_scan_ips:Vec<u128>, res:B,
                                                            out_mod:&mut Box<dyn OutputMethod>, 
                                                            graph:&mut PmapGraph)
-        -> (usize, usize) {
+        -> Result<(usize, usize), OutputError> {
 
         // 存在活跃端口的ip计数
         let mut ip_count:usize = 0;
@@ -103,12 +101,12 @@ impl PmapFileV6 {
         }
 
         // 关闭输出
-        out_mod.close_output();
+        out_mod.close_output()?;
 
         // 生成 绝对概率表
         graph.update_end();
 
-        (ip_count, pair_count)
+        Ok((ip_count, pair_count))
     }
 
     pub fn create_pmap6_iter_queue(targets: Vec<u128>, thread_num: usize) -> Vec<PmapFileIterV6> {
@@ -144,7 +142,7 @@ impl PmapFileV6 {
     }
 
 
-    pub fn recommend_scan_output_train(g_ptr:&mut PmapGraph, pmap_iter_queue:Vec<PmapFileIterV6>, out_mod:&mut Box<dyn OutputMethod>) -> (usize, usize){
+    pub fn recommend_scan_output_train(g_ptr:&mut PmapGraph, pmap_iter_queue:Vec<PmapFileIterV6>, out_mod:&mut Box<dyn OutputMethod>) -> Result<(usize, usize), OutputError> {
 
         init_var!(usize; 0; ip_count, pair_count);
         for pmap_iter in pmap_iter_queue.into_iter() {
@@ -174,15 +172,15 @@ impl PmapFileV6 {
         }
 
         // 关闭输出
-        out_mod.close_output();
+        out_mod.close_output()?;
         // 生成 绝对概率表
         g_ptr.update_end();
 
-        (ip_count, pair_count)
+        Ok((ip_count, pair_count))
     }
 
 
-    pub fn recommend_scan_output(pmap_iter_queue:Vec<PmapFileIterV6>, out_mod:&mut Box<dyn OutputMethod>) -> (usize, usize){
+    pub fn recommend_scan_output(pmap_iter_queue:Vec<PmapFileIterV6>, out_mod:&mut Box<dyn OutputMethod>) -> Result<(usize, usize), OutputError> {
 
         init_var!(usize; 0; ip_count, pair_count);
         for pmap_iter in pmap_iter_queue.into_iter() {
@@ -209,9 +207,9 @@ impl PmapFileV6 {
         }
 
         // 关闭输出
-        out_mod.close_output();
+        out_mod.close_output()?;
 
-        (ip_count, pair_count)
+        Ok((ip_count, pair_count))
     }
 
 }
\ No newline at end of file
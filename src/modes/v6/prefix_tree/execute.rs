@@ -1,8 +1,7 @@
-use std::process::exit;
 use std::thread;
 use ahash::{AHashMap, AHashSet};
 use chrono::Local;
-use log::{error, info};
+use log::info;
 use crate::modes::ModeMethod;
 use crate::modes::v6::prefix_tree::{PrefixTree6, SplitNodeSelectType};
 use crate::modules::output_modules::OutputMod;
@@ -11,9 +10,12 @@ use crate::{computing_time, creat_channels, ending_the_receiving_thread, init_va
 use crate::core::receiver::pcap::PcapReceiver;
 use crate::core::sender::send_prefixes_v6;
 use crate::tools::others::split::split_chains;
+use crate::core::errors::SmapError;
+use crate::core::receiver::join_receiver;
+use crate::core::receiver::pcap::source::CaptureStats;
 
 impl ModeMethod for PrefixTree6 {
-    fn execute(&self) {
+    fn execute(&self) -> Result<(), SmapError> {
         
         // 实例化前缀空间树
         info!("{}", SYS.get_info("info", "ipv6_space_tree_instantiation"));
@@ -24,16 +26,18 @@ impl ModeMethod for PrefixTree6 {
         let mut all_nodes:AHashSet<u128> = AHashSet::new();
         
         // 初始化 DoubleTree 结构体
-        let mut double_tree_struct = Ipv6VecDoubleTree::new(self.initial_ttl, self.gap_limit, self.max_ttl, self.min_target_num);
+        let mut double_tree_struct = Ipv6VecDoubleTree::new(self.initial_ttl, self.gap_limit, self.max_ttl, self.min_target_num)?;
 
         // 初始化 输出模块
-        let mut out_mod = OutputMod::init(&self.receiver_conf.output_v6);
+        let mut out_mod = OutputMod::init(&self.receiver_conf.output_v6)?;
 
         // 定义扫描开始时间
         let start_time = Local::now();
 
         // 定义 接收线程, 发送线程 消息; 已经使用的总预算
         init_var!(u64; 0; total_send_success, total_send_failed);
+        // 各扫描阶段 捕获统计 之和
+        let mut capture_stats = CaptureStats::default();
         // 补充扫描状态
         let mut supplement_scan_state = false;
         
@@ -45,7 +49,7 @@ impl ModeMethod for PrefixTree6 {
             
             if round_count == 1 {
                 // 生成 初始节点队列, 并获得初始探测列表
-                let (target_addrs, addr_to_seq) = prefix_tree.init_queue();
+                let (target_addrs, addr_to_seq) = prefix_tree.init_queue()?;
                 info!("{} {} {} {} {} {}", SYS.get_info("info", "start_scan"), target_addrs.len(), SYS.get_info("info", "cur_no_split_node_num"), 
                     prefix_tree.node_queue.len(), SYS.get_info("info", "cur_split_node_num"), prefix_tree.cur_tar_node_queue.len());
                 double_tree_struct.set_targets(target_addrs, addr_to_seq);
@@ -83,7 +87,7 @@ impl ModeMethod for PrefixTree6 {
                 }
 
                 // 只有接收线程准备完毕后，发送线程才能继续执行
-                recv_ready!(recv_ready_receiver);
+                recv_ready!(recv_ready_receiver; receiver_res);
 
                 // 执行发送线程
                 let targets_list = split_chains(targets, self.sender_conf.send_thread_num);
@@ -106,16 +110,16 @@ impl ModeMethod for PrefixTree6 {
                 ending_the_receiving_thread!(self; recv_close_time_sender);
 
                 // 等待接收线程按照预定时间关闭
-                if let Ok((cur_double_tree_struct, cur_all_nodes, output, new_targets)) = receiver_res.join() {
-                    double_tree_struct = cur_double_tree_struct;
-                    all_nodes = cur_all_nodes;
-                    out_mod = output;
+                let (cur_double_tree_struct, cur_all_nodes, output, new_targets, stats) = join_receiver(receiver_res)?;
+                capture_stats.merge(&stats);
+                double_tree_struct = cur_double_tree_struct;
+                all_nodes = cur_all_nodes;
+                out_mod = output;
 
-                    // 如果新生成的探测目标为空, 说明本轮拓扑探测已经结束
-                    if new_targets.is_empty() { break 'topo }
+                // 如果新生成的探测目标为空, 说明本轮拓扑探测已经结束
+                if new_targets.is_empty() { break 'topo }
 
-                    targets = new_targets;
-                } else { error!("{}", SYS.get_info("err", "recv_thread_err")); exit(1) }
+                targets = new_targets;
             }
 
             // 如果已经发送的总数据包数量超过预算, 结束整个探测任务
@@ -160,7 +164,10 @@ impl ModeMethod for PrefixTree6 {
 
         // 输出到记录文件
         write_to_summary!(self; "PrefixTree6"; "result";
-            [start_time, end_time, running_time, hit_rate, all_nodes_len, total_send_success, total_send_failed;]
+            [start_time, end_time, running_time, hit_rate, all_nodes_len, total_send_success, total_send_failed;];
+            #[capture_stats;; ("pcap_received", received), ("pcap_dropped", dropped), ("pcap_if_dropped", if_dropped)]
         );
+
+        Ok(())
     }
 }
\ No newline at end of file
@@ -2,10 +2,11 @@ use std::sync::Arc;
 use crate::core::conf::set_conf::base_conf::BaseConf;
 use crate::core::conf::set_conf::receiver_conf::ReceiverBaseConf;
 use crate::core::conf::set_conf::sender_conf::SenderBaseConf;
-use crate::modes::Helper;
+use crate::modes::{Helper, ModeReg};
 use crate::modules::probe_modules::topology_probe::topo_mod_v6::TopoModV6;
 use crate::modules::target_iterators::IPv6PrefixTree;
 use crate::SYS;
+use crate::core::conf::modules_config::ArgSpec;
 
 mod execute;
 mod new;
@@ -56,4 +57,38 @@ impl Helper for PrefixTree6 {
     fn print_help() -> String {
         SYS.get_info("help", "PrefixTree6")
     }
-}
\ No newline at end of file
+}
+
+
+impl PrefixTree6 {
+
+    /// 模式注册信息
+    pub const MODE:ModeReg = ModeReg {
+        name: "ipv6_prefix_tree",
+        aliases: &[],
+        help: Some(PrefixTree6::print_help),
+        args: &[
+            ArgSpec::choice("split_node_select_type", &["cascade", "independent"]),
+            ArgSpec::int("budget").min(1.0),
+            ArgSpec::int("divide_dim").range(1.0, 32.0),
+            ArgSpec::int("max_prefix_len").range(2.0, 128.0),
+            ArgSpec::float("learning_rate").range(0.0, 1.0),
+            ArgSpec::str("seeds_path").required(),
+            ArgSpec::int("min_target_num").min(0.0),
+            ArgSpec::bool("rand_ord"),
+            ArgSpec::bool("allow_supplement_scan"),
+            ArgSpec::str("prefix_path").required(),
+            ArgSpec::int("min_prefix_len").range(0.0, 128.0),
+            ArgSpec::str("threshold"),
+            ArgSpec::int("extra_node_num").min(0.0),
+            ArgSpec::int("initial_ttl").range(1.0, 255.0),
+            ArgSpec::int("gap_limit").range(0.0, 255.0),
+            ArgSpec::int("prefix_tree_max_ttl").range(1.0, 255.0),
+            ArgSpec::bool("allow_leaf_expand"),
+            ArgSpec::int("child_max_size").min(1.0),
+        ],
+        listed: false,
+        new: |args, conf| Ok(Box::new(PrefixTree6::new(args, conf)?)),
+        init: (),
+    };
+}
@@ -1,4 +1,3 @@
-use std::process::exit;
 use std::thread;
 use ahash::AHashMap;
 use chrono::Local;
@@ -8,12 +7,15 @@ use crate::core::sender::send_prefixes_v6_2;
 use crate::modes::ModeMethod;
 use crate::modules::output_modules::OutputMod;
 use crate::tools::others::split::split_chains;
-use log::{error, info};
+use log::info;
 use crate::modes::v6::Scour6;
 use crate::modules::target_iterators::Scour6Iter;
+use crate::core::errors::SmapError;
+use crate::core::receiver::join_receiver;
+use crate::core::receiver::pcap::source::CaptureStats;
 
 impl ModeMethod for Scour6 {
-    fn execute(&self) {
+    fn execute(&self) -> Result<(), SmapError> {
 
         // 由目标前缀文件生成目标迭代器
         let mut scour_iter = Scour6Iter::new(&self.path, self.sample_pow, self.window_size, self.start_hop_limit);
@@ -23,9 +25,11 @@ impl ModeMethod for Scour6 {
         let start_time = Local::now();
         // 定义 接收线程, 发送线程 消息; 已经使用的总预算
         init_var!(u64; 0; total_send_success, total_send_failed);
+        // 各扫描阶段 捕获统计 之和
+        let mut capture_stats = CaptureStats::default();
 
         // 初始化 输出模块
-        let mut out_mod = OutputMod::init(&self.receiver_conf.output_v6);
+        let mut out_mod = OutputMod::init(&self.receiver_conf.output_v6)?;
 
         // 所有探测到的响应点的集合
         let mut all_nodes:AHashMap<u128, u8> = AHashMap::new();
@@ -53,7 +57,7 @@ impl ModeMethod for Scour6 {
             }
 
             // 只有接收线程准备完毕后，发送线程才能继续执行
-            recv_ready!(recv_ready_receiver);
+            recv_ready!(recv_ready_receiver; receiver_res);
             
             let targets_list = split_chains(targets, self.sender_conf.send_thread_num);
 
@@ -76,14 +80,13 @@ impl ModeMethod for Scour6 {
             ending_the_receiving_thread!(self; recv_close_time_sender);
 
             // 等待接收线程按照预定时间关闭
-            if let Ok((cur_all_nodes, recorder, recorder2, output)) = receiver_res.join() {
-                all_nodes = cur_all_nodes;
-                out_mod = output;
-                
-                // 更新 信息
-                all_nodes = scour_iter.update_info(recorder, recorder2, all_nodes, self.expand_ttl_b, self.expand_ttl_a);
-                
-            } else { error!("{}", SYS.get_info("err", "recv_thread_err")); exit(1) }
+            let (cur_all_nodes, recorder, recorder2, output, stats) = join_receiver(receiver_res)?;
+            capture_stats.merge(&stats);
+            all_nodes = cur_all_nodes;
+            out_mod = output;
+            
+            // 更新 信息
+            all_nodes = scour_iter.update_info(recorder, recorder2, all_nodes, self.expand_ttl_b, self.expand_ttl_a);
 
             // 当前轮次结束后打印 当前信息
             let all_nodes_len = all_nodes.len();    let hit_rate = (all_nodes_len as f64) / (total_send_success as f64);
@@ -104,9 +107,10 @@ impl ModeMethod for Scour6 {
 
         //输出到记录文件
         write_to_summary!(self; "TreeTracePlus6"; "result";
-            [start_time, end_time, running_time, hit_rate, all_nodes_len, total_send_success, total_send_failed;]
+            [start_time, end_time, running_time, hit_rate, all_nodes_len, total_send_success, total_send_failed;];
+            #[capture_stats;; ("pcap_received", received), ("pcap_dropped", dropped), ("pcap_if_dropped", if_dropped)]
         );
-        
-        
+
+        Ok(())
     }
 }
\ No newline at end of file
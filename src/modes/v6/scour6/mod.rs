@@ -2,9 +2,10 @@ use std::sync::Arc;
 use crate::core::conf::set_conf::base_conf::BaseConf;
 use crate::core::conf::set_conf::receiver_conf::ReceiverBaseConf;
 use crate::core::conf::set_conf::sender_conf::SenderBaseConf;
-use crate::modes::Helper;
+use crate::modes::{Helper, ModeReg};
 use crate::modules::probe_modules::topo_mod_v6::CodeTopoProbeModV6;
 use crate::SYS;
+use crate::core::conf::modules_config::ArgSpec;
 
 mod new;
 mod execute;
@@ -51,3 +52,27 @@ impl Helper for Scour6 {
         SYS.get_info("help", "Scour6")
     }
 }
+
+
+impl Scour6 {
+
+    /// 模式注册信息
+    pub const MODE:ModeReg = ModeReg {
+        name: "s6",
+        aliases: &["scour6"],
+        help: Some(Scour6::print_help),
+        args: &[
+            ArgSpec::int("budget").min(1.0),
+            ArgSpec::int("batch_size").min(1.0),
+            ArgSpec::int("expand_ttl_a").range(0.0, 32.0).required(),
+            ArgSpec::int("expand_ttl_b").range(0.0, 32.0).required(),
+            ArgSpec::int("sample_pow").range(0.0, 63.0).required(),
+            ArgSpec::int("show_prefix_num").min(0.0).required(),
+            ArgSpec::int("window_size").range(1.0, u32::MAX as f64),
+            ArgSpec::int("start_hop_limit").range(1.0, 255.0).required(),
+        ],
+        listed: true,
+        new: |args, conf| Ok(Box::new(Scour6::new(args, conf)?)),
+        init: (),
+    };
+}
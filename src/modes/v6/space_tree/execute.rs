@@ -1,7 +1,6 @@
-use std::process::exit;
 use std::thread;
 use chrono::Local;
-use log::{error, info};
+use log::info;
 use crate::modes::ModeMethod;
 use crate::modes::v6::space_tree::new::SpaceTreeType;
 use crate::modes::v6::space_tree::SpaceTree6;
@@ -10,9 +9,12 @@ use crate::core::receiver::pcap::PcapReceiver;
 use crate::core::sender::send_v6_code_vec;
 use crate::modules::output_modules::OutputMod;
 use crate::tools::others::split::split_chains;
+use crate::core::errors::SmapError;
+use crate::core::receiver::join_receiver;
+use crate::core::receiver::pcap::source::CaptureStats;
 
 impl ModeMethod for SpaceTree6 {
-    fn execute(&self) {
+    fn execute(&self) -> Result<(), SmapError> {
 
         // 实例化空间树
         let mut space_tree = self.space_tree.clone();
@@ -20,15 +22,17 @@ impl ModeMethod for SpaceTree6 {
         match self.space_tree_type {
             // 生成 密度空间树
             SpaceTreeType::DENSITY => space_tree.init_density_tree(),
-        };
+        }?;
 
         // 定义扫描开始时间
         let start_time = Local::now();
         // 定义 接收线程, 发送线程 消息; 已经使用的总预算
         init_var!(u64; 0; total_send_success, total_send_failed, total_active_num, total_used_budget);
+        // 各扫描阶段 捕获统计 之和
+        let mut capture_stats = CaptureStats::default();
 
         // 初始化 输出模块
-        let mut out_mod = OutputMod::init(&self.receiver_conf.output_v6);
+        let mut out_mod = OutputMod::init(&self.receiver_conf.output_v6)?;
 
         let mut count = 0u32;
         loop {
@@ -73,7 +77,7 @@ impl ModeMethod for SpaceTree6 {
             }
 
             // 只有接收线程准备完毕后，发送线程才能继续执行
-            recv_ready!(recv_ready_receiver);
+            recv_ready!(recv_ready_receiver; receiver_res);
 
             // 执行发送线程
             let tar_addrs_list = split_chains(tar_addrs, self.sender_conf.send_thread_num);
@@ -96,14 +100,12 @@ impl ModeMethod for SpaceTree6 {
             ending_the_receiving_thread!(self; recv_close_time_sender);
 
             // 等待接收线程按照预定时间关闭
-            if let Ok((cur_active_num, region_recorder, output)) = receiver_res.join() {
-                info!("{} {}", SYS.get_info("info", "cur_active_num"),cur_active_num);
-                total_active_num += cur_active_num as u64;
-                space_tree.update_tree(region_recorder);
-                out_mod = output;
-            } else {
-                error!("{}", SYS.get_info("err", "recv_thread_err")); exit(1)
-            }
+            let (cur_active_num, region_recorder, output, stats) = join_receiver(receiver_res)?;
+            capture_stats.merge(&stats);
+            info!("{} {}", SYS.get_info("info", "cur_active_num"),cur_active_num);
+            total_active_num += cur_active_num as u64;
+            space_tree.update_tree(region_recorder);
+            out_mod = output;
             
             if total_used_budget >= self.budget { break }
             
@@ -117,10 +119,13 @@ impl ModeMethod for SpaceTree6 {
         let hit_rate = (total_active_num as f64) / (total_send_success as f64);
         
         // 终端显示结果
-        println!("{} {}% {} {} {}", SYS.get_info("print", "ipv6_addrs_gen_finished"), hit_rate * 100.0, total_active_num, total_send_success, total_send_failed);
+        eprintln!("{} {}% {} {} {}", SYS.get_info("print", "ipv6_addrs_gen_finished"), hit_rate * 100.0, total_active_num, total_send_success, total_send_failed);
         
         write_to_summary!(self; "SpaceTree6"; "result";
-            [start_time, end_time, running_time, hit_rate, total_active_num, total_send_success, total_send_failed;]
+            [start_time, end_time, running_time, hit_rate, total_active_num, total_send_success, total_send_failed;];
+            #[capture_stats;; ("pcap_received", received), ("pcap_dropped", dropped), ("pcap_if_dropped", if_dropped)]
         );
+
+        Ok(())
     }
 }
\ No newline at end of file
@@ -2,11 +2,12 @@ use std::sync::Arc;
 use crate::core::conf::set_conf::base_conf::BaseConf;
 use crate::core::conf::set_conf::receiver_conf::ReceiverBaseConf;
 use crate::core::conf::set_conf::sender_conf::SenderBaseConf;
-use crate::modes::Helper;
+use crate::modes::{Helper, ModeReg};
 use crate::modes::v6::space_tree::new::SpaceTreeType;
 use crate::modules::probe_modules::active_probe_ipv6_code::CodeProbeModV6;
 use crate::modules::target_iterators::IPv6SpaceTree;
 use crate::SYS;
+use crate::core::conf::modules_config::ArgSpec;
 
 mod new;
 mod execute;
@@ -42,4 +43,31 @@ impl Helper for SpaceTree6 {
     fn print_help() -> String {
         SYS.get_info("help", "SpaceTree6")
     }
-}
\ No newline at end of file
+}
+
+
+impl SpaceTree6 {
+
+    /// 模式注册信息
+    pub const MODE:ModeReg = ModeReg {
+        name: "ipv6_addrs_gen",
+        aliases: &[],
+        help: Some(SpaceTree6::print_help),
+        args: &[
+            ArgSpec::choice("space_tree_type", &["density"]),
+            ArgSpec::int("budget").min(1.0),
+            ArgSpec::int("batch_size").min(1.0),
+            ArgSpec::int("divide_dim").range(1.0, 32.0),
+            ArgSpec::str("divide_range"),
+            ArgSpec::int("max_leaf_size").min(1.0),
+            ArgSpec::bool("no_allow_gen_seeds"),
+            ArgSpec::float("learning_rate").range(0.0, 1.0),
+            ArgSpec::int("region_extraction_num").range(1.0, u32::MAX as f64),
+            ArgSpec::int("seeds_num").min(0.0),
+            ArgSpec::bool("no_allow_gen_seeds_from_file"),
+        ],
+        listed: true,
+        new: |args, conf| Ok(Box::new(SpaceTree6::new(args, conf)?)),
+        init: (),
+    };
+}
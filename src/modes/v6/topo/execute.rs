@@ -1,9 +1,7 @@
 
 
-use std::process::exit;
 use std::thread;
 use chrono::Local;
-use log::error;
 use crate::core::receiver::pcap::PcapReceiver;
 use crate::{computing_time, creat_channels, ending_the_receiving_thread, init_var, prepare_data, recv_ready, SYS, wait_sender_threads, write_to_summary};
 use crate::core::conf::tools::args_parse::target_iterator::TarIterBaseConf;
@@ -13,9 +11,12 @@ use crate::modes::v6::topo::Topo6;
 use crate::modules::output_modules::OutputMod;
 use crate::modules::target_iterators::{TopoIterV6, TopoStateChainV6};
 use crate::tools::others::split::split_chains;
+use crate::core::errors::SmapError;
+use crate::core::receiver::join_receiver;
+use crate::core::receiver::pcap::source::CaptureStats;
 
 impl ModeMethod for Topo6 {
-    fn execute(&self) {
+    fn execute(&self) -> Result<(), SmapError> {
 
         // 定义 状态链
         let mut state_chain;
@@ -23,8 +24,10 @@ impl ModeMethod for Topo6 {
         let start_time;
         // 初始化全局 发送线程消息
         init_var!(u64; 0; total_send_success, total_send_failed, total_blocked);
+        // 各扫描阶段 捕获统计 之和
+        let mut capture_stats = CaptureStats::default();
         // 初始化 输出模块
-        let mut out_mod = OutputMod::init(&self.receiver_conf.output_v6);
+        let mut out_mod = OutputMod::init(&self.receiver_conf.output_v6)?;
 
         // 预扫描阶段
         // 对 所有目标地址 进行探测, 取得 所有存活目标 的 跳数 和 延迟
@@ -44,7 +47,7 @@ impl ModeMethod for Topo6 {
                     receiver_res = thread::spawn(move || {
                         // ip状态链  用于记录每个ip的状态   ( 是否接收到的标志(1比特) | 最大ttl-1 (6比特) ), 并用于响应查重
                         let state_chain: TopoStateChainV6 = TopoStateChainV6::new(ip_bits_num, base_ip_val,
-                                                                                  mask, parts, ip_move_len);
+                                                                                  mask, parts, ip_move_len)?;
 
                         PcapReceiver::topo_pre_scan_v6(0, out_mod, base_conf, receiver_conf,
                                                        probe, sports, state_chain,
@@ -52,7 +55,7 @@ impl ModeMethod for Topo6 {
                     });
                 }
                 {   // 只有接收线程准备完毕后，发送线程才能继续执行
-                    recv_ready!(recv_ready_receiver);
+                    recv_ready!(recv_ready_receiver; receiver_res);
 
                     // 获取 预扫描的多线程任务分配列表
                     let tar_ranges = TarIterBaseConf::cycle_group_assign_targets_u128(
@@ -68,7 +71,7 @@ impl ModeMethod for Topo6 {
                         let max_ttl = self.max_ttl;
                         prepare_data!(self; clone; blocker, base_conf, sender_conf, probe);
                         // 初始化 局部目标迭代器
-                        let tar_iter = self.tar_iter.init(tar_range.0, tar_range.1);
+                        let tar_iter = self.tar_iter.init(tar_range.0, tar_range.1)?;
 
                         pre_scan_sender_threads.push(thread::spawn(move || {
                             topo_pre_scan_send_v6(0, tar_iter, blocker, max_ttl, probe, base_conf, sender_conf)
@@ -85,9 +88,9 @@ impl ModeMethod for Topo6 {
                 }
 
                 // 等待接收线程按照预定时间关闭
-                if let Ok((s_chain, output)) = receiver_res.join() {
-                    state_chain = s_chain; out_mod = output;
-                } else { error!("{}", SYS.get_info("err", "recv_thread_err"));exit(1) }
+                let (s_chain, output, stats) = join_receiver(receiver_res)?;
+                capture_stats.merge(&stats);
+                state_chain = s_chain; out_mod = output;
             }
 
             // 第二次预扫描  使用 用于补足预扫描结果
@@ -110,7 +113,7 @@ impl ModeMethod for Topo6 {
                     });
                 }
                 {   // 只有接收线程准备完毕后，发送线程才能继续执行
-                    recv_ready!(recv_ready_receiver);
+                    recv_ready!(recv_ready_receiver; receiver_res);
 
                     // 执行 辅助预扫描 发送线程
                     let mut sub_pre_scan_sender_threads = vec![];
@@ -125,7 +128,7 @@ impl ModeMethod for Topo6 {
                         // 初始化 局部目标迭代器
                         let cur_chain_len = split_chain.len();
                         let tar_iter = TopoIterV6::new(cur_start_index, split_chain, self.base_ip_val,
-                                                       self.tar_iter.ip_move_len.clone(), &mut self.base_conf.aes_rand.rng.clone());
+                                                       self.tar_iter.ip_move_len.clone(), &mut self.base_conf.aes_rand.rng.clone())?;
                         cur_start_index += cur_chain_len;
 
                         sub_pre_scan_sender_threads.push(thread::spawn(move || {
@@ -142,9 +145,9 @@ impl ModeMethod for Topo6 {
                     ending_the_receiving_thread!(self; recv_close_time_sender);
                 }
                 // 等待接收线程按照预定时间关闭
-                if let Ok((s_chain, output)) = receiver_res.join() {
-                    state_chain = s_chain; out_mod = output;
-                } else { error!("{}", SYS.get_info("err", "recv_thread_err")); exit(1) }
+                let (s_chain, output, stats) = join_receiver(receiver_res)?;
+                capture_stats.merge(&stats);
+                state_chain = s_chain; out_mod = output;
             }
         }
 
@@ -172,7 +175,7 @@ impl ModeMethod for Topo6 {
                 }
                 {
                     // 只有接收线程准备完毕后，发送线程才能继续执行
-                    recv_ready!(recv_ready_receiver);
+                    recv_ready!(recv_ready_receiver; receiver_res);
 
                     // 执行 拓扑扫描 发送线程
                     let mut topo_scan_sender_threads = vec![];
@@ -185,7 +188,7 @@ impl ModeMethod for Topo6 {
                         // 初始化 局部目标迭代器
                         let cur_chain_len = split_chain.len();
                         let tar_iter = TopoIterV6::new(cur_start_index, split_chain, self.base_ip_val,
-                                                       self.tar_iter.ip_move_len.clone(), &mut self.base_conf.aes_rand.rng.clone());
+                                                       self.tar_iter.ip_move_len.clone(), &mut self.base_conf.aes_rand.rng.clone())?;
                         cur_start_index += cur_chain_len;
 
                         topo_scan_sender_threads.push(thread::spawn(move || {
@@ -201,20 +204,23 @@ impl ModeMethod for Topo6 {
                     ending_the_receiving_thread!(self; recv_close_time_sender);
                 }
                 // 等待接收线程按照预定时间关闭
-                if let Ok((s_chain, output)) = receiver_res.join() {
-                    // 处理接收线程返回的信息
-                    state_chain = s_chain;
-                    out_mod = output;
-                } else { error!("{}", SYS.get_info("err", "recv_thread_err")); exit(1) }
+                let (s_chain, output, stats) = join_receiver(receiver_res)?;
+                capture_stats.merge(&stats);
+                // 处理接收线程返回的信息
+                state_chain = s_chain;
+                out_mod = output;
             }
         }
 
         // 探测 和 接收 执行完毕
-        println!("{} {} {} {}", SYS.get_info("print", "send_finished"), total_send_success, total_send_failed, total_blocked);
+        eprintln!("{} {} {} {}", SYS.get_info("print", "send_finished"), total_send_success, total_send_failed, total_blocked);
 
         computing_time!(start_time; end_time, running_time);
         write_to_summary!(self; "TopoV6"; "result";
-            [start_time, end_time, running_time, total_send_success, total_send_failed, total_blocked;]
+            [start_time, end_time, running_time, total_send_success, total_send_failed, total_blocked;];
+            #[capture_stats;; ("pcap_received", received), ("pcap_dropped", dropped), ("pcap_if_dropped", if_dropped)]
         );
+
+        Ok(())
     }
 }
\ No newline at end of file
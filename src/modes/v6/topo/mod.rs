@@ -3,11 +3,12 @@ use std::sync::Arc;
 use crate::core::conf::set_conf::base_conf::BaseConf;
 use crate::core::conf::set_conf::receiver_conf::ReceiverBaseConf;
 use crate::core::conf::set_conf::sender_conf::SenderBaseConf;
-use crate::modes::Helper;
+use crate::modes::{Helper, ModeReg};
 use crate::modules::probe_modules::topology_probe::topo_mod_v6::TopoModV6;
 use crate::modules::target_iterators::CycleIpv6Pattern;
 use crate::SYS;
 use crate::tools::blocker::ipv6_blocker::BlackWhiteListV6;
+use crate::core::conf::modules_config::ArgSpec;
 
 mod new;
 mod execute;
@@ -42,3 +43,21 @@ impl Helper for Topo6 {
     }
 }
 
+
+impl Topo6 {
+
+    /// 模式注册信息
+    pub const MODE:ModeReg = ModeReg {
+        name: "t6",
+        aliases: &["topo_v6"],
+        help: Some(Topo6::print_help),
+        args: &[
+            ArgSpec::int("topo6_rand_bits").range(0.0, 128.0),
+            ArgSpec::int("topo_max_ttl").range(1.0, 64.0),
+            ArgSpec::str("topo_sub_probe_v6").unset(),
+        ],
+        listed: true,
+        new: |args, conf| Ok(Box::new(Topo6::new(args, conf)?)),
+        init: (),
+    };
+}
@@ -1,8 +1,7 @@
-use std::process::exit;
 use std::thread;
 use ahash::{AHashMap, AHashSet};
 use chrono::Local;
-use log::{error, info};
+use log::info;
 use crate::{computing_time, creat_channels, ending_the_receiving_thread, init_var, prepare_data, recv_ready, SYS, wait_sender_threads, write_to_summary};
 use crate::core::receiver::pcap::PcapReceiver;
 use crate::core::sender::send_prefixes_v6;
@@ -11,18 +10,21 @@ use crate::modes::v6::topo_test::DoubleTreeTest;
 use crate::modules::output_modules::OutputMod;
 use crate::modules::target_iterators::{IPv6FixedPrefixTree, Ipv6VecDoubleTree};
 use crate::tools::others::split::split_chains;
+use crate::core::errors::SmapError;
+use crate::core::receiver::join_receiver;
+use crate::core::receiver::pcap::source::CaptureStats;
 
 impl ModeMethod for DoubleTreeTest {
-    fn execute(&self) {
+    fn execute(&self) -> Result<(), SmapError> {
 
         // 所有探测到的响应点的集合
         let mut all_nodes:AHashSet<u128> = AHashSet::new();
 
         // 初始化 DoubleTree 结构体
-        let mut double_tree_struct = Ipv6VecDoubleTree::new(self.initial_ttl, self.gap_limit, self.max_ttl, self.min_target_num);
+        let mut double_tree_struct = Ipv6VecDoubleTree::new(self.initial_ttl, self.gap_limit, self.max_ttl, self.min_target_num)?;
 
         // 初始化 输出模块
-        let mut out_mod = OutputMod::init(&self.receiver_conf.output_v6);
+        let mut out_mod = OutputMod::init(&self.receiver_conf.output_v6)?;
         
         // 生成探测目标
         {
@@ -37,6 +39,8 @@ impl ModeMethod for DoubleTreeTest {
 
         // 定义 接收线程, 发送线程 消息; 已经使用的总预算
         init_var!(u64; 0; total_send_success, total_send_failed);
+        // 各扫描阶段 捕获统计 之和
+        let mut capture_stats = CaptureStats::default();
 
         // 定义扫描开始时间
         let start_time = Local::now();
@@ -66,7 +70,7 @@ impl ModeMethod for DoubleTreeTest {
             }
 
             // 只有接收线程准备完毕后，发送线程才能继续执行
-            recv_ready!(recv_ready_receiver);
+            recv_ready!(recv_ready_receiver; receiver_res);
 
             // 执行发送线程
             let targets_list = split_chains(targets, self.sender_conf.send_thread_num);
@@ -89,16 +93,16 @@ impl ModeMethod for DoubleTreeTest {
             ending_the_receiving_thread!(self; recv_close_time_sender);
 
             // 等待接收线程按照预定时间关闭
-            if let Ok((cur_double_tree_struct, cur_all_nodes, output, new_targets)) = receiver_res.join() {
-                double_tree_struct = cur_double_tree_struct;
-                all_nodes = cur_all_nodes;
-                out_mod = output;
+            let (cur_double_tree_struct, cur_all_nodes, output, new_targets, stats) = join_receiver(receiver_res)?;
+            capture_stats.merge(&stats);
+            double_tree_struct = cur_double_tree_struct;
+            all_nodes = cur_all_nodes;
+            out_mod = output;
 
-                // 如果新生成的探测目标为空, 说明本轮拓扑探测已经结束
-                if new_targets.is_empty() { break }
+            // 如果新生成的探测目标为空, 说明本轮拓扑探测已经结束
+            if new_targets.is_empty() { break }
 
-                targets = new_targets;
-            } else { error!("{}", SYS.get_info("err", "recv_thread_err")); exit(1) }
+            targets = new_targets;
 
             // 如果已经发送的总数据包数量超过预算, 结束整个探测任务
             if total_send_success >= self.budget { break }
@@ -119,7 +123,10 @@ impl ModeMethod for DoubleTreeTest {
 
         // 输出到记录文件
         write_to_summary!(self; "PrefixTree6"; "result";
-            [start_time, end_time, running_time, hit_rate, all_nodes_len, total_send_success, total_send_failed;]
+            [start_time, end_time, running_time, hit_rate, all_nodes_len, total_send_success, total_send_failed;];
+            #[capture_stats;; ("pcap_received", received), ("pcap_dropped", dropped), ("pcap_if_dropped", if_dropped)]
         );
+
+        Ok(())
     }
 }
\ No newline at end of file
@@ -4,6 +4,8 @@ use crate::core::conf::set_conf::receiver_conf::ReceiverBaseConf;
 use crate::core::conf::set_conf::sender_conf::SenderBaseConf;
 use crate::modules::probe_modules::topology_probe::topo_mod_v6::TopoModV6;
 use crate::modules::target_iterators::IPv6FixedPrefixTree;
+use crate::modes::ModeReg;
+use crate::core::conf::modules_config::ArgSpec;
 
 mod execute;
 mod new;
@@ -35,4 +37,37 @@ pub struct DoubleTreeTest {
     pub gap_limit:u8,
     // 拓扑探测过程单次最小目标数量
     pub min_target_num:usize,
-}
\ No newline at end of file
+}
+
+
+impl DoubleTreeTest {
+
+    /// 模式注册信息
+    pub const MODE:ModeReg = ModeReg {
+        name: "ipv6_double_tree_test",
+        aliases: &[],
+        help: None,
+        args: &[
+            ArgSpec::int("budget").min(1.0),
+            ArgSpec::int("divide_dim").range(1.0, 32.0),
+            ArgSpec::int("max_prefix_len").range(2.0, 128.0),
+            ArgSpec::float("learning_rate").range(0.0, 1.0),
+            ArgSpec::str("seeds_path").required(),
+            ArgSpec::int("min_target_num").min(0.0),
+            ArgSpec::bool("rand_ord"),
+            ArgSpec::str("prefix_path").required(),
+            ArgSpec::int("min_prefix_len").range(0.0, 128.0),
+            ArgSpec::str("threshold"),
+            ArgSpec::int("extra_node_num").min(0.0),
+            ArgSpec::int("initial_ttl").range(1.0, 255.0),
+            ArgSpec::int("gap_limit").range(0.0, 255.0),
+            ArgSpec::int("prefix_tree_max_ttl").range(1.0, 255.0),
+            ArgSpec::bool("allow_leaf_expand"),
+            ArgSpec::bool("allow_layer_expand"),
+            ArgSpec::float("layer_expand_ratio").min(0.0),
+        ],
+        listed: true,
+        new: |args, conf| Ok(Box::new(DoubleTreeTest::new(args, conf)?)),
+        init: (),
+    };
+}
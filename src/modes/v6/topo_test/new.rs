@@ -1,4 +1,5 @@
 use crate::core::conf::args::Args;
+use crate::core::conf::set_conf::ScanConf;
 use crate::core::conf::modules_config::ModuleConf;
 use crate::core::conf::set_conf::base_conf::BaseConf;
 use crate::core::conf::set_conf::receiver_conf::ReceiverBaseConf;
@@ -7,17 +8,21 @@ use crate::{get_conf_from_mod_or_sys, write_to_summary};
 use crate::modes::v6::topo_test::DoubleTreeTest;
 use crate::modules::probe_modules::topology_probe::topo_mod_v6::TopoModV6;
 use crate::modules::target_iterators::IPv6FixedPrefixTree;
+use crate::core::errors::SmapError;
 
 impl DoubleTreeTest {
 
 
-    pub fn new(args:&Args) -> Self {
+    pub fn new(args:&Args, conf:&ScanConf) -> Result<Self, SmapError> {
 
         // 基础配置
-        let base_conf = BaseConf::new(args);
+        let base_conf = BaseConf::new(&conf.base)?;
 
         // 解析自定义参数
-        let module_conf = ModuleConf::new_from_vec_args(&args.custom_args, vec![]);
+        let module_conf = ModuleConf::new_from_vec_args(&args.custom_args, vec![])?;
+        // 按 模式 和 探测模块 声明的参数说明 检查自定义参数
+        let probe_name = SenderBaseConf::parse_probe_v6(&args.probe_v6, "topo6_default_probe_mod");
+        let mod_args = module_conf.check(&[Self::MODE.args, TopoModV6::args(&probe_name)?])?;
         // 预算(注意与ipv6活跃地址生成算法共用), 划分维度, 最大前缀长度, 学习率, 种子地址路径, 
         // 前缀列表路径, 最小前缀长度, 有效节点q_value阀限, 节点抽取数量, 起始ttl, 最大连续沉默数量
         get_conf_from_mod_or_sys!(module_conf; budget, divide_dim, max_prefix_len, learning_rate, seeds_path, min_target_num, rand_ord,
@@ -28,22 +33,22 @@ impl DoubleTreeTest {
         let prefix_tree = IPv6FixedPrefixTree::new(divide_dim, max_prefix_len, learning_rate, 
                                               seeds_path, prefix_path, min_prefix_len, threshold, 
                                               extra_node_num, allow_leaf_expand,allow_layer_expand, 
-                                              layer_expand_ratio, rand_ord);
+                                              layer_expand_ratio, rand_ord)?;
 
         // ipv6 拓扑探测模块
         let probe = TopoModV6::new(
-            &SenderBaseConf::parse_probe_v6(&args.probe_v6, "topo6_default_probe_mod"), module_conf.clone());
+            &probe_name, module_conf.clone())?;
 
         // 发送模块基础配置
-        let sender_conf= SenderBaseConf::new(args, &base_conf.interface, None, None,
-                                             probe.max_packet_length_v6, false, true);
+        let sender_conf= SenderBaseConf::new(conf, &base_conf.interface, None, None,
+                                             probe.max_packet_length_v6, false, true)?;
 
         // 接收模块基础配置
-        let receiver_conf= ReceiverBaseConf::new(args, vec![probe.filter_v6.clone()]);
+        let receiver_conf= ReceiverBaseConf::new(conf, &base_conf, vec![probe.filter_v6.clone()])?;
 
-        write_to_summary!(base_conf; "TestTree6"; "args"; args;);
+        write_to_summary!(base_conf; "TestTree6"; "args"; args; mod_args;);
 
-        Self {
+        Ok(Self {
             base_conf: base_conf.into(),
             sender_conf: sender_conf.into(),
             receiver_conf: receiver_conf.into(),
@@ -56,7 +61,7 @@ impl DoubleTreeTest {
             initial_ttl,
             gap_limit,
             min_target_num,
-        }
+        })
     }
     
 }
\ No newline at end of file
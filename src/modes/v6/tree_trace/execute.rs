@@ -1,8 +1,7 @@
-use std::process::exit;
 use std::thread;
 use ahash::AHashSet;
 use chrono::Local;
-use log::{error, info};
+use log::info;
 use crate::{computing_time, creat_channels, ending_the_receiving_thread, init_var, prepare_data, recv_ready, wait_sender_threads, write_to_summary, SYS};
 use crate::core::receiver::pcap::PcapReceiver;
 use crate::core::sender::send_prefixes_v6_2;
@@ -11,9 +10,12 @@ use crate::modes::v6::tree_trace::TreeTrace6;
 use crate::modules::output_modules::OutputMod;
 use crate::modules::target_iterators::TreeTraceIter;
 use crate::tools::others::split::split_chains;
+use crate::core::errors::SmapError;
+use crate::core::receiver::join_receiver;
+use crate::core::receiver::pcap::source::CaptureStats;
 
 impl ModeMethod for TreeTrace6 {
-    fn execute(&self) {
+    fn execute(&self) -> Result<(), SmapError> {
 
         // 由目标前缀文件生成目标迭代器
         let mut tree_trace_iter = TreeTraceIter::new(&self.path, self.topo_scan);
@@ -23,9 +25,11 @@ impl ModeMethod for TreeTrace6 {
         let start_time = Local::now();
         // 定义 接收线程, 发送线程 消息; 已经使用的总预算
         init_var!(u64; 0; total_send_success, total_send_failed);
+        // 各扫描阶段 捕获统计 之和
+        let mut capture_stats = CaptureStats::default();
 
         // 初始化 输出模块
-        let mut out_mod = OutputMod::init(&self.receiver_conf.output_v6);
+        let mut out_mod = OutputMod::init(&self.receiver_conf.output_v6)?;
 
         // 所有探测到的响应点的集合
         let mut all_nodes:AHashSet<u128> = AHashSet::new();
@@ -50,7 +54,7 @@ impl ModeMethod for TreeTrace6 {
             }
 
             // 只有接收线程准备完毕后，发送线程才能继续执行
-            recv_ready!(recv_ready_receiver);
+            recv_ready!(recv_ready_receiver; receiver_res);
             
             let targets_list = split_chains(
                 if self.topo_scan { tree_trace_iter.gen_topo_target(self.batch_size) } else { tree_trace_iter.gen_target(self.batch_size) }, 
@@ -75,17 +79,16 @@ impl ModeMethod for TreeTrace6 {
             ending_the_receiving_thread!(self; recv_close_time_sender);
 
             // 等待接收线程按照预定时间关闭
-            if let Ok((cur_all_nodes, recorder, output)) = receiver_res.join() {
-                all_nodes = cur_all_nodes;
-                out_mod = output;
-
-                // 更新所有前缀的 reward
-                tree_trace_iter.update_rewards(recorder);
-                
-                // 重新生成哈夫曼树
-                if tree_trace_iter.recreate_huffman(self.topo_scan) { break }
-                
-            } else { error!("{}", SYS.get_info("err", "recv_thread_err")); exit(1) }
+            let (cur_all_nodes, recorder, output, stats) = join_receiver(receiver_res)?;
+            capture_stats.merge(&stats);
+            all_nodes = cur_all_nodes;
+            out_mod = output;
+
+            // 更新所有前缀的 reward
+            tree_trace_iter.update_rewards(recorder);
+            
+            // 重新生成哈夫曼树
+            if tree_trace_iter.recreate_huffman(self.topo_scan) { break }
 
             // 当前轮次结束后打印 当前信息
             let all_nodes_len = all_nodes.len();    let hit_rate = (all_nodes_len as f64) / (total_send_success as f64);
@@ -106,8 +109,10 @@ impl ModeMethod for TreeTrace6 {
 
         // 输出到记录文件
         write_to_summary!(self; "TreeTrace6"; "result";
-            [start_time, end_time, running_time, hit_rate, all_nodes_len, total_send_success, total_send_failed;]
+            [start_time, end_time, running_time, hit_rate, all_nodes_len, total_send_success, total_send_failed;];
+            #[capture_stats;; ("pcap_received", received), ("pcap_dropped", dropped), ("pcap_if_dropped", if_dropped)]
         );
-        
+
+        Ok(())
     }
 }
\ No newline at end of file
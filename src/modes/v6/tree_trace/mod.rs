@@ -3,6 +3,8 @@ use crate::core::conf::set_conf::base_conf::BaseConf;
 use crate::core::conf::set_conf::receiver_conf::ReceiverBaseConf;
 use crate::core::conf::set_conf::sender_conf::SenderBaseConf;
 use crate::modules::probe_modules::topo_mod_v6::CodeTopoProbeModV6;
+use crate::modes::ModeReg;
+use crate::core::conf::modules_config::ArgSpec;
 
 mod new;
 mod execute;
@@ -33,4 +35,24 @@ pub struct TreeTrace6 {
     
     // 显示前缀信息数量
     pub show_prefix_num:usize,
-}
\ No newline at end of file
+}
+
+
+impl TreeTrace6 {
+
+    /// 模式注册信息
+    pub const MODE:ModeReg = ModeReg {
+        name: "tt6",
+        aliases: &[],
+        help: None,
+        args: &[
+            ArgSpec::int("budget").min(1.0),
+            ArgSpec::int("batch_size").min(1.0),
+            ArgSpec::bool("topo_scan").required(),
+            ArgSpec::int("show_prefix_num").min(0.0).required(),
+        ],
+        listed: true,
+        new: |args, conf| Ok(Box::new(TreeTrace6::new(args, conf)?)),
+        init: (),
+    };
+}
@@ -0,0 +1,117 @@
+use std::sync::{Arc, RwLock};
+use crate::modes::Helper;
+use crate::modules::output_modules::{OutputMethod, OutputMod, OutputReg};
+use crate::modules::output_modules::output_writer::OutputWriterConf;
+use crate::SYS;
+use crate::core::errors::OutputError;
+
+
+/// 结果回调函数, 接收线程每输出一行结果 调用一次
+pub type RecordCallback = Arc<dyn Fn(ScanRecord) + Send + Sync>;
+
+/// 当前注册的 结果回调函数, 由 api 在执行扫描前设置
+static RECORD_CALLBACK:RwLock<Option<RecordCallback>> = RwLock::new(None);
+
+
+/// 一行探测结果
+#[derive(Clone, Debug)]
+pub struct ScanRecord {
+
+    // 首行字段, 同一输出模块的所有结果共用
+    pub header:Arc<Vec<String>>,
+
+    pub values:Vec<String>,
+}
+
+impl ScanRecord {
+
+    /// 按 字段名称 获取字段值
+    pub fn get(&self, field:&str) -> Option<&str> {
+        self.header.iter().position(|h| h == field)
+            .and_then(|index| self.values.get(index))
+            .map(|v| v.as_str())
+    }
+}
+
+
+/// 回调输出模块, 将结果逐行交给 注册的回调函数, 不写入任何文件
+pub struct CallbackOutput {
+    callback:RecordCallback,
+    header:Arc<Vec<String>>,
+}
+
+
+impl CallbackOutput {
+
+    /// 输出模块注册信息
+    pub const OUTPUT:OutputReg = OutputReg {
+        name: "callback",
+        aliases: &[],
+        help: Some(CallbackOutput::print_help),
+        args: &[],
+        listed: true,
+        new: |_, _, writer_conf, _| CallbackOutput::new(writer_conf),
+        init: |o| Ok(Box::new(CallbackOutput::init(o)?)),
+    };
+
+    /// 设置 或 清除 结果回调函数
+    pub fn set_callback(callback:Option<RecordCallback>) {
+        *RECORD_CALLBACK.write().unwrap() = callback;
+    }
+
+    fn get_callback() -> Result<RecordCallback, OutputError> {
+        match RECORD_CALLBACK.read().unwrap().clone() {
+            Some(c) => Ok(c),
+            None => {
+                Err(OutputError::invalid("record_callback_not_set"))
+            }
+        }
+    }
+
+    pub fn new(writer_conf:&OutputWriterConf) -> Result<OutputMod, OutputError> {
+
+        // 提前检查, 以免在接收线程中才发现未注册回调函数
+        Self::get_callback()?;
+
+        Ok(OutputMod {
+            name: "callback",
+
+            buffer_capacity: 0,
+            output_file: None,
+            writer_conf: writer_conf.clone(),
+            children: vec![],
+            run_info: None,
+            conf:None
+        })
+    }
+
+    pub fn init(_o:&OutputMod) -> Result<impl OutputMethod, OutputError> {
+        Ok(CallbackOutput {
+            callback: Self::get_callback()?,
+            header: Arc::new(vec![]),
+        })
+    }
+}
+
+impl OutputMethod for CallbackOutput {
+
+    fn writer_header(&mut self, header:&Vec<String>) {
+        self.header = Arc::new(header.clone());
+    }
+
+    fn writer_line(&mut self, data:&Vec<String>) {
+        (self.callback)(ScanRecord {
+            header: self.header.clone(),
+            values: data.clone(),
+        });
+    }
+
+    fn close_output(&mut self) -> Result<(), OutputError> { Ok(()) }
+}
+
+impl Helper for CallbackOutput {
+
+    fn print_help() -> String {
+        SYS.get_info("help", "CallbackOutput")
+    }
+}
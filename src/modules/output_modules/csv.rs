@@ -1,22 +1,41 @@
-use std::fs::File;
-use std::process::exit;
 use csv::{Writer, WriterBuilder};
-use log::error;
 use crate::modes::Helper;
-use crate::modules::output_modules::{OutputMethod, OutputMod};
+use crate::modules::output_modules::{keep_first_err, OutputMethod, OutputMod, OutputReg};
+use crate::modules::output_modules::output_writer::{OutputWriter, OutputWriterConf};
 use crate::SYS;
 use crate::tools::file::get_path::get_current_path;
 use crate::tools::others::time::get_fmt_time;
+use crate::core::errors::OutputError;
 
 
 pub struct Csv{
-    csv_writer:Writer<File>,
+    csv_writer:Writer<OutputWriter>,
+
+    // 首行字段, 轮转模式下写入每个新分段的开头
+    header:Option<Vec<String>>,
+    // 下一行写入前是否需要先写入首行
+    need_header:bool,
+    // 续扫时 追加到已有结果之后, 跳过首次写入的首行
+    skip_header:bool,
+    // 第一个写入错误, 出错后不再写入
+    err:Option<OutputError>,
 }
 
 
 impl Csv {   // 定义构造方法和初始化方法
 
-    pub fn new(output_file_arg:&Option<String>, is_ipv6:bool) -> OutputMod {         // 输出模块创建， 用于初始化参数配置
+    /// 输出模块注册信息
+    pub const OUTPUT:OutputReg = OutputReg {
+        name: "csv",
+        aliases: &[],
+        help: Some(Csv::print_help),
+        args: &[],
+        listed: true,
+        new: |output_file, is_ipv6, writer_conf, _| Csv::new(output_file, is_ipv6, writer_conf),
+        init: |o| Ok(Box::new(Csv::init(o)?)),
+    };
+
+    pub fn new(output_file_arg:&Option<String>, is_ipv6:bool, writer_conf:&OutputWriterConf) -> Result<OutputMod, OutputError> {         // 输出模块创建， 用于初始化参数配置
 
         let output_file = match output_file_arg {
             Some(o) => (*o).clone(),
@@ -27,45 +46,60 @@ impl Csv {   // 定义构造方法和初始化方法
                     get_fmt_time(&SYS.get_info("conf", "output_file_pattern_v4"))
                 };
 
-                get_current_path(&child_path)
+                get_current_path(&child_path)?
             }
         };
 
 
 
-        OutputMod {
+        Ok(OutputMod {
             name: "csv",
 
             buffer_capacity: SYS.get_conf("conf", "default_output_buffer_capacity"),
             output_file: Some(output_file),
+            writer_conf: writer_conf.clone(),
+            children: vec![],
+            run_info: None,
             conf:None
-        }
+        })
 
     }
 
-    pub fn init(o:&OutputMod) -> impl OutputMethod {
+    pub fn init(o:&OutputMod) -> Result<impl OutputMethod, OutputError> {
 
         let output_file = match &o.output_file {
             None => {
-                error!("{}", SYS.get_info("err", "output_file_not_found"));
-                exit(1)
+                return Err(OutputError::invalid("output_file_not_found"))
             }
             Some(output_f) => output_f
         };
 
-        match  WriterBuilder::new()
+        let csv_writer = WriterBuilder::new()
             .buffer_capacity(o.buffer_capacity)   // 注意，io 缓冲区大小， 以字节为单位  默认 1048576
             .flexible(true)
-            .from_path(output_file){
-            Ok(csv_writer) => {
-                Csv{
-                    csv_writer,
-                }
-            }
-            Err(_) => {
-                error!("{} {}", SYS.get_info("err", "open_output_file_failed"), output_file);
-                exit(1)
-            }
+            .from_writer(OutputWriter::new(output_file, &o.writer_conf)?);
+        let skip_header = csv_writer.get_ref().appended();
+
+        Ok(Csv{
+            csv_writer,
+            header: None,
+            need_header: false,
+            skip_header,
+            err: None,
+        })
+    }
+
+    fn write_record(&mut self, data:&Vec<String>) {
+        if self.err.is_some() { return }
+        if let Err(e) = self.csv_writer.write_record(data) {
+            keep_first_err(&mut self.err, OutputError::io("output_write_record_failed").with(e));
+        }
+    }
+
+    fn flush(&mut self) {
+        if self.err.is_some() { return }
+        if let Err(e) = self.csv_writer.flush() {
+            keep_first_err(&mut self.err, OutputError::io("output_flush_failed").with(e));
         }
     }
 
@@ -73,22 +107,43 @@ impl Csv {   // 定义构造方法和初始化方法
 
 impl OutputMethod for Csv {      // 运行过程中的 回调函数
 
+    fn writer_header(&mut self, header:&Vec<String>) {
+        self.header = Some(header.clone());
+        self.need_header = false;
+        if !std::mem::take(&mut self.skip_header) {
+            self.write_record(header);
+        }
+    }
+
     fn writer_line(&mut self, data:&Vec<String>)
     {
-        self.csv_writer.write_record(data).map_err(|_|{
-            error!("{}", SYS.get_info("err", "output_write_record_failed"));
-            exit(1)
-        }).unwrap();
-    }
+        if self.need_header {
+            // 新分段以首行开头
+            self.need_header = false;
+            if let Some(header) = self.header.take() {
+                self.write_record(&header);
+                self.header = Some(header);
+            }
+        }
 
+        self.write_record(data);
+
+        // 只在记录边界处轮转, 轮转前清空缓冲区, 保证记录不会跨越分段
+        if self.csv_writer.get_ref().need_rotate() {
+            self.csv_writer.get_ref().mark_finish();
+            self.flush();
+            self.need_header = true;
+        }
+    }
 
 
-    fn close_output(&mut self){
-        self.csv_writer.flush().map_err(|_|{
-            error!("{}", SYS.get_info("err", "output_flush_failed"));
-            exit(1)
-        }).unwrap();
 
+    fn close_output(&mut self) -> Result<(), OutputError> {
+        // 结束当前分段(完成压缩流, 写入分段清单)
+        self.csv_writer.get_ref().mark_finish();
+        self.flush();
+        self.need_header = self.csv_writer.get_ref().is_rotating();
+        self.err.take().map_or(Ok(()), Err)
     }
 
 }
@@ -0,0 +1,169 @@
+use std::io::{BufWriter, Write};
+use serde_json::{Map, Number, Value};
+use crate::modes::Helper;
+use crate::modules::output_modules::{keep_first_err, OutputMethod, OutputMod, OutputReg};
+use crate::modules::output_modules::output_writer::{OutputWriter, OutputWriterConf};
+use crate::SYS;
+use crate::tools::file::get_path::get_current_path;
+use crate::tools::others::time::get_fmt_time;
+use crate::core::errors::OutputError;
+
+
+pub struct Jsonl {
+    writer:BufWriter<OutputWriter>,
+
+    // 首行字段, 作为每个 json对象 的键
+    header:Vec<String>,
+    // 第一个写入错误, 出错后不再写入
+    err:Option<OutputError>,
+}
+
+
+impl Jsonl {   // 定义构造方法和初始化方法
+
+    /// 输出模块注册信息
+    pub const OUTPUT:OutputReg = OutputReg {
+        name: "jsonl",
+        aliases: &[],
+        help: Some(Jsonl::print_help),
+        args: &[],
+        listed: true,
+        new: |output_file, is_ipv6, writer_conf, _| Jsonl::new(output_file, is_ipv6, writer_conf),
+        init: |o| Ok(Box::new(Jsonl::init(o)?)),
+    };
+
+    pub fn new(output_file_arg:&Option<String>, is_ipv6:bool, writer_conf:&OutputWriterConf) -> Result<OutputMod, OutputError> {         // 输出模块创建， 用于初始化参数配置
+
+        let output_file = match output_file_arg {
+            Some(o) => (*o).clone(),
+            None => {
+                let child_path = if is_ipv6 {
+                    get_fmt_time(&SYS.get_info("conf", "jsonl_output_file_pattern_v6"))
+                } else {
+                    get_fmt_time(&SYS.get_info("conf", "jsonl_output_file_pattern_v4"))
+                };
+
+                get_current_path(&child_path)?
+            }
+        };
+
+        Ok(OutputMod {
+            name: "jsonl",
+
+            buffer_capacity: SYS.get_conf("conf", "default_output_buffer_capacity"),
+            output_file: Some(output_file),
+            writer_conf: writer_conf.clone(),
+            children: vec![],
+            run_info: None,
+            conf:None
+        })
+    }
+
+    pub fn init(o:&OutputMod) -> Result<impl OutputMethod, OutputError> {
+
+        let output_file = match &o.output_file {
+            None => {
+                return Err(OutputError::invalid("output_file_not_found"))
+            }
+            Some(output_f) => output_f
+        };
+
+        Ok(Jsonl {
+            // 注意，io 缓冲区大小， 以字节为单位  默认 1048576
+            writer: BufWriter::with_capacity(o.buffer_capacity, OutputWriter::new(output_file, &o.writer_conf)?),
+            header: vec![],
+            err: None,
+        })
+    }
+
+    fn flush(&mut self) {
+        if self.err.is_some() { return }
+        if let Err(e) = self.writer.flush() {
+            keep_first_err(&mut self.err, OutputError::io("output_flush_failed").with(e));
+        }
+    }
+
+    /// 以 首行字段 为键, 将一行数据转换为 json对象
+    pub fn to_object(header:&[String], data:&[String]) -> Map<String, Value> {
+
+        let mut obj = Map::with_capacity(header.len().max(data.len()));
+
+        for (index, key) in header.iter().enumerate() {
+            // 缺失的字段 置为 null
+            let val = match data.get(index) {
+                Some(field) => Self::to_value(field),
+                None => Value::Null,
+            };
+            obj.insert(key.clone(), val);
+        }
+
+        // 超出首行字段数量(或未设置首行)的字段, 以 字段下标 作为键
+        for (index, field) in data.iter().enumerate().skip(header.len()) {
+            obj.insert(index.to_string(), Self::to_value(field));
+        }
+
+        obj
+    }
+
+    /// 将 字段字符串 转换为带类型的 json值
+    /// 空字符串 为 null, true/false 为 布尔值, 符合 json数字格式 的为 数字, 其余均为 字符串
+    fn to_value(field:&str) -> Value {
+
+        match field {
+            "" => Value::Null,
+            "true" => Value::Bool(true),
+            "false" => Value::Bool(false),
+            _ => {
+                // 注意: 前导零(如 007) 和 十六进制 等不符合 json数字格式 的字段将保持为字符串
+                match serde_json::from_str::<Number>(field) {
+                    Ok(n) => Value::Number(n),
+                    Err(_) => Value::String(field.to_string()),
+                }
+            }
+        }
+    }
+
+}
+
+impl OutputMethod for Jsonl {      // 运行过程中的 回调函数
+
+    fn writer_header(&mut self, header:&Vec<String>) {
+        self.header = header.clone();
+    }
+
+    fn writer_line(&mut self, data:&Vec<String>)
+    {
+        if self.err.is_some() { return }
+        let obj = Self::to_object(&self.header, data);
+
+        let res = serde_json::to_writer(&mut self.writer, &obj).map_err(|e| e.to_string())
+            .and_then(|_| self.writer.write_all(b"\n").map_err(|e| e.to_string()));
+        if let Err(e) = res {
+            keep_first_err(&mut self.err, OutputError::io("output_write_record_failed").with(e));
+            return
+        }
+
+        // 只在记录边界处轮转, 轮转前清空缓冲区, 保证记录不会跨越分段
+        if self.writer.get_ref().need_rotate() {
+            self.writer.get_ref().mark_finish();
+            self.flush();
+        }
+    }
+
+
+    fn close_output(&mut self) -> Result<(), OutputError> {
+        // 结束当前分段(完成压缩流, 写入分段清单)
+        self.writer.get_ref().mark_finish();
+        self.flush();
+        self.err.take().map_or(Ok(()), Err)
+    }
+
+}
+
+impl Helper for Jsonl {
+
+    fn print_help() -> String {
+        SYS.get_info("help", "Jsonl")
+    }
+
+}
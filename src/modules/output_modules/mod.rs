@@ -1,73 +1,136 @@
-use std::process::exit;
 use log::error;
 use crate::core::conf::modules_config::ModuleConf;
+pub use crate::modules::output_modules::callback::{CallbackOutput, RecordCallback, ScanRecord};
 pub use crate::modules::output_modules::csv::Csv;
-use crate::SYS;
-
+pub use crate::modules::output_modules::jsonl::Jsonl;
+use crate::modules::output_modules::multi::MultiOutput;
+use crate::modules::output_modules::output_writer::OutputWriterConf;
+pub use crate::modules::output_modules::sqlite::Sqlite;
+pub use crate::modules::output_modules::stream::StreamOutput;
+use crate::core::errors::OutputError;
+use crate::core::sender::request_abort;
+use crate::core::registry::{find, Registration};
+
+mod callback;
 mod csv;
+mod jsonl;
+mod multi;
+mod sqlite;
+mod stream;
+pub mod output_writer;
 
 
 
-/// 激活的 输出模块名称
-pub const OUTPUT_MODS: [&str; 1] = ["csv"];
+/// 输出模块注册信息: 构造函数 和 初始化函数
+pub type OutputReg = Registration<fn(&Option<String>, bool, &OutputWriterConf, &OutputRunInfo) -> Result<OutputMod, OutputError>,
+    fn(&OutputMod) -> Result<Box<dyn OutputMethod>, OutputError>>;
 
-impl OutputMod {
-    pub fn new(name: &str, _conf:Option<ModuleConf>, output_file:&Option<String>, is_ipv6:bool) -> OutputMod {   // 传递出去一个实现了输出模块方法的 struct
+/// 所有已注册的输出模块
+pub const OUTPUT_MODS:&[OutputReg] = &[
+    Csv::OUTPUT,
+    Jsonl::OUTPUT,
+    Sqlite::OUTPUT,
+    StreamOutput::OUTPUT_CSV,
+    StreamOutput::OUTPUT_JSON,
+    CallbackOutput::OUTPUT,
+];
 
-        if !OUTPUT_MODS.contains(&name) {   // 激活检查
-            error!("{}", SYS.get_info("err", "output_mod_not_exist"));
-            exit(1);
-        }
+impl OutputMod {
 
-        
-        // 警告: 输出模块将截断现有目标文件(定义输出模块时, 目标文件将置空), 一个模式只能使用一个输出模块
-        
-        match name {        // 各类模块的构造方法
+    /// 根据 一个或多个 输出模块名称 创建输出模块
+    /// 第 i 个输出文件对应第 i 个输出模块, 未指定输出文件的模块使用各自的默认输出路径
+    /// 指定多个输出模块时, 将创建组合输出模块, 每一行都会写入所有子模块
+    pub fn new_from_names(names:&Vec<String>, output_files:&Vec<Option<String>>, is_ipv6:bool,
+                          writer_conf:&OutputWriterConf, run_info:&OutputRunInfo) -> Result<OutputMod, OutputError> {
 
-            "csv" => Csv::new(output_file, is_ipv6),
+        if output_files.len() > names.len() {
+            return Err(OutputError::invalid("output_files_more_than_mods"))
+        }
 
+        let mut children:Vec<OutputMod> = Vec::with_capacity(names.len());
+        for (index, name) in names.iter().enumerate() {
+            let output_file = output_files.get(index).cloned().flatten();
+            let child = OutputMod::new(name, None, &output_file, is_ipv6, writer_conf, run_info)?;
 
-            _ => {
-                error!("{}", SYS.get_info("err", "output_mod_not_exist"));
-                exit(1)
+            // 多个输出模块不能写入同一个文件
+            if children.iter().any(|c| c.output_file.is_some() && c.output_file == child.output_file) {
+                return Err(OutputError::invalid("output_file_repeated").with(format!("{:?}", child.output_file)))
             }
+            children.push(child);
         }
-    }
 
+        if children.len() == 1 {
+            Ok(children.pop().unwrap())
+        } else {
+            Ok(MultiOutput::new(children, writer_conf))
+        }
+    }
 
-    pub fn init(o:&OutputMod) -> Box<dyn OutputMethod> {
+    pub fn new(name: &str, _conf:Option<ModuleConf>, output_file:&Option<String>, is_ipv6:bool, writer_conf:&OutputWriterConf, run_info:&OutputRunInfo) -> Result<OutputMod, OutputError> {   // 传递出去一个实现了输出模块方法的 struct
 
-        let name = o.name;
+        // 警告: 输出模块将截断现有目标文件(定义输出模块时, 目标文件将置空)
 
-        match name {        // 各类模块的构造方法
+        match find(OUTPUT_MODS, name) {   // 激活检查, 并调用各类模块的构造方法
+            Some(reg) => (reg.new)(output_file, is_ipv6, writer_conf, run_info),
+            None => Err(OutputError::not_exist("output_mod_not_exist")),
+        }
+    }
 
-            "csv" => Box::new(Csv::init(o)),
 
+    pub fn init(o:&OutputMod) -> Result<Box<dyn OutputMethod>, OutputError> {
 
-            _ => {
-                error!("{}", SYS.get_info("err", "output_mod_not_exist"));
-                exit(1)
-            }
+        // 组合输出模块 由多个输出模块组合而成, 不能直接选择, 因此不在注册表中
+        if o.name == "multi" {
+            return Ok(Box::new(MultiOutput::init(o)?))
         }
 
-
+        match find(OUTPUT_MODS, o.name) {
+            Some(reg) => (reg.init)(o),
+            None => Err(OutputError::not_exist("output_mod_not_exist")),
+        }
     }
 }
 
-pub trait OutputMethod:Send {     // 运行过程中的回调函数
+/// 运行过程中的回调函数
+/// 写入失败时 输出模块 只记录第一个错误 并停止写入, 错误在关闭输出时 返回给调用者
+pub trait OutputMethod:Send {
+
+    // 首行(字段名称)写入, 默认按普通行写入
+    fn writer_header(&mut self, header:&Vec<String>) {
+        self.writer_line(header);
+    }
 
     // vec<string>格式 行写入
     fn writer_line(&mut self, data:&Vec<String>);
 
+    // 区段开始(如 拓扑模式 的辅助预扫描), 默认写入区段提示行: [区段类型, ---, 区段名称]
+    fn begin_section(&mut self, kind:&str, name:&str) {
+        self.writer_line(&vec![kind.to_string(), "---".to_string(), name.to_string()]);
+    }
+
+    // 区段结束, 默认写入区段结束提示行: [区段类型, ---, over]
+    fn end_section(&mut self, kind:&str) {
+        self.writer_line(&vec![kind.to_string(), "---".to_string(), "over".to_string()]);
+    }
 
-    // 关闭输出
-    fn close_output(&mut self);
+
+    // 关闭输出, 返回 运行过程中的第一个写入错误
+    fn close_output(&mut self) -> Result<(), OutputError>;
 
 
 }
 
 
 
+/// 记录 输出模块 的第一个写入错误, 并请求 发送线程 停止发送
+pub(crate) fn keep_first_err(slot:&mut Option<OutputError>, e:OutputError) {
+    if slot.is_none() {
+        error!("{}", e);
+        request_abort();
+        *slot = Some(e);
+    }
+}
+
 
 pub struct OutputMod {
 
@@ -76,9 +139,30 @@ pub struct OutputMod {
     pub buffer_capacity:usize,
     pub output_file:Option<String>,
 
+    // 压缩 与 分段轮转 配置
+    pub writer_conf:OutputWriterConf,
+
+    // 组合输出模块的子模块, 普通输出模块为空
+    pub children:Vec<OutputMod>,
+
+    // 运行信息, 只由需要记录运行元数据的输出模块使用
+    pub run_info:Option<OutputRunInfo>,
+
     #[allow(dead_code)]
     pub conf:Option<ModuleConf>,
 }
 
 
+/// 本次运行的信息
+#[derive(Clone)]
+pub struct OutputRunInfo {
 
+    // 模式名称
+    pub mode:String,
+
+    // 全部输入参数
+    pub args:String,
+
+    // 实际使用的随机数种子(未指定时为随机生成的种子)
+    pub seed:u64,
+}
@@ -0,0 +1,72 @@
+use crate::modules::output_modules::{OutputMethod, OutputMod};
+use crate::modules::output_modules::output_writer::OutputWriterConf;
+use crate::core::errors::OutputError;
+
+
+/// 组合输出模块, 将每一行分发给所有子输出模块
+pub struct MultiOutput {
+    children:Vec<Box<dyn OutputMethod>>,
+}
+
+
+impl MultiOutput {
+
+    pub fn new(children:Vec<OutputMod>, writer_conf:&OutputWriterConf) -> OutputMod {
+
+        OutputMod {
+            name: "multi",
+
+            // 组合模块本身不持有缓冲区, 由各子模块按各自的配置分别缓冲
+            buffer_capacity: 0,
+            output_file: None,
+            writer_conf: writer_conf.clone(),
+            children,
+            run_info: None,
+            conf: None,
+        }
+    }
+
+    pub fn init(o:&OutputMod) -> Result<impl OutputMethod, OutputError> {
+
+        Ok(MultiOutput {
+            children: o.children.iter().map(OutputMod::init).collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl OutputMethod for MultiOutput {
+
+    fn writer_header(&mut self, header:&Vec<String>) {
+        for child in self.children.iter_mut() {
+            child.writer_header(header);
+        }
+    }
+
+    fn writer_line(&mut self, data:&Vec<String>) {
+        for child in self.children.iter_mut() {
+            child.writer_line(data);
+        }
+    }
+
+    fn begin_section(&mut self, kind:&str, name:&str) {
+        for child in self.children.iter_mut() {
+            child.begin_section(kind, name);
+        }
+    }
+
+    fn end_section(&mut self, kind:&str) {
+        for child in self.children.iter_mut() {
+            child.end_section(kind);
+        }
+    }
+
+    fn close_output(&mut self) -> Result<(), OutputError> {
+        // 关闭所有子模块, 返回第一个错误
+        let mut res = Ok(());
+        for child in self.children.iter_mut() {
+            let r = child.close_output();
+            if res.is_ok() { res = r; }
+        }
+        res
+    }
+}
@@ -0,0 +1,359 @@
+//! 输出模块通用写入器
+//! 支持 gzip, zstd 压缩, 以及按 文件大小 或 运行时间 进行分段轮转。
+//! 启用轮转时, 每个分段结束后都会重写分段清单, 即使程序在两次轮转之间被终止, 已完成的分段也不会丢失。
+
+use std::cell::Cell;
+use std::fs::{File, OpenOptions};
+use std::io;
+use std::io::Write;
+use std::path::Path;
+use std::time::Instant;
+use chrono::Local;
+use log::warn;
+use flate2::Compression;
+use flate2::write::GzEncoder;
+use serde_json::json;
+use crate::SYS;
+use crate::tools::others::time::get_fmt_time;
+use crate::core::errors::OutputError;
+
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum OutputCompress {
+    None,
+    Gzip,
+    Zstd,
+}
+
+#[derive(Clone)]
+pub struct OutputWriterConf {
+
+    // 压缩方式
+    pub compress:OutputCompress,
+
+    // 每个分段的最大字节数(写入磁盘的字节数), 为0时不按大小轮转
+    pub rotate_size:u64,
+
+    // 每个分段的最长持续时间(秒), 为0时不按时间轮转
+    pub rotate_seconds:u64,
+
+    // 是否追加到已有的输出文件(断点续扫时), 轮转模式下 分段文件名各不相同, 不受影响
+    pub append:bool,
+
+    // 是否允许覆盖已有的数据库(sqlite), 文件类输出模块 总是截断已有文件
+    pub overwrite:bool,
+}
+
+impl OutputWriterConf {
+
+    pub fn allow_rotate(&self) -> bool {
+        self.rotate_size != 0 || self.rotate_seconds != 0
+    }
+
+    /// 不支持 压缩 和 分段轮转 的输出模块(如 sqlite, 流式输出) 在创建时调用, 返回 不压缩 不轮转 的配置
+    /// 压缩 和 分段轮转 只作用于 文件类输出模块, 与其他输出模块 同时使用时 不影响运行
+    pub fn without_compress_rotate(&self, name:&str) -> Self {
+        if self.compress != OutputCompress::None || self.allow_rotate() {
+            warn!("{} {}", SYS.get_info("warn", "output_compress_rotate_ignored"), name);
+        }
+        Self { compress: OutputCompress::None, rotate_size: 0, rotate_seconds: 0, ..self.clone() }
+    }
+}
+
+
+/// 统计写入磁盘字节数的文件
+struct CountFile {
+    file:File,
+    bytes:u64,
+}
+
+impl Write for CountFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let len = self.file.write(buf)?;
+        self.bytes += len as u64;
+        Ok(len)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+
+enum SegmentEncoder {
+    Plain(CountFile),
+    Gzip(GzEncoder<CountFile>),
+    Zstd(zstd::Encoder<'static, CountFile>),
+}
+
+impl SegmentEncoder {
+
+    fn disk_bytes(&self) -> u64 {
+        match self {
+            SegmentEncoder::Plain(f) => f.bytes,
+            SegmentEncoder::Gzip(e) => e.get_ref().bytes,
+            SegmentEncoder::Zstd(e) => e.get_ref().bytes,
+        }
+    }
+
+    /// 结束压缩流 并 同步到磁盘
+    fn finish(self) -> io::Result<u64> {
+        let mut f = match self {
+            SegmentEncoder::Plain(f) => f,
+            SegmentEncoder::Gzip(e) => e.finish()?,
+            SegmentEncoder::Zstd(e) => e.finish()?,
+        };
+        f.flush()?;
+        f.file.sync_all()?;
+        Ok(f.bytes)
+    }
+}
+
+impl Write for SegmentEncoder {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            SegmentEncoder::Plain(f) => f.write(buf),
+            SegmentEncoder::Gzip(e) => e.write(buf),
+            SegmentEncoder::Zstd(e) => e.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            SegmentEncoder::Plain(f) => f.flush(),
+            SegmentEncoder::Gzip(e) => e.flush(),
+            SegmentEncoder::Zstd(e) => e.flush(),
+        }
+    }
+}
+
+
+/// 分段记录, 用于生成分段清单
+struct SegmentInfo {
+    path:String,
+    start_time:String,
+    end_time:Option<String>,
+    bytes:u64,
+    raw_bytes:u64,
+}
+
+
+pub struct OutputWriter {
+
+    conf:OutputWriterConf,
+
+    // 用户指定 或 由系统生成 的 输出文件路径
+    output_file:String,
+
+    // 当前分段, 为空表示当前分段已结束, 下次写入时将打开新分段
+    cur:Option<SegmentEncoder>,
+    cur_open_time:Instant,
+
+    segments:Vec<SegmentInfo>,
+
+    // 是否在下次 flush 时结束当前分段
+    // 注意: csv 等写入器不提供内部写入器的可变引用, 因此以标记的方式传递
+    finish_on_flush:Cell<bool>,
+
+    // 首个分段 是否追加到了 非空的已有文件
+    appended:bool,
+}
+
+
+impl OutputWriter {
+
+    pub fn new(output_file:&str, conf:&OutputWriterConf) -> Result<Self, OutputError> {
+
+        let mut writer = Self {
+            conf: conf.clone(),
+            output_file: output_file.to_string(),
+            cur: None,
+            cur_open_time: Instant::now(),
+            segments: vec![],
+            finish_on_flush: Cell::new(false),
+            appended: false,
+        };
+
+        if conf.append && !conf.allow_rotate() {
+            writer.appended = std::fs::metadata(writer.segment_path()).is_ok_and(|m| m.len() > 0);
+        }
+
+        // 立即创建首个分段, 与未压缩时的行为保持一致
+        writer.open_segment().map_err(|_| OutputError::io("open_output_file_failed").with(output_file))?;
+
+        Ok(writer)
+    }
+
+    pub fn is_rotating(&self) -> bool {
+        self.conf.allow_rotate()
+    }
+
+    /// 是否追加到了 已有结果之后, 此时 不应重复写入首行
+    pub fn appended(&self) -> bool {
+        self.appended
+    }
+
+    /// 当前分段是否达到轮转条件
+    /// 注意: 调用者应在 记录边界 处检查, 并在轮转前清空自身缓冲区
+    pub fn need_rotate(&self) -> bool {
+
+        if let Some(cur) = &self.cur {
+            if self.conf.rotate_size != 0 && cur.disk_bytes() >= self.conf.rotate_size {
+                return true
+            }
+
+            if self.conf.rotate_seconds != 0 && self.cur_open_time.elapsed().as_secs() >= self.conf.rotate_seconds {
+                return true
+            }
+        }
+        false
+    }
+
+    /// 标记在下次 flush 时结束当前分段 并 写入分段清单
+    /// 结束后仍可继续写入: 轮转模式下将打开新分段, 否则将以追加方式重新打开输出文件(gzip 和 zstd 均支持多帧拼接)
+    pub fn mark_finish(&self) {
+        self.finish_on_flush.set(true);
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+
+        if let Some(cur) = self.cur.take() {
+            let bytes = cur.finish()?;
+
+            if let Some(seg) = self.segments.last_mut() {
+                seg.bytes += bytes;
+                seg.end_time = Some(Local::now().to_rfc3339());
+            }
+        }
+
+        self.write_manifest()
+    }
+
+    fn segment_path(&self) -> String {
+
+        let mut path = if self.conf.allow_rotate() {
+            // 分段名称: 原文件名 + 格式化时间 + 分段序号 + 原扩展名
+            let p = Path::new(&self.output_file);
+            let stem = p.with_extension("").to_string_lossy().to_string();
+            let ext = match p.extension() {
+                Some(e) => format!(".{}", e.to_string_lossy()),
+                None => String::new(),
+            };
+
+            format!("{}{}_{}{}", stem, get_fmt_time(&SYS.get_info("conf", "output_segment_pattern")),
+                    self.segments.len(), ext)
+        } else {
+            self.output_file.clone()
+        };
+
+        let compress_ext = match self.conf.compress {
+            OutputCompress::None => "",
+            OutputCompress::Gzip => ".gz",
+            OutputCompress::Zstd => ".zst",
+        };
+        if !path.ends_with(compress_ext) {
+            path.push_str(compress_ext);
+        }
+
+        path
+    }
+
+    fn open_segment(&mut self) -> io::Result<()> {
+
+        let path = self.segment_path();
+
+        // 非轮转模式下, 重新打开 或 续扫时 以追加方式写入
+        let reopen = !self.conf.allow_rotate() && !self.segments.is_empty();
+        let file = if reopen || (self.conf.append && !self.conf.allow_rotate()) {
+            OpenOptions::new().append(true).create(true).open(&path)?
+        } else {
+            File::create(&path)?
+        };
+        let count_file = CountFile { file, bytes: 0 };
+
+        let encoder = match self.conf.compress {
+            OutputCompress::None => SegmentEncoder::Plain(count_file),
+            OutputCompress::Gzip => SegmentEncoder::Gzip(GzEncoder::new(count_file, Compression::default())),
+            OutputCompress::Zstd => SegmentEncoder::Zstd(zstd::Encoder::new(count_file, 0)?),
+        };
+
+        self.cur = Some(encoder);
+        self.cur_open_time = Instant::now();
+
+        if !reopen {
+            self.segments.push(SegmentInfo {
+                path,
+                start_time: Local::now().to_rfc3339(),
+                end_time: None,
+                bytes: 0,
+                raw_bytes: 0,
+            });
+        } else if let Some(seg) = self.segments.last_mut() {
+            seg.end_time = None;
+        }
+
+        // 新分段打开后立即更新清单, 以记录尚未完成的分段
+        self.write_manifest()
+    }
+
+    /// 重写分段清单, 只在轮转模式下生效
+    fn write_manifest(&self) -> io::Result<()> {
+
+        if !self.conf.allow_rotate() { return Ok(()) }
+
+        let segments:Vec<_> = self.segments.iter().map(|s| {
+            json!({
+                "path": s.path,
+                "start_time": s.start_time,
+                "end_time": s.end_time,
+                "bytes": s.bytes,
+                "raw_bytes": s.raw_bytes,
+                "complete": s.end_time.is_some(),
+            })
+        }).collect();
+
+        let manifest = json!({
+            "output_file": self.output_file,
+            "segments": segments,
+        });
+
+        // 先写入临时文件再重命名, 避免清单本身写到一半
+        let manifest_path = format!("{}.manifest.json", self.output_file);
+        let tmp_path = format!("{}.tmp", manifest_path);
+        std::fs::write(&tmp_path, manifest.to_string())
+            .and_then(|_| std::fs::rename(&tmp_path, &manifest_path))
+            .map_err(|e| io::Error::new(e.kind(),
+                format!("{} {}", SYS.get_info("err", "write_output_manifest_failed"), manifest_path)))
+    }
+}
+
+impl Write for OutputWriter {
+
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+
+        if self.cur.is_none() {
+            self.open_segment()?;
+        }
+
+        let len = match &mut self.cur {
+            Some(cur) => cur.write(buf)?,
+            None => 0,
+        };
+
+        if let Some(seg) = self.segments.last_mut() {
+            seg.raw_bytes += len as u64;
+        }
+        Ok(len)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        if self.finish_on_flush.replace(false) {
+            return self.finish()
+        }
+
+        match &mut self.cur {
+            Some(cur) => cur.flush(),
+            None => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,333 @@
+use chrono::Local;
+use rusqlite::Connection;
+use rusqlite::types::Value;
+use crate::modes::Helper;
+use crate::modules::output_modules::{keep_first_err, OutputMethod, OutputMod, OutputRunInfo, OutputReg};
+use crate::modules::output_modules::output_writer::OutputWriterConf;
+use crate::SYS;
+use crate::tools::file::get_path::get_current_path;
+use crate::tools::others::time::get_fmt_time;
+use crate::core::errors::OutputError;
+
+
+/// 结果表
+struct ResultTable {
+    name:String,
+    header:Vec<String>,
+    columns:Vec<String>,
+}
+
+
+/// sqlite输出模块
+/// 每个首行对应一张结果表, 列名来自 探测模块 的首行字段; 运行信息(模式, 参数, 种子, 起止时间)记录在 run_info 表中
+pub struct Sqlite {
+    conn:Connection,
+
+    tables:Vec<ResultTable>,
+    // 当前写入的结果表
+    cur_table:Option<usize>,
+    // 进入 辅助扫描 区段前的结果表, 区段结束后恢复
+    outer_table:Option<usize>,
+    // 下一张结果表的名称(来自区段提示)
+    next_table_name:Option<String>,
+
+    // 当前事务中 已写入的字节数, 达到 batch_bytes 时提交
+    batch_bytes:usize,
+    cur_batch_bytes:usize,
+    in_transaction:bool,
+
+    run_id:i64,
+
+    // 第一个写入错误, 出错后不再写入
+    err:Option<OutputError>,
+}
+
+
+impl Sqlite {
+
+    /// 输出模块注册信息
+    pub const OUTPUT:OutputReg = OutputReg {
+        name: "sqlite",
+        aliases: &[],
+        help: Some(Sqlite::print_help),
+        args: &[],
+        listed: true,
+        new: |output_file, is_ipv6, writer_conf, run_info| Sqlite::new(output_file, is_ipv6, writer_conf, run_info),
+        init: |o| Ok(Box::new(Sqlite::init(o)?)),
+    };
+
+    pub fn new(output_file_arg:&Option<String>, is_ipv6:bool, writer_conf:&OutputWriterConf, run_info:&OutputRunInfo) -> Result<OutputMod, OutputError> {
+
+        let writer_conf = &writer_conf.without_compress_rotate("sqlite");
+
+        let output_file = match output_file_arg {
+            Some(o) => (*o).clone(),
+            None => {
+                let child_path = if is_ipv6 {
+                    get_fmt_time(&SYS.get_info("conf", "sqlite_output_file_pattern_v6"))
+                } else {
+                    get_fmt_time(&SYS.get_info("conf", "sqlite_output_file_pattern_v4"))
+                };
+
+                get_current_path(&child_path)?
+            }
+        };
+
+        Ok(OutputMod {
+            name: "sqlite",
+
+            // 注意: 对于sqlite, 该值为 每个事务 写入的字节数
+            buffer_capacity: SYS.get_conf("conf", "default_output_buffer_capacity"),
+            output_file: Some(output_file),
+            writer_conf: writer_conf.clone(),
+            children: vec![],
+            run_info: Some(run_info.clone()),
+            conf:None
+        })
+    }
+
+    pub fn init(o:&OutputMod) -> Result<impl OutputMethod, OutputError> {
+
+        let output_file = match &o.output_file {
+            None => {
+                return Err(OutputError::invalid("output_file_not_found"))
+            }
+            Some(output_f) => output_f
+        };
+
+        if std::path::Path::new(output_file).exists() {
+            // 续扫时 不能追加到已有的数据库
+            if o.writer_conf.append {
+                return Err(OutputError::invalid("output_append_not_supported").with(output_file))
+            }
+            // 只有明确要求覆盖时 才删除已有的数据库
+            if !o.writer_conf.overwrite {
+                return Err(OutputError::invalid("output_db_exists").with(output_file))
+            }
+            std::fs::remove_file(output_file).map_err(|_| OutputError::io("open_output_file_failed").with(output_file))?;
+        }
+
+        let conn = Connection::open(output_file).map_err(|_| OutputError::io("open_output_file_failed").with(output_file))?;
+
+        conn.execute_batch("PRAGMA synchronous = NORMAL;\
+            CREATE TABLE run_info (id INTEGER PRIMARY KEY, mode TEXT, args TEXT, seed TEXT, start_time TEXT, end_time TEXT);").map_err(|_| OutputError::io("output_write_record_failed"))?;
+
+        let (mode, args, seed) = match &o.run_info {
+            Some(r) => (Value::Text(r.mode.clone()), Value::Text(r.args.clone()), Value::Text(r.seed.to_string())),
+            None => (Value::Null, Value::Null, Value::Null),
+        };
+        conn.execute("INSERT INTO run_info (mode, args, seed, start_time) VALUES (?1, ?2, ?3, ?4)",
+                     (mode, args, seed, Local::now().to_rfc3339())).map_err(|_| OutputError::io("output_write_record_failed"))?;
+        let run_id = conn.last_insert_rowid();
+
+        Ok(Sqlite {
+            conn,
+            tables: vec![],
+            cur_table: None,
+            outer_table: None,
+            next_table_name: None,
+            batch_bytes: o.buffer_capacity,
+            cur_batch_bytes: 0,
+            in_transaction: false,
+            run_id,
+            err: None,
+        })
+    }
+
+    fn exec(conn:&Connection, err:&mut Option<OutputError>, sql:&str) {
+        if err.is_some() { return }
+        if let Err(e) = conn.execute_batch(sql) {
+            keep_first_err(err, OutputError::io("output_write_record_failed").with(e));
+        }
+    }
+
+    /// 将 标识符 用双引号包裹, 防止与关键字冲突
+    fn quote(ident:&str) -> String {
+        format!("\"{}\"", ident.replace('"', "\"\""))
+    }
+
+    /// 将 首行字段 转换为 互不相同的列名
+    fn to_columns(header:&[String]) -> Vec<String> {
+
+        let mut columns:Vec<String> = Vec::with_capacity(header.len());
+        for (index, field) in header.iter().enumerate() {
+            let mut col = if field.is_empty() { format!("field_{}", index) } else { field.clone() };
+            if columns.contains(&col) {
+                col = format!("{}_{}", col, index);
+            }
+            columns.push(col);
+        }
+        columns
+    }
+
+    /// 将 字段字符串 转换为带类型的 sqlite值
+    /// 空字符串 为 NULL, true/false 为 1/0, 整数 和 浮点数 按数值存储, 其余均为 文本
+    fn to_value(field:&str) -> Value {
+
+        match field {
+            "" => Value::Null,
+            "true" => Value::Integer(1),
+            "false" => Value::Integer(0),
+            _ => {
+                // 注意: 与 jsonl 一致, 带前导零 或 正号 的字段保持为文本, 以免丢失原始格式
+                let first = field.as_bytes()[0];
+                let plain_num = first == b'-' || first.is_ascii_digit();
+                let lead_zero = field.len() > 1 && field.starts_with('0') && field.as_bytes()[1] != b'.';
+
+                if plain_num && !lead_zero {
+                    if let Ok(i) = field.parse::<i64>() {
+                        return Value::Integer(i)
+                    }
+                    if let Ok(f) = field.parse::<f64>() {
+                        if f.is_finite() { return Value::Real(f) }
+                    }
+                }
+                Value::Text(field.to_string())
+            }
+        }
+    }
+
+    /// 创建新的结果表, 并返回其下标
+    fn create_table(&mut self, header:&[String]) -> usize {
+
+        self.commit();
+
+        let base_name = match self.next_table_name.take() {
+            Some(n) => n,
+            None => String::from("results"),
+        };
+        let mut name = base_name.clone();
+        let mut index = 1;
+        while self.tables.iter().any(|t| t.name == name) {
+            name = format!("{}_{}", base_name, index);
+            index += 1;
+        }
+
+        let columns = Self::to_columns(header);
+        // 列不声明类型, 按写入值的类型存储
+        let cols:Vec<String> = columns.iter().map(|c| Self::quote(c)).collect();
+        Self::exec(&self.conn, &mut self.err, &format!("CREATE TABLE {} ({});", Self::quote(&name), cols.join(", ")));
+
+        self.tables.push(ResultTable {
+            name,
+            header: header.to_vec(),
+            columns,
+        });
+        self.tables.len() - 1
+    }
+
+    /// 当记录字段数量超过结果表列数时, 为结果表添加新列
+    fn extend_table(&mut self, table_index:usize, field_len:usize) {
+
+        self.commit();
+
+        let table = &mut self.tables[table_index];
+        for index in table.columns.len()..field_len {
+            let col = format!("field_{}", index);
+            Self::exec(&self.conn, &mut self.err, &format!("ALTER TABLE {} ADD COLUMN {};", Self::quote(&table.name), Self::quote(&col)));
+            table.columns.push(col);
+        }
+    }
+
+    fn begin(&mut self) {
+        if !self.in_transaction {
+            Self::exec(&self.conn, &mut self.err, "BEGIN;");
+            self.in_transaction = true;
+        }
+    }
+
+    fn commit(&mut self) {
+        if self.in_transaction {
+            Self::exec(&self.conn, &mut self.err, "COMMIT;");
+            self.in_transaction = false;
+            self.cur_batch_bytes = 0;
+        }
+    }
+}
+
+impl OutputMethod for Sqlite {
+
+    fn writer_header(&mut self, header:&Vec<String>) {
+
+        // 首行字段与已有结果表一致时(且未指定新的表名), 继续写入该表
+        if self.next_table_name.is_none() {
+            if let Some(index) = self.tables.iter().position(|t| t.header == *header) {
+                self.cur_table = Some(index);
+                return
+            }
+        }
+
+        self.cur_table = Some(self.create_table(header));
+    }
+
+    fn writer_line(&mut self, data:&Vec<String>) {
+
+        if self.err.is_some() { return }
+
+        // 未设置首行时, 以 字段下标 作为列名
+        let table_index = match self.cur_table {
+            Some(i) => i,
+            None => {
+                let header:Vec<String> = (0..data.len()).map(|i| format!("field_{}", i)).collect();
+                let index = self.create_table(&header);
+                self.cur_table = Some(index);
+                index
+            }
+        };
+
+        if data.len() > self.tables[table_index].columns.len() {
+            self.extend_table(table_index, data.len());
+        }
+
+        self.begin();
+
+        let table = &self.tables[table_index];
+        let cols:Vec<String> = table.columns[..data.len()].iter().map(|c| Self::quote(c)).collect();
+        let marks:Vec<String> = (1..=data.len()).map(|i| format!("?{}", i)).collect();
+        let sql = format!("INSERT INTO {} ({}) VALUES ({})", Self::quote(&table.name), cols.join(", "), marks.join(", "));
+
+        let res = self.conn.prepare_cached(&sql).and_then(|mut stmt| {
+            stmt.execute(rusqlite::params_from_iter(data.iter().map(|f| Self::to_value(f))))
+        });
+        if let Err(e) = res {
+            keep_first_err(&mut self.err, OutputError::io("output_write_record_failed").with(e));
+            return
+        }
+
+        // 按 写入字节数 分批提交事务
+        self.cur_batch_bytes += data.iter().map(|f| f.len()).sum::<usize>();
+        if self.cur_batch_bytes >= self.batch_bytes {
+            self.commit();
+        }
+    }
+
+    /// 区段内的记录 写入以 区段类型 和 区段名称 命名的结果表(如 拓扑模式 的 sub_scan_辅助探测模块名称), 区段结束后恢复原结果表
+    fn begin_section(&mut self, kind:&str, name:&str) {
+        self.outer_table = self.cur_table;
+        self.next_table_name = Some(format!("{}_{}", kind, name));
+    }
+
+    fn end_section(&mut self, _kind:&str) {
+        self.cur_table = self.outer_table.take();
+    }
+
+    fn close_output(&mut self) -> Result<(), OutputError> {
+
+        self.commit();
+
+        // 每次关闭时都更新结束时间(拓扑模式 等会在多个阶段间多次关闭输出)
+        if let Err(e) = self.conn.execute("UPDATE run_info SET end_time = ?1 WHERE id = ?2",
+                                          (Local::now().to_rfc3339(), self.run_id)) {
+            keep_first_err(&mut self.err, OutputError::io("output_flush_failed").with(e));
+        }
+        self.err.take().map_or(Ok(()), Err)
+    }
+}
+
+impl Helper for Sqlite {
+
+    fn print_help() -> String {
+        SYS.get_info("help", "Sqlite")
+    }
+
+}
@@ -0,0 +1,286 @@
+use std::io::{BufWriter, Write};
+use std::sync::mpsc::{sync_channel, Receiver, SyncSender, TryRecvError, TrySendError};
+use std::thread;
+use std::thread::JoinHandle;
+use csv::WriterBuilder;
+use log::warn;
+use crate::modes::Helper;
+use crate::modules::output_modules::{keep_first_err, Jsonl, OutputMethod, OutputMod, OutputReg};
+use crate::modules::output_modules::output_writer::OutputWriterConf;
+use crate::SYS;
+use crate::core::errors::OutputError;
+
+
+/// 输出到 标准输出 时使用的目标名称
+const STDOUT_TARGET:&str = "-";
+
+enum StreamMsg {
+    // 一条或多条完整记录
+    Data(Vec<u8>),
+    // 清空缓冲区后应答
+    Sync(SyncSender<()>),
+}
+
+
+/// 流式输出模块, 将结果逐行写入 标准输出 或 Unix域套接字
+/// 写入由独立线程完成, 两者之间为有界队列: 队列满时接收线程将被阻塞等待(背压), 而不是丢弃记录
+pub struct StreamOutput {
+    // true: 按行输出json对象, false: 按行输出csv
+    json:bool,
+
+    header:Vec<String>,
+
+    line_buf:Vec<u8>,
+
+    msg_sender:Option<SyncSender<StreamMsg>>,
+    // 写入线程 写入失败时 停止接收 并返回错误
+    writer_thread:Option<JoinHandle<Result<(), OutputError>>>,
+
+    // 是否已经提示过背压
+    backpressure_warned:bool,
+
+    // 第一个写入错误, 出错后不再写入
+    err:Option<OutputError>,
+}
+
+
+impl StreamOutput {
+
+    /// 输出模块注册信息
+    pub const OUTPUT_CSV:OutputReg = OutputReg {
+        name: "stream_csv",
+        aliases: &[],
+        help: Some(StreamOutput::print_help),
+        args: &[],
+        listed: true,
+        new: |output_file, _, writer_conf, _| StreamOutput::new("stream_csv", output_file, writer_conf),
+        init: |o| Ok(Box::new(StreamOutput::init(o)?)),
+    };
+
+    /// 输出模块注册信息
+    pub const OUTPUT_JSON:OutputReg = OutputReg {
+        name: "stream_json",
+        aliases: &[],
+        help: Some(StreamOutput::print_help),
+        args: &[],
+        listed: true,
+        new: |output_file, _, writer_conf, _| StreamOutput::new("stream_json", output_file, writer_conf),
+        init: |o| Ok(Box::new(StreamOutput::init(o)?)),
+    };
+
+    /// output_file_arg 为 "-" 或 未指定时输出到 标准输出, 否则视为 Unix域套接字 路径
+    pub fn new(name:&'static str, output_file_arg:&Option<String>, writer_conf:&OutputWriterConf) -> Result<OutputMod, OutputError> {
+
+        let writer_conf = &writer_conf.without_compress_rotate(name);
+
+        let target = match output_file_arg {
+            Some(o) => (*o).clone(),
+            None => STDOUT_TARGET.to_string(),
+        };
+
+        Ok(OutputMod {
+            name,
+
+            buffer_capacity: SYS.get_conf("conf", "default_output_buffer_capacity"),
+            output_file: Some(target),
+            writer_conf: writer_conf.clone(),
+            children: vec![],
+            run_info: None,
+            conf:None
+        })
+    }
+
+    pub fn init(o:&OutputMod) -> Result<impl OutputMethod, OutputError> {
+
+        let target = match &o.output_file {
+            None => {
+                return Err(OutputError::invalid("output_file_not_found"))
+            }
+            Some(t) => t.clone()
+        };
+
+        let out:Box<dyn Write + Send> = if target == STDOUT_TARGET {
+            Box::new(std::io::stdout())
+        } else {
+            Self::connect_unix_socket(&target)?
+        };
+        let out = BufWriter::with_capacity(o.buffer_capacity, out);
+
+        let (msg_sender, msg_receiver) = sync_channel(SYS.get_conf("conf", "stream_queue_capacity"));
+        let writer_thread = thread::spawn(move || {
+            Self::run_writer(out, msg_receiver)
+        });
+
+        Ok(StreamOutput {
+            json: o.name == "stream_json",
+            header: vec![],
+            line_buf: Vec::with_capacity(1024),
+            msg_sender: Some(msg_sender),
+            writer_thread: Some(writer_thread),
+            backpressure_warned: false,
+            err: None,
+        })
+    }
+
+    #[cfg(unix)]
+    fn connect_unix_socket(path:&str) -> Result<Box<dyn Write + Send>, OutputError> {
+        match std::os::unix::net::UnixStream::connect(path) {
+            Ok(s) => Ok(Box::new(s)),
+            Err(_) => {
+                Err(OutputError::io("stream_connect_failed").with(path))
+            }
+        }
+    }
+
+    #[cfg(not(unix))]
+    fn connect_unix_socket(path:&str) -> Result<Box<dyn Write + Send>, OutputError> {
+        Err(OutputError::invalid("stream_unix_socket_not_supported").with(path))
+    }
+
+    fn run_writer(mut out:BufWriter<Box<dyn Write + Send>>, msg_receiver:Receiver<StreamMsg>) -> Result<(), OutputError> {
+
+        loop {
+            let msg = match msg_receiver.try_recv() {
+                Ok(m) => m,
+                Err(TryRecvError::Empty) => {
+                    // 队列暂时为空, 将已缓冲的记录推送给消费者
+                    Self::flush(&mut out)?;
+                    match msg_receiver.recv() {
+                        Ok(m) => m,
+                        Err(_) => break,
+                    }
+                }
+                Err(TryRecvError::Disconnected) => break,
+            };
+
+            match msg {
+                StreamMsg::Data(data) => {
+                    // 消费者断开等写入错误 结束写入线程 并报告错误, 不静默丢弃记录
+                    out.write_all(&data).map_err(|e| OutputError::io("output_write_record_failed").with(e))?;
+                }
+                StreamMsg::Sync(ack) => {
+                    Self::flush(&mut out)?;
+                    let _ = ack.send(());
+                }
+            }
+        }
+
+        Self::flush(&mut out)
+    }
+
+    fn flush(out:&mut BufWriter<Box<dyn Write + Send>>) -> Result<(), OutputError> {
+        out.flush().map_err(|e| OutputError::io("output_flush_failed").with(e))
+    }
+
+    /// 写入线程 已退出时, 关闭队列 并记录 写入线程 返回的错误
+    fn keep_writer_err(&mut self) {
+        self.msg_sender.take();
+        let e = match self.writer_thread.take().map(|t| t.join()) {
+            Some(Ok(Err(e))) => e,
+            _ => OutputError::io("output_write_record_failed"),
+        };
+        keep_first_err(&mut self.err, e);
+    }
+
+    fn send(&mut self, msg:StreamMsg) {
+
+        if self.err.is_some() { return }
+        let msg_sender = match &self.msg_sender {
+            Some(s) => s,
+            None => return,
+        };
+
+        let res = match msg_sender.try_send(msg) {
+            Ok(_) => Ok(()),
+            Err(TrySendError::Full(msg)) => {
+                // 消费者处理速度不足, 阻塞等待队列空闲
+                if !self.backpressure_warned {
+                    warn!("{}", SYS.get_info("warn", "stream_backpressure"));
+                    self.backpressure_warned = true;
+                }
+                msg_sender.send(msg).map_err(|_| ())
+            }
+            Err(TrySendError::Disconnected(_)) => Err(()),
+        };
+
+        if res.is_err() {
+            self.keep_writer_err();
+        }
+    }
+
+    /// 将 当前行缓冲区 作为一条完整记录发送
+    fn send_line(&mut self) {
+        let line = std::mem::replace(&mut self.line_buf, Vec::with_capacity(1024));
+        self.send(StreamMsg::Data(line));
+    }
+}
+
+impl OutputMethod for StreamOutput {
+
+    fn writer_header(&mut self, header:&Vec<String>) {
+        self.header = header.clone();
+
+        // json格式 以首行字段作为键, 不单独输出首行
+        if !self.json {
+            self.writer_line(header);
+        }
+    }
+
+    fn writer_line(&mut self, data:&Vec<String>) {
+
+        if self.err.is_some() { return }
+        let res = if self.json {
+            serde_json::to_writer(&mut self.line_buf, &Jsonl::to_object(&self.header, data))
+                .map_err(|_| ())
+                .and_then(|_| self.line_buf.write_all(b"\n").map_err(|_| ()))
+        } else {
+            let mut csv_writer = WriterBuilder::new().flexible(true).from_writer(&mut self.line_buf);
+            csv_writer.write_record(data).and_then(|_| csv_writer.flush().map_err(|e| e.into())).map_err(|_| ())
+        };
+
+        if res.is_err() {
+            keep_first_err(&mut self.err, OutputError::invalid("output_write_record_failed"));
+            return
+        }
+
+        self.send_line();
+    }
+
+    /// 发送 流结束标记, 并等待所有记录写出
+    fn close_output(&mut self) -> Result<(), OutputError> {
+
+        let marker = if self.json {
+            SYS.get_info("conf", "stream_eos_marker_json")
+        } else {
+            SYS.get_info("conf", "stream_eos_marker_csv")
+        };
+        self.line_buf.extend_from_slice(marker.as_bytes());
+        self.line_buf.push(b'\n');
+        self.send_line();
+
+        let (ack_sender, ack_receiver) = sync_channel(1);
+        self.send(StreamMsg::Sync(ack_sender));
+        if self.err.is_none() && ack_receiver.recv().is_err() {
+            self.keep_writer_err();
+        }
+        self.err.take().map_or(Ok(()), Err)
+    }
+}
+
+impl Drop for StreamOutput {
+    fn drop(&mut self) {
+        // 关闭队列, 等待写入线程写出剩余记录
+        self.msg_sender.take();
+        if let Some(t) = self.writer_thread.take() {
+            let _ = t.join();
+        }
+    }
+}
+
+impl Helper for StreamOutput {
+
+    fn print_help() -> String {
+        SYS.get_info("help", "StreamOutput")
+    }
+
+}
@@ -1,66 +1,54 @@
 
 
-use std::process::exit;
 use std::sync::Arc;
-use log::error;
 use pcap::PacketHeader;
-use crate::core::conf::modules_config::ModuleConf;
-use crate::SYS;
+use crate::core::conf::modules_config::{ArgSpec, ModuleConf};
 use crate::tools::encryption_algorithm::aes::AesRand;
 use crate::tools::net_handle::net_interface::mac_addr::MacAddress;
 use crate::tools::net_handle::packet::v4::packet_v4_u32::Ipv4PacketU32;
+use crate::core::errors::ProbeError;
+use crate::core::registry::{find, Registration};
+use crate::core::sys::packet_sender::PacketBuf;
 
 pub use crate::modules::probe_modules::v4::{IcmpEchoV4, TcpSynScanV4, TcpSynAckScanV4, TcpSynOptV4, UdpScanV4};
 
-pub const PROBE_MODS_V4: [&str; 5] = ["icmp_v4", "tcp_syn_scan_v4", "tcp_syn_ack_scan_v4", "tcp_syn_opt_v4", "udp_scan_v4"];
+/// 探测模块注册信息: 构造函数 和 线程初始化函数
+pub type ProbeRegV4 = Registration<fn(ModuleConf, &Vec<u16>, u64, &Vec<String>) -> Result<ProbeModV4, ProbeError>,
+    fn(Arc<ProbeModV4>, Vec<u16>) -> Box<dyn ProbeMethodV4>>;
+
+/// 所有已注册的探测模块
+pub const PROBE_MODS_V4:&[ProbeRegV4] = &[
+    IcmpEchoV4::PROBE,
+    TcpSynScanV4::PROBE,
+    TcpSynAckScanV4::PROBE,
+    TcpSynOptV4::PROBE,
+    UdpScanV4::PROBE,
+];
 
 impl ProbeModV4 {
-    pub fn new(name: &str, conf:ModuleConf, tar_ports:&Vec<u16>, seed:u64, fields:&Vec<String>) -> ProbeModV4 {   // 传递出去一个实现了输出模块方法的 struct
+    pub fn new(name: &str, conf:ModuleConf, tar_ports:&Vec<u16>, seed:u64, fields:&Vec<String>) -> Result<ProbeModV4, ProbeError> {   // 传递出去一个实现了输出模块方法的 struct
 
-        if !PROBE_MODS_V4.contains(&name) {   // 激活检查
-            error!("{}", SYS.get_info("err", "v4_probe_mod_not_exist"));
-            exit(1)
+        match find(PROBE_MODS_V4, name) {   // 激活检查, 并调用各类模块的构造方法
+            Some(reg) => (reg.new)(conf, tar_ports, seed, fields),
+            None => Err(ProbeError::not_exist("v4_probe_mod_not_exist")),
         }
+    }
 
-        match name {        // 各类模块的构造方法
-
-            "icmp_v4" => IcmpEchoV4::new(conf, tar_ports, seed, fields),
-
-            "tcp_syn_scan_v4" => TcpSynScanV4::new(fields),
-            "tcp_syn_ack_scan_v4" => TcpSynAckScanV4::new(fields),
-            "tcp_syn_opt_v4" => TcpSynOptV4::new(conf, seed, fields),
-
-            "udp_scan_v4" => UdpScanV4::new(conf, seed, fields),
-
-            _ => {
-                error!("{}", SYS.get_info("err", "v4_probe_mod_not_exist"));
-                exit(1)
-            }
+    /// 按名称查找 探测模块 可接受的自定义参数
+    pub fn args(name: &str) -> Result<&'static [ArgSpec], ProbeError> {
+        match find(PROBE_MODS_V4, name) {
+            Some(reg) => Ok(reg.args),
+            None => Err(ProbeError::not_exist("v4_probe_mod_not_exist")),
         }
     }
 
 
-    pub fn init(p:Arc<ProbeModV4>, sports:Vec<u16>) -> Box<dyn ProbeMethodV4> {
-
-        let name = p.name;
-
-        match name {        // 各类模块的构造方法
+    pub fn init(p:Arc<ProbeModV4>, sports:Vec<u16>) -> Result<Box<dyn ProbeMethodV4>, ProbeError> {
 
-            "icmp_v4" => Box::new(IcmpEchoV4::init(p)),
-
-            "tcp_syn_scan_v4" => Box::new(TcpSynScanV4::init(p, sports)),
-            "tcp_syn_ack_scan_v4" => Box::new(TcpSynAckScanV4::init(p, sports)),
-            "tcp_syn_opt_v4" => Box::new(TcpSynOptV4::init(p, sports)),
-
-            "udp_scan_v4" => Box::new(UdpScanV4::init(p, sports)),
-
-            _ => {
-                error!("{}", SYS.get_info("err", "v4_probe_mod_not_exist"));
-                exit(1)
-            }
+        match find(PROBE_MODS_V4, p.name) {
+            Some(reg) => Ok((reg.init)(p, sports)),
+            None => Err(ProbeError::not_exist("v4_probe_mod_not_exist")),
         }
-
-
     }
 }
 
@@ -69,7 +57,15 @@ pub trait ProbeMethodV4 {
 
     fn thread_initialize_v4(&mut self, local_mac:&MacAddress, gateway_mac:&MacAddress, rand_u16:u16);
 
-    fn make_packet_v4(&self, source_ip:u32, dest_ip:u32, dest_port:u16, ttl:Option<u8>, aes_rand:&AesRand) -> Vec<u8>;
+    /// 将数据包 写入 空的缓冲区, 批量发送时 缓冲区为 发送槽位, 其内存在各发送轮次间 重复使用
+    fn write_packet_v4(&self, source_ip:u32, dest_ip:u32, dest_port:u16, ttl:Option<u8>, aes_rand:&AesRand, packet:&mut PacketBuf);
+
+    /// 生成 新的数据包
+    fn make_packet_v4(&self, source_ip:u32, dest_ip:u32, dest_port:u16, ttl:Option<u8>, aes_rand:&AesRand) -> Vec<u8> {
+        let mut packet = PacketBuf::new();
+        self.write_packet_v4(source_ip, dest_ip, dest_port, ttl, aes_rand, &mut packet);
+        packet.into_vec()
+    }
 
     fn is_successful(&self, data_link_header:&[u8], ipv4_header:&Ipv4PacketU32, net_layer_data:&[u8], aes_rand:&AesRand) -> bool;
 
@@ -1,72 +1,56 @@
 
 
 
-use std::process::exit;
 use std::sync::Arc;
-use log::error;
 use pcap::PacketHeader;
-use crate::core::conf::modules_config::ModuleConf;
-use crate::SYS;
+use crate::core::conf::modules_config::{ArgSpec, ModuleConf};
 use crate::tools::encryption_algorithm::aes::AesRand;
 use crate::tools::net_handle::net_interface::mac_addr::MacAddress;
 use crate::tools::net_handle::packet::v6::packet_v6_u128::Ipv6PacketU128;
+use crate::core::errors::ProbeError;
+use crate::core::registry::{find, Registration};
+use crate::core::sys::packet_sender::PacketBuf;
 
 pub use crate::modules::probe_modules::v6::{IcmpEchoV6, TcpSynScanV6, TcpSynAckScanV6, TcpSynOptV6, UdpScanV6, TcpSynPayloadScan};
 
-pub const PROBE_MODS_V6: [&str; 6] = ["icmp_v6", "tcp_syn_scan_v6", "tcp_syn_ack_scan_v6", "tcp_syn_opt_v6", "udp_scan_v6", "tcp_syn_payload_scan_v6"];
+/// 探测模块注册信息: 构造函数 和 线程初始化函数
+pub type ProbeRegV6 = Registration<fn(ModuleConf, &Vec<u16>, u64, &Vec<String>) -> Result<ProbeModV6, ProbeError>,
+    fn(Arc<ProbeModV6>, Vec<u16>) -> Box<dyn ProbeMethodV6>>;
+
+/// 所有已注册的探测模块
+pub const PROBE_MODS_V6:&[ProbeRegV6] = &[
+    IcmpEchoV6::PROBE,
+    TcpSynScanV6::PROBE,
+    TcpSynAckScanV6::PROBE,
+    TcpSynOptV6::PROBE,
+    UdpScanV6::PROBE,
+    TcpSynPayloadScan::PROBE,
+];
 
 impl ProbeModV6 {
-    pub fn new(name: &str, conf:ModuleConf, tar_ports:&Vec<u16>, seed:u64, fields:&Vec<String>) -> ProbeModV6 {   // 传递出去一个实现了输出模块方法的 struct
+    pub fn new(name: &str, conf:ModuleConf, tar_ports:&Vec<u16>, seed:u64, fields:&Vec<String>) -> Result<ProbeModV6, ProbeError> {   // 传递出去一个实现了输出模块方法的 struct
 
-        if !PROBE_MODS_V6.contains(&name) {   // 激活检查
-            error!("{}", SYS.get_info("err", "v6_probe_mod_not_exist"));
-            exit(1)
+        match find(PROBE_MODS_V6, name) {   // 激活检查, 并调用各类模块的构造方法
+            Some(reg) => (reg.new)(conf, tar_ports, seed, fields),
+            None => Err(ProbeError::not_exist("v6_probe_mod_not_exist")),
         }
+    }
 
-        match name {        // 各类模块的构造方法
-
-            "icmp_v6" => IcmpEchoV6::new(tar_ports, fields),
-
-            "tcp_syn_scan_v6" => TcpSynScanV6::new(fields),
-            "tcp_syn_ack_scan_v6" => TcpSynAckScanV6::new(fields),
-            "tcp_syn_opt_v6" => TcpSynOptV6::new(conf, seed, fields),
-
-            "udp_scan_v6" => UdpScanV6::new(conf, seed, fields),
-            
-            "tcp_syn_payload_scan_v6" => TcpSynPayloadScan::new(conf, seed, fields),
-
-            _ => {
-                error!("{}", SYS.get_info("err", "v6_probe_mod_not_exist"));
-                exit(1)
-            }
+    /// 按名称查找 探测模块 可接受的自定义参数
+    pub fn args(name: &str) -> Result<&'static [ArgSpec], ProbeError> {
+        match find(PROBE_MODS_V6, name) {
+            Some(reg) => Ok(reg.args),
+            None => Err(ProbeError::not_exist("v6_probe_mod_not_exist")),
         }
     }
 
 
-    pub fn init(p:Arc<ProbeModV6>, sports:Vec<u16>) -> Box<dyn ProbeMethodV6> {
-
-        let name = p.name;
-
-        match name {        // 各类模块的构造方法
+    pub fn init(p:Arc<ProbeModV6>, sports:Vec<u16>) -> Result<Box<dyn ProbeMethodV6>, ProbeError> {
 
-            "icmp_v6" => Box::new(IcmpEchoV6::init(p)),
-
-            "tcp_syn_scan_v6" => Box::new(TcpSynScanV6::init(p, sports)),
-            "tcp_syn_ack_scan_v6" => Box::new(TcpSynAckScanV6::init(p, sports)),
-            "tcp_syn_opt_v6" => Box::new(TcpSynOptV6::init(p, sports)),
-
-            "udp_scan_v6" => Box::new(UdpScanV6::init(p, sports)),
-            
-            "tcp_syn_payload_scan_v6" => Box::new(TcpSynPayloadScan::init(p, sports)),
-
-
-            _ => {
-                error!("{}", SYS.get_info("err", "v6_probe_mod_not_exist"));
-                exit(1)
-            }
+        match find(PROBE_MODS_V6, p.name) {
+            Some(reg) => Ok((reg.init)(p, sports)),
+            None => Err(ProbeError::not_exist("v6_probe_mod_not_exist")),
         }
-
-
     }
 }
 
@@ -77,7 +61,15 @@ pub trait ProbeMethodV6 {
     fn thread_initialize_v6(&mut self, local_mac:&MacAddress, gateway_mac:&MacAddress);
 
 
-    fn make_packet_v6(&self, source_ip:u128, dest_ip:u128, dest_port:u16, hop_limit:Option<u8>, aes_rand:&AesRand) -> Vec<u8>;
+    /// 将数据包 写入 空的缓冲区, 批量发送时 缓冲区为 发送槽位, 其内存在各发送轮次间 重复使用
+    fn write_packet_v6(&self, source_ip:u128, dest_ip:u128, dest_port:u16, hop_limit:Option<u8>, aes_rand:&AesRand, packet:&mut PacketBuf);
+
+    /// 生成 新的数据包
+    fn make_packet_v6(&self, source_ip:u128, dest_ip:u128, dest_port:u16, hop_limit:Option<u8>, aes_rand:&AesRand) -> Vec<u8> {
+        let mut packet = PacketBuf::new();
+        self.write_packet_v6(source_ip, dest_ip, dest_port, hop_limit, aes_rand, &mut packet);
+        packet.into_vec()
+    }
 
     fn is_successful(&self, data_link_header:&[u8], ipv6_header:&Ipv6PacketU128, net_layer_data:&[u8], aes_rand:&AesRand) -> bool;
 
@@ -1,13 +1,14 @@
 mod method;
 
 use std::sync::Arc;
-use crate::core::conf::modules_config::ModuleConf;
+use crate::core::conf::modules_config::{ArgSpec, ModuleConf};
 use crate::modes::Helper;
-use crate::modules::probe_modules::probe_mod_v4::{ProbeMethodV4, ProbeModV4};
+use crate::modules::probe_modules::probe_mod_v4::{ProbeMethodV4, ProbeModV4, ProbeRegV4};
 use crate::modules::probe_modules::tools::payload::get_payload;
 use crate::{not_use_port_check, SYS};
 use crate::tools::file::get_path::get_current_path;
 use crate::tools::net_handle::packet::v4::icmp_v4::fields::IcmpV4Fields;
+use crate::core::errors::ProbeError;
 
 
 pub struct IcmpEchoV4 {
@@ -24,19 +25,30 @@ pub struct IcmpEchoV4 {
 
 impl IcmpEchoV4 {   // 定义构造方法和初始化方法
 
-    pub fn new(mod_conf:ModuleConf, tar_ports:&Vec<u16>, seed:u64, fields:&Vec<String>) -> ProbeModV4 {         // 输出模块创建， 用于初始化参数配置
+    /// 探测模块注册信息
+    pub const PROBE:ProbeRegV4 = ProbeRegV4 {
+        name: "icmp_v4",
+        aliases: &[],
+        help: Some(IcmpEchoV4::print_help),
+        args: &[ArgSpec::str("payload").unset()],
+        listed: true,
+        new: IcmpEchoV4::new,
+        init: |p, _| Box::new(IcmpEchoV4::init(p)),
+    };
+
+    pub fn new(mod_conf:ModuleConf, tar_ports:&Vec<u16>, seed:u64, fields:&Vec<String>) -> Result<ProbeModV4, ProbeError> {         // 输出模块创建， 用于初始化参数配置
 
         // 不使用端口的模块, 强制目标端口为 0
         not_use_port_check!(tar_ports);
 
         // 得到 payload文件路径
-        let payload_path = get_current_path(&SYS.get_info("conf", "default_payload_file"));
+        let payload_path = get_current_path(&SYS.get_info("conf", "default_payload_file"))?;
 
         // 根据自定义参数 payload, 得到具体的 payload字节向量
         let payload = get_payload(mod_conf.get_info(&"payload".to_string()),
-                                  payload_path, seed,0, 8);
+                                  payload_path, seed,0, 8)?;
 
-        ProbeModV4 {
+        Ok(ProbeModV4 {
             name:"icmp_v4",
 
             max_packet_length_v4: 42 + payload.len(),                       // 数据链路层报头(14字节) + ipv4报头(20字节) + icmp_v4报头(8字节) + icmp载荷 = 42字节 +
@@ -52,7 +64,7 @@ impl IcmpEchoV4 {   // 定义构造方法和初始化方法
 
             fields:fields.clone(),
             conf:None,
-        }
+        })
 
     }
 
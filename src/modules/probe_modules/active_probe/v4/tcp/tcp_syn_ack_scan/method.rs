@@ -10,6 +10,7 @@ use crate::tools::net_handle::net_interface::mac_addr::MacAddress;
 use crate::tools::net_handle::packet::tcp::TcpPacket;
 use crate::tools::net_handle::packet::v4::icmp_v4::ICMP_UNREACH;
 use crate::tools::net_handle::packet::v4::packet_v4_u32::Ipv4PacketU32;
+use crate::core::sys::packet_sender::PacketBuf;
 
 impl ProbeMethodV4 for TcpSynAckScanV4 {
     fn thread_initialize_v4(&mut self, local_mac: &MacAddress, gateway_mac: &MacAddress, rand_u16: u16) {
@@ -65,8 +66,9 @@ impl ProbeMethodV4 for TcpSynAckScanV4 {
         }
     }
 
-    fn make_packet_v4(&self, source_ip: u32, dest_ip: u32, dest_port: u16, ttl: Option<u8>, aes_rand: &AesRand) -> Vec<u8> {
-        let mut packet = Vec::with_capacity(self.max_len);
+    fn write_packet_v4(&self, source_ip: u32, dest_ip: u32, dest_port: u16, ttl: Option<u8>, aes_rand: &AesRand, packet:&mut PacketBuf) {
+        // 按最大数据包长度 预留缓冲区容量
+        packet.reserve(self.max_len);
 
         let source_ip_bytes = source_ip.to_be_bytes();
         let dest_ip_bytes = dest_ip.to_be_bytes();
@@ -126,7 +128,6 @@ impl ProbeMethodV4 for TcpSynAckScanV4 {
             packet[50] = tcp_check_sum_bytes[0];
             packet[51] = tcp_check_sum_bytes[1];
         }
-        packet
     }
 
     fn is_successful(&self, _data_link_header:&[u8], ipv4_header:&Ipv4PacketU32, net_layer_data:&[u8], aes_rand:&AesRand) -> bool {
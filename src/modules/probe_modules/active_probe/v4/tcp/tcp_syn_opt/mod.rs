@@ -1,11 +1,12 @@
 use std::sync::Arc;
-use crate::core::conf::modules_config::ModuleConf;
+use crate::core::conf::modules_config::{ArgSpec, ModuleConf};
 use crate::modes::Helper;
-use crate::modules::probe_modules::probe_mod_v4::{ProbeMethodV4, ProbeModV4};
+use crate::modules::probe_modules::probe_mod_v4::{ProbeMethodV4, ProbeModV4, ProbeRegV4};
 use crate::modules::probe_modules::tools::payload::get_payload;
 use crate::SYS;
 use crate::tools::file::get_path::get_current_path;
 use crate::tools::net_handle::packet::tcp::opt::opt_fields::TcpOptFields;
+use crate::core::errors::ProbeError;
 
 mod method;
 
@@ -27,13 +28,24 @@ pub struct TcpSynOptV4 {
 
 impl TcpSynOptV4 {
 
-    pub fn new(mod_conf:ModuleConf, seed:u64, fields:&Vec<String>) -> ProbeModV4 {
+    /// 探测模块注册信息
+    pub const PROBE:ProbeRegV4 = ProbeRegV4 {
+        name: "tcp_syn_opt_v4",
+        aliases: &[],
+        help: Some(TcpSynOptV4::print_help),
+        args: &[ArgSpec::str("payload").unset()],
+        listed: true,
+        new: |conf, _, seed, fields| TcpSynOptV4::new(conf, seed, fields),
+        init: |p, sports| Box::new(TcpSynOptV4::init(p, sports)),
+    };
+
+    pub fn new(mod_conf:ModuleConf, seed:u64, fields:&Vec<String>) -> Result<ProbeModV4, ProbeError> {
 
         // 得到 payload文件路径
-        let payload_path = get_current_path(&SYS.get_info("conf", "default_payload_file"));
+        let payload_path = get_current_path(&SYS.get_info("conf", "default_payload_file"))?;
 
         let mut opt_payload = get_payload(mod_conf.get_info(&"payload".to_string()),
-                                          payload_path, seed, 1, 40);
+                                          payload_path, seed, 1, 40)?;
 
         // 将负载长度填充为 4 的字节倍数
         let mut fill_bytes_len = 4 - (opt_payload.len() % 4);
@@ -41,7 +53,7 @@ impl TcpSynOptV4 {
         opt_payload.extend(vec![0; fill_bytes_len]);
 
 
-        ProbeModV4 {
+        Ok(ProbeModV4 {
             name: "tcp_syn_opt_v4",
             max_packet_length_v4: 54 + opt_payload.len(),                       // 以太网头(14字节) + ipv4报头(20字节) + tcp基本首部(20字节) + tcp选项字段 = 54字节 +
             snap_len_v4: 136,                                                   // 以太网头(14字节) + ipv4报头(20字节) + tcp基本首部(20字节) + tcp选项字段(40字节) = 94
@@ -54,7 +66,7 @@ impl TcpSynOptV4 {
 
             fields: fields.clone(),
             conf: None,
-        }
+        })
     }
 
     pub fn init(p:Arc<ProbeModV4>, sports:Vec<u16>) -> impl ProbeMethodV4 {
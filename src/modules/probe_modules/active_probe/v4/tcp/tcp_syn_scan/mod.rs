@@ -2,7 +2,7 @@ mod method;
 
 use std::sync::Arc;
 use crate::modes::Helper;
-use crate::modules::probe_modules::probe_mod_v4::{ProbeMethodV4, ProbeModV4};
+use crate::modules::probe_modules::probe_mod_v4::{ProbeMethodV4, ProbeModV4, ProbeRegV4};
 use crate::SYS;
 use crate::tools::net_handle::packet::tcp::fields::TcpFields;
 
@@ -21,6 +21,17 @@ pub struct TcpSynScanV4 {
 
 impl TcpSynScanV4 {
 
+    /// 探测模块注册信息
+    pub const PROBE:ProbeRegV4 = ProbeRegV4 {
+        name: "tcp_syn_scan_v4",
+        aliases: &[],
+        help: Some(TcpSynScanV4::print_help),
+        args: &[],
+        listed: true,
+        new: |_, _, _, fields| Ok(TcpSynScanV4::new(fields)),
+        init: |p, sports| Box::new(TcpSynScanV4::init(p, sports)),
+    };
+
     pub fn new(fields:&Vec<String>) -> ProbeModV4 {
 
         ProbeModV4 {
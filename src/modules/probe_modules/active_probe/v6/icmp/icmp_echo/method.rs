@@ -9,6 +9,7 @@ use crate::tools::net_handle::net_interface::mac_addr::MacAddress;
 use crate::tools::net_handle::packet::v6::icmp_v6::fields::IcmpV6Fields;
 use crate::tools::net_handle::packet::v6::icmp_v6::IcmpV6Packet;
 use crate::tools::net_handle::packet::v6::packet_v6_u128::Ipv6PacketU128;
+use crate::core::sys::packet_sender::PacketBuf;
 
 impl ProbeMethodV6 for IcmpEchoV6 {
 
@@ -34,9 +35,9 @@ impl ProbeMethodV6 for IcmpEchoV6 {
     }
 
     /// 发送线程 制作数据包
-    fn make_packet_v6(&self, source_ip: u128, dest_ip: u128, _dest_port: u16, hop_limit:Option<u8>, aes_rand:&AesRand) -> Vec<u8> {
-        // 按最大数据包长度设置 向量容量
-        let mut packet = Vec::with_capacity(70);
+    fn write_packet_v6(&self, source_ip: u128, dest_ip: u128, _dest_port: u16, hop_limit:Option<u8>, aes_rand:&AesRand, packet:&mut PacketBuf) {
+        // 按最大数据包长度 预留缓冲区容量
+        packet.reserve(70);
 
         let source_ip_bytes = source_ip.to_be_bytes();
         let dest_ip_bytes = dest_ip.to_be_bytes();
@@ -87,7 +88,6 @@ impl ProbeMethodV6 for IcmpEchoV6 {
             packet[56] = check_sum_bytes[0];
             packet[57] = check_sum_bytes[1];
         }
-        packet
     }
 
     fn is_successful(&self, _data_link_header:&[u8], ipv6_header:&Ipv6PacketU128, net_layer_data:&[u8], aes_rand:&AesRand) -> bool {
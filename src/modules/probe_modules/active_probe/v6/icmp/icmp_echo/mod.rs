@@ -2,9 +2,10 @@ mod method;
 
 use std::sync::Arc;
 use crate::modes::Helper;
-use crate::modules::probe_modules::probe_mod_v6::{ProbeMethodV6, ProbeModV6};
+use crate::modules::probe_modules::probe_mod_v6::{ProbeMethodV6, ProbeModV6, ProbeRegV6};
 use crate::{not_use_port_check, SYS};
 use crate::tools::net_handle::packet::v6::icmp_v6::fields::IcmpV6Fields;
+use crate::core::errors::ProbeError;
 
 
 pub struct IcmpEchoV6 {
@@ -18,12 +19,23 @@ pub struct IcmpEchoV6 {
 
 impl IcmpEchoV6 {   // 定义构造方法和初始化方法
 
-    pub fn new(tar_ports:&Vec<u16>, fields:&Vec<String>) -> ProbeModV6 {         // 输出模块创建， 用于初始化参数配置
+    /// 探测模块注册信息
+    pub const PROBE:ProbeRegV6 = ProbeRegV6 {
+        name: "icmp_v6",
+        aliases: &[],
+        help: Some(IcmpEchoV6::print_help),
+        args: &[],
+        listed: true,
+        new: |_, tar_ports, _, fields| IcmpEchoV6::new(tar_ports, fields),
+        init: |p, _| Box::new(IcmpEchoV6::init(p)),
+    };
+
+    pub fn new(tar_ports:&Vec<u16>, fields:&Vec<String>) -> Result<ProbeModV6, ProbeError> {         // 输出模块创建， 用于初始化参数配置
 
         // 不使用端口的模块, 强制目标端口为 0
         not_use_port_check!(tar_ports);
 
-        ProbeModV6 {
+        Ok(ProbeModV6 {
             name:"icmp_v6",
 
             max_packet_length_v6: 70,       // 以太网首部(14字节) + ipv6报头(40字节) + icmp报头(8字节) + icmp数据(8字节) = 70
@@ -36,7 +48,7 @@ impl IcmpEchoV6 {   // 定义构造方法和初始化方法
             payload: vec![],
             fields:fields.clone(),
             conf:None
-        }
+        })
 
     }
 
@@ -10,6 +10,7 @@ use crate::tools::net_handle::net_interface::mac_addr::MacAddress;
 use crate::tools::net_handle::packet::tcp::opt::opt_fields::TcpOptFields;
 use crate::tools::net_handle::packet::tcp::TcpPacket;
 use crate::tools::net_handle::packet::v6::packet_v6_u128::Ipv6PacketU128;
+use crate::core::sys::packet_sender::PacketBuf;
 
 impl ProbeMethodV6 for TcpSynOptV6 {
     fn thread_initialize_v6(&mut self, local_mac: &MacAddress, gateway_mac: &MacAddress) {
@@ -51,9 +52,9 @@ impl ProbeMethodV6 for TcpSynOptV6 {
         }.get_u8_vec_after_sequence());
     }
 
-    fn make_packet_v6(&self, source_ip: u128, dest_ip: u128, dest_port: u16, hop_limit: Option<u8>, aes_rand: &AesRand) -> Vec<u8> {
-        // 按最大数据包长度设置 向量容量
-        let mut packet = Vec::with_capacity(self.max_len);
+    fn write_packet_v6(&self, source_ip: u128, dest_ip: u128, dest_port: u16, hop_limit: Option<u8>, aes_rand: &AesRand, packet:&mut PacketBuf) {
+        // 按最大数据包长度 预留缓冲区容量
+        packet.reserve(self.max_len);
 
         let source_ip_bytes = source_ip.to_be_bytes();
         let dest_ip_bytes = dest_ip.to_be_bytes();
@@ -108,7 +109,6 @@ impl ProbeMethodV6 for TcpSynOptV6 {
             packet[70] = tcp_check_sum_bytes[0];
             packet[71] = tcp_check_sum_bytes[1];
         }
-        packet
     }
 
     fn is_successful(&self, _data_link_header:&[u8], ipv6_header:&Ipv6PacketU128, net_layer_data:&[u8], aes_rand:&AesRand) -> bool {
@@ -1,13 +1,14 @@
 mod method;
 
 use std::sync::Arc;
-use crate::core::conf::modules_config::ModuleConf;
+use crate::core::conf::modules_config::{ArgSpec, ModuleConf};
 use crate::modes::Helper;
-use crate::modules::probe_modules::probe_mod_v6::{ProbeMethodV6, ProbeModV6};
+use crate::modules::probe_modules::probe_mod_v6::{ProbeMethodV6, ProbeModV6, ProbeRegV6};
 use crate::modules::probe_modules::tools::payload::get_payload;
 use crate::SYS;
 use crate::tools::file::get_path::get_current_path;
 use crate::tools::net_handle::packet::tcp::opt::opt_fields::TcpOptFields;
+use crate::core::errors::ProbeError;
 
 
 pub struct TcpSynOptV6 {
@@ -27,19 +28,30 @@ pub struct TcpSynOptV6 {
 
 impl TcpSynOptV6 {
 
-    pub fn new(mod_conf:ModuleConf, seed:u64, fields:&Vec<String>) -> ProbeModV6 {
+    /// 探测模块注册信息
+    pub const PROBE:ProbeRegV6 = ProbeRegV6 {
+        name: "tcp_syn_opt_v6",
+        aliases: &[],
+        help: Some(TcpSynOptV6::print_help),
+        args: &[ArgSpec::str("payload").unset()],
+        listed: true,
+        new: |conf, _, seed, fields| TcpSynOptV6::new(conf, seed, fields),
+        init: |p, sports| Box::new(TcpSynOptV6::init(p, sports)),
+    };
+
+    pub fn new(mod_conf:ModuleConf, seed:u64, fields:&Vec<String>) -> Result<ProbeModV6, ProbeError> {
 
         // 得到 payload文件路径
-        let payload_path = get_current_path(&SYS.get_info("conf", "default_payload_file"));
+        let payload_path = get_current_path(&SYS.get_info("conf", "default_payload_file"))?;
         // 将负载长度填充为 4 的字节倍数
         let mut opt_payload = get_payload(mod_conf.get_info(&"payload".to_string()),
-                                          payload_path, seed, 1, 40);
+                                          payload_path, seed, 1, 40)?;
         let mut fill_bytes_len = 4 - (opt_payload.len() % 4);
         if fill_bytes_len == 4 { fill_bytes_len = 0; }
         opt_payload.extend(vec![0; fill_bytes_len]);
 
 
-        ProbeModV6 {
+        Ok(ProbeModV6 {
             name: "tcp_syn_opt_v6",
             max_packet_length_v6: 74 + opt_payload.len(),             //  以太网头(14字节) + ipv6报头(40字节) + tcp基本首部(20字节) + tcp选项字段 = 74字节 +
             snap_len_v6: 156,                                         //  数据链路层报头(14字节) + ipv6报头(40字节) + tcp报头(20字节) + tcp选项字段最大长度(40字节) = 114
@@ -52,7 +64,7 @@ impl TcpSynOptV6 {
 
             fields: fields.clone(),
             conf: None,
-        }
+        })
     }
 
     pub fn init(p:Arc<ProbeModV6>, sports:Vec<u16>) -> impl ProbeMethodV6 {
@@ -1,11 +1,12 @@
 use std::sync::Arc;
-use crate::core::conf::modules_config::ModuleConf;
-use crate::modules::probe_modules::probe_mod_v6::{ProbeMethodV6, ProbeModV6};
+use crate::core::conf::modules_config::{ArgSpec, ModuleConf};
+use crate::modules::probe_modules::probe_mod_v6::{ProbeMethodV6, ProbeModV6, ProbeRegV6};
 use crate::modules::probe_modules::tools::payload::get_payload;
 use crate::SYS;
 use crate::modes::Helper;
 use crate::tools::file::get_path::get_current_path;
 use crate::tools::net_handle::packet::tcp::fields::TcpFields;
+use crate::core::errors::ProbeError;
 
 mod method;
 
@@ -37,25 +38,43 @@ pub struct TcpSynPayloadScan {
 
 impl TcpSynPayloadScan {
 
+    /// 探测模块注册信息
+    pub const PROBE:ProbeRegV6 = ProbeRegV6 {
+        name: "tcp_syn_payload_scan_v6",
+        aliases: &[],
+        help: Some(TcpSynPayloadScan::print_help),
+        args: &[
+            ArgSpec::str("opt_payload").unset(),
+            ArgSpec::str("payload").unset(),
+            ArgSpec::bool("print_data"),
+        ],
+        listed: true,
+        new: |conf, _, seed, fields| TcpSynPayloadScan::new(conf, seed, fields),
+        init: |p, sports| Box::new(TcpSynPayloadScan::init(p, sports)),
+    };
 
-    pub fn new(mod_conf:ModuleConf, seed:u64, fields:&Vec<String>) -> ProbeModV6 {
+
+    pub fn new(mod_conf:ModuleConf, seed:u64, fields:&Vec<String>) -> Result<ProbeModV6, ProbeError> {
+
+        // 构造时 检查自定义参数, 线程初始化时 不再出错
+        mod_conf.get_conf_or_from_sys::<bool>(&"print_data".to_string())?;
 
         // 得到 payload文件路径
-        let payload_path = get_current_path(&SYS.get_info("conf", "default_payload_file"));
+        let payload_path = get_current_path(&SYS.get_info("conf", "default_payload_file"))?;
 
         // 将负载长度填充为 4 的字节倍数
         let mut opt_payload = get_payload(mod_conf.get_info(&"opt_payload".to_string()),
-                                          payload_path.clone(), seed, 0, 40);
+                                          payload_path.clone(), seed, 0, 40)?;
         let mut fill_bytes_len = 4 - (opt_payload.len() % 4);
         if fill_bytes_len == 4 { fill_bytes_len = 0; }
         opt_payload.extend(vec![0; fill_bytes_len]);
 
         // 应用层载荷
         let payload = get_payload(mod_conf.get_info(&"payload".to_string()),
-                                  payload_path, seed, 0, 500);
+                                  payload_path, seed, 0, 500)?;
         
 
-        ProbeModV6 {
+        Ok(ProbeModV6 {
             name: "tcp_syn_payload_scan_v6",
             max_packet_length_v6: 74 + opt_payload.len() + payload.len(),        //  以太网头(14字节) + ipv6报头(40字节) + tcp基本首部(20字节) + 负载长度 = 74字节 +
             snap_len_v6: 1500,
@@ -68,7 +87,7 @@ impl TcpSynPayloadScan {
 
             fields: fields.clone(),
             conf: Some(mod_conf),
-        }
+        })
     }
 
 
@@ -87,7 +106,8 @@ impl TcpSynPayloadScan {
         let print_data:bool;
         if let Some(a) = p.conf.clone() {
             // 如果有自定义参数
-            print_data = a.get_conf_or_from_sys(&"print_data".to_string());
+            // 参数已在构造模块时检查
+            print_data = a.get_conf_or_from_sys(&"print_data".to_string()).unwrap_or(true);
         } else { 
             print_data = true;
         }
@@ -3,7 +3,7 @@ mod method;
 
 use std::sync::Arc;
 use crate::modes::Helper;
-use crate::modules::probe_modules::probe_mod_v6::{ProbeMethodV6, ProbeModV6};
+use crate::modules::probe_modules::probe_mod_v6::{ProbeMethodV6, ProbeModV6, ProbeRegV6};
 use crate::SYS;
 use crate::tools::net_handle::packet::tcp::fields::TcpFields;
 
@@ -22,6 +22,17 @@ pub struct TcpSynScanV6 {
 
 impl TcpSynScanV6 {
 
+    /// 探测模块注册信息
+    pub const PROBE:ProbeRegV6 = ProbeRegV6 {
+        name: "tcp_syn_scan_v6",
+        aliases: &[],
+        help: Some(TcpSynScanV6::print_help),
+        args: &[],
+        listed: true,
+        new: |_, _, _, fields| Ok(TcpSynScanV6::new(fields)),
+        init: |p, sports| Box::new(TcpSynScanV6::init(p, sports)),
+    };
+
     pub fn new(fields:&Vec<String>) -> ProbeModV6 {
 
         ProbeModV6 {
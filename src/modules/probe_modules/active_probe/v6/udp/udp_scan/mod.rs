@@ -1,13 +1,14 @@
 mod method;
 
 use std::sync::Arc;
-use crate::core::conf::modules_config::ModuleConf;
+use crate::core::conf::modules_config::{ArgSpec, ModuleConf};
 use crate::modes::Helper;
-use crate::modules::probe_modules::probe_mod_v6::{ProbeMethodV6, ProbeModV6};
+use crate::modules::probe_modules::probe_mod_v6::{ProbeMethodV6, ProbeModV6, ProbeRegV6};
 use crate::modules::probe_modules::tools::payload::get_payload;
 use crate::{parse_custom_args, SYS};
 use crate::tools::file::get_path::get_current_path;
 use crate::tools::net_handle::packet::udp::fields::UdpFields;
+use crate::core::errors::ProbeError;
 
 
 pub struct UdpScanV6 {
@@ -29,14 +30,31 @@ pub struct UdpScanV6 {
 
 impl UdpScanV6 {
 
-    pub fn new(mod_conf:ModuleConf, seed:u64, fields:&Vec<String>) -> ProbeModV6 {
+    /// 探测模块注册信息
+    pub const PROBE:ProbeRegV6 = ProbeRegV6 {
+        name: "udp_scan_v6",
+        aliases: &[],
+        help: Some(UdpScanV6::print_help),
+        args: &[
+            ArgSpec::str("payload").unset(),
+            ArgSpec::bool("not_check_sport").with_default("true"),
+        ],
+        listed: true,
+        new: |conf, _, seed, fields| UdpScanV6::new(conf, seed, fields),
+        init: |p, sports| Box::new(UdpScanV6::init(p, sports)),
+    };
+
+    pub fn new(mod_conf:ModuleConf, seed:u64, fields:&Vec<String>) -> Result<ProbeModV6, ProbeError> {
+
+        // 构造时 检查自定义参数, 线程初始化时 不再出错
+        parse_custom_args!(check mod_conf; (not_check_sport, bool, "not_check_sport_parse_failed"));
 
         // 得到 payload文件路径
-        let payload_path = get_current_path(&SYS.get_info("conf", "default_payload_file"));
+        let payload_path = get_current_path(&SYS.get_info("conf", "default_payload_file"))?;
         let payload = get_payload(mod_conf.get_info(&"payload".to_string()),
-                                  payload_path, seed, 0, 508);
+                                  payload_path, seed, 0, 508)?;
 
-        ProbeModV6 {
+        Ok(ProbeModV6 {
             name: "udp_scan_v6",
             max_packet_length_v6: 62 + payload.len(),     // 以太网头(14字节) + ipv6首部(40字节) + udp首部(8字节) + 载荷 = 62 + 载荷
             snap_len_v6: 1500,
@@ -48,7 +66,7 @@ impl UdpScanV6 {
             payload,
             fields: fields.clone(),
             conf: Some(mod_conf),
-        }
+        })
     }
 
     pub fn init(p:Arc<ProbeModV6>, sports:Vec<u16>) -> impl ProbeMethodV6 {
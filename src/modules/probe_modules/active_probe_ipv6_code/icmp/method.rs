@@ -2,6 +2,7 @@ use crate::modules::probe_modules::active_probe_ipv6_code::CodeProbeMethodV6;
 use crate::modules::probe_modules::active_probe_ipv6_code::icmp::CodeIcmpEchoV6;
 use crate::modules::probe_modules::tools::ethernet::make_ethernet_header;
 use crate::tools::encryption_algorithm::aes::AesRand;
+use crate::core::errors::ProbeError;
 use crate::tools::net_handle::net_interface::mac_addr::MacAddress;
 use crate::tools::net_handle::packet::v6::icmp_v6::IcmpV6Packet;
 use crate::tools::net_handle::packet::v6::packet_v6_u128::Ipv6PacketU128;
@@ -26,7 +27,7 @@ impl CodeProbeMethodV6 for CodeIcmpEchoV6 {
         }.get_u8_vec_without_addr());
     }
 
-    fn make_packet_v6(&self, source_ip: u128, dest_ip: u128, _:u16,  code: Vec<u8>, aes_rand: &AesRand) -> Vec<u8> {
+    fn make_packet_v6(&self, source_ip: u128, dest_ip: u128, _:u16,  code: Vec<u8>, aes_rand: &AesRand) -> Result<Vec<u8>, ProbeError> {
         // 按最大数据包长度设置 向量容量
         let mut packet = Vec::with_capacity(self.total_len);
 
@@ -80,7 +81,7 @@ impl CodeProbeMethodV6 for CodeIcmpEchoV6 {
             packet[56] = check_sum_bytes[0];
             packet[57] = check_sum_bytes[1];
         }
-        packet
+        Ok(packet)
     }
 
     fn receive_packet_v6(&self, net_layer_header: &[u8], net_layer_data:&[u8], aes_rand: &AesRand) -> Option<(u128, u16, Vec<u8>)> {
@@ -1,8 +1,9 @@
 use std::sync::Arc;
-use crate::core::conf::modules_config::ModuleConf;
+use crate::core::conf::modules_config::{ArgSpec, ModuleConf};
 use crate::modes::Helper;
-use crate::modules::probe_modules::active_probe_ipv6_code::{CodeProbeMethodV6, CodeProbeModV6};
+use crate::modules::probe_modules::active_probe_ipv6_code::{CodeProbeMethodV6, CodeProbeModV6, CodeProbeRegV6};
 use crate::SYS;
+use crate::core::errors::ProbeError;
 
 mod method;
 
@@ -17,13 +18,24 @@ pub struct CodeIcmpEchoV6 {
 
 
 impl CodeIcmpEchoV6 {
+
+    /// 探测模块注册信息
+    pub const PROBE:CodeProbeRegV6 = CodeProbeRegV6 {
+        name: "code_icmp_v6",
+        aliases: &[],
+        help: Some(CodeIcmpEchoV6::print_help),
+        args: &[ArgSpec::int("payload_len").range(0.0, 65535.0).unset()],
+        listed: true,
+        new: |conf| CodeIcmpEchoV6::new(conf),
+        init: |p, _| Box::new(CodeIcmpEchoV6::init(p)),
+    };
     
     
-    pub fn new(conf:ModuleConf) -> CodeProbeModV6 {
+    pub fn new(conf:ModuleConf) -> Result<CodeProbeModV6, ProbeError> {
         
-        let payload_len:usize = conf.get_conf(&"payload_len".to_string());
+        let payload_len:usize = conf.get_conf(&"payload_len".to_string())?;
         
-        CodeProbeModV6 {
+        Ok(CodeProbeModV6 {
             name: "code_icmp_v6",
             
             max_packet_length_v6: 66 + payload_len,       // 以太网首部(14字节) + ipv6报头(40字节) + icmp报头(8字节) + icmp验证数据(4字节) + 编码负载 = 66 + 
@@ -31,7 +43,7 @@ impl CodeIcmpEchoV6 {
             filter_v6: "icmp6 && ip6[40] == 129".to_string(),
             code_len: payload_len as u16,
             conf: None,
-        }
+        })
         
     }
 
@@ -1,56 +1,50 @@
 mod icmp;
 mod tcp_syn;
 
-use std::process::exit;
 use std::sync::Arc;
-use log::error;
-use crate::core::conf::modules_config::ModuleConf;
-use crate::SYS;
+use crate::core::conf::modules_config::{ArgSpec, ModuleConf};
 use crate::tools::encryption_algorithm::aes::AesRand;
 use crate::tools::net_handle::net_interface::mac_addr::MacAddress;
+use crate::core::errors::ProbeError;
+use crate::core::registry::{find, Registration};
 
 pub use crate::modules::probe_modules::active_probe_ipv6_code::icmp::CodeIcmpEchoV6;
 pub use crate::modules::probe_modules::active_probe_ipv6_code::tcp_syn::CodeTcpSynScanV6;
 
-pub const CODE_PROBE_MODS_V6: [&str; 2] = ["code_icmp_v6", "code_tcp_syn_scan_v6"];
+/// 探测模块注册信息: 构造函数 和 线程初始化函数
+pub type CodeProbeRegV6 = Registration<fn(ModuleConf) -> Result<CodeProbeModV6, ProbeError>,
+    fn(Arc<CodeProbeModV6>, Vec<u16>) -> Box<dyn CodeProbeMethodV6>>;
+
+/// 所有已注册的探测模块
+pub const CODE_PROBE_MODS_V6:&[CodeProbeRegV6] = &[
+    CodeIcmpEchoV6::PROBE,
+    CodeTcpSynScanV6::PROBE,
+];
 
 
 impl CodeProbeModV6 {
     
-    pub fn new(name: &str, conf:ModuleConf) -> CodeProbeModV6 {
-        
-        if !CODE_PROBE_MODS_V6.contains(&name) {
-            error!("{}", SYS.get_info("err", "v6_probe_mod_not_exist"));
-            exit(1)
-        }
-
-        match name {        // 各类模块的构造方法
-
-            "code_icmp_v6" => CodeIcmpEchoV6::new(conf),
-            
-            "code_tcp_syn_scan_v6" => CodeTcpSynScanV6::new(),
+    pub fn new(name: &str, conf:ModuleConf) -> Result<CodeProbeModV6, ProbeError> {
 
-            _ => {
-                error!("{}", SYS.get_info("err", "v6_probe_mod_not_exist"));
-                exit(1)
-            }
+        match find(CODE_PROBE_MODS_V6, name) {   // 激活检查, 并调用各类模块的构造方法
+            Some(reg) => (reg.new)(conf),
+            None => Err(ProbeError::not_exist("v6_probe_mod_not_exist")),
         }
     }
 
-    pub fn init(p:Arc<CodeProbeModV6>, sports:Vec<u16>) -> Box<dyn CodeProbeMethodV6> {
-
-        let name = p.name;
-
-        match name {        // 各类模块的构造方法
+    /// 按名称查找 探测模块 可接受的自定义参数
+    pub fn args(name: &str) -> Result<&'static [ArgSpec], ProbeError> {
+        match find(CODE_PROBE_MODS_V6, name) {
+            Some(reg) => Ok(reg.args),
+            None => Err(ProbeError::not_exist("v6_probe_mod_not_exist")),
+        }
+    }
 
-            "code_icmp_v6" => Box::new(CodeIcmpEchoV6::init(p)),
+    pub fn init(p:Arc<CodeProbeModV6>, sports:Vec<u16>) -> Result<Box<dyn CodeProbeMethodV6>, ProbeError> {
 
-            "code_tcp_syn_scan_v6" => Box::new(CodeTcpSynScanV6::init(p, sports)),
-            
-            _ => {
-                error!("{}", SYS.get_info("err", "v6_probe_mod_not_exist"));
-                exit(1)
-            }
+        match find(CODE_PROBE_MODS_V6, p.name) {
+            Some(reg) => Ok((reg.init)(p, sports)),
+            None => Err(ProbeError::not_exist("v6_probe_mod_not_exist")),
         }
     }
     
@@ -62,8 +56,8 @@ pub trait CodeProbeMethodV6 {
     fn thread_initialize_v6(&mut self, local_mac:&MacAddress, gateway_mac:&MacAddress);
 
     
-    // 生成数据包
-    fn make_packet_v6(&self, source_ip:u128, dest_ip:u128, dest_port:u16, code:Vec<u8>, aes_rand:&AesRand) -> Vec<u8>;
+    // 生成数据包, 编码不符合模块要求时 返回错误
+    fn make_packet_v6(&self, source_ip:u128, dest_ip:u128, dest_port:u16, code:Vec<u8>, aes_rand:&AesRand) -> Result<Vec<u8>, ProbeError>;
     
     
     // 接收并验证数据包
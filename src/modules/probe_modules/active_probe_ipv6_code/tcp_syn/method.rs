@@ -1,9 +1,7 @@
-use std::process::exit;
-use log::error;
 use crate::modules::probe_modules::active_probe_ipv6_code::CodeProbeMethodV6;
 use crate::modules::probe_modules::active_probe_ipv6_code::tcp_syn::CodeTcpSynScanV6;
 use crate::modules::probe_modules::tools::ethernet::make_ethernet_header;
-use crate::SYS;
+use crate::core::errors::ProbeError;
 use crate::tools::encryption_algorithm::aes::AesRand;
 use crate::tools::net_handle::net_interface::mac_addr::MacAddress;
 use crate::tools::net_handle::packet::tcp::TcpPacket;
@@ -49,11 +47,10 @@ impl CodeProbeMethodV6 for CodeTcpSynScanV6 {
         }.get_u8_vec_after_sequence());
     }
 
-    fn make_packet_v6(&self, source_ip: u128, dest_ip: u128, dest_port: u16, code: Vec<u8>, aes_rand: &AesRand) -> Vec<u8> {
+    fn make_packet_v6(&self, source_ip: u128, dest_ip: u128, dest_port: u16, code: Vec<u8>, aes_rand: &AesRand) -> Result<Vec<u8>, ProbeError> {
         if code.len() != 4 {
             // 编码长度必须为4字节
-            error!("{}", SYS.get_info("err", "code_len_4"));
-            exit(1)
+            return Err(ProbeError::invalid("code_len_4").with(code.len()))
         }
 
         // 按最大数据包长度设置 向量容量
@@ -107,7 +104,7 @@ impl CodeProbeMethodV6 for CodeTcpSynScanV6 {
             packet[70] = tcp_check_sum_bytes[0];
             packet[71] = tcp_check_sum_bytes[1];
         }
-        packet
+        Ok(packet)
     }
 
     fn receive_packet_v6(&self, net_layer_header: &[u8], net_layer_data: &[u8], aes_rand: &AesRand) -> Option<(u128, u16, Vec<u8>)> {
@@ -1,6 +1,6 @@
 use std::sync::Arc;
 use crate::modes::Helper;
-use crate::modules::probe_modules::active_probe_ipv6_code::{CodeProbeMethodV6, CodeProbeModV6};
+use crate::modules::probe_modules::active_probe_ipv6_code::{CodeProbeMethodV6, CodeProbeModV6, CodeProbeRegV6};
 use crate::SYS;
 
 mod method;
@@ -20,6 +20,17 @@ pub struct CodeTcpSynScanV6 {
 
 impl CodeTcpSynScanV6 {
 
+    /// 探测模块注册信息
+    pub const PROBE:CodeProbeRegV6 = CodeProbeRegV6 {
+        name: "code_tcp_syn_scan_v6",
+        aliases: &[],
+        help: Some(CodeTcpSynScanV6::print_help),
+        args: &[],
+        listed: true,
+        new: |_| Ok(CodeTcpSynScanV6::new()),
+        init: |p, sports| Box::new(CodeTcpSynScanV6::init(p, sports)),
+    };
+
     pub fn new() -> CodeProbeModV6 {
 
         CodeProbeModV6 {
@@ -61,13 +61,11 @@ macro_rules! not_use_port_check {
     ($tar_ports:ident) => (
          if $tar_ports.len() != 1 {
             // 如果有 多个端口 或 没有输入端口
-            log::error!("{}", crate::SYS.get_info("err", "tar_ports_not_match_net_layer"));
-            std::process::exit(1)
+            return Err($crate::core::errors::ProbeError::invalid("tar_ports_not_match_net_layer"))
         } else {
             if $tar_ports[0] != 0 {
                 // 如果输出的目标端口不为0
-                log::error!("{}", crate::SYS.get_info("err", "tar_ports_not_match_net_layer"));
-                std::process::exit(1)
+                return Err($crate::core::errors::ProbeError::invalid("tar_ports_not_match_net_layer"))
             }
         }
     );
@@ -75,6 +73,16 @@ macro_rules! not_use_port_check {
 
 #[macro_export]
 macro_rules! parse_custom_args {
+    // 构造模块时 检查自定义参数, 解析失败时 返回错误
+    (check $conf:ident; $(($field:ident, $t:ty, $err_info:expr)),*) => {
+        $(
+            if let Some(val) = $conf.get_info(&String::from(stringify!($field))) {
+                val.trim().parse::<$t>().map_err(|_| $crate::core::errors::ProbeError::invalid($err_info).with(val.trim()))?;
+            }
+        )*
+    };
+
+    // 线程初始化时 解析自定义参数, 参数已在构造模块时检查
     ($p:ident; $(($field:ident, $t:ty, $default:expr, $err_info:expr)),*) => {
 
         $(let $field:$t;)*
@@ -84,11 +92,7 @@ macro_rules! parse_custom_args {
             Some(c) => {
                 $(
                     $field = match c.get_info(&String::from(stringify!($field))) {
-                        Some(val) => {
-                            val.trim().parse().map_err(|_|{
-                             log::error!("{}", crate::SYS.get_info("err", $err_info)); std::process::exit(1)
-                            }).unwrap()
-                        }
+                        Some(val) => val.trim().parse().unwrap_or($default),
                         None => $default
                     };
                 )*
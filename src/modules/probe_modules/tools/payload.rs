@@ -1,35 +1,33 @@
 use std::fs::File;
 use std::io::{BufRead, BufReader};
-use std::process::exit;
-use log::error;
 use rand::prelude::StdRng;
 use rand::{Rng, SeedableRng};
-use crate::SYS;
 use crate::tools::file::parse_context::parse_line_with_annotation;
+use crate::core::errors::ProbeError;
 
 
-pub fn get_payload(payload_args:Option<String>, file_path:String, seed:u64, min_payload_len:usize, max_payload_len:usize) -> Vec<u8> {
+pub fn get_payload(payload_args:Option<String>, file_path:String, seed:u64, min_payload_len:usize, max_payload_len:usize) -> Result<Vec<u8>, ProbeError> {
 
     if let Some(args) = payload_args {
 
         let option_arg:Vec<&str> = args.split(":").collect();
 
-        if option_arg.len() != 2 { error!("{} {}", SYS.get_info("err", "payload_args_invalid"), args); exit(1) }
+        if option_arg.len() != 2 { return Err(ProbeError::payload("payload_args_invalid").with(args)) }
 
         match option_arg[0] {
 
             "file" => {
-                let payload = parse_payload_from_file(file_path, option_arg[1], seed);
+                let payload = parse_payload_from_file(file_path, option_arg[1], seed)?;
 
                 // 如果负载长度 合法
-                if  min_payload_len <= payload.len() && payload.len() <= max_payload_len { return payload }
+                if  min_payload_len <= payload.len() && payload.len() <= max_payload_len { return Ok(payload) }
             }
 
             "bytes" => {
-                let payload = parse_payload(option_arg[1], seed);
+                let payload = parse_payload(option_arg[1], seed)?;
 
                 // 如果负载长度 合法
-                if min_payload_len <= payload.len() && payload.len() <= max_payload_len { return payload }
+                if min_payload_len <= payload.len() && payload.len() <= max_payload_len { return Ok(payload) }
             }
 
             /*
@@ -47,21 +45,20 @@ pub fn get_payload(payload_args:Option<String>, file_path:String, seed:u64, min_
                     _ => {}
                 }
             }*/
-            _ => { error!("{} {}", SYS.get_info("err", "payload_args_invalid"), args); exit(1) }
+            _ => { return Err(ProbeError::payload("payload_args_invalid").with(args)) }
         }
     } else {
         // 如果不存在 探测参数
 
         // 如果 没有探测参数, 并且 最小负载数量允许为 0, 返回 空负载向量
-        if min_payload_len == 0 { return vec![] }
+        if min_payload_len == 0 { return Ok(vec![]) }
     }
 
     // 没有符合长度等限制
-    error!("{}", SYS.get_info("err", "payload_invalid"));
-    exit(1)
+    Err(ProbeError::payload("payload_invalid"))
 }
 
-fn parse_payload(input:&str, seed:u64) -> Vec<u8> {
+fn parse_payload(input:&str, seed:u64) -> Result<Vec<u8>, ProbeError> {
 
     let mut payload =  vec![];
 
@@ -82,23 +79,19 @@ fn parse_payload(input:&str, seed:u64) -> Vec<u8> {
                 // 如果载荷数组中存在 * 标记, 对该字节进行随机填充
                 tar_byte = rng.random();
             } else {
-                tar_byte = byte_str.parse().map_err(|_|{
-                    error!("{} {}", SYS.get_info("err", "payload_byte_parse_failed"), input);
-                    exit(1)
-                }).unwrap();
+                tar_byte = byte_str.parse().map_err(|_| ProbeError::payload("payload_byte_parse_failed").with(input))?;
             }
 
             payload.push(tar_byte);
         }
     } else {
-        error!("{} {}", SYS.get_info("err", "payload_byte_parse_failed"), input);
-        exit(1)
+        return Err(ProbeError::payload("payload_byte_parse_failed").with(input))
     }
-    payload
+    Ok(payload)
 }
 
 
-fn parse_payload_from_file(path:String, target:&str, seed:u64) -> Vec<u8> {
+fn parse_payload_from_file(path:String, target:&str, seed:u64) -> Result<Vec<u8>, ProbeError> {
 
     let path = if cfg!(target_os = "windows") {
         path.replace("/", "\\")
@@ -108,12 +101,7 @@ fn parse_payload_from_file(path:String, target:&str, seed:u64) -> Vec<u8> {
 
     let path = path.trim();
 
-    let list_file = File::open(path).map_err(
-        |_| {
-            error!("{} {}", SYS.get_info("err", "open_payload_file_failed"), path);
-            exit(1)
-        }
-    ).unwrap();
+    let list_file = File::open(path).map_err(|_| ProbeError::payload("open_payload_file_failed").with(path))?;
 
     let lines = BufReader::new(list_file).lines();
     for line in lines {
@@ -140,6 +128,6 @@ fn parse_payload_from_file(path:String, target:&str, seed:u64) -> Vec<u8> {
             Err(_) => {}
         }
     }
-    error!("{} {} {}", SYS.get_info("err", "match_payload_failed"), target, path);  exit(1)
+    Err(ProbeError::payload("match_payload_failed").with(format!("{} {}", target, path)))
 }
 
@@ -1,50 +1,47 @@
-use std::process::exit;
 use std::sync::Arc;
 use libc::timeval;
-use log::error;
-use crate::core::conf::modules_config::ModuleConf;
+use crate::core::conf::modules_config::{ArgSpec, ModuleConf};
 use crate::modules::probe_modules::topo_probe_code::v6::icmp::CodeTopoIcmpV6;
-use crate::SYS;
 use crate::tools::encryption_algorithm::aes::AesRand;
 use crate::tools::net_handle::net_interface::mac_addr::MacAddress;
+use crate::core::errors::ProbeError;
+use crate::core::registry::{find, Registration};
 
 
 
-pub const CODE_TOPO_PROBE_MODS_V6: [&str; 1] = ["code_topo_icmp_v6"];
+/// 探测模块注册信息: 构造函数 和 线程初始化函数
+pub type CodeTopoProbeRegV6 = Registration<fn(ModuleConf) -> Result<CodeTopoProbeModV6, ProbeError>,
+    fn(Arc<CodeTopoProbeModV6>) -> Box<dyn CodeTopoProbeMethodV6>>;
+
+/// 所有已注册的探测模块
+pub const CODE_TOPO_PROBE_MODS_V6:&[CodeTopoProbeRegV6] = &[
+    CodeTopoIcmpV6::PROBE,
+];
 
 
 impl CodeTopoProbeModV6 {
 
-    pub fn new(name: &str, conf:ModuleConf) -> CodeTopoProbeModV6 {
+    pub fn new(name: &str, conf:ModuleConf) -> Result<CodeTopoProbeModV6, ProbeError> {
 
-        if !CODE_TOPO_PROBE_MODS_V6.contains(&name) {
-            error!("{}", SYS.get_info("err", "v6_probe_mod_not_exist"));
-            exit(1)
+        match find(CODE_TOPO_PROBE_MODS_V6, name) {   // 激活检查, 并调用各类模块的构造方法
+            Some(reg) => (reg.new)(conf),
+            None => Err(ProbeError::not_exist("v6_probe_mod_not_exist")),
         }
+    }
 
-        match name {        // 各类模块的构造方法
-
-            "code_topo_icmp_v6" => CodeTopoIcmpV6::new(conf),
-
-            _ => {
-                error!("{}", SYS.get_info("err", "v6_probe_mod_not_exist"));
-                exit(1)
-            }
+    /// 按名称查找 探测模块 可接受的自定义参数
+    pub fn args(name: &str) -> Result<&'static [ArgSpec], ProbeError> {
+        match find(CODE_TOPO_PROBE_MODS_V6, name) {
+            Some(reg) => Ok(reg.args),
+            None => Err(ProbeError::not_exist("v6_probe_mod_not_exist")),
         }
     }
 
-    pub fn init(p:Arc<CodeTopoProbeModV6>) -> Box<dyn CodeTopoProbeMethodV6> {
-
-        let name = p.name;
-
-        match name {        // 各类模块的构造方法
-
-            "code_topo_icmp_v6" => Box::new(CodeTopoIcmpV6::init(p)),
+    pub fn init(p:Arc<CodeTopoProbeModV6>) -> Result<Box<dyn CodeTopoProbeMethodV6>, ProbeError> {
 
-            _ => {
-                error!("{}", SYS.get_info("err", "v6_probe_mod_not_exist"));
-                exit(1)
-            }
+        match find(CODE_TOPO_PROBE_MODS_V6, p.name) {
+            Some(reg) => Ok((reg.init)(p)),
+            None => Err(ProbeError::not_exist("v6_probe_mod_not_exist")),
         }
     }
 
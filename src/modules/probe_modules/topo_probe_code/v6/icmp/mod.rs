@@ -1,6 +1,7 @@
 use std::sync::Arc;
-use crate::core::conf::modules_config::ModuleConf;
-use crate::modules::probe_modules::topo_probe_code::topo_mod_v6::{CodeTopoProbeMethodV6, CodeTopoProbeModV6};
+use crate::core::conf::modules_config::{ArgSpec, ModuleConf};
+use crate::modules::probe_modules::topo_probe_code::topo_mod_v6::{CodeTopoProbeMethodV6, CodeTopoProbeModV6, CodeTopoProbeRegV6};
+use crate::core::errors::ProbeError;
 
 mod method;
 
@@ -19,12 +20,23 @@ pub struct CodeTopoIcmpV6 {
 
 impl CodeTopoIcmpV6 {
 
+    /// 探测模块注册信息
+    pub const PROBE:CodeTopoProbeRegV6 = CodeTopoProbeRegV6 {
+        name: "code_topo_icmp_v6",
+        aliases: &[],
+        help: None,
+        args: &[ArgSpec::int("payload_len").range(0.0, 65535.0).unset()],
+        listed: true,
+        new: |conf| CodeTopoIcmpV6::new(conf),
+        init: |p| Box::new(CodeTopoIcmpV6::init(p)),
+    };
 
-    pub fn new(conf:ModuleConf) -> CodeTopoProbeModV6 {
 
-        let payload_len:usize = conf.get_conf(&"payload_len".to_string());
+    pub fn new(conf:ModuleConf) -> Result<CodeTopoProbeModV6, ProbeError> {
 
-        CodeTopoProbeModV6 {
+        let payload_len:usize = conf.get_conf(&"payload_len".to_string())?;
+
+        Ok(CodeTopoProbeModV6 {
             name: "code_topo_icmp_v6",
 
             max_packet_length_v6: 59 + payload_len,    // 以太网首部(14字节) + ipv6报头(40字节) + icmp报头(5字节) + 编码负载 = 59 +
@@ -33,7 +45,7 @@ impl CodeTopoIcmpV6 {
 
             code_len: payload_len as u16,
             conf: None,
-        }
+        })
     }
 
 
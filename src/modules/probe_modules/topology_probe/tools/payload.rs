@@ -1,14 +1,12 @@
-use std::process::exit;
-use log::error;
-use crate::SYS;
+use crate::core::errors::ProbeError;
 
-pub fn get_topo_message(text:String, allow_repeat:bool, err_info:&str, len:usize) -> Vec<u8> {
+pub fn get_topo_message(text:String, allow_repeat:bool, err_info:&'static str, len:usize) -> Result<Vec<u8>, ProbeError> {
 
     let payload = text.as_bytes();
 
     if payload.len() < len {
 
-        if allow_repeat {
+        if allow_repeat && !payload.is_empty() {
 
             let repeat_count = (len / payload.len()) + 1;
 
@@ -17,11 +15,10 @@ pub fn get_topo_message(text:String, allow_repeat:bool, err_info:&str, len:usize
                 p.extend_from_slice(payload);
             }
 
-            return p
+            return Ok(p)
         } else {
-            error!("{}", SYS.get_info("err", err_info));
-            exit(1)
+            return Err(ProbeError::payload(err_info).with(text))
         }
     }
-    Vec::from(payload)
+    Ok(Vec::from(payload))
 }
\ No newline at end of file
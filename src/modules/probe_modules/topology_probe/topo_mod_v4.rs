@@ -1,52 +1,47 @@
-use std::process::exit;
 use std::sync::Arc;
-use log::error;
-use crate::core::conf::modules_config::ModuleConf;
-use crate::SYS;
+use crate::core::conf::modules_config::{ArgSpec, ModuleConf};
 use crate::tools::encryption_algorithm::aes::AesRand;
 use crate::tools::net_handle::net_interface::mac_addr::MacAddress;
+use crate::core::errors::ProbeError;
+use crate::core::registry::{find, Registration};
 
 pub use crate::modules::probe_modules::topology_probe::v4::{TopoUdpV4, TopoIcmpV4, TopoTcpV4};
 
-pub const TOPO_MODS_V4: [&str; 3] = ["topo_icmp_v4", "topo_udp_v4", "topo_tcp_v4"];
+/// 探测模块注册信息: 构造函数 和 线程初始化函数
+pub type TopoRegV4 = Registration<fn(ModuleConf) -> Result<TopoModV4, ProbeError>,
+    fn(Arc<TopoModV4>, Vec<u16>) -> Box<dyn TopoMethodV4>>;
+
+/// 所有已注册的探测模块
+pub const TOPO_MODS_V4:&[TopoRegV4] = &[
+    TopoIcmpV4::PROBE,
+    TopoUdpV4::PROBE,
+    TopoTcpV4::PROBE,
+];
 
 impl TopoModV4 {
 
-    pub fn new(name: &str, conf:ModuleConf) -> TopoModV4 {
+    pub fn new(name: &str, conf:ModuleConf) -> Result<TopoModV4, ProbeError> {
 
-        if !TOPO_MODS_V4.contains(&name) { // 激活检查
-            error!("{}", SYS.get_info("err", "v4_probe_mod_not_exist"));
-            exit(1)
+        match find(TOPO_MODS_V4, name) {   // 激活检查, 并调用各类模块的构造方法
+            Some(reg) => (reg.new)(conf),
+            None => Err(ProbeError::not_exist("v4_probe_mod_not_exist")),
         }
+    }
 
-        match name {
-
-            "topo_icmp_v4" => TopoIcmpV4::new(conf),
-            "topo_udp_v4"  => TopoUdpV4::new(conf),
-            "topo_tcp_v4" => TopoTcpV4::new(conf),
-
-            _ => {
-                error!("{}", SYS.get_info("err", "v4_probe_mod_not_exist"));
-                exit(1)
-            }
+    /// 按名称查找 探测模块 可接受的自定义参数
+    pub fn args(name: &str) -> Result<&'static [ArgSpec], ProbeError> {
+        match find(TOPO_MODS_V4, name) {
+            Some(reg) => Ok(reg.args),
+            None => Err(ProbeError::not_exist("v4_probe_mod_not_exist")),
         }
     }
 
 
-    pub fn init(t:Arc<TopoModV4>, sports:Vec<u16>) -> Box<dyn TopoMethodV4> {
-
-        let name = t.name;
-
-        match name {
-
-            "topo_icmp_v4" => Box::new(TopoIcmpV4::init(t)),
-            "topo_udp_v4"  => Box::new(TopoUdpV4::init(t, sports)),
-            "topo_tcp_v4"  => Box::new(TopoTcpV4::init(t, sports)),
+    pub fn init(t:Arc<TopoModV4>, sports:Vec<u16>) -> Result<Box<dyn TopoMethodV4>, ProbeError> {
 
-            _ => {
-                error!("{}", SYS.get_info("err", "v4_probe_mod_not_exist"));
-                exit(1)
-            }
+        match find(TOPO_MODS_V4, t.name) {
+            Some(reg) => Ok((reg.init)(t, sports)),
+            None => Err(ProbeError::not_exist("v4_probe_mod_not_exist")),
         }
     }
 }
@@ -1,54 +1,49 @@
 
-use std::process::exit;
 use std::sync::Arc;
-use log::error;
-use crate::core::conf::modules_config::ModuleConf;
-use crate::SYS;
+use crate::core::conf::modules_config::{ArgSpec, ModuleConf};
 use crate::tools::encryption_algorithm::aes::AesRand;
 use crate::tools::net_handle::net_interface::mac_addr::MacAddress;
+use crate::core::errors::ProbeError;
+use crate::core::registry::{find, Registration};
 
 pub use crate::modules::probe_modules::topology_probe::v6::{TopoUdpV6, TopoIcmpV6, TopoTcpV6};
 
-pub const TOPO_MODS_V6: [&str; 3] = ["topo_icmp_v6", "topo_udp_v6", "topo_tcp_v6"];
+/// 探测模块注册信息: 构造函数 和 线程初始化函数
+pub type TopoRegV6 = Registration<fn(ModuleConf) -> Result<TopoModV6, ProbeError>,
+    fn(Arc<TopoModV6>, Vec<u16>) -> Box<dyn TopoMethodV6>>;
+
+/// 所有已注册的探测模块
+pub const TOPO_MODS_V6:&[TopoRegV6] = &[
+    TopoIcmpV6::PROBE,
+    TopoUdpV6::PROBE,
+    TopoTcpV6::PROBE,
+];
 
 impl TopoModV6 {
 
 
-    pub fn new(name: &str, conf:ModuleConf) -> TopoModV6 {
+    pub fn new(name: &str, conf:ModuleConf) -> Result<TopoModV6, ProbeError> {
 
-        if !TOPO_MODS_V6.contains(&name) { // 激活检查
-            error!("{}", SYS.get_info("err", "v6_probe_mod_not_exist"));
-            exit(1)
+        match find(TOPO_MODS_V6, name) {   // 激活检查, 并调用各类模块的构造方法
+            Some(reg) => (reg.new)(conf),
+            None => Err(ProbeError::not_exist("v6_probe_mod_not_exist")),
         }
+    }
 
-        match name {
-
-            "topo_icmp_v6" => TopoIcmpV6::new(conf),
-            "topo_udp_v6"  => TopoUdpV6::new(conf),
-            "topo_tcp_v6"  => TopoTcpV6::new(conf),
-
-            _ => {
-                error!("{}", SYS.get_info("err", "v6_probe_mod_not_exist"));
-                exit(1)
-            }
+    /// 按名称查找 探测模块 可接受的自定义参数
+    pub fn args(name: &str) -> Result<&'static [ArgSpec], ProbeError> {
+        match find(TOPO_MODS_V6, name) {
+            Some(reg) => Ok(reg.args),
+            None => Err(ProbeError::not_exist("v6_probe_mod_not_exist")),
         }
     }
 
     
-    pub fn init(t:Arc<TopoModV6>, sports:Vec<u16>) -> Box<dyn TopoMethodV6> {
-
-        let name = t.name;
-
-        match name {
-            
-            "topo_icmp_v6" => Box::new(TopoIcmpV6::init(t)),
-            "topo_udp_v6" => Box::new(TopoUdpV6::init(t, sports)),
-            "topo_tcp_v6" => Box::new(TopoTcpV6::init(t, sports)),
+    pub fn init(t:Arc<TopoModV6>, sports:Vec<u16>) -> Result<Box<dyn TopoMethodV6>, ProbeError> {
 
-            _ => {
-                error!("{}", SYS.get_info("err", "v6_probe_mod_not_exist"));
-                exit(1)
-            }
+        match find(TOPO_MODS_V6, t.name) {
+            Some(reg) => Ok((reg.init)(t, sports)),
+            None => Err(ProbeError::not_exist("v6_probe_mod_not_exist")),
         }
     }
 }
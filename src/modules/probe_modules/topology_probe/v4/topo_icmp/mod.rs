@@ -1,8 +1,9 @@
 use std::sync::Arc;
-use crate::core::conf::modules_config::ModuleConf;
+use crate::core::conf::modules_config::{ArgSpec, ModuleConf};
 use crate::modes::Helper;
-use crate::modules::probe_modules::topology_probe::topo_mod_v4::{TopoMethodV4, TopoModV4};
+use crate::modules::probe_modules::topology_probe::topo_mod_v4::{TopoMethodV4, TopoModV4, TopoRegV4};
 use crate::{parse_custom_args, SYS};
+use crate::core::errors::ProbeError;
 
 mod method;
 
@@ -24,17 +25,39 @@ pub struct TopoIcmpV4 {
 }
 
 impl TopoIcmpV4 {
+
+    /// 探测模块注册信息
+    pub const PROBE:TopoRegV4 = TopoRegV4 {
+        name: "topo_icmp_v4",
+        aliases: &[],
+        help: Some(TopoIcmpV4::print_help),
+        args: &[
+            ArgSpec::bool("use_time_encoding").with_default("true"),
+            ArgSpec::bool("print_default_ttl").with_default("false"),
+            ArgSpec::bool("topo_allow_tar_network_respond").with_default("true"),
+        ],
+        listed: true,
+        new: |conf| TopoIcmpV4::new(conf),
+        init: |t, _| Box::new(TopoIcmpV4::init(t)),
+    };
     
-    pub fn new(mod_conf:ModuleConf) -> TopoModV4 {
+    pub fn new(mod_conf:ModuleConf) -> Result<TopoModV4, ProbeError> {
+
+        // 构造时 检查自定义参数, 线程初始化时 不再出错
+        parse_custom_args!(check mod_conf;
+            (use_time_encoding, bool, "use_time_encoding_parse_failed"),
+            (print_default_ttl, bool, "print_default_ttl_parse_failed"),
+            (topo_allow_tar_network_respond, bool, "topo_allow_tar_network_respond_parse_failed")
+        );
         
-        TopoModV4 {
+        Ok(TopoModV4 {
             name: "topo_icmp_v4",
             // 以太网首部(14字节) + ipv4首部(20字节) + icmp首部(8字节)  = 42
             max_packet_length_v4: 42,
             snap_len_v4: 96,
             filter_v4: "icmp".to_string(),
             conf: Some(mod_conf),
-        }
+        })
     }
     
     pub fn init(t:Arc<TopoModV4>) -> impl TopoMethodV4 {
@@ -1,9 +1,10 @@
 use std::sync::Arc;
-use crate::core::conf::modules_config::ModuleConf;
+use crate::core::conf::modules_config::{ArgSpec, ModuleConf};
 use crate::modes::Helper;
-use crate::modules::probe_modules::topology_probe::topo_mod_v4::{TopoMethodV4, TopoModV4};
+use crate::modules::probe_modules::topology_probe::topo_mod_v4::{TopoMethodV4, TopoModV4, TopoRegV4};
 use crate::{cal_output_len, parse_custom_args};
 use crate::SYS;
+use crate::core::errors::ProbeError;
 
 mod method;
 
@@ -35,10 +36,36 @@ pub struct TopoTcpV4 {
 
 
 impl TopoTcpV4 {
+
+    /// 探测模块注册信息
+    pub const PROBE:TopoRegV4 = TopoRegV4 {
+        name: "topo_tcp_v4",
+        aliases: &[],
+        help: Some(TopoTcpV4::print_help),
+        args: &[
+            ArgSpec::bool("use_time_encoding").with_default("true"),
+            ArgSpec::bool("print_default_ttl").with_default("false"),
+            ArgSpec::bool("topo_tcp_use_ack").with_default("false"),
+            ArgSpec::int("topo_dest_port").range(0.0, 65535.0),
+            ArgSpec::bool("topo_allow_tar_network_respond").with_default("true"),
+        ],
+        listed: true,
+        new: |conf| TopoTcpV4::new(conf),
+        init: |t, sports| Box::new(TopoTcpV4::init(t, sports)),
+    };
     
-    pub fn new(mod_conf:ModuleConf) -> TopoModV4 {
+    pub fn new(mod_conf:ModuleConf) -> Result<TopoModV4, ProbeError> {
+
+        // 构造时 检查自定义参数, 线程初始化时 不再出错
+        parse_custom_args!(check mod_conf;
+            (use_time_encoding, bool, "use_time_encoding_parse_failed"),
+            (print_default_ttl, bool, "print_default_ttl_parse_failed"),
+            (topo_tcp_use_ack, bool, "topo_tcp_use_ack_parse_failed"),
+            (topo_dest_port, u16, "topo_dest_port_parse_failed"),
+            (topo_allow_tar_network_respond, bool, "topo_allow_tar_network_respond_parse_failed")
+        );
         
-        TopoModV4 {
+        Ok(TopoModV4 {
             name: "topo_tcp_v4",
             
             max_packet_length_v4: 54,           // 固定为54
@@ -46,7 +73,7 @@ impl TopoTcpV4 {
             filter_v4: "icmp".to_string(),
             
             conf: Some(mod_conf),
-        }
+        })
     }
     
     
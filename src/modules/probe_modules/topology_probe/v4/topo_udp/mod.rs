@@ -1,9 +1,10 @@
 use std::sync::Arc;
 use crate::modes::Helper;
-use crate::modules::probe_modules::topology_probe::topo_mod_v4::{TopoMethodV4, TopoModV4};
+use crate::modules::probe_modules::topology_probe::topo_mod_v4::{TopoMethodV4, TopoModV4, TopoRegV4};
 use crate::{cal_output_len, parse_custom_args, SYS};
-use crate::core::conf::modules_config::ModuleConf;
+use crate::core::conf::modules_config::{ArgSpec, ModuleConf};
 use crate::modules::probe_modules::topology_probe::tools::payload::get_topo_message;
+use crate::core::errors::ProbeError;
 
 mod method;
 
@@ -41,9 +42,37 @@ pub struct TopoUdpV4 {
 
 impl TopoUdpV4 {
 
-    pub fn new(mod_conf:ModuleConf) -> TopoModV4 {
+    /// 探测模块注册信息
+    pub const PROBE:TopoRegV4 = TopoRegV4 {
+        name: "topo_udp_v4",
+        aliases: &[],
+        help: Some(TopoUdpV4::print_help),
+        args: &[
+            ArgSpec::bool("use_time_encoding").with_default("true"),
+            ArgSpec::bool("print_default_ttl").with_default("false"),
+            ArgSpec::int("topo_dest_port").range(0.0, 65535.0),
+            ArgSpec::str("topo_payload"),
+            ArgSpec::bool("topo_payload_allow_repeat").with_default("true"),
+            ArgSpec::bool("topo_allow_tar_network_respond").with_default("true"),
+        ],
+        listed: true,
+        new: |conf| TopoUdpV4::new(conf),
+        init: |t, sports| Box::new(TopoUdpV4::init(t, sports)),
+    };
+
+    pub fn new(mod_conf:ModuleConf) -> Result<TopoModV4, ProbeError> {
+
+        // 构造时 检查自定义参数, 线程初始化时 不再出错
+        parse_custom_args!(check mod_conf;
+            (use_time_encoding, bool, "use_time_encoding_parse_failed"),
+            (print_default_ttl, bool, "print_default_ttl_parse_failed"),
+            (topo_dest_port, u16, "topo_dest_port_parse_failed"),
+            (topo_payload, String, "topo_payload_parse_failed"),
+            (topo_payload_allow_repeat, bool, "topo_payload_allow_repeat_parse_failed"),
+            (topo_allow_tar_network_respond, bool, "topo_allow_tar_network_respond_parse_failed")
+        );
 
-        TopoModV4 {
+        let t = TopoModV4 {
             name: "topo_udp_v4",
 
             max_packet_length_v4: 150,            // 以太网头(14字节) + ipv4数据包(最大127字节) =  141
@@ -51,7 +80,16 @@ impl TopoUdpV4 {
             filter_v4: "icmp".to_string(),
 
             conf: Some(mod_conf),
-        }
+        };
+
+        // 负载长度 同样在构造时检查
+        parse_custom_args!(t;
+            (topo_payload, String, SYS.get_conf("conf","topo_payload"), "topo_payload_parse_failed"),
+            (topo_payload_allow_repeat, bool, true, "topo_payload_allow_repeat_parse_failed")
+        );
+        get_topo_message(topo_payload, topo_payload_allow_repeat, "topo_payload_len_err", 100)?;
+
+        Ok(t)
     }
 
     pub fn init(t:Arc<TopoModV4>, sports:Vec<u16>) -> impl TopoMethodV4 {
@@ -67,8 +105,9 @@ impl TopoUdpV4 {
             (topo_allow_tar_network_respond, bool, true, "topo_allow_tar_network_respond_parse_failed")
         );
 
+        // 负载长度 已在构造模块时检查
         let udp_payload = get_topo_message(topo_payload, topo_payload_allow_repeat,
-                                           "topo_payload_len_err", 100);
+                                           "topo_payload_len_err", 100).unwrap_or_default();
         
         cal_output_len!(output_len, usize, 3; use_time_encoding, print_default_ttl);
 
@@ -1,8 +1,9 @@
 use std::sync::Arc;
-use crate::core::conf::modules_config::ModuleConf;
+use crate::core::conf::modules_config::{ArgSpec, ModuleConf};
 use crate::modes::Helper;
-use crate::modules::probe_modules::topology_probe::topo_mod_v6::{TopoMethodV6, TopoModV6};
+use crate::modules::probe_modules::topology_probe::topo_mod_v6::{TopoMethodV6, TopoModV6, TopoRegV6};
 use crate::{cal_output_len, parse_custom_args, SYS};
+use crate::core::errors::ProbeError;
 
 mod method;
 
@@ -22,10 +23,32 @@ pub struct TopoIcmpV6 {
 
 
 impl TopoIcmpV6 {
+
+    /// 探测模块注册信息
+    pub const PROBE:TopoRegV6 = TopoRegV6 {
+        name: "topo_icmp_v6",
+        aliases: &[],
+        help: Some(TopoIcmpV6::print_help),
+        args: &[
+            ArgSpec::bool("use_time_encoding").with_default("true"),
+            ArgSpec::bool("print_default_ttl").with_default("false"),
+            ArgSpec::bool("topo_allow_tar_network_respond").with_default("true"),
+        ],
+        listed: true,
+        new: |conf| TopoIcmpV6::new(conf),
+        init: |t, _| Box::new(TopoIcmpV6::init(t)),
+    };
     
-    pub fn new(mod_conf:ModuleConf) -> TopoModV6 {
+    pub fn new(mod_conf:ModuleConf) -> Result<TopoModV6, ProbeError> {
+
+        // 构造时 检查自定义参数, 线程初始化时 不再出错
+        parse_custom_args!(check mod_conf;
+            (use_time_encoding, bool, "use_time_encoding_parse_failed"),
+            (print_default_ttl, bool, "print_default_ttl_parse_failed"),
+            (topo_allow_tar_network_respond, bool, "topo_allow_tar_network_respond_parse_failed")
+        );
         
-        TopoModV6 {
+        Ok(TopoModV6 {
             name: "topo_icmp_v6",
             
             max_packet_length_v6: 70,
@@ -33,7 +56,7 @@ impl TopoIcmpV6 {
             filter_v6: "icmp6 && (ip6[40] == 129 || ip6[40] == 3 || ip6[40] == 1)".to_string(),
             
             conf: Some(mod_conf),
-        }
+        })
     }
     
     
@@ -1,8 +1,9 @@
 use std::sync::Arc;
-use crate::core::conf::modules_config::ModuleConf;
+use crate::core::conf::modules_config::{ArgSpec, ModuleConf};
 use crate::modes::Helper;
-use crate::modules::probe_modules::topology_probe::topo_mod_v6::{TopoMethodV6, TopoModV6};
+use crate::modules::probe_modules::topology_probe::topo_mod_v6::{TopoMethodV6, TopoModV6, TopoRegV6};
 use crate::{cal_output_len, parse_custom_args, SYS};
+use crate::core::errors::ProbeError;
 
 mod method;
 
@@ -30,9 +31,35 @@ pub struct TopoTcpV6 {
 
 impl TopoTcpV6 {
 
-    pub fn new(mod_conf:ModuleConf) -> TopoModV6 {
+    /// 探测模块注册信息
+    pub const PROBE:TopoRegV6 = TopoRegV6 {
+        name: "topo_tcp_v6",
+        aliases: &[],
+        help: Some(TopoTcpV6::print_help),
+        args: &[
+            ArgSpec::bool("use_time_encoding").with_default("true"),
+            ArgSpec::bool("print_default_ttl").with_default("false"),
+            ArgSpec::bool("topo_tcp_use_ack").with_default("false"),
+            ArgSpec::int("topo_dest_port").range(0.0, 65535.0),
+            ArgSpec::bool("topo_allow_tar_network_respond").with_default("true"),
+        ],
+        listed: true,
+        new: |conf| TopoTcpV6::new(conf),
+        init: |t, sports| Box::new(TopoTcpV6::init(t, sports)),
+    };
+
+    pub fn new(mod_conf:ModuleConf) -> Result<TopoModV6, ProbeError> {
+
+        // 构造时 检查自定义参数, 线程初始化时 不再出错
+        parse_custom_args!(check mod_conf;
+            (use_time_encoding, bool, "use_time_encoding_parse_failed"),
+            (print_default_ttl, bool, "print_default_ttl_parse_failed"),
+            (topo_tcp_use_ack, bool, "topo_tcp_use_ack_parse_failed"),
+            (topo_dest_port, u16, "topo_dest_port_parse_failed"),
+            (topo_allow_tar_network_respond, bool, "topo_allow_tar_network_respond_parse_failed")
+        );
 
-        TopoModV6 {
+        Ok(TopoModV6 {
             name: "topo_tcp_v6",
 
             max_packet_length_v6: 74,    // 以太网首部(14字节) + ipv6首部(40字节) + tcp首部(20字节) = 74
@@ -40,7 +67,7 @@ impl TopoTcpV6 {
             filter_v6: "icmp6 && (ip6[40] == 1 || ip6[40] == 3)".to_string(),
 
             conf: Some(mod_conf),
-        }
+        })
     }
 
 
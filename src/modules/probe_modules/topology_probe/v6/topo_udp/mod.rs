@@ -1,9 +1,10 @@
 use std::sync::Arc;
-use crate::core::conf::modules_config::ModuleConf;
-use crate::modules::probe_modules::topology_probe::topo_mod_v6::{TopoMethodV6, TopoModV6};
+use crate::core::conf::modules_config::{ArgSpec, ModuleConf};
+use crate::modules::probe_modules::topology_probe::topo_mod_v6::{TopoMethodV6, TopoModV6, TopoRegV6};
 use crate::{cal_output_len, parse_custom_args, SYS};
 use crate::modes::Helper;
 use crate::modules::probe_modules::topology_probe::tools::payload::get_topo_message;
+use crate::core::errors::ProbeError;
 
 mod method;
 
@@ -39,18 +40,57 @@ pub struct TopoUdpV6 {
 
 
 impl TopoUdpV6 {
+
+    /// 探测模块注册信息
+    pub const PROBE:TopoRegV6 = TopoRegV6 {
+        name: "topo_udp_v6",
+        aliases: &[],
+        help: Some(TopoUdpV6::print_help),
+        args: &[
+            ArgSpec::bool("use_time_encoding").with_default("true"),
+            ArgSpec::bool("print_default_ttl").with_default("false"),
+            ArgSpec::int("topo_dest_port").range(0.0, 65535.0),
+            ArgSpec::str("topo_payload"),
+            ArgSpec::bool("topo_payload_allow_repeat").with_default("true"),
+            ArgSpec::bool("topo_allow_tar_network_respond").with_default("true"),
+            ArgSpec::bool("topo_allow_port_unreach").with_default("true"),
+        ],
+        listed: true,
+        new: |conf| TopoUdpV6::new(conf),
+        init: |t, sports| Box::new(TopoUdpV6::init(t, sports)),
+    };
     
-    pub fn new(mod_conf:ModuleConf) -> TopoModV6 {
+    pub fn new(mod_conf:ModuleConf) -> Result<TopoModV6, ProbeError> {
+
+        // 构造时 检查自定义参数, 线程初始化时 不再出错
+        parse_custom_args!(check mod_conf;
+            (use_time_encoding, bool, "use_time_encoding_parse_failed"),
+            (print_default_ttl, bool, "print_default_ttl_parse_failed"),
+            (topo_dest_port, u16, "topo_dest_port_parse_failed"),
+            (topo_payload, String, "topo_payload_parse_failed"),
+            (topo_payload_allow_repeat, bool, "topo_payload_allow_repeat_parse_failed"),
+            (topo_allow_tar_network_respond, bool, "topo_allow_tar_network_respond_parse_failed"),
+            (topo_allow_port_unreach, bool, "topo_allow_port_unreach_parse_failed")
+        );
         
-        TopoModV6 {
+        let t = TopoModV6 {
             name: "topo_udp_v6",
-            
+
             max_packet_length_v6: 100,          //  71 + 16(可变长度) = 87
             snap_len_v6: 150,                   //  14(以太网首部) + 40(ipv6首部) + 66 + 16(可变长度) = 136
             filter_v6: "icmp6 && (ip6[40] == 1 || ip6[40] == 3)".to_string(),
-            
+
             conf: Some(mod_conf),
-        }
+        };
+
+        // 负载长度 同样在构造时检查
+        parse_custom_args!(t;
+            (topo_payload, String, SYS.get_conf("conf","topo_payload"), "topo_payload_parse_failed"),
+            (topo_payload_allow_repeat, bool, true, "topo_payload_allow_repeat_parse_failed")
+        );
+        get_topo_message(topo_payload, topo_payload_allow_repeat, "topo_payload_len_err", 20)?;
+
+        Ok(t)
     }
 
 
@@ -68,7 +108,8 @@ impl TopoUdpV6 {
             (topo_allow_port_unreach, bool, true, "topo_allow_port_unreach_parse_failed")
         );
 
-        let udp_payload = get_topo_message(topo_payload, topo_payload_allow_repeat, "topo_payload_len_err", 20);
+        // 负载长度 已在构造模块时检查
+        let udp_payload = get_topo_message(topo_payload, topo_payload_allow_repeat, "topo_payload_len_err", 20).unwrap_or_default();
 
         cal_output_len!(output_len, usize, 3; use_time_encoding, print_default_ttl);
         
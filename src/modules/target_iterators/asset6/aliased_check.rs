@@ -2,6 +2,7 @@ use std::net::Ipv6Addr;
 use ahash::{AHashMap, AHashSet};
 use rand::{rng, Rng};
 use rand::rngs::ThreadRng;
+use crate::core::errors::OutputError;
 use crate::modules::output_modules::OutputMethod;
 use crate::modules::target_iterators::IPv6PortSpaceTree;
 
@@ -51,7 +52,7 @@ impl IPv6PortSpaceTree {
     
     
     /// 过滤, 将记录输出到文件, 传递区域编码记录信息 
-    pub fn clear_and_print_records(aliased_prefixes:&AHashSet<u64>, open_addrs_ports:&mut AHashMap<u128, u16>, max_port_num:u16, records:Vec<(u128, u16, u32)>, output:&mut Box<dyn OutputMethod>, region_len:usize) -> (Vec<u64>, u64) {
+    pub fn clear_and_print_records(aliased_prefixes:&AHashSet<u64>, open_addrs_ports:&mut AHashMap<u128, u16>, max_port_num:u16, records:Vec<(u128, u16, u32)>, output:&mut Box<dyn OutputMethod>, region_len:usize) -> Result<(Vec<u64>, u64), OutputError> {
         // 生成 区域编码记录器
         let mut region_recorder:Vec<u64> = vec![0u64; region_len];
         region_recorder.shrink_to_fit();
@@ -82,11 +83,11 @@ impl IPv6PortSpaceTree {
                 act_count += 1;
             }
         }
-        output.close_output();
-        (region_recorder, act_count)
+        output.close_output()?;
+        Ok((region_recorder, act_count))
     }
 
-    pub fn print_records(records:Vec<(u128, u16, u32)>, open_addrs_ports:&mut AHashMap<u128, u16>, max_port_num:u16, output:&mut Box<dyn OutputMethod>, region_len:usize) -> (Vec<u64>, u64) {
+    pub fn print_records(records:Vec<(u128, u16, u32)>, open_addrs_ports:&mut AHashMap<u128, u16>, max_port_num:u16, output:&mut Box<dyn OutputMethod>, region_len:usize) -> Result<(Vec<u64>, u64), OutputError> {
         // 生成 区域编码记录器
         let mut region_recorder:Vec<u64> = vec![0u64; region_len];
         region_recorder.shrink_to_fit();
@@ -113,8 +114,8 @@ impl IPv6PortSpaceTree {
             act_count += 1;
         }
         
-        output.close_output();
-        (region_recorder, act_count)
+        output.close_output()?;
+        Ok((region_recorder, act_count))
     }
     
     
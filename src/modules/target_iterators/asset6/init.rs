@@ -1,40 +1,36 @@
 use std::fs::OpenOptions;
 use std::io::{BufRead, BufReader};
 use std::net::Ipv6Addr;
-use std::process::exit;
 use std::str::FromStr;
 use ahash::{AHashMap, AHashSet};
-use log::{error, warn};
+use log::warn;
 use crate::modules::target_iterators::asset6::{IPv6PortSpaceTree, U144};
 use crate::SYS;
 use crate::tools::file::get_path::get_current_path;
+use crate::core::errors::TargetError;
 
 impl IPv6PortSpaceTree {
 
 
     pub fn new(divide_dim:u32, max_leaf_size:usize, mut no_allow_gen_seeds:bool, port_entropy_mul:f64, aliased_threshold:f64,
-               learning_rate:f64, region_extraction_num:u32, seeds_path:String, seeds_num:usize, no_allow_gen_seeds_from_file:bool, aliased_prefixes_path:String) -> Self {
+               learning_rate:f64, region_extraction_num:u32, seeds_path:String, seeds_num:usize, no_allow_gen_seeds_from_file:bool, aliased_prefixes_path:String) -> Result<Self, TargetError> {
 
         // 划分维度必须 在[1, 32]范围内
         if divide_dim < 1 || 32 < divide_dim {
-            error!("{} {}", SYS.get_info("err", "ipv6_space_tree_divide_dim_err"), "[1, 32]");
-            exit(1)
+            return Err(TargetError::invalid("ipv6_space_tree_divide_dim_err").with("[1, 32]"))
         }
 
         // 聚类区域最小上限数量 为 2
         if max_leaf_size < 2 {
-            error!("{}", SYS.get_info("err", "ipv6_space_tree_max_leaf_size_err"));
-            exit(1)
+            return Err(TargetError::invalid("ipv6_space_tree_max_leaf_size_err"))
         }
 
         if region_extraction_num >= (u32::MAX >> 8)  {
-            error!("{}", SYS.get_info("err", "ipv6_space_tree_region_extraction_num_err"));
-            exit(1)
+            return Err(TargetError::invalid("ipv6_space_tree_region_extraction_num_err"))
         }
         
         if 144 % divide_dim != 0 {
-            error!("{}", SYS.get_info("err", "ipv6_space_tree_range_err"));
-            exit(1)
+            return Err(TargetError::invalid("ipv6_space_tree_range_err"))
         }
 
         let move_len_cap = 144 / divide_dim;
@@ -64,7 +60,7 @@ impl IPv6PortSpaceTree {
         let aliased_prefixes_path = if aliased_prefixes_path == "" { None }
         else { Some(aliased_prefixes_path.to_string()) };
         
-        Self {
+        Ok(Self {
             id_num: 0,
             dim: divide_dim,
             dim_size,
@@ -97,7 +93,7 @@ impl IPv6PortSpaceTree {
             aliased_scan_flag: 0b0001_0000,
             
             aliased_prefixes_path,
-        }
+        })
     }
 
 
@@ -130,67 +126,64 @@ impl IPv6PortSpaceTree {
     }
     
     
-    pub fn get_seeds(&self) -> Vec<U144> {
+    pub fn get_seeds(&self) -> Result<Vec<U144>, TargetError> {
         // 端口 -> 用于聚类的信息
         // 在聚类前使用
-        let port2id =  Self::get_mapping(&get_current_path(&SYS.get_info("conf", "asset6_port2id")));
+        let port2id =  Self::get_mapping(&get_current_path(&SYS.get_info("conf", "asset6_port2id"))?);
         
         // 所有种子目标
         let mut seeds:AHashSet<U144> = AHashSet::with_capacity(self.seeds_num);
 
-        match OpenOptions::new().read(true).write(false).open(&self.seeds_path) {
-            Ok(file) => {
-                // 生成读取缓冲区   注意后续调整缓冲区大小
-                let lines = BufReader::with_capacity(SYS.get_conf("conf","max_read_buf_bytes"), file).lines();
-                
-                // 如果范围为整个ipv6地址空间范围
-                for line in lines {
-                    match line {
-                        Ok(addr_port_str) => {
-                            
-                            let mut addr_port = addr_port_str.split(|c| c == '|' || c == ',');
-                            
-                            match addr_port.next() {
-                                None => {}
-                                Some(ipv6_str) => {
-                                    match Ipv6Addr::from_str(ipv6_str.trim()) {
-                                        Ok(ipv6) => {
-                                            
-                                            match addr_port.next() {
-                                                None => {}
-                                                Some(port_str) => {
-                                                    match u16::from_str(port_str.trim()) {
-                                                        Ok(port) => {
-                                                            let ipv6_u144 = U144::from(u128::from(ipv6));
-                                                            
-                                                            // 注意是映射后的port
-                                                            let mapped_port = *port2id.get(&port).unwrap();
-                                                            let port_u144 = U144::from(mapped_port);
-                                                            
-                                                            let tar = (ipv6_u144 << 16) | port_u144;
-                                                            seeds.insert(tar);
-                                                        }
-                                                        Err(_) => warn!("{} {}", SYS.get_info("warn","file_line_invalid"), addr_port_str),
-                                                    }
+        let file = OpenOptions::new().read(true).write(false).open(&self.seeds_path)
+            .map_err(|e| TargetError::io("open_targets_file_failed").with(format!("{} {}", self.seeds_path, e)))?;
+        // 生成读取缓冲区   注意后续调整缓冲区大小
+        let lines = BufReader::with_capacity(SYS.get_conf("conf","max_read_buf_bytes"), file).lines();
+        
+        // 如果范围为整个ipv6地址空间范围
+        for line in lines {
+            match line {
+                Ok(addr_port_str) => {
+                    
+                    let mut addr_port = addr_port_str.split(|c| c == '|' || c == ',');
+                    
+                    match addr_port.next() {
+                        None => {}
+                        Some(ipv6_str) => {
+                            match Ipv6Addr::from_str(ipv6_str.trim()) {
+                                Ok(ipv6) => {
+                                    
+                                    match addr_port.next() {
+                                        None => {}
+                                        Some(port_str) => {
+                                            match u16::from_str(port_str.trim()) {
+                                                Ok(port) => {
+                                                    let ipv6_u144 = U144::from(u128::from(ipv6));
+                                                    
+                                                    // 注意是映射后的port
+                                                    let mapped_port = *port2id.get(&port).unwrap();
+                                                    let port_u144 = U144::from(mapped_port);
+                                                    
+                                                    let tar = (ipv6_u144 << 16) | port_u144;
+                                                    seeds.insert(tar);
                                                 }
+                                                Err(_) => warn!("{} {}", SYS.get_info("warn","file_line_invalid"), addr_port_str),
                                             }
                                         }
-                                        Err(_) => warn!("{} {}", SYS.get_info("warn","file_line_invalid"), addr_port_str),
                                     }
                                 }
+                                Err(_) => warn!("{} {}", SYS.get_info("warn","file_line_invalid"), addr_port_str),
                             }
                         }
-                        Err(_) => {}
                     }
                 }
+                Err(_) => {}
             }
-            Err(_) => error!("{} {}", SYS.get_info("err", "open_targets_file_failed"), &self.seeds_path)
         }
 
         // 所有种子目标
         let seeds:Vec<U144> = seeds.into_iter().collect();
 
-        seeds
+        Ok(seeds)
     }
     
     
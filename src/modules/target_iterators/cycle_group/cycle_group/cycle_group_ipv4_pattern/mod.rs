@@ -1,10 +1,9 @@
 mod get_targets;
 
-use crate::SYS;
-use std::process::exit;
-use log::error;
 use rand::prelude::StdRng;
 use crate::modules::target_iterators::cycle_group::cyclic::Cyclic;
+use crate::modules::target_iterators::CyclePosition;
+use crate::core::errors::TargetError;
 
 
 #[derive(Clone)]
@@ -33,18 +32,18 @@ pub struct CycleIpv4Pattern {
 
 impl CycleIpv4Pattern {
 
-    pub fn new(bits_for_ip:u32, base_ip_val:u32, parts:Vec<(u32, u32)>, rng:&mut StdRng) -> Self {
+    pub fn new(bits_for_ip:u32, base_ip_val:u32, parts:Vec<(u32, u32)>, rng:&mut StdRng) -> Result<Self, TargetError> {
 
         // 获取片段移动位数, 注意: 这里端口位数为 0
         let ip_move_len = Cyclic::get_move_len(bits_for_ip, 0, parts, 32);
 
         // 获得乘法循环群
-        let cycle = Cyclic::new_from_pattern(bits_for_ip, rng, u64::MAX as u128);
+        let cycle = Cyclic::new_from_pattern(bits_for_ip, rng, u64::MAX as u128)?;
 
-        Self {
-            p: Cyclic::get_val_with_check_u64(cycle.p),
-            prim_root: Cyclic::get_val_with_check_u64(cycle.prim_root),
-            p_sub_one: Cyclic::get_val_with_check_u64(cycle.p_sub_one),
+        Ok(Self {
+            p: Cyclic::get_val_with_check_u64(cycle.p)?,
+            prim_root: Cyclic::get_val_with_check_u64(cycle.prim_root)?,
+            p_sub_one: Cyclic::get_val_with_check_u64(cycle.p_sub_one)?,
 
             // 这里只做初始化, 没有实际意义
             current: 0,
@@ -55,18 +54,17 @@ impl CycleIpv4Pattern {
 
             // 使得乘法群生成的值始终在 [1, 2^( ip 和 port 占的总位数)]
             valid_range: (1 << cycle.bits_num) + 1,
-        }
+        })
     }
 
 
     /// 从<u>整体循环群</u>为每个发送线程创建<u>扫描范围</u>   index:[1..p-1]
     /// 这里的 start_index, end_index 为指数顺序范围, 比如 2->4 就是 3^2≡2(mod 7)，3^3≡6(mod 7)，3^4≡4(mod 7)
-    pub fn init(&self, start_index:u64, end_index:u64) -> Self {
+    pub fn init(&self, start_index:u64, end_index:u64) -> Result<Self, TargetError> {
 
         // start_index 和 end_index 均为 [1, p-1], 且 start_index 必须小于等于 end_index
         if start_index > end_index || start_index < 1 || end_index > self.p_sub_one {
-            error!("{}", SYS.get_info("err", "index_invalid"));
-            exit(1)
+            return Err(TargetError::invalid("index_invalid"))
         }
 
         // 转换成对应大数
@@ -79,24 +77,39 @@ impl CycleIpv4Pattern {
         let big_first = big_prim_root.modpow(&big_start, &big_p);
         let big_last  = big_prim_root.modpow(&big_end, &big_p);
 
-        Self {
+        Ok(Self {
             p: self.p,
             prim_root: self.prim_root,
             p_sub_one: self.p_sub_one,
 
-            current:Cyclic::parse_big_num_to_u64(big_first),
-            last:Cyclic::parse_big_num_to_u64(big_last),
+            current:Cyclic::parse_big_num_to_u64(big_first)?,
+            last:Cyclic::parse_big_num_to_u64(big_last)?,
 
             base_ip_val: self.base_ip_val,
             ip_move_len: self.ip_move_len.clone(),
 
             valid_range: self.valid_range,
-        }
+        })
     }
 
     /// 初始化全部扫描范围
     #[allow(dead_code)]
-    pub fn init_whole(&self) -> Self {
+    pub fn init_whole(&self) -> Result<Self, TargetError> {
         self.init(1, self.p_sub_one)
     }
 }
+
+
+impl CyclePosition for CycleIpv4Pattern {
+    fn group(&self) -> (u128, u128) {
+        (self.p as u128, self.prim_root as u128)
+    }
+
+    fn position(&self) -> u128 {
+        self.current as u128
+    }
+
+    fn seek(&mut self, position:u128) {
+        self.current = position as u64;
+    }
+}
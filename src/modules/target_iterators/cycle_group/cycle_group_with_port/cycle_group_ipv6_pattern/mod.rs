@@ -1,13 +1,12 @@
 
 mod get_targets;
 
-use std::process::exit;
-use log::error;
 use rand::rngs::StdRng;
 use crate::core::conf::tools::args_parse::target_iterator::TarIterBaseConf;
 use crate::modules::target_iterators::cycle_group::cycle_group_with_port::cyclic_with_port::CyclicPort;
 use crate::modules::target_iterators::cycle_group::cyclic::Cyclic;
-use crate::SYS;
+use crate::modules::target_iterators::CyclePosition;
+use crate::core::errors::TargetError;
 
 /// ipv6 模式字符串 乘法循环群
 pub struct CycleIpv6PatternPort {
@@ -48,7 +47,7 @@ impl CycleIpv6PatternPort {
 
     /// 创建 ipv6模式字符串 乘法循环群
     pub fn new(bits_for_ip:u32, base_ip_val:u128, parts:Vec<(u32, u32)>,
-               tar_ports:Vec<u16>, rng:&mut StdRng) -> Self {
+               tar_ports:Vec<u16>, rng:&mut StdRng) -> Result<Self, TargetError> {
 
         let bits_for_port = TarIterBaseConf::bits_needed_usize(tar_ports.len());
 
@@ -56,9 +55,9 @@ impl CycleIpv6PatternPort {
         let ip_move_len = Cyclic::get_move_len(bits_for_ip, bits_for_port, parts, 128);
 
         // 获得乘法循环群
-        let cycle = CyclicPort::new_from_pattern(bits_for_ip, bits_for_port, rng, u128::MAX);
+        let cycle = CyclicPort::new_from_pattern(bits_for_ip, bits_for_port, rng, u128::MAX)?;
 
-        Self {
+        Ok(Self {
             p: cycle.p,
             prim_root: cycle.prim_root,
             p_sub_one: cycle.p_sub_one,
@@ -78,17 +77,16 @@ impl CycleIpv6PatternPort {
 
             tar_port_num:tar_ports.len(),
             tar_ports,
-        }
+        })
     }
 
     /// 从<u>整体循环群</u>为每个发送线程创建<u>扫描范围</u>   index:[1..p-1]
     /// 这里的 start_index, end_index 为指数顺序范围, 比如 2->4 就是 3^2≡2(mod 7)，3^3≡6(mod 7)，3^4≡4(mod 7)
-    pub fn init(&self, start_index:u128, end_index:u128) -> Self {
+    pub fn init(&self, start_index:u128, end_index:u128) -> Result<Self, TargetError> {
 
         // start_index 和 end_index 均为 [1, p-1], 且 start_index 必须小于等于 end_index
         if start_index > end_index || start_index < 1 || end_index > self.p_sub_one {
-            error!("{}", SYS.get_info("err", "index_invalid"));
-            exit(1)
+            return Err(TargetError::invalid("index_invalid"))
         }
 
         // 转换成对应大数
@@ -101,13 +99,13 @@ impl CycleIpv6PatternPort {
         let big_first = big_prim_root.modpow(&big_start, &big_p);
         let big_last  = big_prim_root.modpow(&big_end, &big_p);
 
-        Self {
+        Ok(Self {
             p: self.p,
             prim_root: self.prim_root,
             p_sub_one: self.p_sub_one,
 
-            current:Cyclic::parse_big_num_to_u128(big_first),
-            last:Cyclic::parse_big_num_to_u128(big_last),
+            current:Cyclic::parse_big_num_to_u128(big_first)?,
+            last:Cyclic::parse_big_num_to_u128(big_last)?,
 
             bits_for_port: self.bits_for_port,
             base_ip_val: self.base_ip_val,
@@ -119,15 +117,30 @@ impl CycleIpv6PatternPort {
             tar_port_num: self.tar_port_num,
 
             tar_ports: self.tar_ports.clone(),
-        }
+        })
 
     }
 
 
     /// 初始化全部扫描范围
     #[allow(dead_code)]
-    pub fn init_whole(&self) -> Self {
+    pub fn init_whole(&self) -> Result<Self, TargetError> {
         self.init(1, self.p_sub_one)
     }
 
-}
\ No newline at end of file
+}
+
+
+impl CyclePosition for CycleIpv6PatternPort {
+    fn group(&self) -> (u128, u128) {
+        (self.p, self.prim_root)
+    }
+
+    fn position(&self) -> u128 {
+        self.current
+    }
+
+    fn seek(&mut self, position:u128) {
+        self.current = position;
+    }
+}
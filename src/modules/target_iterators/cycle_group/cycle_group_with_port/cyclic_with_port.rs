@@ -2,6 +2,7 @@
 use rand::rngs::StdRng;
 use crate::core::conf::tools::args_parse::target_iterator::TarIterBaseConf;
 use crate::modules::target_iterators::cycle_group::cyclic::Cyclic;
+use crate::core::errors::TargetError;
 
 
 pub struct CyclicPort {
@@ -19,7 +20,7 @@ pub struct CyclicPort {
 
 impl CyclicPort {
 
-    pub fn new(tar_ip_num:u64, tar_port_num:usize, rng:&mut StdRng, type_max:u128) -> Self {
+    pub fn new(tar_ip_num:u64, tar_port_num:usize, rng:&mut StdRng, type_max:u128) -> Result<Self, TargetError> {
 
         // 计算 ip 和 port 所需要的位数
         let bits_for_ip = TarIterBaseConf::bits_needed_u64(tar_ip_num);
@@ -31,19 +32,19 @@ impl CyclicPort {
         let group_min_size = 1u128 << bits_num;
 
         // 获得大于 最小元素 的 质数乘法群
-        let group = Cyclic::get_group(group_min_size);
+        let group = Cyclic::get_group(group_min_size)?;
 
         // 计算 p - 1
         let p_sub_one = group.prime - 1;
 
-        Self {
+        Ok(Self {
             p:group.prime,
-            prim_root:Cyclic::get_prim_root(&group, rng, Cyclic::get_max_root(p_sub_one, type_max)),
+            prim_root:Cyclic::get_prim_root(&group, rng, Cyclic::get_max_root(p_sub_one, type_max))?,
             p_sub_one,
 
             bits_for_port,
             bits_num,
-        }
+        })
     }
 
 
@@ -1,12 +1,10 @@
 use std::cmp::min;
-use std::process::exit;
-use log::error;
 use num_traits::ToPrimitive;
 use rand::prelude::StdRng;
 use rand::Rng;
 use crate::core::conf::tools::args_parse::target_iterator::TarIterBaseConf;
 use crate::modules::target_iterators::cycle_group::cyclic_groups::{CYCLE_GROUP, CyclicGroup};
-use crate::SYS;
+use crate::core::errors::TargetError;
 
 pub struct Cyclic {
 
@@ -19,7 +17,7 @@ pub struct Cyclic {
 
 impl Cyclic {
 
-    pub fn new(tar_ip_num:u64, rng:&mut StdRng, type_max:u128) -> Self {
+    pub fn new(tar_ip_num:u64, rng:&mut StdRng, type_max:u128) -> Result<Self, TargetError> {
 
         // 计算 ip 需要的位数
         let bits_for_ip = TarIterBaseConf::bits_needed_u64(tar_ip_num);
@@ -29,37 +27,36 @@ impl Cyclic {
         let group_min_size = 1u128 << bits_for_ip;
 
         // 获得大于 最小元素 的 质数乘法群
-        let group = Cyclic::get_group(group_min_size);
+        let group = Cyclic::get_group(group_min_size)?;
 
         // 计算 p - 1
         let p_sub_one = group.prime - 1;
 
-        Self {
+        Ok(Self {
             p: group.prime,
-            prim_root: Cyclic::get_prim_root(&group, rng, Cyclic::get_max_root(p_sub_one, type_max)),
+            prim_root: Cyclic::get_prim_root(&group, rng, Cyclic::get_max_root(p_sub_one, type_max))?,
             p_sub_one,
 
             bits_num: bits_for_ip,
-        }
+        })
     }
 
 
     /// 找出和<u>最小元素</u>相匹配的<u>乘法群</u>
-    pub fn get_group(min_size:u128) -> CyclicGroup {
+    pub fn get_group(min_size:u128) -> Result<CyclicGroup, TargetError> {
 
         for i in CYCLE_GROUP {
 
             if i.prime > min_size {
-                return i
+                return Ok(i)
             }
         }
 
-        error!("{}", SYS.get_info("err", "cycle_group_not_found"));
-        exit(1)
+        Err(TargetError::invalid("cycle_group_not_found").with(min_size))
     }
 
     /// 根据选定的乘法群生成原根
-    pub fn get_prim_root(group:&CyclicGroup, rng:&mut StdRng, max_root:u128) -> u128 {
+    pub fn get_prim_root(group:&CyclicGroup, rng:&mut StdRng, max_root:u128) -> Result<u128, TargetError> {
 
         // 生成一个随机数
         let mut candidate:u128 = rng.random_range(2..group.prime);
@@ -83,13 +80,7 @@ impl Cyclic {
                 // 候选 ^ ( ( p - 1 ) / 因子 )  % p
                 let res = base.modpow(&power,&prime);
 
-                let res_ui = match res.to_u128() {
-                    Some(r) => r,
-                    None => {
-                        error!("{}", SYS.get_info("err", "get_prim_root_err"));
-                        exit(1)
-                    }
-                };
+                let res_ui = res.to_u128().ok_or(TargetError::invalid("get_prim_root_err"))?;
 
                 // 如果有一个因子的对应值为 1
                 if res_ui == 1 {
@@ -104,7 +95,7 @@ impl Cyclic {
                 // 如果所有因子的对应值都不是 1, 说明是 原根
                 if candidate != 0 {
                     // 注意不能为 0
-                    return candidate
+                    return Ok(candidate)
                 }
             }
 
@@ -1,54 +1,39 @@
-use std::process::exit;
-use log::error;
 use num_bigint::BigUint;
-use num_traits::{FromPrimitive, ToPrimitive};
+use num_traits::ToPrimitive;
 use crate::modules::target_iterators::cycle_group::cyclic::Cyclic;
-use crate::SYS;
+use crate::core::errors::TargetError;
 
 impl Cyclic {
 
     /// 确保目标值在 u64 表示范围内
-    pub fn get_val_with_check_u64(val:u128) -> u64 {
+    pub fn get_val_with_check_u64(val:u128) -> Result<u64, TargetError> {
 
         if val > (u64::MAX as u128) {
-            error!("{}", SYS.get_info("err","p_too_big"));
-            exit(1)
+            return Err(TargetError::invalid("p_too_big"))
         }
 
-        val as u64
+        Ok(val as u64)
     }
 
     /// 将 u64 解析为大数
     pub fn parse_u64_to_big_num(val:u64) -> BigUint {
-        BigUint::from_u64(val).unwrap_or_else(|| {
-            error!("{}", SYS.get_info("err","u64_to_big_num_err"));
-            exit(1)
-        })
+        BigUint::from(val)
     }
 
     /// 将 大数 解析为 u64
-    pub fn parse_big_num_to_u64(val:BigUint) -> u64 {
-        val.to_u64().unwrap_or_else(|| {
-            error!("{}", SYS.get_info("err","big_num_to_u64_err"));
-            exit(1)
-        })
+    pub fn parse_big_num_to_u64(val:BigUint) -> Result<u64, TargetError> {
+        val.to_u64().ok_or(TargetError::invalid("big_num_to_u64_err"))
     }
 
 
     /// 将 u128 解析为大数
     pub fn parse_u128_to_big_num(val:u128) -> BigUint {
-        BigUint::from_u128(val).unwrap_or_else(|| {
-            error!("{}", SYS.get_info("err","u128_to_big_num_err"));
-            exit(1)
-        })
+        BigUint::from(val)
     }
 
     /// 将 大数 解析为 u128
-    pub fn parse_big_num_to_u128(val:BigUint) -> u128 {
-        val.to_u128().unwrap_or_else(|| {
-            error!("{}", SYS.get_info("err","big_num_to_u128_err"));
-            exit(1)
-        })
+    pub fn parse_big_num_to_u128(val:BigUint) -> Result<u128, TargetError> {
+        val.to_u128().ok_or(TargetError::invalid("big_num_to_u128_err"))
     }
 
 
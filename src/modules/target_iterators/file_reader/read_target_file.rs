@@ -1,9 +1,8 @@
 use std::fs::{File, OpenOptions};
 use std::io::{BufRead, BufReader, Seek};
-use std::process::exit;
-use log::error;
 use regex::Regex;
 use crate::SYS;
+use crate::core::errors::TargetError;
 use crate::tools::file::parse_context::count_file_lines;
 
 pub struct TargetFileReader {
@@ -25,70 +24,58 @@ impl TargetFileReader {
         }
     }
 
-    /// 获取真正的起始索引
-    pub fn get_start(&self, start_index:u64, end_index:u64) -> (bool, u64){
+    /// 打开目标文件, 并将文件指针指向 给定字节索引 的位置
+    pub fn open_at(&self, index:u64) -> Result<File, TargetError> {
 
-        // 获取补全首行
-        let first_line = self.get_full_first_line(start_index);
+        let mut file = OpenOptions::new().read(true).write(false).open(&self.path)
+            .map_err(|e| TargetError::io("open_targets_file_failed").with(format!("{} {}", self.path, e)))?;
 
-        match OpenOptions::new().read(true).write(false).open(&self.path) {
+        file.seek(std::io::SeekFrom::Start(index))
+            .map_err(|_| TargetError::io("seek_file_failed").with(format!("{} {}", index, self.path)))?;
 
-            Ok(mut file) => {
+        Ok(file)
+    }
 
-                // 将文件指针指向 开始索引 的位置
-                file.seek(std::io::SeekFrom::Start(start_index)).map_err(
-                    |_| {
-                        error!("{} {} {}", SYS.get_info("err", "seek_file_failed"), start_index, self.path);
-                        exit(1)
-                    }
-                ).unwrap();
+    /// 获取真正的起始索引
+    pub fn get_start(&self, start_index:u64, end_index:u64) -> Result<(bool, u64), TargetError> {
 
-                // 创建缓冲区读取器
-                let mut reader = BufReader::new(file);
-                let mut buffer= String::new();
+        // 获取补全首行
+        let first_line = self.get_full_first_line(start_index)?;
 
-                // 读取 原生(非补全)的 首行
-                let offset = reader.read_line(&mut buffer).map_err(
-                    |_| {
-                        error!("{} {}", SYS.get_info("err", "read_target_line_failed"), self.path);
-                        exit(1)
-                    }
-                ).unwrap();
+        // 将文件指针指向 开始索引 的位置, 并创建缓冲区读取器
+        let mut reader = BufReader::new(self.open_at(start_index)?);
+        let mut buffer= String::new();
 
-                if buffer.trim() == first_line {
-                    // 如果 补全的首行 和 非补全的首行一致
+        // 读取 原生(非补全)的 首行
+        let offset = reader.read_line(&mut buffer)
+            .map_err(|_| TargetError::io("read_target_line_failed").with(&self.path))?;
 
-                    //       [ 1, 2, 3...  ]
-                    // start [ 1, 2, 3...  ]
+        if buffer.trim() == first_line {
+            // 如果 补全的首行 和 非补全的首行一致
 
-                    (true, start_index)
-                } else {
-                    // 如果 补全的首行 和 非补全的首行 不一致
+            //       [ 1, 2, 3...  ]
+            // start [ 1, 2, 3...  ]
 
-                    //       [ 1, 2, 3...  ]
-                    //       [ 1, start, 3...  ] new_start
+            Ok((true, start_index))
+        } else {
+            // 如果 补全的首行 和 非补全的首行 不一致
 
-                    let new_start_index = start_index + (offset as u64);
-                    return if new_start_index > end_index {
+            //       [ 1, 2, 3...  ]
+            //       [ 1, start, 3...  ] new_start
 
-                        // 新的开始索引 已经到下一个线程的区域
-                        (false, 0)
-                    } else {
-                        (true, new_start_index)
-                    }
-                }
-            }
+            let new_start_index = start_index + (offset as u64);
+            if new_start_index > end_index {
 
-            Err(_) => {
-                error!("{} {}", SYS.get_info("err", "open_targets_file_failed"), self.path);
-                exit(1)
+                // 新的开始索引 已经到下一个线程的区域
+                Ok((false, 0))
+            } else {
+                Ok((true, new_start_index))
             }
         }
-
     }
 
 
-    fn get_full_first_line(&self, start_index:u64) -> String {
+    fn get_full_first_line(&self, start_index:u64) -> Result<String, TargetError> {
 
         // 获取回退索引
         let mut fallback_index = if start_index >= self.fallback_bytes {
@@ -99,49 +86,27 @@ impl TargetFileReader {
             0
         };
 
-        match OpenOptions::new().read(true).write(false).open(&self.path) {
-            Ok(mut file) => {
+        // 将文件指针指向 开始索引之前 fallback_index 字节的位置, 并创建缓冲区读取器
+        let mut reader = BufReader::new(self.open_at(fallback_index)?);
+        let mut buffer= String::new();
 
-                // 将文件指针指向 开始索引之前 fallback_index 字节的位置
-                file.seek(std::io::SeekFrom::Start(fallback_index)).map_err(
-                    |_| {
-                        error!("{} {} {}", SYS.get_info("err", "seek_file_failed"), start_index, self.path);
-                        exit(1)
-                    }
-                ).unwrap();
-
-                // 创建缓冲区读取器
-                let mut reader = BufReader::new(file);
-                let mut buffer= String::new();
-
-                // 按行读取
-                loop {
-                    let bytes = reader.read_line(&mut buffer).map_err(
-                        |_| {
-                            error!("{} {}", SYS.get_info("err", "read_target_line_failed"), self.path);
-                            exit(1)
-                        }
-                    ).unwrap();
-                    fallback_index += bytes as u64;
-
-                    if bytes == 0 {
-                        // 已经到文件末尾
-                        // 在到达 开始索引之前就到了文件末尾, 意味着发生错误
-                        error!("{} {}", SYS.get_info("err", "target_file_index_invalid"), self.path);
-                        exit(1)
-                    }
+        // 按行读取
+        loop {
+            let bytes = reader.read_line(&mut buffer)
+                .map_err(|_| TargetError::io("read_target_line_failed").with(&self.path))?;
+            fallback_index += bytes as u64;
 
-                    if fallback_index > start_index {
-                        // 已经读取过 索引为 start_index 的字节
-                        return buffer.trim().to_string()
-                    }
-                    buffer.clear();
-                }
+            if bytes == 0 {
+                // 已经到文件末尾
+                // 在到达 开始索引之前就到了文件末尾, 意味着发生错误
+                return Err(TargetError::invalid("target_file_index_invalid").with(&self.path))
             }
-            Err(_) => {
-                error!("{} {}", SYS.get_info("err", "open_targets_file_failed"), self.path);
-                exit(1)
+
+            if fallback_index > start_index {
+                // 已经读取过 索引为 start_index 的字节
+                return Ok(buffer.trim().to_string())
             }
+            buffer.clear();
         }
     }
 
@@ -149,16 +114,11 @@ impl TargetFileReader {
 
     /// 0: 起始字节索引, 1:最后字节索引, 2:局部目标数量(字节数 or 目标数量)
     /// 注意: 索引左右均为闭区间
-    pub fn assign(&self, thread_num:u64) -> Vec<(u64, u64, u64)> {
+    pub fn assign(&self, thread_num:u64) -> Result<Vec<(u64, u64, u64)>, TargetError> {
 
         let mut assign_vec;
 
-        let f = File::open(&self.path).map_err(
-            |_| {
-                error!("{} {}", SYS.get_info("err", "open_targets_file_failed"), self.path);
-                exit(1)
-            }
-        ).unwrap();
+        let f = File::open(&self.path).map_err(|e| TargetError::io("open_targets_file_failed").with(format!("{} {}", self.path, e)))?;
 
         if let Some(tar_num) = self.tar_num {
             // 如果存在 有效目标数量, 按照 有效目标数量进行分割
@@ -191,8 +151,7 @@ impl TargetFileReader {
 
                 if pre_last_add_one > cur_last {
                     // 如果范围的 起始字节索引 大于 最后字节索引
-                    error!("{}", SYS.get_info("err", "assign_by_num_failed"));
-                    exit(1)
+                    return Err(TargetError::invalid("assign_by_num_failed"))
                 }
 
                 assign_vec.push((pre_last_add_one, cur_last, cur_num));
@@ -203,17 +162,12 @@ impl TargetFileReader {
             // 如果不存在有效目标数量, 使用 字节大小进行分割
 
             // 获取文件元数据
-            let metadata = f.metadata().map_err(
-                |_| {
-                    error!("{} {}", SYS.get_info("err", "get_target_file_info_failed"), self.path);
-                    exit(1)
-                }
-            ).unwrap();
+            let metadata = f.metadata().map_err(|_| TargetError::io("get_target_file_info_failed").with(&self.path))?;
 
             assign_vec = Self::assign_by_bytes(metadata.len(), thread_num);
 
         }
-        assign_vec
+        Ok(assign_vec)
     }
 
 
@@ -269,24 +223,14 @@ impl TargetFileReader {
 
     /// 从 ipv4目标文件名 中提取附加信息
     /// 0: 目标数量  1: 范围是否有效 2: 最小ip 3: 最大ip
-    pub fn parse_file_info_v4(&mut self) -> (Option<u64>, bool, u32, u32) {
+    pub fn parse_file_info_v4(&mut self) -> Result<(Option<u64>, bool, u32, u32), TargetError> {
 
         // 提取目标数量
         let tar_num;
         {
-            let num_regex = Regex::new(r"_num(\d+)_").map_err(
-                |_|{
-                    error!("{} {}", SYS.get_info("err", "parse_targets_file_name_failed"), self.path);
-                    exit(1)
-                }
-            ).unwrap();
+            let num_regex = Regex::new(r"_num(\d+)_").map_err(|_| TargetError::invalid("parse_targets_file_name_failed").with(&self.path))?;
             if let Some(num_match) = num_regex.captures(&self.path) {
-                let num = num_match[1].parse::<u64>().map_err(
-                    |_|{
-                        error!("{} {}", SYS.get_info("err", "parse_targets_file_name_failed"), self.path);
-                        exit(1)
-                    }
-                ).unwrap();
+                let num = num_match[1].parse::<u64>().map_err(|_| TargetError::invalid("parse_targets_file_name_failed").with(&self.path))?;
                 tar_num = Some(num);
             } else {
                 // 如果未匹配到 总数量字段
@@ -310,19 +254,9 @@ impl TargetFileReader {
         let mut max_ip_flag = false;
         {
 
-            let min_regex = Regex::new(r"_min(\d+)_").map_err(
-                |_|{
-                    error!("{} {}", SYS.get_info("err", "parse_targets_file_name_failed"), self.path);
-                    exit(1)
-                }
-            ).unwrap();
+            let min_regex = Regex::new(r"_min(\d+)_").map_err(|_| TargetError::invalid("parse_targets_file_name_failed").with(&self.path))?;
             if let Some(min_match) = min_regex.captures(&self.path) {
-                let min = min_match[1].parse::<u32>().map_err(
-                    |_|{
-                        error!("{} {}", SYS.get_info("err", "parse_targets_file_name_failed"), self.path);
-                        exit(1)
-                    }
-                ).unwrap();
+                let min = min_match[1].parse::<u32>().map_err(|_| TargetError::invalid("parse_targets_file_name_failed").with(&self.path))?;
                 min_ip = min;
                 min_ip_flag = true;
             } else {
@@ -330,19 +264,9 @@ impl TargetFileReader {
             }
 
 
-            let max_regex = Regex::new(r"_max(\d+)_").map_err(
-                |_|{
-                    error!("{} {}", SYS.get_info("err", "parse_targets_file_name_failed"), self.path);
-                    exit(1)
-                }
-            ).unwrap();
+            let max_regex = Regex::new(r"_max(\d+)_").map_err(|_| TargetError::invalid("parse_targets_file_name_failed").with(&self.path))?;
             if let Some(max_match) = max_regex.captures(&self.path) {
-                let max = max_match[1].parse::<u32>().map_err(
-                    |_|{
-                        error!("{} {}", SYS.get_info("err", "parse_targets_file_name_failed"), self.path);
-                        exit(1)
-                    }
-                ).unwrap();
+                let max = max_match[1].parse::<u32>().map_err(|_| TargetError::invalid("parse_targets_file_name_failed").with(&self.path))?;
                 max_ip = max;
                 max_ip_flag = true;
             } else {
@@ -352,9 +276,9 @@ impl TargetFileReader {
 
         if min_ip_flag && max_ip_flag {
             // 只有 最小ip 和 最大ip 都存在时, 范围才有效
-            (tar_num, true, min_ip, max_ip)
+            Ok((tar_num, true, min_ip, max_ip))
         } else {
-            (tar_num, false, min_ip, max_ip)
+            Ok((tar_num, false, min_ip, max_ip))
         }
 
     }
@@ -363,24 +287,14 @@ impl TargetFileReader {
 
     /// 从 ipv6目标文件名 中提取附加信息
     /// 0: 目标数量  1: 范围是否有效 2: 最小ip 3: 最大ip
-    pub fn parse_file_info_v6(&mut self) -> (Option<u64>, bool, u128, u128) {
+    pub fn parse_file_info_v6(&mut self) -> Result<(Option<u64>, bool, u128, u128), TargetError> {
 
         // 提取目标数量
         let tar_num;
         {
-            let num_regex = Regex::new(r"_num(\d+)_").map_err(
-                |_|{
-                    error!("{} {}", SYS.get_info("err", "parse_targets_file_name_failed"), self.path);
-                    exit(1)
-                }
-            ).unwrap();
+            let num_regex = Regex::new(r"_num(\d+)_").map_err(|_| TargetError::invalid("parse_targets_file_name_failed").with(&self.path))?;
             if let Some(num_match) = num_regex.captures(&self.path) {
-                let num = num_match[1].parse::<u64>().map_err(
-                    |_|{
-                        error!("{} {}", SYS.get_info("err", "parse_targets_file_name_failed"), self.path);
-                        exit(1)
-                    }
-                ).unwrap();
+                let num = num_match[1].parse::<u64>().map_err(|_| TargetError::invalid("parse_targets_file_name_failed").with(&self.path))?;
                 tar_num = Some(num);
             } else {
 
@@ -405,19 +319,9 @@ impl TargetFileReader {
         let mut max_ip_flag = false;
         {
 
-            let min_regex = Regex::new(r"_min(\d+)_").map_err(
-                |_|{
-                    error!("{} {}", SYS.get_info("err", "parse_targets_file_name_failed"), self.path);
-                    exit(1)
-                }
-            ).unwrap();
+            let min_regex = Regex::new(r"_min(\d+)_").map_err(|_| TargetError::invalid("parse_targets_file_name_failed").with(&self.path))?;
             if let Some(min_match) = min_regex.captures(&self.path) {
-                let min = min_match[1].parse::<u128>().map_err(
-                    |_|{
-                        error!("{} {}", SYS.get_info("err", "parse_targets_file_name_failed"), self.path);
-                        exit(1)
-                    }
-                ).unwrap();
+                let min = min_match[1].parse::<u128>().map_err(|_| TargetError::invalid("parse_targets_file_name_failed").with(&self.path))?;
                 min_ip = min;
                 min_ip_flag = true;
             } else {
@@ -425,19 +329,9 @@ impl TargetFileReader {
             }
 
 
-            let max_regex = Regex::new(r"_max(\d+)_").map_err(
-                |_|{
-                    error!("{} {}", SYS.get_info("err", "parse_targets_file_name_failed"), self.path);
-                    exit(1)
-                }
-            ).unwrap();
+            let max_regex = Regex::new(r"_max(\d+)_").map_err(|_| TargetError::invalid("parse_targets_file_name_failed").with(&self.path))?;
             if let Some(max_match) = max_regex.captures(&self.path) {
-                let max = max_match[1].parse::<u128>().map_err(
-                    |_|{
-                        error!("{} {}", SYS.get_info("err", "parse_targets_file_name_failed"), self.path);
-                        exit(1)
-                    }
-                ).unwrap();
+                let max = max_match[1].parse::<u128>().map_err(|_| TargetError::invalid("parse_targets_file_name_failed").with(&self.path))?;
                 max_ip = max;
                 max_ip_flag = true;
             } else {
@@ -447,9 +341,9 @@ impl TargetFileReader {
 
         if min_ip_flag && max_ip_flag {
             // 只有 最小ip 和 最大ip 都存在时, 范围才有效
-            (tar_num, true, min_ip, max_ip)
+            Ok((tar_num, true, min_ip, max_ip))
         } else {
-            (tar_num, false, min_ip, max_ip)
+            Ok((tar_num, false, min_ip, max_ip))
         }
 
     }
@@ -2,7 +2,6 @@ use std::fs::{File};
 use std::io::{BufRead, BufReader};
 use std::net::{Ipv6Addr};
 use std::str::FromStr;
-use std::process::exit;
 use log::{error, warn};
 use crate::modules::target_iterators::Ipv6IterFP;
 use crate::SYS;
@@ -69,12 +68,14 @@ impl Ipv6FileReader {
 impl Ipv6IterFP for Ipv6FileReader {
     fn get_next_ip_port(&mut self) -> (bool, bool, u128, u16) {
         let mut current_line = String::new();
-        let bytes = self.reader.read_line(&mut current_line).map_err(
-            |_|{
-                error!("{}", SYS.get_info("err", "read_cur_target_failed"));
-                exit(1)
+        let bytes = match self.reader.read_line(&mut current_line) {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                // 读取失败时 结束当前线程的目标范围, 不终止进程
+                error!("{} {}", SYS.get_info("err", "read_cur_target_failed"), e);
+                return (false, false, 0, 0)
             }
-        ).unwrap();
+        };
         if bytes == 0 {
             // 到文件末尾, 说明是最后一个, 而且是无效值
             return (false, false, 0, 0)
@@ -1,88 +1,56 @@
 use std::cmp::min;
-use std::fs::OpenOptions;
-use std::io::{BufReader, Seek};
-use std::process::exit;
-use log::error;
+use std::io::BufReader;
 use crate::modules::target_iterators::{Ipv6FileReader, TargetFileReader};
-use crate::SYS;
+use crate::core::errors::TargetError;
 
 pub mod ipv6_file_reader;
 
 
 impl TargetFileReader {
 
-    pub fn get_ipv6_file_reader(&self, assigned_targets:&(u64,u64,u64), cur_tar_port:u16) -> Option<Ipv6FileReader> {
+    pub fn get_ipv6_file_reader(&self, assigned_targets:&(u64,u64,u64), cur_tar_port:u16) -> Result<Option<Ipv6FileReader>, TargetError> {
 
-        if let Some(_) = self.tar_num {
+        if self.tar_num.is_some() {
             // 存在目标数量, 按照目标数量进行分配
 
-            match OpenOptions::new().read(true).write(false).open(&self.path) {
-                Ok(mut file) => {
+            // 将文件指针指向 开始索引(局部) 的位置
+            let file = self.open_at(assigned_targets.0)?;
 
-                    // 将文件指针指向 开始索引(局部) 的位置
-                    file.seek(std::io::SeekFrom::Start(assigned_targets.0)).map_err(
-                        |_| {
-                            error!("{} {} {}", SYS.get_info("err", "seek_file_failed"), assigned_targets.0, self.path);
-                            exit(1)
-                        }
-                    ).unwrap();
+            Ok(Some(
+                Ipv6FileReader {
+                    current_index: assigned_targets.0,
+                    end_index: assigned_targets.1,
+                    reader: BufReader::with_capacity(self.max_read_buf_bytes as usize, file),
 
-                    return Some(
-                        Ipv6FileReader {
-                            current_index: assigned_targets.0,
-                            end_index: assigned_targets.1,
-                            reader: BufReader::with_capacity(self.max_read_buf_bytes as usize, file),
-
-                            tar_port_from_file: cur_tar_port == 0,
-                            tar_port: cur_tar_port,
-                        }
-                    )
-                }
-                Err(_) => {
-                    error!("{} {}", SYS.get_info("err", "open_targets_file_failed"), self.path);
-                    exit(1)
+                    tar_port_from_file: cur_tar_port == 0,
+                    tar_port: cur_tar_port,
                 }
-            }
+            ))
 
         } else {
             // 不存在目标数量, 按照字节数进行分配
 
-            let (valid, start_index) = self.get_start(assigned_targets.0, assigned_targets.1);
+            let (valid, start_index) = self.get_start(assigned_targets.0, assigned_targets.1)?;
 
-            if valid {
-                match OpenOptions::new().read(true).write(false).open(&self.path) {
-                    Ok(mut file) => {
+            if !valid { return Ok(None) }
 
-                        // 将文件指针指向 开始索引(局部) 的位置
-                        file.seek(std::io::SeekFrom::Start(start_index)).map_err(
-                            |_| {
-                                error!("{} {} {}", SYS.get_info("err", "seek_file_failed"), start_index, self.path);
-                                exit(1)
-                            }
-                        ).unwrap();
+            // 将文件指针指向 开始索引(局部) 的位置
+            let file = self.open_at(start_index)?;
 
-                        // 取 设定的最大缓冲区大小(字节数), 当前被分配区域大小(字节) + 回退字节数, 中的 最小值 作为缓冲区大小
-                        let buf_capacity = min(self.max_read_buf_bytes,
-                                               assigned_targets.2 + self.fallback_bytes) as usize;
+            // 取 设定的最大缓冲区大小(字节数), 当前被分配区域大小(字节) + 回退字节数, 中的 最小值 作为缓冲区大小
+            let buf_capacity = min(self.max_read_buf_bytes,
+                                   assigned_targets.2 + self.fallback_bytes) as usize;
 
-                        return Some(
-                            Ipv6FileReader {
-                                current_index: start_index,
-                                end_index: assigned_targets.1,
-                                reader: BufReader::with_capacity(buf_capacity, file),
+            Ok(Some(
+                Ipv6FileReader {
+                    current_index: start_index,
+                    end_index: assigned_targets.1,
+                    reader: BufReader::with_capacity(buf_capacity, file),
 
-                                tar_port_from_file: cur_tar_port == 0,
-                                tar_port: cur_tar_port,
-                            }
-                        )
-                    }
-                    Err(_) => {
-                        error!("{} {}", SYS.get_info("err", "open_targets_file_failed"), self.path);
-                        exit(1)
-                    }
+                    tar_port_from_file: cur_tar_port == 0,
+                    tar_port: cur_tar_port,
                 }
-            }
-            None
+            ))
         }
     }
-}
\ No newline at end of file
+}
@@ -3,7 +3,6 @@ mod method;
 use std::fs::OpenOptions;
 use std::io::{BufRead, BufReader};
 use std::net::Ipv6Addr;
-use std::process::exit;
 use std::str::FromStr;
 use ahash::AHashMap;
 use log::{error, warn};
@@ -11,6 +10,7 @@ use rand::prelude::SliceRandom;
 use rand::rng;
 use crate::modules::target_iterators::IPv6PrefixTree;
 use crate::SYS;
+use crate::core::errors::TargetError;
 
 
 #[derive(Clone)]
@@ -47,38 +47,34 @@ impl IPv6AliaChecker {
 
 
     pub fn new(path:String, prefix_len:u8, prefix_count:usize, rand_count:usize,
-               aliased_threshold:f64, prefixes_len_per_batch:usize, not_aliased_records_path:String) -> Self {
+               aliased_threshold:f64, prefixes_len_per_batch:usize, not_aliased_records_path:String) -> Result<Self, TargetError> {
 
         if prefix_len >= 128 {
             // 前缀长度不能大于等于128
-            error!("{}", SYS.get_info("err", "ipv6_alia_prefix_len_err"));
-            exit(1)
+            return Err(TargetError::invalid("ipv6_alia_prefix_len_err"))
         }
         
         if rand_count > (u8::MAX as usize) {
             // 如果 每个前缀生成随机地址的数量 超过一个字节能表示的值
-            error!("{}", SYS.get_info("err", "ipv6_alia_rand_count_err"));
-            exit(1)
+            return Err(TargetError::invalid("ipv6_alia_rand_count_err"))
         }
 
         if prefixes_len_per_batch >= ((u32::MAX >> 8) as usize) {
             // 如果前缀总量超过表示范围
-            error!("{}", SYS.get_info("err", "prefixes_len_per_batch_over"));
-            exit(1)
+            return Err(TargetError::invalid("prefixes_len_per_batch_over"))
         }
         
         let aliased_threshold = ((rand_count as f64) * aliased_threshold) as u8;
         if aliased_threshold < 1 || (aliased_threshold as usize) > rand_count {
             // 别名阀限必须大于等于1, 且不能大于 每前缀随机地址数量
-            error!("{}", SYS.get_info("err", "alia_threshold_err"));
-            exit(1)
+            return Err(TargetError::invalid("alia_threshold_err"))
         }
 
         let not_aliased_records_path = not_aliased_records_path.trim();
         let not_aliased_records_path = if not_aliased_records_path == "" { None }
         else { Some(not_aliased_records_path.to_string()) };
 
-        Self {
+        Ok(Self {
             prefix_len,
             rand_count,
 
@@ -92,7 +88,7 @@ impl IPv6AliaChecker {
             
             scan_flag: 1,
             not_aliased_records_path,
-        }
+        })
     }
     
     pub fn change_scan_flag(&mut self){
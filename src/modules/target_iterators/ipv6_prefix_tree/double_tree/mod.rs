@@ -2,10 +2,8 @@ mod receive;
 mod gen_targets;
 
 
-use std::process::exit;
 use ahash::AHashMap;
-use log::error;
-use crate::SYS;
+use crate::core::errors::TargetError;
 
 
 pub struct Ipv6VecDoubleTree {
@@ -40,12 +38,12 @@ pub struct Ipv6VecDoubleTree {
 
 
 impl Ipv6VecDoubleTree {
-    pub fn new(initial_ttl:u8, gap_limit:u8, max_ttl:u8, min_target_num:usize) -> Self {
+    pub fn new(initial_ttl:u8, gap_limit:u8, max_ttl:u8, min_target_num:usize) -> Result<Self, TargetError> {
         
         // 最大ttl不得超过64
-        if max_ttl > 64 { error!("{}", SYS.get_info("err", "topo_max_ttl_err"));exit(1) }
+        if max_ttl > 64 { return Err(TargetError::invalid("topo_max_ttl_err").with(max_ttl)) }
         
-        Self {
+        Ok(Self {
             initial_ttl,
             gap_limit,
             max_ttl,
@@ -60,7 +58,7 @@ impl Ipv6VecDoubleTree {
             cur_no_recv: Vec::new(),
             silent_count: Vec::new(),
             reward_used: Vec::new(),
-        }
+        })
     }
 
 
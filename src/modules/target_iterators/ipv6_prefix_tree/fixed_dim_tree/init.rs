@@ -1,7 +1,6 @@
 use std::fs::OpenOptions;
 use std::io::{BufRead, BufReader};
 use std::net::Ipv6Addr;
-use std::process::exit;
 use std::str::FromStr;
 use ahash::AHashMap;
 use log::{error, info, warn};
@@ -10,23 +9,22 @@ use crate::modules::target_iterators::ipv6_prefix_tree::fixed_dim_tree::IPv6Fixe
 use crate::modules::target_iterators::ipv6_prefix_tree::prefix_tree::IPv6PrefixTree;
 use crate::SYS;
 use crate::tools::others::parse::parse_str;
+use crate::core::errors::TargetError;
 
 impl IPv6FixedPrefixTree {
 
     pub fn new(divide_dim:u32, max_prefix_len:u32, learning_rate:Decimal, seeds_path:String, 
                prefix_path:String, start_prefix_len:u8, threshold:String, extra_node_num:usize,
-               allow_leaf_expand:bool, allow_layer_expand:bool, layer_expand_ratio:f64, rand_ord:bool) -> Self {
+               allow_leaf_expand:bool, allow_layer_expand:bool, layer_expand_ratio:f64, rand_ord:bool) -> Result<Self, TargetError> {
 
         // 划分维度必须 在[1, 32]范围内
         if divide_dim < 1 || 32 < divide_dim {
-            error!("{} {}", SYS.get_info("err", "ipv6_space_tree_divide_dim_err"), "[1, 32]");
-            exit(1)
+            return Err(TargetError::invalid("ipv6_space_tree_divide_dim_err").with("[1, 32]"))
         }
 
         // 最大前缀长度必须 大于1, 小于或等于128, 且 能被划分维度整除
         if max_prefix_len <= 1 || 128 < max_prefix_len || (max_prefix_len % divide_dim != 0) {
-            error!("{}", SYS.get_info("err", "ipv6_tree_max_prefix_len_err"));
-            exit(1)
+            return Err(TargetError::invalid("ipv6_tree_max_prefix_len_err"))
         }
 
         let move_len_cap = max_prefix_len / divide_dim;
@@ -47,12 +45,12 @@ impl IPv6FixedPrefixTree {
             "0" | "0.0" => Some(Decimal::ZERO),
             "none" => None,
             other => {
-                let t = parse_str(other);
+                let t = parse_str(other)?;
                 Some(t)
             }
         };
         
-        Self {
+        Ok(Self {
             id_num: 0,
             
             dim: divide_dim as u8,
@@ -89,7 +87,7 @@ impl IPv6FixedPrefixTree {
             cur_parent_id_to_zero_child_id: AHashMap::new(),
 
             rand_ord,
-        }
+        })
     }
     
     /// 从 hit_list 中读取种子地址, 有效前缀位保留, 后续拼接 ::1
@@ -2,7 +2,6 @@ use std::cmp::min;
 use std::fs::OpenOptions;
 use std::io::{BufRead, BufReader};
 use std::net::Ipv6Addr;
-use std::process::exit;
 use std::str::FromStr;
 use ahash::AHashMap;
 use log::{error, warn};
@@ -10,18 +9,18 @@ use rust_decimal::Decimal;
 use crate::modules::target_iterators::ipv6_prefix_tree::prefix_tree::IPv6PrefixTree;
 use crate::SYS;
 use crate::tools::others::parse::parse_str;
+use crate::core::errors::TargetError;
 
 impl IPv6PrefixTree {
 
 
     pub fn new(default_divide_dim:u32,threshold:String, seeds_path:String, prefix_path:String,
                start_prefix_len:u8, max_prefix_len:u8, learning_rate:Decimal, extra_node_num:usize,
-               allow_leaf_expand:bool, rand_ord:bool, child_max_size:usize) -> Self {
+               allow_leaf_expand:bool, rand_ord:bool, child_max_size:usize) -> Result<Self, TargetError> {
 
         // 划分维度必须 在[1, 32]范围内
         if default_divide_dim < 1 || 32 < default_divide_dim {
-            error!("{} {}", SYS.get_info("err", "ipv6_space_tree_divide_dim_err"), "[1, 32]");
-            exit(1)
+            return Err(TargetError::invalid("ipv6_space_tree_divide_dim_err").with("[1, 32]"))
         }
 
         // 计算划分维度对应的单位空间大小
@@ -31,12 +30,12 @@ impl IPv6PrefixTree {
             "0" | "0.0" => Some(Decimal::ZERO),
             "none" => None,
             other => {
-                let t = parse_str(other);
+                let t = parse_str(other)?;
                 Some(t)
             }
         };
 
-        Self {
+        Ok(Self {
             id_num: 0,
 
             default_dim: default_divide_dim as u8,
@@ -65,7 +64,7 @@ impl IPv6PrefixTree {
 
             child_max_size,
             rand_ord,
-        }
+        })
     }
 
     /// 获取初始化掩码， 用于遮蔽有效范围之外的部分
@@ -1,22 +1,17 @@
 use std::cell::RefCell;
-use std::process::exit;
 use std::rc::Rc;
 use ahash::AHashMap;
-use log::error;
 use crate::modules::target_iterators::ipv6_prefix_tree::prefix_tree::IPv6PrefixTree;
 use crate::modules::target_iterators::ipv6_prefix_tree::prefix_tree::node::IPv6PrefixNode;
-use crate::SYS;
+use crate::core::errors::TargetError;
 
 impl IPv6PrefixTree {
 
-    pub fn init_queue(&mut self) -> (Vec<u128>, AHashMap<u128, usize>) {
+    pub fn init_queue(&mut self) -> Result<(Vec<u128>, AHashMap<u128, usize>), TargetError> {
 
         // 获取根节点
         let root = match self.root.as_ref() {
-            None => {
-                error!("{}", SYS.get_info("err", "root_not_found"));
-                exit(1)
-            }
+            None => return Err(TargetError::invalid("root_not_found")),
             Some(r) => r.clone()
         };
 
@@ -73,7 +68,7 @@ impl IPv6PrefixTree {
         }
 
         // 警告: 生成地址的队列 应与 节点队列保持一致
-        (generated_targets, addr_to_code)
+        Ok((generated_targets, addr_to_code))
     }
 
 
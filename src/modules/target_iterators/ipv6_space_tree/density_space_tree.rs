@@ -1,22 +1,20 @@
 use std::cell::{RefCell, RefMut};
-use std::process::exit;
 use std::rc::Rc;
-use log::error;
 use rand::rng;
 use rand::seq::IteratorRandom;
 use crate::modules::target_iterators::ipv6_space_tree::IPv6SpaceTreeNode;
 use crate::modules::target_iterators::ipv6_space_tree::space_tree::IPv6SpaceTree;
-use crate::SYS;
+use crate::core::errors::TargetError;
 
 impl IPv6SpaceTree {
 
     /// 初始化 密度地址空间树
-    pub fn init_density_tree(&mut self){
+    pub fn init_density_tree(&mut self) -> Result<(), TargetError> {
 
         let mut seeds:Vec<u128>;
         {
             // 从 文件 中读取种子地址
-            let all_seeds = self.get_seeds();
+            let all_seeds = self.get_seeds()?;
 
             // 凡是输入文件中的地址都不允许生成
             if self.no_allow_gen_seeds_from_file {
@@ -43,23 +41,23 @@ impl IPv6SpaceTree {
         let root = Rc::new(RefCell::new(root));
 
         // 裂解 种子地址集群, 生成 空间树
-        self.density_split_tree(root.clone());
+        self.density_split_tree(root.clone())?;
         self.root = Some(root);
         
         // 初始化  区域队列 和 对应的奖励队列
-        self.init_queue();
+        self.init_queue()
     }
 
 
     /// 递归分割形成密度空间树
-    pub fn density_split_tree(&mut self, node:Rc<RefCell<IPv6SpaceTreeNode>>){
+    pub fn density_split_tree(&mut self, node:Rc<RefCell<IPv6SpaceTreeNode>>) -> Result<(), TargetError> {
         
         let mut cur_node: RefMut<IPv6SpaceTreeNode> = node.borrow_mut();
 
         // 当前 地址聚类块地址数量 <= 聚类块地址数量上限
         // 该 节点 不需要继续分裂, 直接返回
         if cur_node.seed_addrs_list.len() <= self.max_leaf_size {
-            return
+            return Ok(())
         }
 
         // 该节点需要进行分裂
@@ -69,8 +67,7 @@ impl IPv6SpaceTree {
             let split_move_len = cur_node.split_move_len;
             if split_move_len == u8::MAX {
                 // 地址数量达到分裂要求, 但是不存在非零熵值位, 说明出现错误
-                error!("{}", SYS.get_info("err", "ipv6_space_tree_no_entropy_err"));
-                exit(1)
+                return Err(TargetError::invalid("ipv6_space_tree_no_entropy_err"))
             }
 
             // 取出 该节点分裂点上的统计信息
@@ -99,7 +96,7 @@ impl IPv6SpaceTree {
         }
         
         // 删除该节点中的 零熵值维度 和 分裂维度, 得到 子节点所需分割点维度
-        let child_move_len = cur_node.get_child_move_len_density();
+        let child_move_len = cur_node.get_child_move_len_density()?;
         
         // 生成子节点
         for ips in key_ips {
@@ -113,8 +110,9 @@ impl IPv6SpaceTree {
         
         // 裂解子空间区域
         for child in &cur_node.childs {
-            self.density_split_tree(child.clone());
+            self.density_split_tree(child.clone())?;
         }
+        Ok(())
     }
 
 
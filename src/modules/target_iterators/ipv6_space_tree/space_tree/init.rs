@@ -1,13 +1,13 @@
 use std::fs::OpenOptions;
 use std::io::{BufRead, BufReader};
 use std::net::Ipv6Addr;
-use std::process::exit;
 use std::str::FromStr;
 use ahash::AHashSet;
-use log::{error, warn};
+use log::warn;
 use crate::core::conf::tools::args_parse::port::parse_range;
 use crate::modules::target_iterators::ipv6_space_tree::space_tree::IPv6SpaceTree;
 use crate::SYS;
+use crate::core::errors::TargetError;
 
 
 /// ipv6空间树 初始化方法
@@ -17,32 +17,28 @@ impl IPv6SpaceTree {
     /// 输入 空间树划分维度, 划分范围
     /// 注意: 划分范围为 (开始位置, 结束位置), 位置有效范围[1, 128], 开始位置必须小于或等于结束位置且能被划分维度整除
     pub fn new(divide_dim:u32, divide_range:String, max_leaf_size:usize, mut no_allow_gen_seeds:bool,
-               learning_rate:f64, region_extraction_num:u32, seeds_path:String, seeds_num:usize, no_allow_gen_seeds_from_file:bool) -> Self {
+               learning_rate:f64, region_extraction_num:u32, seeds_path:String, seeds_num:usize, no_allow_gen_seeds_from_file:bool) -> Result<Self, TargetError> {
 
         // 划分维度必须 在[1, 32]范围内
         if divide_dim < 1 || 32 < divide_dim {
-            error!("{} {}", SYS.get_info("err", "ipv6_space_tree_divide_dim_err"), "[1, 32]");
-            exit(1)
+            return Err(TargetError::invalid("ipv6_space_tree_divide_dim_err").with("[1, 32]"))
         }
         
         // 聚类区域最小上限数量 为 2
         if max_leaf_size < 2 {
-            error!("{}", SYS.get_info("err", "ipv6_space_tree_max_leaf_size_err"));
-            exit(1)
+            return Err(TargetError::invalid("ipv6_space_tree_max_leaf_size_err"))
         }
 
         if region_extraction_num >= (u32::MAX >> 8)  {
-            error!("{}", SYS.get_info("err", "ipv6_space_tree_region_extraction_num_err"));
-            exit(1)
+            return Err(TargetError::invalid("ipv6_space_tree_region_extraction_num_err"))
         }
 
         // 计算右移距离向量
-        let range = parse_range(&divide_range, "ipv6_space_tree_range_err");
+        let range = parse_range(&divide_range, "ipv6_space_tree_range_err")?;
         // 保证 开始位置必须小于或等于结束位置, 范围大小必须能被划分维度整除
         let range_size = range.1 - range.0 + 1;
         if range.0 < 1 || 128 < range.0 || range.1 < 1 || 128 < range.1 || (range_size % divide_dim != 0) {
-            error!("{}", SYS.get_info("err", "ipv6_space_tree_range_err"));
-            exit(1)
+            return Err(TargetError::invalid("ipv6_space_tree_range_err"))
         }
 
         let move_len_cap = range_size / divide_dim;
@@ -63,7 +59,7 @@ impl IPv6SpaceTree {
             no_allow_gen_seeds = true;
         }
 
-        Self {
+        Ok(Self {
             id_num: 0,
             dim: divide_dim,
             dim_size,
@@ -90,7 +86,7 @@ impl IPv6SpaceTree {
             seeds_num,
             
             scan_flag: 1,
-        }
+        })
     }
 
 
@@ -104,57 +100,54 @@ impl IPv6SpaceTree {
 
     /// 获取种子地址
     /// 警告: 输入的种子地址必须是排好序且无重复的
-    pub fn get_seeds(&self) -> Vec<u128> {
+    pub fn get_seeds(&self) -> Result<Vec<u128>, TargetError> {
         // 所有种子地址
         let mut seeds:Vec<u128> = Vec::with_capacity(self.seeds_num);
 
-        match OpenOptions::new().read(true).write(false).open(&self.seeds_path) {
-            Ok(file) => {
-                // 生成读取缓冲区   注意后续调整缓冲区大小
-                let lines = BufReader::with_capacity(SYS.get_conf("conf","max_read_buf_bytes"), file).lines();
-
-                if self.range == (1, 128) {
-                    // 如果范围为整个ipv6地址空间范围
-                    for line in lines {
-                        match line {
-                            Ok(addr) => {
-                                match Ipv6Addr::from_str(addr.trim()) {
-                                    Ok(ipv6) => {
-                                        seeds.push(u128::from(ipv6));
-                                    }
-                                    Err(_) => warn!("{} {}", SYS.get_info("warn","file_line_invalid"), addr.trim())
-                                }
+        let file = OpenOptions::new().read(true).write(false).open(&self.seeds_path)
+            .map_err(|e| TargetError::io("open_targets_file_failed").with(format!("{} {}", self.seeds_path, e)))?;
+        // 生成读取缓冲区   注意后续调整缓冲区大小
+        let lines = BufReader::with_capacity(SYS.get_conf("conf","max_read_buf_bytes"), file).lines();
+
+        if self.range == (1, 128) {
+            // 如果范围为整个ipv6地址空间范围
+            for line in lines {
+                match line {
+                    Ok(addr) => {
+                        match Ipv6Addr::from_str(addr.trim()) {
+                            Ok(ipv6) => {
+                                seeds.push(u128::from(ipv6));
                             }
-                            Err(_) => {}
+                            Err(_) => warn!("{} {}", SYS.get_info("warn","file_line_invalid"), addr.trim())
                         }
                     }
-                } else {
-                    // 如果指定了初始化范围
-                    let init_mask = self.get_init_mask();
-                    let mut last_val:u128 = 0;
-                    for line in lines {
-                        match line {
-                            Ok(addr) => {
-                                match Ipv6Addr::from_str(addr.trim()) {
-                                    Ok(ipv6) => {
-                                        let cur_val = u128::from(ipv6) & init_mask;
-
-                                        if cur_val != last_val {
-                                            seeds.push(cur_val);
-                                            last_val = cur_val;
-                                        }
-                                    }
-                                    Err(_) => warn!("{} {}", SYS.get_info("warn","file_line_invalid"), addr.trim())
+                    Err(_) => {}
+                }
+            }
+        } else {
+            // 如果指定了初始化范围
+            let init_mask = self.get_init_mask();
+            let mut last_val:u128 = 0;
+            for line in lines {
+                match line {
+                    Ok(addr) => {
+                        match Ipv6Addr::from_str(addr.trim()) {
+                            Ok(ipv6) => {
+                                let cur_val = u128::from(ipv6) & init_mask;
+
+                                if cur_val != last_val {
+                                    seeds.push(cur_val);
+                                    last_val = cur_val;
                                 }
                             }
-                            Err(_) => {}
+                            Err(_) => warn!("{} {}", SYS.get_info("warn","file_line_invalid"), addr.trim())
                         }
                     }
+                    Err(_) => {}
                 }
             }
-            Err(_) => error!("{} {}", SYS.get_info("err", "open_targets_file_failed"), &self.seeds_path)
         }
-        seeds
+        Ok(seeds)
     }
     
     
@@ -1,35 +1,31 @@
 use std::cell::RefCell;
-use std::process::exit;
 use std::rc::Rc;
-use log::error;
 use crate::modules::target_iterators::ipv6_space_tree::IPv6SpaceTreeNode;
 use crate::modules::target_iterators::ipv6_space_tree::space_tree::IPv6SpaceTree;
-use crate::SYS;
 use crate::tools::others::sort::quick_sort_from_big_to_small;
+use crate::core::errors::TargetError;
 
 impl IPv6SpaceTree {
 
-    pub fn init_queue(&mut self){
+    pub fn init_queue(&mut self) -> Result<(), TargetError> {
 
         // 初始化 叶子节点队列 和 叶子节点队列对应的奖励队列
-        self.init_node_queue(self.no_allow_gen_seeds);
+        self.init_node_queue(self.no_allow_gen_seeds)?;
 
         // 将 奖励队列 和 叶子节点队列 按照 奖励队列 从大到小 排序
         let node_queue_len =  self.all_reward.len();
         quick_sort_from_big_to_small(&mut self.all_reward, &mut self.region_queue, 0, node_queue_len-1);
+        Ok(())
     }
 
-    pub fn init_node_queue(&mut self, no_allow_gen_seed:bool){
+    pub fn init_node_queue(&mut self, no_allow_gen_seed:bool) -> Result<(), TargetError> {
 
         // 存储广度优先遍历的节点队列
         let mut q:Vec<Rc<RefCell<IPv6SpaceTreeNode>>> = Vec::new();
 
         // 加入 根节点
         match self.root.as_ref() {
-            None => {
-                error!("{}", SYS.get_info("err", "root_not_found"));
-                exit(1)
-            },
+            None => return Err(TargetError::invalid("root_not_found")),
             Some(r) => q.push(r.clone())
         };
 
@@ -61,5 +57,6 @@ impl IPv6SpaceTree {
                 }
             }
         }
+        Ok(())
     }
 }
\ No newline at end of file
@@ -1,17 +1,15 @@
-use std::process::exit;
 use ahash::AHashSet;
-use log::error;
 use rand::prelude::IteratorRandom;
 use rand::rng;
 use crate::modules::target_iterators::ipv6_space_tree::IPv6SpaceTreeNode;
 use crate::modules::target_iterators::IPv6SpaceTree;
-use crate::SYS;
+use crate::core::errors::TargetError;
 
 impl IPv6SpaceTreeNode {
 
     /// 当一个节点确定要分裂时, 获取子区域的维度, 并从父区域删除这些维度
     /// 注意: 仅供密度空间树使用
-    pub fn get_child_move_len_density(&mut self) -> Vec<u8> {
+    pub fn get_child_move_len_density(&mut self) -> Result<Vec<u8>, TargetError> {
 
         let mut child_move_len:Vec<u8> = Vec::new();
 
@@ -24,7 +22,7 @@ impl IPv6SpaceTreeNode {
         }
 
         if index == usize::MAX {
-            error!("{}", SYS.get_info("err","get_child_move_len_err"));exit(1)
+            return Err(TargetError::invalid("get_child_move_len_err"))
         }
 
         // 孩子分割点
@@ -37,7 +35,7 @@ impl IPv6SpaceTreeNode {
         self.split_stack = self.split_stack[..=index].to_owned();
 
         child_move_len.sort();
-        child_move_len
+        Ok(child_move_len)
     }
 
     /// 在指定的[分割维度]上进行[扩展]
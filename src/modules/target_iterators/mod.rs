@@ -68,6 +68,19 @@ pub enum CycleIpv6PatternType {
 
 
 
+/// 循环群迭代器的 断点信息, 用于 保存 和 恢复 扫描进度
+pub trait CyclePosition {
+    /// 乘法群的 模数 和 原根, 用于校验断点文件
+    fn group(&self) -> (u128, u128);
+
+    /// 当前位置: 下一个待处理目标 对应的乘法群输出值
+    fn position(&self) -> u128;
+
+    /// 从断点恢复: 以 断点位置 作为当前目标, 最后一个目标不变, 在 init 之后调用
+    fn seek(&mut self, position:u128);
+}
+
+
 /// ipv4迭代器范式, 一般用于 网络层及以下协议 或 自分配端口 的迭代算法
 pub trait Ipv4Iter {
     /// 返回值: 0:是否为<u>非最终值</u>, 1:最终值是否有效, 2:ip地址
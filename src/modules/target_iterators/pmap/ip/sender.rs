@@ -1,14 +1,12 @@
-use std::process::exit;
 use std::sync::Arc;
-use log::error;
 use crate::modules::target_iterators::pmap::graph::Graph;
 use crate::modules::target_iterators::pmap::ip::IpStruct;
-use crate::SYS;
+use crate::core::errors::TargetError;
 
 impl IpStruct {
 
     #[inline]
-    pub fn send_port(&mut self, graph:&Arc<Graph>) -> u16 {
+    pub fn send_port(&mut self, graph:&Arc<Graph>) -> Result<u16, TargetError> {
 
         if let Some(s) = &self.state {
             // 如果 该地址 之前存在过状态
@@ -63,7 +61,7 @@ impl IpStruct {
                     let next_port = port_in_relative_table.port;
                     self.state_index += 1;
                     self.cur_sent_port = next_port;
-                    next_port
+                    Ok(next_port)
                 } else {
                     // 如果 绝对概率表中索引对应端口的概率 大于等于 相对概率表中索引对应端口的概率
                     // 应将 绝对概率表中的对应端口 作为 待推荐端口
@@ -71,7 +69,7 @@ impl IpStruct {
                     let next_port = graph.recommend_ports[self.ab_index];
                     self.ab_index += 1;
                     self.cur_sent_port = next_port;
-                    next_port
+                    Ok(next_port)
                 }
             } else if relative_index_avail {
                 // 如果 相对概率表 可用
@@ -80,7 +78,7 @@ impl IpStruct {
                 let next_port = s.ports[self.state_index].port;
                 self.state_index += 1;
                 self.cur_sent_port = next_port;
-                next_port
+                Ok(next_port)
             } else if ab_index_avail {
                 // 如果 绝对概率表 可用
 
@@ -88,13 +86,13 @@ impl IpStruct {
                 let next_port = graph.recommend_ports[self.ab_index];
                 self.ab_index += 1;
                 self.cur_sent_port = next_port;
-                next_port
+                Ok(next_port)
             } else {
                 // 如果 绝对概率表 和 相对概率表 均不可用
 
-                let next_port = self.get_tar_port_out_of_graph(graph);
+                let next_port = self.get_tar_port_out_of_graph(graph)?;
                 self.cur_sent_port = next_port;
-                next_port
+                Ok(next_port)
             }
         } else {
             // 如果 该地址 没有状态
@@ -102,16 +100,16 @@ impl IpStruct {
             if self.ab_index >= graph.port_cnt {
                 // 如果 该地址的绝对概率表指针 已经超出范围
 
-                let next_port = self.get_tar_port_out_of_graph(graph);
+                let next_port = self.get_tar_port_out_of_graph(graph)?;
                 self.cur_sent_port = next_port;
-                return next_port
+                return Ok(next_port)
             }
 
             // 从 绝对概率表 中取得 下一个待推荐端口, 并直接返回
             let next_port = graph.recommend_ports[self.ab_index];
             self.ab_index += 1;
             self.cur_sent_port = next_port;
-            next_port
+            Ok(next_port)
         }
     }
 
@@ -119,7 +117,7 @@ impl IpStruct {
 
     /// 当 概率相关图 不可用于 端口推荐 时, 使用 预设端口向量, 或逐个遍历所有端口号的方式得到 待推荐端口
     #[inline]
-    fn get_tar_port_out_of_graph(&mut self, graph:&Arc<Graph>) -> u16 {
+    fn get_tar_port_out_of_graph(&mut self, graph:&Arc<Graph>) -> Result<u16, TargetError> {
 
         while self.tar_ports_index < graph.tar_ports_len {
             // 如果 目标端口向量索引 在 目标端口向量 有效范围
@@ -129,7 +127,7 @@ impl IpStruct {
             if self.port_is_avail(&next_port) {
                 // 如果 当前端口 可用
                 self.tar_ports_index += 1;
-                return next_port
+                return Ok(next_port)
             }
 
             // 如果 当前端口不可用, 目标端口向量索引加一, 查找下一个可用端口
@@ -138,8 +136,7 @@ impl IpStruct {
 
         // 如果遍历过了所有端口, 仍需要推荐端口
         // 直接报错
-        error!("{}", SYS.get_info("err", "tar_ports_index_err"));
-        exit(1)
+        Err(TargetError::invalid("tar_ports_index_err"))
     }
 
 }
\ No newline at end of file
@@ -1,4 +1,3 @@
-use std::process::exit;
 use bitvec::bitvec;
 use bitvec::macros::internal::funty::Fundamental;
 use bitvec::prelude::BitVec;
@@ -6,6 +5,7 @@ use log::error;
 use crate::modules::output_modules::OutputMethod;
 use crate::modules::probe_modules::topology_probe::topo_mod_v4::TopoMethodV4;
 use crate::SYS;
+use crate::core::errors::TargetError;
 use crate::tools::check_duplicates::bit_map::BitMapV4Pattern;
 
 pub struct  TopoStateChainV4 {
@@ -76,7 +76,7 @@ impl TopoStateChainV4 {
             None => {
                 // 获取目标出错
                 error!("{} {}", SYS.get_info("err", "bitmap_get_target_failed"), ip);
-                exit(1)
+                false
             }
         }
     }
@@ -86,15 +86,14 @@ impl TopoStateChainV4 {
 
 impl TopoStateChainV4 {
 
-    pub fn new(bits_num:u32, base_ip_val:u32, mask:u32, parts:Vec<(u32, u32)>, ip_move_len:Vec<(u32,u32,u32)>) -> Self {
+    pub fn new(bits_num:u32, base_ip_val:u32, mask:u32, parts:Vec<(u32, u32)>, ip_move_len:Vec<(u32,u32,u32)>) -> Result<Self, TargetError> {
 
         if (u64::MAX as u128) > (usize::MAX as u128) {
             // 不能安全地将 u64 转化为 usize
-            error!("{}", SYS.get_info("err", "bitmap_u64_to_usize_err"));
-            exit(1)
+            return Err(TargetError::invalid("bitmap_u64_to_usize_err"))
         }
 
-        Self  {
+        Ok(Self  {
             base_ip_val,
             mask,
             move_len: BitMapV4Pattern::get_move_len(parts),
@@ -103,7 +102,7 @@ impl TopoStateChainV4 {
             target_count: 0,
             bit_map: bitvec![0; 1usize << 32],
             ip_move_len,
-        }
+        })
     }
 
     /// 将接收到的ip地址转化为对应的ip特征值
@@ -1,5 +1,6 @@
 use rand::rngs::StdRng;
 use crate::modules::target_iterators::cycle_group::Cyclic;
+use crate::core::errors::TargetError;
 
 pub struct  TopoIterV4 {
 
@@ -35,17 +36,17 @@ pub struct  TopoIterV4 {
 
 
 impl TopoIterV4 {
-    pub fn new(start_index:usize, state_chain:Vec<u8>, base_ip_val:u32, ip_move_len:Vec<(u32, u32, u32)>, rng: &mut StdRng) -> Self {
+    pub fn new(start_index:usize, state_chain:Vec<u8>, base_ip_val:u32, ip_move_len:Vec<(u32, u32, u32)>, rng: &mut StdRng) -> Result<Self, TargetError> {
         
         let tar_ip_num = state_chain.len() as u64;
         
         // 获得 乘法循环群
-        let cycle = Cyclic::new(tar_ip_num, rng, u64::MAX as u128);
+        let cycle = Cyclic::new(tar_ip_num, rng, u64::MAX as u128)?;
 
         let mut ori_iter = Self {
-            p: Cyclic::get_val_with_check_u64(cycle.p),
-            prim_root: Cyclic::get_val_with_check_u64(cycle.prim_root),
-            p_sub_one: Cyclic::get_val_with_check_u64(cycle.p_sub_one),
+            p: Cyclic::get_val_with_check_u64(cycle.p)?,
+            prim_root: Cyclic::get_val_with_check_u64(cycle.prim_root)?,
+            p_sub_one: Cyclic::get_val_with_check_u64(cycle.p_sub_one)?,
 
             // 这里只做初始化, 没有实际意义
             current: 0,
@@ -58,11 +59,11 @@ impl TopoIterV4 {
             start_index:start_index as u32,
         };
 
-        ori_iter.init();
-        ori_iter
+        ori_iter.init()?;
+        Ok(ori_iter)
     }
 
-    pub fn init(&mut self){
+    pub fn init(&mut self) -> Result<(), TargetError> {
 
         // 转换成对应大数
         let big_p = Cyclic::parse_u64_to_big_num(self.p);
@@ -74,7 +75,8 @@ impl TopoIterV4 {
         let big_first = big_prim_root.modpow(&big_start, &big_p);
         let big_last  = big_prim_root.modpow(&big_end, &big_p);
 
-        self.current = Cyclic::parse_big_num_to_u64(big_first);
-        self.last =  Cyclic::parse_big_num_to_u64(big_last);
+        self.current = Cyclic::parse_big_num_to_u64(big_first)?;
+        self.last =  Cyclic::parse_big_num_to_u64(big_last)?;
+        Ok(())
     }
 }
@@ -1,10 +1,8 @@
 use std::cmp::max;
-use std::process::exit;
 use ahash::AHashSet;
-use log::error;
 use crate::modules::output_modules::OutputMethod;
 use crate::modules::probe_modules::topology_probe::topo_mod_v6::TopoMethodV6;
-use crate::SYS;
+use crate::core::errors::TargetError;
 use crate::tools::check_duplicates::bit_map::BitMapV6Pattern;
 
 pub struct  TopoStateChainV6 {
@@ -69,15 +67,14 @@ impl TopoStateChainV6 {
 
 impl TopoStateChainV6 {
 
-    pub fn new(bits_num:u32, base_ip_val:u128, mask:u128, parts:Vec<(u32, u32)>, ip_move_len:Vec<(u32,u32,u32)>) -> Self {
+    pub fn new(bits_num:u32, base_ip_val:u128, mask:u128, parts:Vec<(u32, u32)>, ip_move_len:Vec<(u32,u32,u32)>) -> Result<Self, TargetError> {
 
         if (u64::MAX as u128) > (usize::MAX as u128) {
             // 不能安全地将 u64 转化为 usize
-            error!("{}", SYS.get_info("err", "bitmap_u64_to_usize_err"));
-            exit(1)
+            return Err(TargetError::invalid("bitmap_u64_to_usize_err"))
         }
 
-        Self  {
+        Ok(Self  {
             base_ip_val,
             mask,
             move_len: BitMapV6Pattern::get_move_len(parts),
@@ -86,7 +83,7 @@ impl TopoStateChainV6 {
             target_count: 0,
             hash_set: AHashSet::with_capacity(1usize << (max(bits_num, 10) - 2)),
             ip_move_len,
-        }
+        })
     }
 
     /// 将接收到的ip地址转化为对应的ip特征值
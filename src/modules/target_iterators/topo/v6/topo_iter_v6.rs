@@ -1,5 +1,6 @@
 use rand::rngs::StdRng;
 use crate::modules::target_iterators::cycle_group::Cyclic;
+use crate::core::errors::TargetError;
 
 pub struct  TopoIterV6 {
 
@@ -35,12 +36,12 @@ pub struct  TopoIterV6 {
 
 
 impl TopoIterV6 {
-    pub fn new(start_index:usize, state_chain:Vec<u8>, base_ip_val:u128, ip_move_len:Vec<(u32, u32, u32)>, rng: &mut StdRng) -> Self {
+    pub fn new(start_index:usize, state_chain:Vec<u8>, base_ip_val:u128, ip_move_len:Vec<(u32, u32, u32)>, rng: &mut StdRng) -> Result<Self, TargetError> {
 
         let tar_ip_num = state_chain.len() as u64;
 
         // 获得 乘法循环群
-        let cycle = Cyclic::new(tar_ip_num, rng, u128::MAX);
+        let cycle = Cyclic::new(tar_ip_num, rng, u128::MAX)?;
 
         let mut ori_iter = Self {
             p: cycle.p,
@@ -58,11 +59,11 @@ impl TopoIterV6 {
             start_index,
         };
 
-        ori_iter.init();
-        ori_iter
+        ori_iter.init()?;
+        Ok(ori_iter)
     }
 
-    pub fn init(&mut self){
+    pub fn init(&mut self) -> Result<(), TargetError> {
 
         // 转换成对应大数
         let big_p = Cyclic::parse_u128_to_big_num(self.p);
@@ -74,7 +75,8 @@ impl TopoIterV6 {
         let big_first = big_prim_root.modpow(&big_start, &big_p);
         let big_last  = big_prim_root.modpow(&big_end, &big_p);
 
-        self.current = Cyclic::parse_big_num_to_u128(big_first);
-        self.last =  Cyclic::parse_big_num_to_u128(big_last);
+        self.current = Cyclic::parse_big_num_to_u128(big_first)?;
+        self.last =  Cyclic::parse_big_num_to_u128(big_last)?;
+        Ok(())
     }
 }
@@ -1,6 +1,7 @@
 use crate::tools::blocker::ipv4_blocker::ipv4_blocker::Ipv4Blocker;
 use crate::SYS;
 use crate::tools::file::get_path::get_current_path;
+use crate::core::errors::{ConfError, TargetError};
 
 pub mod ipv4_blocker;
 
@@ -27,18 +28,18 @@ impl BlackWhiteListV4 {
 
     /// 创建 ipv4黑白名单拦截器
     pub fn new(black_list_file_arg:&Option<String>,
-                white_list_file_arg:&Option<String>, is_source:bool) -> Self {
+                white_list_file_arg:&Option<String>, is_source:bool) -> Result<Self, TargetError> {
 
         let (black_list_file, white_list_file) = if is_source {
-            get_default_source_black_white_list(black_list_file_arg, white_list_file_arg)
+            get_default_source_black_white_list(black_list_file_arg, white_list_file_arg)?
         } else {
-            get_default_destination_black_white_list(black_list_file_arg, white_list_file_arg)
+            get_default_destination_black_white_list(black_list_file_arg, white_list_file_arg)?
         };
 
-        Self {
-            black_list: Ipv4Blocker::new(black_list_file),
-            white_list: Ipv4Blocker::new(white_list_file),
-        }
+        Ok(Self {
+            black_list: Ipv4Blocker::new(black_list_file)?,
+            white_list: Ipv4Blocker::new(white_list_file)?,
+        })
 
     }
 
@@ -112,7 +113,7 @@ impl BlackWhiteListV4 {
 
 
 fn get_default_destination_black_white_list(black_list_file_arg:&Option<String>,
-                                            white_list_file_arg:&Option<String>) -> (String, String) {
+                                            white_list_file_arg:&Option<String>) -> Result<(String, String), ConfError> {
 
     let black_list_file;
     let white_list_file;
@@ -120,21 +121,21 @@ fn get_default_destination_black_white_list(black_list_file_arg:&Option<String>,
     if let Some(black_path) = black_list_file_arg {
         black_list_file = black_path.to_string();
     } else {
-        black_list_file = get_current_path(&SYS.get_info("conf", "destination_black_list_v4"));
+        black_list_file = get_current_path(&SYS.get_info("conf", "destination_black_list_v4"))?;
     }
 
     if let Some(white_path) = white_list_file_arg {
         white_list_file = white_path.to_string();
     } else {
-        white_list_file = get_current_path(&SYS.get_info("conf", "destination_white_list_v4"));
+        white_list_file = get_current_path(&SYS.get_info("conf", "destination_white_list_v4"))?;
     }
 
-    (black_list_file, white_list_file)
+    Ok((black_list_file, white_list_file))
 }
 
 
 fn get_default_source_black_white_list(black_list_file_arg:&Option<String>,
-                                            white_list_file_arg:&Option<String>) -> (String, String) {
+                                            white_list_file_arg:&Option<String>) -> Result<(String, String), ConfError> {
 
     let black_list_file;
     let white_list_file;
@@ -142,15 +143,15 @@ fn get_default_source_black_white_list(black_list_file_arg:&Option<String>,
     if let Some(black_path) = black_list_file_arg {
         black_list_file = black_path.to_string();
     } else {
-        black_list_file = get_current_path(&SYS.get_info("conf", "source_black_list_v4"));
+        black_list_file = get_current_path(&SYS.get_info("conf", "source_black_list_v4"))?;
     }
 
     if let Some(white_path) = white_list_file_arg {
         white_list_file = white_path.to_string();
     } else {
-        white_list_file = get_current_path(&SYS.get_info("conf", "source_white_list_v4"));
+        white_list_file = get_current_path(&SYS.get_info("conf", "source_white_list_v4"))?;
     }
 
-    (black_list_file, white_list_file)
+    Ok((black_list_file, white_list_file))
 }
 
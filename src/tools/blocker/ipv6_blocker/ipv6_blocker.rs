@@ -1,14 +1,12 @@
 use std::fs::File;
 use std::io::{BufRead, BufReader};
 use std::net::{Ipv6Addr};
-use std::process::exit;
 use std::str::FromStr;
 use ahash::AHashSet;
-use log::error;
-use crate::SYS;
 use crate::DNS;
 use crate::tools::file::parse_context::parse_line_with_annotation;
 use crate::tools::others::sort::quick_sort_from_big_to_small;
+use crate::core::errors::TargetError;
 
 /// ipv6 黑名单 或 白名单 生成器
 pub struct Ipv6Blocker {
@@ -44,12 +42,12 @@ impl Clone for Ipv6Blocker {
 impl Ipv6Blocker {
 
     /// 黑名单 or 白名单 拦截器初始化
-    pub fn new(path: String) -> Self {
+    pub fn new(path: String) -> Result<Self, TargetError> {
 
         let mut blocker = Ipv6Blocker::void();
 
         // 从文件中加载
-        blocker.from(path);
+        blocker.from(path)?;
 
         // 按照网络前缀从小到大排序, 优化查找次序
         // 如 前缀 /24 /8 /16 的次序调整为 /8 /16 /24
@@ -58,7 +56,7 @@ impl Ipv6Blocker {
         // 前缀聚合必须在排序之后
         blocker.aggregation_prefix();
 
-        blocker
+        Ok(blocker)
     }
 
     /// 生成 空拦截器, 默认禁用全域标记
@@ -98,14 +96,9 @@ impl Ipv6Blocker {
     }
 
     /// 从文件路径生成 拦截器
-    fn from(&mut self, list_path:String) {
+    fn from(&mut self, list_path:String) -> Result<(), TargetError> {
 
-        let list_file = File::open(list_path).map_err(
-            |_| {
-                error!("{}", SYS.get_info("err", "open_black_white_list_file_err"));
-                exit(1)
-            }
-        ).unwrap();
+        let list_file = File::open(list_path).map_err(|_| TargetError::io("open_black_white_list_file_err"))?;
 
         let lines = BufReader::new(list_file).lines();
 
@@ -123,10 +116,10 @@ impl Ipv6Blocker {
 
                             // 如果出现全域标记, 添加后续网段是 完全不需要 的,而且会导致匹配开销
                             if self.mark_all_flag {
-                                return
+                                return Ok(())
                             }
 
-                            self.parse_line_str(net_str)
+                            self.parse_line_str(net_str)?
                         },
 
                         None => {}
@@ -134,16 +127,17 @@ impl Ipv6Blocker {
 
                 }
                 Err(_) => {
-                    error!("{}", SYS.get_info("err", "read_black_white_list_file_err"));
-                    exit(1)
+                    return Err(TargetError::io("read_black_white_list_file_err"))
                 }
             }
         }
+
+        Ok(())
     }
 
 
     /// 从 行字符串文本 解析, 一次只能添加一个 网段 or 域名(多个地址) or ip地址
-    fn parse_line_str(&mut self, raw_net_str:String){
+    fn parse_line_str(&mut self, raw_net_str:String) -> Result<(), TargetError> {
 
         let mut net_mask = raw_net_str.split("/");
 
@@ -152,8 +146,7 @@ impl Ipv6Blocker {
         if let Some(n) = net_mask.next() {
             net_str = n;
         } else {
-            error!("{} {}", SYS.get_info("err","parse_line_err"), raw_net_str);
-            exit(1)
+            return Err(TargetError::invalid("parse_line_err").with(&raw_net_str))
         }
 
         if let Some(m) = net_mask.next() {
@@ -164,8 +157,8 @@ impl Ipv6Blocker {
             match Ipv6Addr::from_str(net_str) {
 
                 Ok(ip) => { // 成功解析为 ipv6地址
-                    self.add_net_from_ip_mask(ip, 128);
-                    return
+                    self.add_net_from_ip_mask(ip, 128)?;
+                    return Ok(())
                 }
                 Err(_) => {
                     // 尝试当作 域名 来解析
@@ -175,15 +168,14 @@ impl Ipv6Blocker {
 
                             for ip in ips {
                                 // 对解析出的所有地址进行标记
-                                self.add_net_from_ip_mask(ip, 128);
+                                self.add_net_from_ip_mask(ip, 128)?;
                             }
 
-                            return
+                            return Ok(())
                         }
                         Err(_) => {
                             // 如果既不是 ip 地址, 也不是域名, 就直接报错
-                            error!("{} {}", SYS.get_info("err","parse_line_err"), raw_net_str);
-                            exit(1);
+                            return Err(TargetError::invalid("parse_line_err").with(&raw_net_str));
                         }
                     }
                 }
@@ -191,31 +183,25 @@ impl Ipv6Blocker {
         }
 
         if let Some(_) = net_mask.next() {      // 检查合法性
-            error!("{} {}", SYS.get_info("err","parse_line_err"), raw_net_str);
-            exit(1)
+            return Err(TargetError::invalid("parse_line_err").with(&raw_net_str))
         }
 
-        let ip:Ipv6Addr = net_str.parse().map_err(|_|{
-            error!("{} {}", SYS.get_info("err","parse_line_err"), raw_net_str);
-            exit(1)
-        }).unwrap();
-        let mask:u32 = mask_str.parse().map_err(|_|{
-            error!("{} {}", SYS.get_info("err","parse_line_err"), raw_net_str);
-            exit(1)
-        }).unwrap();
+        let ip:Ipv6Addr = net_str.parse().map_err(|_| TargetError::invalid("parse_line_err").with(&raw_net_str))?;
+        let mask:u32 = mask_str.parse().map_err(|_| TargetError::invalid("parse_line_err").with(&raw_net_str))?;
 
         // 添加网段
-        self.add_net_from_ip_mask(ip, mask);
+        self.add_net_from_ip_mask(ip, mask)?;
+
+        Ok(())
     }
 
     /// 使用 ip 和 mask, 添加网络
-    fn add_net_from_ip_mask(&mut self,ip:Ipv6Addr, mask:u32){
+    fn add_net_from_ip_mask(&mut self,ip:Ipv6Addr, mask:u32) -> Result<(), TargetError> {
 
         if mask > 128 {
 
             // mask 超过 128 位为非法 mask
-            error!("{} {}/{}", SYS.get_info("err","parse_line_err"), ip, mask);
-            exit(1)
+            return Err(TargetError::invalid("parse_line_err").with(format!("{}/{}", ip, mask)))
         }
 
         if mask == 0 {
@@ -223,13 +209,12 @@ impl Ipv6Blocker {
             if ip != Ipv6Addr::new(0,0,0,0,0,0,0,0) {
 
                 // 非法全域标记
-                error!("{} {}/{}", SYS.get_info("err","illegal_all_flag"), ip, mask);
-                exit(1);
+                return Err(TargetError::invalid("illegal_all_flag").with(format!("{}/{}", ip, mask)))
             }
 
             // 清空各向量, 并添加 全域标记
             self.clear(true);
-            return
+            return Ok(())
         }
 
 
@@ -267,6 +252,7 @@ impl Ipv6Blocker {
             self.len += 1;
         }
 
+        Ok(())
     }
 
     /// 按照 网段大小 进行排序, 使得数据包先比较 大的网段, 再比较 小的网段
@@ -1,6 +1,7 @@
 use crate::tools::blocker::ipv6_blocker::ipv6_blocker::Ipv6Blocker;
 use crate::SYS;
 use crate::tools::file::get_path::get_current_path;
+use crate::core::errors::{ConfError, TargetError};
 
 pub mod ipv6_blocker;
 
@@ -25,19 +26,19 @@ impl BlackWhiteListV6 {
 
     /// 创建 ipv6黑白名单
     pub fn new(black_list_file_arg:&Option<String>,
-               white_list_file_arg:&Option<String>, is_source:bool) -> Self {
+               white_list_file_arg:&Option<String>, is_source:bool) -> Result<Self, TargetError> {
 
         let (black_list_file, white_list_file) = if is_source {
-            get_default_source_black_white_list(black_list_file_arg, white_list_file_arg)
+            get_default_source_black_white_list(black_list_file_arg, white_list_file_arg)?
         } else {
-            get_default_destination_black_white_list(black_list_file_arg, white_list_file_arg)
+            get_default_destination_black_white_list(black_list_file_arg, white_list_file_arg)?
         };
 
 
-        Self {
-            black_list: Ipv6Blocker::new(black_list_file),
-            white_list: Ipv6Blocker::new(white_list_file),
-        }
+        Ok(Self {
+            black_list: Ipv6Blocker::new(black_list_file)?,
+            white_list: Ipv6Blocker::new(white_list_file)?,
+        })
 
     }
 
@@ -87,7 +88,7 @@ impl BlackWhiteListV6 {
 
 
 fn get_default_destination_black_white_list(black_list_file_arg:&Option<String>,
-                                            white_list_file_arg:&Option<String>) -> (String, String) {
+                                            white_list_file_arg:&Option<String>) -> Result<(String, String), ConfError> {
 
     let black_list_file;
     let white_list_file;
@@ -95,21 +96,21 @@ fn get_default_destination_black_white_list(black_list_file_arg:&Option<String>,
     if let Some(black_path) = black_list_file_arg {
         black_list_file = black_path.to_string();
     } else {
-        black_list_file = get_current_path(&SYS.get_info("conf", "destination_black_list_v6"));
+        black_list_file = get_current_path(&SYS.get_info("conf", "destination_black_list_v6"))?;
     }
 
     if let Some(white_path) = white_list_file_arg {
         white_list_file = white_path.to_string();
     } else {
-        white_list_file = get_current_path(&SYS.get_info("conf", "destination_white_list_v6"));
+        white_list_file = get_current_path(&SYS.get_info("conf", "destination_white_list_v6"))?;
     }
 
-    (black_list_file, white_list_file)
+    Ok((black_list_file, white_list_file))
 }
 
 
 fn get_default_source_black_white_list(black_list_file_arg:&Option<String>,
-                                       white_list_file_arg:&Option<String>) -> (String, String) {
+                                       white_list_file_arg:&Option<String>) -> Result<(String, String), ConfError> {
 
     let black_list_file;
     let white_list_file;
@@ -117,15 +118,15 @@ fn get_default_source_black_white_list(black_list_file_arg:&Option<String>,
     if let Some(black_path) = black_list_file_arg {
         black_list_file = black_path.to_string();
     } else {
-        black_list_file = get_current_path(&SYS.get_info("conf", "source_black_list_v6"));
+        black_list_file = get_current_path(&SYS.get_info("conf", "source_black_list_v6"))?;
     }
 
     if let Some(white_path) = white_list_file_arg {
         white_list_file = white_path.to_string();
     } else {
-        white_list_file = get_current_path(&SYS.get_info("conf", "source_white_list_v6"));
+        white_list_file = get_current_path(&SYS.get_info("conf", "source_white_list_v6"))?;
     }
 
-    (black_list_file, white_list_file)
+    Ok((black_list_file, white_list_file))
 }
 
@@ -1,10 +1,12 @@
-use std::process::exit;
 use bitvec::bitvec;
 use bitvec::macros::internal::funty::Fundamental;
 use bitvec::vec::BitVec;
 use log::error;
 use crate::SYS;
-use crate::tools::check_duplicates::{DuplicateCheckerV4, NotMarkedV4};
+use crate::core::errors::TargetError;
+use crate::tools::check_duplicates::{DuplicateCheckerV4, NotMarkedV4, RecorderState};
+use crate::tools::check_duplicates::bit_map::{dump_bits, restore_bits};
+use crate::tools::check_duplicates::bit_map::shared::{AtomicBits, SharedBitMapV4};
 
 pub struct BitMapV4 {
     start_ip:u32,
@@ -46,7 +48,7 @@ impl DuplicateCheckerV4 for BitMapV4 {
             None => {
                 // 获取目标出错
                 error!("{} {}", SYS.get_info("err", "bitmap_get_target_failed"), ip_index);
-                exit(1)
+                false
             }
         }
     }
@@ -54,19 +56,23 @@ impl DuplicateCheckerV4 for BitMapV4 {
 
 
 impl BitMapV4 {
-    pub fn new(start_ip: u32, end_ip: u32, tar_ip_num: u64) -> Self {
+    pub fn new(start_ip: u32, end_ip: u32, tar_ip_num: u64) -> Result<Self, TargetError> {
         if (u64::MAX as u128) > (usize::MAX as u128) {
             // 不能安全地将 u64 转化为 usize
-            error!("{}", SYS.get_info("err", "bitmap_u64_to_usize_err"));
-            exit(1)
+            return Err(TargetError::invalid("bitmap_u64_to_usize_err"))
         }
 
 
-        Self {
+        Ok(Self {
             start_ip,
             end_ip,
             map: bitvec![0; tar_ip_num as usize],
-        }
+        })
+    }
+
+    /// 转为 多个接收线程 共用的位图, 保留已有的标记
+    pub fn into_shared(self) -> SharedBitMapV4 {
+        SharedBitMapV4::new(self.start_ip, self.end_ip, AtomicBits::from_bit_vec(self.map))
     }
 }
 
@@ -84,7 +90,7 @@ impl NotMarkedV4 for BitMapV4 {
             None => {
                 // 获取目标出错
                 error!("{} {}", SYS.get_info("err", "bitmap_get_target_failed"), ip_index);
-                exit(1)
+                false
             }
         }
     }
@@ -92,5 +98,12 @@ impl NotMarkedV4 for BitMapV4 {
 }
 
 
+impl RecorderState for BitMapV4 {
+    fn dump(&self) -> Vec<u8> {
+        dump_bits(&self.map)
+    }
 
-
+    fn restore(&mut self, data:&[u8]) -> bool {
+        restore_bits(&mut self.map, data)
+    }
+}
@@ -1,9 +1,9 @@
-use std::process::exit;
 use bitvec::bitvec;
 use bitvec::macros::internal::funty::Fundamental;
 use bitvec::vec::BitVec;
 use log::error;
 use crate::SYS;
+use crate::core::errors::TargetError;
 use crate::tools::check_duplicates::DuplicateCheckerV4;
 
 pub struct BitMapV4Pattern {
@@ -53,7 +53,7 @@ impl DuplicateCheckerV4 for BitMapV4Pattern {
             None => {
                 // 获取目标出错
                 error!("{} {}", SYS.get_info("err", "bitmap_get_target_failed"), ip_index);
-                exit(1)
+                false
             }
         }
     }
@@ -63,23 +63,22 @@ impl DuplicateCheckerV4 for BitMapV4Pattern {
 impl BitMapV4Pattern {
 
     #[allow(dead_code)]
-    pub fn new(bits_num:u32, base_ip_val:u32, mask:u32, parts:Vec<(u32, u32)>) -> Self {
+    pub fn new(bits_num:u32, base_ip_val:u32, mask:u32, parts:Vec<(u32, u32)>) -> Result<Self, TargetError> {
 
         if (u64::MAX as u128) > (usize::MAX as u128) {
 
             // 不能安全地将 u64 转化为 usize
-            error!("{}", SYS.get_info("err", "bitmap_u64_to_usize_err"));
-            exit(1)
+            return Err(TargetError::invalid("bitmap_u64_to_usize_err"))
         }
 
         let capacity= 1usize << bits_num;
 
-        Self  {
+        Ok(Self  {
             map: bitvec![0; capacity],
             base_ip_val,
             mask,
             move_len: Self::get_move_len(parts),
-        }
+        })
     }
 
     /// 将接收到的ip地址转化为对应的ip特征值
@@ -1,13 +1,14 @@
 
 
-use std::process::exit;
 use bitvec::bitvec;
 use bitvec::macros::internal::funty::Fundamental;
 use bitvec::vec::BitVec;
 use log::error;
 use crate::core::conf::tools::args_parse::target_iterator::TarIterBaseConf;
 use crate::SYS;
-use crate::tools::check_duplicates::{DuplicateCheckerV4Port, ExtractActPortsV4, NotMarkedV4Port};
+use crate::core::errors::TargetError;
+use crate::tools::check_duplicates::{DuplicateCheckerV4Port, ExtractActPortsV4, NotMarkedV4Port, RecorderState};
+use crate::tools::check_duplicates::bit_map::{dump_bits, restore_bits};
 
 pub struct BitMapV4Port {
     start_ip:u32,
@@ -61,7 +62,7 @@ impl DuplicateCheckerV4Port for BitMapV4Port {
             None => {
                 // 获取目标出错
                 error!("{} {}", SYS.get_info("err", "bitmap_get_target_failed"), bit_map_index);
-                exit(1)
+                false
             }
         }
     }
@@ -90,7 +91,7 @@ impl NotMarkedV4Port for BitMapV4Port {
             None => {
                 // 获取目标出错
                 error!("{} {}", SYS.get_info("err", "bitmap_get_target_failed"), bit_map_index);
-                exit(1)
+                false
             }
         }
     }
@@ -99,10 +100,10 @@ impl NotMarkedV4Port for BitMapV4Port {
 
 impl BitMapV4Port {
 
-    pub fn new(start_ip:u32, end_ip:u32, tar_ip_num:u64, mut tar_ports:Vec<u16>) -> Self {
+    pub fn new(start_ip:u32, end_ip:u32, tar_ip_num:u64, mut tar_ports:Vec<u16>) -> Result<Self, TargetError> {
 
         // 不能安全地将 u64 转化为 usize
-        if (u64::MAX as u128) > (usize::MAX as u128) { error!("{}", SYS.get_info("err", "bitmap_u64_to_usize_err")); exit(1) }
+        if (u64::MAX as u128) > (usize::MAX as u128) { return Err(TargetError::invalid("bitmap_u64_to_usize_err")) }
         
         // 在检查重复时, 必须添加0端口，以应对特殊情况
         if !tar_ports.contains(&0) {
@@ -140,7 +141,7 @@ impl BitMapV4Port {
             index += 1;
         }
 
-        Self  {
+        Ok(Self  {
             start_ip,
             end_ip,
 
@@ -148,7 +149,7 @@ impl BitMapV4Port {
             map: bitvec![0; total_num],
             tar_ports_index,
             sorted_tar_ports: tar_ports,
-        }
+        })
     }
 
 }
@@ -181,7 +182,7 @@ impl ExtractActPortsV4 for BitMapV4Port {
                         active_ports_str.push_str(&format!("{}|", cur_port));
                     }
                 }
-                None => { error!("{} {}", SYS.get_info("err", "bitmap_get_target_failed"), bit_map_index);exit(1) }
+                None => { error!("{} {}", SYS.get_info("err", "bitmap_get_target_failed"), bit_map_index); break }
             };
         }
         if active_ports.len() != 0 {
@@ -217,7 +218,7 @@ impl ExtractActPortsV4 for BitMapV4Port {
                         ports_count += 1;
                     }
                 }
-                None => { error!("{} {}", SYS.get_info("err", "bitmap_get_target_failed"), bit_map_index);exit(1) }
+                None => { error!("{} {}", SYS.get_info("err", "bitmap_get_target_failed"), bit_map_index); break }
             };
         }
         // 删除字符串中最后一个字符
@@ -226,4 +227,15 @@ impl ExtractActPortsV4 for BitMapV4Port {
         }
         (active_ports, ports_count)
     }
-}
\ No newline at end of file
+}
+
+
+impl RecorderState for BitMapV4Port {
+    fn dump(&self) -> Vec<u8> {
+        dump_bits(&self.map)
+    }
+
+    fn restore(&mut self, data:&[u8]) -> bool {
+        restore_bits(&mut self.map, data)
+    }
+}
@@ -1,10 +1,12 @@
-use std::process::exit;
 use bitvec::bitvec;
 use bitvec::macros::internal::funty::Fundamental;
 use bitvec::vec::BitVec;
 use log::error;
 use crate::SYS;
-use crate::tools::check_duplicates::DuplicateCheckerV6;
+use crate::core::errors::TargetError;
+use crate::tools::check_duplicates::{DuplicateCheckerV6, RecorderState};
+use crate::tools::check_duplicates::bit_map::{dump_bits, restore_bits};
+use crate::tools::check_duplicates::bit_map::shared::{AtomicBits, SharedBitMapV6};
 
 pub struct BitMapV6 {
     start_ip:u128,
@@ -47,7 +49,7 @@ impl DuplicateCheckerV6 for BitMapV6 {
             None => {
                 // 获取目标出错
                 error!("{} {}", SYS.get_info("err", "bitmap_get_target_failed"), ip_index);
-                exit(1)
+                false
             }
         }
     }
@@ -56,21 +58,36 @@ impl DuplicateCheckerV6 for BitMapV6 {
 
 impl BitMapV6 {
 
-    pub fn new(start_ip:u128, end_ip:u128, tar_ip_num:u64) -> Self {
+    pub fn new(start_ip:u128, end_ip:u128, tar_ip_num:u64) -> Result<Self, TargetError> {
 
         // 注意ipv6连续目标范围必须在 2^64 - 1 以内,位图的最大大小为 2^64 - 1
         if (u64::MAX as u128) > (usize::MAX as u128) {
 
             // 不能安全地将 u64 转化为 usize
-            error!("{}", SYS.get_info("err", "bitmap_u64_to_usize_err"));
-            exit(1)
+            return Err(TargetError::invalid("bitmap_u64_to_usize_err"))
         }
 
 
-        Self  {
+        Ok(Self  {
             start_ip,
             end_ip,
             map: bitvec![0; tar_ip_num as usize],
-        }
+        })
+    }
+
+    /// 转为 多个接收线程 共用的位图, 保留已有的标记
+    pub fn into_shared(self) -> SharedBitMapV6 {
+        SharedBitMapV6::new(self.start_ip, self.end_ip, AtomicBits::from_bit_vec(self.map))
+    }
+}
+
+
+impl RecorderState for BitMapV6 {
+    fn dump(&self) -> Vec<u8> {
+        dump_bits(&self.map)
     }
-}
\ No newline at end of file
+
+    fn restore(&mut self, data:&[u8]) -> bool {
+        restore_bits(&mut self.map, data)
+    }
+}
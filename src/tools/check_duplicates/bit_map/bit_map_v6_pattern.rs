@@ -1,10 +1,11 @@
-use std::process::exit;
 use bitvec::bitvec;
 use bitvec::macros::internal::funty::Fundamental;
 use bitvec::vec::BitVec;
 use log::error;
 use crate::SYS;
-use crate::tools::check_duplicates::{DuplicateCheckerV6, NotMarkedV6};
+use crate::core::errors::TargetError;
+use crate::tools::check_duplicates::{DuplicateCheckerV6, NotMarkedV6, RecorderState};
+use crate::tools::check_duplicates::bit_map::{dump_bits, restore_bits};
 
 pub struct BitMapV6Pattern {
     map:BitVec,
@@ -43,7 +44,6 @@ impl DuplicateCheckerV6 for BitMapV6Pattern {
         } else {
             // ip值超过64位, 报错
             error!("{} {}", SYS.get_info("err", "bitmap_get_target_failed"), ip_index);
-            exit(1)
         }
     }
 
@@ -68,7 +68,7 @@ impl DuplicateCheckerV6 for BitMapV6Pattern {
                 None => {
                     // 获取目标出错
                     error!("{} {}", SYS.get_info("err", "bitmap_get_target_failed"), ip_index);
-                    exit(1)
+                    false
                 }
             }
 
@@ -79,7 +79,7 @@ impl DuplicateCheckerV6 for BitMapV6Pattern {
         } else {
             // 得到的ip值大于64位, 说明出错了
             error!("{} {}", SYS.get_info("err", "bitmap_get_target_failed"), ip_index);
-            exit(1)
+            false
         }
     }
 }
@@ -87,13 +87,12 @@ impl DuplicateCheckerV6 for BitMapV6Pattern {
 
 impl BitMapV6Pattern {
 
-    pub fn new(bits_num:u32, base_ip_val:u128, mask:u128, parts:Vec<(u32, u32)>) -> Self {
+    pub fn new(bits_num:u32, base_ip_val:u128, mask:u128, parts:Vec<(u32, u32)>) -> Result<Self, TargetError> {
 
         if (u64::MAX as u128) > (usize::MAX as u128) {
 
             // 不能安全地将 u64 转化为 usize
-            error!("{}", SYS.get_info("err", "bitmap_u64_to_usize_err"));
-            exit(1)
+            return Err(TargetError::invalid("bitmap_u64_to_usize_err"))
         }
 
         let capacity;
@@ -103,18 +102,17 @@ impl BitMapV6Pattern {
             // 如果模式字符达到64位
             capacity = u64::MAX as usize;
         } else {
-            error!("{}", SYS.get_info("err", "pattern_char_over_64"));
-            exit(1)
+            return Err(TargetError::invalid("pattern_char_over_64"))
         }
 
-        Self  {
+        Ok(Self  {
             map: bitvec![0; capacity],
             last: false,
             base_ip_val,
             mask,
             move_len: Self::get_move_len(parts),
             max_val: u64::MAX as u128,
-        }
+        })
     }
 
 
@@ -198,7 +196,7 @@ impl NotMarkedV6 for BitMapV6Pattern {
                 None => {
                     // 获取目标出错
                     error!("{} {}", SYS.get_info("err", "bitmap_get_target_failed"), ip_index);
-                    exit(1)
+                    false
                 }
             }
 
@@ -209,7 +207,28 @@ impl NotMarkedV6 for BitMapV6Pattern {
         } else {
             // 得到的ip值大于64位, 说明出错了
             error!("{} {}", SYS.get_info("err", "bitmap_get_target_failed"), ip_index);
-            exit(1)
+            false
         }
     }
-}
\ No newline at end of file
+}
+
+
+impl RecorderState for BitMapV6Pattern {
+
+    // 首字节为 第 2^64 位 的标记
+    fn dump(&self) -> Vec<u8> {
+        let mut data = vec![self.last as u8];
+        data.extend(dump_bits(&self.map));
+        data
+    }
+
+    fn restore(&mut self, data:&[u8]) -> bool {
+        match data.split_first() {
+            Some((last, bits)) if restore_bits(&mut self.map, bits) => {
+                self.last = *last != 0;
+                true
+            }
+            _ => false,
+        }
+    }
+}
@@ -1,11 +1,12 @@
-use std::process::exit;
 use bitvec::bitvec;
 use bitvec::macros::internal::funty::Fundamental;
 use bitvec::vec::BitVec;
 use log::error;
 use crate::core::conf::tools::args_parse::target_iterator::TarIterBaseConf;
 use crate::SYS;
-use crate::tools::check_duplicates::{DuplicateCheckerV6Port, ExtractActPortsV6, NotMarkedV6Port};
+use crate::core::errors::TargetError;
+use crate::tools::check_duplicates::{DuplicateCheckerV6Port, ExtractActPortsV6, NotMarkedV6Port, RecorderState};
+use crate::tools::check_duplicates::bit_map::{dump_bits, restore_bits};
 
 pub struct BitMapV6PatternPort {
     map:BitVec,
@@ -67,7 +68,7 @@ impl DuplicateCheckerV6Port for BitMapV6PatternPort {
             None => {
                 // 获取目标出错
                 error!("{} {}", SYS.get_info("err", "bitmap_get_target_failed"), bit_map_index);
-                exit(1)
+                false
             }
         }
     }
@@ -98,7 +99,7 @@ impl NotMarkedV6Port for BitMapV6PatternPort {
             None => {
                 // 获取目标出错
                 error!("{} {}", SYS.get_info("err", "bitmap_get_target_failed"), bit_map_index);
-                exit(1)
+                false
             }
         }
     }
@@ -107,10 +108,10 @@ impl NotMarkedV6Port for BitMapV6PatternPort {
 
 impl BitMapV6PatternPort {
 
-    pub fn new(bits_for_ip:u32, base_ip_val:u128, mask:u128, parts:Vec<(u32, u32)>, mut tar_ports:Vec<u16>) -> Self {
+    pub fn new(bits_for_ip:u32, base_ip_val:u128, mask:u128, parts:Vec<(u32, u32)>, mut tar_ports:Vec<u16>) -> Result<Self, TargetError> {
 
         // 不能安全地将 u64 转化为 usize
-        if (u64::MAX as u128) > (usize::MAX as u128) { error!("{}", SYS.get_info("err", "bitmap_u64_to_usize_err")); exit(1) }
+        if (u64::MAX as u128) > (usize::MAX as u128) { return Err(TargetError::invalid("bitmap_u64_to_usize_err")) }
 
         // 在检查重复时, 必须添加0端口，以应对特殊情况
         if !tar_ports.contains(&0) {
@@ -125,7 +126,7 @@ impl BitMapV6PatternPort {
         let total_bits_num = bits_for_ip + bits_for_port;
 
         // 注意: 比特位数最高不得超过 63位
-        if total_bits_num > 63 { error!("{}", SYS.get_info("err", "total_bits_num_over_63"));  exit(1) }
+        if total_bits_num > 63 { return Err(TargetError::invalid("total_bits_num_over_63")) }
 
         // 位图的总位数, 如总比特位数为2, 则位图总位数为 b100
         let total_num = 1usize << total_bits_num;
@@ -146,7 +147,7 @@ impl BitMapV6PatternPort {
         }
 
 
-        Self  {
+        Ok(Self  {
             map: bitvec![0; total_num],
             base_ip_val,
             mask,
@@ -155,7 +156,7 @@ impl BitMapV6PatternPort {
             bits_for_port,
             tar_ports_index,
             sorted_tar_ports: tar_ports,
-        }
+        })
     }
 
     /// 将接收到的ip地址转化为对应的ip特征值
@@ -231,7 +232,7 @@ impl ExtractActPortsV6 for BitMapV6PatternPort {
                         active_ports_str.push_str(&format!("{}|", cur_port));
                     }
                 }
-                None => { error!("{} {}", SYS.get_info("err", "bitmap_get_target_failed"), bit_map_index);exit(1) }
+                None => { error!("{} {}", SYS.get_info("err", "bitmap_get_target_failed"), bit_map_index); break }
             };
         }
         if active_ports.len() != 0 {
@@ -267,7 +268,7 @@ impl ExtractActPortsV6 for BitMapV6PatternPort {
                         ports_count += 1;
                     }
                 }
-                None => { error!("{} {}", SYS.get_info("err", "bitmap_get_target_failed"), bit_map_index);exit(1) }
+                None => { error!("{} {}", SYS.get_info("err", "bitmap_get_target_failed"), bit_map_index); break }
             };
         }
         // 删除字符串中最后一个字符
@@ -276,4 +277,15 @@ impl ExtractActPortsV6 for BitMapV6PatternPort {
         }
         (active_ports, ports_count)
     }
-}
\ No newline at end of file
+}
+
+
+impl RecorderState for BitMapV6PatternPort {
+    fn dump(&self) -> Vec<u8> {
+        dump_bits(&self.map)
+    }
+
+    fn restore(&mut self, data:&[u8]) -> bool {
+        restore_bits(&mut self.map, data)
+    }
+}
@@ -1,11 +1,12 @@
-use std::process::exit;
 use bitvec::bitvec;
 use bitvec::macros::internal::funty::Fundamental;
 use bitvec::vec::BitVec;
 use log::error;
 use crate::core::conf::tools::args_parse::target_iterator::TarIterBaseConf;
 use crate::SYS;
-use crate::tools::check_duplicates::DuplicateCheckerV6Port;
+use crate::core::errors::TargetError;
+use crate::tools::check_duplicates::{DuplicateCheckerV6Port, RecorderState};
+use crate::tools::check_duplicates::bit_map::{dump_bits, restore_bits};
 
 pub struct BitMapV6Port {
     start_ip:u128,
@@ -59,7 +60,7 @@ impl DuplicateCheckerV6Port for BitMapV6Port {
             None => {
                 // 获取目标出错
                 error!("{} {}", SYS.get_info("err", "bitmap_get_target_failed"), bit_map_index);
-                exit(1)
+                false
             }
         }
     }
@@ -68,10 +69,10 @@ impl DuplicateCheckerV6Port for BitMapV6Port {
 
 impl BitMapV6Port {
 
-    pub fn new(start_ip:u128, end_ip:u128, tar_ip_num:u64, mut tar_ports:Vec<u16>) -> Self {
+    pub fn new(start_ip:u128, end_ip:u128, tar_ip_num:u64, mut tar_ports:Vec<u16>) -> Result<Self, TargetError> {
 
         // 不能安全地将 u64 转化为 usize
-        if (u64::MAX as u128) > (usize::MAX as u128) { error!("{}", SYS.get_info("err", "bitmap_u64_to_usize_err")); exit(1) }
+        if (u64::MAX as u128) > (usize::MAX as u128) { return Err(TargetError::invalid("bitmap_u64_to_usize_err")) }
 
         // 在检查重复时, 必须添加0端口，以应对特殊情况
         if !tar_ports.contains(&0) {
@@ -88,7 +89,7 @@ impl BitMapV6Port {
 
         // 注意: 比特位数最高不得超过 63位
         // ipv4地址所需的最大位数为 32位, 目标端口所需的最大位数为 16位, 所以在这里进行检查是不必要的
-        if total_bits_num > 63 { error!("{}", SYS.get_info("err", "total_bits_num_over_63"));  exit(1) }
+        if total_bits_num > 63 { return Err(TargetError::invalid("total_bits_num_over_63")) }
 
         // 位图的总位数, 如总比特位数为2, 则位图总位数为 b100
         let total_num = 1usize << total_bits_num;
@@ -108,14 +109,25 @@ impl BitMapV6Port {
             index += 1;
         }
 
-        Self  {
+        Ok(Self  {
             start_ip,
             end_ip,
 
             bits_for_port,
             map: bitvec![0; total_num],
             tar_ports_index,
-        }
+        })
+    }
+
+}
+
+
+impl RecorderState for BitMapV6Port {
+    fn dump(&self) -> Vec<u8> {
+        dump_bits(&self.map)
     }
 
-}
\ No newline at end of file
+    fn restore(&mut self, data:&[u8]) -> bool {
+        restore_bits(&mut self.map, data)
+    }
+}
@@ -1,3 +1,6 @@
+use std::mem::{size_of, size_of_val};
+use bitvec::vec::BitVec;
+
 mod bit_map_v4;
 mod bit_map_v6;
 mod bit_map_v6_pattern;
@@ -5,6 +8,7 @@ mod bit_map_v4_port;
 mod bit_map_v6_port;
 mod bit_map_v6_pattern_port;
 mod bit_map_v4_pattern;
+mod shared;
 
 
 pub use bit_map_v4::BitMapV4;
@@ -16,4 +20,30 @@ pub use bit_map_v6_port::BitMapV6Port;
 pub use bit_map_v6_pattern::BitMapV6Pattern;
 pub use bit_map_v6_pattern_port::BitMapV6PatternPort;
 
-pub use bit_map_v4_pattern::BitMapV4Pattern;
\ No newline at end of file
+pub use bit_map_v4_pattern::BitMapV4Pattern;
+
+pub use shared::{SharedBitMapV4, SharedBitMapV6};
+
+
+/// 位图 转为 字节序列(按机器字 小端排列), 用于保存断点
+fn dump_bits(map:&BitVec) -> Vec<u8> {
+    let raw = map.as_raw_slice();
+    let mut data = Vec::with_capacity(size_of_val(raw));
+    for word in raw {
+        data.extend_from_slice(&word.to_le_bytes());
+    }
+    data
+}
+
+/// 由 字节序列 恢复位图, 长度与当前位图不一致时 返回 false
+fn restore_bits(map:&mut BitVec, data:&[u8]) -> bool {
+    let raw = map.as_raw_mut_slice();
+    if data.len() != size_of_val(raw) { return false }
+
+    for (word, bytes) in raw.iter_mut().zip(data.chunks_exact(size_of::<usize>())) {
+        let mut buf = [0u8; size_of::<usize>()];
+        buf.copy_from_slice(bytes);
+        *word = usize::from_le_bytes(buf);
+    }
+    true
+}
@@ -0,0 +1,146 @@
+use std::mem::size_of;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use bitvec::vec::BitVec;
+use crate::tools::check_duplicates::{DuplicateCheckerV4, DuplicateCheckerV6, RecorderState};
+
+
+/// 原子位序列, 存储布局 与 BitVec 相同(按机器字, 低位在前), 可由多个接收线程 同时读写
+pub struct AtomicBits {
+    words:Vec<AtomicUsize>,
+}
+
+impl AtomicBits {
+
+    pub fn from_bit_vec(map:BitVec) -> Self {
+        Self { words: map.into_vec().into_iter().map(AtomicUsize::new).collect() }
+    }
+
+    /// 位索引 对应的 机器字下标 和 掩码
+    #[inline]
+    fn locate(index:usize) -> (usize, usize) {
+        (index / usize::BITS as usize, 1 << (index % usize::BITS as usize))
+    }
+
+    #[inline]
+    pub fn get(&self, index:usize) -> bool {
+        let (word, mask) = Self::locate(index);
+        self.words[word].load(Ordering::Relaxed) & mask != 0
+    }
+
+    /// 置位, 返回 该位此前是否未被置位
+    #[inline]
+    pub fn set(&self, index:usize) -> bool {
+        let (word, mask) = Self::locate(index);
+        self.words[word].fetch_or(mask, Ordering::Relaxed) & mask == 0
+    }
+
+    fn dump(&self) -> Vec<u8> {
+        let mut data = Vec::with_capacity(self.words.len() * size_of::<usize>());
+        for word in self.words.iter() {
+            data.extend_from_slice(&word.load(Ordering::Relaxed).to_le_bytes());
+        }
+        data
+    }
+
+    fn restore(&self, data:&[u8]) -> bool {
+        if data.len() != self.words.len() * size_of::<usize>() { return false }
+
+        for (word, bytes) in self.words.iter().zip(data.chunks_exact(size_of::<usize>())) {
+            let mut buf = [0u8; size_of::<usize>()];
+            buf.copy_from_slice(bytes);
+            word.store(usize::from_le_bytes(buf), Ordering::Relaxed);
+        }
+        true
+    }
+}
+
+
+/// 多个接收线程 共用的 ipv4 位图, 克隆后 指向同一位图
+#[derive(Clone)]
+pub struct SharedBitMapV4 {
+    start_ip:u32,
+    end_ip:u32,
+    map:Arc<AtomicBits>,
+}
+
+impl SharedBitMapV4 {
+    pub fn new(start_ip:u32, end_ip:u32, map:AtomicBits) -> Self {
+        Self { start_ip, end_ip, map: Arc::new(map) }
+    }
+}
+
+impl DuplicateCheckerV4 for SharedBitMapV4 {
+    #[inline]
+    fn set(&mut self, ip:u32) {
+        if ip < self.start_ip || ip > self.end_ip { return }
+        self.map.set((ip - self.start_ip) as usize);
+    }
+
+    #[inline]
+    fn not_marked_and_valid(&self, ip:u32) -> bool {
+        if ip < self.start_ip || ip > self.end_ip { return false }
+        !self.map.get((ip - self.start_ip) as usize)
+    }
+
+    #[inline]
+    fn mark(&mut self, ip:u32) -> bool {
+        if ip < self.start_ip || ip > self.end_ip { return false }
+        self.map.set((ip - self.start_ip) as usize)
+    }
+}
+
+impl RecorderState for SharedBitMapV4 {
+    fn dump(&self) -> Vec<u8> {
+        self.map.dump()
+    }
+
+    fn restore(&mut self, data:&[u8]) -> bool {
+        self.map.restore(data)
+    }
+}
+
+
+/// 多个接收线程 共用的 ipv6 位图, 克隆后 指向同一位图
+#[derive(Clone)]
+pub struct SharedBitMapV6 {
+    start_ip:u128,
+    end_ip:u128,
+    map:Arc<AtomicBits>,
+}
+
+impl SharedBitMapV6 {
+    pub fn new(start_ip:u128, end_ip:u128, map:AtomicBits) -> Self {
+        Self { start_ip, end_ip, map: Arc::new(map) }
+    }
+}
+
+impl DuplicateCheckerV6 for SharedBitMapV6 {
+    #[inline]
+    fn set(&mut self, ip:u128) {
+        if ip < self.start_ip || ip > self.end_ip { return }
+        self.map.set((ip - self.start_ip) as usize);
+    }
+
+    #[inline]
+    fn not_marked_and_valid(&self, ip:u128) -> bool {
+        if ip < self.start_ip || ip > self.end_ip { return false }
+        !self.map.get((ip - self.start_ip) as usize)
+    }
+
+    #[inline]
+    fn mark(&mut self, ip:u128) -> bool {
+        if ip < self.start_ip || ip > self.end_ip { return false }
+        self.map.set((ip - self.start_ip) as usize)
+    }
+}
+
+impl RecorderState for SharedBitMapV6 {
+    fn dump(&self) -> Vec<u8> {
+        self.map.dump()
+    }
+
+    fn restore(&mut self, data:&[u8]) -> bool {
+        self.map.restore(data)
+    }
+}
@@ -1,5 +1,5 @@
 use ahash::{AHashMap, AHashSet};
-use crate::tools::check_duplicates::{DuplicateCheckerV4Port, ExtractActPortsV4, NotMarkedV4Port};
+use crate::tools::check_duplicates::{DuplicateCheckerV4Port, ExtractActPortsV4, NotMarkedV4Port, RecorderState};
 
 pub struct HashSetV4Port {
     map:AHashMap<u32, AHashSet<u16>>
@@ -99,3 +99,37 @@ impl ExtractActPortsV4 for HashSetV4Port {
     }
 }
 
+
+impl RecorderState for HashSetV4Port {
+
+    // 每个地址依次为: 地址(4字节), 端口数量(4字节), 各端口(2字节), 均为小端
+    fn dump(&self) -> Vec<u8> {
+        let mut data = vec![];
+        for (ip, ports) in self.map.iter() {
+            data.extend_from_slice(&ip.to_le_bytes());
+            data.extend_from_slice(&(ports.len() as u32).to_le_bytes());
+            for port in ports.iter() {
+                data.extend_from_slice(&port.to_le_bytes());
+            }
+        }
+        data
+    }
+
+    fn restore(&mut self, mut data:&[u8]) -> bool {
+        let mut map = AHashMap::new();
+        while !data.is_empty() {
+            if data.len() < 4 + 4 { return false }
+            let ip = u32::from_le_bytes(data[..4].try_into().unwrap());
+            let num = u32::from_le_bytes(data[4..4 + 4].try_into().unwrap()) as usize;
+            data = &data[4 + 4..];
+
+            if data.len() < num * 2 { return false }
+            let ports:AHashSet<u16> = data[..num * 2].chunks_exact(2).map(|p| u16::from_le_bytes([p[0], p[1]])).collect();
+            data = &data[num * 2..];
+
+            map.insert(ip, ports);
+        }
+        self.map = map;
+        true
+    }
+}
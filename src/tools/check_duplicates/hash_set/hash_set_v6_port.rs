@@ -1,7 +1,7 @@
 
 
 use ahash::{AHashMap, AHashSet};
-use crate::tools::check_duplicates::{DuplicateCheckerV6Port, ExtractActPortsV6, NotMarkedV6Port};
+use crate::tools::check_duplicates::{DuplicateCheckerV6Port, ExtractActPortsV6, NotMarkedV6Port, RecorderState};
 
 pub struct HashSetV6Port {
     map:AHashMap<u128, AHashSet<u16>>
@@ -100,3 +100,37 @@ impl ExtractActPortsV6 for HashSetV6Port {
     }
 }
 
+
+impl RecorderState for HashSetV6Port {
+
+    // 每个地址依次为: 地址(16字节), 端口数量(4字节), 各端口(2字节), 均为小端
+    fn dump(&self) -> Vec<u8> {
+        let mut data = vec![];
+        for (ip, ports) in self.map.iter() {
+            data.extend_from_slice(&ip.to_le_bytes());
+            data.extend_from_slice(&(ports.len() as u32).to_le_bytes());
+            for port in ports.iter() {
+                data.extend_from_slice(&port.to_le_bytes());
+            }
+        }
+        data
+    }
+
+    fn restore(&mut self, mut data:&[u8]) -> bool {
+        let mut map = AHashMap::new();
+        while !data.is_empty() {
+            if data.len() < 16 + 4 { return false }
+            let ip = u128::from_le_bytes(data[..16].try_into().unwrap());
+            let num = u32::from_le_bytes(data[16..16 + 4].try_into().unwrap()) as usize;
+            data = &data[16 + 4..];
+
+            if data.len() < num * 2 { return false }
+            let ports:AHashSet<u16> = data[..num * 2].chunks_exact(2).map(|p| u16::from_le_bytes([p[0], p[1]])).collect();
+            data = &data[num * 2..];
+
+            map.insert(ip, ports);
+        }
+        self.map = map;
+        true
+    }
+}
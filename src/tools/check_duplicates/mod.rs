@@ -10,6 +10,15 @@ pub trait DuplicateCheckerV4 {
     /// 如果没被标记,返回true
     /// 如果被标记或超出目标范围,返回false
     fn not_marked_and_valid(&self, ip:u32) -> bool;
+
+    /// 如果没被标记 且在目标范围内, 进行标记 并返回true, 否则返回false
+    /// 多个接收线程 共用查重器时, 应以原子操作 完成检查与标记
+    fn mark(&mut self, ip:u32) -> bool {
+        if self.not_marked_and_valid(ip) {
+            self.set(ip);
+            true
+        } else { false }
+    }
 }
 
 
@@ -21,6 +30,15 @@ pub trait DuplicateCheckerV6 {
     /// 如果没被标记,返回true
     /// 如果被标记或超出目标范围,返回false
     fn not_marked_and_valid(&self, ip:u128) -> bool;
+
+    /// 如果没被标记 且在目标范围内, 进行标记 并返回true, 否则返回false
+    /// 多个接收线程 共用查重器时, 应以原子操作 完成检查与标记
+    fn mark(&mut self, ip:u128) -> bool {
+        if self.not_marked_and_valid(ip) {
+            self.set(ip);
+            true
+        } else { false }
+    }
 }
 
 
@@ -46,6 +64,18 @@ pub trait DuplicateCheckerV6Port {
 
 
 
+/// 查重器状态的 保存与恢复, 用于断点续扫
+pub trait RecorderState {
+
+    /// 将 标记状态 转为字节序列
+    fn dump(&self) -> Vec<u8>;
+
+    /// 由 字节序列 恢复标记状态, 数据与当前查重器不匹配时 返回 false
+    fn restore(&mut self, data:&[u8]) -> bool;
+}
+
+
+
 pub trait ExtractActPortsV4 {
     fn get_active_ports_u16_string(&self, ip:u32) -> (Vec<u16>, String);
     fn get_active_ports_string(&self, ip:u32) -> (String, usize);
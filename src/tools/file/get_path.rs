@@ -1,21 +1,11 @@
-
-
 use std::{env, fs};
-use std::process::exit;
-use log::error;
-use crate::SYS;
+use crate::core::errors::ConfError;
 
 
 /// 获取当前二进制文件的存储目录, 并拼接子路径
-pub fn get_current_path(child_path:&str) -> String {
-
-    let mut path = match env::current_exe() {
-        Ok(p) => p,
-        Err(_) => {
-            error!("{}", SYS.get_info("err", "get_install_path_failed"));
-            exit(1)
-        }
-    };
+pub fn get_current_path(child_path:&str) -> Result<String, ConfError> {
+
+    let mut path = env::current_exe().map_err(|e| ConfError::io("get_install_path_failed").with(e))?;
 
     // 删除 二进制文件路径
     path.pop();
@@ -31,17 +21,11 @@ pub fn get_current_path(child_path:&str) -> String {
     let path = path.join(filepath);
 
     if let Some(parent) = path.parent() {
-        fs::create_dir_all(parent).map_err(|_| {
-            error!("{} {:?}", SYS.get_info("err", "create_cur_parent_path_failed"), &path);
-            exit(1)
-        }).unwrap();
+        fs::create_dir_all(parent).map_err(|_| ConfError::io("create_cur_parent_path_failed").with(parent.display()))?;
     }
 
     match path.to_str() {
-        None => {
-            error!("{}", SYS.get_info("err", "get_tar_path_failed"));
-            exit(1)
-        }
-        Some(p) => { p.to_string() }
+        None => Err(ConfError::io("get_tar_path_failed").with(path.display())),
+        Some(p) => Ok(p.to_string())
     }
-}
\ No newline at end of file
+}
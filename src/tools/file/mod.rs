@@ -1,3 +1,4 @@
 pub mod parse_context;
 pub mod write_to_file;
-pub mod get_path;
\ No newline at end of file
+pub mod get_path;
+pub mod pcapng;
\ No newline at end of file
@@ -0,0 +1,116 @@
+//! pcapng 文件写入器
+//! 注意: pcap库 的 Savefile 只能写入传统pcap格式, 且不支持注释, 因此这里直接按 pcapng 块格式写入
+
+use std::fs::File;
+use std::io;
+use std::io::{BufWriter, Write};
+
+
+const SHB_TYPE:u32 = 0x0A0D_0D0A;
+const IDB_TYPE:u32 = 0x0000_0001;
+const EPB_TYPE:u32 = 0x0000_0006;
+const BYTE_ORDER_MAGIC:u32 = 0x1A2B_3C4D;
+
+const OPT_END_OF_OPT:u16 = 0;
+const OPT_COMMENT:u16 = 1;
+const SHB_USER_APPL:u16 = 4;
+
+
+pub struct PcapngWriter {
+    writer:BufWriter<File>,
+
+    // 已写入的接口描述: (链路层类型, 捕获长度), 下标即为接口编号
+    interfaces:Vec<(u16, u32)>,
+}
+
+
+impl PcapngWriter {
+
+    /// 创建 pcapng 文件, 并写入携带 注释 的 节头部块
+    pub fn new(path:&str, comment:&str) -> io::Result<Self> {
+
+        let mut pcapng = Self {
+            writer: BufWriter::new(File::create(path)?),
+            interfaces: vec![],
+        };
+
+        let mut body:Vec<u8> = Vec::with_capacity(64 + comment.len());
+        body.extend_from_slice(&BYTE_ORDER_MAGIC.to_le_bytes());
+        // 版本号 1.0
+        body.extend_from_slice(&1u16.to_le_bytes());
+        body.extend_from_slice(&0u16.to_le_bytes());
+        // 节长度 未指定
+        body.extend_from_slice(&(-1i64).to_le_bytes());
+
+        Self::push_option(&mut body, OPT_COMMENT, comment.as_bytes());
+        Self::push_option(&mut body, SHB_USER_APPL, concat!("smap ", env!("CARGO_PKG_VERSION")).as_bytes());
+        Self::push_option(&mut body, OPT_END_OF_OPT, &[]);
+
+        pcapng.write_block(SHB_TYPE, &body)?;
+        Ok(pcapng)
+    }
+
+    /// 获取 接口编号, 链路层类型 或 捕获长度 不同的接口将写入新的 接口描述块
+    pub fn interface_id(&mut self, link_type:u16, snap_len:u32) -> io::Result<u32> {
+
+        if let Some(id) = self.interfaces.iter().position(|i| *i == (link_type, snap_len)) {
+            return Ok(id as u32)
+        }
+
+        let mut body:Vec<u8> = Vec::with_capacity(8);
+        body.extend_from_slice(&link_type.to_le_bytes());
+        // 保留字段
+        body.extend_from_slice(&0u16.to_le_bytes());
+        body.extend_from_slice(&snap_len.to_le_bytes());
+
+        self.write_block(IDB_TYPE, &body)?;
+        self.interfaces.push((link_type, snap_len));
+        Ok((self.interfaces.len() - 1) as u32)
+    }
+
+    /// 写入 增强分组块, 时间戳以微秒为单位(默认时间精度)
+    pub fn write_packet(&mut self, interface_id:u32, ts_sec:i64, ts_usec:i64, data:&[u8], orig_len:u32) -> io::Result<()> {
+
+        let ts = (ts_sec as u64).wrapping_mul(1_000_000).wrapping_add(ts_usec as u64);
+
+        let mut body:Vec<u8> = Vec::with_capacity(20 + data.len() + 3);
+        body.extend_from_slice(&interface_id.to_le_bytes());
+        body.extend_from_slice(&((ts >> 32) as u32).to_le_bytes());
+        body.extend_from_slice(&(ts as u32).to_le_bytes());
+        body.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        body.extend_from_slice(&orig_len.to_le_bytes());
+        body.extend_from_slice(data);
+        Self::pad(&mut body);
+
+        self.write_block(EPB_TYPE, &body)
+    }
+
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+
+    fn write_block(&mut self, block_type:u32, body:&[u8]) -> io::Result<()> {
+
+        // 块总长度 = 块类型 + 两个长度字段 + 块内容
+        let total_len = (body.len() + 12) as u32;
+
+        self.writer.write_all(&block_type.to_le_bytes())?;
+        self.writer.write_all(&total_len.to_le_bytes())?;
+        self.writer.write_all(body)?;
+        self.writer.write_all(&total_len.to_le_bytes())
+    }
+
+    fn push_option(body:&mut Vec<u8>, code:u16, val:&[u8]) {
+        body.extend_from_slice(&code.to_le_bytes());
+        body.extend_from_slice(&(val.len() as u16).to_le_bytes());
+        body.extend_from_slice(val);
+        Self::pad(body);
+    }
+
+    /// 按 4字节 对齐
+    fn pad(body:&mut Vec<u8>) {
+        while body.len() % 4 != 0 {
+            body.push(0);
+        }
+    }
+}
@@ -2,16 +2,18 @@ use std::fs;
 use std::fs::OpenOptions;
 use std::io::{Write, BufRead, BufReader, Seek, SeekFrom};
 use std::path::PathBuf;
-use std::process::exit;
-use log::{error, warn};
+use log::warn;
+use crate::core::conf::profile;
+use crate::core::errors::OutputError;
 use crate::SYS;
 use crate::tools::file::get_path::get_current_path;
 
-pub fn write_record(mode:&str, target:&str, filepath: &String, header: Vec<&str>, record: Vec<String>){
+pub fn write_record(mode:&str, target:&str, filepath: &String, header: Vec<&str>, record: Vec<String>) -> Result<(), OutputError> {
 
-    let filepath = get_current_path(&format!("{}_{}_{}.csv", filepath, mode, target));
+    let filepath = get_current_path(&format!("{}_{}_{}.csv", filepath, mode, target))?;
 
     let filepath = PathBuf::from(filepath);
+    let write_err = |_| OutputError::io("write_record_err").with(filepath.display());
 
     match OpenOptions::new().read(true).write(true).open(&filepath) {
 
@@ -20,49 +22,33 @@ pub fn write_record(mode:&str, target:&str, filepath: &String, header: Vec<&str>
             // 读取第一行
             let mut reader = BufReader::new(&file);
             let mut first_line = String::new();
-            reader.read_line(&mut first_line).map_err(|_| {
-                error!("{}", SYS.get_info("err", "write_record_err"));
-                exit(1)
-            }).unwrap();
+            reader.read_line(&mut first_line).map_err(write_err)?;
 
             if first_line.trim() == header.join(",") {
 
                 // 如果字段一致, 追加写入
 
                 // 将文件指针移动到最后
-                file.seek(SeekFrom::End(0)).map_err(|_|{
-                    error!("{}", SYS.get_info("err", "write_record_err"));
-                    exit(1)
-                }).unwrap();
+                file.seek(SeekFrom::End(0)).map_err(write_err)?;
 
-                writeln!(file, "{}", record.join(",")).map_err(|_| {
-                    error!("{}", SYS.get_info("err", "write_record_err"));
-                    exit(1)
-                }).unwrap();
+                writeln!(file, "{}", record.join(",")).map_err(write_err)?;
 
-                return
+                return Ok(())
             } else {
                 // 如果字段不一致
 
                 warn!("{}", SYS.get_info("warn","record_file_header_not_match"));
 
                 let mut record_option = String::new();
-                std::io::stdin().read_line(&mut record_option).map_err(
-                    | _ | {
-                        error!("{}", SYS.get_info("err","input_record_option_err"));
-                        exit(1)
-                    }
-                ).unwrap();
+                std::io::stdin().read_line(&mut record_option)
+                    .map_err(|_| OutputError::io("input_record_option_err"))?;
 
                 match record_option.trim() {
                     "yes" => {}
-                    _ => exit(1)
+                    _ => return Err(OutputError::invalid("record_file_not_replaced").with(filepath.display()))
                 }
 
-                fs::remove_file(&filepath).map_err(|_| {
-                    error!("{}", SYS.get_info("err", "del_record_file_err"));
-                    exit(1)
-                }).unwrap();
+                fs::remove_file(&filepath).map_err(|_| OutputError::io("del_record_file_err").with(filepath.display()))?;
             }
         }
 
@@ -74,21 +60,49 @@ pub fn write_record(mode:&str, target:&str, filepath: &String, header: Vec<&str>
         .write(true)
         .create(true)
         .open(&filepath)
-        .map_err(|_| {
-            error!("{} {:?}", SYS.get_info("err", "create_record_file_err"), &filepath);
-            exit(1)
-        }).unwrap();
-
-    writeln!(file, "{}", header.join(",")).map_err(|_| {
-        error!("{}", SYS.get_info("err", "write_record_err"));
-        exit(1)
-    }).unwrap();
-    writeln!(file, "{}", record.join(",")).map_err(|_| {
-        error!("{}", SYS.get_info("err", "write_record_err"));
-        exit(1)
-    }).unwrap();
+        .map_err(|_| OutputError::io("create_record_file_err").with(filepath.display()))?;
 
+    writeln!(file, "{}", header.join(",")).map_err(write_err)?;
+    writeln!(file, "{}", record.join(",")).map_err(write_err)?;
+    Ok(())
 }
 
 
+/// 记录 生效的系统配置(内置配置 叠加 运行时配置文件 和 单项覆盖 后的结果)
+pub fn write_sys_conf_record(mode:&str, filepath: &String) -> Result<(), OutputError> {
 
+    let (config_file, overrides) = SYS.get_runtime_layers();
+    let conf = SYS.get_effective_conf();
+
+    let mut header = vec!["config_file", "set"];
+    header.extend(conf.iter().map(|(k, _)| k.as_str()));
+
+    let mut record = vec![config_file.replace(",", " "), overrides.replace(",", " ")];
+    record.extend(conf.iter().map(|(_, v)| v.replace(",", " ")));
+
+    write_record(mode, "sys_conf", filepath, header, record)
+}
+
+
+/// 记录 生效的自定义参数(用户输入 或 默认值), 字段为 模式 和 探测模块 声明的全部参数
+pub fn write_mod_args_record(mode:&str, filepath: &String, mod_args:&[(String, String)]) -> Result<(), OutputError> {
+
+    if mod_args.is_empty() { return Ok(()) }
+    let header = mod_args.iter().map(|(k, _)| k.as_str()).collect();
+    let record = mod_args.iter().map(|(_, v)| v.replace(",", " ")).collect();
+
+    write_record(mode, "mod_args", filepath, header, record)
+}
+
+
+/// 记录 解析后的扫描任务配置(toml), 每次运行一个文件, 使用 --profile 指定该文件即可复现本次扫描
+pub fn write_profile_record(mode:&str, filepath: &String, seed:u64) -> Result<(), OutputError> {
+
+    let resolved = match profile::get_resolved(seed) {
+        Some(r) => r,
+        None => return Ok(()),
+    };
+
+    let filepath = get_current_path(&format!("{}_{}_profile_{}.toml", filepath, mode, chrono::Local::now().format("%Y%m%d_%H%M%S")))?;
+    fs::write(&filepath, resolved).map_err(|_| OutputError::io("create_record_file_err").with(&filepath))
+}
@@ -1,7 +1,5 @@
-use std::process::exit;
 use netdev::{get_default_interface, get_interfaces, Interface};
-use log::error;
-use crate::SYS;
+use crate::core::errors::InterfaceError;
 
 /// 定义网络接口
 pub struct NetInterface {
@@ -14,7 +12,7 @@ impl NetInterface {
     /// 构造网络接口配置
     /// 如果指定了接口名称, 就返回对应接口的信息, 如果未查询到指定接口会报错
     /// 如果未指定接口(None), 就返回默认接口
-    pub fn new(name: Option<String>) -> Self {
+    pub fn new(name: Option<String>) -> Result<Self, InterfaceError> {
 
         if let Some(interface_name) = name {
             // 指定接口
@@ -30,35 +28,31 @@ impl NetInterface {
                         tar_interface.name = format!("{}{}","\\Device\\NPF_", interface_name);
                     }
 
-                    return NetInterface {
+                    return Ok(NetInterface {
                         interface:tar_interface,
-                    };
+                    });
 
                 }
 
             }
 
             // 查询了全部的接口信息, 但是没有找到对应接口
-            error!("{} {}", SYS.get_info("err","interface_not_found"), interface_name);
-            exit(1)
+            Err(InterfaceError::not_found("interface_not_found").with(interface_name))
 
         } else { // 未指定接口
 
-            let mut tar_interface = get_default_interface().map_err(|_|{
-
-                // 无法获取默认接口
-                error!("{}", SYS.get_info("err", "default_interface_not_found"));
-                exit(1)
-            }).unwrap();
+            // 无法获取默认接口
+            let mut tar_interface = get_default_interface()
+                .map_err(|_| InterfaceError::not_found("default_interface_not_found"))?;
 
             if cfg!(target_os = "windows") {
                 tar_interface.name = format!("{}{}","\\Device\\NPF_", tar_interface.name);
             }
 
-            NetInterface {
+            Ok(NetInterface {
                 // 使用默认接口
                 interface: tar_interface,
-            }
+            })
 
         }
     }
@@ -1,7 +1,5 @@
 use std::net::Ipv6Addr;
-use std::process::exit;
-use log::error;
-use crate::SYS;
+use crate::core::errors::ConfError;
 use crate::tools::others::parse::parse_str;
 
 pub struct Netv6{
@@ -14,20 +12,19 @@ impl Netv6 {
 
     /// 构造一个带掩码的 v6 网络地址
     /// 网络地址会根据输入的 ip 和 掩码 自动计算
-    pub fn new(ip:Ipv6Addr, mask:u8) -> Self {
+    pub fn new(ip:Ipv6Addr, mask:u8) -> Result<Self, ConfError> {
 
         // 如果超出 32 直接报错
         if mask > 128 {
-            error!("{} {} {}", SYS.get_info("err", "ipv6_net_invalid"), ip, mask);
-            exit(1)
+            return Err(ConfError::invalid("ipv6_net_invalid").with(format!("{} {}", ip, mask)))
         }
 
         // 如果 mask 为 0, 直接返回 0 ip
         if mask == 0 {
-            return Netv6 {
+            return Ok(Netv6 {
                 ip:Ipv6Addr::from(0),
                 mask
-            }
+            })
         }
 
         let ip_val = u128::from(ip);
@@ -35,15 +32,15 @@ impl Netv6 {
 
         let net_val = ( ip_val >> move_bits ) << move_bits;
 
-        Netv6 {
+        Ok(Netv6 {
             ip:Ipv6Addr::from(net_val),
             mask
-        }
+        })
     }
 
 
     /// 从 str 生成 v6 网络地址
-    pub fn from_str(raw_net_str:&str) -> Self {
+    pub fn from_str(raw_net_str:&str) -> Result<Self, ConfError> {
 
         let mut net_mask = raw_net_str.trim().split("/");
 
@@ -52,24 +49,21 @@ impl Netv6 {
         if let Some(n) = net_mask.next() {
             net_str = n;
         } else {
-            error!("{} {}", SYS.get_info("err","parse_ipv6_net_failed"), raw_net_str);
-            exit(1)
+            return Err(ConfError::invalid("parse_ipv6_net_failed").with(raw_net_str))
         }
 
         if let Some(m) = net_mask.next() {
             mask_str = m;
         } else {
-            error!("{} {}", SYS.get_info("err","parse_ipv6_net_failed"), raw_net_str);
-            exit(1)
+            return Err(ConfError::invalid("parse_ipv6_net_failed").with(raw_net_str))
         }
 
         if let Some(_) = net_mask.next() {      // 检查合法性
-            error!("{} {}", SYS.get_info("err","parse_ipv6_net_failed"), raw_net_str);
-            exit(1)
+            return Err(ConfError::invalid("parse_ipv6_net_failed").with(raw_net_str))
         }
 
-        let ip:Ipv6Addr = parse_str(net_str);
-        let mask:u8 = parse_str(mask_str);
+        let ip:Ipv6Addr = parse_str(net_str)?;
+        let mask:u8 = parse_str(mask_str)?;
 
         Netv6::new(ip, mask)
 
@@ -1,35 +1,27 @@
-use std::process::exit;
 use std::str::FromStr;
-use log::error;
-use crate::SYS;
+use crate::core::errors::ConfError;
 
 /// 把 string 解析成 指定类型
 #[allow(dead_code)]
-pub fn parse_string<T : FromStr>(val:&String) -> T {
+pub fn parse_string<T : FromStr>(val:&String) -> Result<T, ConfError> {
 
     let res = val.parse::<T>();
 
     match res {
-        Ok(r) => r,
-        Err(_) => {
-            error!("{} {}",SYS.get_info("err", "parse_str_failed"), val);
-            exit(1)
-        }
+        Ok(r) => Ok(r),
+        Err(_) => Err(ConfError::invalid("parse_str_failed").with(val)),
     }
 
 }
 
 /// 将 &str 解析成 指定类型
-pub fn parse_str<T : FromStr>(val:&str) -> T {
+pub fn parse_str<T : FromStr>(val:&str) -> Result<T, ConfError> {
 
     let res = val.parse::<T>();
 
     match res {
-        Ok(r) => r,
-        Err(_) => {
-            error!("{} {}",SYS.get_info("err", "parse_str_failed"), val);
-            exit(1)
-        }
+        Ok(r) => Ok(r),
+        Err(_) => Err(ConfError::invalid("parse_str_failed").with(val)),
     }
 
 }
@@ -1,13 +1,11 @@
 use std::fs::File;
 use std::io::{BufRead, BufReader};
 use std::net::Ipv6Addr;
-use std::process::exit;
 use ahash::{AHashMap, AHashSet};
-use log::error;
 use crate::core::conf::tools::args_parse::u8::parse_u8_set;
-use crate::SYS;
 use crate::tools::file::parse_context::parse_line_with_annotation;
 use crate::tools::others::sort::quick_sort_from_small_to_big;
+use crate::core::errors::TargetError;
 
 #[allow(dead_code)]
 pub struct Ipv6PrefixMatcher {
@@ -36,7 +34,7 @@ impl Ipv6PrefixMatcher {
 
 
     #[allow(dead_code)]
-    pub fn new(path: String, default_granularity:u8, prefix_len_limit_str:&str) -> Self {
+    pub fn new(path: String, default_granularity:u8, prefix_len_limit_str:&str) -> Result<Self, TargetError> {
 
         let mut matcher = Self {
             right_move_len: vec![],
@@ -46,27 +44,22 @@ impl Ipv6PrefixMatcher {
         };
 
         // 解析 限制前缀数组
-        let prefix_len_limit = parse_u8_set(prefix_len_limit_str);
+        let prefix_len_limit = parse_u8_set(prefix_len_limit_str)?;
 
         // 从文件中加载
-        matcher.from(path, &prefix_len_limit);
+        matcher.from(path, &prefix_len_limit)?;
 
         // 按照网络前缀从大到小排序, 优化查找次序
         // 如 前缀 /24 /8 /16 的次序调整为 /24 /16 /8
         matcher.sort();
 
-        matcher
+        Ok(matcher)
     }
 
 
-    fn from(&mut self, list_path:String, prefix_len_limit:&AHashSet<u8>) {
+    fn from(&mut self, list_path:String, prefix_len_limit:&AHashSet<u8>) -> Result<(), TargetError> {
 
-        let list_file = File::open(list_path.clone()).map_err(
-            |_| {
-                error!("{} {}", SYS.get_info("err", "open_targets_file_failed"), list_path);
-                exit(1)
-            }
-        ).unwrap();
+        let list_file = File::open(list_path.clone()).map_err(|_| TargetError::io("open_targets_file_failed").with(&list_path))?;
 
         let lines = BufReader::new(list_file).lines();
 
@@ -80,20 +73,21 @@ impl Ipv6PrefixMatcher {
                     // 清除注释和无效行
                     match parse_line_with_annotation(l) {
                         Some(net_str) => {
-                            self.parse_line_str(net_str, prefix_len_limit)
+                            self.parse_line_str(net_str, prefix_len_limit)?
                         },
                         None => {}
                     }
                 }
                 Err(_) => {
-                    error!("{} {}", SYS.get_info("err", "read_target_line_failed"), list_path);
-                    exit(1)
+                    return Err(TargetError::io("read_target_line_failed").with(&list_path))
                 }
             }
         }
+
+        Ok(())
     }
 
-    fn parse_line_str(&mut self, raw_net_str:String, prefix_len_limit:&AHashSet<u8>) {
+    fn parse_line_str(&mut self, raw_net_str:String, prefix_len_limit:&AHashSet<u8>) -> Result<(), TargetError> {
         let mut net_mask_asn = raw_net_str.split("/");
 
         let net_str;
@@ -102,8 +96,7 @@ impl Ipv6PrefixMatcher {
         if let Some(n) = net_mask_asn.next() {
             net_str = n;
         } else {
-            error!("{} {}", SYS.get_info("err","parse_line_err"), raw_net_str);
-            exit(1)
+            return Err(TargetError::invalid("parse_line_err").with(&raw_net_str))
         }
 
         if let Some(m_a) = net_mask_asn.next() {
@@ -113,42 +106,32 @@ impl Ipv6PrefixMatcher {
             if let Some(m) = net_mask_asn.next() {
                 mask_str = m;
             } else {
-                error!("{} {}", SYS.get_info("err","parse_line_err"), raw_net_str);
-                exit(1)
+                return Err(TargetError::invalid("parse_line_err").with(&raw_net_str))
             }
 
             if let Some(a) = net_mask_asn.next() {
                 asn_str = a;
-            } else { error!("{} {}", SYS.get_info("err","parse_line_err"), raw_net_str);
-                exit(1)
+            } else { return Err(TargetError::invalid("parse_line_err").with(&raw_net_str))
             }
 
-        } else { error!("{} {}", SYS.get_info("err","parse_line_err"), raw_net_str);
-            exit(1)
+        } else { return Err(TargetError::invalid("parse_line_err").with(&raw_net_str))
         }
 
 
-        let mask:u8 = mask_str.parse().map_err(|_|{
-            error!("{} {}", SYS.get_info("err","parse_line_err"), raw_net_str);
-            exit(1)
-        }).unwrap();
+        let mask:u8 = mask_str.parse().map_err(|_| TargetError::invalid("parse_line_err").with(&raw_net_str))?;
 
         // 检查 掩码是否符合要求
         // 只有 存在于限制数组中的前缀长度才是有效的
-        if !prefix_len_limit.contains(&mask) { return }
+        if !prefix_len_limit.contains(&mask) { return Ok(()) }
 
-        let ip:Ipv6Addr = net_str.parse().map_err(|_|{
-            error!("{} {}", SYS.get_info("err","parse_line_err"), raw_net_str);
-            exit(1)
-        }).unwrap();
+        let ip:Ipv6Addr = net_str.parse().map_err(|_| TargetError::invalid("parse_line_err").with(&raw_net_str))?;
 
-        let asn:u16 = asn_str.parse().map_err(|_|{
-            error!("{} {}", SYS.get_info("err","parse_line_err"), raw_net_str);
-            exit(1)
-        }).unwrap();
+        let asn:u16 = asn_str.parse().map_err(|_| TargetError::invalid("parse_line_err").with(&raw_net_str))?;
 
         // 添加网段
         self.add_net_from_ip_mask(ip, mask, asn);
+
+        Ok(())
     }
 
     fn add_net_from_ip_mask(&mut self, ip:Ipv6Addr, mask:u8, asn:u16){
@@ -0,0 +1,72 @@
+//! 虚拟网络 集成测试
+//! 使用 --virtual_net 运行 c4 模式, 探测帧不发送到网络接口, 由进程内的虚拟响应器按拓扑文件生成响应,
+//! 并将 输出文件 与 预期结果 比较. 本机地址 会影响 源端口 和 序列号 等字段, 因此只比较与本机无关的字段
+
+use std::path::PathBuf;
+use std::process::Command;
+
+
+const FIXTURES:&str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures");
+
+/// 黑白名单 均使用空文件: 测试目标 位于私有地址段, 持续集成环境的本机地址 也可能位于保留地址段
+const BLOCK_LISTS:[&str; 8] = [
+    "destination_black_list_v4", "destination_white_list_v4", "source_black_list_v4", "source_white_list_v4",
+    "destination_black_list_v6", "destination_white_list_v6", "source_black_list_v6", "source_white_list_v6",
+];
+
+
+fn fixture(name:&str) -> String {
+    format!("{}/{}", FIXTURES, name)
+}
+
+/// 运行 c4 模式, 返回 输出文件的 首行 和 排序后的记录(响应到达顺序 不固定)
+fn run_c4(name:&str, extra_args:&[&str]) -> (String, Vec<String>) {
+
+    let output_file:PathBuf = std::env::temp_dir().join(format!("smap_virtual_net_{}_{}.csv", name, std::process::id()));
+
+    let mut cmd = Command::new(env!("CARGO_BIN_EXE_smap"));
+    cmd.args(["-m", "c4", "-t", "10.0.0.0/29", "--probe_v4", "tcp_syn_scan_v4",
+        "--virtual_net", &fixture("virtual_net_v4.txt"), "--seed", "7",
+        "--send_rate", "1000", "--cool_seconds", "1", "--allow_no_succ", "--no_allow_summary", "-q",
+        "--output_file_v4", output_file.to_str().unwrap()]);
+    for key in BLOCK_LISTS {
+        cmd.args(["--set", &format!("conf.{}={}", key, fixture("empty_block_list.txt"))]);
+    }
+    cmd.args(extra_args);
+
+    let res = cmd.output().unwrap();
+    assert!(res.status.success(), "{}", String::from_utf8_lossy(&res.stderr));
+
+    let output = std::fs::read_to_string(&output_file).unwrap();
+    let _ = std::fs::remove_file(&output_file);
+    split_rows(&output)
+}
+
+fn split_rows(text:&str) -> (String, Vec<String>) {
+    let mut lines = text.lines().map(|l| l.to_string());
+    let header = lines.next().unwrap_or_default();
+    let mut rows:Vec<String> = lines.collect();
+    rows.sort();
+    (header, rows)
+}
+
+fn expected(name:&str) -> (String, Vec<String>) {
+    split_rows(&std::fs::read_to_string(fixture(name)).unwrap())
+}
+
+
+/// 开放端口 回复 SYN-ACK, 其余端口 回复 RST, 不存在的主机 没有响应
+#[test]
+fn c4_tcp_syn_scan() {
+    let res = run_c4("tcp_syn", &["-p", "22,80,443",
+        "--fields", "source_addr", "--fields", "sport", "--fields", "window_size", "--fields", "classification"]);
+    assert_eq!(res, expected("virtual_net_c4_tcp_syn.csv"));
+}
+
+/// ttl 不足时 由路由上的路由器 回复超时消息
+#[test]
+fn c4_ttl_exceeded() {
+    let res = run_c4("ttl", &["-p", "80", "--ttl", "2",
+        "--fields", "source_addr", "--fields", "icmp_type", "--fields", "classification"]);
+    assert_eq!(res, expected("virtual_net_c4_ttl.csv"));
+}